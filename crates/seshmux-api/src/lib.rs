@@ -0,0 +1,39 @@
+//! The supported way to use seshmux from other programs, without the TUI or CLI. Every type
+//! re-exported here is `#[non_exhaustive]`, so build values with their `new` constructors and set
+//! the remaining fields afterwards, and give matches on its enums a wildcard arm. The registry is
+//! read-only from here; worktrees are registered through [`App`].
+//!
+//! ```no_run
+//! let runner = seshmux_api::SystemCommandRunner::new();
+//! let app = seshmux_api::App::new(&runner);
+//! let worktrees = app.list(std::path::Path::new("."))?;
+//! # Ok::<(), seshmux_api::Error>(())
+//! ```
+
+pub use seshmux_app::App;
+pub use seshmux_core::command_runner::{
+    CommandOutput, CommandRunner, CommandTimedOut, RawCommandOutput, RunPolicy, SystemCommandRunner,
+};
+
+pub use seshmux_app::{
+    AttachError, AttachRequest, AttachResult, DeleteError, DeleteRequest, DeleteResult, ListResult,
+    NewError, NewPhase, NewProgress, NewRequest, NewResult, NewSpec, NewStartPoint, WorktreeHead,
+    WorktreeOrder, WorktreeRow, WorktreeSortKey,
+};
+
+pub use seshmux_app::{Error, ErrorKind, RepoError, classify_error};
+
+pub mod registry {
+    pub use seshmux_core::registry::{
+        DiskUsage, RegistryEntry, RegistryError, find_entry_by_name, load_registry, registry_path,
+    };
+}
+
+pub mod config {
+    pub use seshmux_core::config::{
+        ConfigError, ConfigProblem, DEFAULT_WORKTREES_DIR, LoadOptions, REPO_CONFIG_FILE_NAME,
+        RepoConfig, SeshmuxConfig, SessionLayout, TmuxConfig, WindowSpec, config_problems,
+        load_config, load_repo_config, repo_config_path, resolve_config_path,
+        resolve_worktrees_dir, validate_config,
+    };
+}
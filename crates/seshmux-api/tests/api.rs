@@ -0,0 +1,84 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+use seshmux_api::registry::{load_registry, registry_path};
+use seshmux_api::{App, CommandOutput, CommandRunner, WorktreeHead};
+
+struct FakeRunner {
+    repo_root: String,
+    calls: Mutex<Vec<String>>,
+}
+
+impl CommandRunner for FakeRunner {
+    fn run(
+        &self,
+        program: &str,
+        args: &[&str],
+        _cwd: Option<&Path>,
+    ) -> anyhow::Result<CommandOutput> {
+        self.calls
+            .lock()
+            .expect("calls lock")
+            .push(format!("{program} {}", args.join(" ")));
+        let (stdout, status_code) = match program {
+            "git" if args.contains(&"--show-toplevel") => (format!("{}\n", self.repo_root), 0),
+            "git" => ("feature\n".to_string(), 0),
+            _ => (String::new(), 1),
+        };
+        Ok(CommandOutput::new(status_code, stdout, ""))
+    }
+
+    fn run_interactive(
+        &self,
+        _program: &str,
+        _args: &[&str],
+        _cwd: Option<&Path>,
+    ) -> anyhow::Result<i32> {
+        Ok(0)
+    }
+}
+
+#[test]
+fn external_callers_can_list_worktrees_through_the_facade() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+    let repo_root = temp.path().join("repo");
+    let worktrees_dir = repo_root.join(seshmux_api::config::DEFAULT_WORKTREES_DIR);
+    fs::create_dir_all(worktrees_dir.join("feature")).expect("worktree dir");
+    fs::write(
+        registry_path(&worktrees_dir),
+        format!(
+            "version = 1\n\n[settings.extras]\n\n[[worktree]]\nname = \"feature\"\npath = \"{}\"\ncreated_at = \"2026-02-25T10:00:00Z\"\n",
+            worktrees_dir.join("feature").display()
+        ),
+    )
+    .expect("registry");
+    let entries = load_registry(&worktrees_dir).expect("load");
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].name, "feature");
+
+    let runner = FakeRunner {
+        repo_root: repo_root.display().to_string(),
+        calls: Mutex::new(Vec::new()),
+    };
+    let app = App::new(&runner);
+    let result = app.list(&repo_root).expect("list");
+
+    assert_eq!(result.rows.len(), 1);
+    assert_eq!(
+        result.rows[0].head,
+        WorktreeHead::Branch("feature".to_string())
+    );
+    assert!(!result.rows[0].session_running);
+    assert!(
+        runner
+            .calls
+            .lock()
+            .expect("calls lock")
+            .iter()
+            .any(|call| call.starts_with("tmux has-session"))
+    );
+}
@@ -0,0 +1,231 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+use seshmux_core::config::SessionLayout;
+
+use crate::catalog::WorktreeCatalog;
+use crate::runtime;
+use crate::{App, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdoptCandidate {
+    pub worktree_name: String,
+    pub worktree_path: PathBuf,
+    pub session_name: String,
+    pub rename_to: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdoptScan {
+    pub repo_root: PathBuf,
+    pub candidates: Vec<AdoptCandidate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdoptRequest {
+    pub cwd: PathBuf,
+    pub worktree_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdoptResult {
+    pub repo_root: PathBuf,
+    pub adopted: Vec<AdoptCandidate>,
+}
+
+impl<'a> App<'a> {
+    pub fn adopt_scan(&self, cwd: &Path) -> Result<AdoptScan, Error> {
+        runtime::require_tmux_backend(self, "seshmux adopt")?;
+        let catalog = WorktreeCatalog::load(self, cwd)?;
+        let repo_root = catalog.repo_root().to_path_buf();
+
+        let registered_names = registered_names(&catalog);
+        let worktree_paths = unregistered_worktree_paths(self, &catalog)?;
+
+        let sessions =
+            seshmux_core::tmux::list_sessions_with_paths(&runtime::tmux_server(self)?, self.runner)
+                .context("failed to list tmux sessions")?;
+        let session_names: BTreeSet<&str> = sessions
+            .iter()
+            .map(|session| session.name.as_str())
+            .collect();
+        let prefix = seshmux_core::tmux::session_name_prefix(runtime::repo_component(&repo_root));
+
+        let mut candidates = Vec::<AdoptCandidate>::new();
+        for session in &sessions {
+            let session_path = runtime::comparable_path(&session.path);
+            let candidate = match session.name.strip_prefix(&prefix) {
+                Some(worktree_name) => {
+                    let by_name =
+                        runtime::comparable_path(&catalog.worktrees_dir().join(worktree_name));
+                    [session_path, by_name]
+                        .into_iter()
+                        .find(|path| worktree_paths.contains(path))
+                        .map(|worktree_path| AdoptCandidate {
+                            worktree_name: worktree_name.to_string(),
+                            worktree_path,
+                            session_name: session.name.clone(),
+                            rename_to: None,
+                        })
+                }
+                None if worktree_paths.contains(&session_path) => session_path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .map(|worktree_name| AdoptCandidate {
+                        worktree_name: worktree_name.to_string(),
+                        rename_to: Some(runtime::session_name_for(&repo_root, worktree_name)),
+                        worktree_path: session_path.clone(),
+                        session_name: session.name.clone(),
+                    }),
+                None => None,
+            };
+
+            let Some(candidate) = candidate else {
+                continue;
+            };
+            let name_taken = registered_names.contains(candidate.worktree_name.as_str())
+                || candidates
+                    .iter()
+                    .any(|other| other.worktree_name == candidate.worktree_name);
+            let rename_blocked = candidate
+                .rename_to
+                .as_deref()
+                .is_some_and(|target| session_names.contains(target));
+            if name_taken
+                || rename_blocked
+                || seshmux_core::names::validate_worktree_name(&candidate.worktree_name).is_err()
+            {
+                continue;
+            }
+            candidates.push(candidate);
+        }
+
+        Ok(AdoptScan {
+            repo_root,
+            candidates,
+        })
+    }
+
+    pub fn adopt(&self, request: AdoptRequest) -> Result<AdoptResult, Error> {
+        let scan = self.adopt_scan(&request.cwd)?;
+        let worktrees_dir = runtime::worktrees_dir(self, &scan.repo_root)?;
+
+        let mut selected = Vec::new();
+        for name in &request.worktree_names {
+            let candidate = scan
+                .candidates
+                .iter()
+                .find(|candidate| &candidate.worktree_name == name)
+                .ok_or_else(|| anyhow!("no orphan tmux session found for worktree '{name}'"))?;
+            if !selected.contains(candidate) {
+                selected.push(candidate.clone());
+            }
+        }
+        if selected.is_empty() {
+            return Err(anyhow!("no worktrees selected to adopt").into());
+        }
+
+        for candidate in &selected {
+            let created_at = seshmux_core::time::now_utc_rfc3339()
+                .map_err(|error| anyhow!("failed to format timestamp: {error}"))?;
+            register_found_worktree(
+                self,
+                &worktrees_dir,
+                FoundWorktree {
+                    worktree_name: &candidate.worktree_name,
+                    worktree_path: &candidate.worktree_path,
+                    created_at,
+                    session_name: Some(&candidate.session_name),
+                    rename_to: candidate.rename_to.as_deref(),
+                },
+            )?;
+        }
+
+        Ok(AdoptResult {
+            repo_root: scan.repo_root,
+            adopted: selected,
+        })
+    }
+}
+
+pub(crate) struct FoundWorktree<'a> {
+    pub(crate) worktree_name: &'a str,
+    pub(crate) worktree_path: &'a Path,
+    pub(crate) created_at: String,
+    pub(crate) session_name: Option<&'a str>,
+    pub(crate) rename_to: Option<&'a str>,
+}
+
+pub(crate) fn registered_names(catalog: &WorktreeCatalog) -> BTreeSet<&str> {
+    catalog
+        .entries()
+        .iter()
+        .map(|entry| entry.name.as_str())
+        .collect()
+}
+
+// Trashed worktrees are still git worktrees, but `seshmux restore` owns them.
+pub(crate) fn unregistered_worktree_paths(
+    app: &App<'_>,
+    catalog: &WorktreeCatalog,
+) -> Result<Vec<PathBuf>> {
+    let repo_root = catalog.repo_root();
+    let registered_paths: BTreeSet<PathBuf> = catalog
+        .entries()
+        .iter()
+        .map(|entry| runtime::comparable_path(Path::new(&entry.path)))
+        .collect();
+    let repo_key = runtime::comparable_path(repo_root);
+    let trash_dir =
+        seshmux_core::trash::trash_dir(&runtime::comparable_path(catalog.worktrees_dir()));
+
+    Ok(
+        seshmux_core::git::list_worktree_paths(repo_root, app.runner)
+            .with_context(|| format!("failed to list git worktrees in {}", repo_root.display()))?
+            .iter()
+            .map(|path| runtime::comparable_path(path))
+            .filter(|path| {
+                *path != repo_key
+                    && !registered_paths.contains(path)
+                    && !path.starts_with(&trash_dir)
+            })
+            .collect(),
+    )
+}
+
+pub(crate) fn register_found_worktree(
+    app: &App<'_>,
+    worktrees_dir: &Path,
+    found: FoundWorktree<'_>,
+) -> Result<()> {
+    if let (Some(session_name), Some(new_name)) = (found.session_name, found.rename_to) {
+        seshmux_core::tmux::rename_session(
+            session_name,
+            new_name,
+            &runtime::tmux_server(app)?,
+            app.runner,
+        )
+        .with_context(|| {
+            format!("failed to rename tmux session '{session_name}' to '{new_name}'")
+        })?;
+    }
+
+    let mut entry = seshmux_core::registry::RegistryEntry::new(
+        found.worktree_name,
+        found.worktree_path.to_string_lossy(),
+        found.created_at,
+    );
+    entry.created_by = Some(seshmux_core::ownership::current_owner());
+    entry.session_name = found.rename_to.or(found.session_name).map(str::to_string);
+    entry.layout = entry.session_name.as_ref().map(|_| SessionLayout::Session);
+    seshmux_core::registry::insert_unique_entry(worktrees_dir, entry).with_context(|| {
+        format!(
+            "failed to register worktree '{}' in {}",
+            found.worktree_name,
+            seshmux_core::registry::registry_path(worktrees_dir).display()
+        )
+    })?;
+
+    Ok(())
+}
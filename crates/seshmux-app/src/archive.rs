@@ -0,0 +1,349 @@
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result, anyhow};
+use seshmux_core::archive::{idle_longer_than, idle_seconds, whole_days};
+use seshmux_core::registry::{ArchiveRecord, RegistryEntry};
+use seshmux_core::tmux::TmuxServer;
+
+use crate::catalog::WorktreeCatalog;
+use crate::runtime;
+use crate::{App, Error};
+
+const ARCHIVE_DIR_NAME: &str = ".archive";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveRequest {
+    pub cwd: PathBuf,
+    pub idle_days: Option<u64>,
+    pub tar: Option<bool>,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedWorktree {
+    pub worktree_name: String,
+    pub worktree_path: PathBuf,
+    pub idle_days: u64,
+    pub session_name: String,
+    pub session_killed: bool,
+    pub tarball: Option<PathBuf>,
+    pub restore_command: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveResult {
+    pub repo_root: PathBuf,
+    pub idle_days: u64,
+    pub dry_run: bool,
+    pub archived: Vec<ArchivedWorktree>,
+    pub skipped: Vec<(String, String)>,
+    pub failed: Vec<(String, String)>,
+}
+
+enum EntryOutcome {
+    Archived(ArchivedWorktree),
+    Skipped(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestoreResult {
+    pub worktree_name: String,
+    pub worktree_path: PathBuf,
+    pub from_tarball: bool,
+    pub detached_from: Option<String>,
+}
+
+impl<'a> App<'a> {
+    pub fn archive_idle(&self, request: ArchiveRequest) -> Result<ArchiveResult, Error> {
+        let config = self.ensure_config_ready()?;
+        let idle_days = request.idle_days.unwrap_or(config.archive.idle_days);
+        if idle_days == 0 {
+            return Err(anyhow!("idle days must be at least 1").into());
+        }
+        let tar = request.tar.unwrap_or(config.archive.tar);
+
+        let catalog = WorktreeCatalog::load(self, &request.cwd)?;
+        let repo_root = catalog.repo_root().to_path_buf();
+        let layout = config.tmux.layout;
+        let server = TmuxServer::from_config(&config.tmux);
+        let activity = seshmux_core::session_model::activity_by_label(
+            layout,
+            config.tmux.backend,
+            &server,
+            self.runner,
+        )
+        .context("failed to read tmux session activity")?;
+        let now = seshmux_core::time::unix_seconds(SystemTime::now());
+        let owner = seshmux_core::ownership::current_owner();
+
+        let archive_entry = |entry: &RegistryEntry| -> Result<Option<EntryOutcome>> {
+            let worktree_path = PathBuf::from(&entry.path);
+            let session = runtime::session_target_for_entry(
+                layout,
+                config.tmux.backend,
+                &server,
+                &repo_root,
+                entry,
+            );
+            let session_name = session.label();
+            let last_commit = seshmux_core::git::last_commit_unix(&worktree_path, self.runner)
+                .with_context(|| {
+                    format!(
+                        "failed to read the last commit of worktree '{}'",
+                        entry.name
+                    )
+                })?;
+            let Some(idle) = idle_seconds(
+                now,
+                &[
+                    seshmux_core::time::rfc3339_unix_seconds(&entry.created_at),
+                    last_commit,
+                    activity.get(&session_name).copied(),
+                ],
+            ) else {
+                return Ok(None);
+            };
+            if !idle_longer_than(idle, idle_days) {
+                return Ok(None);
+            }
+
+            if let Some(other) =
+                seshmux_core::ownership::foreign_owner(entry.created_by.as_deref(), &owner)
+            {
+                return Ok(Some(EntryOutcome::Skipped(format!("created by {other}"))));
+            }
+
+            let branch = seshmux_core::git::current_branch(&worktree_path, self.runner)
+                .ok()
+                .filter(|branch| branch != "HEAD");
+            if tar && branch.is_none() {
+                return Ok(Some(EntryOutcome::Skipped(
+                    "detached HEAD cannot be restored from a tarball".to_string(),
+                )));
+            }
+
+            let tarball = tar.then(|| {
+                catalog
+                    .worktrees_dir()
+                    .join(ARCHIVE_DIR_NAME)
+                    .join(format!("{}.tar.gz", entry.name))
+            });
+            let session_running = activity.contains_key(&session_name);
+            let mut worktree = ArchivedWorktree {
+                worktree_name: entry.name.clone(),
+                worktree_path: worktree_path.clone(),
+                idle_days: whole_days(idle),
+                session_name: session_name.clone(),
+                session_killed: false,
+                tarball: tarball.clone(),
+                restore_command: restore_command(&entry.name),
+            };
+            if request.dry_run {
+                return Ok(Some(EntryOutcome::Archived(worktree)));
+            }
+
+            if session_running {
+                session
+                    .kill(self.runner)
+                    .with_context(|| format!("failed to kill tmux session '{session_name}'"))?;
+                worktree.session_killed = true;
+            }
+
+            let commit = match &tarball {
+                Some(tarball) => {
+                    let head =
+                        seshmux_core::git::resolve_commit(&worktree_path, "HEAD", self.runner)
+                            .with_context(|| {
+                                format!("failed to read HEAD of worktree '{}'", entry.name)
+                            })?;
+                    pack_worktree(self, &worktree_path, tarball)?;
+                    Some(head.hash)
+                }
+                None => None,
+            };
+
+            let archived_at = seshmux_core::time::now_utc_rfc3339()
+                .map_err(|error| anyhow!("failed to format timestamp: {error}"))?;
+            let marked = seshmux_core::registry::set_archived(
+                catalog.worktrees_dir(),
+                &entry.name,
+                Some(ArchiveRecord {
+                    archived_at,
+                    branch,
+                    tarball: tarball
+                        .as_ref()
+                        .map(|path| path.to_string_lossy().to_string()),
+                    commit,
+                }),
+            )
+            .with_context(|| {
+                format!(
+                    "failed to mark worktree '{}' archived in {}",
+                    entry.name,
+                    seshmux_core::registry::registry_path(catalog.worktrees_dir()).display()
+                )
+            });
+            if let Err(error) = marked {
+                if let Some(tarball) = &tarball {
+                    let _ = std::fs::remove_file(tarball);
+                }
+                return Err(error);
+            }
+
+            if let Some(tarball) = &tarball
+                && let Err(error) = seshmux_core::git::force_remove_worktree(
+                    &repo_root,
+                    &worktree_path,
+                    self.runner,
+                )
+            {
+                // The checkout is still there, so the worktree must not stay recorded as archived.
+                let _ = seshmux_core::registry::set_archived(
+                    catalog.worktrees_dir(),
+                    &entry.name,
+                    None,
+                );
+                let _ = std::fs::remove_file(tarball);
+                return Err(anyhow::Error::new(error).context(format!(
+                    "failed to remove worktree at {}",
+                    worktree_path.display()
+                )));
+            }
+            Ok(Some(EntryOutcome::Archived(worktree)))
+        };
+
+        let mut archived = Vec::new();
+        let mut skipped = Vec::new();
+        let mut failed = Vec::new();
+        for entry in catalog.entries() {
+            if entry.archived.is_some() || !Path::new(&entry.path).exists() {
+                continue;
+            }
+            match archive_entry(entry) {
+                Ok(Some(EntryOutcome::Archived(worktree))) => archived.push(worktree),
+                Ok(Some(EntryOutcome::Skipped(reason))) => {
+                    skipped.push((entry.name.clone(), reason));
+                }
+                Ok(None) => {}
+                Err(error) => failed.push((entry.name.clone(), format!("{error:#}"))),
+            }
+        }
+
+        Ok(ArchiveResult {
+            repo_root,
+            idle_days,
+            dry_run: request.dry_run,
+            archived,
+            skipped,
+            failed,
+        })
+    }
+
+    pub fn restore_archived(
+        &self,
+        cwd: &Path,
+        worktree_name: &str,
+    ) -> Result<RestoreResult, Error> {
+        let catalog = WorktreeCatalog::load(self, cwd)?;
+        let entry = catalog
+            .find(worktree_name)
+            .ok_or_else(|| anyhow!("worktree '{worktree_name}' was not found in worktree.toml"))?;
+        let Some(record) = &entry.archived else {
+            return Err(anyhow!("worktree '{worktree_name}' is not archived").into());
+        };
+        let worktree_path = PathBuf::from(&entry.path);
+
+        let mut detached_from = None;
+        let from_tarball = match &record.tarball {
+            Some(tarball) => {
+                let tarball = PathBuf::from(tarball);
+                let Some(branch) = &record.branch else {
+                    return Err(anyhow!(
+                        "archive of worktree '{worktree_name}' does not record its branch"
+                    )
+                    .into());
+                };
+                let tip = branch_tip(self, catalog.repo_root(), branch)?;
+                match &record.commit {
+                    Some(commit) if tip.as_deref() != Some(commit.as_str()) => {
+                        seshmux_core::git::create_detached_worktree(
+                            catalog.repo_root(),
+                            &worktree_path,
+                            commit,
+                            self.runner,
+                        )
+                        .with_context(|| {
+                            format!(
+                                "failed to check out commit {commit} at {}",
+                                worktree_path.display()
+                            )
+                        })?;
+                        detached_from = Some(branch.clone());
+                    }
+                    _ => {
+                        seshmux_core::git::create_worktree_on_branch(
+                            catalog.repo_root(),
+                            &worktree_path,
+                            branch,
+                            false,
+                            self.runner,
+                        )
+                        .with_context(|| {
+                            format!(
+                                "failed to check out branch '{branch}' at {}",
+                                worktree_path.display()
+                            )
+                        })?;
+                    }
+                }
+                seshmux_core::archive::extract_tarball(&tarball, &worktree_path, self.runner)
+                    .with_context(|| format!("failed to unpack {}", tarball.display()))?;
+                std::fs::remove_file(&tarball)
+                    .with_context(|| format!("failed to remove {}", tarball.display()))?;
+                true
+            }
+            None => false,
+        };
+
+        seshmux_core::registry::set_archived(catalog.worktrees_dir(), worktree_name, None)
+            .with_context(|| {
+                format!(
+                    "failed to clear the archive mark of worktree '{worktree_name}' in {}",
+                    seshmux_core::registry::registry_path(catalog.worktrees_dir()).display()
+                )
+            })?;
+
+        Ok(RestoreResult {
+            worktree_name: worktree_name.to_string(),
+            worktree_path,
+            from_tarball,
+            detached_from,
+        })
+    }
+}
+
+fn restore_command(worktree_name: &str) -> String {
+    format!("seshmux archive --restore {worktree_name}")
+}
+
+fn branch_tip(app: &App<'_>, repo_root: &Path, branch: &str) -> Result<Option<String>> {
+    let context = || format!("failed to read the tip of branch '{branch}'");
+    if !seshmux_core::git::branch_exists(repo_root, branch, app.runner).with_context(context)? {
+        return Ok(None);
+    }
+    let tip =
+        seshmux_core::git::resolve_commit(repo_root, &format!("refs/heads/{branch}"), app.runner)
+            .with_context(context)?;
+    Ok(Some(tip.hash))
+}
+
+// The checkout is only removed once its contents are safely in the tarball.
+fn pack_worktree(app: &App<'_>, worktree_path: &Path, tarball: &Path) -> Result<()> {
+    if let Some(parent) = tarball.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    seshmux_core::archive::create_tarball(worktree_path, tarball, app.runner)
+        .with_context(|| format!("failed to pack {}", worktree_path.display()))
+}
@@ -1,28 +1,69 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result, anyhow};
+use seshmux_core::session_model::SessionTarget;
+use seshmux_core::tmux::{SessionHealth, TmuxError};
 use thiserror::Error;
 
-use crate::App;
+use crate::catalog::WorktreeCatalog;
 use crate::runtime;
 use crate::target;
+use crate::{App, Error};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct AttachRequest {
     pub cwd: PathBuf,
     pub worktree_name: String,
     pub create_if_missing: bool,
+    pub expect_running: bool,
+    pub connect: bool,
+    pub allow_foreign: bool,
+}
+
+impl AttachRequest {
+    pub fn new(cwd: impl Into<PathBuf>, worktree_name: impl Into<String>) -> Self {
+        Self {
+            cwd: cwd.into(),
+            worktree_name: worktree_name.into(),
+            create_if_missing: false,
+            expect_running: false,
+            connect: false,
+            allow_foreign: false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct AttachResult {
     pub worktree_name: String,
     pub worktree_path: PathBuf,
     pub session_name: String,
     pub created_session: bool,
+    pub connect_command: String,
+    pub attach_status: Option<i32>,
+}
+
+impl AttachResult {
+    pub fn new(
+        worktree_name: impl Into<String>,
+        worktree_path: impl Into<PathBuf>,
+        session_name: impl Into<String>,
+    ) -> Self {
+        Self {
+            worktree_name: worktree_name.into(),
+            worktree_path: worktree_path.into(),
+            session_name: session_name.into(),
+            created_session: false,
+            connect_command: String::new(),
+            attach_status: None,
+        }
+    }
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum AttachError {
     #[error("worktree '{name}' was not found in worktree.toml")]
     UnknownWorktree { name: String },
@@ -31,40 +72,95 @@ pub enum AttachError {
         worktree_name: String,
         session_name: String,
     },
+    #[error(
+        "tmux session '{session_name}' for '{worktree_name}' is no longer running{}",
+        if *server_reachable { "" } else { " (the tmux server is not reachable)" }
+    )]
+    SessionGone {
+        worktree_name: String,
+        session_name: String,
+        server_reachable: bool,
+    },
+    #[error("failed to connect to tmux session '{session_name}': tmux exited with status {status}")]
+    ConnectFailed {
+        worktree_name: String,
+        worktree_path: PathBuf,
+        session_name: String,
+        created_session: bool,
+        status: i32,
+    },
 }
 
 impl<'a> App<'a> {
-    pub fn attach(&self, request: AttachRequest) -> Result<AttachResult> {
+    pub fn last_attached_worktree(&self, cwd: &Path) -> Result<String, Error> {
+        let catalog = WorktreeCatalog::load(self, cwd)?;
+        let mut attached: Vec<_> = catalog
+            .entries()
+            .iter()
+            .filter(|entry| entry.last_attached_at.is_some())
+            .collect();
+        attached.sort_by(|left, right| right.last_attached_at.cmp(&left.last_attached_at));
+
+        attached
+            .into_iter()
+            .find(|entry| !cwd.starts_with(&entry.path))
+            .map(|entry| entry.name.clone())
+            .ok_or_else(|| anyhow!("no previously attached worktree found"))
+            .map_err(Error::from)
+    }
+
+    pub fn attach(&self, request: AttachRequest) -> Result<AttachResult, Error> {
         let target = target::resolve_target(self, &request.cwd, &request.worktree_name)?
             .ok_or_else(|| AttachError::UnknownWorktree {
                 name: request.worktree_name.clone(),
             })?;
+        target::check_ownership(self, &target, "attach to", request.allow_foreign)?;
 
         let worktree_path = target.worktree_path.clone();
         if !worktree_path.exists() {
-            bail!(
+            return Err(anyhow!(
                 "worktree path does not exist on disk: {}",
                 worktree_path.display()
-            );
+            )
+            .into());
         }
 
-        let session_name = target.session_name.clone();
+        let session = target.session.clone();
+        let session_name = session.label();
 
-        let session_exists = seshmux_core::tmux::session_exists(&session_name, self.runner)
+        // Checked right before connecting: the session may have died since the caller listed it,
+        // or the tmux server may be gone altogether.
+        let health = session
+            .health(self.runner)
             .with_context(|| format!("failed to query tmux session '{session_name}'"))?;
 
-        if session_exists {
-            seshmux_core::tmux::connect_session(&session_name, runtime::inside_tmux(), self.runner)
-                .with_context(|| format!("failed to connect to tmux session '{session_name}'"))?;
+        let inside_tmux = runtime::inside_tmux(&runtime::tmux_server(self)?);
+        let connect_command = session.connect_command(inside_tmux);
 
-            return Ok(AttachResult {
+        if health == SessionHealth::Running {
+            record_attach(&target);
+            let mut result = AttachResult {
                 worktree_name: target.worktree_name,
                 worktree_path,
                 session_name,
                 created_session: false,
-            });
+                connect_command,
+                attach_status: None,
+            };
+            if request.connect {
+                connect(self, &session, &mut result, inside_tmux)?;
+            }
+            return Ok(result);
         }
 
+        if !request.create_if_missing && request.expect_running {
+            return Err(AttachError::SessionGone {
+                worktree_name: target.worktree_name,
+                session_name,
+                server_reachable: health != SessionHealth::ServerUnreachable,
+            }
+            .into());
+        }
         if !request.create_if_missing {
             return Err(AttachError::MissingSession {
                 worktree_name: target.worktree_name,
@@ -73,24 +169,96 @@ impl<'a> App<'a> {
             .into());
         }
 
-        let config = self.ensure_config_ready()?;
-
-        seshmux_core::tmux::create_session_and_windows(
-            &session_name,
-            &worktree_path,
-            &config.tmux.windows,
-            self.runner,
-        )
-        .with_context(|| format!("failed to create tmux session '{session_name}'"))?;
-
-        seshmux_core::tmux::connect_session(&session_name, runtime::inside_tmux(), self.runner)
-            .with_context(|| format!("failed to connect to tmux session '{session_name}'"))?;
+        start_session(self, &target)?;
+        record_attach(&target);
 
-        Ok(AttachResult {
+        let mut result = AttachResult {
             worktree_name: target.worktree_name,
             worktree_path,
             session_name,
             created_session: true,
-        })
+            connect_command,
+            attach_status: None,
+        };
+        if request.connect {
+            connect(self, &session, &mut result, inside_tmux)?;
+        }
+        Ok(result)
+    }
+}
+
+pub(crate) fn start_session(app: &App<'_>, target: &target::ResolvedTarget) -> Result<()> {
+    let config = app.ensure_repo_config_ready(&target.repo_root)?;
+    let worktree_path = &target.worktree_path;
+    let environment = if config.tmux.environment.is_empty() {
+        Vec::new()
+    } else {
+        let branch = seshmux_core::git::current_branch(worktree_path, app.runner)
+            .ok()
+            .or_else(|| target.branch.clone())
+            .unwrap_or_else(|| target.worktree_name.clone());
+        runtime::session_environment(
+            &config,
+            &target.repo_root,
+            &target.worktree_name,
+            &branch,
+            worktree_path,
+        )
+    };
+
+    let (session_dir, windows) =
+        runtime::project_session(&config, worktree_path, target.project.as_deref());
+    let session_name = target.session.label();
+    target
+        .session
+        .create(&session_dir, windows, &environment, app.runner)
+        .with_context(|| format!("failed to create tmux session '{session_name}'"))
+}
+
+// Best effort: the session is already usable, so a registry that cannot be written must not
+// turn the attach into a failure.
+fn record_attach(target: &target::ResolvedTarget) {
+    if let Ok(now) = seshmux_core::time::now_utc_rfc3339() {
+        let _ = seshmux_core::registry::record_attach(
+            &target.worktrees_dir,
+            &target.worktree_name,
+            &now,
+        );
+    }
+}
+
+fn connect(
+    app: &App<'_>,
+    session: &SessionTarget,
+    result: &mut AttachResult,
+    inside_tmux: bool,
+) -> Result<()> {
+    match session.connect(inside_tmux, app.runner) {
+        Ok(()) => {
+            result.attach_status = Some(0);
+            Ok(())
+        }
+        Err(TmuxError::CommandFailed { status, .. }) => match session.health(app.runner) {
+            Ok(health) if health != SessionHealth::Running => Err(AttachError::SessionGone {
+                worktree_name: result.worktree_name.clone(),
+                session_name: result.session_name.clone(),
+                server_reachable: health != SessionHealth::ServerUnreachable,
+            }
+            .into()),
+            _ => Err(AttachError::ConnectFailed {
+                worktree_name: result.worktree_name.clone(),
+                worktree_path: result.worktree_path.clone(),
+                session_name: result.session_name.clone(),
+                created_session: result.created_session,
+                status,
+            }
+            .into()),
+        },
+        Err(error) => Err(error).with_context(|| {
+            format!(
+                "failed to connect to tmux session '{}'",
+                result.session_name
+            )
+        }),
     }
 }
@@ -0,0 +1,165 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+
+use crate::attach::start_session;
+use crate::catalog::WorktreeCatalog;
+use crate::dash::dash_repo_roots;
+use crate::runtime;
+use crate::target::{self, ResolvedTarget};
+use crate::{App, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootstrapRequest {
+    pub cwd: PathBuf,
+    pub flagged_only: bool,
+    pub dash: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BootstrapState {
+    Started,
+    AlreadyRunning,
+    Skipped(String),
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootstrapSession {
+    pub repo_root: PathBuf,
+    pub worktree_name: String,
+    pub session_name: String,
+    pub state: BootstrapState,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootstrapResult {
+    pub sessions: Vec<BootstrapSession>,
+    pub problems: Vec<(PathBuf, String)>,
+}
+
+impl BootstrapResult {
+    pub fn failed(&self) -> usize {
+        self.sessions
+            .iter()
+            .filter(|session| matches!(session.state, BootstrapState::Failed(_)))
+            .count()
+            + self.problems.len()
+    }
+}
+
+impl<'a> App<'a> {
+    pub fn bootstrap(&self, request: BootstrapRequest) -> Result<BootstrapResult, Error> {
+        let mut result = BootstrapResult {
+            sessions: Vec::new(),
+            problems: Vec::new(),
+        };
+
+        if !request.dash {
+            let catalog = WorktreeCatalog::load(self, &request.cwd)?;
+            self.bootstrap_catalog(&catalog, request.flagged_only, &mut result)?;
+            return Ok(result);
+        }
+
+        let config = self.ensure_config_ready()?;
+        let roots = dash_repo_roots(&config.dash);
+        if roots.is_empty() {
+            return Err(anyhow!(
+                "no repositories configured for seshmux bootstrap --dash; set [dash] repos or base_dir in the config"
+            )
+            .into());
+        }
+
+        let mut seen: Vec<PathBuf> = Vec::new();
+        for root in roots {
+            let catalog = match WorktreeCatalog::load(self, &root) {
+                Ok(catalog) => catalog,
+                Err(error) => {
+                    result.problems.push((root, format!("{error:#}")));
+                    continue;
+                }
+            };
+            // Two configured paths inside the same checkout share one registry.
+            if seen
+                .iter()
+                .any(|repo_root| repo_root == catalog.repo_root())
+            {
+                continue;
+            }
+            seen.push(catalog.repo_root().to_path_buf());
+            self.bootstrap_catalog(&catalog, request.flagged_only, &mut result)?;
+        }
+
+        Ok(result)
+    }
+
+    pub fn set_autostart(
+        &self,
+        cwd: &Path,
+        worktree_name: &str,
+        autostart: bool,
+    ) -> Result<String, Error> {
+        let catalog = WorktreeCatalog::load(self, cwd)?;
+        let entry = catalog
+            .find(worktree_name)
+            .ok_or_else(|| anyhow!("worktree '{worktree_name}' was not found in worktree.toml"))?;
+        seshmux_core::registry::set_autostart(catalog.worktrees_dir(), &entry.name, autostart)
+            .with_context(|| {
+                format!(
+                    "failed to update {}",
+                    seshmux_core::registry::registry_path(catalog.worktrees_dir()).display()
+                )
+            })?;
+        Ok(entry.name.clone())
+    }
+
+    fn bootstrap_catalog(
+        &self,
+        catalog: &WorktreeCatalog,
+        flagged_only: bool,
+        result: &mut BootstrapResult,
+    ) -> Result<()> {
+        let layout = runtime::session_layout(self)?;
+        let backend = runtime::session_backend(self)?;
+        let server = runtime::tmux_server(self)?;
+        for entry in catalog.entries() {
+            if entry.archived.is_some() || (flagged_only && !entry.autostart) {
+                continue;
+            }
+            let target = target::target_for_entry(catalog, entry, layout, backend, &server);
+            let state = self.bootstrap_target(&target);
+            result.sessions.push(BootstrapSession {
+                repo_root: target.repo_root,
+                worktree_name: target.worktree_name,
+                session_name: target.session.label(),
+                state,
+            });
+        }
+        Ok(())
+    }
+
+    fn bootstrap_target(&self, target: &ResolvedTarget) -> BootstrapState {
+        if !target.worktree_path.exists() {
+            return BootstrapState::Skipped(format!(
+                "worktree path does not exist on disk: {}",
+                target.worktree_path.display()
+            ));
+        }
+
+        let session_name = target.session.label();
+        match target.session.exists(self.runner) {
+            Ok(true) => return BootstrapState::AlreadyRunning,
+            Ok(false) => {}
+            Err(error) => {
+                return BootstrapState::Failed(format!(
+                    "failed to query tmux session '{session_name}': {error}"
+                ));
+            }
+        }
+
+        match start_session(self, target) {
+            Ok(()) => BootstrapState::Started,
+            Err(error) => BootstrapState::Failed(format!("{error:#}")),
+        }
+    }
+}
@@ -7,62 +7,92 @@ use crate::runtime;
 
 pub(crate) struct WorktreeCatalog {
     repo_root: PathBuf,
+    worktrees_dir: PathBuf,
     entries: Vec<seshmux_core::registry::RegistryEntry>,
 }
 
 impl WorktreeCatalog {
     pub(crate) fn load(app: &App<'_>, cwd: &Path) -> Result<Self> {
         let repo_root = runtime::resolve_repo_root(app, cwd)?;
-        let entries = seshmux_core::registry::load_registry(&repo_root).with_context(|| {
+        let worktrees_dir = runtime::worktrees_dir(app, &repo_root)?;
+        let entries = seshmux_core::registry::load_registry(&worktrees_dir).with_context(|| {
             format!(
                 "failed to load worktree registry at {}",
-                seshmux_core::registry::registry_path(&repo_root).display()
+                seshmux_core::registry::registry_path(&worktrees_dir).display()
             )
         })?;
 
-        Ok(Self { repo_root, entries })
+        Ok(Self {
+            repo_root,
+            worktrees_dir,
+            entries,
+        })
     }
 
     pub(crate) fn repo_root(&self) -> &Path {
         &self.repo_root
     }
 
+    pub(crate) fn worktrees_dir(&self) -> &Path {
+        &self.worktrees_dir
+    }
+
+    pub(crate) fn entries(&self) -> &[seshmux_core::registry::RegistryEntry] {
+        &self.entries
+    }
+
     pub(crate) fn find(&self, name: &str) -> Option<&seshmux_core::registry::RegistryEntry> {
         self.entries.iter().find(|entry| entry.name == name)
     }
 
     pub(crate) fn list_rows(&self, app: &App<'_>) -> Result<Vec<crate::list::WorktreeRow>> {
         let mut rows = Vec::new();
+        let layout = runtime::session_layout(app)?;
+        let backend = runtime::session_backend(app)?;
+        let server = runtime::tmux_server(app)?;
 
         for entry in &self.entries {
             let path = PathBuf::from(entry.path.clone());
-            let branch = if path.exists() {
-                seshmux_core::git::current_branch(&path, app.runner).with_context(|| {
-                    format!(
-                        "failed to resolve current branch for worktree '{}' at {}",
-                        entry.name,
-                        path.display()
-                    )
-                })?
+            let head = if path.exists() {
+                let branch =
+                    seshmux_core::git::current_branch(&path, app.runner).with_context(|| {
+                        format!(
+                            "failed to resolve current branch for worktree '{}' at {}",
+                            entry.name,
+                            path.display()
+                        )
+                    })?;
+                // `rev-parse --abbrev-ref` prints HEAD itself when no branch is checked out.
+                if branch == "HEAD" {
+                    crate::list::WorktreeHead::Detached
+                } else {
+                    crate::list::WorktreeHead::Branch(branch)
+                }
             } else {
-                "MISSING".to_string()
+                crate::list::WorktreeHead::Missing
             };
 
-            let session_name = runtime::session_name_for(&self.repo_root, &entry.name);
-            let session_running = seshmux_core::tmux::session_exists(&session_name, app.runner)
+            let session =
+                runtime::session_target_for_entry(layout, backend, &server, &self.repo_root, entry);
+            let session_name = session.label();
+            let session_running = session
+                .exists(app.runner)
                 .with_context(|| format!("failed to check tmux session '{session_name}'"))?;
 
             rows.push(crate::list::WorktreeRow {
                 name: entry.name.clone(),
                 path,
                 created_at: entry.created_at.clone(),
-                branch,
+                head,
                 session_name,
                 session_running,
+                disk_usage: entry.disk_usage.clone(),
+                last_attached_at: entry.last_attached_at.clone(),
+                display_name: entry.display_name.clone(),
             });
         }
 
-        rows.sort_by(|left, right| right.created_at.cmp(&left.created_at));
+        crate::list::WorktreeOrder::default().sort(&mut rows);
         Ok(rows)
     }
 }
@@ -120,11 +150,7 @@ mod tests {
     }
 
     fn output(stdout: &str, stderr: &str, status_code: i32) -> anyhow::Result<CommandOutput> {
-        Ok(CommandOutput {
-            status_code,
-            stdout: stdout.to_string(),
-            stderr: stderr.to_string(),
-        })
+        Ok(CommandOutput::new(status_code, stdout, stderr))
     }
 
     #[test]
@@ -139,21 +165,13 @@ mod tests {
         std::fs::create_dir_all(&new_path).expect("new path");
 
         insert_unique_entry(
-            &repo_root,
-            RegistryEntry {
-                name: "old".to_string(),
-                path: old_path.to_string_lossy().to_string(),
-                created_at: "2026-02-24T10:00:00Z".to_string(),
-            },
+            &repo_root.join("worktrees"),
+            RegistryEntry::new("old", old_path.to_string_lossy(), "2026-02-24T10:00:00Z"),
         )
         .expect("insert old");
         insert_unique_entry(
-            &repo_root,
-            RegistryEntry {
-                name: "new".to_string(),
-                path: new_path.to_string_lossy().to_string(),
-                created_at: "2026-02-25T10:00:00Z".to_string(),
-            },
+            &repo_root.join("worktrees"),
+            RegistryEntry::new("new", new_path.to_string_lossy(), "2026-02-25T10:00:00Z"),
         )
         .expect("insert new");
 
@@ -0,0 +1,217 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+use seshmux_core::config::check::{ConfigIssue, check_config_file, check_repo_config_source};
+use seshmux_core::config::{
+    LoadOptions, RepoConfig, SeshmuxConfig, load_repo_config, merge_repo_config, repo_config_path,
+    resolve_config_path, validate_config,
+};
+use seshmux_core::scaffold::{GlobalConfigScaffold, render_global_config};
+
+use crate::errors::ConfigNotReady;
+use crate::open;
+use crate::runtime;
+use crate::{App, Error};
+
+const FALLBACK_EDITOR: &str = "vi";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigEjectRequest {
+    pub cwd: PathBuf,
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigEjectResult {
+    pub path: PathBuf,
+    pub window_names: Vec<String>,
+    pub always_skip_buckets: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigInitRequest {
+    pub scaffold: GlobalConfigScaffold,
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigCheckRequest {
+    pub cwd: PathBuf,
+    pub repo: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigCheckResult {
+    pub path: PathBuf,
+    pub issues: Vec<ConfigIssue>,
+}
+
+impl<'a> App<'a> {
+    pub fn ensure_repo_config_ready(&self, repo_root: &Path) -> Result<SeshmuxConfig, Error> {
+        let mut config = self.ensure_config_ready()?;
+        apply_repo_config(&mut config, repo_root)?;
+        Ok(config)
+    }
+
+    pub fn config_eject_repo(
+        &self,
+        request: ConfigEjectRequest,
+    ) -> Result<ConfigEjectResult, Error> {
+        let repo_root = runtime::resolve_repo_root(self, &request.cwd)?;
+        let path = repo_config_path(&repo_root);
+
+        if path.exists() && !request.force {
+            return Err(anyhow!(
+                "{} already exists; pass --force to overwrite it",
+                path.display()
+            )
+            .into());
+        }
+
+        let ignored_dirs = seshmux_core::git::list_ignored_directories(&repo_root, self.runner)
+            .with_context(|| {
+                format!(
+                    "failed to list ignored directories in {}",
+                    repo_root.display()
+                )
+            })?;
+        let scaffold = seshmux_core::scaffold::scaffold_repo_config(
+            &repo_root,
+            &ignored_dirs,
+            &open::login_shell(),
+        );
+        let rendered = seshmux_core::scaffold::render_repo_config(&scaffold);
+
+        std::fs::write(&path, rendered)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+
+        Ok(ConfigEjectResult {
+            path,
+            window_names: scaffold
+                .windows
+                .iter()
+                .map(|window| window.name.clone())
+                .collect(),
+            always_skip_buckets: scaffold.always_skip_buckets,
+        })
+    }
+
+    pub fn config_missing(&self) -> Result<bool, Error> {
+        let path = resolve_config_path().context("failed to resolve config path")?;
+        Ok(!path.exists())
+    }
+
+    pub fn config_setup_defaults(&self) -> GlobalConfigScaffold {
+        let editor = std::env::var("EDITOR").ok();
+        GlobalConfigScaffold::detected(editor.as_deref(), &open::login_shell())
+    }
+
+    pub fn config_init(&self, request: ConfigInitRequest) -> Result<PathBuf, Error> {
+        let path = resolve_config_path().context("failed to resolve config path")?;
+        if path.exists() && !request.force {
+            return Err(anyhow!(
+                "{} already exists; pass --force to overwrite it",
+                path.display()
+            )
+            .into());
+        }
+        if request.scaffold.windows.is_empty() {
+            return Err(anyhow!("pick at least one window for new sessions").into());
+        }
+
+        let rendered = render_global_config(&request.scaffold);
+        let parsed: SeshmuxConfig =
+            toml::from_str(&rendered).context("the generated config does not parse")?;
+        validate_config(&parsed).map_err(|error| ConfigNotReady::Global(error.to_string()))?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        std::fs::write(&path, rendered)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    pub fn config_validate(&self, request: ConfigCheckRequest) -> Result<ConfigCheckResult, Error> {
+        let path = self.existing_config_path(&request)?;
+        check_config_at(path, request.repo, &self.load_options).map_err(Error::from)
+    }
+
+    pub fn config_edit(&self, request: ConfigCheckRequest) -> Result<ConfigCheckResult, Error> {
+        let path = self.existing_config_path(&request)?;
+        let command = open::resolve_editor_command(None, std::env::var("EDITOR").ok())
+            .unwrap_or_else(|| vec![FALLBACK_EDITOR.to_string()]);
+
+        let program = command[0].as_str();
+        let path_arg = path.to_string_lossy();
+        let mut args = command[1..].iter().map(String::as_str).collect::<Vec<_>>();
+        args.push(path_arg.as_ref());
+
+        let status = self
+            .runner
+            .run_interactive(program, &args, None)
+            .with_context(|| format!("failed to launch editor '{program}'"))?;
+        if status != 0 {
+            return Err(anyhow!("editor '{program}' exited with status {status}").into());
+        }
+
+        check_config_at(path, request.repo, &self.load_options).map_err(Error::from)
+    }
+
+    fn existing_config_path(&self, request: &ConfigCheckRequest) -> Result<PathBuf> {
+        let path = if request.repo {
+            let repo_root = runtime::resolve_repo_root(self, &request.cwd)?;
+            repo_config_path(&repo_root)
+        } else {
+            resolve_config_path().context("failed to resolve config path")?
+        };
+
+        if !path.exists() {
+            let hint = if request.repo {
+                "Run `seshmux config eject --repo` to create one."
+            } else {
+                "Run `seshmux init-config --interactive` to create it, or see README.md for setup instructions."
+            };
+            bail!("missing config at {}\n{hint}", path.display());
+        }
+
+        Ok(path)
+    }
+}
+
+fn check_config_at(path: PathBuf, repo: bool, options: &LoadOptions) -> Result<ConfigCheckResult> {
+    let raw = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let issues = if repo {
+        check_repo_config_source(&raw)
+    } else {
+        check_config_file(&path, &raw, options)
+    };
+
+    Ok(ConfigCheckResult { path, issues })
+}
+
+pub(crate) fn apply_repo_config(config: &mut SeshmuxConfig, repo_root: &Path) -> Result<()> {
+    if let Some(repo_config) = load_repo_config_if_present(repo_root)? {
+        merge_repo_config(config, &repo_config);
+    }
+
+    Ok(())
+}
+
+pub(crate) fn load_repo_config_if_present(repo_root: &Path) -> Result<Option<RepoConfig>> {
+    let path = repo_config_path(repo_root);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    load_repo_config(&path).map(Some).map_err(|error| {
+        ConfigNotReady::Repo(format!(
+            "invalid repo config at {}: {error}",
+            path.display()
+        ))
+        .into()
+    })
+}
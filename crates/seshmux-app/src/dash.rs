@@ -0,0 +1,107 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+use seshmux_core::config::{DashConfig, expand_home};
+
+use crate::list::WorktreeRow;
+use crate::{App, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DashRepo {
+    pub repo_root: PathBuf,
+    pub rows: Vec<WorktreeRow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DashResult {
+    pub repos: Vec<DashRepo>,
+    pub problems: Vec<(PathBuf, String)>,
+}
+
+impl<'a> App<'a> {
+    pub fn dash(&self) -> Result<DashResult, Error> {
+        let config = self.ensure_config_ready()?;
+        let roots = dash_repo_roots(&config.dash);
+        if roots.is_empty() {
+            return Err(anyhow!(
+                "no repositories configured for seshmux dash; set [dash] repos or base_dir in the config"
+            ).into());
+        }
+
+        let mut repos = Vec::new();
+        let mut problems = Vec::new();
+        for root in roots {
+            match self.list(&root) {
+                Ok(result) => {
+                    // Two configured paths inside the same checkout list the same worktrees.
+                    if repos
+                        .iter()
+                        .all(|repo: &DashRepo| repo.repo_root != result.repo_root)
+                    {
+                        repos.push(DashRepo {
+                            repo_root: result.repo_root,
+                            rows: result.rows,
+                        });
+                    }
+                }
+                Err(error) => problems.push((root, format!("{error:#}"))),
+            }
+        }
+
+        Ok(DashResult { repos, problems })
+    }
+}
+
+pub(crate) fn dash_repo_roots(config: &DashConfig) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = config
+        .repos
+        .iter()
+        .map(|repo| expand_home(repo.trim()))
+        .collect();
+
+    if let Some(base_dir) = &config.base_dir
+        && let Ok(entries) = fs::read_dir(expand_home(base_dir.trim()))
+    {
+        let mut discovered: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.join(".git").exists())
+            .collect();
+        discovered.sort();
+        roots.extend(discovered);
+    }
+
+    let mut unique = Vec::with_capacity(roots.len());
+    for root in roots {
+        if !unique.contains(&root) {
+            unique.push(root);
+        }
+    }
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use seshmux_core::config::DashConfig;
+
+    use super::dash_repo_roots;
+
+    #[test]
+    fn dash_repo_roots_adds_git_checkouts_under_base_dir() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let base = temp.path().join("src");
+        fs::create_dir_all(base.join("beta/.git")).expect("beta");
+        fs::create_dir_all(base.join("alpha/.git")).expect("alpha");
+        fs::create_dir_all(base.join("notes")).expect("notes");
+
+        let roots = dash_repo_roots(&DashConfig {
+            repos: vec![base.join("beta").display().to_string()],
+            base_dir: Some(base.display().to_string()),
+        });
+
+        assert_eq!(roots, vec![base.join("beta"), base.join("alpha")]);
+    }
+}
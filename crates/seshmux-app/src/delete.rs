@@ -1,21 +1,44 @@
 use std::path::PathBuf;
 
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result, anyhow};
+use seshmux_core::config::DeleteMode;
+use seshmux_core::session_env::WorktreeTemplateContext;
 use thiserror::Error;
 
-use crate::App;
+use crate::config;
+use crate::runtime;
 use crate::target;
+use crate::trash;
+use crate::{App, Error};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct DeleteRequest {
     pub cwd: PathBuf,
     pub worktree_name: String,
     pub kill_tmux_session: bool,
     pub delete_branch: bool,
     pub force_worktree: bool,
+    pub allow_cwd_inside: bool,
+    pub allow_foreign: bool,
+}
+
+impl DeleteRequest {
+    pub fn new(cwd: impl Into<PathBuf>, worktree_name: impl Into<String>) -> Self {
+        Self {
+            cwd: cwd.into(),
+            worktree_name: worktree_name.into(),
+            kill_tmux_session: false,
+            delete_branch: false,
+            force_worktree: false,
+            allow_cwd_inside: false,
+            allow_foreign: false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct DeleteResult {
     pub worktree_name: String,
     pub repo_root: PathBuf,
@@ -24,18 +47,52 @@ pub struct DeleteResult {
     pub branch_name: String,
     pub branch_deleted: bool,
     pub branch_delete_error: Option<String>,
+    pub leaked_resources: Vec<String>,
+    pub foreign_owner: Option<String>,
+    pub trashed_to: Option<PathBuf>,
+    pub purged: Vec<String>,
+}
+
+impl DeleteResult {
+    pub fn new(
+        worktree_name: impl Into<String>,
+        repo_root: impl Into<PathBuf>,
+        worktree_path: impl Into<PathBuf>,
+        session_name: impl Into<String>,
+        branch_name: impl Into<String>,
+    ) -> Self {
+        Self {
+            worktree_name: worktree_name.into(),
+            repo_root: repo_root.into(),
+            worktree_path: worktree_path.into(),
+            session_name: session_name.into(),
+            branch_name: branch_name.into(),
+            branch_deleted: false,
+            branch_delete_error: None,
+            leaked_resources: Vec::new(),
+            foreign_owner: None,
+            trashed_to: None,
+            purged: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum DeleteError {
     #[error("worktree '{name}' was not found in worktree.toml")]
     UnknownWorktree { name: String },
     #[error("worktree deletion failed: {message}")]
     WorktreeDeleteFailed { message: String },
+    #[error(
+        "the current directory is inside worktree '{name}' ({}); deleting it would leave this shell in a removed directory",
+        path.display()
+    )]
+    CwdInsideWorktree { name: String, path: PathBuf },
 }
 
 impl<'a> App<'a> {
-    pub fn delete(&self, request: DeleteRequest) -> Result<DeleteResult> {
+    pub fn delete(&self, request: DeleteRequest) -> Result<DeleteResult, Error> {
         let worktree_name = request.worktree_name.clone();
         let target =
             target::resolve_target(self, &request.cwd, &worktree_name)?.ok_or_else(|| {
@@ -44,65 +101,180 @@ impl<'a> App<'a> {
                 }
             })?;
 
+        if !request.allow_cwd_inside
+            && runtime::comparable_path(&request.cwd)
+                .starts_with(runtime::comparable_path(&target.worktree_path))
+        {
+            return Err(DeleteError::CwdInsideWorktree {
+                name: target.worktree_name,
+                path: target.worktree_path,
+            }
+            .into());
+        }
+
+        let foreign_owner =
+            target::check_ownership(self, &target, "delete", request.allow_foreign)?;
+
         let repo_root = target.repo_root.clone();
+        let worktrees_dir = target.worktrees_dir.clone();
         let worktree_path = target.worktree_path.clone();
-        let session_name = target.session_name.clone();
+        let session = target.session.clone();
+        let session_name = session.label();
+        let mut leaked_resources = Vec::new();
+
+        let repo_config = config::load_repo_config_if_present(&repo_root)?;
+        let deprovision = repo_config
+            .as_ref()
+            .and_then(|repo_config| repo_config.hooks.as_ref())
+            .map(|hooks| hooks.deprovision.clone())
+            .unwrap_or_default();
+        let deprovisioned =
+            seshmux_core::registry::find_entry_by_name(&worktrees_dir, &worktree_name)
+                .context("failed to read the worktree registry")?
+                .is_some_and(|entry| entry.deprovisioned);
+        if !deprovision.is_empty() && !deprovisioned && worktree_path.exists() {
+            for command in &deprovision {
+                if let Err(error) =
+                    seshmux_core::hooks::run_shell_hook(command, &worktree_path, self.runner)
+                {
+                    leaked_resources.push(format!("deprovision step failed: {error}"));
+                }
+            }
+            seshmux_core::registry::mark_deprovisioned(&worktrees_dir, &worktree_name)
+                .context("failed to record the deprovision hooks in the worktree registry")?;
+        }
 
         if request.kill_tmux_session {
-            let exists = seshmux_core::tmux::session_exists(&session_name, self.runner)
+            let exists = session
+                .exists(self.runner)
                 .with_context(|| format!("failed to query tmux session '{session_name}'"))?;
             if exists {
-                seshmux_core::tmux::kill_session(&session_name, self.runner)
+                session
+                    .kill(self.runner)
                     .with_context(|| format!("failed to kill tmux session '{session_name}'"))?;
+
+                match session.exists(self.runner) {
+                    Ok(false) => {}
+                    Ok(true) => leaked_resources.push(format!(
+                        "tmux session '{session_name}' is still running after kill"
+                    )),
+                    Err(error) => leaked_resources.push(format!(
+                        "could not verify tmux session '{session_name}' was removed: {error}"
+                    )),
+                }
             }
         }
 
-        let remove_result = if request.force_worktree {
-            seshmux_core::git::force_remove_worktree(&repo_root, &worktree_path, self.runner)
-        } else {
-            seshmux_core::git::remove_worktree(&repo_root, &worktree_path, self.runner)
+        let environment_templates = match repo_config.and_then(|repo_config| repo_config.tmux) {
+            Some(tmux) => tmux.environment,
+            None => runtime::environment_templates(self)?,
         };
-        if let Err(error) = remove_result {
-            return Err(DeleteError::WorktreeDeleteFailed {
-                message: format!("{error:#}"),
+        if !environment_templates.is_empty()
+            && session
+                .exists(self.runner)
+                .with_context(|| format!("failed to query tmux session '{session_name}'"))?
+        {
+            let expected = seshmux_core::session_env::render_session_environment(
+                &environment_templates,
+                WorktreeTemplateContext {
+                    repo: runtime::repo_component(&repo_root),
+                    name: &worktree_name,
+                    branch: target.branch.as_deref().unwrap_or(&worktree_name),
+                    path: &worktree_path,
+                },
+            );
+            match session.exported_environment(self.runner) {
+                Ok(exported) => leaked_resources.extend(
+                    expected
+                        .iter()
+                        .filter(|(key, value)| exported.get(key) == Some(value))
+                        .map(|(key, _)| {
+                            format!("tmux session '{session_name}' still exports {key}")
+                        }),
+                ),
+                Err(error) => leaked_resources.push(format!(
+                    "could not read the environment of tmux session '{session_name}': {error}"
+                )),
             }
-            .into());
         }
 
-        let removed = seshmux_core::registry::remove_entry_by_name(&repo_root, &worktree_name)
+        let (delete_mode, trash_config) = runtime::delete_mode(self)?;
+        let trashed_to = if delete_mode == DeleteMode::Trash && worktree_path.exists() {
+            let trash_path = trash::move_to_trash(
+                self,
+                &repo_root,
+                &worktrees_dir,
+                &worktree_name,
+                &worktree_path,
+            )
+            .map_err(|error| DeleteError::WorktreeDeleteFailed {
+                message: format!("{error:#}"),
+            })?;
+            Some(trash_path)
+        } else {
+            let remove_result = runtime::vcs(self, &repo_root)?.remove_worktree(
+                &repo_root,
+                &worktree_path,
+                request.force_worktree,
+            );
+            if let Err(error) = remove_result {
+                return Err(DeleteError::WorktreeDeleteFailed {
+                    message: format!("{error:#}"),
+                }
+                .into());
+            }
+            None
+        };
+
+        let removed = seshmux_core::registry::remove_entry_by_name(&worktrees_dir, &worktree_name)
             .with_context(|| {
                 format!(
                     "failed to update worktree registry at {}",
-                    seshmux_core::registry::registry_path(&repo_root).display()
+                    seshmux_core::registry::registry_path(&worktrees_dir).display()
                 )
             })?;
 
-        if removed.is_none() {
+        let Some(removed) = removed else {
             return Err(DeleteError::UnknownWorktree {
                 name: worktree_name.clone(),
             }
             .into());
-        }
+        };
 
-        let still_present = seshmux_core::registry::find_entry_by_name(&repo_root, &worktree_name)
-            .with_context(|| {
-                format!(
-                    "failed to re-check worktree registry at {}",
-                    seshmux_core::registry::registry_path(&repo_root).display()
-                )
-            })?
-            .is_some();
+        let still_present =
+            seshmux_core::registry::find_entry_by_name(&worktrees_dir, &worktree_name)
+                .with_context(|| {
+                    format!(
+                        "failed to re-check worktree registry at {}",
+                        seshmux_core::registry::registry_path(&worktrees_dir).display()
+                    )
+                })?
+                .is_some();
         if still_present {
-            bail!(
+            return Err(anyhow!(
                 "worktree '{worktree_name}' still exists in registry after delete; aborting to avoid drift"
-            );
+            ).into());
+        }
+
+        let mut purged = Vec::new();
+        if let Some(trash_path) = &trashed_to {
+            trash::record_trashed(&worktrees_dir, trash_path, removed, request.delete_branch)?;
+            let (purged_names, problems) = trash::purge_expired(
+                self,
+                &repo_root,
+                &worktrees_dir,
+                trash_config.retention_days,
+            )?;
+            purged = purged_names;
+            leaked_resources.extend(problems);
         }
 
-        let branch_name = target.worktree_name;
+        let branch_name = target.branch.unwrap_or(target.worktree_name);
         let mut branch_deleted = false;
         let mut branch_delete_error = None;
 
-        if request.delete_branch {
+        // A trashed worktree still has its branch checked out; the purge deletes it later.
+        if request.delete_branch && trashed_to.is_none() {
             match seshmux_core::git::delete_branch(&repo_root, &branch_name, self.runner) {
                 Ok(()) => {
                     branch_deleted = true;
@@ -122,10 +294,18 @@ impl<'a> App<'a> {
             branch_name,
             branch_deleted,
             branch_delete_error,
+            leaked_resources,
+            foreign_owner,
+            trashed_to,
+            purged,
         })
     }
 
-    pub fn force_delete_branch(&self, repo_root: PathBuf, branch_name: String) -> Result<()> {
+    pub fn force_delete_branch(
+        &self,
+        repo_root: PathBuf,
+        branch_name: String,
+    ) -> Result<(), Error> {
         seshmux_core::git::force_delete_branch(&repo_root, &branch_name, self.runner)
             .with_context(|| format!("failed to force delete branch '{branch_name}'"))?;
         Ok(())
@@ -0,0 +1,200 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+
+use crate::runtime;
+use crate::{App, Error, SessionScope};
+
+const DEMO_DEFAULT_BRANCH: &str = "main";
+
+type DemoFiles = &'static [(&'static str, &'static str)];
+
+const DEMO_HISTORY: &[(&str, DemoFiles)] = &[
+    (
+        "Initial commit",
+        &[
+            (
+                "README.md",
+                "# seshmux demo\n\nA throwaway repository for trying out seshmux.\n",
+            ),
+            (".gitignore", ".env\nnode_modules/\nbuild/\n"),
+            (
+                "package.json",
+                "{\n  \"name\": \"seshmux-demo\",\n  \"version\": \"0.1.0\"\n}\n",
+            ),
+        ],
+    ),
+    (
+        "Add greeting module",
+        &[(
+            "src/greet.js",
+            "export function greet(name) {\n  return `Hello, ${name}!`;\n}\n",
+        )],
+    ),
+    (
+        "Document usage",
+        &[(
+            "README.md",
+            "# seshmux demo\n\nA throwaway repository for trying out seshmux.\n\n\
+             Run `node -e \"import('./src/greet.js').then(m => console.log(m.greet('you')))\"`.\n",
+        )],
+    ),
+];
+
+const DEMO_BRANCHES: &[(&str, &str, DemoFiles)] = &[
+    (
+        "feature/greeting-tests",
+        "Add greeting tests",
+        &[(
+            "test/greet.test.js",
+            "import { greet } from '../src/greet.js';\n\n\
+             console.assert(greet('demo') === 'Hello, demo!');\n",
+        )],
+    ),
+    (
+        "fix/readme-typo",
+        "Fix README wording",
+        &[(
+            "README.md",
+            "# seshmux demo\n\nA disposable repository for trying out seshmux.\n",
+        )],
+    ),
+];
+
+const DEMO_UNTRACKED: DemoFiles = &[
+    (".env", "GREETING_API_KEY=demo-only\n"),
+    (
+        "node_modules/left-pad/index.js",
+        "module.exports = (value) => value;\n",
+    ),
+    ("build/output.log", "build ok\n"),
+    ("notes.txt", "Scratch notes that were never committed.\n"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DemoRequest {
+    pub repo_root: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DemoResult {
+    pub repo_root: PathBuf,
+    pub worktrees_dir: PathBuf,
+    pub branches: Vec<String>,
+}
+
+impl<'a> App<'a> {
+    pub fn create_demo(&self, request: DemoRequest) -> Result<DemoResult, Error> {
+        let repo_root = request.repo_root;
+        if repo_root.exists() {
+            return Err(anyhow!("{} already exists", repo_root.display()).into());
+        }
+        fs::create_dir_all(&repo_root)
+            .with_context(|| format!("failed to create {}", repo_root.display()))?;
+
+        let branches = match self.populate_demo(&repo_root) {
+            Ok(branches) => branches,
+            Err(error) => {
+                let _ = fs::remove_dir_all(&repo_root);
+                return Err(error.into());
+            }
+        };
+
+        let worktrees_dir = runtime::worktrees_dir(self, &repo_root)?;
+        Ok(DemoResult {
+            repo_root,
+            worktrees_dir,
+            branches,
+        })
+    }
+
+    fn populate_demo(&self, repo_root: &Path) -> Result<Vec<String>> {
+        seshmux_core::git::init_repo(repo_root, DEMO_DEFAULT_BRANCH, self.runner)
+            .with_context(|| format!("failed to initialize {}", repo_root.display()))?;
+        for (key, value) in [
+            ("user.name", "seshmux demo"),
+            ("user.email", "demo@seshmux.invalid"),
+            ("commit.gpgsign", "false"),
+        ] {
+            seshmux_core::git::set_local_config(repo_root, key, value, self.runner)
+                .with_context(|| format!("failed to set {key} in the demo repository"))?;
+        }
+
+        let (first, rest) = DEMO_HISTORY.split_at(2);
+        for (message, files) in first {
+            self.demo_commit(repo_root, message, files)?;
+        }
+
+        let mut branches = Vec::with_capacity(DEMO_BRANCHES.len());
+        for (branch, _, _) in DEMO_BRANCHES {
+            seshmux_core::git::create_branch(repo_root, branch, self.runner)
+                .with_context(|| format!("failed to create branch '{branch}'"))?;
+        }
+        for (branch, message, files) in DEMO_BRANCHES {
+            self.demo_switch(repo_root, branch)?;
+            self.demo_commit(repo_root, message, files)?;
+            branches.push((*branch).to_string());
+        }
+        self.demo_switch(repo_root, DEMO_DEFAULT_BRANCH)?;
+
+        for (message, files) in rest {
+            self.demo_commit(repo_root, message, files)?;
+        }
+        write_files(repo_root, DEMO_UNTRACKED)?;
+
+        Ok(branches)
+    }
+
+    pub fn remove_demo(&self, demo: &DemoResult) -> Result<(), Error> {
+        if demo.repo_root.exists() {
+            self.kill_all_sessions(&demo.repo_root, SessionScope::Repo)
+                .map_err(anyhow::Error::from)
+                .context("failed to kill the demo tmux sessions")?;
+        }
+
+        // A worktrees_dir template without {repo} is shared with real repositories.
+        let owns_worktrees_dir = demo.worktrees_dir.starts_with(&demo.repo_root)
+            || demo.worktrees_dir.file_name() == demo.repo_root.file_name();
+        if !owns_worktrees_dir && demo.worktrees_dir.exists() {
+            self.notices.borrow_mut().push(format!(
+                "warning: left demo worktrees in shared directory {}",
+                demo.worktrees_dir.display()
+            ));
+        }
+
+        let owned = owns_worktrees_dir.then_some(&demo.worktrees_dir);
+        for path in owned.into_iter().chain([&demo.repo_root]) {
+            if path.exists() {
+                fs::remove_dir_all(path)
+                    .with_context(|| format!("failed to remove {}", path.display()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn demo_commit(&self, repo_root: &Path, message: &str, files: &[(&str, &str)]) -> Result<()> {
+        write_files(repo_root, files)?;
+        seshmux_core::git::commit_all(repo_root, message, self.runner)
+            .with_context(|| format!("failed to commit '{message}' in the demo repository"))
+    }
+
+    fn demo_switch(&self, repo_root: &Path, branch: &str) -> Result<()> {
+        seshmux_core::git::switch_branch(repo_root, branch, self.runner)
+            .with_context(|| format!("failed to check out '{branch}' in the demo repository"))
+    }
+}
+
+fn write_files(repo_root: &Path, files: &[(&str, &str)]) -> Result<()> {
+    for (relative, contents) in files {
+        let path = repo_root.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        fs::write(&path, contents)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
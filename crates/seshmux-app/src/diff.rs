@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use crate::graph::{BranchAndBase, branch_and_base};
+use crate::{App, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchDiffRequest {
+    pub cwd: PathBuf,
+    pub worktree_name: String,
+    pub patch: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchDiff {
+    pub worktree_name: String,
+    pub branch: String,
+    pub base: Option<String>,
+    pub lines: Vec<String>,
+    pub patch: bool,
+}
+
+impl<'a> App<'a> {
+    pub fn branch_diff(&self, request: BranchDiffRequest) -> Result<BranchDiff, Error> {
+        let BranchAndBase {
+            target,
+            branch,
+            base,
+        } = branch_and_base(self, &request.cwd, &request.worktree_name)?;
+
+        let revision = match &base {
+            Some(base) => {
+                seshmux_core::git::merge_base(&target.worktree_path, base, "HEAD", self.runner)
+                    .with_context(|| {
+                        format!("failed to find where '{branch}' forked from '{base}'")
+                    })?
+            }
+            None => "HEAD".to_string(),
+        };
+        let lines = seshmux_core::git::diff_since(
+            &target.worktree_path,
+            &revision,
+            request.patch,
+            self.runner,
+        )
+        .with_context(|| format!("failed to diff '{branch}'"))?;
+
+        Ok(BranchDiff {
+            worktree_name: target.worktree_name,
+            branch,
+            base,
+            lines,
+            patch: request.patch,
+        })
+    }
+}
@@ -0,0 +1,460 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use seshmux_core::command_runner::CommandTimedOut;
+use seshmux_core::config::ConfigError;
+use seshmux_core::config::migrate::MigrationError;
+use seshmux_core::git::GitError;
+use seshmux_core::registry::RegistryError;
+use seshmux_core::tabs::TabError;
+use seshmux_core::tmux::TmuxError;
+use seshmux_core::vcs::VcsError;
+
+use crate::{AttachError, DeleteError, ForeignWorktree, NewError, OpenError};
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    #[error(transparent)]
+    Config(anyhow::Error),
+    #[error(transparent)]
+    Git(anyhow::Error),
+    #[error(transparent)]
+    Tmux(anyhow::Error),
+    #[error(transparent)]
+    Registry(anyhow::Error),
+    #[error(transparent)]
+    Io(anyhow::Error),
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+impl Error {
+    pub fn inner(&self) -> &anyhow::Error {
+        match self {
+            Self::Config(error)
+            | Self::Git(error)
+            | Self::Tmux(error)
+            | Self::Registry(error)
+            | Self::Io(error)
+            | Self::Other(error) => error,
+        }
+    }
+
+    pub fn into_inner(self) -> anyhow::Error {
+        match self {
+            Self::Config(error)
+            | Self::Git(error)
+            | Self::Tmux(error)
+            | Self::Registry(error)
+            | Self::Io(error)
+            | Self::Other(error) => error,
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        classify_error(self.inner())
+    }
+
+    pub fn downcast_ref<E>(&self) -> Option<&E>
+    where
+        E: fmt::Display + fmt::Debug + Send + Sync + 'static,
+    {
+        self.inner().downcast_ref()
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Config(_) => 78,
+            Self::Git(_) => 65,
+            Self::Tmux(_) => 69,
+            Self::Registry(_) => 76,
+            Self::Io(_) => 74,
+            Self::Other(_) => 1,
+        }
+    }
+
+    fn variant(&self) -> fn(anyhow::Error) -> Error {
+        match self {
+            Self::Config(_) => Self::Config,
+            Self::Git(_) => Self::Git,
+            Self::Tmux(_) => Self::Tmux,
+            Self::Registry(_) => Self::Registry,
+            Self::Io(_) => Self::Io,
+            Self::Other(_) => Self::Other,
+        }
+    }
+}
+
+impl From<anyhow::Error> for Error {
+    fn from(error: anyhow::Error) -> Self {
+        let variant = error.chain().find_map(category).unwrap_or(Self::Other);
+        variant(error)
+    }
+}
+
+macro_rules! from_app_error {
+    ($($error:ty),*) => {
+        $(impl From<$error> for Error {
+            fn from(error: $error) -> Self {
+                anyhow::Error::from(error).into()
+            }
+        })*
+    };
+}
+
+from_app_error!(
+    AttachError,
+    DeleteError,
+    ForeignWorktree,
+    NewError,
+    OpenError,
+    RepoError,
+    ConfigNotReady
+);
+
+/// The typed error `E` behind `error`, also when an App method's `Error` was wrapped into anyhow
+/// on the way, which hides the outermost cause from a plain `downcast_ref`.
+pub fn find_cause<E>(error: &anyhow::Error) -> Option<&E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    error.chain().find_map(|cause| {
+        cause
+            .downcast_ref::<E>()
+            .or_else(|| cause.downcast_ref::<Error>()?.downcast_ref::<E>())
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ConfigNotReady {
+    #[error("{0}")]
+    Global(String),
+    #[error("{0}")]
+    Repo(String),
+}
+
+fn category(cause: &(dyn std::error::Error + 'static)) -> Option<fn(anyhow::Error) -> Error> {
+    if let Some(error) = cause.downcast_ref::<Error>() {
+        Some(error.variant())
+    } else if cause.is::<ConfigError>()
+        || cause.is::<MigrationError>()
+        || cause.is::<ConfigNotReady>()
+    {
+        Some(Error::Config)
+    } else if cause.is::<RegistryError>() {
+        Some(Error::Registry)
+    } else if cause.is::<GitError>() || cause.is::<VcsError>() || cause.is::<RepoError>() {
+        Some(Error::Git)
+    } else if cause.is::<TmuxError>() || cause.is::<TabError>() {
+        Some(Error::Tmux)
+    } else if cause.is::<std::io::Error>() {
+        Some(Error::Io)
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum RepoError {
+    #[error(
+        "current branch/HEAD has no commits yet; create an initial commit on this branch before starting seshmux"
+    )]
+    NoCommits,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    BranchExists {
+        branch: String,
+    },
+    BranchCheckedOut {
+        branch: String,
+        worktree_path: PathBuf,
+    },
+    NoCommits,
+    UnknownWorktree {
+        name: String,
+    },
+    MissingSession {
+        worktree_name: String,
+    },
+    NoEditor,
+    ForeignWorktree {
+        name: String,
+        owner: String,
+    },
+    InsufficientSpace {
+        required: u64,
+        available: u64,
+    },
+    InvalidConfig {
+        repo: bool,
+    },
+    CommandTimedOut {
+        command: String,
+        timeout_secs: u64,
+    },
+    Other,
+}
+
+pub fn classify_error(error: &anyhow::Error) -> ErrorKind {
+    error
+        .chain()
+        .find_map(classify_cause)
+        .unwrap_or(ErrorKind::Other)
+}
+
+fn classify_cause(cause: &(dyn std::error::Error + 'static)) -> Option<ErrorKind> {
+    if let Some(error) = cause.downcast_ref::<Error>() {
+        return Some(error.kind()).filter(|kind| *kind != ErrorKind::Other);
+    }
+    if let Some(error) = cause.downcast_ref::<NewError>() {
+        return match error {
+            NewError::BranchCheckedOut {
+                branch,
+                worktree_path,
+                ..
+            } => Some(ErrorKind::BranchCheckedOut {
+                branch: branch.clone(),
+                worktree_path: worktree_path.clone(),
+            }),
+            NewError::InsufficientSpace {
+                required,
+                available,
+                ..
+            } => Some(ErrorKind::InsufficientSpace {
+                required: *required,
+                available: *available,
+            }),
+            NewError::PathTaken { .. } | NewError::Canceled => None,
+        };
+    }
+    if let Some(error) = cause.downcast_ref::<ConfigNotReady>() {
+        return Some(ErrorKind::InvalidConfig {
+            repo: matches!(error, ConfigNotReady::Repo(_)),
+        });
+    }
+    if cause.is::<ConfigError>() || cause.is::<MigrationError>() {
+        return Some(ErrorKind::InvalidConfig { repo: false });
+    }
+    if let Some(RepoError::NoCommits) = cause.downcast_ref::<RepoError>() {
+        return Some(ErrorKind::NoCommits);
+    }
+    if let Some(error) = cause.downcast_ref::<AttachError>() {
+        return match error {
+            AttachError::UnknownWorktree { name } => {
+                Some(ErrorKind::UnknownWorktree { name: name.clone() })
+            }
+            AttachError::MissingSession { worktree_name, .. }
+            | AttachError::SessionGone { worktree_name, .. } => Some(ErrorKind::MissingSession {
+                worktree_name: worktree_name.clone(),
+            }),
+            _ => None,
+        };
+    }
+    if let Some(DeleteError::UnknownWorktree { name }) = cause.downcast_ref::<DeleteError>() {
+        return Some(ErrorKind::UnknownWorktree { name: name.clone() });
+    }
+    if let Some(ForeignWorktree { name, owner }) = cause.downcast_ref::<ForeignWorktree>() {
+        return Some(ErrorKind::ForeignWorktree {
+            name: name.clone(),
+            owner: owner.clone(),
+        });
+    }
+    if let Some(error) = cause.downcast_ref::<OpenError>() {
+        return Some(match error {
+            OpenError::UnknownWorktree { name } => {
+                ErrorKind::UnknownWorktree { name: name.clone() }
+            }
+            OpenError::NoEditor => ErrorKind::NoEditor,
+        });
+    }
+
+    if let Some(TmuxError::TimedOut(timeout)) = cause.downcast_ref::<TmuxError>() {
+        return Some(timed_out(timeout));
+    }
+
+    match cause.downcast_ref::<GitError>()? {
+        GitError::NoCommits => Some(ErrorKind::NoCommits),
+        GitError::CommandFailed { stderr, .. } => {
+            existing_branch(stderr).map(|branch| ErrorKind::BranchExists { branch })
+        }
+        GitError::TimedOut(timeout) => Some(timed_out(timeout)),
+        _ => None,
+    }
+}
+
+fn timed_out(timeout: &CommandTimedOut) -> ErrorKind {
+    ErrorKind::CommandTimedOut {
+        command: timeout.command.clone(),
+        timeout_secs: timeout.timeout.as_secs(),
+    }
+}
+
+fn existing_branch(stderr: &str) -> Option<String> {
+    let rest = stderr.split("a branch named '").nth(1)?;
+    let (branch, tail) = rest.split_once('\'')?;
+    tail.trim_start()
+        .starts_with("already exists")
+        .then(|| branch.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use anyhow::Context;
+    use seshmux_core::command_runner::CommandTimedOut;
+    use seshmux_core::git::GitError;
+
+    use seshmux_core::registry::RegistryError;
+    use seshmux_core::tmux::TmuxError;
+
+    use super::{
+        ConfigNotReady, Error, ErrorKind, RepoError, classify_error, existing_branch, find_cause,
+    };
+    use crate::{ForeignWorktree, NewError};
+
+    #[test]
+    fn classify_error_looks_through_context() {
+        let error = Err::<(), _>(GitError::CommandFailed {
+            command: "worktree add -b feature /tmp/w main".to_string(),
+            status: 255,
+            stderr: "fatal: a branch named 'feature' already exists".to_string(),
+        })
+        .context("failed to create worktree 'feature'")
+        .unwrap_err();
+        assert_eq!(
+            classify_error(&error),
+            ErrorKind::BranchExists {
+                branch: "feature".to_string()
+            }
+        );
+
+        let error = anyhow::Error::from(RepoError::NoCommits).context("opening new flow");
+        assert_eq!(classify_error(&error), ErrorKind::NoCommits);
+
+        let error = anyhow::Error::from(NewError::BranchCheckedOut {
+            branch: "feature".to_string(),
+            worktree_path: PathBuf::from("/tmp/other"),
+            worktree_name: Some("other".to_string()),
+        });
+        assert!(
+            error
+                .to_string()
+                .contains("checked out in worktree 'other' at /tmp/other (seshmux attach other)")
+        );
+        assert_eq!(
+            classify_error(&error),
+            ErrorKind::BranchCheckedOut {
+                branch: "feature".to_string(),
+                worktree_path: PathBuf::from("/tmp/other"),
+            }
+        );
+
+        let error = anyhow::Error::from(NewError::InsufficientSpace {
+            path: PathBuf::from("/tmp/repo/worktrees"),
+            required: 2_048,
+            available: 1_024,
+        })
+        .context("failed to create worktree 'feature'");
+        assert_eq!(
+            classify_error(&error),
+            ErrorKind::InsufficientSpace {
+                required: 2_048,
+                available: 1_024,
+            }
+        );
+
+        let error = Err::<(), _>(GitError::TimedOut(CommandTimedOut {
+            command: "git fetch origin".to_string(),
+            timeout: Duration::from_secs(30),
+        }))
+        .context("failed to fetch")
+        .unwrap_err();
+        assert!(format!("{error:#}").contains("did not finish within 30s and was killed"));
+        assert_eq!(
+            classify_error(&error),
+            ErrorKind::CommandTimedOut {
+                command: "git fetch origin".to_string(),
+                timeout_secs: 30,
+            }
+        );
+
+        assert_eq!(
+            classify_error(&anyhow::anyhow!("disk full")),
+            ErrorKind::Other
+        );
+    }
+
+    #[test]
+    fn error_variant_follows_the_outermost_typed_cause() {
+        let registry = anyhow::Error::from(RegistryError::Read {
+            path: PathBuf::from("/tmp/repo/worktrees/worktree.toml"),
+            source: std::io::Error::other("denied"),
+        })
+        .context("failed to load worktrees");
+        let error = Error::from(registry);
+        assert!(matches!(error, Error::Registry(_)));
+        assert_eq!(error.exit_code(), 76);
+        assert!(error.to_string().starts_with("failed to load worktrees"));
+
+        let tmux = Err::<(), _>(TmuxError::Execute("no server".to_string()))
+            .context("failed to list tmux sessions")
+            .unwrap_err();
+        assert!(matches!(Error::from(tmux), Error::Tmux(_)));
+
+        let config = Error::from(ConfigNotReady::Repo("invalid repo config".to_string()));
+        assert!(matches!(config, Error::Config(_)));
+        assert_eq!(config.kind(), ErrorKind::InvalidConfig { repo: true });
+        assert_eq!(config.exit_code(), 78);
+
+        let other = Error::from(anyhow::anyhow!("no command given to run"));
+        assert!(matches!(other, Error::Other(_)));
+        assert_eq!(other.exit_code(), 1);
+    }
+
+    #[test]
+    fn error_keeps_the_original_chain_through_anyhow() {
+        let error = Error::from(
+            anyhow::Error::from(RepoError::NoCommits).context("failed to open the new flow"),
+        );
+        assert!(matches!(error, Error::Git(_)));
+        assert!(error.downcast_ref::<RepoError>().is_some());
+
+        let error = anyhow::Error::from(error).context("starting seshmux");
+        assert_eq!(
+            format!("{error:#}"),
+            format!(
+                "starting seshmux: failed to open the new flow: {}",
+                RepoError::NoCommits
+            )
+        );
+        assert_eq!(classify_error(&error), ErrorKind::NoCommits);
+        assert!(find_cause::<RepoError>(&error).is_some());
+        assert!(matches!(Error::from(error), Error::Git(_)));
+
+        let foreign = anyhow::Error::from(Error::from(ForeignWorktree {
+            name: "w1".to_string(),
+            owner: "alice".to_string(),
+        }));
+        assert!(foreign.downcast_ref::<ForeignWorktree>().is_none());
+        assert_eq!(
+            find_cause::<ForeignWorktree>(&foreign).map(|foreign| foreign.owner.as_str()),
+            Some("alice")
+        );
+    }
+
+    #[test]
+    fn existing_branch_requires_the_git_wording() {
+        assert_eq!(
+            existing_branch("fatal: a branch named 'fix/typo' already exists\n").as_deref(),
+            Some("fix/typo")
+        );
+        assert_eq!(existing_branch("fatal: '/tmp/w' already exists"), None);
+    }
+}
@@ -0,0 +1,87 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::target::{self, ResolvedTarget};
+use crate::{App, Error};
+
+const GRAPH_COMMIT_LIMIT: usize = 30;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchGraphRequest {
+    pub cwd: PathBuf,
+    pub worktree_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchGraph {
+    pub worktree_name: String,
+    pub branch: String,
+    pub base: Option<String>,
+    pub lines: Vec<String>,
+}
+
+pub(crate) struct BranchAndBase {
+    pub(crate) target: ResolvedTarget,
+    pub(crate) branch: String,
+    pub(crate) base: Option<String>,
+}
+
+pub(crate) fn branch_and_base(
+    app: &App<'_>,
+    cwd: &Path,
+    worktree_name: &str,
+) -> Result<BranchAndBase> {
+    let target = target::resolve_target(app, cwd, worktree_name)?
+        .ok_or_else(|| anyhow!("worktree '{worktree_name}' was not found in worktree.toml"))?;
+    if !target.worktree_path.exists() {
+        bail!(
+            "worktree path does not exist on disk: {}",
+            target.worktree_path.display()
+        );
+    }
+
+    let branch = seshmux_core::git::current_branch(&target.worktree_path, app.runner)
+        .with_context(|| {
+            format!(
+                "failed to read the branch of {}",
+                target.worktree_path.display()
+            )
+        })?;
+    let base = seshmux_core::git::default_branch(&target.repo_root, app.runner)
+        .context("failed to resolve the default branch")?
+        .filter(|base| *base != branch);
+
+    Ok(BranchAndBase {
+        target,
+        branch,
+        base,
+    })
+}
+
+impl<'a> App<'a> {
+    pub fn branch_graph(&self, request: BranchGraphRequest) -> Result<BranchGraph, Error> {
+        let BranchAndBase {
+            target,
+            branch,
+            base,
+        } = branch_and_base(self, &request.cwd, &request.worktree_name)?;
+
+        let mut revisions = vec!["HEAD"];
+        revisions.extend(base.as_deref());
+        let lines = seshmux_core::git::log_graph(
+            &target.worktree_path,
+            &revisions,
+            GRAPH_COMMIT_LIMIT,
+            self.runner,
+        )
+        .with_context(|| format!("failed to read the commit graph for '{branch}'"))?;
+
+        Ok(BranchGraph {
+            worktree_name: target.worktree_name,
+            branch,
+            base,
+            lines,
+        })
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+
+use crate::adopt::{
+    FoundWorktree, register_found_worktree, registered_names, unregistered_worktree_paths,
+};
+use crate::catalog::WorktreeCatalog;
+use crate::runtime;
+use crate::{App, Error};
+
+const CONTAINER_DIRS: [&str; 4] = [".worktrees", "worktrees", ".trees", "trees"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportLayout {
+    WorktreesDir,
+    Sibling,
+    Container,
+    Other,
+}
+
+impl ImportLayout {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::WorktreesDir => "worktrees dir",
+            Self::Sibling => "sibling",
+            Self::Container => "container",
+            Self::Other => "other",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportCandidate {
+    pub worktree_name: String,
+    pub worktree_path: PathBuf,
+    pub layout: ImportLayout,
+    pub created_at: String,
+    pub session_name: Option<String>,
+    pub rename_to: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportScan {
+    pub repo_root: PathBuf,
+    pub candidates: Vec<ImportCandidate>,
+    pub skipped: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportRequest {
+    pub cwd: PathBuf,
+    pub worktree_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportResult {
+    pub repo_root: PathBuf,
+    pub imported: Vec<ImportCandidate>,
+}
+
+impl<'a> App<'a> {
+    pub fn import_scan(&self, cwd: &Path) -> Result<ImportScan, Error> {
+        let catalog = WorktreeCatalog::load(self, cwd)?;
+        let repo_root = catalog.repo_root().to_path_buf();
+        let repo_name = runtime::repo_component(&repo_root);
+
+        let registered_names = registered_names(&catalog);
+        let repo_key = runtime::comparable_path(&repo_root);
+        let worktrees_dir = runtime::comparable_path(catalog.worktrees_dir());
+        let worktree_paths = unregistered_worktree_paths(self, &catalog)?;
+        if worktree_paths.is_empty() {
+            return Ok(ImportScan {
+                repo_root,
+                candidates: Vec::new(),
+                skipped: Vec::new(),
+            });
+        }
+
+        let sessions = if runtime::session_backend(self)?.is_tmux() {
+            seshmux_core::tmux::list_sessions_with_paths(&runtime::tmux_server(self)?, self.runner)
+                .context("failed to list tmux sessions")?
+        } else {
+            Vec::new()
+        };
+        let session_names: BTreeSet<&str> = sessions
+            .iter()
+            .map(|session| session.name.as_str())
+            .collect();
+
+        let mut candidates = Vec::<ImportCandidate>::new();
+        let mut skipped = Vec::new();
+        for worktree_path in worktree_paths {
+            let layout = detect_layout(&worktree_path, &repo_key, &worktrees_dir, repo_name);
+            let name = derive_worktree_name(&worktree_path, layout, repo_name);
+            let usable = name.as_deref().is_some_and(|name| {
+                !registered_names.contains(name)
+                    && !candidates.iter().any(|other| other.worktree_name == name)
+            });
+            let (Some(worktree_name), true) = (name, usable) else {
+                skipped.push(worktree_path);
+                continue;
+            };
+
+            let target_session = runtime::session_name_for(&repo_root, &worktree_name);
+            let session_name = sessions
+                .iter()
+                .find(|session| runtime::comparable_path(&session.path) == worktree_path)
+                .map(|session| session.name.clone())
+                .filter(|name| {
+                    *name != target_session && !session_names.contains(target_session.as_str())
+                });
+
+            candidates.push(ImportCandidate {
+                worktree_name,
+                created_at: modified_at(&worktree_path)?,
+                worktree_path,
+                layout,
+                rename_to: session_name.as_ref().map(|_| target_session),
+                session_name,
+            });
+        }
+
+        Ok(ImportScan {
+            repo_root,
+            candidates,
+            skipped,
+        })
+    }
+
+    pub fn import_worktrees(&self, request: ImportRequest) -> Result<ImportResult, Error> {
+        let scan = self.import_scan(&request.cwd)?;
+        let worktrees_dir = runtime::worktrees_dir(self, &scan.repo_root)?;
+
+        let mut selected = Vec::new();
+        for name in &request.worktree_names {
+            let candidate = scan
+                .candidates
+                .iter()
+                .find(|candidate| &candidate.worktree_name == name)
+                .ok_or_else(|| anyhow!("no unregistered git worktree found for '{name}'"))?;
+            if !selected.contains(candidate) {
+                selected.push(candidate.clone());
+            }
+        }
+        if selected.is_empty() {
+            return Err(anyhow!("no worktrees selected to import").into());
+        }
+
+        for candidate in &selected {
+            register_found_worktree(
+                self,
+                &worktrees_dir,
+                FoundWorktree {
+                    worktree_name: &candidate.worktree_name,
+                    worktree_path: &candidate.worktree_path,
+                    created_at: candidate.created_at.clone(),
+                    session_name: candidate.session_name.as_deref(),
+                    rename_to: candidate.rename_to.as_deref(),
+                },
+            )?;
+        }
+
+        Ok(ImportResult {
+            repo_root: scan.repo_root,
+            imported: selected,
+        })
+    }
+}
+
+fn detect_layout(
+    worktree_path: &Path,
+    repo_root: &Path,
+    worktrees_dir: &Path,
+    repo_name: &str,
+) -> ImportLayout {
+    let Some(parent) = worktree_path.parent() else {
+        return ImportLayout::Other;
+    };
+    if parent == worktrees_dir {
+        return ImportLayout::WorktreesDir;
+    }
+    if Some(parent) == repo_root.parent() {
+        return ImportLayout::Sibling;
+    }
+
+    let parent_name = parent
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let container = CONTAINER_DIRS.contains(&parent_name)
+        || ["-worktrees", ".worktrees", "_worktrees"]
+            .iter()
+            .any(|suffix| parent_name == format!("{repo_name}{suffix}"));
+    if container {
+        ImportLayout::Container
+    } else {
+        ImportLayout::Other
+    }
+}
+
+fn derive_worktree_name(
+    worktree_path: &Path,
+    layout: ImportLayout,
+    repo_name: &str,
+) -> Option<String> {
+    let directory = worktree_path.file_name()?.to_str()?;
+    let stripped = match layout {
+        ImportLayout::Sibling => ['-', '_', '.']
+            .iter()
+            .find_map(|separator| {
+                directory
+                    .strip_prefix(repo_name)
+                    .and_then(|rest| rest.strip_prefix(*separator))
+                    .filter(|rest| !rest.is_empty())
+            })
+            .unwrap_or(directory),
+        _ => directory,
+    };
+
+    let name = seshmux_core::names::sanitize_repo_component(stripped);
+    seshmux_core::names::validate_worktree_name(&name)
+        .is_ok()
+        .then_some(name)
+}
+
+fn modified_at(path: &Path) -> Result<String> {
+    let modified = std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .with_context(|| format!("failed to read metadata of {}", path.display()))?;
+    seshmux_core::time::system_time_rfc3339(modified)
+        .map_err(|error| anyhow!("failed to format timestamp: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{ImportLayout, derive_worktree_name, detect_layout};
+
+    #[test]
+    fn layouts_are_detected_from_directory_conventions() {
+        let repo_root = Path::new("/src/widgets");
+        let worktrees_dir = Path::new("/src/widgets/worktrees");
+        let layout =
+            |path: &str| detect_layout(Path::new(path), repo_root, worktrees_dir, "widgets");
+
+        assert_eq!(
+            layout("/src/widgets/worktrees/a"),
+            ImportLayout::WorktreesDir
+        );
+        assert_eq!(layout("/src/widgets-a"), ImportLayout::Sibling);
+        assert_eq!(layout("/src/widgets.worktrees/a"), ImportLayout::Container);
+        assert_eq!(layout("/src/widgets/.worktrees/a"), ImportLayout::Container);
+        assert_eq!(layout("/tmp/scratch/a"), ImportLayout::Other);
+    }
+
+    #[test]
+    fn names_drop_the_repo_prefix_of_sibling_checkouts() {
+        let name = |path: &str, layout| derive_worktree_name(Path::new(path), layout, "widgets");
+
+        assert_eq!(
+            name("/src/widgets-Fix_Login", ImportLayout::Sibling).as_deref(),
+            Some("fix_login")
+        );
+        assert_eq!(
+            name("/src/widgets.worktrees/feature", ImportLayout::Container).as_deref(),
+            Some("feature")
+        );
+        assert_eq!(name("/src/widgets-_tmp", ImportLayout::Sibling), None);
+    }
+}
@@ -0,0 +1,106 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, anyhow};
+
+use crate::runtime;
+use crate::{App, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitRequest {
+    pub cwd: PathBuf,
+    pub url: String,
+    pub directory: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitResult {
+    pub repo_root: PathBuf,
+    pub git_dir: PathBuf,
+}
+
+impl<'a> App<'a> {
+    pub fn init(&self, request: InitRequest) -> Result<InitResult, Error> {
+        let directory = match request.directory {
+            Some(directory) => directory,
+            None => match directory_name_from_url(&request.url) {
+                Some(name) => PathBuf::from(name),
+                None => {
+                    return Err(anyhow!(
+                        "could not derive a directory name from '{}'; pass one explicitly",
+                        request.url
+                    )
+                    .into());
+                }
+            },
+        };
+
+        let repo_root = request.cwd.join(directory);
+        let Some(file_name) = repo_root.file_name() else {
+            return Err(anyhow!("invalid clone destination {}", repo_root.display()).into());
+        };
+        let git_dir =
+            repo_root.with_file_name(format!("{}.git", file_name.to_string_lossy().as_ref()));
+
+        for path in [&repo_root, &git_dir] {
+            if path.exists() {
+                return Err(anyhow!("{} already exists", path.display()).into());
+            }
+        }
+
+        seshmux_core::git::clone_with_separate_git_dir(
+            &request.url,
+            &repo_root,
+            &git_dir,
+            self.runner,
+        )
+        .with_context(|| format!("failed to clone {}", request.url))?;
+
+        let worktrees_dir = runtime::worktrees_dir(self, &repo_root)?;
+        seshmux_core::registry::save_always_skip_buckets(
+            &worktrees_dir,
+            &seshmux_core::registry::default_always_skip_buckets(),
+        )
+        .with_context(|| {
+            format!(
+                "failed to write initial worktree registry in {}",
+                worktrees_dir.display()
+            )
+        })?;
+
+        Ok(InitResult { repo_root, git_dir })
+    }
+}
+
+fn directory_name_from_url(url: &str) -> Option<String> {
+    let trimmed = url.trim().trim_end_matches('/');
+    let last = trimmed.rsplit(['/', ':']).next()?;
+    let name = last.strip_suffix(".git").unwrap_or(last);
+
+    if name.is_empty() || name == "." || name == ".." {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::directory_name_from_url;
+
+    #[test]
+    fn directory_name_is_derived_from_common_url_shapes() {
+        assert_eq!(
+            directory_name_from_url("https://github.com/acme/widgets.git").as_deref(),
+            Some("widgets")
+        );
+        assert_eq!(
+            directory_name_from_url("git@github.com:acme/widgets.git").as_deref(),
+            Some("widgets")
+        );
+        assert_eq!(
+            directory_name_from_url("/srv/git/widgets/").as_deref(),
+            Some("widgets")
+        );
+        assert_eq!(directory_name_from_url("https://example.com/.git"), None);
+    }
+}
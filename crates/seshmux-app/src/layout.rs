@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, anyhow};
+use seshmux_core::config::resolve_config_path;
+use seshmux_core::tmux::SessionHealth;
+
+use crate::target;
+use crate::{App, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutCaptureRequest {
+    pub cwd: PathBuf,
+    pub worktree_name: String,
+    pub layout_name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutCaptureResult {
+    pub layout_name: String,
+    pub session_name: String,
+    pub config_path: PathBuf,
+    pub windows: usize,
+    pub panes: usize,
+}
+
+impl<'a> App<'a> {
+    pub fn capture_layout(
+        &self,
+        request: LayoutCaptureRequest,
+    ) -> Result<LayoutCaptureResult, Error> {
+        let config_path = resolve_config_path().context("failed to resolve config path")?;
+        self.ensure_config_ready()?;
+
+        let target = target::resolve_target(self, &request.cwd, &request.worktree_name)?
+            .ok_or_else(|| {
+                anyhow!(
+                    "worktree '{}' was not found in worktree.toml",
+                    request.worktree_name
+                )
+            })?;
+        let session_name = target.session.label();
+        let health = target
+            .session
+            .health(self.runner)
+            .with_context(|| format!("failed to query tmux session '{session_name}'"))?;
+        if health != SessionHealth::Running {
+            return Err(anyhow!(
+                "tmux session '{session_name}' is not running; attach to it before capturing its layout"
+            ).into());
+        }
+
+        let layout = seshmux_core::layout::capture_layout(
+            &target.session,
+            &target.worktree_path,
+            self.runner,
+        )
+        .with_context(|| format!("failed to read the layout of tmux session '{session_name}'"))?;
+        if layout.windows.is_empty() {
+            return Err(anyhow!("tmux session '{session_name}' has no windows to capture").into());
+        }
+
+        let layout_name = request
+            .layout_name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| target.worktree_name.clone());
+        seshmux_core::layout::append_layout(&config_path, &layout_name, &layout).with_context(
+            || {
+                format!(
+                    "failed to save layout '{layout_name}' to {}",
+                    config_path.display()
+                )
+            },
+        )?;
+
+        Ok(LayoutCaptureResult {
+            layout_name,
+            session_name,
+            config_path,
+            windows: layout.windows.len(),
+            panes: layout.windows.iter().map(|window| window.panes.len()).sum(),
+        })
+    }
+}
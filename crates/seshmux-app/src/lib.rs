@@ -1,69 +1,194 @@
+mod adopt;
+mod archive;
 mod attach;
+mod bootstrap;
 mod catalog;
+mod config;
+mod dash;
 mod delete;
+mod demo;
+mod diff;
+mod errors;
+mod graph;
+mod import;
+mod init;
+mod layout;
 mod list;
+mod menu;
 mod new;
+mod new_spec;
+mod open;
+mod palette;
+mod review;
+mod run;
 mod runtime;
+mod search;
+mod sessions;
+mod skip_rules;
 mod target;
+mod trash;
+mod update;
 
+pub use adopt::{AdoptCandidate, AdoptRequest, AdoptResult, AdoptScan};
+pub use archive::{ArchiveRequest, ArchiveResult, ArchivedWorktree, RestoreResult};
 pub use attach::{AttachError, AttachRequest, AttachResult};
+pub use bootstrap::{BootstrapRequest, BootstrapResult, BootstrapSession, BootstrapState};
+pub use config::{
+    ConfigCheckRequest, ConfigCheckResult, ConfigEjectRequest, ConfigEjectResult, ConfigInitRequest,
+};
+pub use dash::{DashRepo, DashResult};
 pub use delete::{DeleteError, DeleteRequest, DeleteResult};
-pub use list::{ListResult, WorktreeRow};
-pub use new::{NewPrepare, NewRequest, NewResult, NewStartPoint};
+pub use demo::{DemoRequest, DemoResult};
+pub use diff::{BranchDiff, BranchDiffRequest};
+pub use errors::{Error, ErrorKind, RepoError, classify_error, find_cause};
+pub use graph::{BranchGraph, BranchGraphRequest};
+pub use import::{ImportCandidate, ImportLayout, ImportRequest, ImportResult, ImportScan};
+pub use init::{InitRequest, InitResult};
+pub use layout::{LayoutCaptureRequest, LayoutCaptureResult};
+pub use list::{
+    ListResult, SessionFilter, WorktreeHead, WorktreeOrder, WorktreeRow, WorktreeSortKey,
+};
+pub use menu::TmuxMenu;
+pub use new::{
+    BranchConflict, CarriedChanges, NewError, NewPhase, NewPrepare, NewProgress, NewRequest,
+    NewResult, NewStartPoint, ProvisionLog, ProvisionOutput, ProvisionSummary,
+};
+pub use new_spec::{NewBatch, NewBatchItem, NewBatchOutcome, NewSpec};
+pub use open::{OpenError, OpenLocation, OpenRequest, OpenResult, login_shell};
+pub use review::{ReviewCleanResult, ReviewMode, ReviewRequest, ReviewResult, ReviewWorktree};
+pub use run::{RunRequest, RunResult};
+pub use search::{SearchGroup, SearchRequest, SearchResult};
+pub use sessions::{KillAllSessionsResult, KillSessionResult, ManagedSessions, SessionScope};
+pub use skip_rules::{SkipRule, SkipRuleSource, SkipRules};
+pub use target::ForeignWorktree;
+pub use trash::TrashRestoreResult;
+pub use update::{UpdateOutcome, UpdateRequest, UpdateResult};
 
+use std::cell::RefCell;
 use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result, anyhow, bail};
+use anyhow::{Context, Result};
 use seshmux_core::command_runner::CommandRunner;
-use seshmux_core::config::{SeshmuxConfig, load_config, resolve_config_path};
-use seshmux_core::doctor::{DoctorReport, run_doctor_with_runner};
+use seshmux_core::config::migrate::migrate_config_file;
+use seshmux_core::config::{
+    ConfigError, LoadOptions, SeshmuxConfig, load_config, load_config_fields, resolve_config_path,
+};
+use seshmux_core::doctor::{DoctorReport, repository_checks, run_doctor_with_runner};
+
+use crate::errors::ConfigNotReady;
 
 pub struct App<'a> {
     pub runner: &'a dyn CommandRunner,
+    load_options: LoadOptions,
+    provision_log: Option<ProvisionLog>,
+    notices: RefCell<Vec<String>>,
 }
 
 impl<'a> App<'a> {
     pub fn new(runner: &'a dyn CommandRunner) -> Self {
-        Self { runner }
+        Self {
+            runner,
+            load_options: LoadOptions::resolve(None),
+            provision_log: None,
+            notices: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn with_load_options(mut self, load_options: LoadOptions) -> Self {
+        self.load_options = load_options;
+        self
+    }
+
+    pub fn load_options(&self) -> &LoadOptions {
+        &self.load_options
     }
 
-    pub fn doctor(&self) -> Result<DoctorReport> {
-        Ok(run_doctor_with_runner(self.runner))
+    pub fn with_provision_log(mut self, provision_log: ProvisionLog) -> Self {
+        self.provision_log = Some(provision_log);
+        self
     }
 
-    pub fn ensure_config_ready(&self) -> Result<SeshmuxConfig> {
+    pub fn provision_log(&self) -> Option<&ProvisionLog> {
+        self.provision_log.as_ref()
+    }
+
+    pub fn take_notices(&self) -> Vec<String> {
+        self.notices.take()
+    }
+
+    pub fn doctor(&self, cwd: &Path) -> Result<DoctorReport, Error> {
+        let mut report = run_doctor_with_runner(self.runner, &self.load_options);
+        report.checks.extend(repository_checks(cwd, self.runner));
+        Ok(report)
+    }
+
+    pub fn ensure_config_ready(&self) -> Result<SeshmuxConfig, Error> {
+        self.ready_config(|path| load_config(path, &self.load_options))
+    }
+
+    pub(crate) fn ensure_config_fields(&self, fields: &[&str]) -> Result<SeshmuxConfig, Error> {
+        self.ready_config(|path| load_config_fields(path, fields, &self.load_options))
+    }
+
+    fn ready_config(
+        &self,
+        load: impl FnOnce(&Path) -> Result<SeshmuxConfig, ConfigError>,
+    ) -> Result<SeshmuxConfig, Error> {
         let config_path = resolve_config_path().context("failed to resolve config path")?;
 
         if !config_path.exists() {
-            bail!(
-                "missing config at {}\nCreate ~/.config/seshmux/config.toml and see README.md for setup instructions.",
+            return Err(ConfigNotReady::Global(format!(
+                "missing config at {}\nRun `seshmux init-config --interactive` to create it, or see README.md for setup instructions.",
                 config_path.display()
-            );
+            ))
+            .into());
         }
 
-        load_config(&config_path).map_err(|error| {
-            anyhow!(
+        let migration = migrate_config_file(&config_path).map_err(|error| {
+            ConfigNotReady::Global(format!(
+                "failed to migrate config at {}: {error}\nFix the config and retry. See README.md for setup instructions.",
+                config_path.display()
+            ))
+        })?;
+        if let Some(migration) = migration {
+            self.notices.borrow_mut().push(format!(
+                "config migrated from version {} to {} ({}); original saved to {}",
+                migration.from_version,
+                migration.to_version,
+                migration.path.display(),
+                migration.backup_path.display()
+            ));
+        }
+
+        let config = load(&config_path).map_err(|error| {
+            ConfigNotReady::Global(format!(
                 "invalid config at {}: {error}\nFix the config and retry. See README.md for setup instructions.",
                 config_path.display()
-            )
-        })
+            ))
+        })?;
+        Ok(config)
     }
 
-    pub fn ensure_runtime_repo_ready(&self, cwd: &Path) -> Result<PathBuf> {
+    pub fn ensure_runtime_repo_ready(&self, cwd: &Path) -> Result<PathBuf, Error> {
         let repo_root = runtime::resolve_repo_root(self, cwd)?;
 
-        let commits = seshmux_core::git::query_commits(&repo_root, "", 1, self.runner)
-            .with_context(|| {
-                format!(
-                    "failed to inspect commit history in {}",
-                    repo_root.display()
-                )
-            })?;
+        let commits = seshmux_core::git::query_commits(
+            &repo_root,
+            "",
+            seshmux_core::git::CommitSearch::Hash,
+            0,
+            1,
+            self.runner,
+        )
+        .with_context(|| {
+            format!(
+                "failed to inspect commit history in {}",
+                repo_root.display()
+            )
+        })?;
 
         if commits.is_empty() {
-            bail!(
-                "current branch/HEAD has no commits yet; create an initial commit on this branch before starting seshmux"
-            );
+            return Err(RepoError::NoCommits.into());
         }
 
         Ok(repo_root)
@@ -1,28 +1,190 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result, anyhow};
+
+use seshmux_core::registry::DiskUsage;
+use seshmux_core::tmux::SessionStatus;
 
-use crate::App;
 use crate::catalog::WorktreeCatalog;
+use crate::runtime;
+use crate::{App, Error};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct ListResult {
     pub repo_root: PathBuf,
     pub rows: Vec<WorktreeRow>,
 }
 
+impl ListResult {
+    pub fn new(repo_root: impl Into<PathBuf>, rows: Vec<WorktreeRow>) -> Self {
+        Self {
+            repo_root: repo_root.into(),
+            rows,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct WorktreeRow {
     pub name: String,
     pub path: PathBuf,
     pub created_at: String,
-    pub branch: String,
+    pub head: WorktreeHead,
     pub session_name: String,
     pub session_running: bool,
+    pub disk_usage: Option<DiskUsage>,
+    pub last_attached_at: Option<String>,
+    pub display_name: Option<String>,
+}
+
+impl WorktreeRow {
+    pub fn new(
+        name: impl Into<String>,
+        path: impl Into<PathBuf>,
+        created_at: impl Into<String>,
+        head: WorktreeHead,
+        session_name: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+            created_at: created_at.into(),
+            head,
+            session_name: session_name.into(),
+            session_running: false,
+            disk_usage: None,
+            last_attached_at: None,
+            display_name: None,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(&self.name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WorktreeHead {
+    Branch(String),
+    Detached,
+    Missing,
+}
+
+impl WorktreeHead {
+    pub fn branch(&self) -> Option<&str> {
+        match self {
+            Self::Branch(name) => Some(name),
+            Self::Detached | Self::Missing => None,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Branch(name) => name,
+            Self::Detached => "HEAD",
+            Self::Missing => "MISSING",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WorktreeSortKey {
+    Name,
+    #[default]
+    Created,
+    Branch,
+    Session,
+    Recent,
+}
+
+impl WorktreeSortKey {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Name => Self::Created,
+            Self::Created => Self::Branch,
+            Self::Branch => Self::Session,
+            Self::Session | Self::Recent => Self::Name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorktreeOrder {
+    pub key: WorktreeSortKey,
+    pub reverse: bool,
+    pub group_running: bool,
+}
+
+impl WorktreeOrder {
+    pub fn compare(&self, left: &WorktreeRow, right: &WorktreeRow) -> Ordering {
+        let group = if self.group_running {
+            right.session_running.cmp(&left.session_running)
+        } else {
+            Ordering::Equal
+        };
+
+        let primary = match self.key {
+            WorktreeSortKey::Name => left.name.cmp(&right.name),
+            WorktreeSortKey::Created => right.created_at.cmp(&left.created_at),
+            WorktreeSortKey::Branch => left.head.label().cmp(right.head.label()),
+            WorktreeSortKey::Session => right.session_running.cmp(&left.session_running),
+            WorktreeSortKey::Recent => right.last_attached_at.cmp(&left.last_attached_at),
+        }
+        .then_with(|| left.name.cmp(&right.name));
+
+        group.then(if self.reverse {
+            primary.reverse()
+        } else {
+            primary
+        })
+    }
+
+    pub fn sort(&self, rows: &mut [WorktreeRow]) {
+        rows.sort_by(|left, right| self.compare(left, right));
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SessionFilter {
+    #[default]
+    All,
+    Running,
+    Stopped,
+}
+
+impl SessionFilter {
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::Running,
+            Self::Running => Self::Stopped,
+            Self::Stopped => Self::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Running => "running only",
+            Self::Stopped => "not running only",
+        }
+    }
+
+    pub fn matches(self, row: &WorktreeRow) -> bool {
+        match self {
+            Self::All => true,
+            Self::Running => row.session_running,
+            Self::Stopped => !row.session_running,
+        }
+    }
 }
 
 impl<'a> App<'a> {
-    pub fn list(&self, cwd: &Path) -> Result<ListResult> {
+    pub fn list(&self, cwd: &Path) -> Result<ListResult, Error> {
         let catalog = WorktreeCatalog::load(self, cwd)?;
         let rows = catalog.list_rows(self)?;
 
@@ -31,4 +193,192 @@ impl<'a> App<'a> {
             rows,
         })
     }
+
+    pub fn record_disk_usage(&self, cwd: &Path, sizes: &[(String, u64)]) -> Result<(), Error> {
+        let catalog = WorktreeCatalog::load(self, cwd)?;
+        let measured_at = seshmux_core::time::now_utc_rfc3339()
+            .map_err(|error| anyhow!("failed to format timestamp: {error}"))?;
+        let updates: Vec<(String, DiskUsage)> = sizes
+            .iter()
+            .map(|(name, bytes)| (name.clone(), DiskUsage::new(*bytes, measured_at.clone())))
+            .collect();
+
+        seshmux_core::registry::record_disk_usage(catalog.worktrees_dir(), &updates)
+            .with_context(|| {
+                format!(
+                    "failed to cache worktree sizes in {}",
+                    catalog.worktrees_dir().display()
+                )
+            })
+            .map_err(Error::from)
+    }
+
+    pub fn refresh_sessions(&self) -> Result<BTreeSet<String>, Error> {
+        seshmux_core::session_model::running_labels(
+            runtime::session_layout(self)?,
+            runtime::session_backend(self)?,
+            &runtime::tmux_server(self)?,
+            self.runner,
+        )
+        .context("failed to list tmux sessions")
+        .map_err(Error::from)
+    }
+
+    pub fn session_status(&self) -> Result<BTreeMap<String, SessionStatus>, Error> {
+        if !runtime::session_backend(self)?.is_tmux() {
+            return Ok(BTreeMap::new());
+        }
+        seshmux_core::tmux::list_session_status(&runtime::tmux_server(self)?, self.runner)
+            .context("failed to read tmux session status")
+            .map_err(Error::from)
+    }
+}
+
+impl ListResult {
+    pub fn shared_branches(&self) -> BTreeMap<&str, Vec<&str>> {
+        shared_branches(&self.rows)
+    }
+}
+
+fn shared_branches(rows: &[WorktreeRow]) -> BTreeMap<&str, Vec<&str>> {
+    let mut by_branch = BTreeMap::<&str, Vec<&str>>::new();
+    for row in rows {
+        if let Some(branch) = row.head.branch() {
+            by_branch.entry(branch).or_default().push(row.name.as_str());
+        }
+    }
+    by_branch.retain(|_, names| names.len() > 1);
+    by_branch
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{
+        SessionFilter, WorktreeHead, WorktreeOrder, WorktreeRow, WorktreeSortKey, shared_branches,
+    };
+
+    fn row(name: &str, created_at: &str, running: bool) -> WorktreeRow {
+        WorktreeRow {
+            name: name.to_string(),
+            path: PathBuf::from(format!("/tmp/repo/worktrees/{name}")),
+            created_at: created_at.to_string(),
+            head: WorktreeHead::Branch(format!("feature/{name}")),
+            session_name: format!("repo/{name}"),
+            session_running: running,
+            disk_usage: None,
+            last_attached_at: None,
+            display_name: None,
+        }
+    }
+
+    fn names(rows: &[WorktreeRow]) -> Vec<&str> {
+        rows.iter().map(|row| row.name.as_str()).collect()
+    }
+
+    #[test]
+    fn shared_branches_ignores_detached_and_missing_worktrees() {
+        let mut rows = vec![
+            row("a", "2026-02-25T10:00:00Z", false),
+            row("b", "2026-02-25T11:00:00Z", false),
+            row("c", "2026-02-25T12:00:00Z", false),
+            row("d", "2026-02-25T13:00:00Z", false),
+        ];
+        rows[1].head = WorktreeHead::Branch("feature/a".to_string());
+        rows[2].head = WorktreeHead::Detached;
+        rows[3].head = WorktreeHead::Missing;
+
+        let shared = shared_branches(&rows);
+
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared["feature/a"], vec!["a", "b"]);
+    }
+
+    #[test]
+    fn order_sorts_by_key_and_reverses() {
+        let mut rows = vec![
+            row("b", "2026-02-24T10:00:00Z", false),
+            row("c", "2026-02-26T10:00:00Z", true),
+            row("a", "2026-02-25T10:00:00Z", false),
+        ];
+
+        WorktreeOrder::default().sort(&mut rows);
+        assert_eq!(names(&rows), vec!["c", "a", "b"]);
+
+        WorktreeOrder {
+            key: WorktreeSortKey::Created,
+            reverse: true,
+            group_running: false,
+        }
+        .sort(&mut rows);
+        assert_eq!(names(&rows), vec!["b", "a", "c"]);
+
+        WorktreeOrder {
+            key: WorktreeSortKey::Name,
+            ..WorktreeOrder::default()
+        }
+        .sort(&mut rows);
+        assert_eq!(names(&rows), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn grouping_keeps_running_sessions_first_regardless_of_reverse() {
+        let mut rows = vec![
+            row("a", "2026-02-24T10:00:00Z", false),
+            row("b", "2026-02-25T10:00:00Z", true),
+            row("c", "2026-02-26T10:00:00Z", false),
+            row("d", "2026-02-27T10:00:00Z", true),
+        ];
+
+        WorktreeOrder {
+            key: WorktreeSortKey::Name,
+            reverse: true,
+            group_running: true,
+        }
+        .sort(&mut rows);
+        assert_eq!(names(&rows), vec!["d", "b", "c", "a"]);
+    }
+
+    #[test]
+    fn recent_order_puts_last_attached_first_and_never_attached_last() {
+        let mut rows = vec![
+            row("a", "2026-02-24T10:00:00Z", false),
+            row("b", "2026-02-25T10:00:00Z", false),
+            row("c", "2026-02-26T10:00:00Z", false),
+            row("d", "2026-02-27T10:00:00Z", false),
+        ];
+        rows[1].last_attached_at = Some("2026-03-01T09:00:00Z".to_string());
+        rows[3].last_attached_at = Some("2026-03-02T09:00:00Z".to_string());
+
+        WorktreeOrder {
+            key: WorktreeSortKey::Recent,
+            ..WorktreeOrder::default()
+        }
+        .sort(&mut rows);
+        assert_eq!(names(&rows), vec!["d", "b", "a", "c"]);
+    }
+
+    #[test]
+    fn session_filter_cycles_and_matches_by_session_state() {
+        let running = row("a", "2026-02-24T10:00:00Z", true);
+        let stopped = row("b", "2026-02-25T10:00:00Z", false);
+
+        let mut filter = SessionFilter::default();
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            seen.push((filter, filter.matches(&running), filter.matches(&stopped)));
+            filter = filter.next();
+        }
+
+        assert_eq!(filter, SessionFilter::All);
+        assert_eq!(
+            seen,
+            vec![
+                (SessionFilter::All, true, true),
+                (SessionFilter::Running, true, false),
+                (SessionFilter::Stopped, false, true),
+            ]
+        );
+    }
 }
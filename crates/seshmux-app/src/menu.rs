@@ -0,0 +1,236 @@
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow};
+use seshmux_core::config::{SessionBackend, SessionLayout};
+use seshmux_core::session_model::SessionTarget;
+use seshmux_core::tmux::{MenuItem, TmuxServer, command_string, format_escape, shell_quote};
+
+use crate::list::{WorktreeOrder, WorktreeRow, WorktreeSortKey};
+use crate::runtime;
+use crate::{App, Error};
+
+const MENU_KEYS: &str = "123456789abcdefhilmnoprstuvwyz";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TmuxMenu {
+    pub title: String,
+    pub items: Vec<MenuItem>,
+}
+
+impl<'a> App<'a> {
+    pub fn tmux_menu(&self, cwd: &Path, program: &Path) -> Result<TmuxMenu, Error> {
+        runtime::require_tmux_backend(self, "seshmux menu")?;
+        if !runtime::inside_tmux(&runtime::tmux_server(self)?) {
+            return Err(anyhow!(
+                "seshmux menu must run inside tmux; bind it to a key, for example: bind-key W run-shell -c '#{{pane_current_path}}' 'seshmux menu'"
+            ).into());
+        }
+
+        let mut result = self.list(cwd)?;
+        WorktreeOrder {
+            key: WorktreeSortKey::Name,
+            ..WorktreeOrder::default()
+        }
+        .sort(&mut result.rows);
+
+        let layout = runtime::session_layout(self)?;
+        let server = runtime::tmux_server(self)?;
+        Ok(build_menu(
+            &result.repo_root,
+            &result.rows,
+            layout,
+            &server,
+            program,
+        ))
+    }
+
+    pub fn show_tmux_menu(&self, menu: &TmuxMenu) -> Result<(), Error> {
+        seshmux_core::tmux::display_menu(
+            &menu.title,
+            &menu.items,
+            &runtime::tmux_server(self)?,
+            self.runner,
+        )
+        .context("failed to show the tmux menu")
+        .map_err(Error::from)
+    }
+}
+
+fn build_menu(
+    repo_root: &Path,
+    rows: &[WorktreeRow],
+    layout: SessionLayout,
+    server: &TmuxServer,
+    program: &Path,
+) -> TmuxMenu {
+    let repo_name = runtime::repo_component(repo_root);
+    let title = format_escape(&format!("seshmux: {repo_name}"));
+    if rows.is_empty() {
+        return TmuxMenu {
+            title,
+            items: vec![disabled("No worktrees registered")],
+        };
+    }
+
+    let mut items = Vec::with_capacity(rows.len() + 2);
+    let mut kill_items = Vec::new();
+    for (index, row) in rows.iter().enumerate() {
+        let key = menu_key(index);
+        let attach = format!(
+            "cd {} && {} attach {} >/dev/null",
+            shell_quote(&repo_root.to_string_lossy()),
+            shell_quote(&program.to_string_lossy()),
+            shell_quote(&row.name)
+        );
+        let label = if row.session_running {
+            format!("{}  (running)", row.name)
+        } else {
+            row.name.clone()
+        };
+        items.push(MenuItem {
+            label,
+            key: key.clone(),
+            command: format_escape(&command_string(&["run-shell", &attach])),
+        });
+
+        if row.session_running {
+            let target = SessionTarget::for_worktree(
+                layout,
+                SessionBackend::Tmux,
+                server,
+                repo_name,
+                &row.name,
+            );
+            let prompt = format!("kill {}? (y/n)", target.label());
+            kill_items.push(MenuItem {
+                label: row.name.clone(),
+                key,
+                command: command_string(&[
+                    "confirm-before",
+                    "-p",
+                    &prompt,
+                    &command_string(&target.kill_args()),
+                ]),
+            });
+        }
+    }
+
+    items.push(MenuItem::separator());
+    let kill_label = match layout {
+        SessionLayout::Session => "Kill a session",
+        SessionLayout::Window => "Kill a window",
+    };
+    if kill_items.is_empty() {
+        items.push(disabled(kill_label));
+    } else {
+        // The nested menu is expanded once more when it opens, so its strings are escaped twice.
+        let kill_items: Vec<MenuItem> = kill_items
+            .into_iter()
+            .map(|item| MenuItem {
+                label: format_escape(&item.label),
+                command: format_escape(&item.command),
+                ..item
+            })
+            .collect();
+        let title = format_escape(&format_escape(&format!("Kill: {repo_name}")));
+        items.push(MenuItem {
+            label: kill_label.to_string(),
+            key: "x".to_string(),
+            command: command_string(&seshmux_core::tmux::display_menu_args(&title, &kill_items)),
+        });
+    }
+
+    TmuxMenu { title, items }
+}
+
+fn menu_key(index: usize) -> String {
+    MENU_KEYS
+        .chars()
+        .nth(index)
+        .map(String::from)
+        .unwrap_or_default()
+}
+
+fn disabled(label: &str) -> MenuItem {
+    MenuItem {
+        label: format!("-{label}"),
+        key: String::new(),
+        command: String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use seshmux_core::config::SessionLayout;
+    use seshmux_core::tmux::TmuxServer;
+
+    use super::build_menu;
+    use crate::list::{WorktreeHead, WorktreeRow};
+
+    fn row(name: &str, running: bool) -> WorktreeRow {
+        WorktreeRow {
+            name: name.to_string(),
+            path: PathBuf::from(format!("/tmp/my repo/worktrees/{name}")),
+            created_at: "2026-02-25T10:00:00Z".to_string(),
+            head: WorktreeHead::Branch(name.to_string()),
+            session_name: format!("my-repo/{name}"),
+            session_running: running,
+            disk_usage: None,
+            last_attached_at: None,
+            display_name: None,
+        }
+    }
+
+    #[test]
+    fn menu_attaches_every_worktree_and_kills_only_running_ones() {
+        let menu = build_menu(
+            Path::new("/tmp/my repo"),
+            &[row("w1", true), row("w2", false)],
+            SessionLayout::Session,
+            &TmuxServer::Default,
+            Path::new("/usr/bin/seshmux"),
+        );
+
+        assert_eq!(menu.title, "seshmux: my repo");
+        let labels: Vec<_> = menu.items.iter().map(|item| item.label.as_str()).collect();
+        assert_eq!(labels, vec!["w1  (running)", "w2", "", "Kill a session"]);
+        assert_eq!(menu.items[1].key, "2");
+        assert_eq!(
+            menu.items[1].command,
+            "run-shell \"cd '/tmp/my repo' && /usr/bin/seshmux attach w2 >/dev/null\""
+        );
+        assert_eq!(
+            menu.items[3].command,
+            "display-menu -T 'Kill: my repo' -x C -y C w1 1 \"confirm-before -p 'kill my-repo/w1? (y/n)' \\\"kill-session -t '=my-repo/w1'\\\"\""
+        );
+    }
+
+    #[test]
+    fn menu_disables_kill_without_running_sessions_and_escapes_formats() {
+        let menu = build_menu(
+            Path::new("/tmp/repo#1"),
+            &[row("w1", false)],
+            SessionLayout::Window,
+            &TmuxServer::Default,
+            Path::new("/usr/bin/seshmux"),
+        );
+        assert_eq!(menu.title, "seshmux: repo##1");
+        assert_eq!(
+            menu.items[0].command,
+            "run-shell \"cd '/tmp/repo##1' && /usr/bin/seshmux attach w1 >/dev/null\""
+        );
+        assert_eq!(menu.items[2].label, "-Kill a window");
+
+        let empty = build_menu(
+            Path::new("/tmp/repo"),
+            &[],
+            SessionLayout::Session,
+            &TmuxServer::Default,
+            Path::new("/usr/bin/seshmux"),
+        );
+        assert_eq!(empty.items.len(), 1);
+        assert_eq!(empty.items[0].label, "-No worktrees registered");
+    }
+}
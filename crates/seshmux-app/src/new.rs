@@ -1,248 +1,1275 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::SystemTime;
 
 use anyhow::{Context, Result, anyhow, bail};
+use seshmux_core::config::{
+    CheckoutConfig, DefaultsConfig, GitignoreConfig, ProjectConfig, WindowSpec,
+};
+use seshmux_core::dependency_cache::SharedCache;
+use seshmux_core::disk_usage::format_bytes;
+use seshmux_core::extras::CopySummary;
+use seshmux_core::git::{HeadState, IntegrateOutcome};
+use seshmux_core::hooks::HookError;
+use seshmux_core::names::NameTemplateContext;
+use seshmux_core::session_model::SessionTarget;
+use seshmux_core::tmux::TmuxServer;
+use seshmux_core::vcs::VcsError;
+use thiserror::Error;
 
-use crate::App;
+use crate::config;
 use crate::runtime;
+use crate::target;
+use crate::{App, Error};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NewPrepare {
     pub repo_root: PathBuf,
     pub worktrees_dir: PathBuf,
-    pub gitignore_has_worktrees_entry: bool,
+    pub missing_gitignore_entries: Vec<String>,
+    pub branch_template: Option<String>,
+    pub worktree_path_template: Option<String>,
+    pub detached_head: Option<String>,
+    pub existing_names: BTreeSet<String>,
+    pub name_suggestions: Vec<String>,
+    pub lfs_pull: bool,
+    pub init_submodules: bool,
+    pub has_envrc: bool,
+    pub direnv_allow: bool,
+    pub defaults: DefaultsConfig,
+    pub projects: BTreeMap<String, ProjectConfig>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum NewStartPoint {
     CurrentBranch,
     Branch(String),
     Commit(String),
+    NewBranchFrom(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct NewRequest {
     pub cwd: PathBuf,
     pub worktree_name: String,
+    pub display_name: Option<String>,
+    pub branch_name: Option<String>,
     pub start_point: NewStartPoint,
-    pub add_worktrees_gitignore_entry: bool,
+    pub add_gitignore_entries: bool,
     pub selected_extras: Vec<PathBuf>,
+    pub windows: Option<Vec<WindowSpec>>,
+    pub project: Option<String>,
+    pub ignore_other_worktrees: bool,
+    pub use_existing_branch: bool,
+    pub lfs_pull: Option<bool>,
+    pub init_submodules: Option<bool>,
+    pub direnv_allow: Option<bool>,
+    pub carry_changes: bool,
     pub connect_now: bool,
 }
 
+impl NewRequest {
+    pub fn new(
+        cwd: impl Into<PathBuf>,
+        worktree_name: impl Into<String>,
+        start_point: NewStartPoint,
+    ) -> Self {
+        Self {
+            cwd: cwd.into(),
+            worktree_name: worktree_name.into(),
+            display_name: None,
+            branch_name: None,
+            start_point,
+            add_gitignore_entries: false,
+            selected_extras: Vec::new(),
+            windows: None,
+            project: None,
+            ignore_other_worktrees: false,
+            use_existing_branch: false,
+            lfs_pull: None,
+            init_submodules: None,
+            direnv_allow: None,
+            carry_changes: false,
+            connect_now: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CarriedChanges {
+    Applied,
+    Conflicts { paths: Vec<String> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchConflict {
+    pub branch: String,
+    pub worktree_path: PathBuf,
+    pub worktree_name: Option<String>,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum NewError {
+    #[error(
+        "branch '{branch}' is already checked out in {}; use that worktree, pick another name, or retry ignoring other worktrees",
+        checked_out_location(worktree_name.as_deref(), worktree_path)
+    )]
+    BranchCheckedOut {
+        branch: String,
+        worktree_path: PathBuf,
+        worktree_name: Option<String>,
+    },
+    #[error(
+        "not enough free space in {}: the worktree needs about {} but only {} is available; copy fewer extras or free some space",
+        path.display(),
+        format_bytes(*required),
+        format_bytes(*available)
+    )]
+    InsufficientSpace {
+        path: PathBuf,
+        required: u64,
+        available: u64,
+    },
+    #[error(
+        "{} already exists; pick another name or change worktree_path_template",
+        path.display()
+    )]
+    PathTaken { path: PathBuf },
+    #[error("creating the worktree was canceled")]
+    Canceled,
+}
+
+impl NewPrepare {
+    pub fn worktree_path(&self, worktree_name: &str) -> PathBuf {
+        seshmux_core::config::resolve_worktree_path(
+            self.worktree_path_template.as_deref(),
+            &self.worktrees_dir,
+            &self.repo_root,
+            worktree_name,
+            &seshmux_core::time::compact_utc_date(SystemTime::now()),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NewPhase {
+    CreatingWorktree,
+    PullingLfs,
+    InitializingSubmodules,
+    CarryingChanges,
+    CopyingExtras,
+    Provisioning,
+    AllowingDirenv,
+    SharingDependencyCaches,
+    Registering,
+    StartingSession,
+    Connecting,
+}
+
+impl NewPhase {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::CreatingWorktree => "Creating worktree",
+            Self::PullingLfs => "Pulling Git LFS objects",
+            Self::InitializingSubmodules => "Initializing submodules",
+            Self::CarryingChanges => "Carrying over uncommitted changes",
+            Self::CopyingExtras => "Copying extras",
+            Self::Provisioning => "Running bootstrap command",
+            Self::AllowingDirenv => "Allowing direnv",
+            Self::SharingDependencyCaches => "Sharing dependency caches",
+            Self::Registering => "Registering worktree",
+            Self::StartingSession => "Starting tmux session",
+            Self::Connecting => "Connecting to tmux session",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NewProgress {
+    pub phase: NewPhase,
+    pub detail: String,
+    pub output: Option<String>,
+}
+
+impl NewProgress {
+    pub fn new(phase: NewPhase, detail: impl Into<String>) -> Self {
+        Self {
+            phase,
+            detail: detail.into(),
+            output: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProvisionSummary {
+    pub ran: Vec<String>,
+    pub failure: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProvisionOutput {
+    pub worktree_name: String,
+    pub command: String,
+    pub status: Option<i32>,
+    pub lines: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProvisionLog(Arc<Mutex<Vec<ProvisionOutput>>>);
+
+impl ProvisionLog {
+    pub fn take(&self) -> Vec<ProvisionOutput> {
+        std::mem::take(&mut *self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn record(&self, output: ProvisionOutput) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(output);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct NewResult {
     pub repo_root: PathBuf,
     pub worktrees_dir: PathBuf,
+    pub worktree_name: String,
+    pub display_name: Option<String>,
     pub worktree_path: PathBuf,
     pub branch_name: String,
     pub session_name: String,
     pub attach_command: String,
     pub connected_now: bool,
+    pub extras_copied: CopySummary,
+    pub gitignore_added: Vec<String>,
+    pub shared_caches: Vec<SharedCache>,
+    pub environment: Vec<(String, String)>,
+    pub carried_changes: Option<CarriedChanges>,
+    pub provision: ProvisionSummary,
+}
+
+impl NewResult {
+    pub fn new(
+        repo_root: impl Into<PathBuf>,
+        worktrees_dir: impl Into<PathBuf>,
+        worktree_name: impl Into<String>,
+        worktree_path: impl Into<PathBuf>,
+        branch_name: impl Into<String>,
+        session_name: impl Into<String>,
+    ) -> Self {
+        Self {
+            repo_root: repo_root.into(),
+            worktrees_dir: worktrees_dir.into(),
+            worktree_name: worktree_name.into(),
+            display_name: None,
+            worktree_path: worktree_path.into(),
+            branch_name: branch_name.into(),
+            session_name: session_name.into(),
+            attach_command: String::new(),
+            connected_now: false,
+            extras_copied: CopySummary::default(),
+            gitignore_added: Vec::new(),
+            shared_caches: Vec::new(),
+            environment: Vec::new(),
+            carried_changes: None,
+            provision: ProvisionSummary::default(),
+        }
+    }
 }
 
 impl<'a> App<'a> {
-    pub fn new_prepare(&self, cwd: &Path) -> Result<NewPrepare> {
+    pub fn new_prepare(&self, cwd: &Path) -> Result<NewPrepare, Error> {
         let repo_root = runtime::resolve_repo_root(self, cwd)?;
-        let worktrees_dir = repo_root.join("worktrees");
-        let gitignore_has_worktrees_entry =
-            seshmux_core::git::gitignore_contains_worktrees(&repo_root).with_context(|| {
-                format!("failed to inspect .gitignore in {}", repo_root.display())
-            })?;
+        let worktrees_dir = runtime::worktrees_dir(self, &repo_root)?;
+        let missing_gitignore_entries = seshmux_core::git::missing_gitignore_entries(
+            &repo_root,
+            &managed_gitignore_entries(
+                &repo_root,
+                &worktrees_dir,
+                &runtime::gitignore_config(self)?,
+            ),
+        )
+        .with_context(|| format!("failed to inspect .gitignore in {}", repo_root.display()))?;
+
+        let head = runtime::vcs(self, &repo_root)?
+            .head_state(&repo_root)
+            .with_context(|| format!("failed to read HEAD in {}", repo_root.display()))?;
+        let (branch, detached_head) = match head {
+            Some(HeadState::Branch(branch)) => (Some(branch), None),
+            Some(HeadState::Detached(short_sha)) => (None, Some(short_sha)),
+            None => (None, None),
+        };
+        let existing_names = seshmux_core::registry::load_registry(&worktrees_dir)
+            .with_context(|| {
+                format!(
+                    "failed to load worktree registry at {}",
+                    seshmux_core::registry::registry_path(&worktrees_dir).display()
+                )
+            })?
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect::<BTreeSet<_>>();
+        let name_suggestions = seshmux_core::names::suggest_worktree_names(
+            &runtime::names_config(self)?.templates,
+            &NameTemplateContext {
+                date: seshmux_core::time::compact_utc_date(SystemTime::now()),
+                branch,
+            },
+            &existing_names,
+        );
+        let checkout = runtime::checkout_config(self)?;
+        let (lfs_pull, init_submodules) = checkout_steps(checkout, &repo_root);
+        let has_envrc = seshmux_core::direnv::has_envrc(&repo_root);
+        let projects = runtime::projects(self, &repo_root)?;
 
         Ok(NewPrepare {
             repo_root,
             worktrees_dir,
-            gitignore_has_worktrees_entry,
+            missing_gitignore_entries,
+            branch_template: runtime::branch_template(self)?,
+            worktree_path_template: runtime::worktree_path_template(self)?,
+            detached_head,
+            existing_names,
+            name_suggestions,
+            lfs_pull,
+            init_submodules,
+            has_envrc,
+            direnv_allow: checkout.direnv.enabled(has_envrc),
+            defaults: runtime::defaults_config(self)?,
+            projects,
         })
     }
 
+    pub fn new_changed_files(&self, cwd: &Path) -> Result<Vec<String>, Error> {
+        seshmux_core::git::changed_files(cwd, self.runner)
+            .with_context(|| format!("failed to list changed files in {}", cwd.display()))
+            .map_err(Error::from)
+    }
+
+    pub fn new_branch_conflict(
+        &self,
+        repo_root: &Path,
+        branch: &str,
+    ) -> Result<Option<BranchConflict>, Error> {
+        let checkouts = seshmux_core::git::list_worktree_checkouts(repo_root, self.runner)
+            .with_context(|| format!("failed to list git worktrees in {}", repo_root.display()))?;
+        let Some(checkout) = checkouts
+            .into_iter()
+            .find(|checkout| checkout.branch.as_deref() == Some(branch))
+        else {
+            return Ok(None);
+        };
+
+        let worktrees_dir = runtime::worktrees_dir(self, repo_root)?;
+        let checkout_key = runtime::comparable_path(&checkout.path);
+        let registered_name = seshmux_core::registry::load_registry(&worktrees_dir)
+            .with_context(|| {
+                format!(
+                    "failed to load worktree registry in {}",
+                    worktrees_dir.display()
+                )
+            })?
+            .into_iter()
+            .find(|entry| runtime::comparable_path(Path::new(&entry.path)) == checkout_key)
+            .map(|entry| entry.name);
+
+        Ok(Some(BranchConflict {
+            branch: branch.to_string(),
+            worktree_path: checkout.path,
+            worktree_name: registered_name,
+        }))
+    }
+
     pub fn new_query_branches(
         &self,
         repo_root: &Path,
         query: &str,
-    ) -> Result<Vec<seshmux_core::git::BranchRef>> {
+    ) -> Result<Vec<seshmux_core::git::BranchRef>, Error> {
         seshmux_core::git::query_branches(repo_root, query, self.runner)
             .with_context(|| format!("failed to query branches in {}", repo_root.display()))
+            .map_err(Error::from)
     }
 
     pub fn new_query_commits(
         &self,
         repo_root: &Path,
         query: &str,
+        search: seshmux_core::git::CommitSearch,
+        skip: usize,
         limit: usize,
-    ) -> Result<Vec<seshmux_core::git::CommitRef>> {
-        seshmux_core::git::query_commits(repo_root, query, limit, self.runner)
+    ) -> Result<Vec<seshmux_core::git::CommitRef>, Error> {
+        seshmux_core::git::query_commits(repo_root, query, search, skip, limit, self.runner)
             .with_context(|| format!("failed to query commits in {}", repo_root.display()))
+            .map_err(Error::from)
     }
 
-    pub fn new_list_extras(&self, repo_root: &Path) -> Result<Vec<PathBuf>> {
-        seshmux_core::extras::list_extra_candidates(repo_root, self.runner).with_context(|| {
-            format!(
-                "failed to list extra copy candidates in {}",
-                repo_root.display()
-            )
-        })
+    pub fn new_list_extras(&self, repo_root: &Path) -> Result<Vec<PathBuf>, Error> {
+        seshmux_core::extras::list_extra_candidates(repo_root, self.runner)
+            .with_context(|| {
+                format!(
+                    "failed to list extra copy candidates in {}",
+                    repo_root.display()
+                )
+            })
+            .map_err(Error::from)
     }
 
     pub fn new_load_always_skip_buckets_for_indexing(
         &self,
         repo_root: &Path,
-    ) -> Result<seshmux_core::registry::AlwaysSkipBucketsLoad> {
-        seshmux_core::registry::load_always_skip_buckets_for_indexing(repo_root).with_context(
-            || {
-                format!(
-                    "failed to load extras skip settings in {}",
-                    repo_root.display()
-                )
-            },
-        )
+    ) -> Result<seshmux_core::registry::AlwaysSkipBucketsLoad, Error> {
+        let worktrees_dir = runtime::worktrees_dir(self, repo_root)?;
+        let mut loaded =
+            seshmux_core::registry::load_always_skip_buckets_for_indexing(&worktrees_dir)
+                .with_context(|| {
+                    format!(
+                        "failed to load extras skip settings in {}",
+                        worktrees_dir.display()
+                    )
+                })?;
+
+        if let Some(extras) = config::load_repo_config_if_present(repo_root)?
+            .and_then(|repo_config| repo_config.extras)
+        {
+            for bucket in extras.always_skip_buckets {
+                let bucket = bucket.trim();
+                if bucket.is_empty() {
+                    continue;
+                }
+                loaded.buckets.insert(bucket.to_string());
+                loaded.configured_buckets.insert(bucket.to_string());
+            }
+        }
+
+        Ok(loaded)
     }
 
     pub fn new_save_always_skip_buckets(
         &self,
         repo_root: &Path,
         buckets: &BTreeSet<String>,
-    ) -> Result<()> {
-        seshmux_core::registry::save_always_skip_buckets(repo_root, buckets).with_context(|| {
-            format!(
-                "failed to persist extras skip settings in {}",
-                repo_root.display()
-            )
-        })
+    ) -> Result<(), Error> {
+        let worktrees_dir = runtime::worktrees_dir(self, repo_root)?;
+        seshmux_core::registry::save_always_skip_buckets(&worktrees_dir, buckets)
+            .with_context(|| {
+                format!(
+                    "failed to persist extras skip settings in {}",
+                    worktrees_dir.display()
+                )
+            })
+            .map_err(Error::from)
     }
 
-    pub fn new_execute(&self, request: NewRequest) -> Result<NewResult> {
-        let config = self.ensure_config_ready()?;
+    pub fn new_execute(
+        &self,
+        request: NewRequest,
+        progress: &mut dyn FnMut(NewProgress),
+    ) -> Result<NewResult, Error> {
+        self.new_execute_cancelable(request, progress, &AtomicBool::new(false))
+    }
+
+    pub fn new_execute_cancelable(
+        &self,
+        request: NewRequest,
+        progress: &mut dyn FnMut(NewProgress),
+        cancel: &AtomicBool,
+    ) -> Result<NewResult, Error> {
+        let mut config = self.ensure_config_ready()?;
 
         seshmux_core::names::validate_worktree_name(&request.worktree_name)
             .with_context(|| format!("invalid worktree name '{}'", request.worktree_name))?;
+        if let Some(display_name) = &request.display_name {
+            seshmux_core::names::validate_display_name(display_name)
+                .with_context(|| format!("invalid display name '{display_name}'"))?;
+        }
+
+        let branch_name = match &request.branch_name {
+            Some(branch_name) => branch_name.clone(),
+            None => seshmux_core::config::resolve_branch_name(
+                config.branch_template.as_deref(),
+                &request.worktree_name,
+            ),
+        };
+        seshmux_core::names::validate_branch_name(&branch_name)
+            .with_context(|| format!("invalid branch name '{branch_name}'"))?;
+        if let NewStartPoint::NewBranchFrom(start_branch) = &request.start_point {
+            seshmux_core::names::validate_branch_name(start_branch.trim())
+                .with_context(|| format!("invalid branch name '{start_branch}'"))?;
+        }
 
         let repo_root = runtime::resolve_repo_root(self, &request.cwd)?;
+        config::apply_repo_config(&mut config, &repo_root)?;
+        let provision_commands = config::load_repo_config_if_present(&repo_root)?
+            .and_then(|repo_config| repo_config.hooks)
+            .map(|hooks| hooks.provision)
+            .unwrap_or_default();
+        let project = match &request.project {
+            Some(name) => Some(runtime::find_project(&config, name)?.clone()),
+            None => None,
+        };
+        if let Some(windows) = request
+            .windows
+            .clone()
+            .or_else(|| project.as_ref().and_then(|project| project.windows.clone()))
+        {
+            config.tmux.windows = windows;
+            seshmux_core::config::validate_config(&config)
+                .context("invalid tmux windows override")?;
+        }
 
-        let worktrees_dir = repo_root.join("worktrees");
+        let worktrees_dir = seshmux_core::config::resolve_worktrees_dir(
+            config.worktrees_dir.as_deref(),
+            &repo_root,
+        );
+        runtime::claim_worktrees_dir(&worktrees_dir, &repo_root)?;
         std::fs::create_dir_all(&worktrees_dir)
             .with_context(|| format!("failed to create {}", worktrees_dir.display()))?;
 
-        let worktree_path = worktrees_dir.join(&request.worktree_name);
+        let worktree_path = seshmux_core::config::resolve_worktree_path(
+            config.worktree_path_template.as_deref(),
+            &worktrees_dir,
+            &repo_root,
+            &request.worktree_name,
+            &seshmux_core::time::compact_utc_date(SystemTime::now()),
+        );
 
         seshmux_core::registry::ensure_entry_available(
-            &repo_root,
+            &worktrees_dir,
             &request.worktree_name,
             &worktree_path,
         )
         .with_context(|| "registry already has a conflicting worktree entry".to_string())?;
+        if config.worktree_path_template.is_some() && worktree_path.exists() {
+            return Err(NewError::PathTaken {
+                path: worktree_path,
+            }
+            .into());
+        }
+        if let Some(parent) = worktree_path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
 
-        if request.add_worktrees_gitignore_entry {
-            seshmux_core::git::ensure_worktrees_gitignore_entry(&repo_root).with_context(|| {
-                format!("failed to update .gitignore in {}", repo_root.display())
-            })?;
+        let conflict = self.new_branch_conflict(&repo_root, &branch_name)?;
+        if let Some(conflict) = &conflict
+            && !request.ignore_other_worktrees
+        {
+            return Err(NewError::BranchCheckedOut {
+                branch: conflict.branch.clone(),
+                worktree_path: conflict.worktree_path.clone(),
+                worktree_name: conflict.worktree_name.clone(),
+            }
+            .into());
         }
 
-        let start_point = match &request.start_point {
-            NewStartPoint::CurrentBranch => {
-                seshmux_core::git::resolve_current_start_point(&repo_root, self.runner)?
+        if let Some(required) = estimated_worktree_size(self, &repo_root, &request.selected_extras)
+            && let Ok(Some(available)) = seshmux_core::disk_usage::available_space(&worktrees_dir)
+            && required > available
+        {
+            return Err(NewError::InsufficientSpace {
+                path: worktrees_dir,
+                required,
+                available,
             }
-            NewStartPoint::Branch(value) => {
-                let trimmed = value.trim();
-                if trimmed.is_empty() {
-                    bail!("start branch cannot be empty");
+            .into());
+        }
+
+        let (stash, untracked) = if request.carry_changes {
+            let context = || {
+                format!(
+                    "failed to record uncommitted changes in {}",
+                    request.cwd.display()
+                )
+            };
+            (
+                seshmux_core::git::stash_create(&request.cwd, self.runner).with_context(context)?,
+                seshmux_core::git::untracked_files(&request.cwd, self.runner)
+                    .with_context(context)?,
+            )
+        } else {
+            (None, Vec::new())
+        };
+        let untracked_source = if untracked.is_empty() {
+            None
+        } else {
+            Some(
+                seshmux_core::git::repo_root(&request.cwd, self.runner).with_context(|| {
+                    format!(
+                        "failed to resolve the checkout of {}",
+                        request.cwd.display()
+                    )
+                })?,
+            )
+        };
+
+        let gitignore_added = if request.add_gitignore_entries {
+            seshmux_core::git::ensure_gitignore_entries(
+                &repo_root,
+                &managed_gitignore_entries(&repo_root, &worktrees_dir, &config.gitignore),
+            )
+            .with_context(|| format!("failed to update .gitignore in {}", repo_root.display()))?
+        } else {
+            Vec::new()
+        };
+
+        if cancel.load(Ordering::Relaxed) {
+            return Err(NewError::Canceled.into());
+        }
+        report(
+            progress,
+            NewPhase::CreatingWorktree,
+            worktree_path.display().to_string(),
+        );
+        let mut rollback = Rollback::new(&repo_root, &request.worktree_name);
+        let mut start_point = None;
+        let created = match &conflict {
+            Some(conflict) => seshmux_core::git::create_worktree_on_branch(
+                &repo_root,
+                &worktree_path,
+                &conflict.branch,
+                true,
+                self.runner,
+            )
+            .map_err(VcsError::from),
+            None if request.use_existing_branch => seshmux_core::git::create_worktree_on_branch(
+                &repo_root,
+                &worktree_path,
+                &branch_name,
+                false,
+                self.runner,
+            )
+            .map_err(VcsError::from),
+            None => {
+                let mut resolved = resolve_start_point(self, &repo_root, &request.start_point)?;
+                if let NewStartPoint::NewBranchFrom(start_branch) = &request.start_point {
+                    let start_branch = start_branch.trim();
+                    seshmux_core::git::create_branch_from(
+                        &repo_root,
+                        start_branch,
+                        &resolved,
+                        self.runner,
+                    )
+                    .with_context(|| format!("failed to create branch '{start_branch}'"))?;
+                    rollback.record(CreatedStep::Branch {
+                        name: start_branch.to_string(),
+                    });
+                    resolved = start_branch.to_string();
                 }
-                trimmed.to_string()
+                let created = if resolved == branch_name {
+                    seshmux_core::git::create_worktree_on_branch(
+                        &repo_root,
+                        &worktree_path,
+                        &branch_name,
+                        false,
+                        self.runner,
+                    )
+                    .map_err(VcsError::from)
+                } else {
+                    // `worktree add -b` can create the branch and still fail, so a branch that
+                    // was missing beforehand is rolled back either way.
+                    let exists = rollback.guard(
+                        self,
+                        seshmux_core::git::branch_exists(&repo_root, &branch_name, self.runner)
+                            .with_context(|| format!("failed to check branch '{branch_name}'")),
+                    )?;
+                    if !exists {
+                        rollback.record(CreatedStep::Branch {
+                            name: branch_name.clone(),
+                        });
+                    }
+                    runtime::vcs(self, &repo_root)?.create_worktree(
+                        &repo_root,
+                        &branch_name,
+                        &worktree_path,
+                        &resolved,
+                    )
+                };
+                start_point = Some(resolved);
+                created
+            }
+        };
+        rollback.guard(
+            self,
+            created.with_context(|| {
+                format!(
+                    "failed to create worktree '{}' at {}",
+                    request.worktree_name,
+                    worktree_path.display()
+                )
+            }),
+        )?;
+
+        rollback.record(CreatedStep::Worktree {
+            path: worktree_path.clone(),
+        });
+
+        let (detected_lfs, detected_submodules) = checkout_steps(config.checkout, &repo_root);
+        if request.lfs_pull.unwrap_or(detected_lfs) {
+            rollback.check_cancel(self, cancel)?;
+            report(
+                progress,
+                NewPhase::PullingLfs,
+                worktree_path.display().to_string(),
+            );
+            rollback.guard(
+                self,
+                seshmux_core::git::lfs_pull(&worktree_path, self.runner).with_context(|| {
+                    format!(
+                        "failed to pull Git LFS objects in {}",
+                        worktree_path.display()
+                    )
+                }),
+            )?;
+        }
+        if request.init_submodules.unwrap_or(detected_submodules) {
+            rollback.check_cancel(self, cancel)?;
+            report(
+                progress,
+                NewPhase::InitializingSubmodules,
+                worktree_path.display().to_string(),
+            );
+            rollback.guard(
+                self,
+                seshmux_core::git::update_submodules(&worktree_path, self.runner).with_context(
+                    || {
+                        format!(
+                            "failed to initialize submodules in {}",
+                            worktree_path.display()
+                        )
+                    },
+                ),
+            )?;
+        }
+
+        let mut carried_changes = None;
+        if stash.is_some() || untracked_source.is_some() {
+            rollback.check_cancel(self, cancel)?;
+            report(
+                progress,
+                NewPhase::CarryingChanges,
+                worktree_path.display().to_string(),
+            );
+            carried_changes = Some(CarriedChanges::Applied);
+        }
+        if let Some(stash) = &stash {
+            let applied = rollback.guard(
+                self,
+                seshmux_core::git::stash_apply(&worktree_path, stash, self.runner).with_context(
+                    || {
+                        format!(
+                            "failed to apply uncommitted changes in {}",
+                            worktree_path.display()
+                        )
+                    },
+                ),
+            )?;
+            if let IntegrateOutcome::Conflicts { paths, .. } = applied {
+                carried_changes = Some(CarriedChanges::Conflicts { paths });
+            }
+        }
+        if let Some(source) = &untracked_source {
+            let paths: Vec<PathBuf> = untracked.iter().map(PathBuf::from).collect();
+            rollback.guard(
+                self,
+                seshmux_core::extras::copy_selected_extras(
+                    source,
+                    &worktree_path,
+                    &paths,
+                    config.extras.copy_concurrency,
+                )
+                .with_context(|| {
+                    format!(
+                        "failed to copy untracked files into {}",
+                        worktree_path.display()
+                    )
+                }),
+            )?;
+        }
+
+        if !request.selected_extras.is_empty() {
+            rollback.check_cancel(self, cancel)?;
+            report(
+                progress,
+                NewPhase::CopyingExtras,
+                format!("{} path(s)", request.selected_extras.len()),
+            );
+        }
+        let extras_copied = rollback.guard(
+            self,
+            seshmux_core::extras::copy_selected_extras(
+                &repo_root,
+                &worktree_path,
+                &request.selected_extras,
+                config.extras.copy_concurrency,
+            )
+            .with_context(|| {
+                format!(
+                    "failed to copy selected extras into {}",
+                    worktree_path.display()
+                )
+            }),
+        )?;
+
+        let mut provision = ProvisionSummary::default();
+        for command in &provision_commands {
+            rollback.check_cancel(self, cancel)?;
+            report(progress, NewPhase::Provisioning, command.clone());
+            let mut lines = Vec::new();
+            let result = seshmux_core::hooks::run_shell_hook_streaming(
+                command,
+                &worktree_path,
+                self.runner,
+                &mut |line| {
+                    progress(NewProgress {
+                        phase: NewPhase::Provisioning,
+                        detail: command.clone(),
+                        output: Some(line.to_string()),
+                    });
+                    lines.push(line.to_string());
+                },
+            );
+            let status = match &result {
+                Ok(()) => Some(0),
+                Err(HookError::CommandFailed { status, .. }) => Some(*status),
+                Err(HookError::Execute { .. }) => None,
+            };
+            if let Some(log) = self.provision_log() {
+                log.record(ProvisionOutput {
+                    worktree_name: request.worktree_name.clone(),
+                    command: command.clone(),
+                    status,
+                    lines,
+                });
             }
-            NewStartPoint::Commit(value) => {
-                let trimmed = value.trim();
-                if trimmed.is_empty() {
-                    bail!("start commit cannot be empty");
+            match result {
+                Ok(()) => provision.ran.push(command.clone()),
+                Err(error) => {
+                    provision.failure = Some(match status {
+                        Some(status) => format!("`{command}` failed (exit {status})"),
+                        None => error.to_string(),
+                    });
+                    break;
                 }
-                trimmed.to_string()
             }
-        };
+        }
+
+        // After the extras copy, so an untracked `.envrc` copied over is allowed too.
+        let detected_direnv = config
+            .checkout
+            .direnv
+            .enabled(seshmux_core::direnv::has_envrc(&repo_root));
+        if request.direnv_allow.unwrap_or(detected_direnv)
+            && seshmux_core::direnv::has_envrc(&worktree_path)
+        {
+            rollback.check_cancel(self, cancel)?;
+            report(
+                progress,
+                NewPhase::AllowingDirenv,
+                worktree_path.display().to_string(),
+            );
+            rollback.guard(
+                self,
+                seshmux_core::direnv::allow(&worktree_path, self.runner).with_context(|| {
+                    format!("failed to run direnv allow in {}", worktree_path.display())
+                }),
+            )?;
+        }
 
-        seshmux_core::git::create_worktree(
+        let mut shared_caches = Vec::new();
+        if let Some(dependency_caches) = &config.dependency_caches
+            && dependency_caches.enabled
+        {
+            let caches = seshmux_core::dependency_cache::find_dependency_caches(
+                &repo_root,
+                &dependency_caches.dirs,
+            );
+            if !caches.is_empty() {
+                rollback.check_cancel(self, cancel)?;
+                let names: Vec<String> = caches
+                    .iter()
+                    .map(|cache| cache.display().to_string())
+                    .collect();
+                report(
+                    progress,
+                    NewPhase::SharingDependencyCaches,
+                    names.join(", "),
+                );
+            }
+            shared_caches = rollback.guard(
+                self,
+                seshmux_core::dependency_cache::share_dependency_caches(
+                    &repo_root,
+                    &worktree_path,
+                    &caches,
+                    self.runner,
+                )
+                .with_context(|| {
+                    format!(
+                        "failed to share dependency caches into {}",
+                        worktree_path.display()
+                    )
+                }),
+            )?;
+        }
+
+        rollback.check_cancel(self, cancel)?;
+        report(
+            progress,
+            NewPhase::Registering,
+            seshmux_core::registry::registry_path(&worktrees_dir)
+                .display()
+                .to_string(),
+        );
+        let created_at = rollback.guard(
+            self,
+            seshmux_core::time::now_utc_rfc3339()
+                .map_err(|error| anyhow!("failed to format timestamp: {error}")),
+        )?;
+
+        let session = runtime::session_target_for(
+            config.tmux.layout,
+            config.tmux.backend,
+            &TmuxServer::from_config(&config.tmux),
             &repo_root,
             &request.worktree_name,
-            &worktree_path,
-            &start_point,
-            self.runner,
-        )
-        .with_context(|| {
-            format!(
-                "failed to create worktree '{}' at {}",
-                request.worktree_name,
-                worktree_path.display()
-            )
-        })?;
+        );
+        let checked_out_branch = conflict
+            .as_ref()
+            .map_or_else(|| branch_name.clone(), |conflict| conflict.branch.clone());
 
-        seshmux_core::extras::copy_selected_extras(
-            &repo_root,
-            &worktree_path,
-            &request.selected_extras,
-        )
-        .with_context(|| {
-            format!(
-                "failed to copy selected extras into {}",
-                worktree_path.display()
-            )
-        })?;
+        let mut entry = seshmux_core::registry::RegistryEntry::new(
+            request.worktree_name.clone(),
+            worktree_path.to_string_lossy(),
+            created_at,
+        );
+        entry.created_by = Some(seshmux_core::ownership::current_owner());
+        entry.branch = Some(checked_out_branch);
+        entry.start_point = start_point;
+        entry.session_name = Some(session.session_name().to_string());
+        entry.layout = Some(config.tmux.layout);
+        entry.extras_copied = request
+            .selected_extras
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        entry.display_name = request.display_name.clone();
+        entry.project = request.project.clone();
+        rollback.guard(
+            self,
+            seshmux_core::registry::insert_unique_entry(&worktrees_dir, entry).with_context(|| {
+                format!(
+                    "failed to register worktree '{}' in {}",
+                    request.worktree_name,
+                    seshmux_core::registry::registry_path(&worktrees_dir).display()
+                )
+            }),
+        )?;
+        rollback.record(CreatedStep::RegistryEntry {
+            worktrees_dir: worktrees_dir.clone(),
+        });
+        rollback.guard(
+            self,
+            runtime::claim_worktrees_dir(&worktrees_dir, &repo_root),
+        )?;
 
-        let created_at = seshmux_core::time::now_utc_rfc3339()
-            .map_err(|error| anyhow!("failed to format timestamp: {error}"))?;
+        let session_name = session.label();
+        let attach_command = session.connect_command(false);
 
-        seshmux_core::registry::insert_unique_entry(
+        let environment = runtime::session_environment(
+            &config,
             &repo_root,
-            seshmux_core::registry::RegistryEntry {
-                name: request.worktree_name.clone(),
-                path: worktree_path.to_string_lossy().to_string(),
-                created_at,
-            },
-        )
-        .with_context(|| {
-            format!(
-                "failed to register worktree '{}' in {}",
-                request.worktree_name,
-                worktrees_dir.join("worktree.toml").display()
-            )
-        })?;
+            &request.worktree_name,
+            &branch_name,
+            &worktree_path,
+        );
 
-        let session_name = runtime::session_name_for(&repo_root, &request.worktree_name);
-        let attach_command = format!("tmux attach-session -t {session_name}");
+        let session_dir = match &project {
+            Some(project) => {
+                let dir = project.session_dir(&worktree_path);
+                if !dir.is_dir() {
+                    let error = anyhow!(
+                        "project directory {} does not exist in the worktree",
+                        dir.display()
+                    );
+                    return Err(rollback.undo(self, error).into());
+                }
+                dir
+            }
+            None => worktree_path.clone(),
+        };
 
-        seshmux_core::tmux::create_session_and_windows(
-            &session_name,
-            &worktree_path,
-            &config.tmux.windows,
-            self.runner,
-        )
-        .with_context(|| {
-            format!(
-                "failed to create tmux session '{session_name}'; attach manually with '{attach_command}' after resolving tmux errors"
-            )
-        })?;
+        rollback.check_cancel(self, cancel)?;
+        report(progress, NewPhase::StartingSession, session_name.clone());
+        // Recorded up front: a failure after new-session still leaves a partial session behind.
+        rollback.record(CreatedStep::Session {
+            target: session.clone(),
+        });
+        rollback.guard(
+            self,
+            session
+                .create(
+                    &session_dir,
+                    &config.tmux.windows,
+                    &environment,
+                    self.runner,
+                )
+                .with_context(|| format!("failed to create tmux session '{session_name}'")),
+        )?;
 
         let mut connected_now = false;
         if request.connect_now {
-            seshmux_core::tmux::connect_session(
-                &session_name,
-                runtime::inside_tmux(),
-                self.runner,
-            )
-            .with_context(|| {
-                format!(
-                    "failed to connect to tmux session '{session_name}'; attach manually with '{attach_command}'"
+            report(progress, NewPhase::Connecting, session_name.clone());
+            session
+                .connect(
+                    runtime::inside_tmux(&TmuxServer::from_config(&config.tmux)),
+                    self.runner,
                 )
-            })?;
+                .with_context(|| {
+                    format!(
+                        "failed to connect to tmux session '{session_name}'; attach manually with '{attach_command}'"
+                    )
+                })?;
             connected_now = true;
         }
 
         Ok(NewResult {
             repo_root,
             worktrees_dir,
+            worktree_name: request.worktree_name,
+            display_name: request.display_name,
             worktree_path,
-            branch_name: request.worktree_name,
+            branch_name,
             session_name,
             attach_command,
             connected_now,
+            extras_copied,
+            gitignore_added,
+            shared_caches,
+            environment,
+            carried_changes,
+            provision,
         })
     }
+
+    pub fn new_connect(&self, result: &NewResult) -> Result<(), Error> {
+        let target = target::resolve_target(self, &result.repo_root, &result.worktree_name)?
+            .ok_or_else(|| anyhow!("worktree '{}' is not registered", result.worktree_name))?;
+        let session_name = &result.session_name;
+        let attach_command = &result.attach_command;
+        target
+            .session
+            .connect(runtime::inside_tmux(&runtime::tmux_server(self)?), self.runner)
+            .with_context(|| {
+                format!(
+                    "failed to connect to tmux session '{session_name}'; attach manually with '{attach_command}'"
+                )
+            })
+            .map_err(Error::from)
+    }
+}
+
+fn report(progress: &mut dyn FnMut(NewProgress), phase: NewPhase, detail: String) {
+    progress(NewProgress {
+        phase,
+        detail,
+        output: None,
+    });
+}
+
+fn checked_out_location(worktree_name: Option<&str>, worktree_path: &Path) -> String {
+    match worktree_name {
+        Some(name) => format!(
+            "worktree '{name}' at {} (seshmux attach {name})",
+            worktree_path.display()
+        ),
+        None => worktree_path.display().to_string(),
+    }
+}
+
+fn estimated_worktree_size(
+    app: &App<'_>,
+    repo_root: &Path,
+    selected_extras: &[PathBuf],
+) -> Option<u64> {
+    let checkout = seshmux_core::git::index_checkout_size(repo_root, app.runner)?;
+    Some(
+        selected_extras
+            .iter()
+            .filter_map(|extra| {
+                seshmux_core::disk_usage::directory_size(&repo_root.join(extra)).ok()
+            })
+            .fold(checkout, u64::saturating_add),
+    )
+}
+
+fn managed_gitignore_entries(
+    repo_root: &Path,
+    worktrees_dir: &Path,
+    config: &GitignoreConfig,
+) -> Vec<String> {
+    seshmux_core::git::worktrees_gitignore_entry(repo_root, worktrees_dir)
+        .into_iter()
+        .chain(config.entries.iter().cloned())
+        .collect()
+}
+
+fn checkout_steps(config: CheckoutConfig, repo_root: &Path) -> (bool, bool) {
+    (
+        config.lfs.enabled(seshmux_core::git::uses_lfs(repo_root)),
+        config
+            .submodules
+            .enabled(seshmux_core::git::has_submodules(repo_root)),
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CreatedStep {
+    Branch { name: String },
+    Worktree { path: PathBuf },
+    RegistryEntry { worktrees_dir: PathBuf },
+    Session { target: SessionTarget },
+}
+
+#[derive(Debug)]
+struct Rollback {
+    repo_root: PathBuf,
+    worktree_name: String,
+    steps: Vec<CreatedStep>,
+}
+
+impl Rollback {
+    fn new(repo_root: &Path, worktree_name: &str) -> Self {
+        Self {
+            repo_root: repo_root.to_path_buf(),
+            worktree_name: worktree_name.to_string(),
+            steps: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, step: CreatedStep) {
+        self.steps.push(step);
+    }
+
+    fn guard<T>(&self, app: &App<'_>, result: Result<T>) -> Result<T> {
+        result.map_err(|error| self.undo(app, error))
+    }
+
+    fn check_cancel(&self, app: &App<'_>, cancel: &AtomicBool) -> Result<()> {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(self.undo(app, NewError::Canceled.into()));
+        }
+        Ok(())
+    }
+
+    fn undo(&self, app: &App<'_>, error: anyhow::Error) -> anyhow::Error {
+        if self.steps.is_empty() {
+            return error;
+        }
+        let mut undone = Vec::new();
+        let mut leftovers = Vec::new();
+        for step in self.steps.iter().rev() {
+            match self.undo_step(app, step) {
+                Ok(Some(description)) => undone.push(description),
+                Ok(None) => {}
+                Err(failure) => leftovers.push(format!("{failure:#}")),
+            }
+        }
+
+        let name = &self.worktree_name;
+        let outcome = match error.downcast_ref() {
+            Some(NewError::Canceled) => "was canceled",
+            _ => "failed",
+        };
+        if leftovers.is_empty() {
+            error.context(format!(
+                "creating worktree '{name}' {outcome} and was rolled back ({})",
+                undone.join(", ")
+            ))
+        } else {
+            error.context(format!(
+                "creating worktree '{name}' {outcome} and rollback is incomplete; clean up manually: {}",
+                leftovers.join("; ")
+            ))
+        }
+    }
+
+    fn undo_step(&self, app: &App<'_>, step: &CreatedStep) -> Result<Option<String>> {
+        match step {
+            CreatedStep::Session { target } => {
+                let name = target.label();
+                let exists = target
+                    .exists(app.runner)
+                    .with_context(|| format!("failed to check tmux session '{name}'"))?;
+                if !exists {
+                    return Ok(None);
+                }
+                target
+                    .kill(app.runner)
+                    .with_context(|| format!("failed to kill tmux session '{name}'"))?;
+                Ok(Some(format!("killed tmux session '{name}'")))
+            }
+            CreatedStep::RegistryEntry { worktrees_dir } => {
+                seshmux_core::registry::remove_entry_by_name(worktrees_dir, &self.worktree_name)
+                    .with_context(|| {
+                        format!(
+                            "failed to remove registry entry '{}' from {}",
+                            self.worktree_name,
+                            seshmux_core::registry::registry_path(worktrees_dir).display()
+                        )
+                    })?;
+                Ok(Some("removed the registry entry".to_string()))
+            }
+            CreatedStep::Branch { name } => {
+                let exists = seshmux_core::git::branch_exists(&self.repo_root, name, app.runner)
+                    .with_context(|| format!("failed to check branch '{name}'"))?;
+                if !exists {
+                    return Ok(None);
+                }
+                seshmux_core::git::force_delete_branch(&self.repo_root, name, app.runner)
+                    .with_context(|| format!("failed to delete branch '{name}'"))?;
+                Ok(Some(format!("deleted branch '{name}'")))
+            }
+            CreatedStep::Worktree { path } => {
+                seshmux_core::git::force_remove_worktree(&self.repo_root, path, app.runner)
+                    .with_context(|| format!("failed to remove worktree {}", path.display()))?;
+                Ok(Some(format!("removed worktree {}", path.display())))
+            }
+        }
+    }
+}
+
+fn resolve_start_point(
+    app: &App<'_>,
+    repo_root: &Path,
+    start_point: &NewStartPoint,
+) -> Result<String> {
+    match start_point {
+        NewStartPoint::CurrentBranch | NewStartPoint::NewBranchFrom(_) => Ok(
+            seshmux_core::git::resolve_current_start_point(repo_root, app.runner)?,
+        ),
+        NewStartPoint::Branch(value) => {
+            let trimmed = value.trim();
+            if trimmed.is_empty() {
+                bail!("start branch cannot be empty");
+            }
+            Ok(trimmed.to_string())
+        }
+        NewStartPoint::Commit(value) => {
+            let trimmed = value.trim();
+            if trimmed.is_empty() {
+                bail!("start commit cannot be empty");
+            }
+            Ok(trimmed.to_string())
+        }
+    }
 }
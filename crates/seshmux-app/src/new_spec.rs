@@ -0,0 +1,409 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use seshmux_core::config::WindowSpec;
+
+use crate::new::{NewError, NewProgress, NewRequest, NewResult, NewStartPoint};
+use crate::runtime;
+use crate::{App, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NewSpec {
+    pub name: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub branch_name: Option<String>,
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub commit: Option<String>,
+    #[serde(default)]
+    pub extras: Vec<String>,
+    #[serde(default)]
+    pub windows: Option<Vec<WindowSpec>>,
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub gitignore: bool,
+    #[serde(default)]
+    pub ignore_other_worktrees: bool,
+    #[serde(default)]
+    pub use_existing_branch: bool,
+    #[serde(default)]
+    pub lfs: Option<bool>,
+    #[serde(default)]
+    pub submodules: Option<bool>,
+    #[serde(default)]
+    pub direnv: Option<bool>,
+    #[serde(default)]
+    pub carry_changes: bool,
+    #[serde(default)]
+    pub connect: bool,
+}
+
+impl NewSpec {
+    pub fn parse(raw: &str) -> Result<Self> {
+        if raw.trim_start().starts_with('{') {
+            serde_json::from_str(raw).context("failed to parse JSON worktree spec")
+        } else {
+            toml::from_str(raw).context("failed to parse TOML worktree spec")
+        }
+    }
+
+    fn start_point(&self) -> Result<NewStartPoint> {
+        match (&self.branch, &self.commit) {
+            (Some(_), Some(_)) => bail!("worktree spec cannot set both branch and commit"),
+            (Some(branch), None) => Ok(NewStartPoint::Branch(branch.clone())),
+            (None, Some(commit)) => Ok(NewStartPoint::Commit(commit.clone())),
+            (None, None) => Ok(NewStartPoint::CurrentBranch),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NewBatch {
+    pub worktrees: Vec<NewSpec>,
+}
+
+impl NewBatch {
+    pub fn parse(raw: &str) -> Result<Self> {
+        let trimmed = raw.trim_start();
+        // `[[worktrees]]` opens TOML too, so only `[` followed by an object or `]` is JSON.
+        let json_array = trimmed
+            .strip_prefix('[')
+            .is_some_and(|rest| rest.trim_start().starts_with(['{', ']']));
+        let batch: Self = if json_array {
+            Self {
+                worktrees: serde_json::from_str(raw)
+                    .context("failed to parse JSON worktree batch")?,
+            }
+        } else if trimmed.starts_with('{') {
+            serde_json::from_str(raw).context("failed to parse JSON worktree batch")?
+        } else {
+            toml::from_str(raw).context("failed to parse TOML worktree batch")?
+        };
+        batch.validate()?;
+        Ok(batch)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.worktrees.is_empty() {
+            bail!("worktree batch lists no worktrees");
+        }
+        let mut names = BTreeSet::new();
+        for spec in &self.worktrees {
+            if !names.insert(spec.name.as_str()) {
+                bail!("worktree batch lists '{}' more than once", spec.name);
+            }
+        }
+        let mut branches = BTreeMap::new();
+        for spec in &self.worktrees {
+            if let Some(branch) = &spec.branch_name
+                && let Some(first) = branches.insert(branch.as_str(), spec.name.as_str())
+            {
+                bail!(
+                    "worktree batch uses branch '{branch}' for both '{first}' and '{}'",
+                    spec.name
+                );
+            }
+        }
+        let connecting: Vec<&str> = self
+            .worktrees
+            .iter()
+            .filter(|spec| spec.connect)
+            .map(|spec| spec.name.as_str())
+            .collect();
+        if connecting.len() > 1 {
+            bail!(
+                "only one worktree in a batch can set connect, found: {}",
+                connecting.join(", ")
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NewBatchOutcome {
+    Created(Box<NewResult>),
+    Failed(String),
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewBatchItem {
+    pub name: String,
+    pub outcome: NewBatchOutcome,
+}
+
+impl<'a> App<'a> {
+    pub fn new_batch(
+        &self,
+        cwd: &Path,
+        batch: NewBatch,
+        keep_going: bool,
+        progress: &mut dyn FnMut(&str, NewProgress),
+    ) -> Vec<NewBatchItem> {
+        let conflicts = self.batch_branch_conflicts(cwd, &batch);
+        let mut items = Vec::with_capacity(batch.worktrees.len());
+        let mut failed = conflicts.iter().any(Option::is_some);
+        for (spec, conflict) in batch.worktrees.into_iter().zip(conflicts) {
+            let name = spec.name.clone();
+            let outcome = if let Some(conflict) = conflict {
+                NewBatchOutcome::Failed(conflict)
+            } else if failed && !keep_going {
+                NewBatchOutcome::Skipped
+            } else {
+                match self.new_from_spec(cwd, spec, &mut |update| progress(&name, update)) {
+                    Ok(result) => NewBatchOutcome::Created(Box::new(result)),
+                    Err(error) => {
+                        failed = true;
+                        NewBatchOutcome::Failed(format!("{error:#}"))
+                    }
+                }
+            };
+            items.push(NewBatchItem { name, outcome });
+        }
+        items
+    }
+
+    fn batch_branch_conflicts(&self, cwd: &Path, batch: &NewBatch) -> Vec<Option<String>> {
+        let lookup = self
+            .ensure_config_ready()
+            .and_then(|config| Ok((config, runtime::resolve_repo_root(self, cwd)?)));
+        let (config, repo_root) = match lookup {
+            Ok(found) => found,
+            Err(error) => return vec![Some(format!("{error:#}")); batch.worktrees.len()],
+        };
+
+        batch
+            .worktrees
+            .iter()
+            .map(|spec| {
+                if spec.ignore_other_worktrees {
+                    return None;
+                }
+                let branch = spec.branch_name.clone().unwrap_or_else(|| {
+                    seshmux_core::config::resolve_branch_name(
+                        config.branch_template.as_deref(),
+                        &spec.name,
+                    )
+                });
+                match self.new_branch_conflict(&repo_root, &branch) {
+                    Ok(None) => None,
+                    Ok(Some(conflict)) => Some(
+                        NewError::BranchCheckedOut {
+                            branch: conflict.branch,
+                            worktree_path: conflict.worktree_path,
+                            worktree_name: conflict.worktree_name,
+                        }
+                        .to_string(),
+                    ),
+                    Err(error) => Some(format!("{error:#}")),
+                }
+            })
+            .collect()
+    }
+
+    pub fn new_from_spec(
+        &self,
+        cwd: &Path,
+        spec: NewSpec,
+        progress: &mut dyn FnMut(NewProgress),
+    ) -> Result<NewResult, Error> {
+        let start_point = spec.start_point()?;
+
+        let selected_extras = self.matching_extras(cwd, &spec.extras, true)?;
+
+        self.new_execute(
+            NewRequest {
+                cwd: cwd.to_path_buf(),
+                worktree_name: spec.name,
+                display_name: spec.display_name,
+                branch_name: spec.branch_name,
+                start_point,
+                add_gitignore_entries: spec.gitignore,
+                selected_extras,
+                windows: spec.windows,
+                project: spec.project,
+                ignore_other_worktrees: spec.ignore_other_worktrees,
+                use_existing_branch: spec.use_existing_branch,
+                lfs_pull: spec.lfs,
+                init_submodules: spec.submodules,
+                direnv_allow: spec.direnv,
+                carry_changes: spec.carry_changes,
+                connect_now: spec.connect,
+            },
+            progress,
+        )
+    }
+
+    pub fn new_quick_request(&self, cwd: &Path, worktree_name: &str) -> Result<NewRequest, Error> {
+        seshmux_core::names::validate_worktree_name(worktree_name).map_err(anyhow::Error::from)?;
+        let quick_new = runtime::quick_new_config(self)?;
+        let selected_extras = self.matching_extras(cwd, &quick_new.extras, false)?;
+
+        Ok(NewRequest {
+            cwd: cwd.to_path_buf(),
+            worktree_name: worktree_name.to_string(),
+            display_name: None,
+            branch_name: None,
+            start_point: match quick_new.start_point {
+                Some(start_point) => NewStartPoint::Branch(start_point.trim().to_string()),
+                None => NewStartPoint::CurrentBranch,
+            },
+            add_gitignore_entries: quick_new.gitignore,
+            selected_extras,
+            windows: None,
+            project: None,
+            ignore_other_worktrees: false,
+            use_existing_branch: false,
+            lfs_pull: None,
+            init_submodules: None,
+            direnv_allow: None,
+            carry_changes: false,
+            connect_now: quick_new.connect,
+        })
+    }
+
+    fn matching_extras(
+        &self,
+        cwd: &Path,
+        patterns: &[String],
+        require_match: bool,
+    ) -> Result<Vec<PathBuf>> {
+        if patterns.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let repo_root = runtime::resolve_repo_root(self, cwd)?;
+        let candidates = self.new_list_extras(&repo_root)?;
+        let mut selected = Vec::new();
+        for pattern in patterns {
+            let matched: Vec<_> = candidates
+                .iter()
+                .filter(|candidate| seshmux_core::extras::extra_matches_glob(pattern, candidate))
+                .collect();
+            if matched.is_empty() && require_match {
+                bail!("extras pattern '{pattern}' matched no untracked or ignored files");
+            }
+            for candidate in matched {
+                if !selected.contains(candidate) {
+                    selected.push(candidate.clone());
+                }
+            }
+        }
+        Ok(selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NewBatch, NewSpec, NewStartPoint};
+
+    #[test]
+    fn parses_toml_and_json_specs() {
+        let toml_spec = NewSpec::parse(
+            r#"
+name = "feature-a"
+branch_name = "feature/a"
+branch = "main"
+extras = [".env"]
+lfs = false
+
+[[windows]]
+name = "editor"
+program = "nvim"
+"#,
+        )
+        .expect("toml spec");
+        let json_spec = NewSpec::parse(
+            r#"{"name": "feature-a", "branch_name": "feature/a", "branch": "main", "extras": [".env"], "lfs": false,
+                "windows": [{"name": "editor", "program": "nvim"}]}"#,
+        )
+        .expect("json spec");
+
+        assert_eq!(toml_spec, json_spec);
+        assert_eq!(toml_spec.branch_name.as_deref(), Some("feature/a"));
+        assert_eq!((toml_spec.lfs, toml_spec.submodules), (Some(false), None));
+        assert_eq!(
+            toml_spec.start_point().expect("start point"),
+            NewStartPoint::Branch("main".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_fields_and_conflicting_start_points() {
+        let error = NewSpec::parse("name = \"w1\"\nbranchh = \"main\"\n").expect_err("typo");
+        assert!(format!("{error:#}").contains("unknown field `branchh`"));
+
+        let spec = NewSpec::parse(r#"{"name": "w1", "branch": "main", "commit": "abc123"}"#)
+            .expect("spec");
+        let error = spec.start_point().expect_err("conflict");
+        assert!(error.to_string().contains("both branch and commit"));
+    }
+
+    #[test]
+    fn parses_batches_from_toml_json_objects_and_json_arrays() {
+        let toml_batch = NewBatch::parse(
+            r#"
+[[worktrees]]
+name = "w1"
+branch = "main"
+extras = [".env"]
+
+[[worktrees]]
+name = "w2"
+connect = true
+"#,
+        )
+        .expect("toml batch");
+        let object_batch = NewBatch::parse(
+            r#"{"worktrees": [{"name": "w1", "branch": "main", "extras": [".env"]}, {"name": "w2", "connect": true}]}"#,
+        )
+        .expect("json object batch");
+        let array_batch = NewBatch::parse(
+            r#"[{"name": "w1", "branch": "main", "extras": [".env"]}, {"name": "w2", "connect": true}]"#,
+        )
+        .expect("json array batch");
+
+        assert_eq!(toml_batch, object_batch);
+        assert_eq!(toml_batch, array_batch);
+        let names: Vec<&str> = toml_batch
+            .worktrees
+            .iter()
+            .map(|spec| spec.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["w1", "w2"]);
+    }
+
+    #[test]
+    fn rejects_empty_batches_repeated_names_and_more_than_one_connect() {
+        let error = NewBatch::parse("[]").expect_err("empty");
+        assert!(error.to_string().contains("lists no worktrees"));
+
+        let error = NewBatch::parse(r#"[{"name": "w1"}, {"name": "w1"}]"#).expect_err("repeat");
+        assert!(error.to_string().contains("'w1' more than once"));
+
+        let error = NewBatch::parse(
+            r#"[{"name": "w1", "connect": true}, {"name": "w2", "connect": true}]"#,
+        )
+        .expect_err("two connects");
+        assert!(error.to_string().contains("found: w1, w2"));
+
+        let error = NewBatch::parse(
+            r#"[{"name": "w1", "branch_name": "fix"}, {"name": "w2", "branch_name": "fix"}]"#,
+        )
+        .expect_err("shared branch");
+        assert!(
+            error
+                .to_string()
+                .contains("uses branch 'fix' for both 'w1' and 'w2'")
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+use thiserror::Error;
+
+use crate::target;
+use crate::{App, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenRequest {
+    pub cwd: PathBuf,
+    pub worktree_name: String,
+    pub location: Option<OpenLocation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenLocation {
+    pub path: PathBuf,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenResult {
+    pub worktree_name: String,
+    pub worktree_path: PathBuf,
+    pub command: Vec<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum OpenError {
+    #[error("worktree '{name}' was not found in worktree.toml")]
+    UnknownWorktree { name: String },
+    #[error("no editor configured; set `editor` in config.toml or export $EDITOR")]
+    NoEditor,
+}
+
+impl<'a> App<'a> {
+    pub fn open(&self, request: OpenRequest) -> Result<OpenResult, Error> {
+        let target = target::resolve_target(self, &request.cwd, &request.worktree_name)?
+            .ok_or_else(|| OpenError::UnknownWorktree {
+                name: request.worktree_name.clone(),
+            })?;
+
+        let worktree_path = target.worktree_path.clone();
+        if !worktree_path.exists() {
+            return Err(anyhow!(
+                "worktree path does not exist on disk: {}",
+                worktree_path.display()
+            )
+            .into());
+        }
+
+        let config = self.ensure_repo_config_ready(&target.repo_root)?;
+        let command =
+            resolve_editor_command(config.editor.as_deref(), std::env::var("EDITOR").ok())
+                .ok_or(OpenError::NoEditor)?;
+
+        let program = command[0].as_str();
+        let location_args = match &request.location {
+            Some(location) => location_args(program, location),
+            None => vec![".".to_string()],
+        };
+        let mut args = command[1..].iter().map(String::as_str).collect::<Vec<_>>();
+        args.extend(location_args.iter().map(String::as_str));
+
+        let status = self
+            .runner
+            .run_interactive(program, &args, Some(&worktree_path))
+            .with_context(|| format!("failed to launch editor '{program}'"))?;
+        if status != 0 {
+            return Err(anyhow!("editor '{program}' exited with status {status}").into());
+        }
+
+        Ok(OpenResult {
+            worktree_name: target.worktree_name,
+            worktree_path,
+            command,
+        })
+    }
+
+    pub fn open_shell(&self, dir: &Path) -> Result<i32, Error> {
+        let shell = login_shell();
+        self.runner
+            .run_interactive(&shell, &[], Some(dir))
+            .with_context(|| format!("failed to start {shell} in {}", dir.display()))
+            .map_err(Error::from)
+    }
+}
+
+pub fn login_shell() -> String {
+    std::env::var("SHELL")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "/bin/sh".to_string())
+}
+
+pub(crate) fn resolve_editor_command(
+    configured: Option<&[String]>,
+    env_editor: Option<String>,
+) -> Option<Vec<String>> {
+    if let Some(configured) = configured
+        && !configured.is_empty()
+    {
+        return Some(configured.to_vec());
+    }
+
+    let parts = env_editor?
+        .split_whitespace()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    if parts.is_empty() { None } else { Some(parts) }
+}
+
+fn location_args(program: &str, location: &OpenLocation) -> Vec<String> {
+    let path = location.path.display();
+    let editor = Path::new(program)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(program);
+
+    match editor {
+        "code" | "code-insiders" | "codium" | "cursor" => {
+            vec!["--goto".to_string(), format!("{path}:{}", location.line)]
+        }
+        "zed" | "subl" | "hx" | "helix" => vec![format!("{path}:{}", location.line)],
+        _ => vec![format!("+{}", location.line), path.to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{OpenLocation, location_args, resolve_editor_command};
+
+    #[test]
+    fn configured_editor_wins_over_environment() {
+        let configured = vec!["code".to_string(), "--wait".to_string()];
+        assert_eq!(
+            resolve_editor_command(Some(&configured), Some("nvim".to_string())),
+            Some(configured.clone())
+        );
+    }
+
+    #[test]
+    fn environment_editor_is_split_into_arguments() {
+        assert_eq!(
+            resolve_editor_command(None, Some("emacsclient -t".to_string())),
+            Some(vec!["emacsclient".to_string(), "-t".to_string()])
+        );
+        assert_eq!(resolve_editor_command(None, Some("  ".to_string())), None);
+        assert_eq!(resolve_editor_command(None, None), None);
+    }
+
+    #[test]
+    fn location_args_follow_each_editor_convention() {
+        let location = OpenLocation {
+            path: PathBuf::from("src/main.rs"),
+            line: 12,
+        };
+
+        assert_eq!(location_args("nvim", &location), vec!["+12", "src/main.rs"]);
+        assert_eq!(
+            location_args("/usr/bin/code", &location),
+            vec!["--goto", "src/main.rs:12"]
+        );
+        assert_eq!(location_args("hx", &location), vec!["src/main.rs:12"]);
+    }
+}
@@ -0,0 +1,40 @@
+use std::path::Path;
+
+use anyhow::Context;
+
+use crate::runtime;
+use crate::{App, Error};
+
+const RECENT_COMMANDS_LIMIT: usize = 20;
+
+impl<'a> App<'a> {
+    pub fn recent_commands(&self, cwd: &Path) -> Result<Vec<String>, Error> {
+        let repo_root = runtime::resolve_repo_root(self, cwd)?;
+        let worktrees_dir = runtime::worktrees_dir(self, &repo_root)?;
+        seshmux_core::registry::load_recent_commands(&worktrees_dir)
+            .with_context(|| {
+                format!(
+                    "failed to read recent commands from {}",
+                    worktrees_dir.display()
+                )
+            })
+            .map_err(Error::from)
+    }
+
+    pub fn record_command(&self, cwd: &Path, command: &str) -> Result<(), Error> {
+        let repo_root = runtime::resolve_repo_root(self, cwd)?;
+        let worktrees_dir = runtime::worktrees_dir(self, &repo_root)?;
+        seshmux_core::registry::record_recent_command(
+            &worktrees_dir,
+            command,
+            RECENT_COMMANDS_LIMIT,
+        )
+        .with_context(|| {
+            format!(
+                "failed to record a recent command in {}",
+                worktrees_dir.display()
+            )
+        })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+use seshmux_core::git::CommitRef;
+
+use crate::runtime;
+use crate::{App, Error};
+
+const REVIEW_DIR_NAME: &str = ".review";
+const REVIEW_MAX_COMMITS: usize = 25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewMode {
+    EachCommit,
+    Endpoints,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewRequest {
+    pub cwd: PathBuf,
+    pub range: String,
+    pub mode: ReviewMode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewWorktree {
+    pub commit: CommitRef,
+    pub path: PathBuf,
+    pub reused: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewResult {
+    pub repo_root: PathBuf,
+    pub worktrees: Vec<ReviewWorktree>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewCleanResult {
+    pub repo_root: PathBuf,
+    pub removed: Vec<PathBuf>,
+}
+
+impl<'a> App<'a> {
+    pub fn review(&self, request: ReviewRequest) -> Result<ReviewResult, Error> {
+        let repo_root = runtime::resolve_repo_root(self, &request.cwd)?;
+        let commits = review_commits(self, &repo_root, &request.range, request.mode)?;
+
+        if commits.is_empty() {
+            return Err(anyhow!("commit range '{}' contains no commits", request.range).into());
+        }
+
+        if commits.len() > REVIEW_MAX_COMMITS {
+            return Err(anyhow!(
+                "commit range '{}' contains {} commits (limit {REVIEW_MAX_COMMITS}); narrow the range or use --endpoints",
+                request.range,
+                commits.len()
+            ).into());
+        }
+
+        let review_dir = runtime::worktrees_dir(self, &repo_root)?.join(REVIEW_DIR_NAME);
+        std::fs::create_dir_all(&review_dir)
+            .with_context(|| format!("failed to create {}", review_dir.display()))?;
+
+        let mut worktrees = Vec::new();
+        for commit in commits {
+            let path = review_dir.join(&commit.short_hash);
+            let reused = path.exists();
+
+            if !reused {
+                seshmux_core::git::create_detached_worktree(
+                    &repo_root,
+                    &path,
+                    &commit.hash,
+                    self.runner,
+                )
+                .with_context(|| {
+                    format!(
+                        "failed to create review worktree for {} at {}",
+                        commit.short_hash,
+                        path.display()
+                    )
+                })?;
+            }
+
+            worktrees.push(ReviewWorktree {
+                commit,
+                path,
+                reused,
+            });
+        }
+
+        Ok(ReviewResult {
+            repo_root,
+            worktrees,
+        })
+    }
+
+    pub fn review_clean(&self, cwd: &Path) -> Result<ReviewCleanResult, Error> {
+        let repo_root = runtime::resolve_repo_root(self, cwd)?;
+        let review_dir = runtime::worktrees_dir(self, &repo_root)?.join(REVIEW_DIR_NAME);
+        let review_key = runtime::comparable_path(&review_dir);
+
+        let paths = seshmux_core::git::list_worktree_paths(&repo_root, self.runner)
+            .with_context(|| format!("failed to list git worktrees in {}", repo_root.display()))?;
+
+        let mut removed = Vec::new();
+        for path in paths
+            .into_iter()
+            .filter(|path| runtime::comparable_path(path).starts_with(&review_key))
+        {
+            seshmux_core::git::force_remove_worktree(&repo_root, &path, self.runner)
+                .with_context(|| format!("failed to remove review worktree {}", path.display()))?;
+            removed.push(path);
+        }
+
+        let _ = std::fs::remove_dir(&review_dir);
+
+        Ok(ReviewCleanResult { repo_root, removed })
+    }
+}
+
+fn review_commits(
+    app: &App<'_>,
+    repo_root: &Path,
+    range: &str,
+    mode: ReviewMode,
+) -> Result<Vec<CommitRef>> {
+    match mode {
+        ReviewMode::EachCommit => seshmux_core::git::commits_in_range(repo_root, range, app.runner)
+            .with_context(|| format!("failed to list commits in '{range}'")),
+        ReviewMode::Endpoints => {
+            let Some((base, head)) = split_range(range) else {
+                bail!("--endpoints requires a range in the form <base>..<head>");
+            };
+
+            let merge_base = seshmux_core::git::merge_base(repo_root, base, head, app.runner)
+                .with_context(|| {
+                    format!("failed to resolve merge-base of '{base}' and '{head}'")
+                })?;
+            let base_commit = seshmux_core::git::resolve_commit(repo_root, &merge_base, app.runner)
+                .with_context(|| format!("failed to resolve merge-base commit {merge_base}"))?;
+            let head_commit = seshmux_core::git::resolve_commit(repo_root, head, app.runner)
+                .with_context(|| format!("failed to resolve '{head}'"))?;
+
+            if base_commit.hash == head_commit.hash {
+                return Ok(vec![head_commit]);
+            }
+
+            Ok(vec![base_commit, head_commit])
+        }
+    }
+}
+
+fn split_range(range: &str) -> Option<(&str, &str)> {
+    let (base, head) = range.split_once("...").or_else(|| range.split_once(".."))?;
+    let base = base.trim();
+    let head = head.trim();
+
+    if base.is_empty() || head.is_empty() {
+        return None;
+    }
+
+    Some((base, head))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_range;
+
+    #[test]
+    fn split_range_accepts_two_and_three_dot_forms() {
+        assert_eq!(split_range("main..feature"), Some(("main", "feature")));
+        assert_eq!(split_range("main...feature"), Some(("main", "feature")));
+        assert_eq!(split_range("main"), None);
+        assert_eq!(split_range("..feature"), None);
+    }
+}
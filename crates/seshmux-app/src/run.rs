@@ -0,0 +1,118 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result, anyhow};
+use seshmux_core::tmux::SessionHealth;
+
+use crate::runtime;
+use crate::target;
+use crate::{App, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunRequest {
+    pub cwd: PathBuf,
+    pub worktree_name: String,
+    pub command: Vec<String>,
+    pub in_window: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunResult {
+    pub worktree_name: String,
+    pub worktree_path: PathBuf,
+    pub status: i32,
+    pub window: Option<String>,
+}
+
+impl<'a> App<'a> {
+    pub fn run_in_worktree(&self, request: RunRequest) -> Result<RunResult, Error> {
+        let Some((program, args)) = request.command.split_first() else {
+            return Err(anyhow!("no command given to run").into());
+        };
+
+        let target = target::resolve_target(self, &request.cwd, &request.worktree_name)?
+            .ok_or_else(|| {
+                anyhow!(
+                    "worktree '{}' was not found in worktree.toml",
+                    request.worktree_name
+                )
+            })?;
+        let worktree_path = target.worktree_path.clone();
+        if !worktree_path.exists() {
+            return Err(anyhow!(
+                "worktree path does not exist on disk: {}",
+                worktree_path.display()
+            )
+            .into());
+        }
+
+        if !request.in_window {
+            let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+            let status = self
+                .runner
+                .run_interactive(program, &args, Some(&worktree_path))
+                .with_context(|| format!("failed to run '{program}'"))?;
+            return Ok(RunResult {
+                worktree_name: target.worktree_name,
+                worktree_path,
+                status,
+                window: None,
+            });
+        }
+
+        runtime::require_tmux_backend(self, "seshmux run --window")?;
+        let session_name = target.session.session_name().to_string();
+        let health = target
+            .session
+            .health(self.runner)
+            .with_context(|| format!("failed to query tmux session '{session_name}'"))?;
+        if health != SessionHealth::Running {
+            return Err(anyhow!(
+                "tmux session '{}' is not running; attach to it first or run without --window",
+                target.session.label()
+            )
+            .into());
+        }
+
+        let server = runtime::tmux_server(self)?;
+        let window_name = format!("run-{}", program_name(program));
+        let channel = run_channel();
+        let window_id = seshmux_core::tmux::start_run_window(
+            &session_name,
+            &window_name,
+            &worktree_path,
+            &request.command,
+            &channel,
+            &server,
+            self.runner,
+        )
+        .with_context(|| format!("failed to open a run window in '{session_name}'"))?;
+        let status = seshmux_core::tmux::wait_for_run(&channel, &window_id, &server, self.runner)
+            .with_context(|| format!("failed to wait for '{program}' in '{session_name}'"))?
+            .ok_or_else(|| anyhow!("'{program}' finished without reporting an exit status"))?;
+
+        Ok(RunResult {
+            worktree_name: target.worktree_name,
+            worktree_path,
+            status,
+            window: Some(format!("{session_name}:{window_name}")),
+        })
+    }
+}
+
+fn program_name(program: &str) -> String {
+    let name = Path::new(program)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| program.to_string());
+    seshmux_core::names::sanitize_repo_component(&name)
+}
+
+// Unique per invocation so concurrent runs never wake each other.
+fn run_channel() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or_default();
+    format!("seshmux-run-{}-{nanos}", std::process::id())
+}
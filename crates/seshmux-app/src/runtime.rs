@@ -1,18 +1,69 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
+
+use seshmux_core::config::{
+    CheckoutConfig, DefaultsConfig, DeleteMode, GitignoreConfig, NamesConfig, ProjectConfig,
+    QuickNewConfig, SeshmuxConfig, SessionBackend, SessionLayout, TrashConfig, UpdateConfig,
+    WindowSpec,
+};
+use seshmux_core::registry::RegistryEntry;
+use seshmux_core::session_env::WorktreeTemplateContext;
+use seshmux_core::session_model::SessionTarget;
+use seshmux_core::tabs::TabBackend;
+use seshmux_core::tmux::TmuxServer;
+use seshmux_core::vcs::{Vcs, VcsPreference};
 
 use crate::App;
 
 pub(crate) fn resolve_repo_root(app: &App<'_>, cwd: &Path) -> Result<PathBuf> {
-    seshmux_core::git::repo_root(cwd, app.runner).with_context(|| {
+    let vcs = vcs(app, cwd)?;
+    vcs.repo_root(cwd).with_context(|| {
         format!(
-            "failed to resolve git repository root from {}",
+            "failed to resolve {} repository root from {}",
+            vcs.kind().command(),
             cwd.display()
         )
     })
 }
 
+pub(crate) fn vcs<'r>(app: &App<'r>, cwd: &Path) -> Result<Box<dyn Vcs + 'r>> {
+    let preference = configured(app, &["vcs"])?.map_or(VcsPreference::Auto, |config| config.vcs);
+
+    Ok(seshmux_core::vcs::for_kind(
+        seshmux_core::vcs::detect(cwd, preference),
+        app.runner,
+    ))
+}
+
+pub(crate) fn worktrees_dir(app: &App<'_>, repo_root: &Path) -> Result<PathBuf> {
+    let template = configured(app, &["worktrees_dir"])?.and_then(|config| config.worktrees_dir);
+    let worktrees_dir = seshmux_core::config::resolve_worktrees_dir(template.as_deref(), repo_root);
+    claim_worktrees_dir(&worktrees_dir, repo_root)?;
+    Ok(worktrees_dir)
+}
+
+pub(crate) fn claim_worktrees_dir(worktrees_dir: &Path, repo_root: &Path) -> Result<()> {
+    if worktrees_dir.starts_with(repo_root) {
+        return Ok(());
+    }
+    Ok(seshmux_core::registry::claim_registry(
+        worktrees_dir,
+        &comparable_path(repo_root),
+    )?)
+}
+
+fn configured(app: &App<'_>, fields: &[&str]) -> Result<Option<SeshmuxConfig>> {
+    let config_path =
+        seshmux_core::config::resolve_config_path().context("failed to resolve config path")?;
+    if !config_path.exists() {
+        return Ok(None);
+    }
+
+    Ok(Some(app.ensure_config_fields(fields)?))
+}
+
 pub(crate) fn repo_component(repo_root: &Path) -> &str {
     repo_root
         .file_name()
@@ -24,8 +75,198 @@ pub(crate) fn session_name_for(repo_root: &Path, worktree_name: &str) -> String
     seshmux_core::tmux::session_name(repo_component(repo_root), worktree_name)
 }
 
-pub(crate) fn inside_tmux() -> bool {
-    std::env::var_os("TMUX").is_some()
+pub(crate) fn session_layout(app: &App<'_>) -> Result<SessionLayout> {
+    Ok(configured(app, &["tmux.layout"])?
+        .map_or_else(SessionLayout::default, |config| config.tmux.layout))
+}
+
+pub(crate) fn tmux_server(app: &App<'_>) -> Result<TmuxServer> {
+    Ok(configured(app, &["tmux.socket_name", "tmux.socket_path"])?
+        .map_or_else(TmuxServer::default, |config| {
+            TmuxServer::from_config(&config.tmux)
+        }))
+}
+
+pub(crate) fn session_backend(app: &App<'_>) -> Result<SessionBackend> {
+    Ok(configured(app, &["tmux.backend"])?
+        .map_or_else(SessionBackend::default, |config| config.tmux.backend))
+}
+
+pub(crate) fn require_tmux_backend(app: &App<'_>, command: &str) -> Result<()> {
+    let backend = session_backend(app)?;
+    if !backend.is_tmux() {
+        bail!(
+            "{command} needs the tmux backend, but tmux.backend is '{}'",
+            backend.as_str()
+        );
+    }
+    Ok(())
+}
+
+pub(crate) fn branch_template(app: &App<'_>) -> Result<Option<String>> {
+    Ok(configured(app, &["branch_template"])?.and_then(|config| config.branch_template))
+}
+
+pub(crate) fn worktree_path_template(app: &App<'_>) -> Result<Option<String>> {
+    Ok(configured(app, &["worktree_path_template"])?
+        .and_then(|config| config.worktree_path_template))
+}
+
+pub(crate) fn checkout_config(app: &App<'_>) -> Result<CheckoutConfig> {
+    Ok(configured(app, &["checkout"])?
+        .map_or_else(CheckoutConfig::default, |config| config.checkout))
+}
+
+pub(crate) fn quick_new_config(app: &App<'_>) -> Result<QuickNewConfig> {
+    Ok(configured(app, &["quick_new"])?
+        .map_or_else(QuickNewConfig::default, |config| config.quick_new))
+}
+
+pub(crate) fn defaults_config(app: &App<'_>) -> Result<DefaultsConfig> {
+    Ok(configured(app, &["defaults"])?
+        .map_or_else(DefaultsConfig::default, |config| config.defaults))
+}
+
+pub(crate) fn gitignore_config(app: &App<'_>) -> Result<GitignoreConfig> {
+    Ok(configured(app, &["gitignore"])?
+        .map_or_else(GitignoreConfig::default, |config| config.gitignore))
+}
+
+pub(crate) fn delete_mode(app: &App<'_>) -> Result<(DeleteMode, TrashConfig)> {
+    Ok(configured(app, &["delete_mode", "trash"])?.map_or_else(
+        || (DeleteMode::default(), TrashConfig::default()),
+        |config| (config.delete_mode, config.trash),
+    ))
+}
+
+pub(crate) fn environment_templates(app: &App<'_>) -> Result<BTreeMap<String, String>> {
+    Ok(configured(app, &["tmux.environment"])?
+        .map(|config| config.tmux.environment)
+        .unwrap_or_default())
+}
+
+pub(crate) fn names_config(app: &App<'_>) -> Result<NamesConfig> {
+    Ok(configured(app, &["names"])?.map_or_else(NamesConfig::default, |config| config.names))
+}
+
+pub(crate) fn update_config(app: &App<'_>) -> Result<UpdateConfig> {
+    Ok(configured(app, &["update"])?.map_or_else(UpdateConfig::default, |config| config.update))
+}
+
+pub(crate) fn projects(app: &App<'_>, repo_root: &Path) -> Result<BTreeMap<String, ProjectConfig>> {
+    let config_path =
+        seshmux_core::config::resolve_config_path().context("failed to resolve config path")?;
+    if !config_path.exists() {
+        return Ok(crate::config::load_repo_config_if_present(repo_root)?
+            .map(|repo_config| repo_config.projects)
+            .unwrap_or_default());
+    }
+
+    Ok(app.ensure_repo_config_ready(repo_root)?.projects)
+}
+
+pub(crate) fn find_project<'c>(config: &'c SeshmuxConfig, name: &str) -> Result<&'c ProjectConfig> {
+    if let Some(project) = config.projects.get(name) {
+        return Ok(project);
+    }
+    if config.projects.is_empty() {
+        bail!("unknown project '{name}'; no projects are defined in [projects]");
+    }
+    let names: Vec<_> = config.projects.keys().map(String::as_str).collect();
+    bail!(
+        "unknown project '{name}'; defined projects: {}",
+        names.join(", ")
+    )
+}
+
+pub(crate) fn project_session<'c>(
+    config: &'c SeshmuxConfig,
+    worktree_path: &Path,
+    project: Option<&str>,
+) -> (PathBuf, &'c [WindowSpec]) {
+    match project.and_then(|name| config.projects.get(name)) {
+        Some(project) => (
+            project.session_dir(worktree_path),
+            project.windows.as_deref().unwrap_or(&config.tmux.windows),
+        ),
+        None => (worktree_path.to_path_buf(), &config.tmux.windows),
+    }
+}
+
+pub(crate) fn session_target_for(
+    layout: SessionLayout,
+    backend: SessionBackend,
+    server: &TmuxServer,
+    repo_root: &Path,
+    worktree_name: &str,
+) -> SessionTarget {
+    SessionTarget::for_worktree(
+        layout,
+        backend,
+        server,
+        repo_component(repo_root),
+        worktree_name,
+    )
+}
+
+pub(crate) fn session_target_for_entry(
+    layout: SessionLayout,
+    backend: SessionBackend,
+    server: &TmuxServer,
+    repo_root: &Path,
+    entry: &RegistryEntry,
+) -> SessionTarget {
+    let layout = entry.layout.unwrap_or(layout);
+    if let Some(backend) = TabBackend::for_backend(backend) {
+        let title = match (&entry.session_name, layout) {
+            (Some(session), SessionLayout::Session) => session.clone(),
+            _ => session_name_for(repo_root, &entry.name),
+        };
+        return SessionTarget::Tab {
+            backend,
+            title,
+            worktree: entry.name.clone(),
+        };
+    }
+    match (&entry.session_name, layout) {
+        (Some(session), SessionLayout::Session) => SessionTarget::Session {
+            server: server.clone(),
+            session: session.clone(),
+        },
+        (Some(session), SessionLayout::Window) => SessionTarget::Window {
+            server: server.clone(),
+            session: session.clone(),
+            window: entry.name.clone(),
+        },
+        (None, layout) => session_target_for(layout, backend, server, repo_root, &entry.name),
+    }
+}
+
+pub(crate) fn session_environment(
+    config: &SeshmuxConfig,
+    repo_root: &Path,
+    worktree_name: &str,
+    branch: &str,
+    worktree_path: &Path,
+) -> Vec<(String, String)> {
+    seshmux_core::session_env::render_session_environment(
+        &config.tmux.environment,
+        WorktreeTemplateContext {
+            repo: repo_component(repo_root),
+            name: worktree_name,
+            branch,
+            path: worktree_path,
+        },
+    )
+}
+
+// tmux, git, and the registry may report the same directory through different symlinks.
+pub(crate) fn comparable_path(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+pub(crate) fn inside_tmux(server: &TmuxServer) -> bool {
+    std::env::var("TMUX").is_ok_and(|tmux| server.serves_client(&tmux))
 }
 
 #[cfg(test)]
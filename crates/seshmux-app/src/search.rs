@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use seshmux_core::git::GrepMatch;
+
+use crate::catalog::WorktreeCatalog;
+use crate::{App, Error};
+
+const MAX_MATCHES_PER_WORKTREE: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchRequest {
+    pub cwd: PathBuf,
+    pub query: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult {
+    pub repo_root: PathBuf,
+    pub query: String,
+    pub groups: Vec<SearchGroup>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchGroup {
+    pub worktree_name: String,
+    pub worktree_path: PathBuf,
+    pub matches: Vec<GrepMatch>,
+    pub truncated: bool,
+}
+
+impl<'a> App<'a> {
+    pub fn search_worktrees(&self, request: SearchRequest) -> Result<SearchResult, Error> {
+        let catalog = WorktreeCatalog::load(self, &request.cwd)?;
+
+        let mut groups = Vec::new();
+        for entry in catalog.entries() {
+            let worktree_path = PathBuf::from(&entry.path);
+            if !worktree_path.exists() {
+                continue;
+            }
+
+            let mut matches = seshmux_core::git::grep(&worktree_path, &request.query, self.runner)
+                .with_context(|| format!("failed to search worktree '{}'", entry.name))?;
+            if matches.is_empty() {
+                continue;
+            }
+            let truncated = matches.len() > MAX_MATCHES_PER_WORKTREE;
+            matches.truncate(MAX_MATCHES_PER_WORKTREE);
+
+            groups.push(SearchGroup {
+                worktree_name: entry.name.clone(),
+                worktree_path,
+                matches,
+                truncated,
+            });
+        }
+        groups.sort_by(|left, right| left.worktree_name.cmp(&right.worktree_name));
+
+        Ok(SearchResult {
+            repo_root: catalog.repo_root().to_path_buf(),
+            query: request.query,
+            groups,
+        })
+    }
+}
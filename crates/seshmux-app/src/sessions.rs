@@ -0,0 +1,192 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+use seshmux_core::config::SessionLayout;
+use seshmux_core::config::resolve_config_path;
+use seshmux_core::tabs::TabBackend;
+
+use crate::dash::dash_repo_roots;
+use crate::runtime;
+use crate::target;
+use crate::{App, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KillAllSessionsResult {
+    pub repo_roots: Vec<PathBuf>,
+    pub killed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SessionScope {
+    #[default]
+    Repo,
+    Known,
+    All,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManagedSessions {
+    pub repo_roots: Vec<PathBuf>,
+    pub session_names: Vec<String>,
+    pub current: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KillSessionResult {
+    pub worktree_name: String,
+    pub session_name: String,
+    pub killed: bool,
+}
+
+impl<'a> App<'a> {
+    pub fn managed_sessions(
+        &self,
+        cwd: &Path,
+        scope: SessionScope,
+    ) -> Result<ManagedSessions, Error> {
+        let mut repo_roots = Vec::new();
+        match scope {
+            SessionScope::Repo => repo_roots.push(runtime::resolve_repo_root(self, cwd)?),
+            SessionScope::Known | SessionScope::All => {
+                if let Ok(repo_root) = runtime::resolve_repo_root(self, cwd) {
+                    repo_roots.push(repo_root);
+                }
+                let config_path = resolve_config_path().context("failed to resolve config path")?;
+                if config_path.exists() {
+                    for root in dash_repo_roots(&self.ensure_config_ready()?.dash) {
+                        if let Ok(repo_root) = runtime::resolve_repo_root(self, &root)
+                            && !repo_roots.contains(&repo_root)
+                        {
+                            repo_roots.push(repo_root);
+                        }
+                    }
+                }
+            }
+        }
+
+        let layout = runtime::session_layout(self)?;
+        let backend = runtime::session_backend(self)?;
+        let server = runtime::tmux_server(self)?;
+        let mut owned = BTreeSet::new();
+        for repo_root in &repo_roots {
+            if layout == SessionLayout::Window {
+                owned.insert(seshmux_core::tmux::repo_session_name(
+                    runtime::repo_component(repo_root),
+                ));
+            }
+            let worktrees_dir = runtime::worktrees_dir(self, repo_root)?;
+            for entry in seshmux_core::registry::load_registry(&worktrees_dir).unwrap_or_default() {
+                owned.insert(
+                    runtime::session_target_for_entry(layout, backend, &server, repo_root, &entry)
+                        .session_name()
+                        .to_string(),
+                );
+            }
+        }
+        let backend = TabBackend::for_backend(runtime::session_backend(self)?);
+        let running = match backend {
+            Some(backend) => seshmux_core::tabs::list_tab_titles(backend, self.runner)
+                .with_context(|| format!("failed to list {} tabs", backend.program()))?,
+            None => {
+                seshmux_core::tmux::list_session_names(&runtime::tmux_server(self)?, self.runner)
+                    .context("failed to list tmux sessions")?
+            }
+        };
+        let mut session_names: Vec<String> = running
+            .into_iter()
+            .filter(|name| {
+                owned.contains(name)
+                    || (scope == SessionScope::All
+                        && seshmux_core::tmux::is_worktree_session_name(name))
+            })
+            .collect();
+        let current = match backend {
+            Some(_) => None,
+            None => current_session_last(self, &mut session_names)?,
+        };
+
+        Ok(ManagedSessions {
+            repo_roots,
+            session_names,
+            current,
+        })
+    }
+
+    pub fn kill_all_sessions(
+        &self,
+        cwd: &Path,
+        scope: SessionScope,
+    ) -> Result<KillAllSessionsResult, Error> {
+        let sessions = self.managed_sessions(cwd, scope)?;
+        Ok(KillAllSessionsResult {
+            repo_roots: sessions.repo_roots,
+            killed: self.kill_sessions(&sessions.session_names)?,
+        })
+    }
+
+    pub fn kill_sessions(&self, session_names: &[String]) -> Result<Vec<String>, Error> {
+        Ok(kill_named_sessions(self, session_names)?)
+    }
+
+    pub fn kill_worktree_session(
+        &self,
+        cwd: &Path,
+        worktree_name: &str,
+        allow_foreign: bool,
+    ) -> Result<KillSessionResult, Error> {
+        let target = target::resolve_target(self, cwd, worktree_name)?
+            .ok_or_else(|| anyhow!("worktree '{worktree_name}' was not found in worktree.toml"))?;
+        target::check_ownership(self, &target, "kill the session of", allow_foreign)?;
+        let session_name = target.session.label();
+        let running = target
+            .session
+            .exists(self.runner)
+            .with_context(|| format!("failed to query tmux session '{session_name}'"))?;
+        if running {
+            target
+                .session
+                .kill(self.runner)
+                .with_context(|| format!("failed to kill tmux session '{session_name}'"))?;
+        }
+
+        Ok(KillSessionResult {
+            worktree_name: target.worktree_name,
+            session_name,
+            killed: running,
+        })
+    }
+}
+
+// Killing the session we are running in ends this process, so it goes last.
+fn current_session_last(app: &App<'_>, session_names: &mut Vec<String>) -> Result<Option<String>> {
+    let server = runtime::tmux_server(app)?;
+    if runtime::inside_tmux(&server)
+        && let Some(current) = seshmux_core::tmux::current_session(&server, app.runner)
+            .context("failed to resolve the current tmux session")?
+        && let Some(index) = session_names.iter().position(|name| *name == current)
+    {
+        let current = session_names.remove(index);
+        session_names.push(current.clone());
+        return Ok(Some(current));
+    }
+    Ok(None)
+}
+
+fn kill_named_sessions(app: &App<'_>, session_names: &[String]) -> Result<Vec<String>> {
+    let backend = TabBackend::for_backend(runtime::session_backend(app)?);
+    let server = runtime::tmux_server(app)?;
+    let mut killed = Vec::with_capacity(session_names.len());
+    for session_name in session_names {
+        match backend {
+            Some(backend) => seshmux_core::tabs::close_tab(backend, session_name, app.runner)
+                .with_context(|| {
+                    format!("failed to close {} tab '{session_name}'", backend.program())
+                })?,
+            None => seshmux_core::tmux::kill_session(session_name, &server, app.runner)
+                .with_context(|| format!("failed to kill tmux session '{session_name}'"))?,
+        }
+        killed.push(session_name.clone());
+    }
+    Ok(killed)
+}
@@ -0,0 +1,176 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::config;
+use crate::runtime;
+use crate::{App, Error};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SkipRuleSource {
+    Registry,
+    Default,
+    RepoConfig,
+}
+
+impl SkipRuleSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Registry => "registry",
+            Self::Default => "default",
+            Self::RepoConfig => ".seshmux.toml",
+        }
+    }
+
+    pub fn editable(self) -> bool {
+        self != Self::RepoConfig
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkipRule {
+    pub bucket: String,
+    pub source: SkipRuleSource,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkipRules {
+    pub repo_root: PathBuf,
+    pub registry_path: PathBuf,
+    pub rules: Vec<SkipRule>,
+}
+
+impl<'a> App<'a> {
+    pub fn skip_rules(&self, cwd: &Path) -> Result<SkipRules, Error> {
+        let repo_root = runtime::resolve_repo_root(self, cwd)?;
+        let worktrees_dir = runtime::worktrees_dir(self, &repo_root)?;
+        skip_rules_in(repo_root, &worktrees_dir).map_err(Error::from)
+    }
+
+    pub fn add_skip_rule(&self, cwd: &Path, bucket: &str) -> Result<SkipRules, Error> {
+        let bucket = normalize_bucket(bucket)?;
+        self.update_skip_rules(cwd, |_, buckets| {
+            if !buckets.insert(bucket.clone()) {
+                bail!("'{bucket}' is already an always-skip bucket");
+            }
+            Ok(())
+        })
+        .map_err(Error::from)
+    }
+
+    pub fn remove_skip_rule(&self, cwd: &Path, bucket: &str) -> Result<SkipRules, Error> {
+        let bucket = bucket.trim().to_string();
+        self.update_skip_rules(cwd, |repo_root, buckets| {
+            if buckets.remove(&bucket) {
+                return Ok(());
+            }
+            if repo_config_buckets(repo_root)?.contains(&bucket) {
+                bail!(
+                    "'{bucket}' comes from [extras] always_skip_buckets in {}; edit that file to remove it",
+                    seshmux_core::config::REPO_CONFIG_FILE_NAME
+                );
+            }
+            Err(anyhow!("'{bucket}' is not an always-skip bucket"))
+        })
+            .map_err(Error::from)
+    }
+
+    /// Renaming is a single write so a failure never leaves the old bucket removed without
+    /// the new one recorded.
+    pub fn replace_skip_rule(&self, cwd: &Path, from: &str, to: &str) -> Result<SkipRules, Error> {
+        let from = from.trim().to_string();
+        let to = normalize_bucket(to)?;
+        self.update_skip_rules(cwd, |_, buckets| {
+            if !buckets.remove(&from) {
+                bail!("'{from}' is not an always-skip bucket");
+            }
+            buckets.insert(to.clone());
+            Ok(())
+        })
+        .map_err(Error::from)
+    }
+
+    fn update_skip_rules(
+        &self,
+        cwd: &Path,
+        edit: impl FnOnce(&Path, &mut BTreeSet<String>) -> Result<()>,
+    ) -> Result<SkipRules> {
+        let repo_root = runtime::resolve_repo_root(self, cwd)?;
+        let worktrees_dir = runtime::worktrees_dir(self, &repo_root)?;
+        let mut buckets = load_registry_buckets(&worktrees_dir)?.buckets;
+        edit(&repo_root, &mut buckets)?;
+        seshmux_core::registry::save_always_skip_buckets(&worktrees_dir, &buckets).with_context(
+            || {
+                format!(
+                    "failed to persist extras skip settings in {}",
+                    worktrees_dir.display()
+                )
+            },
+        )?;
+        skip_rules_in(repo_root, &worktrees_dir)
+    }
+}
+
+fn skip_rules_in(repo_root: PathBuf, worktrees_dir: &Path) -> Result<SkipRules> {
+    let loaded = load_registry_buckets(worktrees_dir)?;
+    let source = if loaded.registry_missing || loaded.configured_buckets.is_empty() {
+        SkipRuleSource::Default
+    } else {
+        SkipRuleSource::Registry
+    };
+
+    let mut rules: Vec<SkipRule> = loaded
+        .buckets
+        .into_iter()
+        .map(|bucket| SkipRule { bucket, source })
+        .collect();
+    rules.extend(
+        repo_config_buckets(&repo_root)?
+            .into_iter()
+            .map(|bucket| SkipRule {
+                bucket,
+                source: SkipRuleSource::RepoConfig,
+            }),
+    );
+    rules.sort_by(|left, right| (&left.bucket, left.source).cmp(&(&right.bucket, right.source)));
+
+    Ok(SkipRules {
+        repo_root,
+        registry_path: seshmux_core::registry::registry_path(worktrees_dir),
+        rules,
+    })
+}
+
+fn load_registry_buckets(
+    worktrees_dir: &Path,
+) -> Result<seshmux_core::registry::AlwaysSkipBucketsLoad> {
+    seshmux_core::registry::load_always_skip_buckets_for_indexing(worktrees_dir).with_context(
+        || {
+            format!(
+                "failed to load extras skip settings in {}",
+                worktrees_dir.display()
+            )
+        },
+    )
+}
+
+fn repo_config_buckets(repo_root: &Path) -> Result<BTreeSet<String>> {
+    Ok(config::load_repo_config_if_present(repo_root)?
+        .and_then(|repo_config| repo_config.extras)
+        .map(|extras| {
+            extras
+                .always_skip_buckets
+                .iter()
+                .map(|bucket| bucket.trim().to_string())
+                .filter(|bucket| !bucket.is_empty())
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+fn normalize_bucket(bucket: &str) -> Result<String> {
+    let clean = seshmux_core::extras::normalize_extra_relative_path(Path::new(bucket.trim()))
+        .map_err(|_| anyhow!("'{bucket}' is not a repository-relative path"))?;
+    Ok(clean.to_string_lossy().to_string())
+}
@@ -1,6 +1,11 @@
 use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
+use seshmux_core::config::{SessionBackend, SessionLayout};
+use seshmux_core::registry::RegistryEntry;
+use seshmux_core::session_model::SessionTarget;
+use seshmux_core::tmux::TmuxServer;
+use thiserror::Error;
 
 use crate::App;
 use crate::catalog::WorktreeCatalog;
@@ -9,9 +14,20 @@ use crate::runtime;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct ResolvedTarget {
     pub(crate) repo_root: PathBuf,
+    pub(crate) worktrees_dir: PathBuf,
     pub(crate) worktree_name: String,
     pub(crate) worktree_path: PathBuf,
-    pub(crate) session_name: String,
+    pub(crate) session: SessionTarget,
+    pub(crate) created_by: Option<String>,
+    pub(crate) branch: Option<String>,
+    pub(crate) project: Option<String>,
+}
+
+#[derive(Debug, Error)]
+#[error("worktree '{name}' was created by {owner}")]
+pub struct ForeignWorktree {
+    pub name: String,
+    pub owner: String,
 }
 
 pub(crate) fn resolve_target(
@@ -24,15 +40,73 @@ pub(crate) fn resolve_target(
         return Ok(None);
     };
 
+    Ok(Some(target_for_entry(
+        &catalog,
+        entry,
+        runtime::session_layout(app)?,
+        runtime::session_backend(app)?,
+        &runtime::tmux_server(app)?,
+    )))
+}
+
+pub(crate) fn target_for_entry(
+    catalog: &WorktreeCatalog,
+    entry: &RegistryEntry,
+    layout: SessionLayout,
+    backend: SessionBackend,
+    server: &TmuxServer,
+) -> ResolvedTarget {
     let repo_root = catalog.repo_root().to_path_buf();
-    let resolved_name = entry.name.clone();
-    let worktree_path = PathBuf::from(&entry.path);
-    let session_name = runtime::session_name_for(&repo_root, &resolved_name);
+    let session = runtime::session_target_for_entry(layout, backend, server, &repo_root, entry);
 
-    Ok(Some(ResolvedTarget {
+    ResolvedTarget {
         repo_root,
-        worktree_name: resolved_name,
-        worktree_path,
-        session_name,
-    }))
+        worktrees_dir: catalog.worktrees_dir().to_path_buf(),
+        worktree_name: entry.name.clone(),
+        worktree_path: PathBuf::from(&entry.path),
+        session,
+        created_by: entry.created_by.clone(),
+        branch: entry.branch.clone(),
+        project: entry.project.clone(),
+    }
+}
+
+pub(crate) fn check_ownership(
+    app: &App<'_>,
+    target: &ResolvedTarget,
+    action: &str,
+    allow_foreign: bool,
+) -> Result<Option<String>> {
+    let current = seshmux_core::ownership::current_owner();
+    let Some(owner) =
+        seshmux_core::ownership::foreign_owner(target.created_by.as_deref(), &current)
+    else {
+        return Ok(None);
+    };
+
+    if block_foreign_worktrees(app)? {
+        bail!(
+            "refusing to {action} worktree '{}': it was created by {owner} and you are {current} (ownership.block_foreign_worktrees is set)",
+            target.worktree_name
+        );
+    }
+    if !allow_foreign {
+        return Err(ForeignWorktree {
+            name: target.worktree_name.clone(),
+            owner: owner.to_string(),
+        }
+        .into());
+    }
+
+    Ok(Some(owner.to_string()))
+}
+
+fn block_foreign_worktrees(app: &App<'_>) -> Result<bool> {
+    let config_path =
+        seshmux_core::config::resolve_config_path().context("failed to resolve config path")?;
+    if !config_path.exists() {
+        return Ok(false);
+    }
+
+    Ok(app.ensure_config_ready()?.ownership.block_foreign_worktrees)
 }
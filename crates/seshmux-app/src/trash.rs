@@ -0,0 +1,208 @@
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result, anyhow, bail};
+use seshmux_core::registry::RegistryEntry;
+use seshmux_core::trash::TrashedWorktree;
+use seshmux_core::vcs::VcsKind;
+
+use crate::runtime;
+use crate::{App, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashRestoreResult {
+    pub worktree_name: String,
+    pub worktree_path: PathBuf,
+    pub branch: Option<String>,
+    pub trashed_at: String,
+}
+
+impl<'a> App<'a> {
+    pub fn restore_trashed(
+        &self,
+        cwd: &Path,
+        worktree_name: &str,
+    ) -> Result<TrashRestoreResult, Error> {
+        let repo_root = runtime::resolve_repo_root(self, cwd)?;
+        let worktrees_dir = runtime::worktrees_dir(self, &repo_root)?;
+        let mut trashed = load_trash(&worktrees_dir)?;
+
+        let Some(index) = trashed
+            .iter()
+            .rposition(|item| item.entry.name == worktree_name)
+        else {
+            let names = trashed
+                .iter()
+                .map(|item| item.entry.name.as_str())
+                .collect::<Vec<_>>();
+            if names.is_empty() {
+                return Err(anyhow!(
+                    "worktree '{worktree_name}' is not in the trash; the trash is empty"
+                )
+                .into());
+            }
+            return Err(anyhow!(
+                "worktree '{worktree_name}' is not in the trash; trashed worktrees: {}",
+                names.join(", ")
+            )
+            .into());
+        };
+
+        let item = trashed.remove(index);
+        let worktree_path = PathBuf::from(&item.entry.path);
+        if worktree_path.exists() {
+            return Err(anyhow!(
+                "cannot restore worktree '{worktree_name}': {} already exists",
+                worktree_path.display()
+            )
+            .into());
+        }
+        seshmux_core::registry::ensure_entry_available(
+            &worktrees_dir,
+            worktree_name,
+            &worktree_path,
+        )
+        .with_context(|| {
+            format!("cannot restore worktree '{worktree_name}' over a registered worktree")
+        })?;
+
+        let trash_path = PathBuf::from(&item.trash_path);
+        seshmux_core::git::move_worktree(&repo_root, &trash_path, &worktree_path, self.runner)
+            .with_context(|| {
+                format!(
+                    "failed to move {} back to {}",
+                    trash_path.display(),
+                    worktree_path.display()
+                )
+            })?;
+        seshmux_core::registry::insert_unique_entry(&worktrees_dir, item.entry.clone())
+            .with_context(|| {
+                format!(
+                    "failed to register restored worktree '{worktree_name}' in {}",
+                    seshmux_core::registry::registry_path(&worktrees_dir).display()
+                )
+            })?;
+        save_trash(&worktrees_dir, &trashed)?;
+
+        Ok(TrashRestoreResult {
+            worktree_name: worktree_name.to_string(),
+            worktree_path,
+            branch: item.entry.branch,
+            trashed_at: item.trashed_at,
+        })
+    }
+}
+
+// Git keeps tracking the worktree at its new path, so the move is undone by moving it back.
+pub(crate) fn move_to_trash(
+    app: &App<'_>,
+    repo_root: &Path,
+    worktrees_dir: &Path,
+    worktree_name: &str,
+    worktree_path: &Path,
+) -> Result<PathBuf> {
+    if runtime::vcs(app, repo_root)?.kind() != VcsKind::Git {
+        bail!("delete_mode = \"trash\" only supports git worktrees");
+    }
+
+    let now = seshmux_core::time::unix_seconds(SystemTime::now());
+    let trash_path = seshmux_core::trash::trash_path(worktrees_dir, worktree_name, now);
+    let trash_dir = seshmux_core::trash::trash_dir(worktrees_dir);
+    std::fs::create_dir_all(&trash_dir)
+        .with_context(|| format!("failed to create {}", trash_dir.display()))?;
+    seshmux_core::git::move_worktree(repo_root, worktree_path, &trash_path, app.runner)
+        .with_context(|| {
+            format!(
+                "failed to move {} to {}",
+                worktree_path.display(),
+                trash_path.display()
+            )
+        })?;
+    Ok(trash_path)
+}
+
+pub(crate) fn record_trashed(
+    worktrees_dir: &Path,
+    trash_path: &Path,
+    entry: RegistryEntry,
+    delete_branch: bool,
+) -> Result<()> {
+    let trashed_at = seshmux_core::time::now_utc_rfc3339()
+        .map_err(|error| anyhow!("failed to format timestamp: {error}"))?;
+    let mut trashed = load_trash(worktrees_dir)?;
+    trashed.push(TrashedWorktree {
+        trashed_at,
+        trash_path: trash_path.to_string_lossy().to_string(),
+        delete_branch,
+        entry,
+    });
+    save_trash(worktrees_dir, &trashed)
+}
+
+pub(crate) fn purge_expired(
+    app: &App<'_>,
+    repo_root: &Path,
+    worktrees_dir: &Path,
+    retention_days: u64,
+) -> Result<(Vec<String>, Vec<String>)> {
+    let now = seshmux_core::time::unix_seconds(SystemTime::now());
+    let mut purged = Vec::new();
+    let mut problems = Vec::new();
+    let mut kept = Vec::new();
+
+    for item in load_trash(worktrees_dir)? {
+        let trashed_unix = seshmux_core::time::rfc3339_unix_seconds(&item.trashed_at);
+        if !seshmux_core::trash::is_expired(trashed_unix, now, retention_days) {
+            kept.push(item);
+            continue;
+        }
+
+        let trash_path = PathBuf::from(&item.trash_path);
+        if trash_path.exists()
+            && let Err(error) =
+                seshmux_core::git::force_remove_worktree(repo_root, &trash_path, app.runner)
+        {
+            problems.push(format!(
+                "could not purge trashed worktree '{}' at {}: {error}",
+                item.entry.name,
+                trash_path.display()
+            ));
+            kept.push(item);
+            continue;
+        }
+
+        if item.delete_branch
+            && let Some(branch) = &item.entry.branch
+            && let Err(error) = seshmux_core::git::delete_branch(repo_root, branch, app.runner)
+        {
+            problems.push(format!(
+                "branch {branch} of purged worktree '{}' was kept: {error}",
+                item.entry.name
+            ));
+        }
+        purged.push(item.entry.name);
+    }
+
+    if !purged.is_empty() {
+        save_trash(worktrees_dir, &kept)?;
+    }
+    Ok((purged, problems))
+}
+
+fn load_trash(worktrees_dir: &Path) -> Result<Vec<TrashedWorktree>> {
+    seshmux_core::trash::load(worktrees_dir).with_context(|| {
+        format!(
+            "failed to read the trash at {}",
+            seshmux_core::trash::manifest_path(worktrees_dir).display()
+        )
+    })
+}
+
+fn save_trash(worktrees_dir: &Path, trashed: &[TrashedWorktree]) -> Result<()> {
+    seshmux_core::trash::save(worktrees_dir, trashed).with_context(|| {
+        format!(
+            "failed to update the trash at {}",
+            seshmux_core::trash::manifest_path(worktrees_dir).display()
+        )
+    })
+}
@@ -0,0 +1,126 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, anyhow};
+use seshmux_core::config::UpdateStrategy;
+use seshmux_core::git::IntegrateOutcome;
+
+use crate::runtime;
+use crate::target;
+use crate::{App, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateRequest {
+    pub cwd: PathBuf,
+    pub worktree_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateOutcome {
+    Updated,
+    Conflicts { paths: Vec<String> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateResult {
+    pub worktree_name: String,
+    pub worktree_path: PathBuf,
+    pub branch: String,
+    pub upstream: String,
+    pub strategy: UpdateStrategy,
+    pub outcome: UpdateOutcome,
+}
+
+impl<'a> App<'a> {
+    pub fn update_worktree(
+        &self,
+        request: UpdateRequest,
+        output: &mut dyn FnMut(String),
+    ) -> Result<UpdateResult, Error> {
+        let target = target::resolve_target(self, &request.cwd, &request.worktree_name)?
+            .ok_or_else(|| {
+                anyhow!(
+                    "worktree '{}' was not found in worktree.toml",
+                    request.worktree_name
+                )
+            })?;
+        let worktree_path = target.worktree_path.clone();
+        if !worktree_path.exists() {
+            return Err(anyhow!(
+                "worktree path does not exist on disk: {}",
+                worktree_path.display()
+            )
+            .into());
+        }
+
+        let config = runtime::update_config(self)?;
+        let upstream = match config.upstream {
+            Some(upstream) => upstream.trim().to_string(),
+            None => seshmux_core::git::default_branch(&target.repo_root, self.runner)
+                .context("failed to resolve the default branch")?
+                .ok_or_else(|| {
+                    anyhow!("no default branch found to update onto; set [update] upstream")
+                })?,
+        };
+
+        let branch = seshmux_core::git::current_branch(&worktree_path, self.runner)
+            .with_context(|| format!("failed to read the branch of {}", worktree_path.display()))?;
+        if branch == "HEAD" {
+            return Err(anyhow!(
+                "worktree '{}' is on a detached HEAD; check out a branch before updating",
+                target.worktree_name
+            )
+            .into());
+        }
+
+        let remotes = seshmux_core::git::remotes(&target.repo_root, self.runner)
+            .context("failed to list git remotes")?;
+        if let Some((remote, _)) = upstream.split_once('/')
+            && remotes.iter().any(|name| name == remote)
+        {
+            output(format!("$ git fetch {remote}"));
+            seshmux_core::git::fetch_remote(&worktree_path, remote, self.runner, &mut |line| {
+                emit_line(output, line)
+            })
+            .with_context(|| format!("failed to fetch '{remote}'"))?;
+        }
+
+        let command = match config.strategy {
+            UpdateStrategy::Rebase => format!("git rebase {upstream}"),
+            UpdateStrategy::Merge => format!("git merge --no-edit {upstream}"),
+        };
+        output(format!("$ {command}"));
+        let mut on_line = |line: &str| emit_line(output, line);
+        let finished = match config.strategy {
+            UpdateStrategy::Rebase => {
+                seshmux_core::git::rebase_onto(&worktree_path, &upstream, self.runner, &mut on_line)
+            }
+            UpdateStrategy::Merge => seshmux_core::git::merge_upstream(
+                &worktree_path,
+                &upstream,
+                self.runner,
+                &mut on_line,
+            ),
+        }
+        .with_context(|| format!("failed to run {command} in '{}'", target.worktree_name))?;
+
+        let outcome = match finished {
+            IntegrateOutcome::Clean(_) => UpdateOutcome::Updated,
+            IntegrateOutcome::Conflicts { paths, .. } => UpdateOutcome::Conflicts { paths },
+        };
+
+        Ok(UpdateResult {
+            worktree_name: target.worktree_name,
+            worktree_path,
+            branch,
+            upstream,
+            strategy: config.strategy,
+            outcome,
+        })
+    }
+}
+
+fn emit_line(output: &mut dyn FnMut(String), line: &str) {
+    if !line.trim().is_empty() {
+        output(line.to_string());
+    }
+}
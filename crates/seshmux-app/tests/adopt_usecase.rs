@@ -0,0 +1,89 @@
+mod support;
+
+use std::fs;
+
+use seshmux_app::{AdoptRequest, App};
+use seshmux_core::registry::load_registry;
+
+use support::{ENV_LOCK, QueueRunner, add_registry_entry, output, write_valid_config};
+
+#[test]
+fn adopt_registers_orphan_sessions_and_renames_foreign_ones() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    let base = fs::canonicalize(temp.path()).expect("canonical temp");
+    unsafe {
+        std::env::set_var("HOME", &base);
+    }
+    write_valid_config(&base, false);
+
+    let repo_root = base.join("repo");
+    let worktrees_dir = repo_root.join("worktrees");
+    let known = worktrees_dir.join("known");
+    let orphan = worktrees_dir.join("orphan");
+    let legacy = base.join("legacy-checkout");
+    for dir in [&known, &orphan, &legacy] {
+        fs::create_dir_all(dir).expect("worktree dir");
+    }
+    add_registry_entry(&repo_root, "known", "2026-02-25T10:00:00Z");
+
+    let worktree_list = format!(
+        "worktree {}\nHEAD abc\n\nworktree {}\nHEAD def\n\nworktree {}\nHEAD 123\n\nworktree {}\nHEAD 456\n",
+        repo_root.display(),
+        known.display(),
+        orphan.display(),
+        legacy.display()
+    );
+    let sessions = format!(
+        "repo/known\t{}\nrepo/orphan\t{}\nrepo/ghost\t/nowhere\nscratch\t{}\n",
+        known.display(),
+        orphan.display(),
+        legacy.display()
+    );
+    let scan_outputs = || {
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output(&worktree_list, "", 0),
+            output(&sessions, "", 0),
+        ]
+    };
+    let mut outputs = scan_outputs();
+    outputs.extend(scan_outputs());
+    outputs.push(output("", "", 0));
+    let runner = QueueRunner::new(outputs, Vec::new());
+
+    let app = App::new(&runner);
+    let scan = app.adopt_scan(&repo_root).expect("scan");
+    let names: Vec<&str> = scan
+        .candidates
+        .iter()
+        .map(|candidate| candidate.worktree_name.as_str())
+        .collect();
+    assert_eq!(names, vec!["orphan", "legacy-checkout"]);
+    assert_eq!(scan.candidates[0].rename_to, None);
+    assert_eq!(
+        scan.candidates[1].rename_to.as_deref(),
+        Some("repo/legacy-checkout")
+    );
+
+    let result = app
+        .adopt(AdoptRequest {
+            cwd: repo_root.clone(),
+            worktree_names: vec!["orphan".to_string(), "legacy-checkout".to_string()],
+        })
+        .expect("adopt");
+    assert_eq!(result.adopted.len(), 2);
+
+    let registry = load_registry(&worktrees_dir).expect("registry");
+    let registered: Vec<&str> = registry.iter().map(|entry| entry.name.as_str()).collect();
+    assert!(registered.contains(&"orphan"));
+    assert!(registered.contains(&"legacy-checkout"));
+
+    let rename = runner.calls().pop().expect("rename call");
+    assert_eq!(rename.program, "tmux");
+    assert_eq!(
+        rename.args,
+        vec!["rename-session", "-t", "scratch", "repo/legacy-checkout"]
+    );
+}
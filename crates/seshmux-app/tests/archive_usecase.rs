@@ -0,0 +1,255 @@
+mod support;
+
+use seshmux_app::{App, ArchiveRequest};
+use seshmux_core::registry::ArchiveRecord;
+
+use support::{ENV_LOCK, QueueRunner, add_registry_entry, output, write_valid_config};
+
+#[test]
+fn archive_kills_idle_sessions_marks_the_registry_and_restores() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    let now = seshmux_core::time::now_utc_rfc3339().expect("timestamp");
+    add_registry_entry(&repo_root, "old", "2020-01-01T00:00:00Z");
+    add_registry_entry(&repo_root, "fresh", &now);
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("repo/old\t1600000000\nrepo/fresh\t1600000000\n", "", 0),
+            output("1600000000\n", "", 0),
+            output("feature/old\n", "", 0),
+            output("", "", 0),
+            output("1600000000\n", "", 0),
+            output(&format!("{}\n", repo_root.display()), "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+
+    let result = app
+        .archive_idle(ArchiveRequest {
+            cwd: repo_root.clone(),
+            idle_days: None,
+            tar: None,
+            dry_run: false,
+        })
+        .expect("archive");
+
+    assert_eq!(result.idle_days, 30);
+    assert_eq!(result.archived.len(), 1);
+    let archived = &result.archived[0];
+    assert_eq!(archived.worktree_name, "old");
+    assert!(archived.session_killed);
+    assert_eq!(archived.tarball, None);
+    assert_eq!(archived.restore_command, "seshmux archive --restore old");
+    assert!(runner.calls().iter().any(|call| {
+        call.program == "tmux" && call.args == ["kill-session", "-t", "=repo/old"]
+    }));
+
+    let worktrees_dir = repo_root.join("worktrees");
+    let entry = seshmux_core::registry::find_entry_by_name(&worktrees_dir, "old")
+        .expect("registry")
+        .expect("entry");
+    let record = entry.archived.expect("archived");
+    assert_eq!(record.branch.as_deref(), Some("feature/old"));
+    assert!(
+        seshmux_core::registry::find_entry_by_name(&worktrees_dir, "fresh")
+            .expect("registry")
+            .expect("entry")
+            .archived
+            .is_none()
+    );
+
+    let restored = app.restore_archived(&repo_root, "old").expect("restore");
+    assert!(!restored.from_tarball);
+    let entry = seshmux_core::registry::find_entry_by_name(&worktrees_dir, "old")
+        .expect("registry")
+        .expect("entry");
+    assert!(entry.archived.is_none());
+}
+
+#[test]
+fn archive_reports_a_failed_worktree_and_keeps_going() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    add_registry_entry(&repo_root, "first", "2020-01-01T00:00:00Z");
+    add_registry_entry(&repo_root, "second", "2020-01-01T00:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("repo/first\t1600000000\nrepo/second\t1600000000\n", "", 0),
+            output("1600000000\n", "", 0),
+            output("feature/first\n", "", 0),
+            output("", "no server running", 1),
+            output("1600000000\n", "", 0),
+            output("feature/second\n", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+
+    let result = app
+        .archive_idle(ArchiveRequest {
+            cwd: repo_root.clone(),
+            idle_days: None,
+            tar: None,
+            dry_run: false,
+        })
+        .expect("archive");
+
+    assert_eq!(result.failed.len(), 1);
+    assert_eq!(result.failed[0].0, "first");
+    assert!(result.failed[0].1.contains("repo/first"));
+    assert_eq!(result.archived.len(), 1);
+    assert_eq!(result.archived[0].worktree_name, "second");
+
+    let worktrees_dir = repo_root.join("worktrees");
+    let archived = |name: &str| {
+        seshmux_core::registry::find_entry_by_name(&worktrees_dir, name)
+            .expect("registry")
+            .expect("entry")
+            .archived
+            .is_some()
+    };
+    assert!(!archived("first"));
+    assert!(archived("second"));
+}
+
+#[test]
+fn archive_unmarks_the_worktree_when_removing_the_packed_checkout_fails() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    add_registry_entry(&repo_root, "old", "2020-01-01T00:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("1600000000\n", "", 0),
+            output("feature/old\n", "", 0),
+            output("abc1234567\x1fabc1234\x1fwork\n", "", 0),
+            output("", "", 0),
+            output("", "fatal: cannot remove worktree", 128),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+
+    let result = app
+        .archive_idle(ArchiveRequest {
+            cwd: repo_root.clone(),
+            idle_days: None,
+            tar: Some(true),
+            dry_run: false,
+        })
+        .expect("archive");
+
+    assert!(result.archived.is_empty());
+    assert_eq!(result.failed.len(), 1);
+    assert!(result.failed[0].1.contains("cannot remove worktree"));
+    let calls = runner.calls();
+    let tar = calls
+        .iter()
+        .position(|call| call.program == "tar")
+        .expect("tar call");
+    let remove = calls
+        .iter()
+        .position(|call| call.args.iter().any(|arg| arg == "remove"))
+        .expect("remove call");
+    assert!(tar < remove);
+
+    let entry = seshmux_core::registry::find_entry_by_name(&repo_root.join("worktrees"), "old")
+        .expect("registry")
+        .expect("entry");
+    assert!(entry.archived.is_none());
+}
+
+#[test]
+fn restore_detaches_when_the_branch_moved_after_packing() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    let worktree_path = add_registry_entry(&repo_root, "old", "2020-01-01T00:00:00Z");
+    std::fs::remove_dir_all(&worktree_path).expect("remove checkout");
+    let worktrees_dir = repo_root.join("worktrees");
+    let tarball = worktrees_dir.join(".archive").join("old.tar.gz");
+    std::fs::create_dir_all(tarball.parent().expect("parent")).expect("archive dir");
+    std::fs::write(&tarball, "").expect("tarball");
+    seshmux_core::registry::set_archived(
+        &worktrees_dir,
+        "old",
+        Some(ArchiveRecord {
+            archived_at: "2020-02-01T00:00:00Z".to_string(),
+            branch: Some("feature/old".to_string()),
+            tarball: Some(tarball.to_string_lossy().to_string()),
+            commit: Some("aaaaaaa".to_string()),
+        }),
+    )
+    .expect("archive mark");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("bbbbbbb\n", "", 0),
+            output("bbbbbbb\x1fbbb\x1fnewer work\n", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+
+    let restored = app.restore_archived(&repo_root, "old").expect("restore");
+
+    assert!(restored.from_tarball);
+    assert_eq!(restored.detached_from.as_deref(), Some("feature/old"));
+    let calls = runner.calls();
+    assert_eq!(
+        calls[3].args,
+        [
+            "worktree",
+            "add",
+            "--detach",
+            worktree_path.to_str().expect("utf-8"),
+            "aaaaaaa"
+        ]
+    );
+    assert!(!tarball.exists());
+    assert!(
+        seshmux_core::registry::find_entry_by_name(&worktrees_dir, "old")
+            .expect("registry")
+            .expect("entry")
+            .archived
+            .is_none()
+    );
+}
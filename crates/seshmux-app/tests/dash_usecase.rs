@@ -0,0 +1,60 @@
+mod support;
+
+use std::fs;
+
+use seshmux_app::App;
+
+use support::{ENV_LOCK, QueueRunner, add_registry_entry, output, write_valid_config};
+
+#[test]
+fn dash_lists_every_configured_repo_and_reports_unreadable_ones() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    let repo_root = temp.path().join("repo");
+    let missing = temp.path().join("missing");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let config_path = temp.path().join(".config/seshmux/config.toml");
+    let config = fs::read_to_string(&config_path).expect("read config");
+    fs::write(
+        &config_path,
+        format!(
+            "{config}\n[dash]\nrepos = [\"{}\", \"{}\"]\n",
+            repo_root.display(),
+            missing.display()
+        ),
+    )
+    .expect("write config");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("w1\n", "", 0),
+            output("", "", 0),
+            output("", "fatal: not a git repository", 128),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let result = app.dash().expect("dash");
+
+    assert_eq!(result.repos.len(), 1);
+    assert_eq!(result.repos[0].repo_root, repo_root);
+    assert_eq!(result.repos[0].rows[0].name, "w1");
+    assert!(result.repos[0].rows[0].session_running);
+    assert_eq!(result.problems.len(), 1);
+    assert_eq!(result.problems[0].0, missing);
+    let session_checks = runner
+        .calls()
+        .into_iter()
+        .filter(|call| call.program == "tmux" && call.args[0] == "has-session")
+        .count();
+    assert_eq!(session_checks, 1);
+}
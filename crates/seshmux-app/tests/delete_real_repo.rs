@@ -59,25 +59,19 @@ fn delete_removes_worktree_folder_and_registry_entry_in_real_repo() {
     );
 
     insert_unique_entry(
-        &repo_root,
-        RegistryEntry {
-            name: "w1".to_string(),
-            path: worktree_path.to_string_lossy().to_string(),
-            created_at: "2026-02-25T10:00:00Z".to_string(),
-        },
+        &worktrees_dir,
+        RegistryEntry::new(
+            "w1",
+            worktree_path.to_string_lossy(),
+            "2026-02-25T10:00:00Z",
+        ),
     )
     .expect("insert registry entry");
 
     let runner = SystemCommandRunner::new();
     let app = App::new(&runner);
     let result = app
-        .delete(DeleteRequest {
-            cwd: repo_root.clone(),
-            worktree_name: "w1".to_string(),
-            kill_tmux_session: false,
-            delete_branch: false,
-            force_worktree: false,
-        })
+        .delete(DeleteRequest::new(repo_root.clone(), "w1"))
         .expect("delete should succeed");
 
     let expected_repo_root = repo_root.canonicalize().expect("canonical repo root");
@@ -92,7 +86,7 @@ fn delete_removes_worktree_folder_and_registry_entry_in_real_repo() {
     assert!(result.branch_delete_error.is_none());
     assert!(!worktree_path.exists());
     assert!(
-        find_entry_by_name(&repo_root, "w1")
+        find_entry_by_name(&worktrees_dir, "w1")
             .expect("load registry")
             .is_none()
     );
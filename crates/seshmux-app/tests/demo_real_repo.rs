@@ -0,0 +1,68 @@
+use std::path::Path;
+use std::process::Command;
+
+use seshmux_app::{App, DemoRequest};
+use seshmux_core::command_runner::SystemCommandRunner;
+
+fn run_git(repo: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo)
+        .output()
+        .expect("git command should execute");
+
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+#[test]
+fn demo_creates_history_branches_and_ignored_files() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("seshmux-demo");
+
+    let runner = SystemCommandRunner::new();
+    let app = App::new(&runner);
+    let demo = app
+        .create_demo(DemoRequest {
+            repo_root: repo_root.clone(),
+        })
+        .expect("demo should be created");
+
+    assert_eq!(demo.repo_root, repo_root);
+    assert_eq!(
+        demo.branches,
+        vec!["feature/greeting-tests", "fix/readme-typo"]
+    );
+    assert_eq!(run_git(&repo_root, &["branch", "--show-current"]), "main");
+    assert_eq!(run_git(&repo_root, &["rev-list", "--count", "main"]), "3");
+    assert_eq!(
+        run_git(
+            &repo_root,
+            &["rev-list", "--count", "main..fix/readme-typo"]
+        ),
+        "1"
+    );
+    assert_eq!(
+        run_git(&repo_root, &["status", "--porcelain"]),
+        "?? notes.txt"
+    );
+    assert_eq!(
+        run_git(
+            &repo_root,
+            &["check-ignore", ".env", "node_modules/left-pad/index.js"]
+        ),
+        ".env\nnode_modules/left-pad/index.js"
+    );
+
+    let error = app
+        .create_demo(DemoRequest {
+            repo_root: repo_root.clone(),
+        })
+        .expect_err("existing destination should fail");
+    assert!(error.to_string().contains("already exists"));
+}
@@ -0,0 +1,98 @@
+mod support;
+
+use std::fs;
+
+use seshmux_app::{App, ImportLayout, ImportRequest};
+use seshmux_core::registry::load_registry;
+
+use support::{ENV_LOCK, QueueRunner, add_registry_entry, output, write_valid_config};
+
+#[test]
+fn import_registers_worktrees_from_sibling_and_container_layouts() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    let base = fs::canonicalize(temp.path()).expect("canonical temp");
+    unsafe {
+        std::env::set_var("HOME", &base);
+    }
+    write_valid_config(&base, false);
+
+    let repo_root = base.join("repo");
+    let worktrees_dir = repo_root.join("worktrees");
+    let known = worktrees_dir.join("known");
+    let sibling = base.join("repo-Fix-Login");
+    let contained = base.join("repo.worktrees").join("alpha");
+    for dir in [&known, &sibling, &contained] {
+        fs::create_dir_all(dir).expect("worktree dir");
+    }
+    add_registry_entry(&repo_root, "known", "2026-02-25T10:00:00Z");
+
+    let worktree_list = format!(
+        "worktree {}\nHEAD abc\n\nworktree {}\nHEAD def\n\nworktree {}\nHEAD 123\n\nworktree {}\nHEAD 456\n",
+        repo_root.display(),
+        known.display(),
+        sibling.display(),
+        contained.display()
+    );
+    let sessions = format!("scratch\t{}\n", sibling.display());
+    let scan_outputs = || {
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output(&worktree_list, "", 0),
+            output(&sessions, "", 0),
+        ]
+    };
+    let mut outputs = scan_outputs();
+    outputs.extend(scan_outputs());
+    outputs.push(output("", "", 0));
+    let runner = QueueRunner::new(outputs, Vec::new());
+
+    let app = App::new(&runner);
+    let scan = app.import_scan(&repo_root).expect("scan");
+    let found: Vec<(&str, ImportLayout)> = scan
+        .candidates
+        .iter()
+        .map(|candidate| (candidate.worktree_name.as_str(), candidate.layout))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("fix-login", ImportLayout::Sibling),
+            ("alpha", ImportLayout::Container)
+        ]
+    );
+    assert_eq!(scan.candidates[0].session_name.as_deref(), Some("scratch"));
+    assert_eq!(
+        scan.candidates[0].rename_to.as_deref(),
+        Some("repo/fix-login")
+    );
+    assert_eq!(scan.candidates[1].session_name, None);
+
+    let result = app
+        .import_worktrees(ImportRequest {
+            cwd: repo_root.clone(),
+            worktree_names: vec!["fix-login".to_string(), "alpha".to_string()],
+        })
+        .expect("import");
+    assert_eq!(result.imported.len(), 2);
+
+    let registry = load_registry(&worktrees_dir).expect("registry");
+    let imported = registry
+        .iter()
+        .find(|entry| entry.name == "fix-login")
+        .expect("fix-login registered");
+    assert_eq!(imported.path, sibling.to_string_lossy());
+    assert_eq!(
+        imported.created_by,
+        Some(seshmux_core::ownership::current_owner())
+    );
+    assert_eq!(imported.session_name.as_deref(), Some("repo/fix-login"));
+
+    let rename = runner.calls().pop().expect("rename call");
+    assert_eq!(rename.program, "tmux");
+    assert_eq!(
+        rename.args,
+        vec!["rename-session", "-t", "scratch", "repo/fix-login"]
+    );
+}
@@ -0,0 +1,76 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use seshmux_app::{App, InitRequest};
+use seshmux_core::command_runner::SystemCommandRunner;
+
+fn run_git(repo: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo)
+        .output()
+        .expect("git command should execute");
+
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+#[test]
+fn init_clones_with_separate_git_dir_and_writes_registry() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let source = temp.path().join("widgets");
+    fs::create_dir_all(&source).expect("source dir");
+    run_git(&source, &["init"]);
+    fs::write(source.join("README.md"), "widgets\n").expect("write readme");
+    run_git(&source, &["add", "."]);
+    run_git(
+        &source,
+        &[
+            "-c",
+            "user.name=seshmux-test",
+            "-c",
+            "user.email=seshmux-test@example.com",
+            "commit",
+            "-m",
+            "initial",
+        ],
+    );
+
+    let workspace = temp.path().join("workspace");
+    fs::create_dir_all(&workspace).expect("workspace dir");
+
+    let runner = SystemCommandRunner::new();
+    let app = App::new(&runner);
+    let result = app
+        .init(InitRequest {
+            cwd: workspace.clone(),
+            url: source.to_string_lossy().to_string(),
+            directory: None,
+        })
+        .expect("init should succeed");
+
+    assert_eq!(result.repo_root, workspace.join("widgets"));
+    assert_eq!(result.git_dir, workspace.join("widgets.git"));
+    assert!(result.repo_root.join("README.md").is_file());
+    assert!(result.repo_root.join(".git").is_file());
+    assert!(result.repo_root.join("worktrees/worktree.toml").is_file());
+    assert_eq!(
+        run_git(&result.repo_root, &["rev-parse", "--git-dir"]),
+        result.git_dir.to_string_lossy()
+    );
+
+    let error = app
+        .init(InitRequest {
+            cwd: workspace.clone(),
+            url: source.to_string_lossy().to_string(),
+            directory: None,
+        })
+        .expect_err("existing destination should fail");
+    assert!(error.to_string().contains("already exists"));
+}
@@ -1,8 +1,13 @@
 mod support;
 
 use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use seshmux_app::{App, NewRequest, NewStartPoint};
+use seshmux_app::{
+    App, CarriedChanges, NewBatch, NewBatchOutcome, NewError, NewPhase, NewRequest, NewStartPoint,
+    ProvisionLog,
+};
 
 use support::{ENV_LOCK, QueueRunner, output, write_valid_config};
 
@@ -22,6 +27,7 @@ fn new_execute_returns_no_commits_error_for_current_branch_start() {
     let runner = QueueRunner::new(
         vec![
             output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
             output(
                 "",
                 "fatal: ambiguous argument 'HEAD': unknown revision or path not in the working tree.",
@@ -33,14 +39,10 @@ fn new_execute_returns_no_commits_error_for_current_branch_start() {
 
     let app = App::new(&runner);
     let error = app
-        .new_execute(NewRequest {
-            cwd: repo_root.clone(),
-            worktree_name: "w1".to_string(),
-            start_point: NewStartPoint::CurrentBranch,
-            add_worktrees_gitignore_entry: false,
-            selected_extras: Vec::new(),
-            connect_now: false,
-        })
+        .new_execute(
+            NewRequest::new(repo_root.clone(), "w1", NewStartPoint::CurrentBranch),
+            &mut |_| {},
+        )
         .expect_err("expected no commits error");
 
     assert!(error.to_string().contains(
@@ -48,6 +50,40 @@ fn new_execute_returns_no_commits_error_for_current_branch_start() {
     ));
 }
 
+#[test]
+fn new_execute_refuses_a_jj_repo_without_a_colocated_git_repository() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".jj/repo")).expect("jj repo");
+
+    let runner = QueueRunner::new(
+        vec![output(&format!("{}\n", repo_root.display()), "", 0)],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let error = app
+        .new_execute(
+            NewRequest::new(repo_root.clone(), "w1", NewStartPoint::CurrentBranch),
+            &mut |_| {},
+        )
+        .expect_err("plain jj repo");
+
+    assert!(format!("{error:#}").contains("jj git init --colocate"));
+    let calls = runner.calls();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].program, "jj");
+    assert_eq!(calls[0].args, vec!["root"]);
+    assert!(!repo_root.join("worktrees").exists());
+}
+
 #[test]
 fn new_execute_rejects_duplicate_registry_name_before_second_mutation() {
     let _guard = ENV_LOCK.lock().expect("env lock");
@@ -65,7 +101,11 @@ fn new_execute_rejects_duplicate_registry_name_before_second_mutation() {
         vec![
             output(&format!("{}\n", repo_root.display()), "", 0),
             output("", "", 0),
+            output("", "", 1),
+            output("", "", 0),
             output("", "", 0),
+            output("0\n", "", 0),
+            output("0\n", "", 0),
             output(&format!("{}\n", repo_root.display()), "", 0),
         ],
         Vec::new(),
@@ -73,24 +113,20 @@ fn new_execute_rejects_duplicate_registry_name_before_second_mutation() {
 
     let app = App::new(&runner);
 
-    let first = app.new_execute(NewRequest {
-        cwd: repo_root.clone(),
-        worktree_name: "w1".to_string(),
-        start_point: NewStartPoint::Commit("abc123".to_string()),
-        add_worktrees_gitignore_entry: false,
-        selected_extras: Vec::new(),
-        connect_now: false,
-    });
+    let first = app.new_execute(
+        NewRequest::new(
+            repo_root.clone(),
+            "w1",
+            NewStartPoint::Commit("abc123".to_string()),
+        ),
+        &mut |_| {},
+    );
     assert!(first.is_ok());
 
-    let second = app.new_execute(NewRequest {
-        cwd: repo_root,
-        worktree_name: "w1".to_string(),
-        start_point: NewStartPoint::Commit("abc123".to_string()),
-        add_worktrees_gitignore_entry: false,
-        selected_extras: Vec::new(),
-        connect_now: false,
-    });
+    let second = app.new_execute(
+        NewRequest::new(repo_root, "w1", NewStartPoint::Commit("abc123".to_string())),
+        &mut |_| {},
+    );
 
     assert!(second.is_err());
     let message = second.expect_err("second run should fail").to_string();
@@ -109,3 +145,1287 @@ fn new_execute_rejects_duplicate_registry_name_before_second_mutation() {
 
     assert_eq!(worktree_add_calls, 1);
 }
+
+#[test]
+fn new_prepare_reports_a_detached_head_by_its_short_hash() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 1),
+            output("1a2b3c4\n", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let prepare = app.new_prepare(&repo_root).expect("prepare");
+    assert_eq!(prepare.detached_head.as_deref(), Some("1a2b3c4"));
+
+    let calls = runner.calls();
+    assert!(calls.iter().all(|call| call.program == "git"));
+    assert_eq!(calls[1].args, vec!["symbolic-ref", "-q", "HEAD"]);
+}
+
+#[test]
+fn new_prepare_suggests_templated_names_that_are_not_taken() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    let config_path = temp.path().join(".config/seshmux/config.toml");
+    let config = fs::read_to_string(&config_path).expect("read config");
+    fs::write(
+        &config_path,
+        format!("{config}\n[names]\ntemplates = [\"PROJ-{{date}}\", \"{{branch}}\"]\n"),
+    )
+    .expect("write config");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    let worktrees_dir = repo_root.join("worktrees");
+    fs::create_dir_all(&worktrees_dir).expect("worktrees dir");
+    seshmux_core::registry::insert_unique_entry(
+        &worktrees_dir,
+        seshmux_core::registry::RegistryEntry::new(
+            "feature-login",
+            worktrees_dir
+                .join("feature-login")
+                .to_string_lossy()
+                .to_string(),
+            "2026-02-25T10:00:00Z",
+        ),
+    )
+    .expect("insert registry entry");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("refs/heads/feature/login\n", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let prepare = app.new_prepare(&repo_root).expect("prepare");
+    let date = seshmux_core::time::compact_utc_date(std::time::SystemTime::now());
+    assert!(prepare.existing_names.contains("feature-login"));
+    assert_eq!(
+        prepare.name_suggestions,
+        vec![format!("proj-{date}"), "feature-login-2".to_string()]
+    );
+}
+
+#[test]
+fn new_execute_honors_configured_worktrees_dir_outside_repo() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    let config_path = temp.path().join(".config/seshmux/config.toml");
+    let config = fs::read_to_string(&config_path).expect("read config");
+    let external = temp.path().join("fast-disk");
+    fs::write(
+        &config_path,
+        format!(
+            "worktrees_dir = \"{}/{{repo}}\"\n{config}",
+            external.display()
+        ),
+    )
+    .expect("write config");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("refs/heads/main\n", "", 0),
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "", 1),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let prepare = app.new_prepare(&repo_root).expect("prepare");
+    assert_eq!(prepare.worktrees_dir, external.join("repo"));
+    assert!(prepare.missing_gitignore_entries.is_empty());
+
+    let mut phases = Vec::new();
+    let mut request = NewRequest::new(
+        repo_root.clone(),
+        "w1",
+        NewStartPoint::Commit("abc123".to_string()),
+    );
+    request.add_gitignore_entries = true;
+    let result = app
+        .new_execute(request, &mut |progress| phases.push(progress.phase))
+        .expect("new should succeed");
+    assert_eq!(
+        phases,
+        vec![
+            NewPhase::CreatingWorktree,
+            NewPhase::Registering,
+            NewPhase::StartingSession,
+        ]
+    );
+
+    assert_eq!(result.worktree_path, external.join("repo").join("w1"));
+    assert!(external.join("repo").join("worktree.toml").exists());
+    assert!(!repo_root.join("worktrees").exists());
+    assert!(!repo_root.join(".gitignore").exists());
+}
+
+#[test]
+fn new_execute_places_the_worktree_at_the_templated_path_and_refuses_a_taken_one() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    let config_path = temp.path().join(".config/seshmux/config.toml");
+    let config = fs::read_to_string(&config_path).expect("read config");
+    fs::write(
+        &config_path,
+        format!("worktree_path_template = \"{{worktrees_dir}}/{{date}}-{{name}}\"\n{config}"),
+    )
+    .expect("write config");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("refs/heads/main\n", "", 0),
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "", 1),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+            output(&format!("{}\n", repo_root.display()), "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let prepare = app.new_prepare(&repo_root).expect("prepare");
+    let date = seshmux_core::time::compact_utc_date(std::time::SystemTime::now());
+    let expected = repo_root.join("worktrees").join(format!("{date}-w1"));
+    assert_eq!(prepare.worktree_path("w1"), expected);
+
+    let request = |name: &str| {
+        NewRequest::new(
+            repo_root.clone(),
+            name.to_string(),
+            NewStartPoint::Commit("abc123".to_string()),
+        )
+    };
+    let result = app
+        .new_execute(request("w1"), &mut |_| {})
+        .expect("new should succeed");
+    assert_eq!(result.worktree_path, expected);
+    let entry = seshmux_core::registry::find_entry_by_name(&repo_root.join("worktrees"), "w1")
+        .expect("registry")
+        .expect("entry");
+    assert_eq!(PathBuf::from(entry.path), expected);
+
+    let taken = repo_root.join("worktrees").join(format!("{date}-w2"));
+    fs::create_dir_all(&taken).expect("taken dir");
+    let error = app
+        .new_execute(request("w2"), &mut |_| {})
+        .expect_err("taken path should be refused");
+    match error.downcast_ref::<NewError>() {
+        Some(NewError::PathTaken { path }) => assert_eq!(path, &taken),
+        other => panic!("expected taken path, got {other:?}"),
+    }
+}
+
+#[test]
+fn new_execute_adds_configured_gitignore_entries_that_are_missing() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    let config_path = temp.path().join(".config/seshmux/config.toml");
+    let config = fs::read_to_string(&config_path).expect("read config");
+    fs::write(
+        &config_path,
+        format!("{config}\n[gitignore]\nentries = [\".direnv/\", \"/worktrees/\"]\n"),
+    )
+    .expect("write config");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+    fs::write(repo_root.join(".gitignore"), "/worktrees/\n").expect("write gitignore");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("refs/heads/main\n", "", 0),
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "", 1),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let prepare = app.new_prepare(&repo_root).expect("prepare");
+    assert_eq!(
+        prepare.missing_gitignore_entries,
+        vec![".direnv/".to_string()]
+    );
+
+    let mut request = NewRequest::new(
+        repo_root.clone(),
+        "w1",
+        NewStartPoint::Commit("abc123".to_string()),
+    );
+    request.add_gitignore_entries = true;
+    let result = app
+        .new_execute(request, &mut |_| {})
+        .expect("new should succeed");
+
+    assert_eq!(result.gitignore_added, vec![".direnv/".to_string()]);
+    assert_eq!(
+        fs::read_to_string(repo_root.join(".gitignore")).expect("read gitignore"),
+        "/worktrees/\n.direnv/\n"
+    );
+}
+
+fn write_oversized_index(repo_root: &std::path::Path) {
+    let mut index = b"DIRC".to_vec();
+    index.extend(2u32.to_be_bytes());
+    index.extend(4_096u32.to_be_bytes());
+    for number in 0..4_096 {
+        let path = format!("blob-{number}");
+        let start = index.len();
+        index.extend([0u8; 36]);
+        index.extend(u32::MAX.to_be_bytes());
+        index.extend([0u8; 20]);
+        index.extend((path.len() as u16).to_be_bytes());
+        index.extend(path.as_bytes());
+        let padded = start + (index.len() - start + 8) / 8 * 8;
+        index.resize(padded, 0);
+    }
+    fs::create_dir_all(repo_root.join(".git")).expect("git dir");
+    fs::write(repo_root.join(".git/index"), index).expect("write index");
+}
+
+#[test]
+fn new_execute_fails_before_creating_a_worktree_that_will_not_fit() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+    write_oversized_index(&repo_root);
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("sha1\n", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let mut request = NewRequest::new(
+        repo_root.clone(),
+        "w1",
+        NewStartPoint::Commit("abc123".to_string()),
+    );
+    request.add_gitignore_entries = true;
+    let error = app
+        .new_execute(request, &mut |_| {})
+        .expect_err("expected insufficient space");
+
+    match error.downcast_ref::<NewError>() {
+        Some(NewError::InsufficientSpace { path, required, .. }) => {
+            assert_eq!(path, &repo_root.join("worktrees"));
+            assert_eq!(*required, 4_096 * u64::from(u32::MAX));
+        }
+        other => panic!("expected insufficient space, got {other:?}"),
+    }
+    assert!(!repo_root.join(".gitignore").exists());
+    assert!(!runner.calls().iter().any(|call| {
+        call.args
+            .starts_with(&["worktree".to_string(), "add".to_string()])
+    }));
+}
+
+#[test]
+fn new_execute_registers_the_display_name_next_to_the_slug() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "", 1),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+    let request = |display_name: &str| {
+        let mut request = NewRequest::new(
+            repo_root.clone(),
+            "fix-login-sso",
+            NewStartPoint::Commit("abc123".to_string()),
+        );
+        request.display_name = Some(display_name.to_string());
+        request
+    };
+
+    let error = app
+        .new_execute(request("Fix\nlogin"), &mut |_| {})
+        .expect_err("control characters");
+    assert!(
+        error.to_string().contains("invalid display name"),
+        "{error:#}"
+    );
+
+    let result = app
+        .new_execute(request("Fix login & SSO"), &mut |_| {})
+        .expect("new should succeed");
+    assert_eq!(result.display_name.as_deref(), Some("Fix login & SSO"));
+
+    let entries =
+        seshmux_core::registry::load_registry(&repo_root.join("worktrees")).expect("registry");
+    assert_eq!(entries[0].name, "fix-login-sso");
+    assert_eq!(entries[0].display_name.as_deref(), Some("Fix login & SSO"));
+}
+
+#[test]
+fn new_execute_roots_a_project_session_in_its_directory_with_its_windows() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+    fs::write(
+        repo_root.join(".seshmux.toml"),
+        r#"
+version = 1
+
+[projects.api]
+path = "services/api"
+
+[[projects.api.windows]]
+name = "server"
+program = "cargo"
+args = ["run"]
+"#,
+    )
+    .expect("repo config");
+    // The scripted `git worktree add` creates nothing, so the project directory is laid down here.
+    let project_dir = repo_root.join("worktrees/w1/services/api");
+    fs::create_dir_all(&project_dir).expect("project dir");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "", 1),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+    let request = |project: &str| {
+        let mut request = NewRequest::new(
+            repo_root.clone(),
+            "w1",
+            NewStartPoint::Commit("abc123".to_string()),
+        );
+        request.project = Some(project.to_string());
+        request
+    };
+
+    let error = app
+        .new_execute(request("web"), &mut |_| {})
+        .expect_err("unknown project");
+    assert_eq!(
+        error.to_string(),
+        "unknown project 'web'; defined projects: api"
+    );
+
+    app.new_execute(request("api"), &mut |_| {})
+        .expect("new should succeed");
+
+    let new_session = runner
+        .calls()
+        .into_iter()
+        .find(|call| call.args.first().is_some_and(|arg| arg == "new-session"))
+        .expect("new-session call");
+    let dir = new_session
+        .args
+        .iter()
+        .position(|arg| arg == "-c")
+        .map(|index| new_session.args[index + 1].clone());
+    assert_eq!(dir, Some(project_dir.to_string_lossy().to_string()));
+    assert!(new_session.args.iter().any(|arg| arg == "server"));
+
+    let entries =
+        seshmux_core::registry::load_registry(&repo_root.join("worktrees")).expect("registry");
+    assert_eq!(entries[0].project.as_deref(), Some("api"));
+}
+
+#[test]
+fn new_execute_detects_branch_checked_out_in_another_worktree() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+    let other = temp.path().join("elsewhere");
+    let worktree_list = format!(
+        "worktree {}\nHEAD abc\nbranch refs/heads/main\n\nworktree {}\nHEAD def\nbranch refs/heads/w1\n",
+        repo_root.display(),
+        other.display()
+    );
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output(&worktree_list, "", 0),
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output(&worktree_list, "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let mut request = NewRequest::new(repo_root.clone(), "w1", NewStartPoint::CurrentBranch);
+
+    let error = app
+        .new_execute(request.clone(), &mut |_| {})
+        .expect_err("conflict should be reported");
+    match error.downcast_ref::<NewError>() {
+        Some(NewError::BranchCheckedOut {
+            branch,
+            worktree_path,
+            worktree_name,
+        }) => {
+            assert_eq!(branch, "w1");
+            assert_eq!(worktree_path, &other);
+            assert_eq!(worktree_name, &None);
+        }
+        _ => panic!("unexpected error: {error:#}"),
+    }
+
+    request.ignore_other_worktrees = true;
+    let result = app
+        .new_execute(request, &mut |_| {})
+        .expect("forced checkout");
+    assert_eq!(result.branch_name, "w1");
+
+    let add = runner
+        .calls()
+        .into_iter()
+        .find(|call| {
+            call.args
+                .starts_with(&["worktree".to_string(), "add".to_string()])
+        })
+        .expect("worktree add call");
+    assert_eq!(
+        add.args,
+        vec![
+            "worktree".to_string(),
+            "add".to_string(),
+            "--force".to_string(),
+            result.worktree_path.display().to_string(),
+            "w1".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn new_execute_checks_out_an_existing_branch_when_asked() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+    let worktree_list = format!(
+        "worktree {}\nHEAD abc\nbranch refs/heads/main\n",
+        repo_root.display()
+    );
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output(&worktree_list, "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let mut request = NewRequest::new(
+        repo_root.clone(),
+        "w1",
+        NewStartPoint::Branch("w1".to_string()),
+    );
+    request.use_existing_branch = true;
+    let result = app
+        .new_execute(request, &mut |_| {})
+        .expect("existing branch checkout");
+
+    let add = runner
+        .calls()
+        .into_iter()
+        .find(|call| {
+            call.args
+                .starts_with(&["worktree".to_string(), "add".to_string()])
+        })
+        .expect("worktree add call");
+    assert_eq!(
+        add.args,
+        vec![
+            "worktree".to_string(),
+            "add".to_string(),
+            result.worktree_path.display().to_string(),
+            "w1".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn new_execute_rolls_back_worktree_branch_and_registry_when_tmux_fails() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "", 1),
+            output("", "", 0),
+            output("", "no server running", 1),
+            output("", "can't find session", 1),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let error = app
+        .new_execute(
+            NewRequest::new(
+                repo_root.clone(),
+                "w1",
+                NewStartPoint::Commit("abc123".to_string()),
+            ),
+            &mut |_| {},
+        )
+        .expect_err("tmux failure should fail new");
+
+    let message = format!("{error:#}");
+    assert!(message.contains("creating worktree 'w1' failed and was rolled back"));
+    assert!(message.contains("failed to create tmux session 'repo/w1'"));
+
+    let registry =
+        seshmux_core::registry::load_registry(&repo_root.join("worktrees")).expect("registry");
+    assert!(registry.is_empty());
+
+    let calls: Vec<String> = runner
+        .calls()
+        .iter()
+        .map(|call| format!("{} {}", call.program, call.args.join(" ")))
+        .collect();
+    let worktree_path = repo_root.join("worktrees").join("w1");
+    assert_eq!(
+        &calls[calls.len() - 3..],
+        [
+            format!("git worktree remove --force {}", worktree_path.display()),
+            "git rev-parse --verify --quiet refs/heads/w1".to_string(),
+            "git branch -D w1".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn new_execute_cancelable_stops_at_the_next_phase_and_rolls_back() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "", 1),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let cancel = AtomicBool::new(false);
+    let mut phases = Vec::new();
+    let error = app
+        .new_execute_cancelable(
+            NewRequest::new(
+                repo_root.clone(),
+                "w1",
+                NewStartPoint::Commit("abc123".to_string()),
+            ),
+            &mut |progress| {
+                phases.push(progress.phase);
+                cancel.store(true, Ordering::Relaxed);
+            },
+            &cancel,
+        )
+        .expect_err("canceled");
+
+    assert_eq!(phases, vec![NewPhase::CreatingWorktree]);
+    assert!(matches!(
+        error.downcast_ref::<NewError>(),
+        Some(NewError::Canceled)
+    ));
+    let message = format!("{error:#}");
+    assert!(message.contains("creating worktree 'w1' was canceled and was rolled back"));
+
+    let calls: Vec<String> = runner
+        .calls()
+        .iter()
+        .map(|call| format!("{} {}", call.program, call.args.join(" ")))
+        .collect();
+    assert!(!calls.iter().any(|call| call.starts_with("tmux")));
+    let worktree_path = repo_root.join("worktrees").join("w1");
+    assert_eq!(
+        &calls[calls.len() - 3..],
+        [
+            format!("git worktree remove --force {}", worktree_path.display()),
+            "git rev-parse --verify --quiet refs/heads/w1".to_string(),
+            "git branch -D w1".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn new_execute_creates_the_templated_or_requested_branch() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    let config_path = temp.path().join(".config/seshmux/config.toml");
+    let config = fs::read_to_string(&config_path).expect("read config");
+    fs::write(
+        &config_path,
+        format!("branch_template = \"feature/{{name}}\"\n{config}"),
+    )
+    .expect("write config");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "", 1),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+    let mut request = NewRequest::new(
+        repo_root.clone(),
+        "w1",
+        NewStartPoint::Commit("abc123".to_string()),
+    );
+
+    let result = app
+        .new_execute(request.clone(), &mut |_| {})
+        .expect("new should succeed");
+    assert_eq!(result.worktree_name, "w1");
+    assert_eq!(result.branch_name, "feature/w1");
+    assert_eq!(result.worktree_path, repo_root.join("worktrees").join("w1"));
+    assert!(runner.calls().iter().any(|call| {
+        call.program == "git"
+            && call.args.starts_with(&[
+                "worktree".to_string(),
+                "add".to_string(),
+                "-b".to_string(),
+                "feature/w1".to_string(),
+            ])
+    }));
+    let entry = seshmux_core::registry::find_entry_by_name(&repo_root.join("worktrees"), "w1")
+        .expect("registry")
+        .expect("entry");
+    assert_eq!(entry.branch.as_deref(), Some("feature/w1"));
+    assert_eq!(entry.start_point.as_deref(), Some("abc123"));
+    assert_eq!(entry.session_name.as_deref(), Some("repo/w1"));
+    assert_eq!(
+        entry.layout,
+        Some(seshmux_core::config::SessionLayout::Session)
+    );
+    assert!(entry.extras_copied.is_empty());
+
+    let calls_before = runner.calls().len();
+    request.worktree_name = "w2".to_string();
+    request.branch_name = Some("fix..typo".to_string());
+    let error = app
+        .new_execute(request, &mut |_| {})
+        .expect_err("invalid branch should fail");
+    assert!(format!("{error:#}").contains("invalid branch name 'fix..typo'"));
+    assert_eq!(runner.calls().len(), calls_before);
+}
+
+#[test]
+fn new_execute_runs_detected_checkout_steps_unless_overridden() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+    fs::write(repo_root.join(".gitmodules"), "").expect("gitmodules");
+    fs::write(
+        repo_root.join(".gitattributes"),
+        "*.bin filter=lfs diff=lfs merge=lfs -text\n",
+    )
+    .expect("gitattributes");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("refs/heads/main\n", "", 0),
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "", 1),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let prepare = app.new_prepare(&repo_root).expect("prepare");
+    assert!(prepare.lfs_pull);
+    assert!(prepare.init_submodules);
+
+    let mut phases = Vec::new();
+    let mut request = NewRequest::new(
+        repo_root.clone(),
+        "w1",
+        NewStartPoint::Commit("abc123".to_string()),
+    );
+    request.lfs_pull = Some(false);
+    app.new_execute(request, &mut |progress| phases.push(progress.phase))
+        .expect("new should succeed");
+
+    assert_eq!(
+        phases,
+        vec![
+            NewPhase::CreatingWorktree,
+            NewPhase::InitializingSubmodules,
+            NewPhase::Registering,
+            NewPhase::StartingSession,
+        ]
+    );
+    let calls = runner.calls();
+    assert!(calls.iter().any(|call| {
+        call.program == "git" && call.args == ["submodule", "update", "--init", "--recursive"]
+    }));
+    assert!(
+        !calls
+            .iter()
+            .any(|call| call.args.first().is_some_and(|arg| arg == "lfs"))
+    );
+}
+
+#[test]
+fn new_execute_allows_direnv_when_configured_and_the_worktree_has_an_envrc() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    let config_path = temp.path().join(".config/seshmux/config.toml");
+    let config = fs::read_to_string(&config_path).expect("read config");
+    fs::write(
+        &config_path,
+        format!("{config}\n[checkout]\ndirenv = \"auto\"\n"),
+    )
+    .expect("write config");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+    fs::write(repo_root.join(".envrc"), "use flake\n").expect("envrc");
+    // The scripted `git worktree add` creates nothing, so the checkout's `.envrc` is laid down here.
+    let worktree_path = repo_root.join("worktrees/w1");
+    fs::create_dir_all(&worktree_path).expect("worktree dir");
+    fs::write(worktree_path.join(".envrc"), "use flake\n").expect("worktree envrc");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("refs/heads/main\n", "", 0),
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "", 1),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let prepare = app.new_prepare(&repo_root).expect("prepare");
+    assert!(prepare.has_envrc);
+    assert!(prepare.direnv_allow);
+
+    let mut phases = Vec::new();
+    app.new_execute(
+        NewRequest::new(
+            repo_root.clone(),
+            "w1",
+            NewStartPoint::Commit("abc123".to_string()),
+        ),
+        &mut |progress| phases.push(progress.phase),
+    )
+    .expect("new should succeed");
+
+    assert_eq!(
+        phases,
+        vec![
+            NewPhase::CreatingWorktree,
+            NewPhase::AllowingDirenv,
+            NewPhase::Registering,
+            NewPhase::StartingSession,
+        ]
+    );
+    assert!(
+        runner
+            .calls()
+            .iter()
+            .any(|call| call.program == "direnv" && call.args == ["allow", "."])
+    );
+}
+
+#[test]
+fn new_quick_request_answers_from_the_quick_new_profile() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    let config_path = temp.path().join(".config/seshmux/config.toml");
+    let config = fs::read_to_string(&config_path).expect("read config");
+    fs::write(
+        &config_path,
+        format!(
+            "{config}\n[quick_new]\nstart_point = \"develop\"\ngitignore = true\nextras = [\".env\", \"missing/*\"]\nconnect = false\n"
+        ),
+    )
+    .expect("write config");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+    fs::write(repo_root.join(".env"), "TOKEN=1\n").expect("env file");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output(".env\0", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+
+    let request = app
+        .new_quick_request(&repo_root, "w1")
+        .expect("quick request");
+    assert_eq!(request.worktree_name, "w1");
+    assert_eq!(
+        request.start_point,
+        NewStartPoint::Branch("develop".to_string())
+    );
+    assert!(request.add_gitignore_entries);
+    assert_eq!(request.selected_extras, vec![PathBuf::from(".env")]);
+    assert!(!request.connect_now);
+
+    let error = app
+        .new_quick_request(&repo_root, "bad name")
+        .expect_err("invalid name");
+    assert!(
+        error.to_string().contains("invalid character ' '"),
+        "{error:#}"
+    );
+}
+
+#[test]
+fn new_execute_carries_uncommitted_and_untracked_changes_and_keeps_the_worktree_on_conflicts() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("notes")).expect("repo dir");
+    fs::write(repo_root.join("notes/todo.md"), "draft").expect("untracked file");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("9f8e7d6c\n", "", 0),
+            output("notes/todo.md\0", "", 0),
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 1),
+            output("", "", 0),
+            output("", "CONFLICT (content): Merge conflict in src/lib.rs", 1),
+            output("src/lib.rs\n", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let mut phases = Vec::new();
+    let mut request = NewRequest::new(
+        repo_root.clone(),
+        "w1",
+        NewStartPoint::Commit("abc123".to_string()),
+    );
+    request.lfs_pull = Some(false);
+    request.init_submodules = Some(false);
+    request.carry_changes = true;
+    let result = app
+        .new_execute(request, &mut |progress| phases.push(progress.phase))
+        .expect("new should succeed");
+
+    assert_eq!(
+        result.carried_changes,
+        Some(CarriedChanges::Conflicts {
+            paths: vec!["src/lib.rs".to_string()]
+        })
+    );
+    assert_eq!(
+        phases,
+        vec![
+            NewPhase::CreatingWorktree,
+            NewPhase::CarryingChanges,
+            NewPhase::Registering,
+            NewPhase::StartingSession,
+        ]
+    );
+    let calls = runner.calls();
+    assert_eq!(calls[2].args, vec!["stash", "create"]);
+    assert_eq!(
+        calls[3].args[..3],
+        ["ls-files", "--others", "--exclude-standard"]
+    );
+    assert_eq!(calls[7].args, vec!["stash", "apply", "9f8e7d6c"]);
+    assert_eq!(
+        fs::read_to_string(result.worktree_path.join("notes/todo.md")).expect("carried file"),
+        "draft"
+    );
+    assert!(!calls.iter().any(|call| {
+        call.args
+            .starts_with(&["worktree".to_string(), "remove".to_string()])
+    }));
+}
+
+#[test]
+fn new_execute_creates_the_start_branch_and_deletes_it_when_the_worktree_fails() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("abc123\n", "", 0),
+            output("", "", 0),
+            output("", "", 1),
+            output("", "fatal: could not create work tree dir", 128),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let mut request = NewRequest::new(
+        repo_root.clone(),
+        "w1",
+        NewStartPoint::NewBranchFrom("topic".to_string()),
+    );
+    request.lfs_pull = Some(false);
+    request.init_submodules = Some(false);
+    let error = app
+        .new_execute(request, &mut |_| {})
+        .expect_err("worktree creation should fail");
+
+    assert!(
+        error
+            .to_string()
+            .contains("was rolled back (deleted branch 'w1', deleted branch 'topic')")
+    );
+    let calls = runner.calls();
+    assert_eq!(calls[3].args, vec!["branch", "topic", "HEAD"]);
+    assert_eq!(
+        calls[4].args,
+        vec!["rev-parse", "--verify", "--quiet", "refs/heads/w1"]
+    );
+    assert_eq!(calls[5].args[..3], ["worktree", "add", "-b"]);
+    assert_eq!(calls[5].args.last().map(String::as_str), Some("topic"));
+    // git can leave the new branch behind when the checkout itself fails.
+    assert_eq!(calls[7].args, vec!["branch", "-D", "w1"]);
+    assert_eq!(calls[9].args, vec!["branch", "-D", "topic"]);
+}
+
+#[test]
+fn new_execute_streams_bootstrap_commands_and_keeps_the_worktree_when_one_fails() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+    fs::write(
+        repo_root.join(".seshmux.toml"),
+        "version = 1\n\n[hooks]\nprovision = [\"npm ci\", \"cargo fetch\", \"make env\"]\n",
+    )
+    .expect("repo config");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "", 1),
+            output("", "", 0),
+            output("added 3 packages\n", "", 0),
+            output("", "error: failed to fetch\n", 101),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let provision_log = ProvisionLog::default();
+    let app = App::new(&runner).with_provision_log(provision_log.clone());
+    let mut events = Vec::new();
+    let mut request = NewRequest::new(
+        repo_root.clone(),
+        "w1",
+        NewStartPoint::Commit("abc123".to_string()),
+    );
+    request.lfs_pull = Some(false);
+    request.init_submodules = Some(false);
+    let result = app
+        .new_execute(request, &mut |progress| {
+            events.push((progress.phase, progress.detail, progress.output))
+        })
+        .expect("new should succeed");
+
+    assert_eq!(result.provision.ran, vec!["npm ci".to_string()]);
+    assert_eq!(
+        result.provision.failure.as_deref(),
+        Some("`cargo fetch` failed (exit 101)")
+    );
+    let provisioning: Vec<_> = events
+        .iter()
+        .filter(|(phase, _, _)| *phase == NewPhase::Provisioning)
+        .map(|(_, detail, output)| (detail.as_str(), output.as_deref()))
+        .collect();
+    assert_eq!(
+        provisioning,
+        vec![
+            ("npm ci", None),
+            ("npm ci", Some("added 3 packages")),
+            ("cargo fetch", None),
+            ("cargo fetch", Some("error: failed to fetch")),
+        ]
+    );
+    let logged: Vec<_> = provision_log
+        .take()
+        .into_iter()
+        .map(|output| (output.command, output.status, output.lines))
+        .collect();
+    assert_eq!(
+        logged,
+        vec![
+            (
+                "npm ci".to_string(),
+                Some(0),
+                vec!["added 3 packages".to_string()]
+            ),
+            (
+                "cargo fetch".to_string(),
+                Some(101),
+                vec!["error: failed to fetch".to_string()]
+            ),
+        ]
+    );
+    let calls = runner.calls();
+    assert_eq!(calls[4].program, "/bin/sh");
+    assert_eq!(calls[4].args, vec!["-c", "npm ci"]);
+    assert!(
+        !calls
+            .iter()
+            .any(|call| call.args.contains(&"make env".to_string()))
+    );
+    assert!(!calls.iter().any(|call| {
+        call.args
+            .starts_with(&["worktree".to_string(), "remove".to_string()])
+    }));
+}
+
+#[test]
+fn new_batch_reports_a_failed_branch_lookup_instead_of_creating() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "fatal: not a git repository", 128),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+    let batch = NewBatch::parse(r#"[{"name": "w1"}]"#).expect("batch");
+
+    let items = app.new_batch(&repo_root, batch, false, &mut |_, _| {});
+
+    assert_eq!(items.len(), 1);
+    let NewBatchOutcome::Failed(message) = &items[0].outcome else {
+        panic!(
+            "lookup failure should fail the worktree: {:?}",
+            items[0].outcome
+        );
+    };
+    assert!(message.contains("failed to list git worktrees"));
+    assert_eq!(runner.calls().len(), 2);
+}
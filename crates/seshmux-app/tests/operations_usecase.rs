@@ -2,8 +2,14 @@ mod support;
 
 use std::fs;
 
-use seshmux_app::{App, AttachError, AttachRequest, DeleteRequest};
-use seshmux_core::registry::load_registry;
+use seshmux_app::{
+    App, AttachError, AttachRequest, BootstrapRequest, BootstrapState, BranchDiffRequest,
+    BranchGraphRequest, DeleteError, DeleteRequest, ForeignWorktree, LayoutCaptureRequest,
+    OpenRequest, SearchRequest, SessionScope,
+};
+use seshmux_core::registry::{
+    RegistryEntry, find_entry_by_name, insert_unique_entry, load_registry,
+};
 
 use support::{ENV_LOCK, QueueRunner, add_registry_entry, output, write_valid_config};
 
@@ -32,14 +38,93 @@ fn list_sorts_by_recency_and_includes_runtime_fields() {
     assert_eq!(result.rows.len(), 2);
     assert_eq!(result.rows[0].name, "new");
     assert_eq!(result.rows[0].path, new_path);
-    assert_eq!(result.rows[0].branch, "new-branch");
+    assert_eq!(result.rows[0].head.branch(), Some("new-branch"));
     assert!(result.rows[0].session_running);
     assert_eq!(result.rows[1].name, "old");
     assert_eq!(result.rows[1].path, old_path);
-    assert_eq!(result.rows[1].branch, "old-branch");
+    assert_eq!(result.rows[1].head.branch(), Some("old-branch"));
     assert!(!result.rows[1].session_running);
 }
 
+#[test]
+fn list_ignores_invalid_settings_it_does_not_read() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    let config_dir = temp.path().join(".config").join("seshmux");
+    fs::create_dir_all(&config_dir).expect("config dir");
+    fs::write(
+        config_dir.join("config.toml"),
+        "version = 1\n\n[ui]\ntick_ms = 1\n\n[[tmux.windows]]\nname = \"editor\"\nprogram = \"nvim\"\n",
+    )
+    .expect("write config");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+    let repo_root = temp.path().join("repo");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("w1-branch\n", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+    let result = app.list(&repo_root).expect("list result");
+    assert_eq!(result.rows[0].head.branch(), Some("w1-branch"));
+
+    let error = app.ensure_config_ready().expect_err("full validation");
+    assert!(error.to_string().contains("ui.tick_ms"), "{error}");
+}
+
+#[test]
+fn shared_worktrees_dir_belongs_to_the_first_repository() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    let config_dir = temp.path().join(".config").join("seshmux");
+    fs::create_dir_all(&config_dir).expect("config dir");
+    let shared = temp.path().join("shared");
+    fs::write(
+        config_dir.join("config.toml"),
+        format!(
+            "version = 1\nworktrees_dir = \"{}/{{repo}}\"\n\n[[tmux.windows]]\nname = \"editor\"\nprogram = \"nvim\"\n",
+            shared.display()
+        ),
+    )
+    .expect("write config");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+    let first = temp.path().join("a").join("shop");
+    let second = temp.path().join("b").join("shop");
+    fs::create_dir_all(&first).expect("first repo");
+    fs::create_dir_all(&second).expect("second repo");
+    seshmux_core::registry::save_always_skip_buckets(
+        &shared.join("shop"),
+        &std::collections::BTreeSet::new(),
+    )
+    .expect("create registry");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", first.display()), "", 0),
+            output(&format!("{}\n", second.display()), "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+    app.list(&first).expect("first repository");
+    let error = app.list(&second).expect_err("second repository");
+    assert!(
+        error.to_string().contains("belongs to the repository at"),
+        "{error}"
+    );
+}
+
 #[test]
 fn attach_returns_missing_session_error_when_create_is_false() {
     let temp = tempfile::tempdir().expect("temp dir");
@@ -56,18 +141,281 @@ fn attach_returns_missing_session_error_when_create_is_false() {
     );
 
     let app = App::new(&runner);
-    let error = app
-        .attach(AttachRequest {
+    let mut request = AttachRequest::new(repo_root.clone(), "w1");
+    request.connect = true;
+    let error = app.attach(request).expect_err("missing session error");
+
+    let typed = error
+        .downcast_ref::<AttachError>()
+        .expect("typed attach error");
+    assert!(matches!(typed, AttachError::MissingSession { .. }));
+}
+
+#[test]
+fn attach_reports_a_listed_session_that_died_as_gone() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "no server running on /tmp/tmux-1000/default", 1),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let mut request = AttachRequest::new(repo_root.clone(), "w1");
+    request.expect_running = true;
+    request.connect = true;
+    let error = app.attach(request).expect_err("session gone error");
+
+    let typed = error
+        .downcast_ref::<AttachError>()
+        .expect("typed attach error");
+    assert!(matches!(
+        typed,
+        AttachError::SessionGone {
+            server_reachable: false,
+            ..
+        }
+    ));
+    assert!(
+        error
+            .to_string()
+            .contains("the tmux server is not reachable")
+    );
+    assert!(runner.calls().iter().all(|call| !call.interactive));
+}
+
+#[test]
+fn attach_without_connect_returns_command_for_existing_session() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let mut request = AttachRequest::new(repo_root.clone(), "w1");
+    request.create_if_missing = true;
+    let result = app.attach(request).expect("attach result");
+
+    assert!(!result.created_session);
+    assert!(result.connect_command.starts_with("tmux "));
+    assert!(result.connect_command.ends_with(" -t '=repo/w1'"));
+    let entry = find_entry_by_name(&repo_root.join("worktrees"), "w1")
+        .expect("registry load")
+        .expect("entry");
+    assert!(entry.last_attached_at.is_some());
+}
+
+#[test]
+fn attach_uses_the_session_recorded_in_the_registry() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("repo");
+    let worktree_path = repo_root.join("worktrees").join("w1");
+    fs::create_dir_all(&worktree_path).expect("worktree dir");
+    insert_unique_entry(&repo_root.join("worktrees"), {
+        let mut entry = RegistryEntry::new(
+            "w1",
+            worktree_path.to_string_lossy(),
+            "2026-02-25T10:00:00Z",
+        );
+        entry.session_name = Some("legacy-w1".to_string());
+        entry.layout = Some(seshmux_core::config::SessionLayout::Session);
+        entry
+    })
+    .expect("insert registry");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let result = app
+        .attach(AttachRequest::new(repo_root.clone(), "w1"))
+        .expect("attach result");
+
+    assert_eq!(result.session_name, "legacy-w1");
+    assert!(result.connect_command.ends_with(" -t '=legacy-w1'"));
+    assert!(
+        runner
+            .calls()
+            .iter()
+            .any(|call| call.args.iter().any(|arg| arg.contains("legacy-w1")))
+    );
+}
+
+#[test]
+fn last_attached_worktree_skips_the_one_cwd_is_inside() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("repo");
+    let worktrees_dir = repo_root.join("worktrees");
+    fs::create_dir_all(&worktrees_dir).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+    let w2_path = add_registry_entry(&repo_root, "w2", "2026-02-25T11:00:00Z");
+    add_registry_entry(&repo_root, "w3", "2026-02-25T12:00:00Z");
+    seshmux_core::registry::record_attach(&worktrees_dir, "w1", "2026-03-01T09:00:00Z")
+        .expect("record w1");
+    seshmux_core::registry::record_attach(&worktrees_dir, "w2", "2026-03-02T09:00:00Z")
+        .expect("record w2");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output(&format!("{}\n", repo_root.display()), "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+
+    assert_eq!(
+        app.last_attached_worktree(&repo_root).expect("from repo"),
+        "w2"
+    );
+    assert_eq!(
+        app.last_attached_worktree(&w2_path.join("src"))
+            .expect("from w2"),
+        "w1"
+    );
+    assert!(runner.calls().iter().all(|call| call.program == "git"));
+}
+
+#[test]
+fn capture_layout_appends_the_running_session_to_the_config() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    let worktree_path = add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let panes = format!(
+        "1\teditor\tc1a2,200x50,0,0\tnvim\t{path}\n2\tdev\tb3c4,200x50,0,0[200x25,0,0,2,200x24,0,26,3]\tcargo\t{path}/crates\n2\tdev\tb3c4,200x50,0,0[200x25,0,0,2,200x24,0,26,3]\tzsh\t{path}\n",
+        path = worktree_path.display()
+    );
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output(&panes, "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+
+    let result = app
+        .capture_layout(LayoutCaptureRequest {
             cwd: repo_root.clone(),
             worktree_name: "w1".to_string(),
-            create_if_missing: false,
+            layout_name: Some("rust".to_string()),
         })
-        .expect_err("missing session error");
+        .expect("capture layout");
+
+    assert_eq!(result.layout_name, "rust");
+    assert_eq!(result.session_name, "repo/w1");
+    assert_eq!((result.windows, result.panes), (2, 3));
+
+    let config = seshmux_core::config::load_config(&result.config_path, &Default::default())
+        .expect("load config");
+    let layout = &config.layouts["rust"];
+    assert_eq!(layout.windows[1].name, "dev");
+    assert_eq!(layout.windows[1].panes[0].cwd.as_deref(), Some("crates"));
+    assert_eq!(layout.windows[1].panes[0].command.as_deref(), Some("cargo"));
+    assert_eq!(layout.windows[1].panes[1].command, None);
+    assert_eq!(config.tmux.windows[0].name, "editor");
+
+    let calls = runner.calls();
+    assert!(calls.iter().skip(1).all(|call| call.program == "tmux"));
+    assert_eq!(calls[2].args[..4], ["list-panes", "-s", "-t", "repo/w1"]);
+}
+
+#[test]
+fn attach_reports_tmux_exit_status_when_connect_fails() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+        ],
+        vec![Ok(2)],
+    );
+
+    let app = App::new(&runner);
+    let mut request = AttachRequest::new(repo_root.clone(), "w1");
+    request.create_if_missing = true;
+    request.connect = true;
+    let error = app.attach(request).expect_err("connect failure");
 
     let typed = error
         .downcast_ref::<AttachError>()
         .expect("typed attach error");
-    assert!(matches!(typed, AttachError::MissingSession { .. }));
+    assert!(matches!(
+        typed,
+        AttachError::ConnectFailed {
+            created_session: false,
+            status: 2,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn attach_reports_a_session_that_died_while_connecting_as_gone() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "can't find session: repo/w1", 1),
+        ],
+        vec![Ok(1)],
+    );
+
+    let app = App::new(&runner);
+    let mut request = AttachRequest::new(repo_root.clone(), "w1");
+    request.create_if_missing = true;
+    request.connect = true;
+    let error = app.attach(request).expect_err("session gone error");
+
+    let typed = error
+        .downcast_ref::<AttachError>()
+        .expect("typed attach error");
+    assert!(matches!(
+        typed,
+        AttachError::SessionGone {
+            server_reachable: true,
+            ..
+        }
+    ));
+    let calls = runner.calls();
+    assert_eq!(calls.last().expect("health query").args[0], "has-session");
 }
 
 #[test]
@@ -89,21 +437,23 @@ fn attach_creates_session_when_missing_and_connects() {
             output(&format!("{}\n", repo_root.display()), "", 0),
             output("", "missing session", 1),
             output("", "", 0),
+            output("1\n", "", 0),
+            output("0\n", "", 0),
+            output("", "", 0),
+            output("", "", 0),
             output("", "", 0),
         ],
         vec![Ok(0)],
     );
 
     let app = App::new(&runner);
-    let result = app
-        .attach(AttachRequest {
-            cwd: repo_root.clone(),
-            worktree_name: "w1".to_string(),
-            create_if_missing: true,
-        })
-        .expect("attach result");
+    let mut request = AttachRequest::new(repo_root.clone(), "w1");
+    request.create_if_missing = true;
+    request.connect = true;
+    let result = app.attach(request).expect("attach result");
 
     assert!(result.created_session);
+    assert_eq!(result.attach_status, Some(0));
     assert_eq!(result.worktree_name, "w1");
 
     let calls = runner.calls();
@@ -112,6 +462,10 @@ fn attach_creates_session_when_missing_and_connects() {
             && call.args.first().map(|value| value.as_str()) == Some("new-session")
             && !call.interactive
     }));
+    assert!(calls.iter().any(|call| {
+        call.args.first().map(|value| value.as_str()) == Some("new-window")
+            && call.args.get(2).map(|value| value.as_str()) == Some("repo/w1:2")
+    }));
     assert!(calls.iter().any(|call| {
         call.program == "tmux"
             && matches!(
@@ -123,76 +477,953 @@ fn attach_creates_session_when_missing_and_connects() {
 }
 
 #[test]
-fn delete_with_all_options_kills_session_removes_worktree_and_branch() {
+fn attach_in_window_layout_adds_a_window_to_the_repo_session() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
     let temp = tempfile::tempdir().expect("temp dir");
+    let config_dir = temp.path().join(".config").join("seshmux");
+    fs::create_dir_all(&config_dir).expect("config dir");
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+version = 1
+
+[tmux]
+layout = "window"
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+
+[[tmux.windows]]
+name = "git"
+program = "lazygit"
+"#,
+    )
+    .expect("write config");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
     let repo_root = temp.path().join("repo");
     fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
-    let worktree_path = add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
 
     let runner = QueueRunner::new(
         vec![
             output(&format!("{}\n", repo_root.display()), "", 0),
-            output("", "", 0),
-            output("", "", 0),
+            output("w2\nw10\n", "", 0),
             output("", "", 0),
             output("", "", 0),
         ],
-        Vec::new(),
+        vec![Ok(0)],
     );
 
     let app = App::new(&runner);
-    let result = app
-        .delete(DeleteRequest {
-            cwd: repo_root.clone(),
-            worktree_name: "w1".to_string(),
-            kill_tmux_session: true,
-            delete_branch: true,
-            force_worktree: false,
-        })
-        .expect("delete result");
+    let mut request = AttachRequest::new(repo_root.clone(), "w1");
+    request.create_if_missing = true;
+    request.connect = true;
+    let result = app.attach(request).expect("attach result");
 
-    assert_eq!(result.worktree_path, worktree_path);
-    assert_eq!(result.repo_root, repo_root);
-    assert!(result.branch_deleted);
-    assert!(result.branch_delete_error.is_none());
-    assert!(load_registry(&repo_root).expect("registry load").is_empty());
+    assert!(result.created_session);
+    assert_eq!(result.session_name, "repo:w1");
+    assert!(
+        result
+            .connect_command
+            .starts_with("tmux select-window -t 'repo:=w1' ';' ")
+    );
+
+    let calls = runner.calls();
+    assert_eq!(
+        calls[1].args,
+        vec!["list-windows", "-t", "=repo", "-F", "#{window_name}"]
+    );
+    assert!(calls[3].args.starts_with(&[
+        "new-window".to_string(),
+        "-d".to_string(),
+        "-t".to_string(),
+        "repo:".to_string(),
+    ]));
+    assert!(calls[3].args.contains(&"nvim".to_string()));
+    assert!(
+        !calls
+            .iter()
+            .any(|call| call.args.contains(&"lazygit".to_string()))
+    );
+    let connect = calls.last().expect("connect call");
+    assert!(connect.interactive);
+    assert_eq!(connect.args[..4], ["select-window", "-t", "repo:=w1", ";"]);
 }
 
 #[test]
-fn delete_keeps_branch_when_not_fully_merged() {
+fn open_launches_configured_editor_in_worktree() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
     let temp = tempfile::tempdir().expect("temp dir");
+    let config_dir = temp.path().join(".config").join("seshmux");
+    fs::create_dir_all(&config_dir).expect("config dir");
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+version = 1
+editor = ["code", "--wait"]
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#,
+    )
+    .expect("write config");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
     let repo_root = temp.path().join("repo");
     fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
-    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+    let worktree_path = add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
 
     let runner = QueueRunner::new(
-        vec![
-            output(&format!("{}\n", repo_root.display()), "", 0),
-            output("", "", 0),
-            output("", "error: the branch 'w1' is not fully merged.", 1),
-        ],
-        Vec::new(),
+        vec![output(&format!("{}\n", repo_root.display()), "", 0)],
+        vec![Ok(0)],
     );
 
     let app = App::new(&runner);
     let result = app
-        .delete(DeleteRequest {
+        .open(OpenRequest {
             cwd: repo_root.clone(),
             worktree_name: "w1".to_string(),
-            kill_tmux_session: false,
-            delete_branch: true,
-            force_worktree: false,
+            location: None,
         })
-        .expect("delete should still succeed");
+        .expect("open result");
 
-    assert!(!result.branch_deleted);
-    assert_eq!(result.repo_root, repo_root);
-    assert!(
-        result
-            .branch_delete_error
-            .as_deref()
+    assert_eq!(result.worktree_path, worktree_path);
+    let calls = runner.calls();
+    let launch = calls.last().expect("editor call");
+    assert_eq!(launch.program, "code");
+    assert_eq!(launch.args, vec!["--wait", "."]);
+    assert!(launch.interactive);
+}
+
+#[test]
+fn delete_with_all_options_kills_session_removes_worktree_and_branch() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    let worktree_path = add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "can't find session", 1),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let mut request = DeleteRequest::new(repo_root.clone(), "w1");
+    request.kill_tmux_session = true;
+    request.delete_branch = true;
+    let result = app.delete(request).expect("delete result");
+
+    assert_eq!(result.worktree_path, worktree_path);
+    assert_eq!(result.repo_root, repo_root);
+    assert!(result.branch_deleted);
+    assert!(result.branch_delete_error.is_none());
+    assert!(result.leaked_resources.is_empty());
+    assert!(
+        load_registry(&repo_root.join("worktrees"))
+            .expect("registry load")
+            .is_empty()
+    );
+}
+
+#[test]
+fn delete_refuses_the_worktree_cwd_is_inside_until_allowed() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    let worktree_path = add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+    let cwd = worktree_path.join("src");
+    fs::create_dir_all(&cwd).expect("cwd");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+    let mut request = DeleteRequest::new(cwd, "w1");
+
+    let error = app.delete(request.clone()).expect_err("cwd inside");
+    assert!(matches!(
+        error.downcast_ref::<DeleteError>(),
+        Some(DeleteError::CwdInsideWorktree { name, .. }) if name == "w1"
+    ));
+    assert_eq!(runner.calls().len(), 1);
+
+    request.allow_cwd_inside = true;
+    app.delete(request).expect("delete from inside");
+    let calls = runner.calls();
+    assert_eq!(calls[2].program, "git");
+    assert_eq!(calls[2].args[..2], ["worktree", "remove"]);
+}
+
+#[test]
+fn delete_runs_deprovision_hooks_and_reports_leaks() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    let worktree_path = add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+    fs::create_dir_all(&worktree_path).expect("worktree dir");
+    fs::write(
+        repo_root.join(".seshmux.toml"),
+        "version = 1\n\n[hooks]\ndeprovision = [\"docker compose down\", \"./release-port\"]\n",
+    )
+    .expect("write repo config");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "port still bound", 1),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let mut request = DeleteRequest::new(repo_root.clone(), "w1");
+    request.kill_tmux_session = true;
+    let result = app.delete(request).expect("delete result");
+
+    let calls = runner.calls();
+    assert_eq!(calls[1].program, "/bin/sh");
+    assert_eq!(calls[1].args, vec!["-c", "docker compose down"]);
+    assert_eq!(calls[2].args, vec!["-c", "./release-port"]);
+    assert_eq!(result.leaked_resources.len(), 2);
+    assert!(result.leaked_resources[0].contains("./release-port"));
+    assert!(result.leaked_resources[0].contains("port still bound"));
+    assert!(result.leaked_resources[1].contains("still running"));
+}
+
+#[test]
+fn delete_does_not_rerun_deprovision_hooks_after_a_failed_removal() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    let worktree_path = add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+    fs::create_dir_all(&worktree_path).expect("worktree dir");
+    fs::write(
+        repo_root.join(".seshmux.toml"),
+        "version = 1\n\n[hooks]\ndeprovision = [\"docker compose down\"]\n",
+    )
+    .expect("write repo config");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "no session", 1),
+            output("", "fatal: contains modified or untracked files", 128),
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "no session", 1),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+    let mut request = DeleteRequest::new(repo_root.clone(), "w1");
+    request.kill_tmux_session = true;
+
+    app.delete(request.clone()).expect_err("removal fails");
+    assert!(
+        find_entry_by_name(&repo_root.join("worktrees"), "w1")
+            .expect("registry")
+            .expect("entry kept")
+            .deprovisioned
+    );
+    request.force_worktree = true;
+    app.delete(request).expect("forced retry");
+
+    let hook_runs = runner
+        .calls()
+        .iter()
+        .filter(|call| call.program == "/bin/sh")
+        .count();
+    assert_eq!(hook_runs, 1);
+}
+
+#[test]
+fn delete_reports_environment_a_surviving_session_still_exports() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+    fs::write(
+        repo_root.join(".seshmux.toml"),
+        "version = 1\n\n[tmux]\nenvironment = { PORT = \"{port}\", WORKTREE = \"{name}\" }\n\n[[tmux.windows]]\nname = \"editor\"\nprogram = \"nvim\"\n",
+    )
+    .expect("write repo config");
+    let port = seshmux_core::session_env::worktree_port("repo", "w1");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output(&format!("PORT={port}\nWORKTREE=other\n-GONE\n"), "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+    let result = app
+        .delete(DeleteRequest::new(repo_root.clone(), "w1"))
+        .expect("delete result");
+
+    assert_eq!(
+        runner.calls()[2].args,
+        vec!["show-environment", "-t", "repo/w1"]
+    );
+    assert_eq!(
+        result.leaked_resources,
+        vec!["tmux session 'repo/w1' still exports PORT".to_string()]
+    );
+}
+
+#[test]
+fn delete_keeps_branch_when_not_fully_merged() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "error: the branch 'w1' is not fully merged.", 1),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let mut request = DeleteRequest::new(repo_root.clone(), "w1");
+    request.delete_branch = true;
+    let result = app.delete(request).expect("delete should still succeed");
+
+    assert!(!result.branch_deleted);
+    assert_eq!(result.repo_root, repo_root);
+    assert!(
+        result
+            .branch_delete_error
+            .as_deref()
             .unwrap_or("")
             .contains("not fully merged")
     );
-    assert!(load_registry(&repo_root).expect("registry load").is_empty());
+    assert!(
+        load_registry(&repo_root.join("worktrees"))
+            .expect("registry load")
+            .is_empty()
+    );
+}
+
+#[test]
+fn delete_removes_the_branch_recorded_in_the_registry() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("repo");
+    let worktree_path = repo_root.join("worktrees").join("w1");
+    fs::create_dir_all(&worktree_path).expect("worktree dir");
+    insert_unique_entry(&repo_root.join("worktrees"), {
+        let mut entry = RegistryEntry::new(
+            "w1",
+            worktree_path.to_string_lossy(),
+            "2026-02-25T10:00:00Z",
+        );
+        entry.branch = Some("users/me/w1".to_string());
+        entry
+    })
+    .expect("insert registry");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let mut request = DeleteRequest::new(repo_root.clone(), "w1");
+    request.delete_branch = true;
+    let result = app.delete(request).expect("delete");
+
+    assert!(result.branch_deleted);
+    assert_eq!(result.branch_name, "users/me/w1");
+    let calls = runner.calls();
+    assert_eq!(
+        calls.last().expect("branch delete call").args,
+        vec!["branch", "-d", "users/me/w1"]
+    );
+}
+
+#[test]
+fn delete_refuses_foreign_worktree_when_ownership_is_enforced() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    let config_path = temp.path().join(".config/seshmux/config.toml");
+    let mut config = fs::read_to_string(&config_path).expect("read config");
+    config.push_str("\n[ownership]\nblock_foreign_worktrees = true\n");
+    fs::write(&config_path, config).expect("write config");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    let worktree_path = repo_root.join("worktrees").join("w1");
+    fs::create_dir_all(&worktree_path).expect("worktree dir");
+    insert_unique_entry(&repo_root.join("worktrees"), {
+        let mut entry = RegistryEntry::new(
+            "w1",
+            worktree_path.to_string_lossy(),
+            "2026-02-25T10:00:00Z",
+        );
+        entry.created_by = Some("someone-else@elsewhere".to_string());
+        entry
+    })
+    .expect("insert registry");
+
+    let runner = QueueRunner::new(
+        vec![output(&format!("{}\n", repo_root.display()), "", 0)],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let mut request = DeleteRequest::new(repo_root.clone(), "w1");
+    request.kill_tmux_session = true;
+    request.delete_branch = true;
+    let error = app
+        .delete(request)
+        .expect_err("foreign worktree should be protected");
+
+    assert!(format!("{error:#}").contains("created by someone-else@elsewhere"));
+    assert_eq!(
+        load_registry(&repo_root.join("worktrees"))
+            .expect("registry load")
+            .len(),
+        1
+    );
+    assert!(worktree_path.exists());
+}
+
+#[test]
+fn delete_asks_before_removing_someone_elses_worktree() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    let worktree_path = repo_root.join("worktrees").join("w1");
+    fs::create_dir_all(&worktree_path).expect("worktree dir");
+    insert_unique_entry(&repo_root.join("worktrees"), {
+        let mut entry = RegistryEntry::new(
+            "w1",
+            worktree_path.to_string_lossy(),
+            "2026-02-25T10:00:00Z",
+        );
+        entry.created_by = Some("someone-else@elsewhere".to_string());
+        entry
+    })
+    .expect("insert registry");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "no session", 1),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+    let mut request = DeleteRequest::new(repo_root.clone(), "w1");
+    request.kill_tmux_session = true;
+
+    let error = app.delete(request.clone()).expect_err("needs confirmation");
+    assert!(matches!(
+        error.downcast_ref::<ForeignWorktree>(),
+        Some(ForeignWorktree { owner, .. }) if owner == "someone-else@elsewhere"
+    ));
+    assert_eq!(runner.calls().len(), 1);
+
+    request.allow_foreign = true;
+    let result = app.delete(request).expect("confirmed delete");
+    assert_eq!(
+        result.foreign_owner.as_deref(),
+        Some("someone-else@elsewhere")
+    );
+}
+
+#[test]
+fn ensure_config_ready_migrates_unversioned_config_once() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    let config_dir = temp.path().join(".config").join("seshmux");
+    fs::create_dir_all(&config_dir).expect("config dir");
+    fs::write(
+        config_dir.join("config.toml"),
+        "[[tmux.windows]]\nname = \"editor\"\nprogram = \"nvim\"\n",
+    )
+    .expect("write config");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let runner = QueueRunner::default();
+    let app = App::new(&runner);
+
+    let config = app.ensure_config_ready().expect("config ready");
+    assert_eq!(config.version, 1);
+    let notices = app.take_notices();
+    assert_eq!(notices.len(), 1);
+    assert!(notices[0].contains("config migrated from version 0 to 1"));
+    assert!(config_dir.join("config.toml.v0.bak").exists());
+
+    app.ensure_config_ready().expect("config ready again");
+    assert!(app.take_notices().is_empty());
+}
+
+#[test]
+fn kill_all_sessions_kills_registered_sessions_and_saves_current_for_last() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+    let temp = tempfile::tempdir().expect("temp dir");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+        std::env::set_var("TMUX", "/tmp/tmux-1000/default,1,0");
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    for name in ["w1", "w2", "w3"] {
+        add_registry_entry(&repo_root, name, "2026-02-25T10:00:00Z");
+    }
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output(
+                "other/w1\nrepo/w1\nrepo/w2\nrepo/stray\nrepo/w3\nscratch\n",
+                "",
+                0,
+            ),
+            output("repo/w2\n", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let result = app.kill_all_sessions(&repo_root, SessionScope::Repo);
+    unsafe {
+        std::env::remove_var("TMUX");
+    }
+    let result = result.expect("kill all");
+
+    assert_eq!(result.killed, vec!["repo/w1", "repo/w3", "repo/w2"]);
+    let kills: Vec<Vec<String>> = runner
+        .calls()
+        .into_iter()
+        .filter(|call| call.args.first().map(String::as_str) == Some("kill-session"))
+        .map(|call| call.args)
+        .collect();
+    assert_eq!(kills.len(), 3);
+    assert_eq!(kills[2], vec!["kill-session", "-t", "=repo/w2"]);
+}
+
+#[test]
+fn kill_sessions_kills_exactly_the_given_sessions() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+    let temp = tempfile::tempdir().expect("temp dir");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let runner = QueueRunner::new(
+        vec![output("", "", 0), output("", "", 0), output("", "", 0)],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let killed = app
+        .kill_sessions(&["repo/w1".to_string(), "repo/w2".to_string()])
+        .expect("kill sessions");
+
+    assert_eq!(killed, vec!["repo/w1", "repo/w2"]);
+    let kills: Vec<Vec<String>> = runner
+        .calls()
+        .into_iter()
+        .filter(|call| call.args.first().map(String::as_str) == Some("kill-session"))
+        .map(|call| call.args)
+        .collect();
+    assert_eq!(
+        kills,
+        vec![
+            vec!["kill-session", "-t", "=repo/w1"],
+            vec!["kill-session", "-t", "=repo/w2"],
+        ]
+    );
+}
+
+#[test]
+fn kill_all_sessions_all_scope_also_kills_seshmux_named_sessions_of_other_repositories() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+        std::env::remove_var("TMUX");
+    }
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let listing = "other/w1\nrepo/w1\nscratch\nnested/a/b\n";
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output(listing, "", 0),
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output(listing, "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let known = app
+        .managed_sessions(&repo_root, SessionScope::Known)
+        .expect("known sessions");
+    assert_eq!(known.session_names, vec!["repo/w1"]);
+    assert_eq!(known.repo_roots, vec![repo_root.clone()]);
+
+    let killed = app
+        .kill_all_sessions(&repo_root, SessionScope::All)
+        .expect("kill sessions")
+        .killed;
+
+    assert_eq!(killed, vec!["other/w1", "repo/w1"]);
+    let kills: Vec<Vec<String>> = runner
+        .calls()
+        .into_iter()
+        .filter(|call| call.args.first().map(String::as_str) == Some("kill-session"))
+        .map(|call| call.args)
+        .collect();
+    assert_eq!(
+        kills,
+        vec![
+            vec!["kill-session", "-t", "=other/w1"],
+            vec!["kill-session", "-t", "=repo/w1"],
+        ]
+    );
+}
+
+#[test]
+fn kill_worktree_session_kills_only_that_session_and_keeps_the_worktree() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees").join("w1")).expect("worktree");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let result = app
+        .kill_worktree_session(&repo_root, "w1", false)
+        .expect("kill session");
+
+    assert_eq!(result.worktree_name, "w1");
+    assert_eq!(result.session_name, "repo/w1");
+    assert!(result.killed);
+    let calls = runner.calls();
+    assert_eq!(calls[2].args, vec!["kill-session", "-t", "=repo/w1"]);
+    assert!(repo_root.join("worktrees").join("w1").exists());
+}
+
+#[test]
+fn bootstrap_starts_flagged_sessions_without_recording_an_attach() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+    let repo_root = temp.path().join("repo");
+    let worktrees_dir = repo_root.join("worktrees");
+    add_registry_entry(&repo_root, "running", "2026-02-25T10:00:00Z");
+    add_registry_entry(&repo_root, "stopped", "2026-02-25T10:00:00Z");
+    add_registry_entry(&repo_root, "unflagged", "2026-02-25T10:00:00Z");
+    add_registry_entry(&repo_root, "gone", "2026-02-25T10:00:00Z");
+    fs::remove_dir_all(worktrees_dir.join("gone")).expect("remove worktree");
+    for name in ["running", "stopped", "gone"] {
+        seshmux_core::registry::set_autostart(&worktrees_dir, name, true).expect("flag");
+    }
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("", "missing session", 1),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let result = app
+        .bootstrap(BootstrapRequest {
+            cwd: repo_root.clone(),
+            flagged_only: true,
+            dash: false,
+        })
+        .expect("bootstrap");
+
+    let states: Vec<_> = result
+        .sessions
+        .iter()
+        .map(|session| (session.worktree_name.as_str(), &session.state))
+        .collect();
+    assert_eq!(states.len(), 3);
+    assert_eq!(states[0], ("running", &BootstrapState::AlreadyRunning));
+    assert_eq!(states[1], ("stopped", &BootstrapState::Started));
+    assert_eq!(states[2].0, "gone");
+    assert!(matches!(states[2].1, BootstrapState::Skipped(_)));
+    assert_eq!(result.failed(), 0);
+
+    let calls = runner.calls();
+    assert_eq!(calls[1].args, vec!["has-session", "-t", "=repo/running"]);
+    assert_eq!(
+        calls[3].args.first().map(String::as_str),
+        Some("new-session")
+    );
+    assert!(calls.iter().all(|call| !call.interactive));
+    let registry = load_registry(&worktrees_dir).expect("registry");
+    assert!(
+        registry
+            .iter()
+            .all(|entry| entry.last_attached_at.is_none())
+    );
+}
+
+#[test]
+fn bootstrap_queries_the_exact_session_so_a_prefix_sibling_does_not_count_as_running() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+    let repo_root = temp.path().join("repo");
+    let worktrees_dir = repo_root.join("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+    add_registry_entry(&repo_root, "w10", "2026-02-25T10:00:00Z");
+    seshmux_core::registry::set_autostart(&worktrees_dir, "w1", true).expect("flag");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "can't find session: =repo/w1", 1),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let result = app
+        .bootstrap(BootstrapRequest {
+            cwd: repo_root.clone(),
+            flagged_only: true,
+            dash: false,
+        })
+        .expect("bootstrap");
+
+    assert_eq!(result.sessions.len(), 1);
+    assert_eq!(result.sessions[0].worktree_name, "w1");
+    assert_eq!(result.sessions[0].state, BootstrapState::Started);
+    let calls = runner.calls();
+    assert_eq!(calls[1].args, vec!["has-session", "-t", "=repo/w1"]);
+    assert_eq!(
+        calls[2].args.first().map(String::as_str),
+        Some("new-session")
+    );
+}
+
+#[test]
+fn branch_graph_logs_worktree_head_against_default_branch() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("feature-w1\n", "", 0),
+            output("", "", 1),
+            output("", "", 0),
+            output(
+                "* abc1234 (HEAD -> feature-w1) work\n* def5678 (main) base\n",
+                "",
+                0,
+            ),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let graph = app
+        .branch_graph(BranchGraphRequest {
+            cwd: repo_root.clone(),
+            worktree_name: "w1".to_string(),
+        })
+        .expect("graph");
+
+    assert_eq!(graph.branch, "feature-w1");
+    assert_eq!(graph.base.as_deref(), Some("main"));
+    assert_eq!(graph.lines.len(), 2);
+    let log = runner.calls().pop().expect("log call");
+    assert_eq!(&log.args[log.args.len() - 3..], ["HEAD", "main", "--"]);
+}
+
+#[test]
+fn branch_diff_compares_the_worktree_with_its_merge_base() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("feature-w1\n", "", 0),
+            output("", "", 1),
+            output("", "", 0),
+            output("def5678\n", "", 0),
+            output(
+                " src/main.rs | 3 ++-\n 1 file changed, 2 insertions(+), 1 deletion(-)\n",
+                "",
+                0,
+            ),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let diff = app
+        .branch_diff(BranchDiffRequest {
+            cwd: repo_root.clone(),
+            worktree_name: "w1".to_string(),
+            patch: true,
+        })
+        .expect("diff");
+
+    assert_eq!(diff.base.as_deref(), Some("main"));
+    assert_eq!(diff.lines.len(), 2);
+    let calls = runner.calls();
+    assert_eq!(calls[4].args, ["merge-base", "main", "HEAD"]);
+    assert_eq!(
+        calls[5].args,
+        [
+            "diff",
+            "--color=never",
+            "--patch-with-stat",
+            "def5678",
+            "--"
+        ]
+    );
+}
+
+#[test]
+fn search_worktrees_groups_matches_and_skips_missing_worktrees() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-24T10:00:00Z");
+    let missing = add_registry_entry(&repo_root, "w2", "2026-02-25T10:00:00Z");
+    add_registry_entry(&repo_root, "w3", "2026-02-26T10:00:00Z");
+    fs::remove_dir_all(&missing).expect("remove w2");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("src/main.rs\x004\x00let port = 8080;\n", "", 0),
+            output("", "", 1),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let result = app
+        .search_worktrees(SearchRequest {
+            cwd: repo_root.clone(),
+            query: "port".to_string(),
+        })
+        .expect("search");
+
+    assert_eq!(result.groups.len(), 1);
+    assert_eq!(result.groups[0].worktree_name, "w1");
+    assert_eq!(result.groups[0].matches[0].line, 4);
+    assert!(!result.groups[0].truncated);
+    assert_eq!(runner.calls().len(), 3);
 }
@@ -0,0 +1,48 @@
+mod support;
+
+use std::collections::BTreeSet;
+use std::fs;
+
+use seshmux_app::App;
+use seshmux_core::registry::save_always_skip_buckets;
+
+use support::{ENV_LOCK, QueueRunner, output, write_valid_config};
+
+#[test]
+fn recent_commands_are_kept_in_the_registry_once_it_exists() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    let base = fs::canonicalize(temp.path()).expect("canonical temp");
+    unsafe {
+        std::env::set_var("HOME", &base);
+    }
+    write_valid_config(&base, false);
+
+    let repo_root = base.join("repo");
+    let worktrees_dir = repo_root.join("worktrees");
+    fs::create_dir_all(&repo_root).expect("repo");
+
+    let repo_root_output = || output(&format!("{}\n", repo_root.display()), "", 0);
+    let runner = QueueRunner::new((0..5).map(|_| repo_root_output()).collect(), Vec::new());
+    let app = App::new(&runner);
+
+    app.record_command(&repo_root, "doctor").expect("record");
+    assert!(!worktrees_dir.join("worktree.toml").exists());
+    assert!(app.recent_commands(&repo_root).expect("load").is_empty());
+
+    save_always_skip_buckets(&worktrees_dir, &BTreeSet::new()).expect("registry");
+    app.record_command(&repo_root, "doctor").expect("record");
+    app.record_command(&repo_root, "attach w1").expect("record");
+
+    assert_eq!(
+        app.recent_commands(&repo_root).expect("load"),
+        vec!["attach w1".to_string(), "doctor".to_string()]
+    );
+    assert!(
+        runner
+            .calls()
+            .iter()
+            .all(|call| call.program == "git" && call.args == vec!["rev-parse", "--show-toplevel"])
+    );
+}
@@ -0,0 +1,120 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use seshmux_app::{App, ReviewMode, ReviewRequest};
+use seshmux_core::command_runner::SystemCommandRunner;
+
+fn run_git(repo: &Path, args: &[&str]) {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo)
+        .output()
+        .expect("git command should execute");
+
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn commit_file(repo: &Path, file: &str, message: &str) {
+    fs::write(repo.join(file), format!("{message}\n")).expect("write file");
+    run_git(repo, &["add", "."]);
+    run_git(
+        repo,
+        &[
+            "-c",
+            "user.name=seshmux-test",
+            "-c",
+            "user.email=seshmux-test@example.com",
+            "commit",
+            "-m",
+            message,
+        ],
+    );
+}
+
+#[test]
+fn review_checks_out_each_commit_and_clean_removes_them_in_real_repo() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+
+    run_git(&repo_root, &["init"]);
+    commit_file(&repo_root, "README.md", "initial");
+    run_git(&repo_root, &["tag", "base"]);
+    commit_file(&repo_root, "a.txt", "add a");
+    commit_file(&repo_root, "b.txt", "add b");
+
+    let runner = SystemCommandRunner::new();
+    let app = App::new(&runner);
+    let result = app
+        .review(ReviewRequest {
+            cwd: repo_root.clone(),
+            range: "base..HEAD".to_string(),
+            mode: ReviewMode::EachCommit,
+        })
+        .expect("review should succeed");
+
+    let subjects: Vec<&str> = result
+        .worktrees
+        .iter()
+        .map(|worktree| worktree.commit.subject.as_str())
+        .collect();
+    assert_eq!(subjects, vec!["add a", "add b"]);
+    assert!(result.worktrees[0].path.join("a.txt").exists());
+    assert!(!result.worktrees[0].path.join("b.txt").exists());
+    assert!(result.worktrees[1].path.join("b.txt").exists());
+    let review_dir = result.worktrees[0]
+        .path
+        .parent()
+        .expect("review dir")
+        .to_path_buf();
+    assert_eq!(review_dir.file_name().expect("name"), ".review");
+
+    let again = app
+        .review(ReviewRequest {
+            cwd: repo_root.clone(),
+            range: "base..HEAD".to_string(),
+            mode: ReviewMode::EachCommit,
+        })
+        .expect("repeat review should succeed");
+    assert!(again.worktrees.iter().all(|worktree| worktree.reused));
+
+    let cleaned = app.review_clean(&repo_root).expect("clean should succeed");
+    assert_eq!(cleaned.removed.len(), 2);
+    assert!(!review_dir.exists());
+}
+
+#[test]
+fn review_endpoints_checks_out_merge_base_and_head_in_real_repo() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+
+    run_git(&repo_root, &["init"]);
+    commit_file(&repo_root, "README.md", "initial");
+    run_git(&repo_root, &["tag", "base"]);
+    commit_file(&repo_root, "a.txt", "add a");
+    commit_file(&repo_root, "b.txt", "add b");
+
+    let runner = SystemCommandRunner::new();
+    let app = App::new(&runner);
+    let result = app
+        .review(ReviewRequest {
+            cwd: repo_root.clone(),
+            range: "base..HEAD".to_string(),
+            mode: ReviewMode::Endpoints,
+        })
+        .expect("review should succeed");
+
+    let subjects: Vec<&str> = result
+        .worktrees
+        .iter()
+        .map(|worktree| worktree.commit.subject.as_str())
+        .collect();
+    assert_eq!(subjects, vec!["initial", "add b"]);
+}
@@ -0,0 +1,128 @@
+mod support;
+
+use std::fs;
+
+use seshmux_app::{App, RunRequest};
+
+use support::{ENV_LOCK, QueueRunner, add_registry_entry, output, write_valid_config};
+
+#[test]
+fn run_executes_in_the_worktree_and_returns_the_exit_status() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    let worktree_path = add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![output(&format!("{}\n", repo_root.display()), "", 0)],
+        vec![Ok(101)],
+    );
+    let app = App::new(&runner);
+
+    let result = app
+        .run_in_worktree(RunRequest {
+            cwd: repo_root.clone(),
+            worktree_name: "w1".to_string(),
+            command: vec!["cargo".to_string(), "test".to_string()],
+            in_window: false,
+        })
+        .expect("run");
+
+    assert_eq!(result.status, 101);
+    assert_eq!(result.worktree_path, worktree_path);
+    assert_eq!(result.window, None);
+
+    let calls = runner.calls();
+    let last = calls.last().expect("command call");
+    assert_eq!(last.program, "cargo");
+    assert_eq!(last.args, vec!["test"]);
+    assert!(last.interactive);
+}
+
+#[test]
+fn run_in_a_window_waits_for_the_status_the_window_records() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "", 0),
+            output("@4\n", "", 0),
+            output("", "", 0),
+            output("0\n", "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+
+    let result = app
+        .run_in_worktree(RunRequest {
+            cwd: repo_root.clone(),
+            worktree_name: "w1".to_string(),
+            command: vec!["/usr/bin/make".to_string(), "check".to_string()],
+            in_window: true,
+        })
+        .expect("run");
+
+    assert_eq!(result.status, 0);
+    assert_eq!(result.window.as_deref(), Some("repo/w1:run-make"));
+
+    let calls = runner.calls();
+    assert!(calls.iter().skip(1).all(|call| call.program == "tmux"));
+    assert_eq!(calls[1].args, vec!["has-session", "-t", "=repo/w1"]);
+    assert_eq!(calls[2].args[0], "new-window");
+    assert!(
+        calls[2]
+            .args
+            .ends_with(&["/usr/bin/make".to_string(), "check".to_string()])
+    );
+    assert_eq!(calls[3].args[0], "wait-for");
+    assert_eq!(calls[4].args[..2], ["show-options", "-w"]);
+}
+
+#[test]
+fn run_in_a_window_needs_a_running_session() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("", "can't find session: repo/w1", 1),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+
+    let error = app
+        .run_in_worktree(RunRequest {
+            cwd: repo_root.clone(),
+            worktree_name: "w1".to_string(),
+            command: vec!["make".to_string()],
+            in_window: true,
+        })
+        .expect_err("session is not running");
+
+    assert!(error.to_string().contains("is not running"));
+}
@@ -0,0 +1,81 @@
+mod support;
+
+use std::fs;
+
+use seshmux_app::{App, SkipRuleSource};
+use seshmux_core::registry::load_always_skip_buckets_for_indexing;
+
+use support::{ENV_LOCK, QueueRunner, output, write_valid_config};
+
+#[test]
+fn skip_rules_edit_the_registry_and_leave_repo_config_rules_alone() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    let base = fs::canonicalize(temp.path()).expect("canonical temp");
+    unsafe {
+        std::env::set_var("HOME", &base);
+    }
+    write_valid_config(&base, false);
+
+    let repo_root = base.join("repo");
+    let worktrees_dir = repo_root.join("worktrees");
+    fs::create_dir_all(&repo_root).expect("repo");
+    fs::write(
+        repo_root.join(".seshmux.toml"),
+        "version = 1\n\n[extras]\nalways_skip_buckets = [\"generated\"]\n",
+    )
+    .expect("repo config");
+
+    let repo_root_output = || output(&format!("{}\n", repo_root.display()), "", 0);
+    let runner = QueueRunner::new((0..5).map(|_| repo_root_output()).collect(), Vec::new());
+    let app = App::new(&runner);
+
+    let listed = app.skip_rules(&repo_root).expect("list");
+    assert!(
+        listed
+            .rules
+            .iter()
+            .any(|rule| rule.bucket == "node_modules" && rule.source == SkipRuleSource::Default)
+    );
+    assert!(
+        listed
+            .rules
+            .iter()
+            .any(|rule| rule.bucket == "generated" && rule.source == SkipRuleSource::RepoConfig)
+    );
+    assert!(!worktrees_dir.join("worktree.toml").exists());
+
+    let added = app
+        .add_skip_rule(&repo_root, "./apps/web/.next")
+        .expect("add");
+    assert!(
+        added
+            .rules
+            .iter()
+            .any(|rule| rule.bucket == "apps/web/.next" && rule.source == SkipRuleSource::Registry)
+    );
+
+    app.remove_skip_rule(&repo_root, "node_modules")
+        .expect("remove");
+    let saved = load_always_skip_buckets_for_indexing(&worktrees_dir).expect("load");
+    assert!(saved.buckets.contains("apps/web/.next"));
+    assert!(!saved.buckets.contains("node_modules"));
+
+    let error = app
+        .remove_skip_rule(&repo_root, "generated")
+        .expect_err("repo config rule");
+    assert!(format!("{error:#}").contains("edit that file to remove it"));
+
+    let error = app
+        .add_skip_rule(&repo_root, "../outside")
+        .expect_err("escaping path");
+    assert!(format!("{error:#}").contains("not a repository-relative path"));
+
+    assert!(
+        runner
+            .calls()
+            .iter()
+            .all(|call| call.program == "git" && call.args == vec!["rev-parse", "--show-toplevel"])
+    );
+}
@@ -81,13 +81,10 @@ impl CommandRunner for QueueRunner {
 }
 
 pub fn output(stdout: &str, stderr: &str, status: i32) -> anyhow::Result<CommandOutput> {
-    Ok(CommandOutput {
-        status_code: status,
-        stdout: stdout.to_string(),
-        stderr: stderr.to_string(),
-    })
+    Ok(CommandOutput::new(status, stdout, stderr))
 }
 
+#[allow(dead_code)]
 pub fn write_valid_config(home: &Path, include_git_window: bool) {
     let config_dir = home.join(".config").join("seshmux");
     fs::create_dir_all(&config_dir).expect("create config dir");
@@ -123,12 +120,8 @@ pub fn add_registry_entry(repo_root: &Path, name: &str, created_at: &str) -> Pat
     fs::create_dir_all(&path).expect("create worktree dir");
 
     seshmux_core::registry::insert_unique_entry(
-        repo_root,
-        seshmux_core::registry::RegistryEntry {
-            name: name.to_string(),
-            path: path.to_string_lossy().to_string(),
-            created_at: created_at.to_string(),
-        },
+        &repo_root.join("worktrees"),
+        seshmux_core::registry::RegistryEntry::new(name, path.to_string_lossy(), created_at),
     )
     .expect("insert registry");
 
@@ -0,0 +1,114 @@
+mod support;
+
+use std::fs;
+use std::path::Path;
+
+use seshmux_app::{App, AttachRequest};
+
+use support::{ENV_LOCK, QueueRunner, add_registry_entry, output};
+
+fn write_kitty_config(home: &Path) {
+    let config_dir = home.join(".config").join("seshmux");
+    fs::create_dir_all(&config_dir).expect("create config dir");
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+version = 1
+
+[tmux]
+backend = "kitty"
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+args = []
+
+[[tmux.windows]]
+name = "git"
+program = "lazygit"
+args = []
+"#,
+    )
+    .expect("write config");
+}
+
+#[test]
+fn attach_opens_a_kitty_tab_with_a_split_per_window_and_focuses_it() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_kitty_config(temp.path());
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("[]", "", 0),
+            output("4\n", "", 0),
+            output("5\n", "", 0),
+            output(
+                r#"[{"tabs": [{"id": 2, "title": "repo/w1", "windows": [{"id": 4}, {"id": 5}]}]}]"#,
+                "",
+                0,
+            ),
+            output("", "", 0),
+        ],
+        Vec::new(),
+    );
+
+    let app = App::new(&runner);
+    let mut request = AttachRequest::new(repo_root.clone(), "w1");
+    request.create_if_missing = true;
+    request.connect = true;
+    let result = app.attach(request).expect("attach result");
+
+    assert!(result.created_session);
+    assert_eq!(result.session_name, "repo/w1");
+    assert_eq!(result.attach_status, Some(0));
+    assert_eq!(
+        result.connect_command,
+        "kitty @ focus-tab --match 'title:^repo/w1$'"
+    );
+
+    let calls = runner.calls();
+    assert!(calls.iter().all(|call| call.program != "tmux"));
+    assert_eq!(calls[1].args, vec!["@", "ls"]);
+    assert_eq!(calls[2].args[..3], ["@", "launch", "--type=tab"]);
+    assert!(calls[2].args.ends_with(&[
+        "--tab-title".to_string(),
+        "repo/w1".to_string(),
+        "nvim".to_string()
+    ]));
+    assert!(calls[3].args.contains(&"window_id:4".to_string()));
+    assert_eq!(calls[5].args, vec!["@", "focus-tab", "--match", "id:2"]);
+}
+
+#[test]
+fn tmux_only_commands_refuse_a_tab_backend() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_kitty_config(temp.path());
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+
+    let runner = QueueRunner::new(Vec::new(), Vec::new());
+    let app = App::new(&runner);
+    let error = app.adopt_scan(&repo_root).expect_err("adopt needs tmux");
+
+    assert_eq!(
+        error.to_string(),
+        "seshmux adopt needs the tmux backend, but tmux.backend is 'kitty'"
+    );
+    assert!(runner.calls().is_empty());
+}
@@ -0,0 +1,163 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use seshmux_app::{App, DeleteRequest};
+use seshmux_core::command_runner::SystemCommandRunner;
+use seshmux_core::registry::{RegistryEntry, find_entry_by_name, insert_unique_entry};
+
+fn run_git(repo: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo)
+        .output()
+        .expect("git command should execute");
+
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+fn add_worktree(repo_root: &Path, worktrees_dir: &Path, name: &str) -> PathBuf {
+    let worktree_path = worktrees_dir.join(name);
+    run_git(
+        repo_root,
+        &[
+            "worktree",
+            "add",
+            "-b",
+            name,
+            worktree_path.to_str().expect("path utf8"),
+            "HEAD",
+        ],
+    );
+    insert_unique_entry(worktrees_dir, {
+        let mut entry = RegistryEntry::new(
+            name,
+            worktree_path.to_string_lossy(),
+            "2026-02-25T10:00:00Z",
+        );
+        entry.branch = Some(name.to_string());
+        entry
+    })
+    .expect("insert registry entry");
+    worktree_path
+}
+
+fn delete_request(repo_root: &Path, name: &str) -> DeleteRequest {
+    let mut request = DeleteRequest::new(repo_root.to_path_buf(), name.to_string());
+    request.delete_branch = true;
+    request
+}
+
+#[test]
+fn trash_mode_keeps_deleted_worktrees_restorable_until_the_retention_passes() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let config_dir = temp.path().join(".config").join("seshmux");
+    fs::create_dir_all(&config_dir).expect("config dir");
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+version = 1
+delete_mode = "trash"
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+args = []
+"#,
+    )
+    .expect("write config");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(&repo_root).expect("repo dir");
+    run_git(&repo_root, &["init"]);
+    fs::write(repo_root.join("README.md"), "hello\n").expect("write readme");
+    run_git(&repo_root, &["add", "."]);
+    run_git(
+        &repo_root,
+        &[
+            "-c",
+            "user.name=seshmux-test",
+            "-c",
+            "user.email=seshmux-test@example.com",
+            "commit",
+            "-m",
+            "initial",
+        ],
+    );
+    let worktrees_dir = repo_root.join("worktrees");
+    fs::create_dir_all(&worktrees_dir).expect("worktrees dir");
+    let w1 = add_worktree(&repo_root, &worktrees_dir, "w1");
+    add_worktree(&repo_root, &worktrees_dir, "w2");
+    fs::write(w1.join("notes.txt"), "uncommitted\n").expect("write notes");
+
+    let runner = SystemCommandRunner::new();
+    let app = App::new(&runner);
+
+    let deleted = app
+        .delete(delete_request(&repo_root, "w1"))
+        .expect("trash w1");
+    let trash_path = deleted.trashed_to.expect("moved to the trash");
+    assert!(!w1.exists());
+    assert!(trash_path.join("notes.txt").is_file());
+    assert!(!deleted.branch_deleted);
+    assert!(
+        find_entry_by_name(&worktrees_dir, "w1")
+            .expect("load registry")
+            .is_none()
+    );
+
+    let restored = app.restore_trashed(&repo_root, "w1").expect("restore w1");
+    assert_eq!(restored.worktree_path, w1);
+    assert_eq!(restored.branch.as_deref(), Some("w1"));
+    assert_eq!(
+        fs::read_to_string(w1.join("notes.txt")).expect("notes"),
+        "uncommitted\n"
+    );
+    assert!(
+        find_entry_by_name(&worktrees_dir, "w1")
+            .expect("load registry")
+            .is_some()
+    );
+    assert!(
+        seshmux_core::trash::load(&worktrees_dir)
+            .expect("trash")
+            .is_empty()
+    );
+
+    let deleted = app
+        .delete(delete_request(&repo_root, "w1"))
+        .expect("trash w1 again");
+    let trash_path = deleted.trashed_to.expect("moved to the trash");
+    let mut trashed = seshmux_core::trash::load(&worktrees_dir).expect("trash");
+    trashed[0].trashed_at = "2020-01-01T00:00:00Z".to_string();
+    seshmux_core::trash::save(&worktrees_dir, &trashed).expect("age the trash");
+
+    let deleted = app
+        .delete(delete_request(&repo_root, "w2"))
+        .expect("trash w2");
+    assert_eq!(deleted.purged, vec!["w1".to_string()]);
+    assert!(deleted.leaked_resources.is_empty());
+    assert!(!trash_path.exists());
+    assert!(
+        run_git(&repo_root, &["branch", "--list", "w1"])
+            .trim()
+            .is_empty()
+    );
+    let remaining = seshmux_core::trash::load(&worktrees_dir).expect("trash");
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].entry.name, "w2");
+
+    let error = app
+        .restore_trashed(&repo_root, "w1")
+        .expect_err("w1 was purged");
+    assert!(error.to_string().contains("trashed worktrees: w2"));
+}
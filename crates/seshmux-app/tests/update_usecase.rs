@@ -0,0 +1,125 @@
+mod support;
+
+use std::fs;
+
+use seshmux_app::{App, UpdateOutcome, UpdateRequest};
+use seshmux_core::config::UpdateStrategy;
+
+use support::{ENV_LOCK, QueueRunner, add_registry_entry, output, write_valid_config};
+
+#[test]
+fn update_fetches_and_rebases_onto_the_default_branch_and_reports_conflicts() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("origin/main\n", "", 0),
+            output("w1\n", "", 0),
+            output("origin\n", "", 0),
+            output(
+                "",
+                "From github.com:me/repo\n   1234..5678  main -> origin/main\n",
+                0,
+            ),
+            output(
+                "",
+                "CONFLICT (content): Merge conflict in src/lib.rs\nerror: could not apply 9abc\n",
+                1,
+            ),
+            output("src/lib.rs\n", "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+
+    let mut lines = Vec::new();
+    let result = app
+        .update_worktree(
+            UpdateRequest {
+                cwd: repo_root.clone(),
+                worktree_name: "w1".to_string(),
+            },
+            &mut |line| lines.push(line),
+        )
+        .expect("update");
+
+    assert_eq!(result.branch, "w1");
+    assert_eq!(result.upstream, "origin/main");
+    assert_eq!(result.strategy, UpdateStrategy::Rebase);
+    assert_eq!(
+        result.outcome,
+        UpdateOutcome::Conflicts {
+            paths: vec!["src/lib.rs".to_string()]
+        }
+    );
+    assert_eq!(lines[0], "$ git fetch origin");
+    assert!(lines.contains(&"$ git rebase origin/main".to_string()));
+    assert!(lines.iter().any(|line| line.starts_with("CONFLICT")));
+
+    let calls = runner.calls();
+    assert!(calls.iter().all(|call| call.program == "git"));
+    assert_eq!(calls[4].args, vec!["fetch", "origin"]);
+    assert_eq!(calls[5].args, vec!["rebase", "origin/main"]);
+}
+
+#[test]
+fn update_merges_a_configured_local_upstream_without_fetching() {
+    let _guard = ENV_LOCK.lock().expect("env lock");
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    write_valid_config(temp.path(), false);
+    let config_path = temp.path().join(".config/seshmux/config.toml");
+    let mut config = fs::read_to_string(&config_path).expect("read config");
+    config.push_str("\n[update]\nupstream = \"develop\"\nstrategy = \"merge\"\n");
+    fs::write(&config_path, config).expect("write config");
+    unsafe {
+        std::env::set_var("HOME", temp.path());
+    }
+    let repo_root = temp.path().join("repo");
+    fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees");
+    add_registry_entry(&repo_root, "w1", "2026-02-25T10:00:00Z");
+
+    let runner = QueueRunner::new(
+        vec![
+            output(&format!("{}\n", repo_root.display()), "", 0),
+            output("w1\n", "", 0),
+            output("origin\n", "", 0),
+            output("Already up to date.\n", "", 0),
+        ],
+        Vec::new(),
+    );
+    let app = App::new(&runner);
+
+    let mut lines = Vec::new();
+    let result = app
+        .update_worktree(
+            UpdateRequest {
+                cwd: repo_root.clone(),
+                worktree_name: "w1".to_string(),
+            },
+            &mut |line| lines.push(line),
+        )
+        .expect("update");
+
+    assert_eq!(result.outcome, UpdateOutcome::Updated);
+    assert_eq!(
+        lines,
+        vec![
+            "$ git merge --no-edit develop".to_string(),
+            "Already up to date.".to_string()
+        ]
+    );
+    assert_eq!(
+        runner.calls().last().expect("merge call").args,
+        vec!["merge", "--no-edit", "develop"]
+    );
+}
@@ -1,4 +1,8 @@
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::diagnostics::DiagnosticsLevel;
 
 #[derive(Debug, Parser)]
 #[command(name = "seshmux")]
@@ -13,6 +17,50 @@ pub struct Cli {
     )]
     pub diagnostics: bool,
 
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_name = "LEVEL",
+        help = "Minimum level written to the diagnostics log (implies --diagnostics) [default: info]"
+    )]
+    pub diagnostics_level: Option<DiagnosticsLevel>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILE",
+        conflicts_with = "record",
+        help = "Drive the TUI from a recorded input script instead of the keyboard"
+    )]
+    pub replay: Option<PathBuf>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "FACTOR",
+        default_value_t = 1.0,
+        requires = "replay",
+        help = "Play the --replay script this many times faster"
+    )]
+    pub replay_speed: f64,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILE",
+        help = "Record the TUI's key presses and pastes into an input script for --replay"
+    )]
+    pub record: Option<PathBuf>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "NAME",
+        help = "Apply the config's [profiles.NAME] section (overrides SESHMUX_PROFILE)"
+    )]
+    pub profile: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<Command>,
 }
@@ -21,4 +69,508 @@ pub struct Cli {
 pub enum Command {
     #[command(about = "Run environment and configuration checks")]
     Doctor,
+    #[command(about = "Create a worktree from a TOML or JSON spec and print the result as JSON")]
+    New(NewArgs),
+    #[command(about = "Print the worktrees registered in this repository")]
+    List(ListArgs),
+    #[command(about = "Attach to a worktree's tmux session, creating it if needed")]
+    Attach(AttachArgs),
+    #[command(
+        about = "Pick a worktree to attach to, or a session to kill, from a tmux menu (bind it to a tmux key)"
+    )]
+    Menu,
+    #[command(about = "Delete a worktree by name")]
+    Delete(DeleteArgs),
+    #[command(about = "Bring back a worktree deleted with delete_mode = \"trash\"")]
+    Restore(RestoreArgs),
+    #[command(about = "Open a worktree in your editor without tmux")]
+    Open(OpenArgs),
+    #[command(about = "Run a command inside a worktree and exit with its status")]
+    Run(RunArgs),
+    #[command(about = "Check out commits into temporary review worktrees")]
+    Review(ReviewArgs),
+    #[command(about = "Register worktrees whose tmux sessions seshmux does not know about yet")]
+    Adopt(AdoptArgs),
+    #[command(about = "Register git worktrees created by other tools or scripts")]
+    Import(ImportArgs),
+    #[command(about = "Manage the tmux sessions that belong to this repository")]
+    Sessions(SessionsArgs),
+    #[command(about = "Manage the always-skip buckets used when indexing extras to copy")]
+    SkipRules(SkipRulesArgs),
+    #[command(about = "Save the window and pane layout of running sessions into the config")]
+    Layout(LayoutArgs),
+    #[command(about = "Manage seshmux configuration files")]
+    Config(ConfigArgs),
+    #[command(
+        about = "Clone a repository into a worktree-ready layout and create a first worktree"
+    )]
+    Init(InitArgs),
+    #[command(
+        about = "Write ~/.config/seshmux/config.toml, asking for the basics with --interactive"
+    )]
+    InitConfig(InitConfigArgs),
+    #[command(about = "Explore seshmux in a throwaway repository that is deleted afterwards")]
+    Demo(DemoArgs),
+    #[command(about = "Browse worktrees and sessions from every configured repository at once")]
+    Dash,
+    #[command(about = "Archive worktrees that have been idle longer than the configured threshold")]
+    Archive(ArchiveArgs),
+    #[command(
+        about = "Recreate the sessions of registered worktrees without attaching, e.g. after a reboot"
+    )]
+    Bootstrap(BootstrapArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct BootstrapArgs {
+    #[arg(long, help = "Only start worktrees flagged with --flag")]
+    pub flagged: bool,
+
+    #[arg(
+        long,
+        help = "Start worktrees in every repository configured under [dash], from any directory"
+    )]
+    pub dash: bool,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with_all = ["flagged", "dash", "unflag"],
+        help = "Flag a worktree so bootstrap --flagged starts it"
+    )]
+    pub flag: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with_all = ["flagged", "dash"],
+        help = "Clear a worktree's bootstrap flag"
+    )]
+    pub unflag: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct ArchiveArgs {
+    #[arg(
+        long,
+        value_name = "DAYS",
+        help = "Idle threshold in days instead of archive.idle_days from the config"
+    )]
+    pub days: Option<u64>,
+
+    #[arg(
+        long,
+        help = "Pack each worktree into a tarball and remove its checkout"
+    )]
+    pub tar: bool,
+
+    #[arg(
+        long,
+        help = "List the worktrees that would be archived without changing anything"
+    )]
+    pub dry_run: bool,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with_all = ["days", "tar", "dry_run"],
+        help = "Restore an archived worktree"
+    )]
+    pub restore: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct AdoptArgs {
+    #[arg(help = "Worktree names to adopt (lists candidates when omitted)")]
+    pub names: Vec<String>,
+
+    #[arg(long, conflicts_with = "names", help = "Adopt every candidate")]
+    pub all: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ImportArgs {
+    #[arg(help = "Worktree names to import (lists candidates when omitted)")]
+    pub names: Vec<String>,
+
+    #[arg(long, conflicts_with = "names", help = "Import every candidate")]
+    pub all: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct SessionsArgs {
+    #[command(subcommand)]
+    pub command: SessionsCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SessionsCommand {
+    #[command(
+        about = "Kill every tmux session seshmux manages for this repository, keeping the worktrees"
+    )]
+    KillAll(KillAllArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct KillAllArgs {
+    #[arg(
+        long,
+        conflicts_with = "all",
+        help = "Also kill the sessions of the repositories configured under [dash]"
+    )]
+    pub known: bool,
+
+    #[arg(
+        long,
+        help = "Like --known, and also kill sessions named like seshmux's (<repo>/<worktree>) from repositories it does not know"
+    )]
+    pub all: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct LayoutArgs {
+    #[command(subcommand)]
+    pub command: LayoutCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum LayoutCommand {
+    #[command(about = "Capture a worktree's running tmux session as a named layout in config.toml")]
+    Capture(LayoutCaptureArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct LayoutCaptureArgs {
+    #[arg(help = "Worktree name from worktree.toml")]
+    pub name: String,
+
+    #[arg(
+        long = "as",
+        value_name = "LAYOUT",
+        help = "Name to save the layout under (defaults to the worktree name)"
+    )]
+    pub layout_name: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct SkipRulesArgs {
+    #[command(subcommand)]
+    pub command: SkipRulesCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SkipRulesCommand {
+    #[command(about = "Show the configured and registry-persisted skip buckets")]
+    List,
+    #[command(about = "Always skip a repository-relative directory when indexing extras")]
+    Add(SkipRuleArgs),
+    #[command(about = "Stop skipping a bucket saved in the worktree registry")]
+    Remove(SkipRuleArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct SkipRuleArgs {
+    #[arg(help = "Repository-relative path, e.g. node_modules or apps/web/.next")]
+    pub bucket: String,
+}
+
+#[derive(Debug, Args)]
+pub struct InitArgs {
+    #[arg(help = "Git URL or path to clone")]
+    pub url: String,
+
+    #[arg(help = "Checkout directory (defaults to the repository name)")]
+    pub directory: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct InitConfigArgs {
+    #[arg(
+        long,
+        help = "Ask for the editor, windows, layout, and worktrees directory in a setup wizard"
+    )]
+    pub interactive: bool,
+
+    #[arg(long, help = "Overwrite an existing config file")]
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct DemoArgs {
+    #[arg(
+        long,
+        help = "Keep the demo repository, worktrees, and sessions on exit"
+    )]
+    pub keep: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    #[command(about = "Write a starter config with defaults detected from the project")]
+    Eject(ConfigEjectArgs),
+    #[command(about = "Check a config file and report every problem with its line")]
+    Validate(ConfigFileArgs),
+    #[command(about = "Open a config file in $EDITOR and validate it afterwards")]
+    Edit(ConfigFileArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigFileArgs {
+    #[arg(
+        long,
+        help = "Use .seshmux.toml in the current repository instead of the global config"
+    )]
+    pub repo: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigEjectArgs {
+    #[arg(
+        long,
+        required = true,
+        help = "Write .seshmux.toml into the current repository"
+    )]
+    pub repo: bool,
+
+    #[arg(long, help = "Overwrite an existing config file")]
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct NewArgs {
+    #[arg(
+        long,
+        value_name = "PATH",
+        required_unless_present_any = ["quick", "batch"],
+        help = "Worktree spec file, or - to read it from stdin"
+    )]
+    pub spec: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["spec", "quick", "branch"],
+        help = "File listing several worktree specs, or - to read it from stdin; creates them in order"
+    )]
+    pub batch: Option<PathBuf>,
+
+    #[arg(
+        long,
+        requires = "batch",
+        conflicts_with_all = ["spec", "quick"],
+        help = "Keep creating the rest of a batch after a worktree fails"
+    )]
+    pub keep_going: bool,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with = "spec",
+        help = "Create the worktree straight away using the [quick_new] config answers"
+    )]
+    pub quick: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Branch to create for the worktree instead of the configured default"
+    )]
+    pub branch: Option<String>,
+
+    #[arg(
+        long,
+        help = "Check out the worktree's branch even if another worktree already has it"
+    )]
+    pub ignore_other_worktrees: bool,
+
+    #[arg(long, help = "Run git lfs pull in the new worktree")]
+    pub lfs: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "lfs",
+        help = "Skip git lfs pull in the new worktree"
+    )]
+    pub no_lfs: bool,
+
+    #[arg(long, help = "Initialize submodules recursively in the new worktree")]
+    pub submodules: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "submodules",
+        help = "Skip submodule initialization in the new worktree"
+    )]
+    pub no_submodules: bool,
+
+    #[arg(
+        long,
+        help = "Run direnv allow in the new worktree when it has an .envrc"
+    )]
+    pub direnv: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "direnv",
+        help = "Skip direnv allow in the new worktree"
+    )]
+    pub no_direnv: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "batch",
+        help = "Apply the uncommitted changes of the current checkout to the new worktree"
+    )]
+    pub carry_changes: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    #[arg(long, value_enum, default_value_t = SortColumn::Created, help = "Column to sort by")]
+    pub sort: SortColumn,
+
+    #[arg(long, help = "Reverse the sort direction")]
+    pub reverse: bool,
+
+    #[arg(long, help = "Show worktrees with a running tmux session first")]
+    pub group_running: bool,
+
+    #[arg(long, help = "Only show worktrees with a running tmux session")]
+    pub running: bool,
+
+    #[arg(long, value_enum, default_value_t = ListFormat::Table, help = "Output format")]
+    pub format: ListFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListFormat {
+    Table,
+    Json,
+    Names,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SortColumn {
+    Name,
+    Created,
+    Branch,
+    Session,
+    Recent,
+}
+
+#[derive(Debug, Args)]
+pub struct AttachArgs {
+    #[arg(
+        required_unless_present = "last",
+        conflicts_with = "last",
+        help = "Worktree name from worktree.toml"
+    )]
+    pub name: Option<String>,
+
+    #[arg(
+        long,
+        help = "Attach to the most recently attached worktree instead of naming one"
+    )]
+    pub last: bool,
+
+    #[arg(
+        long,
+        help = "Print the tmux command that attaches to the session instead of running it"
+    )]
+    pub print: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "print",
+        help = "Report the session state and tmux exit status as stable key-value lines"
+    )]
+    pub porcelain: bool,
+
+    #[arg(long, help = "Attach even if another user created the worktree")]
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct DeleteArgs {
+    #[arg(help = "Worktree name from worktree.toml")]
+    pub name: String,
+
+    #[arg(long, help = "Kill the worktree's tmux session if it is running")]
+    pub kill_session: bool,
+
+    #[arg(long, help = "Also delete the worktree's branch")]
+    pub delete_branch: bool,
+
+    #[arg(
+        long,
+        help = "Remove the worktree even if it has local changes or another user created it"
+    )]
+    pub force: bool,
+
+    #[arg(
+        long,
+        help = "Delete the worktree even if the current directory is inside it"
+    )]
+    pub from_inside: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct RestoreArgs {
+    #[arg(help = "Name the worktree had before it was deleted")]
+    pub name: String,
+}
+
+#[derive(Debug, Args)]
+pub struct OpenArgs {
+    #[arg(help = "Worktree name from worktree.toml")]
+    pub name: String,
+}
+
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    #[arg(help = "Worktree name from worktree.toml")]
+    pub name: String,
+
+    #[arg(
+        long,
+        help = "Run in a new window of the worktree's tmux session, which stays open with the output"
+    )]
+    pub window: bool,
+
+    #[arg(
+        last = true,
+        required = true,
+        value_name = "COMMAND",
+        help = "Command and arguments to run, after --"
+    )]
+    pub command: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct ReviewArgs {
+    #[arg(
+        help = "Commit or range to check out (for example main..feature)",
+        required_unless_present = "clean"
+    )]
+    pub range: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with = "clean",
+        help = "Only check out the merge-base and head of the range"
+    )]
+    pub endpoints: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "range",
+        help = "Remove every review worktree in this repository"
+    )]
+    pub clean: bool,
 }
@@ -6,11 +6,34 @@ use std::sync::{Mutex, Once, OnceLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::{Context, Result, anyhow};
+use clap::ValueEnum;
+use serde_json::{Map, Value, json};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum DiagnosticsLevel {
+    Debug,
+    #[default]
+    Info,
+    Warn,
+    Error,
+}
+
+impl DiagnosticsLevel {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+}
 
 #[derive(Default)]
 struct RuntimeDiagnostics {
     path: Option<PathBuf>,
     file: Option<File>,
+    level: DiagnosticsLevel,
 }
 
 static HOOK_ONCE: Once = Once::new();
@@ -25,7 +48,7 @@ pub struct DiagnosticsSession {
 }
 
 impl DiagnosticsSession {
-    pub fn initialize(enabled: bool) -> Result<Self> {
+    pub fn initialize(enabled: bool, level: DiagnosticsLevel) -> Result<Self> {
         install_panic_hook();
         if !enabled {
             let mut state = diagnostics_state()
@@ -43,19 +66,18 @@ impl DiagnosticsSession {
             .open(&path)
             .with_context(|| format!("failed to create diagnostics log at {}", path.display()))?;
 
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis();
-        writeln!(
-            file,
-            "seshmux diagnostics start\nversion={}\nstart_epoch_ms={now}\npid={}",
-            env!("CARGO_PKG_VERSION"),
-            std::process::id()
-        )
-        .with_context(|| format!("failed to write diagnostics header to {}", path.display()))?;
-        writeln!(file, "argv={:?}", std::env::args().collect::<Vec<String>>())
-            .with_context(|| format!("failed to write diagnostics args to {}", path.display()))?;
+        let header = event_line(
+            DiagnosticsLevel::Info,
+            "diagnostics start",
+            &[
+                ("version", json!(env!("CARGO_PKG_VERSION"))),
+                ("pid", json!(std::process::id())),
+                ("min_level", json!(level.as_str())),
+                ("argv", json!(std::env::args().collect::<Vec<String>>())),
+            ],
+        );
+        writeln!(file, "{header}")
+            .with_context(|| format!("failed to write diagnostics header to {}", path.display()))?;
         file.flush()
             .with_context(|| format!("failed to flush diagnostics header to {}", path.display()))?;
 
@@ -64,6 +86,7 @@ impl DiagnosticsSession {
             .unwrap_or_else(std::sync::PoisonError::into_inner);
         state.path = Some(path.clone());
         state.file = Some(file);
+        state.level = level;
 
         Ok(Self { path: Some(path) })
     }
@@ -72,8 +95,8 @@ impl DiagnosticsSession {
         self.path.as_ref()
     }
 
-    pub fn record<S: AsRef<str>>(&self, entry: S) {
-        append_line(entry.as_ref());
+    pub fn record(&self, level: DiagnosticsLevel, event: &str, fields: &[(&str, Value)]) {
+        append_event(level, event, fields);
     }
 }
 
@@ -87,10 +110,15 @@ fn install_panic_hook() {
                 .unwrap_or_else(|| "UNCONFIRMED".to_string());
             let backtrace = Backtrace::force_capture();
 
-            append_line("panic captured");
-            append_line(format!("panic_message={payload}"));
-            append_line(format!("panic_location={location}"));
-            append_line(format!("panic_backtrace={backtrace:?}"));
+            append_event(
+                DiagnosticsLevel::Error,
+                "panic captured",
+                &[
+                    ("message", json!(payload)),
+                    ("location", json!(location)),
+                    ("backtrace", json!(format!("{backtrace:?}"))),
+                ],
+            );
 
             let path = diagnostics_state()
                 .lock()
@@ -121,20 +149,37 @@ fn panic_payload(panic_info: &std::panic::PanicHookInfo<'_>) -> String {
     "unknown panic payload".to_string()
 }
 
-fn append_line<S: AsRef<str>>(line: S) {
+fn append_event(level: DiagnosticsLevel, event: &str, fields: &[(&str, Value)]) {
     let mut state = diagnostics_state()
         .lock()
         .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if level < state.level {
+        return;
+    }
     let Some(file) = state.file.as_mut() else {
         return;
     };
 
+    let _ = writeln!(file, "{}", event_line(level, event, fields));
+    let _ = file.flush();
+}
+
+fn event_line(level: DiagnosticsLevel, event: &str, fields: &[(&str, Value)]) -> Value {
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_default()
         .as_millis();
-    let _ = writeln!(file, "[{now}] {}", line.as_ref());
-    let _ = file.flush();
+    let fields: Map<String, Value> = fields
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.clone()))
+        .collect();
+
+    json!({
+        "ts_ms": now,
+        "level": level.as_str(),
+        "event": event,
+        "fields": fields,
+    })
 }
 
 fn create_diagnostics_log_path() -> Result<PathBuf> {
@@ -161,3 +206,27 @@ fn create_diagnostics_log_path() -> Result<PathBuf> {
         .as_millis();
     Ok(diagnostics_dir.join(format!("{now}.log")))
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{DiagnosticsLevel, event_line};
+
+    #[test]
+    fn events_serialize_as_single_json_lines() {
+        let line = event_line(
+            DiagnosticsLevel::Warn,
+            "command failed",
+            &[("error", json!("boom\nsecond line")), ("status", json!(2))],
+        );
+        let text = line.to_string();
+
+        assert!(!text.contains('\n'));
+        assert_eq!(line["level"], "warn");
+        assert_eq!(line["event"], "command failed");
+        assert_eq!(line["fields"]["error"], "boom\nsecond line");
+        assert_eq!(line["fields"]["status"], 2);
+        assert!(DiagnosticsLevel::Debug < DiagnosticsLevel::Error);
+    }
+}
@@ -1,34 +1,1153 @@
+use std::io::IsTerminal;
 use std::path::Path;
 
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
 use comfy_table::{Cell, ContentArrangement, Table};
-use seshmux_app::App;
+use seshmux_app::{
+    AdoptRequest, AdoptScan, App, ArchiveRequest, AttachError, AttachRequest, AttachResult,
+    BootstrapRequest, BootstrapState, CarriedChanges, ConfigCheckRequest, ConfigCheckResult,
+    ConfigEjectRequest, ConfigInitRequest, DeleteError, DeleteRequest, DemoRequest,
+    ForeignWorktree, ImportRequest, ImportScan, InitRequest, LayoutCaptureRequest, ListResult,
+    NewBatch, NewBatchItem, NewBatchOutcome, NewProgress, NewResult, NewSpec, OpenRequest,
+    ReviewMode, ReviewRequest, ReviewResult, RunRequest, SessionFilter, SessionScope, SkipRules,
+    WorktreeOrder, WorktreeRow, WorktreeSortKey,
+};
 use seshmux_core::doctor::{CheckState, DoctorReport};
+use seshmux_tui::{FrameTiming, InputScript};
 
-use crate::cli::{Cli, Command};
+use crate::cli::{
+    AdoptArgs, ArchiveArgs, AttachArgs, BootstrapArgs, Cli, Command, ConfigArgs, ConfigCommand,
+    DeleteArgs, DemoArgs, ImportArgs, InitArgs, InitConfigArgs, LayoutArgs, LayoutCommand,
+    ListArgs, ListFormat, NewArgs, OpenArgs, RestoreArgs, ReviewArgs, RunArgs, SessionsArgs,
+    SessionsCommand, SkipRulesArgs, SkipRulesCommand, SortColumn,
+};
 
-pub fn run_with_deps(cli: Cli, app: &App<'_>, cwd: &Path) -> Result<()> {
-    match cli.command {
-        Some(Command::Doctor) => run_doctor_command(app),
-        None => run_root_command(app, cwd),
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandExit(pub i32);
+
+impl std::fmt::Display for CommandExit {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "command exited with status {}", self.0)
+    }
+}
+
+impl std::error::Error for CommandExit {}
+
+#[derive(Debug)]
+struct Hinted {
+    error: seshmux_app::Error,
+    hint: &'static str,
+}
+
+impl Hinted {
+    fn new(error: seshmux_app::Error, hint: &'static str) -> Self {
+        Self { error, hint }
+    }
+}
+
+impl std::fmt::Display for Hinted {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "{}\n{}", self.error, self.hint)
+    }
+}
+
+impl std::error::Error for Hinted {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+pub fn run_with_deps(
+    cli: Cli,
+    app: &App<'_>,
+    input: &InputScript,
+    timing: &FrameTiming,
+    cwd: &Path,
+) -> Result<()> {
+    let result = match cli.command {
+        Some(Command::Doctor) => run_doctor_command(app, cwd),
+        Some(Command::New(args)) => run_new_command(app, cwd, args),
+        Some(Command::List(args)) => run_list_command(app, cwd, args),
+        Some(Command::Attach(args)) => run_attach_command(app, cwd, args),
+        Some(Command::Menu) => run_menu_command(app, cwd),
+        Some(Command::Delete(args)) => run_delete_command(app, cwd, args),
+        Some(Command::Restore(args)) => run_restore_command(app, cwd, args),
+        Some(Command::Open(args)) => run_open_command(app, cwd, args),
+        Some(Command::Run(args)) => run_run_command(app, cwd, args),
+        Some(Command::Review(args)) => run_review_command(app, cwd, args),
+        Some(Command::Adopt(args)) => run_adopt_command(app, cwd, args),
+        Some(Command::Import(args)) => run_import_command(app, cwd, args),
+        Some(Command::Sessions(args)) => run_sessions_command(app, cwd, args),
+        Some(Command::SkipRules(args)) => run_skip_rules_command(app, cwd, args),
+        Some(Command::Layout(args)) => run_layout_command(app, cwd, args),
+        Some(Command::Config(args)) => run_config_command(app, cwd, args),
+        Some(Command::Init(args)) => run_init_command(app, input, timing, cwd, args),
+        Some(Command::InitConfig(args)) => run_init_config_command(app, input, args),
+        Some(Command::Demo(args)) => run_demo_command(app, input, timing, args),
+        Some(Command::Dash) => run_dash_command(app, input, timing, cwd),
+        Some(Command::Archive(args)) => run_archive_command(app, cwd, args),
+        Some(Command::Bootstrap(args)) => run_bootstrap_command(app, cwd, args),
+        None => run_root_command(app, input, timing, cwd),
+    };
+
+    print_notices(app);
+    result
+}
+
+fn print_notices(app: &App<'_>) {
+    for notice in app.take_notices() {
+        eprintln!("{notice}");
     }
 }
 
-fn run_root_command(app: &App<'_>, cwd: &Path) -> Result<()> {
+fn run_root_command(
+    app: &App<'_>,
+    input: &InputScript,
+    timing: &FrameTiming,
+    cwd: &Path,
+) -> Result<()> {
+    offer_first_run_setup(app, input)?;
     app.ensure_config_ready()?;
+    print_notices(app);
     app.ensure_runtime_repo_ready(cwd)?;
 
-    let _ = seshmux_tui::run_root(app, cwd)?;
+    let _ = seshmux_tui::run_root(app, input, timing, cwd)?;
+
+    Ok(())
+}
+
+fn run_dash_command(
+    app: &App<'_>,
+    input: &InputScript,
+    timing: &FrameTiming,
+    cwd: &Path,
+) -> Result<()> {
+    offer_first_run_setup(app, input)?;
+    app.ensure_config_ready()?;
+    print_notices(app);
+
+    let _ = seshmux_tui::run_dash(app, input, timing, cwd)?;
+
+    Ok(())
+}
+
+fn offer_first_run_setup(app: &App<'_>, input: &InputScript) -> Result<()> {
+    if !app.config_missing()? || !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal()
+    {
+        return Ok(());
+    }
+
+    if let Some(path) = seshmux_tui::run_setup(app, input, false)? {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn run_init_config_command(app: &App<'_>, input: &InputScript, args: InitConfigArgs) -> Result<()> {
+    let path = if args.interactive {
+        match seshmux_tui::run_setup(app, input, args.force)? {
+            Some(path) => path,
+            None => bail!("setup canceled; no config was written"),
+        }
+    } else {
+        app.config_init(ConfigInitRequest {
+            scaffold: app.config_setup_defaults(),
+            force: args.force,
+        })?
+    };
+
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+fn run_init_command(
+    app: &App<'_>,
+    input: &InputScript,
+    timing: &FrameTiming,
+    cwd: &Path,
+    args: InitArgs,
+) -> Result<()> {
+    app.ensure_config_ready()?;
+    print_notices(app);
+
+    let result = app.init(InitRequest {
+        cwd: cwd.to_path_buf(),
+        url: args.url,
+        directory: args.directory,
+    })?;
+
+    println!(
+        "Cloned into {} (git directory {})",
+        result.repo_root.display(),
+        result.git_dir.display()
+    );
+
+    let _ = seshmux_tui::run_new(app, input, timing, &result.repo_root)?;
+
+    Ok(())
+}
+
+fn run_demo_command(
+    app: &App<'_>,
+    input: &InputScript,
+    timing: &FrameTiming,
+    args: DemoArgs,
+) -> Result<()> {
+    app.ensure_config_ready()?;
+    print_notices(app);
+
+    let repo_root = std::env::temp_dir().join(format!("seshmux-demo-{}", std::process::id()));
+    let demo = app.create_demo(DemoRequest { repo_root })?;
+
+    println!(
+        "Demo repository at {} with branches {}",
+        demo.repo_root.display(),
+        demo.branches.join(", ")
+    );
+
+    let result = seshmux_tui::run_root(app, input, timing, &demo.repo_root);
 
+    if args.keep {
+        println!("Kept demo repository at {}", demo.repo_root.display());
+    } else {
+        app.remove_demo(&demo)?;
+        println!("Removed demo repository {}", demo.repo_root.display());
+    }
+
+    result?;
     Ok(())
 }
 
-fn run_doctor_command(app: &App<'_>) -> Result<()> {
-    let report = app.doctor()?;
+fn run_doctor_command(app: &App<'_>, cwd: &Path) -> Result<()> {
+    let report = app.doctor(cwd)?;
     print_doctor_report(&report);
     Ok(())
 }
 
+fn run_new_command(app: &App<'_>, cwd: &Path, args: NewArgs) -> Result<()> {
+    app.ensure_config_ready()?;
+    print_notices(app);
+    app.ensure_runtime_repo_ready(cwd)?;
+
+    let lfs = flag_override(args.lfs, args.no_lfs);
+    let submodules = flag_override(args.submodules, args.no_submodules);
+    let direnv = flag_override(args.direnv, args.no_direnv);
+    // Phase lines go to stderr so stdout stays a single JSON document.
+    let mut report = |progress: NewProgress| match &progress.output {
+        Some(line) => eprintln!("  {line}"),
+        None => eprintln!("{}: {}", progress.phase.label(), progress.detail),
+    };
+
+    if let Some(batch_path) = args.batch {
+        let mut batch = NewBatch::parse(&read_spec_input(&batch_path, "batch")?)?;
+        for spec in &mut batch.worktrees {
+            spec.ignore_other_worktrees |= args.ignore_other_worktrees;
+            spec.lfs = lfs.or(spec.lfs);
+            spec.submodules = submodules.or(spec.submodules);
+            spec.direnv = direnv.or(spec.direnv);
+        }
+        let items = app.new_batch(
+            cwd,
+            batch,
+            args.keep_going,
+            &mut |name, progress| match &progress.output {
+                Some(line) => eprintln!("{name}:   {line}"),
+                None => eprintln!("{name}: {}: {}", progress.phase.label(), progress.detail),
+            },
+        );
+        println!("{}", new_batch_json(&items));
+
+        let failed = items
+            .iter()
+            .filter(|item| matches!(item.outcome, NewBatchOutcome::Failed(_)))
+            .count();
+        if failed > 0 {
+            bail!("{failed} of {} worktrees in the batch failed", items.len());
+        }
+        return Ok(());
+    }
+
+    let result = match (args.spec, args.quick) {
+        (Some(spec_path), _) => {
+            let mut spec = NewSpec::parse(&read_spec_input(&spec_path, "spec")?)?;
+            if let Some(branch) = args.branch {
+                spec.branch_name = Some(branch);
+            }
+            spec.ignore_other_worktrees |= args.ignore_other_worktrees;
+            spec.lfs = lfs.or(spec.lfs);
+            spec.submodules = submodules.or(spec.submodules);
+            spec.direnv = direnv.or(spec.direnv);
+            spec.carry_changes |= args.carry_changes;
+            app.new_from_spec(cwd, spec, &mut report)?
+        }
+        (None, Some(name)) => {
+            let mut request = app.new_quick_request(cwd, &name)?;
+            if let Some(branch) = args.branch {
+                request.branch_name = Some(branch);
+            }
+            request.ignore_other_worktrees = args.ignore_other_worktrees;
+            request.lfs_pull = lfs;
+            request.init_submodules = submodules;
+            request.direnv_allow = direnv;
+            request.carry_changes = args.carry_changes;
+            app.new_execute(request, &mut report)?
+        }
+        (None, None) => bail!("either --spec or --quick is required"),
+    };
+
+    println!("{}", new_result_json(&result));
+    Ok(())
+}
+
+fn read_spec_input(path: &Path, what: &str) -> Result<String> {
+    if path.as_os_str() == "-" {
+        std::io::read_to_string(std::io::stdin())
+            .with_context(|| format!("failed to read {what} from stdin"))
+    } else {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {what} {}", path.display()))
+    }
+}
+
+fn flag_override(enable: bool, disable: bool) -> Option<bool> {
+    match (enable, disable) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
+fn new_result_json(result: &NewResult) -> serde_json::Value {
+    let environment: serde_json::Map<String, serde_json::Value> = result
+        .environment
+        .iter()
+        .map(|(key, value)| (key.clone(), serde_json::Value::from(value.as_str())))
+        .collect();
+    let shared_caches: Vec<String> = result
+        .shared_caches
+        .iter()
+        .map(|cache| cache.relative_path.display().to_string())
+        .collect();
+    let carried_changes = result
+        .carried_changes
+        .as_ref()
+        .map(|carried| match carried {
+            CarriedChanges::Applied => serde_json::json!({ "conflicts": [] }),
+            CarriedChanges::Conflicts { paths } => serde_json::json!({ "conflicts": paths }),
+        });
+
+    serde_json::json!({
+        "name": result.worktree_name,
+        "display_name": result.display_name,
+        "branch": result.branch_name,
+        "path": result.worktree_path.display().to_string(),
+        "repo_root": result.repo_root.display().to_string(),
+        "worktrees_dir": result.worktrees_dir.display().to_string(),
+        "session": result.session_name,
+        "attach_command": result.attach_command,
+        "connected": result.connected_now,
+        "extras": {
+            "copied": result.extras_copied.copied,
+            "skipped": result.extras_copied.skipped,
+            "bytes": result.extras_copied.bytes,
+        },
+        "gitignore_added": result.gitignore_added,
+        "shared_caches": shared_caches,
+        "environment": environment,
+        "carried_changes": carried_changes,
+        "provision": {
+            "ran": result.provision.ran,
+            "failure": result.provision.failure,
+        },
+    })
+}
+
+fn new_batch_json(items: &[NewBatchItem]) -> serde_json::Value {
+    let worktrees: Vec<serde_json::Value> = items
+        .iter()
+        .map(|item| match &item.outcome {
+            NewBatchOutcome::Created(result) => {
+                let mut value = new_result_json(result);
+                value["status"] = "created".into();
+                value
+            }
+            NewBatchOutcome::Failed(error) => serde_json::json!({
+                "name": item.name,
+                "status": "failed",
+                "error": error,
+            }),
+            NewBatchOutcome::Skipped => serde_json::json!({
+                "name": item.name,
+                "status": "skipped",
+            }),
+        })
+        .collect();
+    serde_json::json!({ "worktrees": worktrees })
+}
+
+fn run_list_command(app: &App<'_>, cwd: &Path, args: ListArgs) -> Result<()> {
+    let mut result = app.list(cwd)?;
+    let order = WorktreeOrder {
+        key: match args.sort {
+            SortColumn::Name => WorktreeSortKey::Name,
+            SortColumn::Created => WorktreeSortKey::Created,
+            SortColumn::Branch => WorktreeSortKey::Branch,
+            SortColumn::Session => WorktreeSortKey::Session,
+            SortColumn::Recent => WorktreeSortKey::Recent,
+        },
+        reverse: args.reverse,
+        group_running: args.group_running,
+    };
+    order.sort(&mut result.rows);
+    if args.running {
+        result
+            .rows
+            .retain(|row| SessionFilter::Running.matches(row));
+    }
+
+    match args.format {
+        ListFormat::Table => print_list_result(&result),
+        ListFormat::Json => println!("{}", list_result_json(&result)),
+        ListFormat::Names => {
+            for row in &result.rows {
+                println!("{}", row.name);
+            }
+        }
+    }
+    for (branch, names) in result.shared_branches() {
+        eprintln!(
+            "warning: worktrees {} are checked out on the same branch '{branch}'",
+            names.join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn run_attach_command(app: &App<'_>, cwd: &Path, args: AttachArgs) -> Result<()> {
+    app.ensure_runtime_repo_ready(cwd)?;
+
+    let worktree_name = match args.name {
+        Some(name) => name,
+        None => app.last_attached_worktree(cwd)?,
+    };
+    let mut request = AttachRequest::new(cwd.to_path_buf(), worktree_name);
+    request.create_if_missing = true;
+    request.connect = !args.print;
+    request.allow_foreign = args.force;
+    let result = app.attach(request);
+
+    if args.porcelain {
+        return report_attach_porcelain(result);
+    }
+    let result = match result {
+        Ok(result) => result,
+        Err(error) => {
+            if error.downcast_ref::<ForeignWorktree>().is_some() {
+                return Err(Hinted::new(error, "Retry with --force to attach anyway.").into());
+            }
+            return Err(error.into());
+        }
+    };
+
+    if args.print {
+        // Keep stdout to the command alone so shell functions can eval it.
+        if result.created_session {
+            eprintln!("Created tmux session {}", result.session_name);
+        }
+        println!("{}", result.connect_command);
+        return Ok(());
+    }
+
+    if result.created_session {
+        println!("Created tmux session {}", result.session_name);
+    }
+
+    Ok(())
+}
+
+fn run_menu_command(app: &App<'_>, cwd: &Path) -> Result<()> {
+    // Menu entries run this same binary, so it works without seshmux on tmux's PATH.
+    let program = std::env::current_exe().context("failed to locate the seshmux executable")?;
+    let menu = app.tmux_menu(cwd, &program)?;
+    Ok(app.show_tmux_menu(&menu)?)
+}
+
+fn report_attach_porcelain(result: Result<AttachResult, seshmux_app::Error>) -> Result<()> {
+    match result {
+        Ok(result) => {
+            print_attach_porcelain(
+                &result.worktree_name,
+                &result.worktree_path,
+                &result.session_name,
+                result.created_session,
+                result.attach_status.unwrap_or_default(),
+            );
+            Ok(())
+        }
+        Err(error) => {
+            if let Some(AttachError::ConnectFailed {
+                worktree_name,
+                worktree_path,
+                session_name,
+                created_session,
+                status,
+            }) = error.downcast_ref::<AttachError>()
+            {
+                print_attach_porcelain(
+                    worktree_name,
+                    worktree_path,
+                    session_name,
+                    *created_session,
+                    *status,
+                );
+            }
+            Err(error.into())
+        }
+    }
+}
+
+fn print_attach_porcelain(
+    worktree_name: &str,
+    worktree_path: &Path,
+    session_name: &str,
+    created_session: bool,
+    status: i32,
+) {
+    let session_state = if created_session {
+        "created"
+    } else {
+        "existed"
+    };
+
+    println!("worktree {worktree_name}");
+    println!("path {}", worktree_path.display());
+    println!("session {session_name}");
+    println!("session-state {session_state}");
+    println!("attach-status {status}");
+}
+
+fn run_delete_command(app: &App<'_>, cwd: &Path, args: DeleteArgs) -> Result<()> {
+    app.ensure_runtime_repo_ready(cwd)?;
+
+    let mut request = DeleteRequest::new(cwd.to_path_buf(), args.name);
+    request.kill_tmux_session = args.kill_session;
+    request.delete_branch = args.delete_branch;
+    request.force_worktree = args.force;
+    request.allow_cwd_inside = args.from_inside;
+    request.allow_foreign = args.force;
+    let result = app.delete(request);
+
+    let result = match result {
+        Ok(result) => result,
+        Err(error) => {
+            if let Some(DeleteError::CwdInsideWorktree { .. }) = error.downcast_ref::<DeleteError>()
+            {
+                return Err(Hinted::new(
+                    error,
+                    "cd out of it first, or retry with --from-inside to delete it anyway.",
+                )
+                .into());
+            }
+            if error.downcast_ref::<ForeignWorktree>().is_some() {
+                return Err(Hinted::new(error, "Retry with --force to delete it anyway.").into());
+            }
+            if !args.force
+                && let Some(DeleteError::WorktreeDeleteFailed { .. }) =
+                    error.downcast_ref::<DeleteError>()
+            {
+                return Err(Hinted::new(
+                    error,
+                    "Retry with --force to remove the worktree anyway.",
+                )
+                .into());
+            }
+            return Err(error.into());
+        }
+    };
+
+    match &result.trashed_to {
+        Some(trash_path) => {
+            println!(
+                "Moved worktree {} to the trash ({})",
+                result.worktree_name,
+                trash_path.display()
+            );
+            println!("Restore it with: seshmux restore {}", result.worktree_name);
+        }
+        None => println!(
+            "Deleted worktree {} ({})",
+            result.worktree_name,
+            result.worktree_path.display()
+        ),
+    }
+    if !result.purged.is_empty() {
+        println!("Purged from the trash: {}", result.purged.join(", "));
+    }
+    if let Some(owner) = &result.foreign_owner {
+        eprintln!(
+            "Warning: worktree {} was created by {owner}",
+            result.worktree_name
+        );
+    }
+    if result.branch_deleted {
+        println!("Deleted branch {}", result.branch_name);
+    }
+    if let Some(error) = &result.branch_delete_error {
+        eprintln!("Branch {} was kept: {error}", result.branch_name);
+    }
+    for leaked in &result.leaked_resources {
+        eprintln!("Possible leaked resource: {leaked}");
+    }
+
+    Ok(())
+}
+
+fn run_restore_command(app: &App<'_>, cwd: &Path, args: RestoreArgs) -> Result<()> {
+    app.ensure_runtime_repo_ready(cwd)?;
+
+    let result = app.restore_trashed(cwd, &args.name)?;
+    println!(
+        "Restored worktree {} ({})",
+        result.worktree_name,
+        result.worktree_path.display()
+    );
+    if let Some(branch) = &result.branch {
+        println!("Branch: {branch}");
+    }
+    Ok(())
+}
+
+fn run_open_command(app: &App<'_>, cwd: &Path, args: OpenArgs) -> Result<()> {
+    app.open(OpenRequest {
+        cwd: cwd.to_path_buf(),
+        worktree_name: args.name,
+        location: None,
+    })?;
+    Ok(())
+}
+
+fn run_run_command(app: &App<'_>, cwd: &Path, args: RunArgs) -> Result<()> {
+    let result = app.run_in_worktree(RunRequest {
+        cwd: cwd.to_path_buf(),
+        worktree_name: args.name,
+        command: args.command,
+        in_window: args.window,
+    })?;
+    if let Some(window) = &result.window {
+        eprintln!("Output is in tmux window {window}");
+    }
+    if result.status != 0 {
+        return Err(CommandExit(result.status).into());
+    }
+    Ok(())
+}
+
+fn run_review_command(app: &App<'_>, cwd: &Path, args: ReviewArgs) -> Result<()> {
+    if args.clean {
+        let result = app.review_clean(cwd)?;
+        for path in &result.removed {
+            println!("removed {}", path.display());
+        }
+        println!("{} review worktree(s) removed", result.removed.len());
+        return Ok(());
+    }
+
+    app.ensure_runtime_repo_ready(cwd)?;
+
+    let range = args.range.unwrap_or_default();
+    let result = app.review(ReviewRequest {
+        cwd: cwd.to_path_buf(),
+        range,
+        mode: if args.endpoints {
+            ReviewMode::Endpoints
+        } else {
+            ReviewMode::EachCommit
+        },
+    })?;
+    print_review_result(&result);
+    Ok(())
+}
+
+fn run_adopt_command(app: &App<'_>, cwd: &Path, args: AdoptArgs) -> Result<()> {
+    let worktree_names = if args.all {
+        app.adopt_scan(cwd)?
+            .candidates
+            .into_iter()
+            .map(|candidate| candidate.worktree_name)
+            .collect()
+    } else {
+        args.names
+    };
+
+    if worktree_names.is_empty() {
+        let scan = app.adopt_scan(cwd)?;
+        print_adopt_scan(&scan);
+        return Ok(());
+    }
+
+    let result = app.adopt(AdoptRequest {
+        cwd: cwd.to_path_buf(),
+        worktree_names,
+    })?;
+    for candidate in &result.adopted {
+        println!(
+            "Adopted worktree {} ({})",
+            candidate.worktree_name,
+            candidate.worktree_path.display()
+        );
+        if let Some(new_name) = &candidate.rename_to {
+            println!(
+                "Renamed tmux session {} to {new_name}",
+                candidate.session_name
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn run_import_command(app: &App<'_>, cwd: &Path, args: ImportArgs) -> Result<()> {
+    let worktree_names = if args.all {
+        app.import_scan(cwd)?
+            .candidates
+            .into_iter()
+            .map(|candidate| candidate.worktree_name)
+            .collect()
+    } else {
+        args.names
+    };
+
+    if worktree_names.is_empty() {
+        let scan = app.import_scan(cwd)?;
+        print_import_scan(&scan);
+        return Ok(());
+    }
+
+    let result = app.import_worktrees(ImportRequest {
+        cwd: cwd.to_path_buf(),
+        worktree_names,
+    })?;
+    for candidate in &result.imported {
+        println!(
+            "Imported worktree {} ({})",
+            candidate.worktree_name,
+            candidate.worktree_path.display()
+        );
+        if let (Some(session_name), Some(new_name)) =
+            (&candidate.session_name, &candidate.rename_to)
+        {
+            println!("Renamed tmux session {session_name} to {new_name}");
+        }
+    }
+
+    Ok(())
+}
+
+fn run_sessions_command(app: &App<'_>, cwd: &Path, args: SessionsArgs) -> Result<()> {
+    match args.command {
+        SessionsCommand::KillAll(args) => {
+            let scope = if args.all {
+                SessionScope::All
+            } else if args.known {
+                SessionScope::Known
+            } else {
+                SessionScope::Repo
+            };
+            let result = app.kill_all_sessions(cwd, scope)?;
+            if result.killed.is_empty() {
+                println!("No seshmux sessions are running.");
+            }
+            for session_name in &result.killed {
+                println!("Killed tmux session {session_name}");
+            }
+            Ok(())
+        }
+    }
+}
+
+fn run_skip_rules_command(app: &App<'_>, cwd: &Path, args: SkipRulesArgs) -> Result<()> {
+    let rules = match args.command {
+        SkipRulesCommand::List => app.skip_rules(cwd)?,
+        SkipRulesCommand::Add(args) => {
+            let rules = app.add_skip_rule(cwd, &args.bucket)?;
+            println!("Always skipping {}", args.bucket.trim());
+            rules
+        }
+        SkipRulesCommand::Remove(args) => {
+            let rules = app.remove_skip_rule(cwd, &args.bucket)?;
+            println!("No longer skipping {}", args.bucket.trim());
+            rules
+        }
+    };
+    print_skip_rules(&rules);
+    Ok(())
+}
+
+fn print_skip_rules(rules: &SkipRules) {
+    if rules.rules.is_empty() {
+        println!("No always-skip buckets for {}.", rules.repo_root.display());
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Bucket", "Source"]);
+    for rule in &rules.rules {
+        table.add_row(vec![
+            Cell::new(&rule.bucket),
+            Cell::new(rule.source.label()),
+        ]);
+    }
+    println!("{table}");
+    println!("Registry: {}", rules.registry_path.display());
+}
+
+fn run_layout_command(app: &App<'_>, cwd: &Path, args: LayoutArgs) -> Result<()> {
+    app.ensure_config_ready()?;
+    print_notices(app);
+    app.ensure_runtime_repo_ready(cwd)?;
+
+    match args.command {
+        LayoutCommand::Capture(args) => {
+            let result = app.capture_layout(LayoutCaptureRequest {
+                cwd: cwd.to_path_buf(),
+                worktree_name: args.name,
+                layout_name: args.layout_name,
+            })?;
+            println!(
+                "Saved {} window(s) and {} pane(s) from {} as layout '{}' in {}",
+                result.windows,
+                result.panes,
+                result.session_name,
+                result.layout_name,
+                result.config_path.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn run_archive_command(app: &App<'_>, cwd: &Path, args: ArchiveArgs) -> Result<()> {
+    app.ensure_config_ready()?;
+    print_notices(app);
+    app.ensure_runtime_repo_ready(cwd)?;
+
+    if let Some(name) = args.restore {
+        let result = app.restore_archived(cwd, &name)?;
+        if let Some(branch) = &result.detached_from {
+            println!(
+                "Restored worktree {} into {} on a detached HEAD; branch {branch} has moved since it was archived",
+                result.worktree_name,
+                result.worktree_path.display()
+            );
+        } else if result.from_tarball {
+            println!(
+                "Restored worktree {} into {}",
+                result.worktree_name,
+                result.worktree_path.display()
+            );
+        } else {
+            println!("Restored worktree {}", result.worktree_name);
+        }
+        return Ok(());
+    }
+
+    let result = app.archive_idle(ArchiveRequest {
+        cwd: cwd.to_path_buf(),
+        idle_days: args.days,
+        tar: args.tar.then_some(true),
+        dry_run: args.dry_run,
+    })?;
+    if result.archived.is_empty() {
+        println!(
+            "No worktrees in {} have been idle for {} days.",
+            result.repo_root.display(),
+            result.idle_days
+        );
+    }
+    let verb = if result.dry_run {
+        "Would archive"
+    } else {
+        "Archived"
+    };
+    for worktree in &result.archived {
+        println!(
+            "{verb} worktree {} (idle {} days)",
+            worktree.worktree_name, worktree.idle_days
+        );
+        if worktree.session_killed {
+            println!("  killed tmux session {}", worktree.session_name);
+        }
+        if let Some(tarball) = &worktree.tarball {
+            println!("  packed into {}", tarball.display());
+        }
+        println!("  restore with: {}", worktree.restore_command);
+    }
+    for (name, reason) in &result.skipped {
+        println!("Skipped worktree {name}: {reason}");
+    }
+    for (name, error) in &result.failed {
+        eprintln!("Failed to archive worktree {name}: {error}");
+    }
+    if !result.failed.is_empty() {
+        bail!(
+            "{} of the idle worktrees could not be archived",
+            result.failed.len()
+        );
+    }
+
+    Ok(())
+}
+
+fn run_bootstrap_command(app: &App<'_>, cwd: &Path, args: BootstrapArgs) -> Result<()> {
+    app.ensure_config_ready()?;
+    print_notices(app);
+
+    let flag = match (args.flag, args.unflag) {
+        (Some(name), _) => Some((name, true)),
+        (None, Some(name)) => Some((name, false)),
+        (None, None) => None,
+    };
+    if let Some((name, autostart)) = flag {
+        app.ensure_runtime_repo_ready(cwd)?;
+        let name = app.set_autostart(cwd, &name, autostart)?;
+        if autostart {
+            println!("Flagged worktree {name}; bootstrap --flagged starts it");
+        } else {
+            println!("Cleared the bootstrap flag on worktree {name}");
+        }
+        return Ok(());
+    }
+
+    if !args.dash {
+        app.ensure_runtime_repo_ready(cwd)?;
+    }
+    let result = app.bootstrap(BootstrapRequest {
+        cwd: cwd.to_path_buf(),
+        flagged_only: args.flagged,
+        dash: args.dash,
+    })?;
+
+    if result.sessions.is_empty() && result.problems.is_empty() {
+        println!("No worktrees to start.");
+    }
+    for session in &result.sessions {
+        match &session.state {
+            BootstrapState::Started => println!("Started {}", session.session_name),
+            BootstrapState::AlreadyRunning => {
+                println!("Already running {}", session.session_name)
+            }
+            BootstrapState::Skipped(reason) => {
+                println!("Skipped worktree {}: {reason}", session.worktree_name)
+            }
+            BootstrapState::Failed(reason) => {
+                eprintln!("Failed to start {}: {reason}", session.session_name)
+            }
+        }
+    }
+    for (path, reason) in &result.problems {
+        eprintln!("Could not read {}: {reason}", path.display());
+    }
+
+    let failed = result.failed();
+    if failed > 0 {
+        bail!("{failed} of the worktree sessions could not be started");
+    }
+    Ok(())
+}
+
+fn run_config_command(app: &App<'_>, cwd: &Path, args: ConfigArgs) -> Result<()> {
+    match args.command {
+        ConfigCommand::Eject(eject) => {
+            let result = app.config_eject_repo(ConfigEjectRequest {
+                cwd: cwd.to_path_buf(),
+                force: eject.force,
+            })?;
+
+            println!("Wrote {}", result.path.display());
+            println!("Windows: {}", result.window_names.join(", "));
+            if result.always_skip_buckets.is_empty() {
+                println!("Skip rules: none detected");
+            } else {
+                let buckets: Vec<&str> = result
+                    .always_skip_buckets
+                    .iter()
+                    .map(String::as_str)
+                    .collect();
+                println!("Skip rules: {}", buckets.join(", "));
+            }
+            Ok(())
+        }
+        ConfigCommand::Validate(file) => {
+            let result = app.config_validate(ConfigCheckRequest {
+                cwd: cwd.to_path_buf(),
+                repo: file.repo,
+            })?;
+            report_config_check(&result)
+        }
+        ConfigCommand::Edit(file) => {
+            let result = app.config_edit(ConfigCheckRequest {
+                cwd: cwd.to_path_buf(),
+                repo: file.repo,
+            })?;
+            report_config_check(&result)
+        }
+    }
+}
+
+fn report_config_check(result: &ConfigCheckResult) -> Result<()> {
+    if result.issues.is_empty() {
+        println!("{} is valid", result.path.display());
+        return Ok(());
+    }
+
+    for issue in &result.issues {
+        println!("{issue}");
+    }
+    bail!(
+        "{} has {} problem(s)",
+        result.path.display(),
+        result.issues.len()
+    );
+}
+
+fn print_list_result(result: &ListResult) {
+    if result.rows.is_empty() {
+        println!("No worktrees are registered.");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Name", "Created", "Branch", "Session", "Path"]);
+
+    for row in &result.rows {
+        table.add_row(vec![
+            Cell::new(row.name.as_str()),
+            Cell::new(row.created_at.as_str()),
+            Cell::new(row.head.label()),
+            Cell::new(session_status(row)),
+            Cell::new(row.path.display().to_string()),
+        ]);
+    }
+
+    println!("{table}");
+}
+
+fn list_result_json(result: &ListResult) -> serde_json::Value {
+    let rows: Vec<serde_json::Value> = result.rows.iter().map(list_row_json).collect();
+    serde_json::json!({
+        "repo_root": result.repo_root.display().to_string(),
+        "worktrees": rows,
+    })
+}
+
+fn list_row_json(row: &WorktreeRow) -> serde_json::Value {
+    serde_json::json!({
+        "name": row.name,
+        "display_name": row.display_name,
+        "branch": row.head.label(),
+        "path": row.path.display().to_string(),
+        "created_at": row.created_at,
+        "session": row.session_name,
+        "session_running": row.session_running,
+        "disk_usage_bytes": row.disk_usage.as_ref().map(|usage| usage.bytes),
+        "last_attached_at": row.last_attached_at,
+    })
+}
+
+fn session_status(row: &WorktreeRow) -> &'static str {
+    if row.session_running {
+        "running"
+    } else {
+        "not running"
+    }
+}
+
+fn print_import_scan(scan: &ImportScan) {
+    for path in &scan.skipped {
+        eprintln!(
+            "warning: skipped {}; could not derive a free, valid worktree name",
+            path.display()
+        );
+    }
+    if scan.candidates.is_empty() {
+        println!("No unregistered git worktrees found.");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Name", "Layout", "Session", "Path"]);
+
+    for candidate in &scan.candidates {
+        table.add_row(vec![
+            Cell::new(candidate.worktree_name.as_str()),
+            Cell::new(candidate.layout.as_str()),
+            Cell::new(match (&candidate.session_name, &candidate.rename_to) {
+                (Some(session_name), Some(new_name)) => {
+                    format!("{session_name} (renamed to {new_name})")
+                }
+                _ => "-".to_string(),
+            }),
+            Cell::new(candidate.worktree_path.display().to_string()),
+        ]);
+    }
+
+    println!("{table}");
+}
+
+fn print_adopt_scan(scan: &AdoptScan) {
+    if scan.candidates.is_empty() {
+        println!("No orphan tmux sessions found.");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Name", "Session", "Path"]);
+
+    for candidate in &scan.candidates {
+        let session = match &candidate.rename_to {
+            Some(new_name) => format!("{} (renamed to {new_name})", candidate.session_name),
+            None => candidate.session_name.clone(),
+        };
+
+        table.add_row(vec![
+            Cell::new(candidate.worktree_name.as_str()),
+            Cell::new(session),
+            Cell::new(candidate.worktree_path.display().to_string()),
+        ]);
+    }
+
+    println!("{table}");
+    println!("Run `seshmux adopt <name>...` or `seshmux adopt --all` to register them.");
+}
+
+fn print_review_result(result: &ReviewResult) {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Commit", "Subject", "Path"]);
+
+    for worktree in &result.worktrees {
+        let commit = if worktree.reused {
+            format!("{} (existing)", worktree.commit.short_hash)
+        } else {
+            worktree.commit.short_hash.clone()
+        };
+
+        table.add_row(vec![
+            Cell::new(commit),
+            Cell::new(worktree.commit.subject.as_str()),
+            Cell::new(worktree.path.display().to_string()),
+        ]);
+    }
+
+    println!("{table}");
+    println!("Run `seshmux review --clean` to remove review worktrees.");
+}
+
 fn print_doctor_report(report: &DoctorReport) {
     let mut table = Table::new();
     table.set_content_arrangement(ContentArrangement::Dynamic);
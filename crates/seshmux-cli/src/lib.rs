@@ -4,32 +4,115 @@ pub mod dispatch;
 
 use anyhow::{Context, Result};
 use clap::Parser;
-use seshmux_app::App;
-use seshmux_core::command_runner::SystemCommandRunner;
+use serde_json::json;
+use seshmux_app::{App, ProvisionLog};
+use seshmux_core::command_runner::{RunPolicy, SystemCommandRunner};
+use seshmux_core::config::LoadOptions;
+use seshmux_tui::{FrameTiming, InputScript};
 
 use crate::cli::Cli;
-use crate::diagnostics::DiagnosticsSession;
+use crate::diagnostics::{DiagnosticsLevel, DiagnosticsSession};
 
 pub fn run() -> Result<()> {
     let cli = Cli::parse();
-    let diagnostics = DiagnosticsSession::initialize(cli.diagnostics)?;
-    diagnostics.record(format!("cli parsed: command={:?}", cli.command));
+    let diagnostics = DiagnosticsSession::initialize(
+        cli.diagnostics || cli.diagnostics_level.is_some(),
+        cli.diagnostics_level.unwrap_or_default(),
+    )?;
+    diagnostics.record(
+        DiagnosticsLevel::Debug,
+        "cli parsed",
+        &[("command", json!(format!("{:?}", cli.command)))],
+    );
     if let Some(path) = diagnostics.path() {
         eprintln!("Diagnostics enabled: {}", path.display());
     }
 
-    let command_runner = SystemCommandRunner::new();
-    diagnostics.record("command runner initialized");
-    let app = App::new(&command_runner);
-    diagnostics.record("app initialized");
+    let frame_timing = if diagnostics.path().is_some() {
+        FrameTiming::enabled()
+    } else {
+        FrameTiming::default()
+    };
+    let input = InputScript::default();
+    if let Some(path) = &cli.replay {
+        input.replay(path, cli.replay_speed)?;
+    }
+    if let Some(path) = &cli.record {
+        input.record(path)?;
+    }
+
+    let load_options = LoadOptions::resolve(cli.profile.clone());
+    let policy = run_policy(&load_options);
+    let command_runner = SystemCommandRunner::with_policy(policy);
+    diagnostics.record(
+        DiagnosticsLevel::Debug,
+        "command runner initialized",
+        &[
+            (
+                "timeout_secs",
+                json!(policy.timeout.map(|timeout| timeout.as_secs())),
+            ),
+            ("retries", json!(policy.retries)),
+        ],
+    );
+    let provision_log = ProvisionLog::default();
+    let mut app = App::new(&command_runner).with_load_options(load_options);
+    if diagnostics.path().is_some() {
+        app = app.with_provision_log(provision_log.clone());
+    }
+    diagnostics.record(DiagnosticsLevel::Debug, "app initialized", &[]);
     let cwd = std::env::current_dir().context("failed to determine current directory")?;
-    diagnostics.record(format!("cwd={}", cwd.display()));
+    diagnostics.record(
+        DiagnosticsLevel::Info,
+        "working directory resolved",
+        &[("cwd", json!(cwd.display().to_string()))],
+    );
 
-    let result = dispatch::run_with_deps(cli, &app, &cwd);
+    let result = dispatch::run_with_deps(cli, &app, &input, &frame_timing, &cwd);
+    if let Some(report) = frame_timing.take_report() {
+        diagnostics.record(
+            DiagnosticsLevel::Info,
+            "frame timing",
+            &[("report", json!(report))],
+        );
+    }
+    for output in provision_log.take() {
+        diagnostics.record(
+            DiagnosticsLevel::Info,
+            "bootstrap command",
+            &[
+                ("worktree", json!(output.worktree_name)),
+                ("command", json!(output.command)),
+                ("status", json!(output.status)),
+                ("output", json!(output.lines)),
+            ],
+        );
+    }
+    // Written even when the command failed; a recording of the failure is the useful one.
+    match input.finish_recording() {
+        Ok(Some((path, events))) => {
+            eprintln!("Recorded {events} input events to {}", path.display());
+        }
+        Ok(None) => {}
+        Err(error) => eprintln!("{error:#}"),
+    }
     match &result {
-        Ok(()) => diagnostics.record("command completed successfully"),
-        Err(error) => diagnostics.record(format!("command failed: {error:#}")),
+        Ok(()) => diagnostics.record(DiagnosticsLevel::Info, "command completed", &[]),
+        Err(error) => diagnostics.record(
+            DiagnosticsLevel::Error,
+            "command failed",
+            &[("error", json!(format!("{error:#}")))],
+        ),
     }
 
     result
 }
+
+fn run_policy(load_options: &LoadOptions) -> RunPolicy {
+    seshmux_core::config::resolve_config_path()
+        .ok()
+        .filter(|path| path.exists())
+        .and_then(|path| seshmux_core::config::load_config(&path, load_options).ok())
+        .map(|config| config.run_policy())
+        .unwrap_or_default()
+}
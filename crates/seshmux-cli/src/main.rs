@@ -1,6 +1,10 @@
 fn main() {
     if let Err(error) = seshmux_cli::run() {
         eprintln!("Error: {error}");
-        std::process::exit(1);
+        let command_exit = error
+            .downcast_ref::<seshmux_cli::dispatch::CommandExit>()
+            .map(|exit| exit.0);
+        let status = command_exit.unwrap_or_else(|| seshmux_app::Error::from(error).exit_code());
+        std::process::exit(status);
     }
 }
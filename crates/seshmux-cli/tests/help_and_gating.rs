@@ -19,10 +19,10 @@ fn root_help_runs_without_config() {
         .stdout(predicate::str::contains("Usage: seshmux"))
         .stdout(predicate::str::contains("--diagnostics"))
         .stdout(predicate::str::contains("doctor"))
-        .stdout(predicate::str::contains("new").not())
-        .stdout(predicate::str::contains("list").not())
-        .stdout(predicate::str::contains("attach").not())
-        .stdout(predicate::str::contains("delete").not());
+        .stdout(predicate::str::contains("attach"))
+        .stdout(predicate::str::contains("delete"))
+        .stdout(predicate::str::contains("list"))
+        .stdout(predicate::str::contains("new"));
 }
 
 #[test]
@@ -49,17 +49,653 @@ fn doctor_runs_without_config() {
 }
 
 #[test]
-fn legacy_subcommands_are_rejected() {
-    for subcommand in ["new", "list", "attach", "delete"] {
+fn review_help_runs_without_config() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["review", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Check out commits into temporary review worktrees",
+        ))
+        .stdout(predicate::str::contains("--endpoints"))
+        .stdout(predicate::str::contains("--clean"));
+}
+
+#[test]
+fn init_is_gated_without_config() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["init", "https://example.com/acme/widgets.git"])
+        .assert()
+        .code(78)
+        .stderr(predicate::str::contains("missing config at"));
+}
+
+#[test]
+fn review_requires_range_or_clean() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command.arg("review").assert().failure();
+}
+
+#[test]
+fn config_eject_repo_writes_starter_config_without_global_config() {
+    let (mut command, temp_home) = new_command_with_temp_home();
+    let repo = temp_home.path().join("repo");
+    init_git_repo(&repo);
+    fs::write(repo.join(".gitignore"), "target/\n").expect("write gitignore");
+    fs::create_dir_all(repo.join("target")).expect("create target");
+    fs::write(repo.join("target").join("out.bin"), "x").expect("write artifact");
+    fs::write(repo.join("Cargo.toml"), "[workspace]\n").expect("write Cargo.toml");
+
+    command
+        .current_dir(&repo)
+        .args(["config", "eject", "--repo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(".seshmux.toml"))
+        .stdout(predicate::str::contains("Skip rules: target"));
+
+    let written = fs::read_to_string(repo.join(".seshmux.toml")).expect("read repo config");
+    assert!(written.contains("name = \"cargo\""));
+    assert!(written.contains("always_skip_buckets = [\"target\"]"));
+
+    let (mut again, _other_home) = new_command_with_temp_home();
+    again
+        .current_dir(&repo)
+        .args(["config", "eject", "--repo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--force"));
+}
+
+#[test]
+fn config_validate_reports_every_problem_with_line_numbers() {
+    let (mut command, temp_home) = new_command_with_temp_home();
+    write_valid_config(temp_home.path());
+    command
+        .args(["config", "validate"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("is valid"));
+
+    let config_path = temp_home.path().join(".config/seshmux/config.toml");
+    fs::write(
+        &config_path,
+        "version = 2\n\n[[tmux.windows]]\nname = \"\"\n",
+    )
+    .expect("write invalid config");
+
+    command
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("line 1: version must be 1"))
+        .stdout(predicate::str::contains(
+            "line 4: window[0] name must be non-empty",
+        ))
+        .stderr(predicate::str::contains("has 2 problem(s)"));
+}
+
+#[test]
+fn config_validate_applies_includes_and_the_selected_profile() {
+    let (mut command, temp_home) = new_command_with_temp_home();
+    let config_dir = temp_home.path().join(".config/seshmux");
+    fs::create_dir_all(&config_dir).expect("config dir");
+    fs::write(
+        config_dir.join("work.toml"),
+        "[profiles.work]\nbranch_template = \"work/{name}\"\n\n[[tmux.windows]]\nname = \"editor\"\nprogram = \"nvim\"\n",
+    )
+    .expect("write include");
+    fs::write(
+        config_dir.join("config.toml"),
+        "version = 1\ninclude = [\"work.toml\"]\n",
+    )
+    .expect("write config");
+
+    command
+        .env("SESHMUX_PROFILE", "work")
+        .args(["config", "validate"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("is valid"));
+
+    command
+        .args(["--profile", "home"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "unknown config profile 'home'; defined profiles: work",
+        ));
+}
+
+#[test]
+fn init_config_writes_a_valid_starter_config_once() {
+    let (mut command, temp_home) = new_command_with_temp_home();
+
+    command
+        .env("EDITOR", "hx")
+        .env("SHELL", "/bin/zsh")
+        .arg("init-config")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(".config/seshmux/config.toml"));
+
+    let written = fs::read_to_string(temp_home.path().join(".config/seshmux/config.toml"))
+        .expect("read config");
+    assert!(written.contains("editor = [\"hx\"]"));
+    assert!(written.contains("program = \"/bin/zsh\""));
+    assert!(written.contains("layout = \"session\""));
+
+    let seshmux = || {
+        let mut command = assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("seshmux"));
+        command.env("HOME", temp_home.path());
+        command.env("XDG_CONFIG_HOME", temp_home.path().join(".config"));
+        command
+    };
+    seshmux()
+        .args(["config", "validate"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("is valid"));
+
+    seshmux()
+        .arg("init-config")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("pass --force to overwrite it"));
+}
+
+#[test]
+fn config_edit_runs_editor_then_validates() {
+    let (mut command, temp_home) = new_command_with_temp_home();
+    write_valid_config(temp_home.path());
+
+    command
+        .env("EDITOR", "true")
+        .args(["config", "edit"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("is valid"));
+}
+
+#[test]
+fn delete_help_lists_flags() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["delete", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--kill-session"))
+        .stdout(predicate::str::contains("--delete-branch"))
+        .stdout(predicate::str::contains("--force"));
+}
+
+#[test]
+fn named_worktree_subcommands_require_a_name() {
+    for subcommand in ["attach", "delete", "open"] {
         let (mut command, _temp_home) = new_command_with_temp_home();
+        command.arg(subcommand).assert().failure();
+    }
+}
+
+#[test]
+fn named_worktree_subcommands_reject_unknown_worktree() {
+    for subcommand in ["attach", "delete", "open"] {
+        let (mut command, temp_home) = new_command_with_temp_home();
+        let repo = temp_home.path().join("repo");
+        init_git_repo(&repo);
+        fs::write(repo.join("README.md"), "hello\n").expect("write readme");
+        run_git(&repo, &["add", "."]);
+        run_git(
+            &repo,
+            &[
+                "-c",
+                "user.name=seshmux-test",
+                "-c",
+                "user.email=seshmux-test@example.com",
+                "commit",
+                "-m",
+                "initial",
+            ],
+        );
+
         command
-            .arg(subcommand)
+            .current_dir(&repo)
+            .args([subcommand, "missing"])
             .assert()
             .failure()
-            .stderr(predicate::str::contains("unrecognized subcommand"));
+            .stderr(predicate::str::contains(
+                "worktree 'missing' was not found in worktree.toml",
+            ));
     }
 }
 
+#[test]
+fn attach_porcelain_conflicts_with_print() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["attach", "w1", "--print", "--porcelain"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn attach_last_conflicts_with_a_name() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["attach", "w1", "--last"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn restore_requires_the_worktree_name() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["restore"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("<NAME>"));
+
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("restore"));
+}
+
+#[test]
+fn run_requires_a_command_after_the_separator() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["run", "w1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("<COMMAND>"));
+
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["run", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--window"));
+}
+
+#[test]
+fn sessions_help_lists_kill_all() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["sessions", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("kill-all"));
+}
+
+#[test]
+fn sessions_kill_all_known_and_all_flags_conflict() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["sessions", "kill-all", "--known", "--all"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn bootstrap_flag_conflicts_with_starting_sessions() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["bootstrap", "--flag", "w1", "--flagged"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn layout_capture_help_lists_the_layout_name_option() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["layout", "capture", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--as <LAYOUT>"));
+}
+
+#[test]
+fn skip_rules_help_lists_subcommands() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["skip-rules", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("list"))
+        .stdout(predicate::str::contains("add"))
+        .stdout(predicate::str::contains("remove"));
+}
+
+#[test]
+fn import_help_describes_candidates_and_all_flag() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["import", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("lists candidates when omitted"))
+        .stdout(predicate::str::contains("--all"));
+}
+
+#[test]
+fn demo_help_lists_keep_flag() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["demo", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--keep"));
+}
+
+#[test]
+fn dash_fails_before_tui_without_configured_repos() {
+    let (mut command, temp_home) = new_command_with_temp_home();
+    write_valid_config(temp_home.path());
+
+    command
+        .current_dir(temp_home.path())
+        .arg("dash")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "no repositories configured for seshmux dash",
+        ));
+}
+
+#[test]
+fn menu_outside_tmux_explains_how_to_bind_it() {
+    let (mut command, temp_home) = new_command_with_temp_home();
+    write_valid_config(temp_home.path());
+    let repo = temp_home.path().join("repo");
+    init_git_repo(&repo);
+
+    command
+        .current_dir(&repo)
+        .env_remove("TMUX")
+        .arg("menu")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "seshmux menu must run inside tmux",
+        ))
+        .stderr(predicate::str::contains("bind-key W run-shell"));
+}
+
+#[test]
+fn list_help_lists_sort_flags() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["list", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--sort"))
+        .stdout(predicate::str::contains("--reverse"))
+        .stdout(predicate::str::contains("--group-running"))
+        .stdout(predicate::str::contains(
+            "Only show worktrees with a running tmux session",
+        ))
+        .stdout(predicate::str::contains("--format"));
+}
+
+#[test]
+fn list_rejects_unknown_formats() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["list", "--format", "csv"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "possible values: table, json, names",
+        ));
+}
+
+#[test]
+fn new_subcommand_requires_a_spec() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .arg("new")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--spec <PATH>"));
+}
+
+#[test]
+fn new_spec_from_stdin_rejects_invalid_spec() {
+    let (mut command, temp_home) = new_command_with_temp_home();
+    write_valid_config(temp_home.path());
+    let repo = temp_home.path().join("repo");
+    init_git_repo(&repo);
+    run_git(
+        &repo,
+        &[
+            "-c",
+            "user.name=seshmux-test",
+            "-c",
+            "user.email=seshmux-test@example.com",
+            "commit",
+            "--allow-empty",
+            "-m",
+            "initial",
+        ],
+    );
+
+    command
+        .current_dir(&repo)
+        .args(["new", "--spec", "-"])
+        .write_stdin(r#"{"name": "w1", "branch": "main", "commit": "HEAD"}"#)
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(
+            "cannot set both branch and commit",
+        ));
+}
+
+#[test]
+fn new_branch_flag_is_validated_as_a_git_ref() {
+    let (mut command, temp_home) = new_command_with_temp_home();
+    write_valid_config(temp_home.path());
+    let repo = temp_home.path().join("repo");
+    init_git_repo(&repo);
+    run_git(
+        &repo,
+        &[
+            "-c",
+            "user.name=seshmux-test",
+            "-c",
+            "user.email=seshmux-test@example.com",
+            "commit",
+            "--allow-empty",
+            "-m",
+            "initial",
+        ],
+    );
+
+    command
+        .current_dir(&repo)
+        .args(["new", "--spec", "-", "--branch", "feature/bad..name"])
+        .write_stdin(r#"{"name": "w1"}"#)
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(
+            "invalid branch name 'feature/bad..name'",
+        ));
+    assert!(!repo.join("worktrees/w1").exists());
+}
+
+#[test]
+fn new_quick_conflicts_with_a_spec() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["new", "--quick", "w1", "--spec", "-"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn new_batch_reports_every_worktree_and_stops_at_the_first_failure() {
+    let (mut command, temp_home) = new_command_with_temp_home();
+    write_valid_config(temp_home.path());
+    let repo = temp_home.path().join("repo");
+    init_git_repo(&repo);
+    run_git(
+        &repo,
+        &[
+            "-c",
+            "user.name=seshmux-test",
+            "-c",
+            "user.email=seshmux-test@example.com",
+            "commit",
+            "--allow-empty",
+            "-m",
+            "initial",
+        ],
+    );
+    let batch = r#"[{"name": "Bad"}, {"name": "w2", "branch": "main", "commit": "HEAD"}]"#;
+
+    let output = command
+        .current_dir(&repo)
+        .args(["new", "--batch", "-"])
+        .write_stdin(batch)
+        .output()
+        .expect("run seshmux");
+    assert!(!output.status.success());
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(summary["worktrees"][0]["name"], "Bad");
+    assert_eq!(summary["worktrees"][0]["status"], "failed");
+    assert!(
+        summary["worktrees"][0]["error"]
+            .as_str()
+            .expect("error")
+            .contains("invalid worktree name 'Bad'")
+    );
+    assert_eq!(summary["worktrees"][1]["status"], "skipped");
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("1 of 2 worktrees in the batch failed")
+    );
+
+    let (mut command, _other_home) = new_command_with_temp_home();
+    let output = command
+        .env("HOME", temp_home.path())
+        .env("XDG_CONFIG_HOME", temp_home.path().join(".config"))
+        .current_dir(&repo)
+        .args(["new", "--batch", "-", "--keep-going"])
+        .write_stdin(batch)
+        .output()
+        .expect("run seshmux");
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(summary["worktrees"][1]["status"], "failed");
+    assert!(
+        summary["worktrees"][1]["error"]
+            .as_str()
+            .expect("error")
+            .contains("cannot set both branch and commit")
+    );
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("2 of 2 worktrees in the batch failed")
+    );
+    assert!(!repo.join("worktrees").join("w2").exists());
+}
+
+#[test]
+fn new_batch_fails_before_creating_anything_when_a_branch_is_checked_out_elsewhere() {
+    let (mut command, temp_home) = new_command_with_temp_home();
+    write_valid_config(temp_home.path());
+    let repo = temp_home.path().join("repo");
+    init_git_repo(&repo);
+    run_git(
+        &repo,
+        &[
+            "-c",
+            "user.name=seshmux-test",
+            "-c",
+            "user.email=seshmux-test@example.com",
+            "commit",
+            "--allow-empty",
+            "-m",
+            "initial",
+        ],
+    );
+    let elsewhere = temp_home.path().join("elsewhere");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "w2", &elsewhere.to_string_lossy()],
+    );
+
+    let output = command
+        .current_dir(&repo)
+        .args(["new", "--batch", "-"])
+        .write_stdin(r#"[{"name": "w1"}, {"name": "w2", "use_existing_branch": true}]"#)
+        .output()
+        .expect("run seshmux");
+
+    assert!(!output.status.success());
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(summary["worktrees"][0]["status"], "skipped");
+    assert_eq!(summary["worktrees"][1]["status"], "failed");
+    assert!(
+        summary["worktrees"][1]["error"]
+            .as_str()
+            .expect("error")
+            .contains("branch 'w2' is already checked out in")
+    );
+    assert!(!repo.join("worktrees").join("w1").exists());
+}
+
+#[test]
+fn new_batch_conflicts_with_a_spec_and_keep_going_needs_a_batch() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["new", "--batch", "-", "--spec", "-"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["new", "--spec", "-", "--keep-going"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["new", "--keep-going"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--batch <PATH>"));
+}
+
+#[test]
+fn new_checkout_step_flags_conflict_with_their_negations() {
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["new", "--spec", "-", "--lfs", "--no-lfs"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .args(["new", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--submodules"))
+        .stdout(predicate::str::contains("--no-submodules"));
+}
+
 #[test]
 fn root_command_is_gated_without_config() {
     let (mut command, _temp_home) = new_command_with_temp_home();
@@ -68,6 +704,9 @@ fn root_command_is_gated_without_config() {
         .failure()
         .stderr(predicate::str::contains("missing config at"))
         .stderr(predicate::str::contains(".config/seshmux/config.toml"))
+        .stderr(predicate::str::contains(
+            "seshmux init-config --interactive",
+        ))
         .stderr(predicate::str::contains("README.md"));
 }
 
@@ -141,6 +780,81 @@ fn doctor_with_diagnostics_creates_log_file() {
     assert_timestamp_log_names(&logs);
 }
 
+#[test]
+fn diagnostics_level_writes_filtered_json_lines() {
+    let (mut command, temp_home) = new_command_with_temp_home();
+    command
+        .args(["--diagnostics-level", "warn", "attach", "missing"])
+        .assert()
+        .failure();
+
+    let diagnostics_dir = temp_home.path().join(".config/seshmux/diagnostics");
+    let logs: Vec<_> = fs::read_dir(&diagnostics_dir)
+        .expect("diagnostics dir")
+        .filter_map(Result::ok)
+        .collect();
+    assert_timestamp_log_names(&logs);
+
+    let contents = fs::read_to_string(logs[0].path()).expect("read log");
+    let events: Vec<serde_json::Value> = contents
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("json line"))
+        .collect();
+    let names: Vec<&str> = events
+        .iter()
+        .map(|event| event["event"].as_str().expect("event name"))
+        .collect();
+    assert_eq!(names, vec!["diagnostics start", "command failed"]);
+    assert_eq!(events[1]["level"], "error");
+    assert!(events[1]["fields"]["error"].is_string());
+}
+
+#[test]
+fn replay_rejects_a_bad_script_and_conflicts_with_record() {
+    let (mut command, temp_home) = new_command_with_temp_home();
+    let script = temp_home.path().join("demo.json");
+    fs::write(
+        &script,
+        r#"{"version": 1, "events": [{"key": "Down"}, {"key": "Hyper+x"}]}"#,
+    )
+    .expect("write script");
+    command
+        .arg("--replay")
+        .arg(&script)
+        .arg("doctor")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid input script"))
+        .stderr(predicate::str::contains("event 2: bad key"));
+
+    let (mut command, _temp_home) = new_command_with_temp_home();
+    command
+        .arg("--replay")
+        .arg(&script)
+        .args(["--record", "out.json", "doctor"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn record_writes_an_input_script_when_the_command_exits() {
+    let (mut command, temp_home) = new_command_with_temp_home();
+    let script = temp_home.path().join("session.json");
+    command
+        .arg("--record")
+        .arg(&script)
+        .arg("doctor")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Recorded 0 input events to"));
+
+    let recorded: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&script).expect("read script")).expect("json");
+    assert_eq!(recorded["version"], 1);
+    assert_eq!(recorded["events"], serde_json::json!([]));
+}
+
 fn run_git(repo_dir: &Path, args: &[&str]) {
     let output = StdCommand::new("git")
         .args(args)
@@ -0,0 +1,137 @@
+use std::path::Path;
+
+use thiserror::Error;
+
+use crate::command_adapter::ensure_success;
+use crate::command_runner::CommandRunner;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Error)]
+pub enum ArchiveError {
+    #[error("{0}")]
+    InvalidPath(String),
+    #[error("failed to execute tar: {0}")]
+    Execute(String),
+    #[error("tar {command} failed (exit {status}) {stderr}")]
+    CommandFailed {
+        command: String,
+        status: i32,
+        stderr: String,
+    },
+}
+
+pub fn idle_seconds(now: u64, activity: &[Option<u64>]) -> Option<u64> {
+    let latest = activity.iter().flatten().max()?;
+    Some(now.saturating_sub(*latest))
+}
+
+pub fn idle_longer_than(idle_seconds: u64, days: u64) -> bool {
+    idle_seconds >= days.saturating_mul(SECONDS_PER_DAY)
+}
+
+pub fn whole_days(seconds: u64) -> u64 {
+    seconds / SECONDS_PER_DAY
+}
+
+/// Packs the worktree contents without its `.git` file, which points at git's per-worktree
+/// metadata and is recreated when the worktree is added back.
+pub fn create_tarball(
+    worktree_path: &Path,
+    tarball: &Path,
+    runner: &dyn CommandRunner,
+) -> Result<(), ArchiveError> {
+    let source = utf8_path(worktree_path)?;
+    let target = utf8_path(tarball)?;
+    run_tar(
+        runner,
+        &["-czf", target, "-C", source, "--exclude=./.git", "."],
+    )
+}
+
+pub fn extract_tarball(
+    tarball: &Path,
+    worktree_path: &Path,
+    runner: &dyn CommandRunner,
+) -> Result<(), ArchiveError> {
+    let source = utf8_path(tarball)?;
+    let target = utf8_path(worktree_path)?;
+    run_tar(runner, &["-xzf", source, "-C", target])
+}
+
+fn run_tar(runner: &dyn CommandRunner, args: &[&str]) -> Result<(), ArchiveError> {
+    let output = runner
+        .run_untimed("tar", args, None)
+        .map_err(|error| ArchiveError::Execute(error.to_string()))?;
+    ensure_success(args, output).map_err(|failure| ArchiveError::CommandFailed {
+        command: failure.command,
+        status: failure.status,
+        stderr: failure.stderr,
+    })?;
+    Ok(())
+}
+
+fn utf8_path(path: &Path) -> Result<&str, ArchiveError> {
+    path.to_str().ok_or_else(|| {
+        ArchiveError::InvalidPath(format!("path is not valid UTF-8: {}", path.display()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::test_support::{RecordingRunner, output};
+
+    use super::*;
+
+    #[test]
+    fn idle_seconds_measures_from_the_latest_activity() {
+        assert_eq!(
+            idle_seconds(1_000, &[Some(100), None, Some(400)]),
+            Some(600)
+        );
+        assert_eq!(idle_seconds(1_000, &[None, None]), None);
+        assert_eq!(idle_seconds(100, &[Some(400)]), Some(0));
+
+        assert!(idle_longer_than(2 * SECONDS_PER_DAY, 2));
+        assert!(!idle_longer_than(2 * SECONDS_PER_DAY - 1, 2));
+        assert_eq!(whole_days(3 * SECONDS_PER_DAY + 5), 3);
+    }
+
+    #[test]
+    fn tarball_round_trip_skips_the_git_link() {
+        let runner = RecordingRunner::new(
+            vec![output("", "", 0), output("", "tar: bad file", 2)],
+            Vec::new(),
+        );
+
+        create_tarball(
+            Path::new("/repo/worktrees/w1"),
+            Path::new("/repo/worktrees/.archive/w1.tar.gz"),
+            &runner,
+        )
+        .expect("create");
+        let error = extract_tarball(
+            Path::new("/repo/worktrees/.archive/w1.tar.gz"),
+            Path::new("/repo/worktrees/w1"),
+            &runner,
+        )
+        .expect_err("extract fails");
+
+        let calls = runner.calls();
+        assert_eq!(calls[0].program, "tar");
+        assert_eq!(
+            calls[0].args,
+            vec![
+                "-czf",
+                "/repo/worktrees/.archive/w1.tar.gz",
+                "-C",
+                "/repo/worktrees/w1",
+                "--exclude=./.git",
+                "."
+            ]
+        );
+        assert!(error.to_string().contains("tar: bad file"));
+    }
+}
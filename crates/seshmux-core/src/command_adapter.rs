@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use crate::command_runner::{CommandOutput, CommandRunner};
+use crate::command_runner::{CommandOutput, CommandRunner, CommandTimedOut};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct CommandFailure {
@@ -9,15 +9,36 @@ pub(crate) struct CommandFailure {
     pub(crate) stderr: String,
 }
 
+#[derive(Debug)]
+pub(crate) enum RunFailure {
+    TimedOut(CommandTimedOut),
+    Execute(String),
+}
+
 pub(crate) fn run_program(
     runner: &dyn CommandRunner,
     program: &str,
     args: &[&str],
     cwd: Option<&Path>,
 ) -> Result<CommandOutput, String> {
+    run_program_timed(runner, program, args, cwd).map_err(|failure| match failure {
+        RunFailure::TimedOut(timeout) => timeout.to_string(),
+        RunFailure::Execute(message) => message,
+    })
+}
+
+pub(crate) fn run_program_timed(
+    runner: &dyn CommandRunner,
+    program: &str,
+    args: &[&str],
+    cwd: Option<&Path>,
+) -> Result<CommandOutput, RunFailure> {
     runner
         .run(program, args, cwd)
-        .map_err(|error| error.to_string())
+        .map_err(|error| match error.downcast::<CommandTimedOut>() {
+            Ok(timeout) => RunFailure::TimedOut(timeout),
+            Err(error) => RunFailure::Execute(error.to_string()),
+        })
 }
 
 pub(crate) fn ensure_success(
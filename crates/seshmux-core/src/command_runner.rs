@@ -1,13 +1,49 @@
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
-use std::process::Command;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Sender};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct CommandOutput {
     pub status_code: i32,
     pub stdout: String,
     pub stderr: String,
 }
 
+impl CommandOutput {
+    pub fn new(status_code: i32, stdout: impl Into<String>, stderr: impl Into<String>) -> Self {
+        Self {
+            status_code,
+            stdout: stdout.into(),
+            stderr: stderr.into(),
+        }
+    }
+}
+
+/// A command's output with stdout left as bytes, for output that is not text, such as the
+/// NUL-separated paths of `git ls-files -z`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawCommandOutput {
+    pub status_code: i32,
+    pub stdout: Vec<u8>,
+    pub stderr: String,
+}
+
+impl From<RawCommandOutput> for CommandOutput {
+    fn from(output: RawCommandOutput) -> Self {
+        Self {
+            status_code: output.status_code,
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: output.stderr,
+        }
+    }
+}
+
 pub trait CommandRunner {
     fn run(
         &self,
@@ -22,24 +58,103 @@ pub trait CommandRunner {
         args: &[&str],
         cwd: Option<&Path>,
     ) -> anyhow::Result<i32>;
+
+    /// Like `run`, keeping stdout exactly as the command wrote it.
+    fn run_raw(
+        &self,
+        program: &str,
+        args: &[&str],
+        cwd: Option<&Path>,
+    ) -> anyhow::Result<RawCommandOutput> {
+        let output = self.run(program, args, cwd)?;
+        Ok(RawCommandOutput {
+            status_code: output.status_code,
+            stdout: output.stdout.into_bytes(),
+            stderr: output.stderr,
+        })
+    }
+
+    /// Like `run`, without the policy's timeout, for steps that may legitimately run for a long
+    /// time such as `git lfs pull` or a user's hook.
+    fn run_untimed(
+        &self,
+        program: &str,
+        args: &[&str],
+        cwd: Option<&Path>,
+    ) -> anyhow::Result<CommandOutput> {
+        self.run(program, args, cwd)
+    }
+
+    /// The policy this runner applies, so work moved to another thread can build a runner that
+    /// behaves the same.
+    fn policy(&self) -> RunPolicy {
+        RunPolicy::default()
+    }
+
+    fn run_streaming(
+        &self,
+        program: &str,
+        args: &[&str],
+        cwd: Option<&Path>,
+        on_line: &mut dyn FnMut(&str),
+    ) -> anyhow::Result<CommandOutput> {
+        let output = self.run(program, args, cwd)?;
+        for line in output.stdout.lines().chain(output.stderr.lines()) {
+            on_line(line);
+        }
+        Ok(output)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("`{command}` did not finish within {}s and was killed (command_timeout_secs)", timeout.as_secs())]
+pub struct CommandTimedOut {
+    pub command: String,
+    pub timeout: Duration,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RunPolicy {
+    pub timeout: Option<Duration>,
+    pub retries: u32,
 }
 
+const TRANSIENT_STDERR: &[&str] = &[
+    ".lock': File exists",
+    "Could not resolve host",
+    "Connection timed out",
+    "Connection reset by peer",
+    "The remote end hung up unexpectedly",
+];
+
+// Only git commands that read from a remote are retried; running anything else twice could
+// repeat work the first attempt already did.
+const RETRIED_GIT_COMMANDS: &[&str] = &["fetch", "ls-remote"];
+
+const RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
 #[derive(Debug, Default)]
-pub struct SystemCommandRunner;
+pub struct SystemCommandRunner {
+    policy: RunPolicy,
+}
 
 impl SystemCommandRunner {
     pub fn new() -> Self {
-        Self
+        Self::default()
     }
-}
 
-impl CommandRunner for SystemCommandRunner {
-    fn run(
+    pub fn with_policy(policy: RunPolicy) -> Self {
+        Self { policy }
+    }
+
+    fn run_once(
         &self,
         program: &str,
         args: &[&str],
         cwd: Option<&Path>,
-    ) -> anyhow::Result<CommandOutput> {
+        timeout: Option<Duration>,
+    ) -> anyhow::Result<RawCommandOutput> {
         let mut command = Command::new(program);
         command.args(args);
 
@@ -47,14 +162,151 @@ impl CommandRunner for SystemCommandRunner {
             command.current_dir(working_directory);
         }
 
-        let output = command.output()?;
+        let Some(timeout) = timeout else {
+            let output = command.output()?;
+            return Ok(RawCommandOutput {
+                status_code: output.status.code().unwrap_or(-1),
+                stdout: output.stdout,
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        };
 
-        Ok(CommandOutput {
-            status_code: output.status.code().unwrap_or(-1),
-            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+        let mut child = command.spawn()?;
+        let stdout = read_in_background(child.stdout.take());
+        let stderr = read_in_background(child.stderr.take());
+        let status = match wait_with_deadline(&mut child, Instant::now() + timeout)? {
+            Some(status) => status,
+            None => {
+                kill_process_group(&mut child);
+                let _ = child.wait();
+                // The readers are left behind: a grandchild may still hold the pipes open.
+                return Err(CommandTimedOut {
+                    command: describe(program, args),
+                    timeout,
+                }
+                .into());
+            }
+        };
+
+        Ok(RawCommandOutput {
+            status_code: status.code().unwrap_or(-1),
+            stdout: join_reader(stdout),
+            stderr: String::from_utf8_lossy(&join_reader(stderr)).to_string(),
         })
     }
+}
+
+// The child leads its own process group, so helpers it started (git's remote helpers, a hook's
+// subshells) are killed with it.
+fn kill_process_group(child: &mut Child) {
+    #[cfg(unix)]
+    if let Some(pid) = rustix::process::Pid::from_raw(child.id() as i32)
+        && rustix::process::kill_process_group(pid, rustix::process::Signal::KILL).is_ok()
+    {
+        return;
+    }
+    let _ = child.kill();
+}
+
+fn read_in_background<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
+
+fn join_reader(reader: JoinHandle<Vec<u8>>) -> Vec<u8> {
+    reader.join().unwrap_or_default()
+}
+
+fn wait_with_deadline(
+    child: &mut Child,
+    deadline: Instant,
+) -> std::io::Result<Option<std::process::ExitStatus>> {
+    let mut interval = Duration::from_millis(1);
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(None);
+        }
+        std::thread::sleep(interval.min(deadline - now));
+        interval = (interval * 2).min(Duration::from_millis(50));
+    }
+}
+
+fn describe(program: &str, args: &[&str]) -> String {
+    std::iter::once(program)
+        .chain(args.iter().copied())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn is_retryable(program: &str, args: &[&str], output: &RawCommandOutput) -> bool {
+    let is_git = Path::new(program)
+        .file_name()
+        .is_some_and(|name| name == "git");
+    is_git
+        && args
+            .first()
+            .is_some_and(|subcommand| RETRIED_GIT_COMMANDS.contains(subcommand))
+        && output.status_code != 0
+        && TRANSIENT_STDERR
+            .iter()
+            .any(|marker| output.stderr.contains(marker))
+}
+
+impl CommandRunner for SystemCommandRunner {
+    fn run(
+        &self,
+        program: &str,
+        args: &[&str],
+        cwd: Option<&Path>,
+    ) -> anyhow::Result<CommandOutput> {
+        Ok(self.run_raw(program, args, cwd)?.into())
+    }
+
+    fn run_raw(
+        &self,
+        program: &str,
+        args: &[&str],
+        cwd: Option<&Path>,
+    ) -> anyhow::Result<RawCommandOutput> {
+        // A timed-out command is not retried: it may have done part of its work before the kill.
+        let mut attempt = 0;
+        loop {
+            let output = self.run_once(program, args, cwd, self.policy.timeout)?;
+            if attempt >= self.policy.retries || !is_retryable(program, args, &output) {
+                return Ok(output);
+            }
+            attempt += 1;
+            std::thread::sleep(RETRY_BACKOFF * attempt);
+        }
+    }
+
+    fn run_untimed(
+        &self,
+        program: &str,
+        args: &[&str],
+        cwd: Option<&Path>,
+    ) -> anyhow::Result<CommandOutput> {
+        Ok(self.run_once(program, args, cwd, None)?.into())
+    }
+
+    fn policy(&self) -> RunPolicy {
+        self.policy
+    }
 
     fn run_interactive(
         &self,
@@ -72,4 +324,205 @@ impl CommandRunner for SystemCommandRunner {
         let status = command.status()?;
         Ok(status.code().unwrap_or(-1))
     }
+
+    fn run_streaming(
+        &self,
+        program: &str,
+        args: &[&str],
+        cwd: Option<&Path>,
+        on_line: &mut dyn FnMut(&str),
+    ) -> anyhow::Result<CommandOutput> {
+        let mut command = Command::new(program);
+        command
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        if let Some(working_directory) = cwd {
+            command.current_dir(working_directory);
+        }
+
+        let mut child = command.spawn()?;
+        let (sender, receiver) = mpsc::channel();
+        let stdout = forward_lines(child.stdout.take(), false, sender.clone());
+        let stderr = forward_lines(child.stderr.take(), true, sender);
+
+        let mut output = CommandOutput {
+            status_code: -1,
+            stdout: String::new(),
+            stderr: String::new(),
+        };
+        for (is_stderr, line) in receiver {
+            on_line(&line);
+            let target = if is_stderr {
+                &mut output.stderr
+            } else {
+                &mut output.stdout
+            };
+            target.push_str(&line);
+            target.push('\n');
+        }
+        let _ = stdout.join();
+        let _ = stderr.join();
+        output.status_code = child.wait()?.code().unwrap_or(-1);
+        Ok(output)
+    }
+}
+
+fn forward_lines<R: Read + Send + 'static>(
+    pipe: Option<R>,
+    is_stderr: bool,
+    sender: Sender<(bool, String)>,
+) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        let Some(pipe) = pipe else {
+            return;
+        };
+        for line in BufReader::new(pipe).lines() {
+            let Ok(line) = line else {
+                return;
+            };
+            if sender.send((is_stderr, line)).is_err() {
+                return;
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{CommandRunner, CommandTimedOut, RunPolicy, SystemCommandRunner};
+
+    #[test]
+    fn a_command_past_the_timeout_is_killed_with_a_typed_error() {
+        let runner = SystemCommandRunner::with_policy(RunPolicy {
+            timeout: Some(Duration::from_millis(200)),
+            retries: 0,
+        });
+        let started = Instant::now();
+        let error = runner
+            .run("sh", &["-c", "sleep 5"], None)
+            .expect_err("should time out");
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert_eq!(
+            error.downcast_ref::<CommandTimedOut>(),
+            Some(&CommandTimedOut {
+                command: "sh -c sleep 5".to_string(),
+                timeout: Duration::from_millis(200),
+            })
+        );
+
+        let output = runner
+            .run("sh", &["-c", "echo out; echo err >&2; exit 3"], None)
+            .expect("fast command");
+        assert_eq!(
+            (
+                output.status_code,
+                output.stdout.as_str(),
+                output.stderr.as_str()
+            ),
+            (3, "out\n", "err\n")
+        );
+    }
+
+    #[test]
+    fn streaming_hands_over_each_line_and_keeps_the_output() {
+        let mut lines = Vec::new();
+        let output = SystemCommandRunner::new()
+            .run_streaming(
+                "sh",
+                &["-c", "echo one; echo two >&2; echo three; exit 4"],
+                None,
+                &mut |line| lines.push(line.to_string()),
+            )
+            .expect("run");
+
+        lines.sort();
+        assert_eq!(lines, vec!["one", "three", "two"]);
+        assert_eq!(output.status_code, 4);
+        assert_eq!(output.stdout, "one\nthree\n");
+        assert_eq!(output.stderr, "two\n");
+    }
+
+    #[test]
+    fn lock_contention_is_retried_up_to_the_limit_for_git_fetch_only() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let git = temp.path().join("git");
+        std::fs::write(
+            &git,
+            "#!/bin/sh\necho x >> attempts; [ $(wc -l < attempts) -ge 3 ] && exit 0; \
+             echo \"fatal: Unable to create '/repo/.git/index.lock': File exists.\" >&2; exit 128\n",
+        )
+        .expect("write fake git");
+        std::fs::set_permissions(&git, std::os::unix::fs::PermissionsExt::from_mode(0o755))
+            .expect("chmod");
+        let git = git.to_string_lossy().to_string();
+        let attempts = || {
+            let count = std::fs::read_to_string(temp.path().join("attempts"))
+                .expect("attempts")
+                .lines()
+                .count();
+            std::fs::remove_file(temp.path().join("attempts")).expect("reset");
+            count
+        };
+        let runner = |retries| {
+            SystemCommandRunner::with_policy(RunPolicy {
+                timeout: None,
+                retries,
+            })
+        };
+
+        let output = runner(1)
+            .run(&git, &["fetch", "origin"], Some(temp.path()))
+            .expect("run");
+        assert_eq!((output.status_code, attempts()), (128, 2));
+
+        let output = runner(2)
+            .run(&git, &["fetch", "origin"], Some(temp.path()))
+            .expect("run");
+        assert_eq!((output.status_code, attempts()), (0, 3));
+
+        let output = runner(2)
+            .run(&git, &["worktree", "add", "w1"], Some(temp.path()))
+            .expect("run");
+        assert_eq!((output.status_code, attempts()), (128, 1));
+    }
+
+    #[test]
+    fn a_timeout_kills_the_whole_process_group_and_untimed_runs_opt_out() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let runner = SystemCommandRunner::with_policy(RunPolicy {
+            timeout: Some(Duration::from_millis(200)),
+            retries: 0,
+        });
+        runner
+            .run(
+                "sh",
+                &["-c", "sleep 30 & echo $! > pid; wait"],
+                Some(temp.path()),
+            )
+            .expect_err("should time out");
+        let pid = std::fs::read_to_string(temp.path().join("pid")).expect("pid");
+        let alive = || {
+            std::process::Command::new("kill")
+                .args(["-0", pid.trim()])
+                .stderr(std::process::Stdio::null())
+                .status()
+                .expect("kill -0")
+                .success()
+        };
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while alive() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert!(!alive(), "grandchild {} survived the timeout", pid.trim());
+
+        let output = runner
+            .run_untimed("sh", &["-c", "sleep 0.4; echo done"], None)
+            .expect("untimed run");
+        assert_eq!(output.stdout, "done\n");
+    }
 }
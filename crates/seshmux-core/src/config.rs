@@ -1,22 +1,575 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use directories::BaseDirs;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::command_runner::RunPolicy;
+
+pub mod check;
+mod include;
+pub mod migrate;
+
+pub use include::{LoadOptions, PROFILE_ENV};
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct SeshmuxConfig {
     pub version: u32,
+    #[serde(default)]
+    pub editor: Option<Vec<String>>,
     pub tmux: TmuxConfig,
+    #[serde(default)]
+    pub dependency_caches: Option<DependencyCachesConfig>,
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    #[serde(default)]
+    pub ownership: OwnershipConfig,
+    #[serde(default)]
+    pub ui: UiConfig,
+    #[serde(default)]
+    pub theme: ThemeConfig,
+    #[serde(default)]
+    pub dash: DashConfig,
+    #[serde(default)]
+    pub archive: ArchiveConfig,
+    #[serde(default)]
+    pub extras: ExtrasConfig,
+    #[serde(default)]
+    pub checkout: CheckoutConfig,
+    #[serde(default)]
+    pub quick_new: QuickNewConfig,
+    #[serde(default)]
+    pub defaults: DefaultsConfig,
+    #[serde(default)]
+    pub update: UpdateConfig,
+    #[serde(default)]
+    pub layouts: BTreeMap<String, LayoutConfig>,
+    #[serde(default)]
+    pub worktrees_dir: Option<String>,
+    #[serde(default)]
+    pub branch_template: Option<String>,
+    #[serde(default)]
+    pub worktree_path_template: Option<String>,
+    #[serde(default)]
+    pub vcs: crate::vcs::VcsPreference,
+    #[serde(default)]
+    pub command_timeout_secs: Option<u64>,
+    #[serde(default = "default_command_retries")]
+    pub command_retries: u32,
+    #[serde(default)]
+    pub delete_mode: DeleteMode,
+    #[serde(default)]
+    pub trash: TrashConfig,
+    #[serde(default)]
+    pub names: NamesConfig,
+    #[serde(default)]
+    pub gitignore: GitignoreConfig,
+    #[serde(default)]
+    pub projects: BTreeMap<String, ProjectConfig>,
+}
+
+impl SeshmuxConfig {
+    pub fn run_policy(&self) -> RunPolicy {
+        RunPolicy {
+            timeout: self.command_timeout_secs.map(Duration::from_secs),
+            retries: self.command_retries,
+        }
+    }
+}
+
+fn default_command_retries() -> u32 {
+    2
+}
+
+const MAX_COMMAND_RETRIES: u32 = 10;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProjectConfig {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub windows: Option<Vec<WindowSpec>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extras: Vec<String>,
+}
+
+impl ProjectConfig {
+    pub fn session_dir(&self, worktree_path: &Path) -> PathBuf {
+        worktree_path.join(self.path.trim())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct GitignoreConfig {
+    #[serde(default)]
+    pub entries: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NamesConfig {
+    #[serde(default = "default_name_templates")]
+    pub templates: Vec<String>,
+}
+
+impl Default for NamesConfig {
+    fn default() -> Self {
+        Self {
+            templates: default_name_templates(),
+        }
+    }
+}
+
+fn default_name_templates() -> Vec<String> {
+    vec!["{branch}-{date}".to_string()]
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeleteMode {
+    #[default]
+    Remove,
+    Trash,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TrashConfig {
+    #[serde(default = "default_trash_retention_days")]
+    pub retention_days: u64,
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self {
+            retention_days: default_trash_retention_days(),
+        }
+    }
+}
+
+fn default_trash_retention_days() -> u64 {
+    7
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct DashConfig {
+    #[serde(default)]
+    pub repos: Vec<String>,
+    #[serde(default)]
+    pub base_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ArchiveConfig {
+    #[serde(default = "default_archive_idle_days")]
+    pub idle_days: u64,
+    #[serde(default)]
+    pub tar: bool,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            idle_days: default_archive_idle_days(),
+            tar: false,
+        }
+    }
+}
+
+fn default_archive_idle_days() -> u64 {
+    30
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExtrasConfig {
+    #[serde(default = "default_extras_copy_concurrency")]
+    pub copy_concurrency: usize,
+}
+
+impl Default for ExtrasConfig {
+    fn default() -> Self {
+        Self {
+            copy_concurrency: default_extras_copy_concurrency(),
+        }
+    }
+}
+
+fn default_extras_copy_concurrency() -> usize {
+    4
+}
+
+const MAX_EXTRAS_COPY_CONCURRENCY: usize = 64;
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct CheckoutConfig {
+    #[serde(default)]
+    pub lfs: CheckoutStep,
+    #[serde(default)]
+    pub submodules: CheckoutStep,
+    #[serde(default = "default_direnv_step")]
+    pub direnv: CheckoutStep,
+}
+
+impl Default for CheckoutConfig {
+    fn default() -> Self {
+        Self {
+            lfs: CheckoutStep::Auto,
+            submodules: CheckoutStep::Auto,
+            direnv: default_direnv_step(),
+        }
+    }
+}
+
+fn default_direnv_step() -> CheckoutStep {
+    CheckoutStep::Never
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CheckoutStep {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl CheckoutStep {
+    pub fn enabled(self, detected: bool) -> bool {
+        match self {
+            Self::Auto => detected,
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QuickNewConfig {
+    #[serde(default)]
+    pub start_point: Option<String>,
+    #[serde(default)]
+    pub gitignore: bool,
+    #[serde(default)]
+    pub extras: Vec<String>,
+    #[serde(default = "default_quick_new_connect")]
+    pub connect: bool,
+}
+
+impl Default for QuickNewConfig {
+    fn default() -> Self {
+        Self {
+            start_point: None,
+            gitignore: false,
+            extras: Vec::new(),
+            connect: default_quick_new_connect(),
+        }
+    }
+}
+
+fn default_quick_new_connect() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DefaultsConfig {
+    #[serde(default = "default_answer_yes")]
+    pub gitignore: bool,
+    #[serde(default)]
+    pub copy_extras: bool,
+    #[serde(default = "default_answer_yes")]
+    pub connect_now: bool,
+}
+
+impl Default for DefaultsConfig {
+    fn default() -> Self {
+        Self {
+            gitignore: default_answer_yes(),
+            copy_extras: false,
+            connect_now: default_answer_yes(),
+        }
+    }
+}
+
+fn default_answer_yes() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct UpdateConfig {
+    #[serde(default)]
+    pub upstream: Option<String>,
+    #[serde(default)]
+    pub strategy: UpdateStrategy,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UpdateStrategy {
+    #[default]
+    Rebase,
+    Merge,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ThemeConfig {
+    #[serde(default)]
+    pub preset: ThemePreset,
+    #[serde(default)]
+    pub highlight: Option<String>,
+    #[serde(default)]
+    pub header: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub modal_border: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemePreset {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    ColorblindSafe,
+}
+
+const THEME_COLOR_NAMES: &[&str] = &[
+    "reset",
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "gray",
+    "grey",
+    "darkgray",
+    "darkgrey",
+    "lightred",
+    "lightgreen",
+    "lightyellow",
+    "lightblue",
+    "lightmagenta",
+    "lightcyan",
+    "white",
+];
+
+pub fn is_theme_color(value: &str) -> bool {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix('#') {
+        return hex.len() == 6 && hex.chars().all(|character| character.is_ascii_hexdigit());
+    }
+    if value.parse::<u8>().is_ok() {
+        return true;
+    }
+    let name: String = value
+        .chars()
+        .filter(|character| !matches!(character, ' ' | '-' | '_'))
+        .collect::<String>()
+        .to_ascii_lowercase();
+    THEME_COLOR_NAMES.contains(&name.as_str())
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UiConfig {
+    #[serde(default)]
+    pub columns: Option<Vec<WorktreeColumn>>,
+    #[serde(default = "default_ui_tick_ms")]
+    pub tick_ms: u64,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            columns: None,
+            tick_ms: default_ui_tick_ms(),
+        }
+    }
+}
+
+fn default_ui_tick_ms() -> u64 {
+    120
+}
+
+const UI_TICK_MS_RANGE: std::ops::RangeInclusive<u64> = 16..=1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WorktreeColumn {
+    Name,
+    Created,
+    Age,
+    Size,
+    Branch,
+    Session,
+    Path,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct OwnershipConfig {
+    #[serde(default)]
+    pub block_foreign_worktrees: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NotificationsConfig {
+    #[serde(default)]
+    pub desktop: DesktopNotifications,
+    #[serde(default = "default_min_job_seconds")]
+    pub min_job_seconds: u64,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            desktop: DesktopNotifications::default(),
+            min_job_seconds: default_min_job_seconds(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DesktopNotifications {
+    #[default]
+    Off,
+    Osc777,
+    NotifySend,
+}
+
+fn default_min_job_seconds() -> u64 {
+    5
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DependencyCachesConfig {
+    #[serde(default = "default_dependency_caches_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_dependency_cache_dirs")]
+    pub dirs: Vec<String>,
+}
+
+fn default_dependency_caches_enabled() -> bool {
+    true
+}
+
+pub fn default_dependency_cache_dirs() -> Vec<String> {
+    ["node_modules", "target", ".venv"]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+}
+
+pub const REPO_CONFIG_FILE_NAME: &str = ".seshmux.toml";
+pub const DEFAULT_WORKTREES_DIR: &str = "worktrees";
+const WORKTREES_DIR_PLACEHOLDERS: [&str; 1] = ["repo"];
+const BRANCH_TEMPLATE_PLACEHOLDERS: [&str; 1] = ["name"];
+const WORKTREE_PATH_PLACEHOLDERS: [&str; 4] = ["worktrees_dir", "repo", "name", "date"];
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct RepoConfig {
+    pub version: u32,
+    #[serde(default)]
+    pub editor: Option<Vec<String>>,
+    #[serde(default)]
+    pub tmux: Option<TmuxConfig>,
+    #[serde(default)]
+    pub extras: Option<RepoExtrasConfig>,
+    #[serde(default)]
+    pub hooks: Option<RepoHooksConfig>,
+    #[serde(default)]
+    pub projects: BTreeMap<String, ProjectConfig>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct RepoExtrasConfig {
+    #[serde(default)]
+    pub always_skip_buckets: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct RepoHooksConfig {
+    #[serde(default)]
+    pub provision: Vec<String>,
+    #[serde(default)]
+    pub deprovision: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LayoutConfig {
+    pub windows: Vec<LayoutWindow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LayoutWindow {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tmux_layout: Option<String>,
+    #[serde(default)]
+    pub panes: Vec<LayoutPane>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LayoutPane {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct TmuxConfig {
     pub windows: Vec<WindowSpec>,
+    #[serde(default)]
+    pub environment: BTreeMap<String, String>,
+    #[serde(default)]
+    pub layout: SessionLayout,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub socket_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub socket_path: Option<String>,
+    #[serde(default, skip_serializing_if = "SessionBackend::is_tmux")]
+    pub backend: SessionBackend,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SessionLayout {
+    #[default]
+    Session,
+    Window,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SessionBackend {
+    #[default]
+    Tmux,
+    Kitty,
+    Wezterm,
+}
+
+impl SessionBackend {
+    pub fn is_tmux(&self) -> bool {
+        *self == Self::Tmux
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tmux => "tmux",
+            Self::Kitty => "kitty",
+            Self::Wezterm => "wezterm",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct WindowSpec {
     pub name: String,
     pub program: Option<String>,
@@ -72,6 +625,7 @@ pub enum WindowLaunchParseError {
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ConfigError {
     #[error("could not resolve home directory for config path")]
     HomeDirectoryUnavailable,
@@ -89,6 +643,10 @@ pub enum ConfigError {
     },
     #[error("invalid config: {message}")]
     Validation { message: String },
+    #[error("invalid include in {path}: {message}")]
+    Include { path: PathBuf, message: String },
+    #[error("unknown config profile '{name}'; {available}")]
+    UnknownProfile { name: String, available: String },
 }
 
 pub fn resolve_config_path() -> anyhow::Result<PathBuf> {
@@ -100,21 +658,154 @@ pub fn resolve_config_path() -> anyhow::Result<PathBuf> {
         .join("config.toml"))
 }
 
-pub fn load_config(path: &Path) -> Result<SeshmuxConfig, ConfigError> {
+pub fn load_config(path: &Path, options: &LoadOptions) -> Result<SeshmuxConfig, ConfigError> {
+    let parsed = parse_config_file(path, options)?;
+    validate_config(&parsed)?;
+    Ok(parsed)
+}
+
+/// Like `load_config`, but only problems under `fields` (dotted key paths such as
+/// `tmux.layout`) are errors, so a command is not blocked by settings it never reads.
+pub fn load_config_fields(
+    path: &Path,
+    fields: &[&str],
+    options: &LoadOptions,
+) -> Result<SeshmuxConfig, ConfigError> {
+    let parsed = parse_config_file(path, options)?;
+    first_problem(
+        config_problems(&parsed)
+            .into_iter()
+            .filter(|problem| {
+                fields.iter().any(|field| {
+                    problem.path == *field
+                        || problem
+                            .path
+                            .strip_prefix(field)
+                            .is_some_and(|rest| rest.starts_with('.'))
+                })
+            })
+            .collect(),
+    )?;
+    Ok(parsed)
+}
+
+fn parse_config_file(path: &Path, options: &LoadOptions) -> Result<SeshmuxConfig, ConfigError> {
+    let raw = fs::read_to_string(path).map_err(|source| ConfigError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let table = include::layered_table(path, &raw, options.profile.as_deref())?;
+    table.try_into().map_err(|source| ConfigError::Parse {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+pub fn repo_config_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(REPO_CONFIG_FILE_NAME)
+}
+
+pub fn resolve_worktrees_dir(template: Option<&str>, repo_root: &Path) -> PathBuf {
+    let template = template
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(DEFAULT_WORKTREES_DIR);
+    let repo = repo_root
+        .file_name()
+        .and_then(|value| value.to_str())
+        .unwrap_or("repo");
+    let expanded = expand_home(&template.replace("{repo}", repo));
+
+    if expanded.is_absolute() {
+        expanded
+    } else {
+        repo_root.join(expanded)
+    }
+}
+
+pub fn resolve_branch_name(template: Option<&str>, worktree_name: &str) -> String {
+    match template.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(template) => template.replace("{name}", worktree_name),
+        None => worktree_name.to_string(),
+    }
+}
+
+pub fn resolve_worktree_path(
+    template: Option<&str>,
+    worktrees_dir: &Path,
+    repo_root: &Path,
+    worktree_name: &str,
+    date: &str,
+) -> PathBuf {
+    let Some(template) = template.map(str::trim).filter(|value| !value.is_empty()) else {
+        return worktrees_dir.join(worktree_name);
+    };
+    let repo = repo_root
+        .file_name()
+        .and_then(|value| value.to_str())
+        .unwrap_or("repo");
+    let expanded = expand_home(&crate::session_env::fill_placeholders(
+        template,
+        &[
+            ("worktrees_dir", &worktrees_dir.to_string_lossy()),
+            ("repo", repo),
+            ("date", date),
+            ("name", worktree_name),
+        ],
+    ));
+
+    if expanded.is_absolute() {
+        expanded
+    } else {
+        repo_root.join(expanded)
+    }
+}
+
+pub fn expand_home(value: &str) -> PathBuf {
+    match value.strip_prefix("~/") {
+        Some(rest) => match BaseDirs::new() {
+            Some(base_dirs) => base_dirs.home_dir().join(rest),
+            None => PathBuf::from(value),
+        },
+        None => PathBuf::from(value),
+    }
+}
+
+pub fn load_repo_config(path: &Path) -> Result<RepoConfig, ConfigError> {
     let raw = fs::read_to_string(path).map_err(|source| ConfigError::Read {
         path: path.to_path_buf(),
         source,
     })?;
 
-    let parsed: SeshmuxConfig = toml::from_str(&raw).map_err(|source| ConfigError::Parse {
+    let parsed: RepoConfig = toml::from_str(&raw).map_err(|source| ConfigError::Parse {
         path: path.to_path_buf(),
         source,
     })?;
 
-    validate_config(&parsed)?;
+    validate_repo_config(&parsed)?;
     Ok(parsed)
 }
 
+pub fn merge_repo_config(config: &mut SeshmuxConfig, repo_config: &RepoConfig) {
+    if let Some(editor) = &repo_config.editor {
+        config.editor = Some(editor.clone());
+    }
+    if let Some(tmux) = &repo_config.tmux {
+        let socket_name = config.tmux.socket_name.take();
+        let socket_path = config.tmux.socket_path.take();
+        config.tmux = TmuxConfig {
+            socket_name,
+            socket_path,
+            backend: config.tmux.backend,
+            ..tmux.clone()
+        };
+    }
+    if !repo_config.projects.is_empty() {
+        config.projects = repo_config.projects.clone();
+    }
+}
+
 pub fn parse_window_launch(window: &WindowSpec) -> Result<WindowLaunch, WindowLaunchParseError> {
     let direct_mode_selected = window.program.is_some() || window.args.is_some();
     let shell_mode_selected = window.shell.is_some() || window.command.is_some();
@@ -155,62 +846,500 @@ pub fn parse_window_launch(window: &WindowSpec) -> Result<WindowLaunch, WindowLa
 }
 
 pub fn validate_config(config: &SeshmuxConfig) -> Result<(), ConfigError> {
-    if config.version != 1 {
-        return Err(ConfigError::Validation {
-            message: "version must be 1".to_string(),
-        });
-    }
+    first_problem(config_problems(config))
+}
+
+pub fn validate_repo_config(config: &RepoConfig) -> Result<(), ConfigError> {
+    first_problem(repo_config_problems(config))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    pub path: String,
+    pub message: String,
+}
 
-    if config.tmux.windows.is_empty() {
-        return Err(ConfigError::Validation {
-            message: "at least one tmux window must be configured".to_string(),
-        });
+impl ConfigProblem {
+    fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
     }
+}
 
-    for (index, window) in config.tmux.windows.iter().enumerate() {
-        if window.name.trim().is_empty() {
-            return Err(ConfigError::Validation {
-                message: format!("window[{index}] name must be non-empty"),
-            });
+pub fn config_problems(config: &SeshmuxConfig) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+    check_version(config.version, &mut problems);
+    check_editor(config.editor.as_deref(), &mut problems);
+    if let Some(dependency_caches) = &config.dependency_caches {
+        check_dependency_cache_dirs(&dependency_caches.dirs, &mut problems);
+    }
+    if let Some(columns) = &config.ui.columns {
+        check_columns(columns, &mut problems);
+    }
+    if !UI_TICK_MS_RANGE.contains(&config.ui.tick_ms) {
+        problems.push(ConfigProblem::new(
+            "ui.tick_ms",
+            format!(
+                "ui.tick_ms must be between {} and {}",
+                UI_TICK_MS_RANGE.start(),
+                UI_TICK_MS_RANGE.end()
+            ),
+        ));
+    }
+    check_theme(&config.theme, &mut problems);
+    if config
+        .update
+        .upstream
+        .as_deref()
+        .is_some_and(|upstream| upstream.trim().is_empty())
+    {
+        problems.push(ConfigProblem::new(
+            "update.upstream",
+            "update.upstream cannot be empty",
+        ));
+    }
+    for (name, layout) in &config.layouts {
+        check_layout(name, layout, &mut problems);
+    }
+    if let Some(worktrees_dir) = &config.worktrees_dir {
+        check_worktrees_dir(worktrees_dir, &mut problems);
+    }
+    for (index, entry) in config.gitignore.entries.iter().enumerate() {
+        if entry.trim().is_empty() || entry.contains('\n') || entry.trim_start().starts_with('#') {
+            problems.push(ConfigProblem::new(
+                format!("gitignore.entries.{index}"),
+                format!("gitignore.entries[{index}] must be a single non-empty pattern"),
+            ));
         }
+    }
+    if config.archive.idle_days == 0 {
+        problems.push(ConfigProblem::new(
+            "archive.idle_days",
+            "archive.idle_days must be at least 1",
+        ));
+    }
+    if !(1..=MAX_EXTRAS_COPY_CONCURRENCY).contains(&config.extras.copy_concurrency) {
+        problems.push(ConfigProblem::new(
+            "extras.copy_concurrency",
+            format!("extras.copy_concurrency must be between 1 and {MAX_EXTRAS_COPY_CONCURRENCY}"),
+        ));
+    }
+    if let Some(branch_template) = &config.branch_template {
+        check_branch_template(branch_template, &mut problems);
+    }
+    if let Some(template) = &config.worktree_path_template {
+        check_worktree_path_template(template, &mut problems);
+    }
+    if config.command_timeout_secs == Some(0) {
+        problems.push(ConfigProblem::new(
+            "command_timeout_secs",
+            "command_timeout_secs must be at least 1; leave it unset for no timeout",
+        ));
+    }
+    if config.command_retries > MAX_COMMAND_RETRIES {
+        problems.push(ConfigProblem::new(
+            "command_retries",
+            format!("command_retries must be at most {MAX_COMMAND_RETRIES}"),
+        ));
+    }
+    check_quick_new(&config.quick_new, &mut problems);
+    check_tmux(&config.tmux, &mut problems);
+    check_projects(&config.projects, &mut problems);
+    problems
+}
 
-        if let Err(error) = parse_window_launch(window) {
-            let message = match error {
-                WindowLaunchParseError::MixedModes => format!(
-                    "window[{index}] must use exactly one launch mode (direct or shell), not both"
-                ),
-                WindowLaunchParseError::MissingMode => format!(
-                    "window[{index}] must define either direct mode (program/args) or shell mode (shell/command)"
-                ),
-                WindowLaunchParseError::MissingProgram => {
-                    format!("window[{index}] direct mode requires non-empty program")
-                }
-                WindowLaunchParseError::MissingShell => {
-                    format!("window[{index}] shell mode requires shell field")
-                }
-                WindowLaunchParseError::MissingShellExecutable => {
-                    format!("window[{index}] shell mode requires shell[0] executable")
-                }
-                WindowLaunchParseError::MissingCommand => {
-                    format!("window[{index}] shell mode requires non-empty command")
+pub fn repo_config_problems(config: &RepoConfig) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+    check_version(config.version, &mut problems);
+    check_editor(config.editor.as_deref(), &mut problems);
+
+    if let Some(hooks) = &config.hooks {
+        for (key, commands) in [
+            ("provision", &hooks.provision),
+            ("deprovision", &hooks.deprovision),
+        ] {
+            for (index, command) in commands.iter().enumerate() {
+                if command.trim().is_empty() {
+                    problems.push(ConfigProblem::new(
+                        format!("hooks.{key}.{index}"),
+                        format!("hooks.{key}[{index}] must be non-empty"),
+                    ));
                 }
-            };
+            }
+        }
+    }
 
-            return Err(ConfigError::Validation { message });
+    if let Some(tmux) = &config.tmux {
+        check_tmux(tmux, &mut problems);
+        for (key, set) in [
+            ("socket_name", tmux.socket_name.is_some()),
+            ("socket_path", tmux.socket_path.is_some()),
+            ("backend", !tmux.backend.is_tmux()),
+        ] {
+            if set {
+                problems.push(ConfigProblem::new(
+                    format!("tmux.{key}"),
+                    format!("tmux.{key} can only be set in the global config"),
+                ));
+            }
         }
     }
+    check_projects(&config.projects, &mut problems);
+    problems
+}
 
-    Ok(())
+fn first_problem(problems: Vec<ConfigProblem>) -> Result<(), ConfigError> {
+    match problems.into_iter().next() {
+        Some(problem) => Err(ConfigError::Validation {
+            message: problem.message,
+        }),
+        None => Ok(()),
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn check_version(version: u32, problems: &mut Vec<ConfigProblem>) {
+    if version != 1 {
+        problems.push(ConfigProblem::new("version", "version must be 1"));
+    }
+}
+
+fn check_tmux(tmux: &TmuxConfig, problems: &mut Vec<ConfigProblem>) {
+    for (name, template) in &tmux.environment {
+        let path = format!("tmux.environment.{name}");
+        if !crate::session_env::is_valid_variable_name(name) {
+            problems.push(ConfigProblem::new(
+                path,
+                format!("tmux.environment key '{name}' is not a valid variable name"),
+            ));
+        } else if let Some(placeholder) = crate::session_env::unknown_placeholder(template) {
+            problems.push(ConfigProblem::new(
+                path,
+                format!("tmux.environment.{name} uses unknown placeholder '{{{placeholder}}}'"),
+            ));
+        }
+    }
+
+    check_windows("tmux.windows", &tmux.windows, problems);
+
+    if tmux.socket_name.is_some() && tmux.socket_path.is_some() {
+        problems.push(ConfigProblem::new(
+            "tmux.socket_path",
+            "set either tmux.socket_name or tmux.socket_path, not both",
+        ));
+    }
+    for (key, value) in [
+        ("socket_name", &tmux.socket_name),
+        ("socket_path", &tmux.socket_path),
+    ] {
+        if value
+            .as_deref()
+            .is_some_and(|value| value.trim().is_empty())
+        {
+            problems.push(ConfigProblem::new(
+                format!("tmux.{key}"),
+                format!("tmux.{key} must be non-empty"),
+            ));
+        } else if value.is_some() && !tmux.backend.is_tmux() {
+            problems.push(ConfigProblem::new(
+                format!("tmux.{key}"),
+                format!(
+                    "tmux.{key} only applies to the tmux backend, not '{}'",
+                    tmux.backend.as_str()
+                ),
+            ));
+        }
+    }
+}
+
+fn check_editor(editor: Option<&[String]>, problems: &mut Vec<ConfigProblem>) {
+    if let Some(editor) = editor
+        && editor
+            .first()
+            .is_none_or(|program| program.trim().is_empty())
+    {
+        problems.push(ConfigProblem::new(
+            "editor",
+            "editor[0] must be a non-empty executable",
+        ));
+    }
+}
+
+fn check_dependency_cache_dirs(dirs: &[String], problems: &mut Vec<ConfigProblem>) {
+    for (index, dir) in dirs.iter().enumerate() {
+        let path = Path::new(dir.trim());
+        let is_plain_relative = !dir.trim().is_empty()
+            && path
+                .components()
+                .all(|component| matches!(component, std::path::Component::Normal(_)));
+        if !is_plain_relative || path.starts_with("worktrees") {
+            problems.push(ConfigProblem::new(
+                format!("dependency_caches.dirs.{index}"),
+                format!(
+                    "dependency_caches.dirs[{index}] must be a relative path inside the repository"
+                ),
+            ));
+        }
+    }
+}
+
+fn check_layout(name: &str, layout: &LayoutConfig, problems: &mut Vec<ConfigProblem>) {
+    let path = format!("layouts.{name}");
+    if layout.windows.is_empty() {
+        problems.push(ConfigProblem::new(
+            format!("{path}.windows"),
+            format!("layout '{name}' must have at least one window"),
+        ));
+    }
+    for (index, window) in layout.windows.iter().enumerate() {
+        if window.name.trim().is_empty() {
+            problems.push(ConfigProblem::new(
+                format!("{path}.windows.{index}.name"),
+                format!("layout '{name}' window[{index}] name must be non-empty"),
+            ));
+        }
+    }
+}
+
+fn check_worktrees_dir(template: &str, problems: &mut Vec<ConfigProblem>) {
+    if template.trim().is_empty() {
+        problems.push(ConfigProblem::new(
+            "worktrees_dir",
+            "worktrees_dir must be non-empty",
+        ));
+        return;
+    }
+
+    if let Some(placeholder) = unknown_placeholder(template, &WORKTREES_DIR_PLACEHOLDERS) {
+        problems.push(ConfigProblem::new(
+            "worktrees_dir",
+            format!("worktrees_dir uses unknown placeholder '{{{placeholder}}}'"),
+        ));
+    }
+
+    let trimmed = template.trim();
+    let leaves_repo = trimmed.starts_with("~/")
+        || Path::new(trimmed).is_absolute()
+        || Path::new(trimmed)
+            .components()
+            .any(|component| component == std::path::Component::ParentDir);
+    if leaves_repo && !template.contains("{repo}") {
+        problems.push(ConfigProblem::new(
+            "worktrees_dir",
+            "worktrees_dir outside the repository must contain '{repo}' so each repository keeps its own registry",
+        ));
+    }
+}
+
+fn check_quick_new(quick_new: &QuickNewConfig, problems: &mut Vec<ConfigProblem>) {
+    if quick_new
+        .start_point
+        .as_ref()
+        .is_some_and(|start_point| start_point.trim().is_empty())
+    {
+        problems.push(ConfigProblem::new(
+            "quick_new.start_point",
+            "quick_new.start_point must be non-empty when set",
+        ));
+    }
+
+    for (index, pattern) in quick_new.extras.iter().enumerate() {
+        if pattern.trim().is_empty() {
+            problems.push(ConfigProblem::new(
+                format!("quick_new.extras.{index}"),
+                format!("quick_new.extras[{index}] must be non-empty"),
+            ));
+        }
+    }
+}
+
+fn check_projects(projects: &BTreeMap<String, ProjectConfig>, problems: &mut Vec<ConfigProblem>) {
+    for (name, project) in projects {
+        let path = format!("projects.{name}");
+        if name.trim().is_empty() {
+            problems.push(ConfigProblem::new(
+                path.clone(),
+                "project names must be non-empty",
+            ));
+        }
+
+        let dir = Path::new(project.path.trim());
+        let is_plain_relative = !project.path.trim().is_empty()
+            && dir
+                .components()
+                .all(|component| matches!(component, std::path::Component::Normal(_)));
+        if !is_plain_relative {
+            problems.push(ConfigProblem::new(
+                format!("{path}.path"),
+                format!("projects.{name}.path must be a relative path inside the repository"),
+            ));
+        }
+
+        if let Some(windows) = &project.windows {
+            check_windows(&format!("{path}.windows"), windows, problems);
+        }
+
+        for (index, pattern) in project.extras.iter().enumerate() {
+            if pattern.trim().is_empty() {
+                problems.push(ConfigProblem::new(
+                    format!("{path}.extras.{index}"),
+                    format!("projects.{name}.extras[{index}] must be non-empty"),
+                ));
+            }
+        }
+    }
+}
+
+fn check_branch_template(template: &str, problems: &mut Vec<ConfigProblem>) {
+    if !template.contains("{name}") {
+        problems.push(ConfigProblem::new(
+            "branch_template",
+            "branch_template must contain '{name}'",
+        ));
+        return;
+    }
+
+    if let Some(placeholder) = unknown_placeholder(template, &BRANCH_TEMPLATE_PLACEHOLDERS) {
+        problems.push(ConfigProblem::new(
+            "branch_template",
+            format!("branch_template uses unknown placeholder '{{{placeholder}}}'"),
+        ));
+        return;
+    }
+
+    // Worktree names are always valid in a ref, so checking one sample covers every expansion.
+    if let Err(error) =
+        crate::names::validate_branch_name(&resolve_branch_name(Some(template), "name"))
+    {
+        problems.push(ConfigProblem::new(
+            "branch_template",
+            format!("branch_template does not produce a valid branch: {error}"),
+        ));
+    }
+}
+
+fn check_worktree_path_template(template: &str, problems: &mut Vec<ConfigProblem>) {
+    if !template.contains("{name}") {
+        problems.push(ConfigProblem::new(
+            "worktree_path_template",
+            "worktree_path_template must contain '{name}'",
+        ));
+        return;
+    }
+
+    if let Some(placeholder) = unknown_placeholder(template, &WORKTREE_PATH_PLACEHOLDERS) {
+        problems.push(ConfigProblem::new(
+            "worktree_path_template",
+            format!("worktree_path_template uses unknown placeholder '{{{placeholder}}}'"),
+        ));
+    }
+}
+
+fn unknown_placeholder<'t>(template: &'t str, known: &[&str]) -> Option<&'t str> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let end = after.find('}')?;
+        let placeholder = &after[..end];
+        if !known.contains(&placeholder) {
+            return Some(placeholder);
+        }
+        rest = &after[end + 1..];
+    }
+    None
+}
+
+fn check_columns(columns: &[WorktreeColumn], problems: &mut Vec<ConfigProblem>) {
+    if columns.is_empty() {
+        problems.push(ConfigProblem::new(
+            "ui.columns",
+            "ui.columns must list at least one column",
+        ));
+    }
+
+    for (index, column) in columns.iter().enumerate() {
+        if columns[..index].contains(column) {
+            problems.push(ConfigProblem::new(
+                format!("ui.columns.{index}"),
+                format!("ui.columns[{index}] repeats an earlier column"),
+            ));
+        }
+    }
+}
+
+fn check_theme(theme: &ThemeConfig, problems: &mut Vec<ConfigProblem>) {
+    let overrides = [
+        ("highlight", &theme.highlight),
+        ("header", &theme.header),
+        ("error", &theme.error),
+        ("modal_border", &theme.modal_border),
+    ];
+    for (key, value) in overrides {
+        if let Some(value) = value
+            && !is_theme_color(value)
+        {
+            problems.push(ConfigProblem::new(
+                format!("theme.{key}"),
+                format!("theme.{key} '{value}' is not a color name, 0-255 index, or #rrggbb value"),
+            ));
+        }
+    }
+}
+
+fn check_windows(key: &str, windows: &[WindowSpec], problems: &mut Vec<ConfigProblem>) {
+    if windows.is_empty() {
+        problems.push(ConfigProblem::new(
+            key,
+            "at least one tmux window must be configured",
+        ));
+    }
+
+    for (index, window) in windows.iter().enumerate() {
+        let path = format!("{key}.{index}");
+        if window.name.trim().is_empty() {
+            problems.push(ConfigProblem::new(
+                format!("{path}.name"),
+                format!("window[{index}] name must be non-empty"),
+            ));
+            continue;
+        }
+
+        if let Err(error) = parse_window_launch(window) {
+            let message = match error {
+                WindowLaunchParseError::MixedModes => format!(
+                    "window[{index}] must use exactly one launch mode (direct or shell), not both"
+                ),
+                WindowLaunchParseError::MissingMode => format!(
+                    "window[{index}] must define either direct mode (program/args) or shell mode (shell/command)"
+                ),
+                WindowLaunchParseError::MissingProgram => {
+                    format!("window[{index}] direct mode requires non-empty program")
+                }
+                WindowLaunchParseError::MissingShell => {
+                    format!("window[{index}] shell mode requires shell field")
+                }
+                WindowLaunchParseError::MissingShellExecutable => {
+                    format!("window[{index}] shell mode requires shell[0] executable")
+                }
+                WindowLaunchParseError::MissingCommand => {
+                    format!("window[{index}] shell mode requires non-empty command")
+                }
+            };
+
+            problems.push(ConfigProblem::new(path, message));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     fn load_config_from_toml(raw: &str) -> Result<SeshmuxConfig, ConfigError> {
         let file = tempfile::NamedTempFile::new().expect("temp file");
         fs::write(file.path(), raw).expect("write temp config");
-        load_config(file.path())
+        load_config(file.path(), &LoadOptions::default())
     }
 
     #[test]
@@ -243,6 +1372,96 @@ command = "echo ready"
         assert_eq!(config.tmux.windows.len(), 1);
     }
 
+    #[test]
+    fn tmux_layout_defaults_to_session_per_worktree() {
+        let windows = r#"
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let config = load_config_from_toml(&format!("version = 1\n{windows}")).expect("config");
+        assert_eq!(config.tmux.layout, SessionLayout::Session);
+
+        let config = load_config_from_toml(&format!(
+            "version = 1\n\n[tmux]\nlayout = \"window\"\n{windows}"
+        ))
+        .expect("config");
+        assert_eq!(config.tmux.layout, SessionLayout::Window);
+    }
+
+    #[test]
+    fn checkout_direnv_defaults_to_never_and_keeps_other_steps_on_auto() {
+        let windows = r#"
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let config = load_config_from_toml(&format!("version = 1\n{windows}")).expect("config");
+        assert_eq!(config.checkout.direnv, CheckoutStep::Never);
+
+        let config = load_config_from_toml(&format!(
+            "version = 1\n\n[checkout]\ndirenv = \"auto\"\n{windows}"
+        ))
+        .expect("config");
+        assert_eq!(config.checkout.direnv, CheckoutStep::Auto);
+        assert_eq!(config.checkout.lfs, CheckoutStep::Auto);
+    }
+
+    #[test]
+    fn defaults_keep_the_built_in_answers_for_unset_keys() {
+        let windows = r#"
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let config = load_config_from_toml(&format!("version = 1\n{windows}")).expect("config");
+        assert_eq!(config.defaults, DefaultsConfig::default());
+
+        let config = load_config_from_toml(&format!(
+            "version = 1\n\n[defaults]\ncopy_extras = true\nconnect_now = false\n{windows}"
+        ))
+        .expect("config");
+        assert_eq!(
+            config.defaults,
+            DefaultsConfig {
+                gitignore: true,
+                copy_extras: true,
+                connect_now: false,
+            }
+        );
+    }
+
+    #[test]
+    fn tmux_socket_accepts_one_of_name_or_path() {
+        let windows = r#"
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let config = load_config_from_toml(&format!(
+            "version = 1\n\n[tmux]\nsocket_name = \"seshmux\"\n{windows}"
+        ))
+        .expect("config");
+        assert_eq!(config.tmux.socket_name.as_deref(), Some("seshmux"));
+        assert_eq!(config.tmux.socket_path, None);
+
+        let error = load_config_from_toml(&format!(
+            "version = 1\n\n[tmux]\nsocket_name = \"seshmux\"\nsocket_path = \"/tmp/seshmux.sock\"\n{windows}"
+        ))
+        .expect_err("both sockets");
+        assert!(error.to_string().contains("not both"));
+
+        let error = load_config_from_toml(&format!(
+            "version = 1\n\n[tmux]\nsocket_path = \" \"\n{windows}"
+        ))
+        .expect_err("empty socket");
+        assert!(
+            error
+                .to_string()
+                .contains("tmux.socket_path must be non-empty")
+        );
+    }
+
     #[test]
     fn rejects_window_with_both_launch_modes() {
         let raw = r#"
@@ -299,4 +1518,608 @@ windows = []
         let error = load_config_from_toml(raw).expect_err("config should fail");
         assert!(error.to_string().contains("at least one tmux window"));
     }
+
+    #[test]
+    fn repo_config_windows_replace_global_windows() {
+        let mut config = load_config_from_toml(
+            r#"
+version = 1
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#,
+        )
+        .expect("valid config");
+
+        let file = tempfile::NamedTempFile::new().expect("temp file");
+        fs::write(
+            file.path(),
+            r#"
+version = 1
+
+[[tmux.windows]]
+name = "dev"
+shell = ["/bin/sh", "-lc"]
+command = "cargo build"
+
+[extras]
+always_skip_buckets = ["target"]
+"#,
+        )
+        .expect("write repo config");
+        let repo_config = load_repo_config(file.path()).expect("valid repo config");
+
+        config.tmux.socket_name = Some("seshmux".to_string());
+        config.tmux.backend = SessionBackend::Wezterm;
+        merge_repo_config(&mut config, &repo_config);
+        assert_eq!(config.tmux.windows.len(), 1);
+        assert_eq!(config.tmux.windows[0].name, "dev");
+        assert_eq!(config.tmux.socket_name.as_deref(), Some("seshmux"));
+        assert_eq!(config.tmux.backend, SessionBackend::Wezterm);
+        assert_eq!(
+            repo_config.extras.expect("extras").always_skip_buckets,
+            vec!["target".to_string()]
+        );
+    }
+
+    #[test]
+    fn repo_projects_replace_global_projects_and_must_stay_inside_the_repo() {
+        let mut config = load_config_from_toml(
+            r#"
+version = 1
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+
+[projects.docs]
+path = "docs"
+"#,
+        )
+        .expect("valid config");
+
+        let file = tempfile::NamedTempFile::new().expect("temp file");
+        fs::write(
+            file.path(),
+            r#"
+version = 1
+
+[projects.api]
+path = "services/api"
+extras = [".env*"]
+
+[[projects.api.windows]]
+name = "server"
+program = "cargo"
+args = ["run"]
+"#,
+        )
+        .expect("write repo config");
+        let repo_config = load_repo_config(file.path()).expect("valid repo config");
+        merge_repo_config(&mut config, &repo_config);
+
+        let names: Vec<_> = config.projects.keys().map(String::as_str).collect();
+        assert_eq!(names, vec!["api"]);
+        let api = &config.projects["api"];
+        assert_eq!(api.extras, vec![".env*".to_string()]);
+        assert_eq!(api.windows.as_ref().expect("windows")[0].name, "server");
+        assert_eq!(
+            api.session_dir(Path::new("/tmp/worktrees/w1")),
+            PathBuf::from("/tmp/worktrees/w1/services/api")
+        );
+
+        for (path, windows) in [("../api", ""), ("/srv/api", ""), ("api", "windows = []")] {
+            let error = load_config_from_toml(&format!(
+                "version = 1\n[[tmux.windows]]\nname = \"editor\"\nprogram = \"nvim\"\n[projects.api]\npath = \"{path}\"\n{windows}\n"
+            ))
+            .expect_err("invalid project");
+            let expected = if windows.is_empty() {
+                "projects.api.path must be a relative path inside the repository"
+            } else {
+                "at least one tmux window must be configured"
+            };
+            assert!(error.to_string().contains(expected), "{error}");
+        }
+    }
+
+    #[test]
+    fn tmux_backend_defaults_to_tmux_and_excludes_sockets() {
+        let windows = r#"
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let config = load_config_from_toml(&format!("version = 1\n{windows}")).expect("config");
+        assert_eq!(config.tmux.backend, SessionBackend::Tmux);
+
+        let config = load_config_from_toml(&format!(
+            "version = 1\n\n[tmux]\nbackend = \"kitty\"\n{windows}"
+        ))
+        .expect("kitty config");
+        assert_eq!(config.tmux.backend, SessionBackend::Kitty);
+
+        let error = load_config_from_toml(&format!(
+            "version = 1\n\n[tmux]\nbackend = \"wezterm\"\nsocket_name = \"seshmux\"\n{windows}"
+        ))
+        .expect_err("socket with wezterm");
+        assert!(
+            error
+                .to_string()
+                .contains("tmux.socket_name only applies to the tmux backend, not 'wezterm'")
+        );
+        assert!(
+            load_config_from_toml(&format!(
+                "version = 1\n\n[tmux]\nbackend = \"screen\"\n{windows}"
+            ))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_empty_editor_command() {
+        let raw = r#"
+version = 1
+editor = []
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+
+        let error = load_config_from_toml(raw).expect_err("config should fail");
+        assert!(error.to_string().contains("editor[0]"));
+    }
+
+    #[test]
+    fn rejects_blank_or_multiline_gitignore_entries() {
+        let raw = r#"
+version = 1
+
+[gitignore]
+entries = [".direnv/", "  ", "a\nb"]
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+
+        let config: SeshmuxConfig = toml::from_str(raw).expect("parse");
+        let paths: Vec<_> = config_problems(&config)
+            .into_iter()
+            .map(|problem| problem.path)
+            .collect();
+        assert_eq!(paths, vec!["gitignore.entries.1", "gitignore.entries.2"]);
+    }
+
+    #[test]
+    fn repo_config_without_tmux_section_is_valid() {
+        let file = tempfile::NamedTempFile::new().expect("temp file");
+        fs::write(file.path(), "version = 1\n").expect("write repo config");
+
+        let repo_config = load_repo_config(file.path()).expect("valid repo config");
+        assert!(repo_config.tmux.is_none());
+    }
+
+    #[test]
+    fn repo_hooks_reject_empty_provision_commands() {
+        let file = tempfile::NamedTempFile::new().expect("temp file");
+        fs::write(
+            file.path(),
+            "version = 1\n\n[hooks]\nprovision = [\"npm ci\", \" \"]\n",
+        )
+        .expect("write repo config");
+
+        let error = load_repo_config(file.path()).expect_err("config should fail");
+        assert!(
+            error
+                .to_string()
+                .contains("hooks.provision[1] must be non-empty")
+        );
+    }
+
+    #[test]
+    fn dependency_caches_default_dirs_and_reject_escaping_paths() {
+        let raw = r#"
+version = 1
+
+[dependency_caches]
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+
+        let config = load_config_from_toml(raw).expect("valid config");
+        let caches = config.dependency_caches.expect("dependency caches");
+        assert!(caches.enabled);
+        assert_eq!(caches.dirs, default_dependency_cache_dirs());
+
+        let raw = r#"
+version = 1
+
+[dependency_caches]
+dirs = ["../shared/node_modules"]
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+
+        let error = load_config_from_toml(raw).expect_err("config should fail");
+        assert!(error.to_string().contains("dependency_caches.dirs[0]"));
+    }
+
+    #[test]
+    fn notifications_default_off_and_parse_desktop_mode() {
+        let raw = r#"
+version = 1
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let config = load_config_from_toml(raw).expect("valid config");
+        assert_eq!(config.notifications.desktop, DesktopNotifications::Off);
+        assert_eq!(config.notifications.min_job_seconds, 5);
+
+        let raw = r#"
+version = 1
+
+[notifications]
+desktop = "notify-send"
+min_job_seconds = 0
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let config = load_config_from_toml(raw).expect("valid config");
+        assert_eq!(
+            config.notifications.desktop,
+            DesktopNotifications::NotifySend
+        );
+        assert_eq!(config.notifications.min_job_seconds, 0);
+    }
+
+    #[test]
+    fn tmux_environment_rejects_unknown_placeholders() {
+        let raw = r#"
+version = 1
+
+[tmux.environment]
+DATABASE_URL = "postgres://localhost/{name}"
+PORT = "{port}"
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let config = load_config_from_toml(raw).expect("valid config");
+        assert_eq!(config.tmux.environment.len(), 2);
+
+        let raw = r#"
+version = 1
+
+[tmux.environment]
+OWNER = "{user}"
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let error = load_config_from_toml(raw).expect_err("config should fail");
+        assert!(error.to_string().contains("unknown placeholder '{user}'"));
+    }
+
+    #[test]
+    fn ui_columns_parse_and_reject_duplicates() {
+        let raw = r#"
+version = 1
+
+[ui]
+columns = ["name", "session", "path"]
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let config = load_config_from_toml(raw).expect("valid config");
+        assert_eq!(
+            config.ui.columns,
+            Some(vec![
+                WorktreeColumn::Name,
+                WorktreeColumn::Session,
+                WorktreeColumn::Path
+            ])
+        );
+
+        let raw = r#"
+version = 1
+
+[ui]
+columns = ["name", "name"]
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let error = load_config_from_toml(raw).expect_err("config should fail");
+        assert!(error.to_string().contains("ui.columns[1]"));
+    }
+
+    #[test]
+    fn theme_parses_presets_and_rejects_unknown_colors() {
+        let raw = r##"
+version = 1
+
+[theme]
+preset = "colorblind-safe"
+highlight = "#0072b2"
+header = "light-blue"
+modal_border = "244"
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"##;
+        let config = load_config_from_toml(raw).expect("valid config");
+        assert_eq!(config.theme.preset, ThemePreset::ColorblindSafe);
+        assert_eq!(config.theme.highlight.as_deref(), Some("#0072b2"));
+        assert_eq!(config.theme.error, None);
+
+        let raw = r#"
+version = 1
+
+[theme]
+error = "crimson"
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let error = load_config_from_toml(raw).expect_err("config should fail");
+        assert!(
+            error
+                .to_string()
+                .contains("theme.error 'crimson' is not a color name")
+        );
+    }
+
+    #[test]
+    fn command_timeout_and_retries_become_the_run_policy() {
+        let raw = r#"
+version = 1
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let config = load_config_from_toml(raw).expect("valid config");
+        assert_eq!(
+            config.run_policy(),
+            RunPolicy {
+                timeout: None,
+                retries: 2,
+            }
+        );
+
+        let raw = r#"
+version = 1
+command_timeout_secs = 30
+command_retries = 0
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let config = load_config_from_toml(raw).expect("valid config");
+        assert_eq!(
+            config.run_policy(),
+            RunPolicy {
+                timeout: Some(Duration::from_secs(30)),
+                retries: 0,
+            }
+        );
+
+        let raw = r#"
+version = 1
+command_timeout_secs = 0
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let error = load_config_from_toml(raw).expect_err("config should fail");
+        assert!(
+            error
+                .to_string()
+                .contains("command_timeout_secs must be at least 1")
+        );
+    }
+
+    #[test]
+    fn ui_tick_ms_defaults_and_rejects_out_of_range_values() {
+        let raw = r#"
+version = 1
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let config = load_config_from_toml(raw).expect("valid config");
+        assert_eq!(config.ui.tick_ms, 120);
+
+        let raw = r#"
+version = 1
+
+[ui]
+tick_ms = 5
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let error = load_config_from_toml(raw).expect_err("config should fail");
+        assert!(
+            error
+                .to_string()
+                .contains("ui.tick_ms must be between 16 and 1000")
+        );
+    }
+
+    #[test]
+    fn worktrees_dir_resolves_templates_and_rejects_unknown_placeholders() {
+        let repo_root = Path::new("/code/shop");
+        assert_eq!(
+            resolve_worktrees_dir(None, repo_root),
+            PathBuf::from("/code/shop/worktrees")
+        );
+        assert_eq!(
+            resolve_worktrees_dir(Some(".trees"), repo_root),
+            PathBuf::from("/code/shop/.trees")
+        );
+        assert_eq!(
+            resolve_worktrees_dir(Some("/mnt/fast/{repo}-worktrees"), repo_root),
+            PathBuf::from("/mnt/fast/shop-worktrees")
+        );
+
+        let raw = r#"
+version = 1
+worktrees_dir = "/mnt/fast/{name}"
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+        let error = load_config_from_toml(raw).expect_err("config should fail");
+        assert!(error.to_string().contains("unknown placeholder '{name}'"));
+
+        for template in ["/mnt/fast/worktrees", "~/worktrees", "../worktrees"] {
+            let raw = format!(
+                r#"
+version = 1
+worktrees_dir = "{template}"
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#
+            );
+            let error = load_config_from_toml(&raw).expect_err("config should fail");
+            assert!(
+                error.to_string().contains("must contain '{repo}'"),
+                "{template}: {error}"
+            );
+        }
+    }
+
+    #[test]
+    fn branch_template_expands_the_worktree_name_and_must_form_a_ref() {
+        assert_eq!(resolve_branch_name(None, "login"), "login");
+        assert_eq!(
+            resolve_branch_name(Some("feature/{name}"), "login"),
+            "feature/login"
+        );
+
+        for (template, expected) in [
+            ("feature/", "must contain '{name}'"),
+            ("{repo}/{name}", "unknown placeholder '{repo}'"),
+            ("wip {name}", "does not produce a valid branch"),
+        ] {
+            let raw = format!(
+                r#"
+version = 1
+branch_template = "{template}"
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#
+            );
+            let error = load_config_from_toml(&raw).expect_err("config should fail");
+            assert!(error.to_string().contains(expected), "{template}: {error}");
+        }
+    }
+
+    #[test]
+    fn worktree_path_template_expands_placeholders_and_must_name_the_worktree() {
+        let repo_root = Path::new("/code/shop");
+        let worktrees_dir = Path::new("/code/shop/worktrees");
+        assert_eq!(
+            resolve_worktree_path(None, worktrees_dir, repo_root, "login", "20260301"),
+            PathBuf::from("/code/shop/worktrees/login")
+        );
+        assert_eq!(
+            resolve_worktree_path(
+                Some("{worktrees_dir}/{date}-{name}"),
+                worktrees_dir,
+                repo_root,
+                "login",
+                "20260301"
+            ),
+            PathBuf::from("/code/shop/worktrees/20260301-login")
+        );
+        assert_eq!(
+            resolve_worktree_path(
+                Some("../{repo}-{name}"),
+                worktrees_dir,
+                repo_root,
+                "login",
+                "20260301"
+            ),
+            PathBuf::from("/code/shop/../shop-login")
+        );
+
+        for (template, expected) in [
+            ("{worktrees_dir}/{date}", "must contain '{name}'"),
+            (
+                "{worktrees_dir}/{ticket}-{name}",
+                "unknown placeholder '{ticket}'",
+            ),
+        ] {
+            let raw = format!(
+                r#"
+version = 1
+worktree_path_template = "{template}"
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#
+            );
+            let error = load_config_from_toml(&raw).expect_err("config should fail");
+            assert!(error.to_string().contains(expected), "{template}: {error}");
+        }
+    }
+
+    #[test]
+    fn extras_copy_concurrency_defaults_and_is_bounded() {
+        let window = "\n[[tmux.windows]]\nname = \"editor\"\nprogram = \"nvim\"\n";
+        let config = load_config_from_toml(&format!("version = 1\n{window}")).expect("config");
+        assert_eq!(config.extras.copy_concurrency, 4);
+
+        for value in [0, 65] {
+            let raw = format!("version = 1\n\n[extras]\ncopy_concurrency = {value}\n{window}");
+            let error = load_config_from_toml(&raw).expect_err("config should fail");
+            assert!(error.to_string().contains("extras.copy_concurrency"));
+        }
+    }
+
+    #[test]
+    fn quick_new_defaults_to_connecting_and_rejects_blank_values() {
+        let window = "\n[[tmux.windows]]\nname = \"editor\"\nprogram = \"nvim\"\n";
+        let config = load_config_from_toml(&format!("version = 1\n{window}")).expect("config");
+        assert!(config.quick_new.connect);
+        assert!(config.quick_new.start_point.is_none());
+
+        for (section, expected) in [
+            ("start_point = \" \"", "quick_new.start_point"),
+            ("extras = [\".env\", \"\"]", "quick_new.extras[1]"),
+        ] {
+            let raw = format!("version = 1\n\n[quick_new]\n{section}\n{window}");
+            let error = load_config_from_toml(&raw).expect_err("config should fail");
+            assert!(error.to_string().contains(expected), "{section}: {error}");
+        }
+    }
 }
@@ -0,0 +1,220 @@
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use toml::de::{DeTable, DeValue};
+
+use super::{
+    ConfigProblem, LoadOptions, RepoConfig, SeshmuxConfig, config_problems, repo_config_problems,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    pub message: String,
+    pub line: Option<usize>,
+    pub source_line: Option<String>,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(line) = self.line else {
+            return write!(f, "{}", self.message);
+        };
+
+        write!(f, "line {line}: {}", self.message)?;
+        if let Some(source_line) = &self.source_line {
+            write!(f, "\n  {line} | {source_line}")?;
+        }
+        Ok(())
+    }
+}
+
+pub fn check_config_source(raw: &str) -> Vec<ConfigIssue> {
+    check_source::<SeshmuxConfig>(raw, config_problems)
+}
+
+pub fn check_repo_config_source(raw: &str) -> Vec<ConfigIssue> {
+    check_source::<RepoConfig>(raw, repo_config_problems)
+}
+
+pub fn check_config_file(path: &Path, raw: &str, options: &LoadOptions) -> Vec<ConfigIssue> {
+    let document = match parse_document(raw) {
+        Ok(document) => document,
+        Err(issues) => return issues,
+    };
+
+    let table = match super::include::layered_table(path, raw, options.profile.as_deref()) {
+        Ok(table) => table,
+        Err(error) => return vec![issue_at(raw, &error.to_string(), None)],
+    };
+    let config: SeshmuxConfig = match table.try_into() {
+        Ok(config) => config,
+        Err(error) => return vec![issue_at(raw, error.message(), None)],
+    };
+
+    problem_issues(raw, document, &config_problems(&config))
+}
+
+fn check_source<T: DeserializeOwned>(
+    raw: &str,
+    problems: fn(&T) -> Vec<ConfigProblem>,
+) -> Vec<ConfigIssue> {
+    let document = match parse_document(raw) {
+        Ok(document) => document,
+        Err(issues) => return issues,
+    };
+
+    let config: T = match toml::from_str(raw) {
+        Ok(config) => config,
+        Err(error) => return vec![issue_at(raw, error.message(), error.span())],
+    };
+
+    problem_issues(raw, document, &problems(&config))
+}
+
+fn parse_document(raw: &str) -> Result<DeTable<'_>, Vec<ConfigIssue>> {
+    let (document, errors) = DeTable::parse_recoverable(raw);
+    if errors.is_empty() {
+        return Ok(document.into_inner());
+    }
+
+    let mut issues: Vec<_> = errors
+        .iter()
+        .map(|error| issue_at(raw, error.message(), error.span()))
+        .collect();
+    issues.sort_by_key(|issue| issue.line);
+    Err(issues)
+}
+
+fn problem_issues(
+    raw: &str,
+    document: DeTable<'_>,
+    problems: &[ConfigProblem],
+) -> Vec<ConfigIssue> {
+    let root = DeValue::Table(document);
+    problems
+        .iter()
+        .map(|problem| issue_at(raw, &problem.message, locate(&root, &problem.path)))
+        .collect()
+}
+
+fn locate(root: &DeValue<'_>, path: &str) -> Option<Range<usize>> {
+    let mut value = root;
+    let mut span = None;
+
+    for segment in path.split('.') {
+        let next = segment
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| value.get(index))
+            .or_else(|| value.get(segment));
+        let Some(next) = next else {
+            break;
+        };
+        span = Some(next.span());
+        value = next.get_ref();
+    }
+
+    span
+}
+
+fn issue_at(raw: &str, message: &str, span: Option<Range<usize>>) -> ConfigIssue {
+    let line = span
+        .and_then(|span| raw.get(..span.start))
+        .map(|before| before.matches('\n').count() + 1);
+    let source_line = line
+        .and_then(|line| raw.lines().nth(line - 1))
+        .map(|source| source.trim_end().to_string());
+
+    ConfigIssue {
+        message: message.trim().to_string(),
+        line,
+        source_line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_config_file, check_config_source, check_repo_config_source};
+    use crate::config::LoadOptions;
+
+    #[test]
+    fn valid_config_has_no_issues() {
+        let raw = r#"
+version = 1
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#;
+
+        assert!(check_config_source(raw).is_empty());
+    }
+
+    #[test]
+    fn reports_every_validation_problem_with_its_line() {
+        let raw = r#"version = 1
+editor = [""]
+
+[ui]
+columns = ["name", "name"]
+
+[[tmux.windows]]
+name = "editor"
+
+[[tmux.windows]]
+name = "git"
+program = "lazygit"
+command = "lazygit"
+"#;
+
+        let issues = check_config_source(raw);
+        let lines: Vec<_> = issues.iter().map(|issue| issue.line).collect();
+
+        assert_eq!(issues.len(), 4);
+        assert_eq!(lines, vec![Some(2), Some(5), Some(7), Some(10)]);
+        assert!(issues[2].message.starts_with("window[0] must define"));
+        assert_eq!(
+            issues[1].to_string(),
+            "line 5: ui.columns[1] repeats an earlier column\n  5 | columns = [\"name\", \"name\"]"
+        );
+    }
+
+    #[test]
+    fn config_file_is_checked_with_its_includes() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        std::fs::write(
+            temp.path().join("windows.toml"),
+            "[[tmux.windows]]\nname = \"editor\"\nprogram = \"nvim\"\n",
+        )
+        .expect("write include");
+        let path = temp.path().join("config.toml");
+        let raw =
+            "version = 1\ninclude = [\"windows.toml\"]\n\n[ui]\ncolumns = [\"name\", \"name\"]\n";
+
+        let issues = check_config_file(&path, raw, &LoadOptions::default());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(5));
+
+        let missing = check_config_file(
+            &path,
+            "version = 1\ninclude = [\"absent.toml\"]\n",
+            &LoadOptions::default(),
+        );
+        assert_eq!(missing.len(), 1);
+        assert!(missing[0].message.contains("absent.toml"));
+    }
+
+    #[test]
+    fn reports_syntax_and_type_errors_with_line_context() {
+        let syntax = check_config_source("version = 1\nname = \n[tmux\n");
+        assert!(syntax.len() >= 2);
+        assert_eq!(syntax[0].line, Some(2));
+
+        let typed = check_repo_config_source("version = \"one\"\n");
+        assert_eq!(typed.len(), 1);
+        assert_eq!(typed[0].line, Some(1));
+        assert_eq!(typed[0].source_line.as_deref(), Some("version = \"one\""));
+    }
+}
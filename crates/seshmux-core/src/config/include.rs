@@ -0,0 +1,237 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use toml::{Table, Value};
+
+use super::{ConfigError, expand_home};
+
+pub const PROFILE_ENV: &str = "SESHMUX_PROFILE";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadOptions {
+    pub profile: Option<String>,
+}
+
+impl LoadOptions {
+    pub fn resolve(profile: Option<String>) -> Self {
+        let profile = profile
+            .or_else(|| std::env::var(PROFILE_ENV).ok())
+            .map(|profile| profile.trim().to_string())
+            .filter(|profile| !profile.is_empty());
+        Self { profile }
+    }
+}
+
+// Merges the files named by `include` (in order, each overriding the ones before it), then the
+// file itself, then `[profiles.<profile>]`. Tables merge key by key; arrays and values replace,
+// so a profile's `[[tmux.windows]]` replaces the windows rather than adding to them.
+pub(crate) fn layered_table(
+    path: &Path,
+    raw: &str,
+    profile: Option<&str>,
+) -> Result<Table, ConfigError> {
+    let mut table = parse_table(path, raw)?;
+    table = resolve_includes(path, table, &mut vec![identity(path)])?;
+
+    let profiles = table.remove("profiles");
+    let Some(profile) = profile else {
+        return Ok(table);
+    };
+
+    let mut profiles = match profiles {
+        None => Table::new(),
+        Some(Value::Table(profiles)) => profiles,
+        Some(_) => {
+            return Err(ConfigError::Include {
+                path: path.to_path_buf(),
+                message: "profiles must be a table of [profiles.<name>] sections".to_string(),
+            });
+        }
+    };
+    match profiles.remove(profile) {
+        Some(Value::Table(overlay)) => {
+            merge_tables(&mut table, overlay);
+            Ok(table)
+        }
+        Some(_) => Err(ConfigError::Include {
+            path: path.to_path_buf(),
+            message: format!("profiles.{profile} must be a table"),
+        }),
+        None => {
+            let names: Vec<_> = profiles.keys().map(String::as_str).collect();
+            let available = if names.is_empty() {
+                "no profiles are defined".to_string()
+            } else {
+                format!("defined profiles: {}", names.join(", "))
+            };
+            Err(ConfigError::UnknownProfile {
+                name: profile.to_string(),
+                available,
+            })
+        }
+    }
+}
+
+fn resolve_includes(
+    path: &Path,
+    mut table: Table,
+    chain: &mut Vec<PathBuf>,
+) -> Result<Table, ConfigError> {
+    let Some(include) = table.remove("include") else {
+        return Ok(table);
+    };
+    let Value::Array(entries) = include else {
+        return Err(ConfigError::Include {
+            path: path.to_path_buf(),
+            message: "include must be a list of file paths".to_string(),
+        });
+    };
+
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut merged = Table::new();
+    for entry in entries {
+        let Value::String(entry) = entry else {
+            return Err(ConfigError::Include {
+                path: path.to_path_buf(),
+                message: "include must be a list of file paths".to_string(),
+            });
+        };
+        let included = base_dir.join(expand_home(&entry));
+        let id = identity(&included);
+        if chain.contains(&id) {
+            return Err(ConfigError::Include {
+                path: path.to_path_buf(),
+                message: format!("{} is already being included", included.display()),
+            });
+        }
+
+        let raw = fs::read_to_string(&included).map_err(|source| ConfigError::Read {
+            path: included.clone(),
+            source,
+        })?;
+        let included_table = parse_table(&included, &raw)?;
+        chain.push(id);
+        let included_table = resolve_includes(&included, included_table, chain)?;
+        chain.pop();
+        merge_tables(&mut merged, included_table);
+    }
+
+    merge_tables(&mut merged, table);
+    Ok(merged)
+}
+
+fn parse_table(path: &Path, raw: &str) -> Result<Table, ConfigError> {
+    toml::from_str(raw).map_err(|source| ConfigError::Parse {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn merge_tables(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(existing)), Value::Table(value)) => merge_tables(existing, value),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn identity(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, name: &str, raw: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, raw).expect("write config");
+        path
+    }
+
+    #[test]
+    fn includes_merge_in_order_under_the_including_file_and_profile() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        write(
+            temp.path(),
+            "work.toml",
+            r#"
+branch_template = "work/{name}"
+
+[defaults]
+connect_now = false
+
+[[tmux.windows]]
+name = "editor"
+program = "nvim"
+"#,
+        );
+        write(
+            temp.path(),
+            "personal.toml",
+            r#"
+[defaults]
+copy_extras = false
+
+[profiles.home]
+branch_template = "me/{name}"
+
+[[profiles.home.tmux.windows]]
+name = "shell"
+program = "zsh"
+"#,
+        );
+        let raw = r#"
+version = 1
+include = ["work.toml", "personal.toml"]
+
+[defaults]
+copy_extras = true
+"#;
+        let path = write(temp.path(), "config.toml", raw);
+
+        let table = layered_table(&path, raw, None).expect("layered");
+        assert_eq!(table["branch_template"].as_str(), Some("work/{name}"));
+        assert_eq!(table["defaults"]["connect_now"].as_bool(), Some(false));
+        assert_eq!(table["defaults"]["copy_extras"].as_bool(), Some(true));
+        assert!(!table.contains_key("include") && !table.contains_key("profiles"));
+
+        let table = layered_table(&path, raw, Some("home")).expect("layered");
+        assert_eq!(table["branch_template"].as_str(), Some("me/{name}"));
+        let windows = table["tmux"]["windows"].as_array().expect("windows");
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0]["name"].as_str(), Some("shell"));
+    }
+
+    #[test]
+    fn an_explicit_profile_is_trimmed_and_a_blank_one_selects_none() {
+        assert_eq!(
+            LoadOptions::resolve(Some(" work ".to_string()))
+                .profile
+                .as_deref(),
+            Some("work")
+        );
+        assert_eq!(LoadOptions::resolve(Some("  ".to_string())).profile, None);
+    }
+
+    #[test]
+    fn unknown_profiles_and_include_cycles_are_reported() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let raw = "version = 1\ninclude = [\"other.toml\"]\n[profiles.work]\n";
+        let path = write(temp.path(), "config.toml", raw);
+        write(temp.path(), "other.toml", "include = [\"config.toml\"]\n");
+
+        let error = layered_table(&path, raw, None).expect_err("cycle");
+        assert!(error.to_string().contains("is already being included"));
+
+        write(temp.path(), "other.toml", "");
+        let error = layered_table(&path, raw, Some("home")).expect_err("unknown profile");
+        assert_eq!(
+            error.to_string(),
+            "unknown config profile 'home'; defined profiles: work"
+        );
+    }
+}
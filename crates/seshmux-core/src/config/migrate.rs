@@ -0,0 +1,206 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+use toml::{Table, Value};
+
+pub const CURRENT_CONFIG_VERSION: u32 = 1;
+
+type MigrationStep = fn(&mut Table) -> Result<(), String>;
+
+const MIGRATIONS: &[(u32, MigrationStep)] = &[(0, migrate_v0_to_v1)];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigMigration {
+    pub path: PathBuf,
+    pub backup_path: PathBuf,
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    #[error("failed to read config at {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error(
+        "config at {path} has version {found}, newer than supported version {CURRENT_CONFIG_VERSION}; upgrade seshmux"
+    )]
+    UnsupportedVersion { path: PathBuf, found: u32 },
+    #[error("no migration available from config version {from}")]
+    MissingStep { from: u32 },
+    #[error("failed to migrate config from version {from}: {message}")]
+    Step { from: u32, message: String },
+    #[error("failed to serialize migrated config: {source}")]
+    Serialize {
+        #[source]
+        source: toml::ser::Error,
+    },
+    #[error("failed to back up config to {path}: {source}")]
+    Backup {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to write migrated config to {path}: {source}")]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+pub fn migrate_config_file(path: &Path) -> Result<Option<ConfigMigration>, MigrationError> {
+    let raw = fs::read_to_string(path).map_err(|source| MigrationError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let Ok(mut table) = toml::from_str::<Table>(&raw) else {
+        return Ok(None);
+    };
+    // Includes came after versioning, and the version may live in an included file.
+    if table.contains_key("include") {
+        return Ok(None);
+    }
+    let Some(from_version) = config_version(&table) else {
+        return Ok(None);
+    };
+
+    if from_version > CURRENT_CONFIG_VERSION {
+        return Err(MigrationError::UnsupportedVersion {
+            path: path.to_path_buf(),
+            found: from_version,
+        });
+    }
+    if from_version == CURRENT_CONFIG_VERSION {
+        return Ok(None);
+    }
+
+    let to_version = migrate_table(&mut table, from_version)?;
+    let rendered =
+        toml::to_string(&table).map_err(|source| MigrationError::Serialize { source })?;
+
+    let backup_path = backup_path(path, from_version);
+    fs::write(&backup_path, &raw).map_err(|source| MigrationError::Backup {
+        path: backup_path.clone(),
+        source,
+    })?;
+    fs::write(path, rendered).map_err(|source| MigrationError::Write {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    Ok(Some(ConfigMigration {
+        path: path.to_path_buf(),
+        backup_path,
+        from_version,
+        to_version,
+    }))
+}
+
+pub fn migrate_table(table: &mut Table, from_version: u32) -> Result<u32, MigrationError> {
+    let mut version = from_version;
+
+    while version < CURRENT_CONFIG_VERSION {
+        let step = MIGRATIONS
+            .iter()
+            .find(|(from, _)| *from == version)
+            .map(|(_, step)| step)
+            .ok_or(MigrationError::MissingStep { from: version })?;
+
+        step(table).map_err(|message| MigrationError::Step {
+            from: version,
+            message,
+        })?;
+
+        version += 1;
+        table.insert("version".to_string(), Value::Integer(i64::from(version)));
+    }
+
+    Ok(version)
+}
+
+fn config_version(table: &Table) -> Option<u32> {
+    match table.get("version") {
+        None => Some(0),
+        Some(Value::Integer(value)) => u32::try_from(*value).ok(),
+        Some(_) => None,
+    }
+}
+
+fn backup_path(path: &Path, from_version: u32) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "config.toml".to_string());
+
+    path.with_file_name(format!("{file_name}.v{from_version}.bak"))
+}
+
+fn migrate_v0_to_v1(table: &mut Table) -> Result<(), String> {
+    if !table.contains_key("tmux") {
+        return Err("missing [tmux] section".to_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{LoadOptions, load_config};
+
+    #[test]
+    fn unversioned_config_is_upgraded_with_backup() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let path = temp.path().join("config.toml");
+        let original = "[[tmux.windows]]\nname = \"editor\"\nprogram = \"nvim\"\n";
+        fs::write(&path, original).expect("write config");
+
+        let migration = migrate_config_file(&path)
+            .expect("migration")
+            .expect("config migrated");
+
+        assert_eq!(migration.from_version, 0);
+        assert_eq!(migration.to_version, CURRENT_CONFIG_VERSION);
+        assert_eq!(
+            migration.backup_path,
+            temp.path().join("config.toml.v0.bak")
+        );
+        assert_eq!(
+            fs::read_to_string(&migration.backup_path).expect("read backup"),
+            original
+        );
+
+        let config = load_config(&path, &LoadOptions::default()).expect("migrated config is valid");
+        assert_eq!(config.version, CURRENT_CONFIG_VERSION);
+        assert_eq!(config.tmux.windows[0].name, "editor");
+    }
+
+    #[test]
+    fn current_and_unparseable_configs_are_left_alone() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let path = temp.path().join("config.toml");
+
+        fs::write(&path, "version = 1\n[tmux]\nwindows = []\n").expect("write config");
+        assert!(migrate_config_file(&path).expect("no-op").is_none());
+
+        fs::write(&path, "version = [").expect("write config");
+        assert!(migrate_config_file(&path).expect("no-op").is_none());
+        assert!(!temp.path().join("config.toml.v0.bak").exists());
+    }
+
+    #[test]
+    fn newer_config_version_is_rejected() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let path = temp.path().join("config.toml");
+        fs::write(&path, "version = 7\n").expect("write config");
+
+        let error = migrate_config_file(&path).expect_err("newer version");
+        assert!(error.to_string().contains("upgrade seshmux"));
+    }
+}
@@ -0,0 +1,229 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::command_adapter::{self, ensure_success};
+use crate::command_runner::CommandRunner;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheShareStrategy {
+    CopyOnWrite,
+    Symlink,
+}
+
+impl CacheShareStrategy {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::CopyOnWrite => "copy-on-write clone",
+            Self::Symlink => "read-only symlink",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedCache {
+    pub relative_path: PathBuf,
+    pub strategy: CacheShareStrategy,
+}
+
+#[derive(Debug, Error)]
+pub enum DependencyCacheError {
+    #[error("failed to remove partial cache clone at {path}: {error}")]
+    Cleanup { path: String, error: std::io::Error },
+    #[error("failed to create parent directory for {path}: {error}")]
+    CreateParent { path: String, error: std::io::Error },
+    #[error("failed to symlink dependency cache {from} to {to}: {error}")]
+    Symlink {
+        from: String,
+        to: String,
+        error: std::io::Error,
+    },
+}
+
+pub fn find_dependency_caches(source_root: &Path, dirs: &[String]) -> Vec<PathBuf> {
+    dirs.iter()
+        .map(|dir| PathBuf::from(dir.trim()))
+        .filter(|relative| !relative.as_os_str().is_empty())
+        .filter(|relative| {
+            fs::symlink_metadata(source_root.join(relative))
+                .is_ok_and(|metadata| metadata.file_type().is_dir())
+        })
+        .collect()
+}
+
+pub fn share_dependency_caches(
+    source_root: &Path,
+    target_root: &Path,
+    caches: &[PathBuf],
+    runner: &dyn CommandRunner,
+) -> Result<Vec<SharedCache>, DependencyCacheError> {
+    let mut shared = Vec::new();
+
+    for relative in caches {
+        let source = source_root.join(relative);
+        let target = target_root.join(relative);
+
+        if fs::symlink_metadata(&target).is_ok() {
+            continue;
+        }
+
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|error| DependencyCacheError::CreateParent {
+                path: target.display().to_string(),
+                error,
+            })?;
+        }
+
+        let strategy = if clone_copy_on_write(&source, &target, runner) {
+            CacheShareStrategy::CopyOnWrite
+        } else {
+            remove_partial_clone(&target)?;
+            let linked = link_read_only(&source, &target).map_err(|error| {
+                DependencyCacheError::Symlink {
+                    from: source.display().to_string(),
+                    to: target.display().to_string(),
+                    error,
+                }
+            })?;
+            if !linked {
+                continue;
+            }
+            CacheShareStrategy::Symlink
+        };
+
+        shared.push(SharedCache {
+            relative_path: relative.clone(),
+            strategy,
+        });
+    }
+
+    Ok(shared)
+}
+
+fn clone_copy_on_write(source: &Path, target: &Path, runner: &dyn CommandRunner) -> bool {
+    let source = source.to_string_lossy();
+    let target = target.to_string_lossy();
+    let mut args = copy_on_write_flags().to_vec();
+    args.push(source.as_ref());
+    args.push(target.as_ref());
+
+    command_adapter::run_program(runner, "cp", &args, None)
+        .ok()
+        .is_some_and(|output| ensure_success(&args, output).is_ok())
+}
+
+#[cfg(target_os = "macos")]
+fn copy_on_write_flags() -> &'static [&'static str] {
+    &["-c", "-R"]
+}
+
+#[cfg(not(target_os = "macos"))]
+fn copy_on_write_flags() -> &'static [&'static str] {
+    &["-R", "--reflink=always"]
+}
+
+fn remove_partial_clone(target: &Path) -> Result<(), DependencyCacheError> {
+    match fs::remove_dir_all(target) {
+        Ok(()) => Ok(()),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(DependencyCacheError::Cleanup {
+            path: target.display().to_string(),
+            error,
+        }),
+    }
+}
+
+/// Points `target` at the main checkout's cache. The link is meant to be read from only: an
+/// install or build that writes through it changes the main checkout's copy.
+#[cfg(unix)]
+fn link_read_only(source: &Path, target: &Path) -> std::io::Result<bool> {
+    std::os::unix::fs::symlink(source, target)?;
+    Ok(true)
+}
+
+#[cfg(not(unix))]
+fn link_read_only(_source: &Path, _target: &Path) -> std::io::Result<bool> {
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_runner::CommandOutput;
+    use crate::test_support::{RecordingRunner, output};
+
+    fn failing_cp() -> anyhow::Result<CommandOutput> {
+        output("", "cp: failed to clone: Operation not supported", 1)
+    }
+
+    #[test]
+    fn find_dependency_caches_keeps_existing_directories_only() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        fs::create_dir_all(temp.path().join("node_modules")).expect("node_modules");
+        fs::write(temp.path().join("target"), "not a dir").expect("target file");
+
+        let dirs = vec![
+            "node_modules".to_string(),
+            "target".to_string(),
+            ".venv".to_string(),
+        ];
+
+        assert_eq!(
+            find_dependency_caches(temp.path(), &dirs),
+            vec![PathBuf::from("node_modules")]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_a_symlink_when_copy_on_write_is_unavailable() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source_root = temp.path().join("repo");
+        let target_root = temp.path().join("worktree");
+        fs::create_dir_all(source_root.join("node_modules/pkg")).expect("source cache");
+        fs::write(source_root.join("node_modules/pkg/index.js"), "v1").expect("source file");
+        fs::create_dir_all(&target_root).expect("target root");
+
+        let runner = RecordingRunner::from_outputs(vec![failing_cp()]);
+        let shared = share_dependency_caches(
+            &source_root,
+            &target_root,
+            &[PathBuf::from("node_modules")],
+            &runner,
+        )
+        .expect("share caches");
+
+        assert_eq!(shared[0].strategy, CacheShareStrategy::Symlink);
+        let cache = target_root.join("node_modules");
+        assert_eq!(
+            fs::read_link(&cache).expect("read link"),
+            source_root.join("node_modules")
+        );
+        assert_eq!(
+            fs::read_to_string(cache.join("pkg/index.js")).expect("linked file"),
+            "v1"
+        );
+        assert_eq!(runner.calls()[0].program, "cp");
+    }
+
+    #[test]
+    fn existing_target_is_left_untouched() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source_root = temp.path().join("repo");
+        let target_root = temp.path().join("worktree");
+        fs::create_dir_all(source_root.join("target")).expect("source cache");
+        fs::create_dir_all(target_root.join("target")).expect("existing target");
+
+        let runner = RecordingRunner::from_outputs(Vec::new());
+        let shared = share_dependency_caches(
+            &source_root,
+            &target_root,
+            &[PathBuf::from("target")],
+            &runner,
+        )
+        .expect("share caches");
+
+        assert!(shared.is_empty());
+        assert!(runner.calls().is_empty());
+    }
+}
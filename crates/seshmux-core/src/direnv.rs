@@ -0,0 +1,67 @@
+use std::path::Path;
+
+use thiserror::Error;
+
+use crate::command_adapter;
+use crate::command_runner::CommandRunner;
+
+pub const ENVRC: &str = ".envrc";
+
+#[derive(Debug, Error)]
+pub enum DirenvError {
+    #[error("failed to execute direnv: {0}")]
+    Execute(String),
+    #[error("direnv command failed: direnv {command} (exit {status}) {stderr}")]
+    CommandFailed {
+        command: String,
+        status: i32,
+        stderr: String,
+    },
+}
+
+pub fn has_envrc(dir: &Path) -> bool {
+    dir.join(ENVRC).is_file()
+}
+
+pub fn allow(worktree_path: &Path, runner: &dyn CommandRunner) -> Result<(), DirenvError> {
+    let args = ["allow", "."];
+    let output = command_adapter::run_program(runner, "direnv", &args, Some(worktree_path))
+        .map_err(DirenvError::Execute)?;
+    command_adapter::ensure_success(&args, output).map_err(|failure| {
+        DirenvError::CommandFailed {
+            command: failure.command,
+            status: failure.status,
+            stderr: failure.stderr,
+        }
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::test_support::{RecordingRunner, output};
+
+    use super::allow;
+
+    #[test]
+    fn allow_runs_direnv_in_the_worktree_and_reports_failures() {
+        let runner = RecordingRunner::from_outputs(vec![
+            output("", "", 0),
+            output("", "direnv: error .envrc file not found", 1),
+        ]);
+
+        allow(Path::new("/repo/worktrees/w1"), &runner).expect("allow");
+        let error = allow(Path::new("/repo/worktrees/w2"), &runner).expect_err("missing");
+
+        let calls = runner.calls();
+        assert_eq!(calls[0].program, "direnv");
+        assert_eq!(calls[0].args, vec!["allow", "."]);
+        assert_eq!(
+            calls[0].cwd.as_deref(),
+            Some(Path::new("/repo/worktrees/w1"))
+        );
+        assert!(error.to_string().contains("(exit 1) direnv: error"));
+    }
+}
@@ -0,0 +1,111 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub fn directory_size(root: &Path) -> io::Result<u64> {
+    let metadata = fs::symlink_metadata(root)?;
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+
+    let mut total = 0u64;
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                pending.push(entry.path());
+            } else if file_type.is_file()
+                && let Ok(metadata) = entry.metadata()
+            {
+                total = total.saturating_add(metadata.len());
+            }
+        }
+    }
+
+    Ok(total)
+}
+
+#[cfg(unix)]
+pub fn available_space(path: &Path) -> io::Result<Option<u64>> {
+    let stats = rustix::fs::statvfs(path)?;
+    Ok(Some(stats.f_bavail.saturating_mul(stats.f_frsize)))
+}
+
+#[cfg(not(unix))]
+pub fn available_space(_path: &Path) -> io::Result<Option<u64>> {
+    Ok(None)
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if value < 10.0 {
+        format!("{value:.1} {}", UNITS[unit])
+    } else {
+        format!("{value:.0} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{available_space, directory_size, format_bytes};
+
+    #[test]
+    fn directory_size_sums_nested_files() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        fs::create_dir_all(temp.path().join("a/b")).expect("dirs");
+        fs::write(temp.path().join("top.txt"), vec![0u8; 10]).expect("top");
+        fs::write(temp.path().join("a/b/deep.bin"), vec![0u8; 2_048]).expect("deep");
+
+        assert_eq!(directory_size(temp.path()).expect("size"), 2_058);
+        assert!(directory_size(&temp.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn format_bytes_uses_binary_units() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(42 * 1024 * 1024), "42 MiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024 + 1), "3.0 GiB");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn available_space_reports_the_filesystem_of_an_existing_path() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        assert!(available_space(temp.path()).expect("statvfs").is_some());
+        assert!(available_space(&temp.path().join("missing")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn directory_size_does_not_follow_symlinks() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let outside = temp.path().join("outside");
+        let root = temp.path().join("root");
+        fs::create_dir_all(&outside).expect("outside");
+        fs::create_dir_all(&root).expect("root");
+        fs::write(outside.join("big.bin"), vec![0u8; 4_096]).expect("big");
+        std::os::unix::fs::symlink(&outside, root.join("link")).expect("symlink");
+
+        assert_eq!(directory_size(&root).expect("size"), 0);
+    }
+}
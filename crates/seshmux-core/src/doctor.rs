@@ -3,7 +3,13 @@ use std::fmt;
 use std::path::Path;
 
 use crate::command_runner::{CommandRunner, SystemCommandRunner};
-use crate::config::{WindowSpec, load_config, parse_window_launch, resolve_config_path};
+use crate::config::{
+    LoadOptions, WindowSpec, load_config, parse_window_launch, resolve_config_path,
+};
+
+// The oldest git with every worktree subcommand seshmux runs; `worktree move` and
+// `worktree remove` arrived in 2.17.
+const MIN_GIT_VERSION: (u32, u32) = (2, 17);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CheckState {
@@ -52,10 +58,10 @@ impl DoctorReport {
 
 pub fn run_doctor() -> DoctorReport {
     let runner = SystemCommandRunner::new();
-    run_doctor_with_runner(&runner)
+    run_doctor_with_runner(&runner, &LoadOptions::resolve(None))
 }
 
-pub fn run_doctor_with_runner(runner: &dyn CommandRunner) -> DoctorReport {
+pub fn run_doctor_with_runner(runner: &dyn CommandRunner, options: &LoadOptions) -> DoctorReport {
     let mut checks = Vec::new();
 
     checks.push(match env::consts::OS {
@@ -74,6 +80,7 @@ pub fn run_doctor_with_runner(runner: &dyn CommandRunner) -> DoctorReport {
     });
 
     checks.push(check_git_worktree_support(runner));
+    checks.push(check_git_version(runner));
     checks.push(check_tmux_callable(runner));
 
     match resolve_config_path() {
@@ -84,7 +91,7 @@ pub fn run_doctor_with_runner(runner: &dyn CommandRunner) -> DoctorReport {
                     format!("found at {}", config_path.display()),
                 ));
 
-                match load_config(&config_path) {
+                match load_config(&config_path, options) {
                     Ok(config) => {
                         checks.push(pass_check("config parses and validates", "config is valid"));
                         checks.push(check_window_targets(&config.tmux.windows));
@@ -155,6 +162,128 @@ fn check_git_worktree_support(runner: &dyn CommandRunner) -> DoctorCheck {
     }
 }
 
+pub fn repository_checks(cwd: &Path, runner: &dyn CommandRunner) -> Vec<DoctorCheck> {
+    let bare = match runner.run("git", &["rev-parse", "--is-bare-repository"], Some(cwd)) {
+        Ok(output) if output.status_code == 0 => output.stdout.trim() == "true",
+        _ => return Vec::new(),
+    };
+
+    vec![
+        check_main_checkout(cwd, bare, runner),
+        check_core_worktree(cwd, runner),
+    ]
+}
+
+fn check_git_version(runner: &dyn CommandRunner) -> DoctorCheck {
+    const NAME: &str = "git version supports worktrees";
+    let (major, minor) = MIN_GIT_VERSION;
+
+    let output = match runner.run("git", &["--version"], None) {
+        Ok(output) if output.status_code == 0 => output,
+        Ok(output) => {
+            return fail_check(
+                NAME,
+                format!(
+                    "git --version returned exit code {}: {}",
+                    output.status_code,
+                    output.stderr.trim()
+                ),
+            );
+        }
+        Err(error) => {
+            return fail_check(NAME, format!("failed to execute git --version: {error}"));
+        }
+    };
+
+    let reported = output.stdout.trim();
+    match parse_git_version(reported) {
+        Some(version) if version >= MIN_GIT_VERSION => pass_check(NAME, reported),
+        Some(_) => fail_check(
+            NAME,
+            format!(
+                "{reported} is too old: seshmux needs git {major}.{minor} or newer for `git worktree move` and `git worktree remove`; upgrade git with your package manager (for example `brew upgrade git` or `apt install git`)"
+            ),
+        ),
+        None => fail_check(
+            NAME,
+            format!(
+                "could not read a version from '{reported}'; make sure `git` in PATH is git {major}.{minor} or newer"
+            ),
+        ),
+    }
+}
+
+fn parse_git_version(output: &str) -> Option<(u32, u32)> {
+    let version = output
+        .strip_prefix("git version ")?
+        .split_whitespace()
+        .next()?;
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+fn check_main_checkout(cwd: &Path, bare: bool, runner: &dyn CommandRunner) -> DoctorCheck {
+    const NAME: &str = "repository has a main checkout";
+    if !bare {
+        return pass_check(NAME, "the current repository has a working tree");
+    }
+
+    let worktrees = crate::git::list_worktree_paths(cwd, runner)
+        .map(|paths| paths.into_iter().skip(1).collect::<Vec<_>>())
+        .unwrap_or_default();
+    if worktrees.is_empty() {
+        fail_check(
+            NAME,
+            "the current repository is bare and has no worktrees, so there is no checkout for seshmux to create worktrees beside; add one with `git worktree add <path> <branch>` and run seshmux from it, or clone a regular repository with `seshmux init <url>`",
+        )
+    } else {
+        let paths = worktrees
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>();
+        fail_check(
+            NAME,
+            format!(
+                "the current directory is a bare repository; run seshmux from one of its worktrees instead: {}",
+                paths.join(", ")
+            ),
+        )
+    }
+}
+
+fn check_core_worktree(cwd: &Path, runner: &dyn CommandRunner) -> DoctorCheck {
+    const NAME: &str = "core.worktree is unset";
+    match runner.run(
+        "git",
+        &["config", "--local", "--get", "core.worktree"],
+        Some(cwd),
+    ) {
+        // `git config --get` exits 1 when the key is missing.
+        Ok(output) if output.status_code == 1 => pass_check(
+            NAME,
+            "the repository config does not override the working tree",
+        ),
+        Ok(output) if output.status_code == 0 => fail_check(
+            NAME,
+            format!(
+                "core.worktree = {} in the repository config makes every worktree use that directory as its working tree, so new worktrees check out the wrong files; remove it with `git config --unset core.worktree`, or keep it for one worktree with `git config extensions.worktreeConfig true` and `git config --worktree core.worktree <path>`",
+                output.stdout.trim()
+            ),
+        ),
+        Ok(output) => fail_check(
+            NAME,
+            format!(
+                "git config returned exit code {}: {}",
+                output.status_code,
+                output.stderr.trim()
+            ),
+        ),
+        Err(error) => fail_check(NAME, format!("failed to execute git config: {error}")),
+    }
+}
+
 fn check_tmux_callable(runner: &dyn CommandRunner) -> DoctorCheck {
     match runner.run("tmux", &["-V"], None) {
         Ok(output) if output.status_code == 0 => {
@@ -279,6 +408,8 @@ fn is_executable_file(path: &Path) -> bool {
 
 #[cfg(test)]
 mod tests {
+    use crate::test_support::{RecordingRunner, output};
+
     use super::*;
 
     #[test]
@@ -312,4 +443,64 @@ mod tests {
         assert_eq!(report.summary(), "2 passed, 1 failed");
         assert!(report.has_failures());
     }
+
+    #[test]
+    fn git_versions_are_read_from_every_known_format() {
+        assert_eq!(parse_git_version("git version 2.39.5"), Some((2, 39)));
+        assert_eq!(
+            parse_git_version("git version 2.39.3 (Apple Git-146)"),
+            Some((2, 39))
+        );
+        assert_eq!(
+            parse_git_version("git version 2.45.1.windows.1"),
+            Some((2, 45))
+        );
+        assert_eq!(parse_git_version("hub version 2.14.2"), None);
+    }
+
+    #[test]
+    fn old_git_fails_with_an_upgrade_hint() {
+        let runner = RecordingRunner::from_outputs(vec![output("git version 2.11.0\n", "", 0)]);
+        let check = check_git_version(&runner);
+        assert_eq!(check.state, CheckState::Fail);
+        assert!(check.details.contains("needs git 2.17 or newer"));
+
+        let runner = RecordingRunner::from_outputs(vec![output("git version 2.17.0\n", "", 0)]);
+        assert_eq!(check_git_version(&runner).state, CheckState::Pass);
+    }
+
+    #[test]
+    fn repository_checks_flag_bare_repositories_and_core_worktree() {
+        let runner = RecordingRunner::from_outputs(vec![output("", "not a git repository", 128)]);
+        assert!(repository_checks(Path::new("/tmp"), &runner).is_empty());
+
+        let runner = RecordingRunner::from_outputs(vec![
+            output("true\n", "", 0),
+            output(
+                "worktree /srv/app.git\nbare\n\nworktree /srv/app/main\nHEAD abc\nbranch refs/heads/main\n",
+                "",
+                0,
+            ),
+            output("/srv/app/main\n", "", 0),
+        ]);
+        let checks = repository_checks(Path::new("/srv/app.git"), &runner);
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].state, CheckState::Fail);
+        assert!(
+            checks[0]
+                .details
+                .contains("one of its worktrees instead: /srv/app/main")
+        );
+        assert_eq!(checks[1].state, CheckState::Fail);
+        assert!(
+            checks[1]
+                .details
+                .contains("git config --unset core.worktree")
+        );
+
+        let runner =
+            RecordingRunner::from_outputs(vec![output("false\n", "", 0), output("", "", 1)]);
+        let checks = repository_checks(Path::new("/repo"), &runner);
+        assert!(checks.iter().all(|check| check.state == CheckState::Pass));
+    }
 }
@@ -1,6 +1,13 @@
 use std::collections::{BTreeMap, BTreeSet};
+#[cfg(unix)]
+use std::ffi::OsString;
 use std::fs;
+#[cfg(unix)]
+use std::os::unix::ffi::OsStringExt;
 use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
 
 use thiserror::Error;
 
@@ -24,6 +31,35 @@ pub enum ExtrasError {
         to: String,
         error: std::io::Error,
     },
+    #[error(
+        "failed to copy {} of {total} extra(s): {}",
+        .failures.len(),
+        failure_summary(.failures)
+    )]
+    CopyFailures {
+        total: usize,
+        failures: Vec<ExtrasError>,
+    },
+}
+
+const REPORTED_COPY_FAILURES: usize = 3;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CopySummary {
+    pub copied: usize,
+    pub skipped: usize,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExtraOrigin {
+    Untracked,
+    Ignored,
+}
+
+enum CopyOutcome {
+    Copied(u64),
+    Skipped,
 }
 
 pub fn list_extra_candidates(
@@ -38,19 +74,82 @@ pub fn copy_selected_extras(
     repo_root: &Path,
     target_root: &Path,
     selected: &[PathBuf],
-) -> Result<(), ExtrasError> {
+    concurrency: usize,
+) -> Result<CopySummary, ExtrasError> {
+    let mut summary = CopySummary::default();
+    let mut pending = Vec::with_capacity(selected.len());
     for relative in selected {
         let normalized = normalize_extra_relative_path(relative)?;
         if is_worktrees_relative_path(&normalized) {
-            continue;
+            summary.skipped += 1;
+        } else {
+            pending.push(normalized);
+        }
+    }
+
+    let next = AtomicUsize::new(0);
+    let outcomes = Mutex::new(Vec::with_capacity(pending.len()));
+    let workers = concurrency.clamp(1, pending.len().max(1));
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(normalized) = pending.get(index) else {
+                        break;
+                    };
+                    let source = repo_root.join(normalized);
+                    let target = target_root.join(normalized);
+                    let outcome = copy_existing_path(repo_root, &source, &target);
+                    outcomes
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .push((index, outcome));
+                }
+            });
+        }
+    });
+
+    let mut outcomes = outcomes
+        .into_inner()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    outcomes.sort_by_key(|(index, _)| *index);
+    let mut failures = Vec::new();
+    for (_, outcome) in outcomes {
+        match outcome {
+            Ok(CopyOutcome::Copied(bytes)) => {
+                summary.copied += 1;
+                summary.bytes = summary.bytes.saturating_add(bytes);
+            }
+            Ok(CopyOutcome::Skipped) => summary.skipped += 1,
+            Err(error) => failures.push(error),
         }
+    }
 
-        let source = repo_root.join(&normalized);
-        let target = target_root.join(&normalized);
-        copy_existing_path(repo_root, &source, &target)?;
+    if failures.is_empty() {
+        Ok(summary)
+    } else {
+        Err(ExtrasError::CopyFailures {
+            total: selected.len(),
+            failures,
+        })
     }
+}
 
-    Ok(())
+fn failure_summary(failures: &[ExtrasError]) -> String {
+    let mut summary = failures
+        .iter()
+        .take(REPORTED_COPY_FAILURES)
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ");
+    if failures.len() > REPORTED_COPY_FAILURES {
+        summary.push_str(&format!(
+            "; and {} more",
+            failures.len() - REPORTED_COPY_FAILURES
+        ));
+    }
+    summary
 }
 
 pub fn classify_flagged_buckets(
@@ -76,6 +175,56 @@ pub fn classify_flagged_buckets(
     buckets
 }
 
+pub fn suggest_skip_buckets(
+    candidates: &[PathBuf],
+    origins: &BTreeMap<PathBuf, ExtraOrigin>,
+    flagged_buckets: &BTreeSet<String>,
+    min_files: usize,
+) -> BTreeMap<String, usize> {
+    let flagged_components = compiled_bucket_components(flagged_buckets);
+    let mut directory_counts = BTreeMap::<Vec<String>, usize>::new();
+
+    for candidate in candidates {
+        if origins.get(candidate) != Some(&ExtraOrigin::Ignored)
+            || candidate_matches_any_skipped_bucket(candidate, &flagged_components)
+        {
+            continue;
+        }
+        let Some(components) = normalized_components(candidate) else {
+            continue;
+        };
+        if components.first().map(String::as_str) == Some("worktrees") {
+            continue;
+        }
+        for depth in 1..components.len() {
+            *directory_counts
+                .entry(components[..depth].to_vec())
+                .or_insert(0) += 1;
+        }
+    }
+
+    let mut suggested = BTreeMap::<String, usize>::new();
+    let mut taken = Vec::<Vec<String>>::new();
+    let mut qualifying = directory_counts
+        .into_iter()
+        .filter(|(_, count)| *count >= min_files)
+        .collect::<Vec<_>>();
+    qualifying.sort_by(|(left, _), (right, _)| left.len().cmp(&right.len()).then(left.cmp(right)));
+
+    for (components, count) in qualifying {
+        if taken
+            .iter()
+            .any(|existing| components.starts_with(existing))
+        {
+            continue;
+        }
+        suggested.insert(components.join("/"), count);
+        taken.push(components);
+    }
+
+    suggested
+}
+
 pub fn filter_candidates_by_skipped_buckets(
     candidates: &[PathBuf],
     skipped_buckets: &BTreeSet<String>,
@@ -97,6 +246,48 @@ pub fn filter_candidates_by_skipped_buckets(
         .collect()
 }
 
+pub fn extra_matches_glob(pattern: &str, path: &Path) -> bool {
+    let Some(components) = normalized_components(path) else {
+        return false;
+    };
+    let segments: Vec<&str> = pattern
+        .trim()
+        .trim_start_matches("./")
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    if segments.is_empty() {
+        return false;
+    }
+
+    glob_matches_segments(&segments, &components)
+}
+
+fn glob_matches_segments(pattern: &[&str], components: &[String]) -> bool {
+    match pattern.split_first() {
+        None => true,
+        Some((&"**", rest)) => {
+            (0..=components.len()).any(|skip| glob_matches_segments(rest, &components[skip..]))
+        }
+        Some((segment, rest)) => components.split_first().is_some_and(|(first, tail)| {
+            let pattern: Vec<char> = segment.chars().collect();
+            let text: Vec<char> = first.chars().collect();
+            glob_matches_segment(&pattern, &text) && glob_matches_segments(rest, tail)
+        }),
+    }
+}
+
+fn glob_matches_segment(pattern: &[char], text: &[char]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some(('*', rest)) => (0..=text.len()).any(|skip| glob_matches_segment(rest, &text[skip..])),
+        Some(('?', rest)) => !text.is_empty() && glob_matches_segment(rest, &text[1..]),
+        Some((expected, rest)) => {
+            text.first() == Some(expected) && glob_matches_segment(rest, &text[1..])
+        }
+    }
+}
+
 pub fn depth_two_bucket_key(path: &Path) -> Option<String> {
     let components = normalized_components(path)?;
     let first = components.first()?;
@@ -132,30 +323,36 @@ pub fn normalize_extra_relative_path(path: &Path) -> Result<PathBuf, ExtrasError
     Ok(clean)
 }
 
-fn copy_existing_path(repo_root: &Path, source: &Path, target: &Path) -> Result<(), ExtrasError> {
+fn copy_existing_path(
+    repo_root: &Path,
+    source: &Path,
+    target: &Path,
+) -> Result<CopyOutcome, ExtrasError> {
     if is_worktrees_source_path(repo_root, source) {
-        return Ok(());
+        return Ok(CopyOutcome::Skipped);
     }
 
     let metadata = match fs::symlink_metadata(source) {
         Ok(metadata) => metadata,
-        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(CopyOutcome::Skipped);
+        }
         Err(error) => return Err(copy_error(source, target, error)),
     };
 
     if metadata.file_type().is_symlink() {
-        return Ok(());
+        return Ok(CopyOutcome::Skipped);
     }
 
     if metadata.is_dir() {
-        return Ok(());
+        return Ok(CopyOutcome::Skipped);
     }
 
     if metadata.is_file() {
-        return copy_file(source, target);
+        return copy_file(source, target).map(CopyOutcome::Copied);
     }
 
-    Ok(())
+    Ok(CopyOutcome::Skipped)
 }
 
 #[derive(Debug)]
@@ -290,13 +487,12 @@ fn is_worktrees_relative_path(path: &Path) -> bool {
     )
 }
 
-fn copy_file(source: &Path, target: &Path) -> Result<(), ExtrasError> {
+fn copy_file(source: &Path, target: &Path) -> Result<u64, ExtrasError> {
     if let Some(parent) = target.parent() {
         create_dir_all(source, target, parent)?;
     }
 
-    fs::copy(source, target).map_err(|error| copy_error(source, target, error))?;
-    Ok(())
+    fs::copy(source, target).map_err(|error| copy_error(source, target, error))
 }
 
 fn create_dir_all(from: &Path, to: &Path, dir: &Path) -> Result<(), ExtrasError> {
@@ -315,12 +511,23 @@ pub fn collect_git_extra_paths_nul_two_pass(
     repo_root: &Path,
     runner: &dyn CommandRunner,
 ) -> Result<Vec<PathBuf>, ExtrasError> {
-    let untracked = run_git_stdout(
+    Ok(collect_git_extra_origins(repo_root, runner)?
+        .into_keys()
+        .collect())
+}
+
+/// Keyed by the path as git prints it. The passes do not overlap: without `-i`,
+/// `--exclude-standard` leaves ignored files out.
+pub fn collect_git_extra_origins(
+    repo_root: &Path,
+    runner: &dyn CommandRunner,
+) -> Result<BTreeMap<PathBuf, ExtraOrigin>, ExtrasError> {
+    let untracked = run_git_raw_stdout(
         runner,
         repo_root,
         &["ls-files", "-o", "-z", "--exclude-standard", "--", "."],
     )?;
-    let ignored = run_git_stdout(
+    let ignored = run_git_raw_stdout(
         runner,
         repo_root,
         &[
@@ -334,32 +541,51 @@ pub fn collect_git_extra_paths_nul_two_pass(
         ],
     )?;
 
-    let mut merged = BTreeSet::new();
-    for path in parse_nul_paths(&untracked)
-        .into_iter()
-        .chain(parse_nul_paths(&ignored))
-    {
-        merged.insert(path);
+    let mut origins = BTreeMap::new();
+    for path in parse_nul_paths(&untracked) {
+        origins.insert(path, ExtraOrigin::Untracked);
+    }
+    for path in parse_nul_paths(&ignored) {
+        origins.insert(path, ExtraOrigin::Ignored);
     }
 
-    Ok(merged.into_iter().collect())
+    Ok(origins)
 }
 
-pub fn parse_nul_paths(stdout: &str) -> Vec<PathBuf> {
+pub fn parse_nul_paths(stdout: &[u8]) -> Vec<PathBuf> {
     stdout
-        .split('\0')
+        .split(|byte| *byte == 0)
         .filter(|value| !value.is_empty())
-        .map(PathBuf::from)
+        .map(path_from_bytes)
         .collect()
 }
 
+#[cfg(unix)]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    PathBuf::from(OsString::from_vec(bytes.to_vec()))
+}
+
+#[cfg(not(unix))]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(bytes).into_owned())
+}
+
 pub fn filter_safe_extra_paths(
     repo_root: &Path,
     raw: Vec<PathBuf>,
+) -> Result<Vec<PathBuf>, ExtrasError> {
+    filter_safe_extra_paths_with_progress(repo_root, raw, &mut |_| {})
+}
+
+pub fn filter_safe_extra_paths_with_progress(
+    repo_root: &Path,
+    raw: Vec<PathBuf>,
+    checked: &mut dyn FnMut(&Path),
 ) -> Result<Vec<PathBuf>, ExtrasError> {
     let mut filtered = BTreeSet::new();
 
     for entry in raw {
+        checked(&entry);
         let normalized = normalize_extra_relative_path(&entry)?;
         if is_worktrees_relative_path(&normalized) {
             continue;
@@ -373,13 +599,13 @@ pub fn filter_safe_extra_paths(
     Ok(filtered.into_iter().collect())
 }
 
-fn run_git_stdout(
+fn run_git_raw_stdout(
     runner: &dyn CommandRunner,
     repo_root: &Path,
     args: &[&str],
-) -> Result<String, ExtrasError> {
+) -> Result<Vec<u8>, ExtrasError> {
     let output = runner
-        .run("git", args, Some(repo_root))
+        .run_raw("git", args, Some(repo_root))
         .map_err(|error| ExtrasError::Execute(error.to_string()))?;
 
     if output.status_code != 0 {
@@ -480,9 +706,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn collect_origins_tags_each_pass() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let runner = RecordingRunner::from_outputs(vec![
+            output("notes.md\0.env.local\0", "", 0),
+            output("target/debug/app\0.env\0", "", 0),
+        ]);
+
+        let origins = collect_git_extra_origins(temp.path(), &runner).expect("origins");
+
+        assert_eq!(
+            origins.into_iter().collect::<Vec<_>>(),
+            vec![
+                (PathBuf::from(".env"), ExtraOrigin::Ignored),
+                (PathBuf::from(".env.local"), ExtraOrigin::Untracked),
+                (PathBuf::from("notes.md"), ExtraOrigin::Untracked),
+                (PathBuf::from("target/debug/app"), ExtraOrigin::Ignored),
+            ]
+        );
+    }
+
     #[test]
     fn parse_nul_paths_ignores_empty_segments() {
-        let parsed = parse_nul_paths("one.txt\0\0nested/two.txt\0");
+        let parsed = parse_nul_paths(b"one.txt\0\0nested/two.txt\0");
         assert_eq!(
             parsed,
             vec![PathBuf::from("one.txt"), PathBuf::from("nested/two.txt")]
@@ -502,6 +749,7 @@ mod tests {
             &repo_root,
             &target_root,
             &[PathBuf::from("nested/file.txt")],
+            4,
         )
         .expect("copy extras");
 
@@ -510,6 +758,70 @@ mod tests {
         assert_eq!(fs::read_to_string(copied).expect("read copied"), "hello");
     }
 
+    #[test]
+    fn copy_selected_extras_summarizes_a_parallel_copy() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        let target_root = temp.path().join("target");
+
+        let mut selected = Vec::new();
+        for index in 0..20 {
+            let relative = PathBuf::from(format!("dir{}/file{index}.txt", index % 3));
+            fs::create_dir_all(repo_root.join(relative.parent().expect("parent"))).expect("dir");
+            fs::write(repo_root.join(&relative), "12345").expect("file");
+            selected.push(relative);
+        }
+        selected.push(PathBuf::from("missing.txt"));
+
+        let summary =
+            copy_selected_extras(&repo_root, &target_root, &selected, 4).expect("copy extras");
+
+        assert_eq!(
+            summary,
+            CopySummary {
+                copied: 20,
+                skipped: 1,
+                bytes: 100,
+            }
+        );
+        assert!(target_root.join("dir2/file17.txt").exists());
+    }
+
+    #[test]
+    fn copy_selected_extras_attempts_every_path_and_aggregates_failures() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        let target_root = temp.path().join("target");
+
+        fs::create_dir_all(repo_root.join("blocked")).expect("blocked");
+        fs::write(repo_root.join("blocked/a.txt"), "a").expect("a");
+        fs::write(repo_root.join("ok.txt"), "ok").expect("ok");
+        fs::create_dir_all(&target_root).expect("target");
+        fs::write(target_root.join("blocked"), "").expect("blocker");
+
+        let error = copy_selected_extras(
+            &repo_root,
+            &target_root,
+            &[PathBuf::from("blocked/a.txt"), PathBuf::from("ok.txt")],
+            2,
+        )
+        .expect_err("one copy fails");
+
+        match &error {
+            ExtrasError::CopyFailures { total, failures } => {
+                assert_eq!(*total, 2);
+                assert_eq!(failures.len(), 1);
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+        assert!(
+            error
+                .to_string()
+                .starts_with("failed to copy 1 of 2 extra(s): ")
+        );
+        assert!(target_root.join("ok.txt").exists());
+    }
+
     #[test]
     fn copy_selected_extras_skips_worktrees_directory_paths() {
         let temp = tempfile::tempdir().expect("temp dir");
@@ -528,6 +840,7 @@ mod tests {
                 PathBuf::from("worktrees/cache/state.txt"),
                 PathBuf::from("keep.txt"),
             ],
+            4,
         )
         .expect("copy extras");
 
@@ -548,7 +861,7 @@ mod tests {
         fs::write(outside_root.join("secret.txt"), "TOPSECRET").expect("secret");
         symlink(outside_root.join("secret.txt"), repo_root.join("link.txt")).expect("symlink");
 
-        copy_selected_extras(&repo_root, &target_root, &[PathBuf::from("link.txt")])
+        copy_selected_extras(&repo_root, &target_root, &[PathBuf::from("link.txt")], 4)
             .expect("copy extras");
 
         assert!(!target_root.join("link.txt").exists());
@@ -567,7 +880,7 @@ mod tests {
         fs::write(outside_root.join("secrets/file.txt"), "TOPSECRET").expect("secret");
         symlink(outside_root.join("secrets"), repo_root.join("linkdir")).expect("symlink");
 
-        copy_selected_extras(&repo_root, &target_root, &[PathBuf::from("linkdir")])
+        copy_selected_extras(&repo_root, &target_root, &[PathBuf::from("linkdir")], 4)
             .expect("copy extras");
 
         assert!(!target_root.join("linkdir").exists());
@@ -598,6 +911,7 @@ mod tests {
                 PathBuf::from("assets/keep.txt"),
                 PathBuf::from("assets/link.txt"),
             ],
+            4,
         )
         .expect("copy extras");
 
@@ -616,8 +930,9 @@ mod tests {
 
         fs::create_dir_all(&repo_root).expect("repo dir");
 
-        let error = copy_selected_extras(&repo_root, &target_root, &[PathBuf::from("../secret")])
-            .expect_err("expected path validation error");
+        let error =
+            copy_selected_extras(&repo_root, &target_root, &[PathBuf::from("../secret")], 4)
+                .expect_err("expected path validation error");
 
         assert!(matches!(error, ExtrasError::InvalidPath(_)));
     }
@@ -652,7 +967,8 @@ mod tests {
                 .any(|path| path == &PathBuf::from("crates/seshmux-tui/src/new_flow/mod.rs"))
         );
 
-        copy_selected_extras(&repo_root, &target_root, &[PathBuf::from("crates")]).expect("copy");
+        copy_selected_extras(&repo_root, &target_root, &[PathBuf::from("crates")], 4)
+            .expect("copy");
 
         assert!(!target_root.join("crates/.DS_Store").exists());
         assert!(
@@ -721,6 +1037,37 @@ mod tests {
         assert!(!flagged.contains_key("app/mobile/vendor"));
     }
 
+    #[test]
+    fn suggest_skip_buckets_picks_shallowest_dense_ignored_directory_outside_flagged() {
+        let mut candidates = Vec::new();
+        for index in 0..4 {
+            candidates.push(PathBuf::from(format!("data/raw/file-{index}.csv")));
+            candidates.push(PathBuf::from(format!("target/debug/file-{index}.o")));
+        }
+        for index in 0..4 {
+            candidates.push(PathBuf::from(format!("drafts/note-{index}.md")));
+        }
+        candidates.push(PathBuf::from("notes/todo.md"));
+        candidates.push(PathBuf::from(".env"));
+        let origins = candidates
+            .iter()
+            .map(|path| {
+                let origin = if path.starts_with("drafts") {
+                    ExtraOrigin::Untracked
+                } else {
+                    ExtraOrigin::Ignored
+                };
+                (path.clone(), origin)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let flagged = BTreeSet::from(["target".to_string()]);
+        let suggested = suggest_skip_buckets(&candidates, &origins, &flagged, 3);
+
+        assert_eq!(suggested.len(), 1);
+        assert_eq!(suggested.get("data"), Some(&4));
+    }
+
     #[test]
     fn filter_candidates_by_skipped_buckets_omits_matching_directory_prefixes() {
         let candidates = vec![
@@ -795,4 +1142,32 @@ mod tests {
         );
         assert_eq!(paths.len(), 110_000);
     }
+
+    #[test]
+    fn extra_globs_match_segments_and_directories() {
+        assert!(extra_matches_glob(".env", Path::new(".env")));
+        assert!(!extra_matches_glob(".env", Path::new(".env.local")));
+        assert!(extra_matches_glob(".env*", Path::new(".env.local")));
+        assert!(extra_matches_glob(
+            "config/*.toml",
+            Path::new("config/dev.toml")
+        ));
+        assert!(!extra_matches_glob(
+            "config/*.toml",
+            Path::new("config/nested/dev.toml")
+        ));
+        assert!(extra_matches_glob(
+            "**/*.toml",
+            Path::new("config/nested/dev.toml")
+        ));
+        assert!(extra_matches_glob(
+            "config",
+            Path::new("config/nested/dev.toml")
+        ));
+        assert!(extra_matches_glob(
+            "./config/",
+            Path::new("config/dev.toml")
+        ));
+        assert!(!extra_matches_glob("", Path::new("config/dev.toml")));
+    }
 }
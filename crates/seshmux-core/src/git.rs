@@ -1,9 +1,10 @@
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
-use crate::command_adapter;
-use crate::command_runner::{CommandOutput, CommandRunner};
+use crate::command_adapter::{self, RunFailure};
+use crate::command_runner::{CommandOutput, CommandRunner, CommandTimedOut};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BranchSource {
@@ -33,6 +34,45 @@ pub struct CommitRef {
     pub short_hash: String,
     pub subject: String,
     pub display: String,
+    pub author: String,
+    pub relative_date: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CommitSearch {
+    #[default]
+    Hash,
+    Message,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeCheckout {
+    pub path: PathBuf,
+    pub branch: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrepMatch {
+    pub path: PathBuf,
+    pub line: usize,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegrateOutcome {
+    Clean(CommandOutput),
+    Conflicts {
+        output: CommandOutput,
+        paths: Vec<String>,
+    },
+}
+
+impl IntegrateOutcome {
+    pub fn output(&self) -> &CommandOutput {
+        match self {
+            Self::Clean(output) | Self::Conflicts { output, .. } => output,
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -45,6 +85,8 @@ pub enum GitError {
     },
     #[error("failed to execute git command: {0}")]
     Execute(String),
+    #[error(transparent)]
+    TimedOut(CommandTimedOut),
     #[error("failed to parse git output: {0}")]
     Parse(String),
     #[error(
@@ -63,48 +105,71 @@ pub fn repo_root(cwd: &Path, runner: &dyn CommandRunner) -> Result<PathBuf, GitE
     )?))
 }
 
-pub fn gitignore_contains_worktrees(repo_root: &Path) -> Result<bool, GitError> {
-    let gitignore_path = repo_root.join(".gitignore");
-
-    if !gitignore_path.exists() {
-        return Ok(false);
+pub fn worktrees_gitignore_entry(repo_root: &Path, worktrees_dir: &Path) -> Option<String> {
+    let relative = worktrees_dir.strip_prefix(repo_root).ok()?;
+    let parts = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    if parts.is_empty() {
+        return None;
     }
 
-    let content = std::fs::read_to_string(&gitignore_path)
-        .map_err(|error| GitError::Execute(error.to_string()))?;
+    Some(format!("{}/", parts.join("/")))
+}
 
-    for line in content.lines().map(str::trim) {
-        if line == "worktrees/" || line == "/worktrees/" {
-            return Ok(true);
+pub fn missing_gitignore_entries(
+    repo_root: &Path,
+    entries: &[String],
+) -> Result<Vec<String>, GitError> {
+    let content = read_gitignore(repo_root)?;
+    let present: BTreeSet<&str> = content.lines().map(str::trim).collect();
+
+    let mut missing: Vec<String> = Vec::new();
+    for entry in entries {
+        let entry = entry.trim();
+        let anchored = format!("/{entry}");
+        if !present.contains(entry)
+            && !present.contains(anchored.as_str())
+            && !missing.iter().any(|seen| seen == entry)
+        {
+            missing.push(entry.to_string());
         }
     }
-
-    Ok(false)
+    Ok(missing)
 }
 
-pub fn ensure_worktrees_gitignore_entry(repo_root: &Path) -> Result<(), GitError> {
-    if gitignore_contains_worktrees(repo_root)? {
-        return Ok(());
+pub fn ensure_gitignore_entries(
+    repo_root: &Path,
+    entries: &[String],
+) -> Result<Vec<String>, GitError> {
+    let missing = missing_gitignore_entries(repo_root, entries)?;
+    if missing.is_empty() {
+        return Ok(missing);
     }
 
-    let gitignore_path = repo_root.join(".gitignore");
-    let mut content = if gitignore_path.exists() {
-        std::fs::read_to_string(&gitignore_path)
-            .map_err(|error| GitError::Execute(error.to_string()))?
-    } else {
-        String::new()
-    };
-
+    let mut content = read_gitignore(repo_root)?;
     if !content.is_empty() && !content.ends_with('\n') {
         content.push('\n');
     }
+    for entry in &missing {
+        content.push_str(entry);
+        content.push('\n');
+    }
 
-    content.push_str("worktrees/\n");
-
-    std::fs::write(&gitignore_path, content)
+    std::fs::write(repo_root.join(".gitignore"), content)
         .map_err(|error| GitError::Execute(error.to_string()))?;
 
-    Ok(())
+    Ok(missing)
+}
+
+fn read_gitignore(repo_root: &Path) -> Result<String, GitError> {
+    let gitignore_path = repo_root.join(".gitignore");
+    if !gitignore_path.exists() {
+        return Ok(String::new());
+    }
+
+    std::fs::read_to_string(&gitignore_path).map_err(|error| GitError::Execute(error.to_string()))
 }
 
 pub fn resolve_current_start_point(
@@ -122,14 +187,33 @@ pub fn resolve_current_start_point(
 
 pub fn create_worktree(
     repo_root: &Path,
-    worktree_name: &str,
+    branch: &str,
     target_path: &Path,
     start_point: &str,
     runner: &dyn CommandRunner,
 ) -> Result<(), GitError> {
     let target = utf8_path(target_path, "worktree path is not valid UTF-8")?;
 
-    let args = ["worktree", "add", "-b", worktree_name, target, start_point];
+    let args = ["worktree", "add", "-b", branch, target, start_point];
+    run_git_checked(runner, &args, Some(repo_root))?;
+
+    Ok(())
+}
+
+pub fn create_worktree_on_branch(
+    repo_root: &Path,
+    target_path: &Path,
+    branch: &str,
+    force: bool,
+    runner: &dyn CommandRunner,
+) -> Result<(), GitError> {
+    let target = utf8_path(target_path, "worktree path is not valid UTF-8")?;
+
+    let mut args = vec!["worktree", "add"];
+    if force {
+        args.push("--force");
+    }
+    args.extend([target, branch]);
     run_git_checked(runner, &args, Some(repo_root))?;
 
     Ok(())
@@ -148,6 +232,63 @@ pub fn current_branch(
     first_non_empty_stdout_line(&output, "git rev-parse returned empty branch name")
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeadState {
+    Branch(String),
+    Detached(String),
+}
+
+pub fn head_state(worktree_path: &Path, runner: &dyn CommandRunner) -> Result<HeadState, GitError> {
+    // `symbolic-ref -q` exits 1, quietly, exactly when HEAD is not a branch ref.
+    let args = ["symbolic-ref", "-q", "HEAD"];
+    let output = run_git(runner, &args, Some(worktree_path))?;
+    if output.status_code != 1 {
+        let output = command_adapter::ensure_success(&args, output).map_err(|failure| {
+            GitError::CommandFailed {
+                command: failure.command,
+                status: failure.status,
+                stderr: failure.stderr,
+            }
+        })?;
+        let reference = first_non_empty_stdout_line(&output, "git symbolic-ref returned no ref")?;
+        let branch = reference
+            .strip_prefix("refs/heads/")
+            .unwrap_or(&reference)
+            .to_string();
+        return Ok(HeadState::Branch(branch));
+    }
+
+    let output = run_git_checked(
+        runner,
+        &["rev-parse", "--short", "HEAD"],
+        Some(worktree_path),
+    )?;
+    first_non_empty_stdout_line(&output, "git rev-parse returned empty commit hash")
+        .map(HeadState::Detached)
+}
+
+pub fn detached_head(
+    worktree_path: &Path,
+    runner: &dyn CommandRunner,
+) -> Result<Option<String>, GitError> {
+    Ok(match head_state(worktree_path, runner)? {
+        HeadState::Branch(_) => None,
+        HeadState::Detached(short_sha) => Some(short_sha),
+    })
+}
+
+pub fn last_commit_unix(
+    worktree_path: &Path,
+    runner: &dyn CommandRunner,
+) -> Result<Option<u64>, GitError> {
+    let output = run_git(runner, &["log", "-1", "--format=%ct"], Some(worktree_path))?;
+    if output.status_code != 0 {
+        return Ok(None);
+    }
+
+    Ok(output.stdout.trim().parse().ok())
+}
+
 pub fn remove_worktree(
     repo_root: &Path,
     target_path: &Path,
@@ -159,6 +300,19 @@ pub fn remove_worktree(
     Ok(())
 }
 
+pub fn move_worktree(
+    repo_root: &Path,
+    from: &Path,
+    to: &Path,
+    runner: &dyn CommandRunner,
+) -> Result<(), GitError> {
+    let from = utf8_path(from, "worktree path is not valid UTF-8")?;
+    let to = utf8_path(to, "worktree destination is not valid UTF-8")?;
+
+    run_git_checked(runner, &["worktree", "move", from, to], Some(repo_root))?;
+    Ok(())
+}
+
 pub fn force_remove_worktree(
     repo_root: &Path,
     target_path: &Path,
@@ -199,6 +353,21 @@ pub fn delete_branch(
     })
 }
 
+pub fn branch_exists(
+    repo_root: &Path,
+    branch_name: &str,
+    runner: &dyn CommandRunner,
+) -> Result<bool, GitError> {
+    let branch = non_empty_trimmed(branch_name, "branch name cannot be empty")?;
+    let reference = format!("refs/heads/{branch}");
+    let output = run_git(
+        runner,
+        &["rev-parse", "--verify", "--quiet", reference.as_str()],
+        Some(repo_root),
+    )?;
+    Ok(output.status_code == 0)
+}
+
 pub fn force_delete_branch(
     repo_root: &Path,
     branch_name: &str,
@@ -259,26 +428,42 @@ pub fn query_branches(
     Ok(filtered)
 }
 
+const COMMIT_LIST_FORMAT: &str = "--format=%H%x1f%h%x1f%s%x1f%an%x1f%ar";
+
 pub fn query_commits(
     repo_root: &Path,
     query: &str,
+    search: CommitSearch,
+    skip: usize,
     limit: usize,
     runner: &dyn CommandRunner,
 ) -> Result<Vec<CommitRef>, GitError> {
     let effective_limit = if limit == 0 { 50 } else { limit };
     let trimmed = query.trim();
+    let paged_by_git = trimmed.is_empty() || search == CommitSearch::Message;
 
-    let output = if trimmed.is_empty() {
-        let limit_value = effective_limit.to_string();
-        let args = ["log", "--format=%H%x1f%h%x1f%s", "-n", limit_value.as_str()];
-        run_git(runner, &args, Some(repo_root))?
-    } else {
-        run_git(
-            runner,
-            &["log", "--all", "--format=%H%x1f%h%x1f%s"],
-            Some(repo_root),
-        )?
-    };
+    let mut args = vec!["log".to_string()];
+    if !trimmed.is_empty() {
+        args.push("--all".to_string());
+    }
+    if !trimmed.is_empty() && search == CommitSearch::Message {
+        // Fixed strings, so a half-typed pattern like `fix(` is not a regex error.
+        args.extend([
+            "--regexp-ignore-case".to_string(),
+            "--fixed-strings".to_string(),
+            format!("--grep={trimmed}"),
+        ]);
+    }
+    args.push(COMMIT_LIST_FORMAT.to_string());
+    if paged_by_git {
+        if skip > 0 {
+            args.push(format!("--skip={skip}"));
+        }
+        args.extend(["-n".to_string(), effective_limit.to_string()]);
+    }
+
+    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+    let output = run_git(runner, &arg_refs, Some(repo_root))?;
 
     if output.status_code != 0 {
         if looks_like_empty_history(&output.stderr) {
@@ -286,132 +471,754 @@ pub fn query_commits(
         }
 
         return Err(GitError::CommandFailed {
-            command: if trimmed.is_empty() {
-                format!("log --format=%H%x1f%h%x1f%s -n {effective_limit}")
-            } else {
-                "log --all --format=%H%x1f%h%x1f%s".to_string()
-            },
+            command: args.join(" "),
             status: output.status_code,
             stderr: output.stderr.trim().to_string(),
         });
     }
 
-    let mut commits = parse_commit_lines(&output.stdout)?;
-
-    if !trimmed.is_empty() {
-        let normalized = trimmed.to_lowercase();
-        commits.retain(|commit| commit.hash.to_lowercase().contains(&normalized));
-        commits.truncate(effective_limit);
+    let commits = parse_commit_lines(&output.stdout)?;
+    if paged_by_git {
+        return Ok(commits);
     }
 
-    Ok(commits)
+    let normalized = trimmed.to_lowercase();
+    Ok(commits
+        .into_iter()
+        .filter(|commit| commit.hash.to_lowercase().contains(&normalized))
+        .skip(skip)
+        .take(effective_limit)
+        .collect())
 }
 
-fn parse_branch_lines(raw: &str, source: BranchSource) -> Vec<BranchRef> {
-    raw.lines()
-        .map(str::trim)
-        .filter(|line| !line.is_empty())
-        .map(|name| BranchRef {
-            name: name.to_string(),
-            display: format!("{} [{}]", name, source.as_str()),
-            source: source.clone(),
+pub fn resolve_commit(
+    repo_root: &Path,
+    revision: &str,
+    runner: &dyn CommandRunner,
+) -> Result<CommitRef, GitError> {
+    let revision = non_empty_trimmed(revision, "revision cannot be empty")?;
+    let output = run_git_checked(
+        runner,
+        &["log", "-n", "1", "--format=%H%x1f%h%x1f%s", revision, "--"],
+        Some(repo_root),
+    )?;
+
+    parse_commit_lines(&output.stdout)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            GitError::Parse(format!("revision '{revision}' did not resolve to a commit"))
         })
-        .collect()
 }
 
-fn parse_commit_lines(raw: &str) -> Result<Vec<CommitRef>, GitError> {
-    let mut commits = Vec::new();
-
-    for line in raw.lines().map(str::trim).filter(|line| !line.is_empty()) {
-        let mut parts = line.splitn(3, '\u{1f}');
-
-        let hash = parts
-            .next()
-            .filter(|value| !value.is_empty())
-            .ok_or_else(|| GitError::Parse("missing commit hash in git log output".to_string()))?;
+pub fn commits_in_range(
+    repo_root: &Path,
+    range: &str,
+    runner: &dyn CommandRunner,
+) -> Result<Vec<CommitRef>, GitError> {
+    let range = non_empty_trimmed(range, "commit range cannot be empty")?;
+    if !range.contains("..") {
+        return Ok(vec![resolve_commit(repo_root, range, runner)?]);
+    }
 
-        let short_hash = parts
-            .next()
-            .filter(|value| !value.is_empty())
-            .ok_or_else(|| GitError::Parse("missing short hash in git log output".to_string()))?;
+    let output = run_git_checked(
+        runner,
+        &["log", "--reverse", "--format=%H%x1f%h%x1f%s", range, "--"],
+        Some(repo_root),
+    )?;
+    parse_commit_lines(&output.stdout)
+}
 
-        let subject = parts
-            .next()
-            .filter(|value| !value.is_empty())
-            .ok_or_else(|| GitError::Parse("missing subject in git log output".to_string()))?;
+pub fn merge_base(
+    repo_root: &Path,
+    left: &str,
+    right: &str,
+    runner: &dyn CommandRunner,
+) -> Result<String, GitError> {
+    let left = non_empty_trimmed(left, "merge-base revision cannot be empty")?;
+    let right = non_empty_trimmed(right, "merge-base revision cannot be empty")?;
+    let output = run_git_checked(runner, &["merge-base", left, right], Some(repo_root))?;
+    first_non_empty_stdout_line(&output, "git merge-base returned empty output")
+}
 
-        commits.push(CommitRef {
-            hash: hash.to_string(),
-            short_hash: short_hash.to_string(),
-            subject: subject.to_string(),
-            display: format!("{short_hash} {subject}"),
-        });
+pub fn default_branch(
+    repo_root: &Path,
+    runner: &dyn CommandRunner,
+) -> Result<Option<String>, GitError> {
+    let output = run_git(
+        runner,
+        &[
+            "symbolic-ref",
+            "--quiet",
+            "--short",
+            "refs/remotes/origin/HEAD",
+        ],
+        Some(repo_root),
+    )?;
+    if output.status_code == 0
+        && let Some(name) = output
+            .stdout
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+    {
+        return Ok(Some(name.to_string()));
     }
 
-    Ok(commits)
-}
+    for candidate in ["main", "master"] {
+        let reference = format!("refs/heads/{candidate}");
+        let output = run_git(
+            runner,
+            &["rev-parse", "--verify", "--quiet", reference.as_str()],
+            Some(repo_root),
+        )?;
+        if output.status_code == 0 {
+            return Ok(Some(candidate.to_string()));
+        }
+    }
 
-fn looks_like_empty_history(stderr: &str) -> bool {
-    let normalized = stderr.to_lowercase();
-    normalized.contains("does not have any commits yet")
-        || normalized.contains("fatal: ambiguous argument 'head'")
-        || normalized.contains("unknown revision or path not in the working tree")
+    Ok(None)
 }
 
-fn looks_like_branch_not_fully_merged(stderr: &str) -> bool {
-    let normalized = stderr.to_lowercase();
-    normalized.contains("not fully merged")
+pub fn log_graph(
+    cwd: &Path,
+    revisions: &[&str],
+    limit: usize,
+    runner: &dyn CommandRunner,
+) -> Result<Vec<String>, GitError> {
+    let limit_value = limit.to_string();
+    let mut args = vec![
+        "log",
+        "--graph",
+        "--oneline",
+        "--decorate",
+        "--color=never",
+        "-n",
+        limit_value.as_str(),
+    ];
+    args.extend_from_slice(revisions);
+    args.push("--");
+
+    let output = run_git_checked(runner, &args, Some(cwd))?;
+    Ok(output.stdout.lines().map(str::to_string).collect())
 }
 
-fn utf8_path<'a>(path: &'a Path, message: &str) -> Result<&'a str, GitError> {
-    path.to_str()
-        .ok_or_else(|| GitError::Parse(message.to_string()))
+pub fn diff_since(
+    cwd: &Path,
+    revision: &str,
+    patch: bool,
+    runner: &dyn CommandRunner,
+) -> Result<Vec<String>, GitError> {
+    let revision = non_empty_trimmed(revision, "diff revision cannot be empty")?;
+    let format = if patch { "--patch-with-stat" } else { "--stat" };
+    let output = run_git_checked(
+        runner,
+        &["diff", "--color=never", format, revision, "--"],
+        Some(cwd),
+    )?;
+    Ok(output.stdout.lines().map(str::to_string).collect())
 }
 
-fn non_empty_trimmed<'a>(value: &'a str, message: &str) -> Result<&'a str, GitError> {
-    let trimmed = value.trim();
-    if trimmed.is_empty() {
-        return Err(GitError::Parse(message.to_string()));
-    }
+pub fn create_detached_worktree(
+    repo_root: &Path,
+    target_path: &Path,
+    revision: &str,
+    runner: &dyn CommandRunner,
+) -> Result<(), GitError> {
+    let target = utf8_path(target_path, "worktree path is not valid UTF-8")?;
 
-    Ok(trimmed)
+    run_git_checked(
+        runner,
+        &["worktree", "add", "--detach", target, revision],
+        Some(repo_root),
+    )?;
+    Ok(())
 }
 
-fn first_non_empty_stdout_line(output: &CommandOutput, message: &str) -> Result<String, GitError> {
-    output
+pub fn list_worktree_paths(
+    repo_root: &Path,
+    runner: &dyn CommandRunner,
+) -> Result<Vec<PathBuf>, GitError> {
+    let output = run_git_checked(
+        runner,
+        &["worktree", "list", "--porcelain"],
+        Some(repo_root),
+    )?;
+    Ok(output
         .stdout
         .lines()
-        .next()
-        .and_then(|line| {
-            let trimmed = line.trim();
-            (!trimmed.is_empty()).then_some(trimmed.to_string())
-        })
-        .ok_or_else(|| GitError::Parse(message.to_string()))
+        .filter_map(|line| line.strip_prefix("worktree "))
+        .map(|path| PathBuf::from(path.trim()))
+        .collect())
 }
 
-fn run_git_checked(
+pub fn list_worktree_checkouts(
+    repo_root: &Path,
     runner: &dyn CommandRunner,
-    args: &[&str],
-    cwd: Option<&Path>,
-) -> Result<CommandOutput, GitError> {
-    let output = run_git(runner, args, cwd)?;
-    command_adapter::ensure_success(args, output).map_err(|failure| GitError::CommandFailed {
-        command: failure.command,
-        status: failure.status,
-        stderr: failure.stderr,
-    })
+) -> Result<Vec<WorktreeCheckout>, GitError> {
+    let output = run_git_checked(
+        runner,
+        &["worktree", "list", "--porcelain"],
+        Some(repo_root),
+    )?;
+
+    let mut checkouts = Vec::new();
+    for line in output.stdout.lines() {
+        if let Some(path) = line.strip_prefix("worktree ") {
+            checkouts.push(WorktreeCheckout {
+                path: PathBuf::from(path.trim()),
+                branch: None,
+            });
+        } else if let Some(reference) = line.strip_prefix("branch ")
+            && let Some(checkout) = checkouts.last_mut()
+        {
+            let reference = reference.trim();
+            let branch = reference.strip_prefix("refs/heads/").unwrap_or(reference);
+            checkout.branch = Some(branch.to_string());
+        }
+    }
+
+    Ok(checkouts)
 }
 
-fn run_git(
+pub fn grep(
+    cwd: &Path,
+    query: &str,
     runner: &dyn CommandRunner,
-    args: &[&str],
-    cwd: Option<&Path>,
-) -> Result<CommandOutput, GitError> {
-    command_adapter::run_program(runner, "git", args, cwd).map_err(GitError::Execute)
+) -> Result<Vec<GrepMatch>, GitError> {
+    let query = non_empty_trimmed(query, "search query cannot be empty")?;
+    let mut args = vec![
+        "grep",
+        "-n",
+        "-z",
+        "-I",
+        "--no-color",
+        "--untracked",
+        "--fixed-strings",
+    ];
+    if !query.chars().any(char::is_uppercase) {
+        args.push("--ignore-case");
+    }
+    args.extend(["-e", query]);
+
+    let output = run_git(runner, &args, Some(cwd))?;
+    // git grep exits 1 when nothing matched.
+    if output.status_code == 1 && output.stderr.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    if output.status_code != 0 {
+        return Err(GitError::CommandFailed {
+            command: args.join(" "),
+            status: output.status_code,
+            stderr: output.stderr.trim().to_string(),
+        });
+    }
+
+    parse_grep_lines(&output.stdout)
 }
 
-#[cfg(test)]
-mod tests {
+pub fn list_ignored_directories(
+    repo_root: &Path,
+    runner: &dyn CommandRunner,
+) -> Result<Vec<PathBuf>, GitError> {
+    let output = run_git_checked(
+        runner,
+        &[
+            "ls-files",
+            "-o",
+            "-i",
+            "--directory",
+            "-z",
+            "--exclude-standard",
+            "--",
+            ".",
+        ],
+        Some(repo_root),
+    )?;
+    Ok(output
+        .stdout
+        .split('\0')
+        .filter_map(|entry| entry.strip_suffix('/'))
+        .filter(|entry| !entry.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+pub fn clone_with_separate_git_dir(
+    url: &str,
+    destination: &Path,
+    git_dir: &Path,
+    runner: &dyn CommandRunner,
+) -> Result<(), GitError> {
+    let url = non_empty_trimmed(url, "clone URL cannot be empty")?;
+    let destination = utf8_path(destination, "clone destination is not valid UTF-8")?;
+    let git_dir = utf8_path(git_dir, "git directory path is not valid UTF-8")?;
+    let args = ["clone", "--separate-git-dir", git_dir, url, destination];
+
+    let status = runner
+        .run_interactive("git", &args, None)
+        .map_err(|error| GitError::Execute(error.to_string()))?;
+    if status != 0 {
+        return Err(GitError::CommandFailed {
+            command: args.join(" "),
+            status,
+            stderr: String::new(),
+        });
+    }
+
+    Ok(())
+}
+
+pub fn init_repo(
+    repo_root: &Path,
+    initial_branch: &str,
+    runner: &dyn CommandRunner,
+) -> Result<(), GitError> {
+    let path = utf8_path(repo_root, "repository path is not valid UTF-8")?;
+    let branch = non_empty_trimmed(initial_branch, "initial branch cannot be empty")?;
+    run_git_checked(runner, &["init", "-q", path], None)?;
+    // Older git has no --initial-branch, so point the unborn HEAD at the branch instead.
+    let head = format!("refs/heads/{branch}");
+    run_git_checked(runner, &["symbolic-ref", "HEAD", &head], Some(repo_root))?;
+    Ok(())
+}
+
+pub fn set_local_config(
+    repo_root: &Path,
+    key: &str,
+    value: &str,
+    runner: &dyn CommandRunner,
+) -> Result<(), GitError> {
+    run_git_checked(runner, &["config", "--local", key, value], Some(repo_root))?;
+    Ok(())
+}
+
+pub fn commit_all(
+    repo_root: &Path,
+    message: &str,
+    runner: &dyn CommandRunner,
+) -> Result<(), GitError> {
+    let message = non_empty_trimmed(message, "commit message cannot be empty")?;
+    run_git_checked(runner, &["add", "-A"], Some(repo_root))?;
+    run_git_checked(runner, &["commit", "-q", "-m", message], Some(repo_root))?;
+    Ok(())
+}
+
+pub fn create_branch(
+    repo_root: &Path,
+    branch: &str,
+    runner: &dyn CommandRunner,
+) -> Result<(), GitError> {
+    let branch = non_empty_trimmed(branch, "branch name cannot be empty")?;
+    run_git_checked(runner, &["branch", branch], Some(repo_root))?;
+    Ok(())
+}
+
+pub fn create_branch_from(
+    repo_root: &Path,
+    branch: &str,
+    start_point: &str,
+    runner: &dyn CommandRunner,
+) -> Result<(), GitError> {
+    let branch = non_empty_trimmed(branch, "branch name cannot be empty")?;
+    let start_point = non_empty_trimmed(start_point, "start point cannot be empty")?;
+    run_git_checked(runner, &["branch", branch, start_point], Some(repo_root))?;
+    Ok(())
+}
+
+pub fn switch_branch(
+    repo_root: &Path,
+    branch: &str,
+    runner: &dyn CommandRunner,
+) -> Result<(), GitError> {
+    let branch = non_empty_trimmed(branch, "branch name cannot be empty")?;
+    run_git_checked(runner, &["checkout", "-q", branch], Some(repo_root))?;
+    Ok(())
+}
+
+pub fn uses_lfs(repo_root: &Path) -> bool {
+    std::fs::read_to_string(repo_root.join(".gitattributes"))
+        .is_ok_and(|content| content.lines().any(|line| line.contains("filter=lfs")))
+}
+
+pub fn has_submodules(repo_root: &Path) -> bool {
+    repo_root.join(".gitmodules").is_file()
+}
+
+pub fn index_checkout_size(repo_root: &Path, runner: &dyn CommandRunner) -> Option<u64> {
+    let dot_git = repo_root.join(".git");
+    let git_dir = if dot_git.is_dir() {
+        dot_git
+    } else {
+        let content = std::fs::read_to_string(&dot_git).ok()?;
+        repo_root.join(content.trim().strip_prefix("gitdir:")?.trim())
+    };
+    let index = std::fs::read(git_dir.join("index")).ok()?;
+    let output = run_git_checked(
+        runner,
+        &["rev-parse", "--show-object-format"],
+        Some(repo_root),
+    )
+    .ok()?;
+    let oid_len = match output.stdout.trim() {
+        "sha1" => 20,
+        "sha256" => 32,
+        _ => return None,
+    };
+    parse_index_size(&index, oid_len)
+}
+
+pub fn lfs_pull(worktree_path: &Path, runner: &dyn CommandRunner) -> Result<(), GitError> {
+    run_git_untimed_checked(runner, &["lfs", "pull"], Some(worktree_path))?;
+    Ok(())
+}
+
+pub fn update_submodules(worktree_path: &Path, runner: &dyn CommandRunner) -> Result<(), GitError> {
+    run_git_untimed_checked(
+        runner,
+        &["submodule", "update", "--init", "--recursive"],
+        Some(worktree_path),
+    )?;
+    Ok(())
+}
+
+pub fn remotes(repo_root: &Path, runner: &dyn CommandRunner) -> Result<Vec<String>, GitError> {
+    let output = run_git_checked(runner, &["remote"], Some(repo_root))?;
+    Ok(output
+        .stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+pub fn fetch_remote(
+    cwd: &Path,
+    remote: &str,
+    runner: &dyn CommandRunner,
+    on_line: &mut dyn FnMut(&str),
+) -> Result<CommandOutput, GitError> {
+    let remote = non_empty_trimmed(remote, "remote name cannot be empty")?;
+    let args = ["fetch", remote];
+    ensure_git_success(&args, run_git_streaming(runner, &args, cwd, on_line)?)
+}
+
+pub fn rebase_onto(
+    worktree_path: &Path,
+    upstream: &str,
+    runner: &dyn CommandRunner,
+    on_line: &mut dyn FnMut(&str),
+) -> Result<IntegrateOutcome, GitError> {
+    let upstream = non_empty_trimmed(upstream, "upstream cannot be empty")?;
+    let args = ["rebase", upstream];
+    let output = run_git_streaming(runner, &args, worktree_path, on_line)?;
+    integrate_outcome(worktree_path, &args, output, runner)
+}
+
+pub fn merge_upstream(
+    worktree_path: &Path,
+    upstream: &str,
+    runner: &dyn CommandRunner,
+    on_line: &mut dyn FnMut(&str),
+) -> Result<IntegrateOutcome, GitError> {
+    let upstream = non_empty_trimmed(upstream, "upstream cannot be empty")?;
+    let args = ["merge", "--no-edit", upstream];
+    let output = run_git_streaming(runner, &args, worktree_path, on_line)?;
+    integrate_outcome(worktree_path, &args, output, runner)
+}
+
+pub fn unmerged_paths(
+    worktree_path: &Path,
+    runner: &dyn CommandRunner,
+) -> Result<Vec<String>, GitError> {
+    let output = run_git_checked(
+        runner,
+        &["diff", "--name-only", "--diff-filter=U"],
+        Some(worktree_path),
+    )?;
+    Ok(output
+        .stdout
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+pub fn changed_files(cwd: &Path, runner: &dyn CommandRunner) -> Result<Vec<String>, GitError> {
+    let output = run_git_checked(runner, &["diff", "--name-only", "HEAD"], Some(cwd))?;
+    let mut files: Vec<String> = output
+        .stdout
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect();
+    files.extend(untracked_files(cwd, runner)?);
+    Ok(files)
+}
+
+/// Untracked, non-ignored files anywhere in the checkout, relative to its top level.
+/// `git stash create` leaves these out, so carrying changes copies them separately.
+pub fn untracked_files(cwd: &Path, runner: &dyn CommandRunner) -> Result<Vec<String>, GitError> {
+    let output = run_git_checked(
+        runner,
+        &[
+            "ls-files",
+            "--others",
+            "--exclude-standard",
+            "--full-name",
+            "-z",
+            "--",
+            ":/",
+        ],
+        Some(cwd),
+    )?;
+    Ok(output
+        .stdout
+        .split('\0')
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+pub fn stash_create(cwd: &Path, runner: &dyn CommandRunner) -> Result<Option<String>, GitError> {
+    let output = run_git_checked(runner, &["stash", "create"], Some(cwd))?;
+    let commit = output.stdout.trim();
+    Ok((!commit.is_empty()).then(|| commit.to_string()))
+}
+
+pub fn stash_apply(
+    worktree_path: &Path,
+    stash: &str,
+    runner: &dyn CommandRunner,
+) -> Result<IntegrateOutcome, GitError> {
+    let stash = non_empty_trimmed(stash, "stash commit cannot be empty")?;
+    run_integrating_step(worktree_path, &["stash", "apply", stash], runner)
+}
+
+fn run_integrating_step(
+    worktree_path: &Path,
+    args: &[&str],
+    runner: &dyn CommandRunner,
+) -> Result<IntegrateOutcome, GitError> {
+    let output = run_git(runner, args, Some(worktree_path))?;
+    integrate_outcome(worktree_path, args, output, runner)
+}
+
+fn integrate_outcome(
+    worktree_path: &Path,
+    args: &[&str],
+    output: CommandOutput,
+    runner: &dyn CommandRunner,
+) -> Result<IntegrateOutcome, GitError> {
+    if output.status_code != 0 {
+        let paths = unmerged_paths(worktree_path, runner)?;
+        if !paths.is_empty() {
+            return Ok(IntegrateOutcome::Conflicts { output, paths });
+        }
+    }
+    ensure_git_success(args, output).map(IntegrateOutcome::Clean)
+}
+
+fn parse_grep_lines(raw: &str) -> Result<Vec<GrepMatch>, GitError> {
+    let mut matches = Vec::new();
+
+    for line in raw.lines().filter(|line| !line.is_empty()) {
+        let mut parts = line.splitn(3, '\0');
+        let (Some(path), Some(number), Some(text)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(GitError::Parse(format!("unexpected git grep line: {line}")));
+        };
+        let line = number
+            .parse()
+            .map_err(|_| GitError::Parse(format!("invalid line number in git grep: {number}")))?;
+
+        matches.push(GrepMatch {
+            path: PathBuf::from(path),
+            line,
+            text: text.to_string(),
+        });
+    }
+
+    Ok(matches)
+}
+
+// Index entries are 40 bytes of stat data (the file size last), the object id, 16-bit flags, an
+// extra 16 bits of flags in version 3+ when flagged, then the path: NUL-padded to a multiple of 8
+// bytes up to version 3, prefix-compressed against the previous path in version 4.
+fn parse_index_size(data: &[u8], oid_len: usize) -> Option<u64> {
+    let read_u32 = |offset: usize| {
+        data.get(offset..offset + 4)
+            .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    };
+    if data.get(..4)? != b"DIRC" {
+        return None;
+    }
+    let version = read_u32(4)?;
+    if !(2..=4).contains(&version) {
+        return None;
+    }
+
+    let mut total = 0u64;
+    let mut offset = 12;
+    for _ in 0..read_u32(8)? {
+        let start = offset;
+        total += u64::from(read_u32(start + 36)?);
+        let flags_at = start + 40 + oid_len;
+        let flags = data.get(flags_at..flags_at + 2)?;
+        offset = flags_at + 2;
+        if version >= 3 && flags[0] & 0x40 != 0 {
+            offset += 2;
+        }
+        if version == 4 {
+            while data.get(offset)? & 0x80 != 0 {
+                offset += 1;
+            }
+            offset += 1;
+        }
+        offset += data.get(offset..)?.iter().position(|byte| *byte == 0)? + 1;
+        if version < 4 {
+            offset = start + (offset - start).div_ceil(8) * 8;
+        }
+    }
+    Some(total)
+}
+
+fn parse_branch_lines(raw: &str, source: BranchSource) -> Vec<BranchRef> {
+    raw.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|name| BranchRef {
+            name: name.to_string(),
+            display: format!("{} [{}]", name, source.as_str()),
+            source: source.clone(),
+        })
+        .collect()
+}
+
+fn parse_commit_lines(raw: &str) -> Result<Vec<CommitRef>, GitError> {
+    let mut commits = Vec::new();
+
+    for line in raw.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let mut parts = line.splitn(5, '\u{1f}');
+
+        let hash = parts
+            .next()
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| GitError::Parse("missing commit hash in git log output".to_string()))?;
+
+        let short_hash = parts
+            .next()
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| GitError::Parse("missing short hash in git log output".to_string()))?;
+
+        let subject = parts
+            .next()
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| GitError::Parse("missing subject in git log output".to_string()))?;
+
+        commits.push(CommitRef {
+            hash: hash.to_string(),
+            short_hash: short_hash.to_string(),
+            subject: subject.to_string(),
+            display: format!("{short_hash} {subject}"),
+            author: parts.next().unwrap_or_default().to_string(),
+            relative_date: parts.next().unwrap_or_default().to_string(),
+        });
+    }
+
+    Ok(commits)
+}
+
+fn looks_like_empty_history(stderr: &str) -> bool {
+    let normalized = stderr.to_lowercase();
+    normalized.contains("does not have any commits yet")
+        || normalized.contains("fatal: ambiguous argument 'head'")
+        || normalized.contains("unknown revision or path not in the working tree")
+}
+
+fn looks_like_branch_not_fully_merged(stderr: &str) -> bool {
+    let normalized = stderr.to_lowercase();
+    normalized.contains("not fully merged")
+}
+
+fn utf8_path<'a>(path: &'a Path, message: &str) -> Result<&'a str, GitError> {
+    path.to_str()
+        .ok_or_else(|| GitError::Parse(message.to_string()))
+}
+
+fn non_empty_trimmed<'a>(value: &'a str, message: &str) -> Result<&'a str, GitError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(GitError::Parse(message.to_string()));
+    }
+
+    Ok(trimmed)
+}
+
+fn first_non_empty_stdout_line(output: &CommandOutput, message: &str) -> Result<String, GitError> {
+    output
+        .stdout
+        .lines()
+        .next()
+        .and_then(|line| {
+            let trimmed = line.trim();
+            (!trimmed.is_empty()).then_some(trimmed.to_string())
+        })
+        .ok_or_else(|| GitError::Parse(message.to_string()))
+}
+
+fn run_git_checked(
+    runner: &dyn CommandRunner,
+    args: &[&str],
+    cwd: Option<&Path>,
+) -> Result<CommandOutput, GitError> {
+    ensure_git_success(args, run_git(runner, args, cwd)?)
+}
+
+fn run_git_untimed_checked(
+    runner: &dyn CommandRunner,
+    args: &[&str],
+    cwd: Option<&Path>,
+) -> Result<CommandOutput, GitError> {
+    let output = runner
+        .run_untimed("git", args, cwd)
+        .map_err(|error| GitError::Execute(error.to_string()))?;
+    ensure_git_success(args, output)
+}
+
+fn ensure_git_success(args: &[&str], output: CommandOutput) -> Result<CommandOutput, GitError> {
+    command_adapter::ensure_success(args, output).map_err(|failure| GitError::CommandFailed {
+        command: failure.command,
+        status: failure.status,
+        stderr: failure.stderr,
+    })
+}
+
+fn run_git_streaming(
+    runner: &dyn CommandRunner,
+    args: &[&str],
+    cwd: &Path,
+    on_line: &mut dyn FnMut(&str),
+) -> Result<CommandOutput, GitError> {
+    runner
+        .run_streaming("git", args, Some(cwd), on_line)
+        .map_err(|error| GitError::Execute(error.to_string()))
+}
+
+fn run_git(
+    runner: &dyn CommandRunner,
+    args: &[&str],
+    cwd: Option<&Path>,
+) -> Result<CommandOutput, GitError> {
+    command_adapter::run_program_timed(runner, "git", args, cwd).map_err(|failure| match failure {
+        RunFailure::TimedOut(timeout) => GitError::TimedOut(timeout),
+        RunFailure::Execute(message) => GitError::Execute(message),
+    })
+}
+
+#[cfg(test)]
+mod tests {
     use crate::test_support::{RecordingRunner, output};
     use std::path::Path;
 
@@ -431,6 +1238,83 @@ mod tests {
         assert_eq!(branches[3].display, "origin/main [remote]");
     }
 
+    #[test]
+    fn lfs_and_submodules_are_detected_from_the_repo_root() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        assert!(!uses_lfs(temp.path()));
+        assert!(!has_submodules(temp.path()));
+
+        std::fs::write(
+            temp.path().join(".gitattributes"),
+            "*.md text\n*.psd filter=lfs diff=lfs merge=lfs -text\n",
+        )
+        .expect("attributes");
+        std::fs::write(temp.path().join(".gitmodules"), "").expect("modules");
+        assert!(uses_lfs(temp.path()));
+        assert!(has_submodules(temp.path()));
+
+        let runner = RecordingRunner::from_outputs(vec![
+            output("", "", 0),
+            output("", "fatal: no submodule mapping", 128),
+        ]);
+        lfs_pull(Path::new("/repo/worktrees/w1"), &runner).expect("lfs pull");
+        let error = update_submodules(Path::new("/repo/worktrees/w1"), &runner)
+            .expect_err("submodules fail");
+        assert!(error.to_string().contains("no submodule mapping"));
+
+        let calls = runner.calls();
+        assert_eq!(calls[0].args, vec!["lfs", "pull"]);
+        assert_eq!(
+            calls[1].args,
+            vec!["submodule", "update", "--init", "--recursive"]
+        );
+        assert_eq!(
+            calls[1].cwd.as_deref(),
+            Some(Path::new("/repo/worktrees/w1"))
+        );
+    }
+
+    #[test]
+    fn default_branch_falls_back_to_local_main_then_master() {
+        let runner = RecordingRunner::from_outputs(vec![
+            output("origin/trunk\n", "", 0),
+            output("", "", 1),
+            output("", "", 1),
+            output("", "", 0),
+            output("", "", 1),
+            output("", "", 1),
+            output("", "", 1),
+        ]);
+
+        let repo = Path::new(".");
+        assert_eq!(
+            default_branch(repo, &runner).expect("remote head"),
+            Some("origin/trunk".to_string())
+        );
+        assert_eq!(
+            default_branch(repo, &runner).expect("local master"),
+            Some("master".to_string())
+        );
+        assert_eq!(default_branch(repo, &runner).expect("none"), None);
+    }
+
+    #[test]
+    fn list_worktree_checkouts_reads_branches_and_detached_heads() {
+        let runner = RecordingRunner::from_outputs(vec![output(
+            "worktree /code/repo\nHEAD abc\nbranch refs/heads/main\n\nworktree /code/repo/worktrees/review\nHEAD def\ndetached\n\nworktree /code/repo/worktrees/w1\nHEAD 123\nbranch refs/heads/feature/w1\n",
+            "",
+            0,
+        )]);
+
+        let checkouts = list_worktree_checkouts(Path::new("."), &runner).expect("checkouts");
+
+        assert_eq!(checkouts.len(), 3);
+        assert_eq!(checkouts[0].branch.as_deref(), Some("main"));
+        assert_eq!(checkouts[1].branch, None);
+        assert_eq!(checkouts[2].path, PathBuf::from("/code/repo/worktrees/w1"));
+        assert_eq!(checkouts[2].branch.as_deref(), Some("feature/w1"));
+    }
+
     #[test]
     fn query_commits_returns_latest_list() {
         let runner = RecordingRunner::from_outputs(vec![output(
@@ -439,7 +1323,8 @@ mod tests {
             0,
         )]);
 
-        let commits = query_commits(Path::new("."), "", 50, &runner).expect("commits");
+        let commits =
+            query_commits(Path::new("."), "", CommitSearch::Hash, 0, 50, &runner).expect("commits");
 
         assert_eq!(commits.len(), 1);
         assert_eq!(commits[0].display, "aaaaaaa first");
@@ -453,13 +1338,67 @@ mod tests {
             0,
         )]);
 
-        let commits = query_commits(Path::new("."), "aaaa", 2, &runner).expect("commits");
+        let commits = query_commits(Path::new("."), "aaaa", CommitSearch::Hash, 0, 2, &runner)
+            .expect("commits");
 
         assert_eq!(commits.len(), 2);
         assert_eq!(commits[0].short_hash, "aaaaaaa");
         assert_eq!(commits[1].short_hash, "aaaabbb");
     }
 
+    #[test]
+    fn query_commits_pages_message_search_through_git_log_grep() {
+        let runner = RecordingRunner::from_outputs(vec![output(
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\u{1f}aaaaaaa\u{1f}fix(parser): quotes\u{1f}Ada Lovelace\u{1f}3 days ago\n",
+            "",
+            0,
+        )]);
+
+        let commits = query_commits(
+            Path::new("."),
+            " fix( ",
+            CommitSearch::Message,
+            50,
+            50,
+            &runner,
+        )
+        .expect("commits");
+
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].subject, "fix(parser): quotes");
+        assert_eq!(commits[0].author, "Ada Lovelace");
+        assert_eq!(commits[0].relative_date, "3 days ago");
+        assert_eq!(
+            runner.calls()[0].args,
+            vec![
+                "log",
+                "--all",
+                "--regexp-ignore-case",
+                "--fixed-strings",
+                "--grep=fix(",
+                "--format=%H%x1f%h%x1f%s%x1f%an%x1f%ar",
+                "--skip=50",
+                "-n",
+                "50",
+            ]
+        );
+    }
+
+    #[test]
+    fn query_commits_skips_earlier_pages_of_a_hash_search() {
+        let runner = RecordingRunner::from_outputs(vec![output(
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\u{1f}aaaaaaa\u{1f}first\nbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb\u{1f}bbbbbbb\u{1f}second\naaaabbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb\u{1f}aaaabbb\u{1f}third\n",
+            "",
+            0,
+        )]);
+
+        let commits = query_commits(Path::new("."), "aaaa", CommitSearch::Hash, 1, 2, &runner)
+            .expect("commits");
+
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].short_hash, "aaaabbb");
+    }
+
     #[test]
     fn query_commits_returns_empty_when_history_is_empty() {
         let runner = RecordingRunner::from_outputs(vec![output(
@@ -468,7 +1407,8 @@ mod tests {
             128,
         )]);
 
-        let commits = query_commits(Path::new("."), "", 50, &runner).expect("no commits");
+        let commits = query_commits(Path::new("."), "", CommitSearch::Hash, 0, 50, &runner)
+            .expect("no commits");
         assert!(commits.is_empty());
     }
 
@@ -496,6 +1436,144 @@ mod tests {
         assert_eq!(branch, "feature-1");
     }
 
+    #[test]
+    fn detached_head_reports_the_short_hash_only_without_a_branch() {
+        let runner = RecordingRunner::from_outputs(vec![
+            output("refs/heads/main\n", "", 0),
+            output("", "", 1),
+            output("1a2b3c4\n", "", 0),
+        ]);
+
+        assert_eq!(
+            detached_head(Path::new("."), &runner).expect("on branch"),
+            None
+        );
+        assert_eq!(
+            detached_head(Path::new("."), &runner).expect("detached"),
+            Some("1a2b3c4".to_string())
+        );
+        assert_eq!(runner.calls()[2].args, vec!["rev-parse", "--short", "HEAD"]);
+    }
+
+    #[test]
+    fn rebase_onto_streams_its_output_and_reports_conflicted_paths() {
+        let runner = RecordingRunner::from_outputs(vec![
+            output("", "CONFLICT (content): Merge conflict in src/lib.rs", 1),
+            output("src/lib.rs\n", "", 0),
+        ]);
+
+        let mut lines = Vec::new();
+        let outcome = rebase_onto(Path::new("/tmp/wt"), "origin/main", &runner, &mut |line| {
+            lines.push(line.to_string())
+        })
+        .expect("rebase");
+
+        assert_eq!(
+            lines,
+            vec!["CONFLICT (content): Merge conflict in src/lib.rs"]
+        );
+        assert!(matches!(
+            outcome,
+            IntegrateOutcome::Conflicts { ref paths, .. } if paths == &["src/lib.rs"]
+        ));
+        assert_eq!(runner.calls()[0].args, vec!["rebase", "origin/main"]);
+    }
+
+    #[test]
+    fn changed_files_lists_untracked_files_after_the_modified_ones() {
+        let runner = RecordingRunner::from_outputs(vec![
+            output("src/lib.rs\n", "", 0),
+            output("notes/todo.md\0src/new file.rs\0", "", 0),
+        ]);
+
+        assert_eq!(
+            changed_files(Path::new("/tmp/repo/src"), &runner).expect("changed"),
+            vec!["src/lib.rs", "notes/todo.md", "src/new file.rs"]
+        );
+        assert_eq!(
+            runner.calls()[1].args,
+            vec![
+                "ls-files",
+                "--others",
+                "--exclude-standard",
+                "--full-name",
+                "-z",
+                "--",
+                ":/"
+            ]
+        );
+    }
+
+    #[test]
+    fn stash_create_is_none_without_changes_and_apply_tells_conflicts_from_failures() {
+        let runner = RecordingRunner::from_outputs(vec![
+            output("\n", "", 0),
+            output("0f1e2d3c\n", "", 0),
+            output("", "CONFLICT (content)", 1),
+            output("src/lib.rs\n", "", 0),
+            output("", "error: cannot apply", 1),
+            output("", "", 0),
+        ]);
+
+        assert_eq!(stash_create(Path::new("."), &runner).expect("clean"), None);
+        assert_eq!(
+            stash_create(Path::new("."), &runner).expect("dirty"),
+            Some("0f1e2d3c".to_string())
+        );
+        let applied = stash_apply(Path::new("/tmp/wt"), "0f1e2d3c", &runner).expect("apply");
+        assert!(matches!(
+            applied,
+            IntegrateOutcome::Conflicts { paths, .. } if paths == ["src/lib.rs"]
+        ));
+        let error = stash_apply(Path::new("/tmp/wt"), "0f1e2d3c", &runner)
+            .expect_err("a failure without conflicts is an error");
+        assert!(matches!(error, GitError::CommandFailed { status: 1, .. }));
+
+        let calls = runner.calls();
+        assert_eq!(calls[0].args, vec!["stash", "create"]);
+        assert_eq!(calls[2].args, vec!["stash", "apply", "0f1e2d3c"]);
+        assert_eq!(calls[2].cwd.as_deref(), Some(Path::new("/tmp/wt")));
+    }
+
+    #[test]
+    fn head_state_names_the_branch_without_its_ref_prefix() {
+        let runner =
+            RecordingRunner::from_outputs(vec![output("refs/heads/feature/login\n", "", 0)]);
+
+        assert_eq!(
+            head_state(Path::new("."), &runner).expect("on branch"),
+            HeadState::Branch("feature/login".to_string())
+        );
+    }
+
+    #[test]
+    fn grep_parses_matches_and_treats_exit_one_as_no_matches() {
+        let runner = RecordingRunner::from_outputs(vec![
+            output(
+                "src/main.rs\x0012\x00    let port = 8080;\nREADME.md\x003\x00Port: 8080\n",
+                "",
+                0,
+            ),
+            output("", "", 1),
+        ]);
+
+        let matches = grep(Path::new("."), "port", &runner).expect("grep");
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].path, Path::new("src/main.rs"));
+        assert_eq!(matches[0].line, 12);
+        assert_eq!(matches[0].text, "    let port = 8080;");
+        assert!(
+            grep(Path::new("."), "Missing", &runner)
+                .expect("grep")
+                .is_empty()
+        );
+
+        let calls = runner.calls();
+        assert!(calls[0].args.contains(&"--ignore-case".to_string()));
+        assert!(!calls[1].args.contains(&"--ignore-case".to_string()));
+        assert_eq!(calls[1].args[calls[1].args.len() - 2..], ["-e", "Missing"]);
+    }
+
     #[test]
     fn delete_branch_safe_reports_not_fully_merged() {
         let runner = RecordingRunner::from_outputs(vec![output(
@@ -517,10 +1595,250 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn move_worktree_passes_source_and_destination() {
+        let runner = RecordingRunner::from_outputs(vec![output("", "", 0)]);
+        move_worktree(
+            Path::new("."),
+            Path::new("/repo/worktrees/w1"),
+            Path::new("/repo/worktrees/.seshmux-trash/w1-1"),
+            &runner,
+        )
+        .expect("move");
+        assert_eq!(
+            runner.calls()[0].args,
+            vec![
+                "worktree",
+                "move",
+                "/repo/worktrees/w1",
+                "/repo/worktrees/.seshmux-trash/w1-1"
+            ]
+        );
+    }
+
+    #[test]
+    fn commits_in_range_resolves_single_revision_without_walking_history() {
+        let runner = RecordingRunner::from_outputs(vec![output(
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\u{1f}aaaaaaa\u{1f}first\n",
+            "",
+            0,
+        )]);
+
+        let commits = commits_in_range(Path::new("."), "aaaaaaa", &runner).expect("commits");
+
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].short_hash, "aaaaaaa");
+        assert_eq!(
+            runner.calls()[0].args,
+            vec!["log", "-n", "1", "--format=%H%x1f%h%x1f%s", "aaaaaaa", "--"]
+        );
+    }
+
+    #[test]
+    fn commits_in_range_lists_range_oldest_first() {
+        let runner = RecordingRunner::from_outputs(vec![output(
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\u{1f}aaaaaaa\u{1f}first\nbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb\u{1f}bbbbbbb\u{1f}second\n",
+            "",
+            0,
+        )]);
+
+        let commits = commits_in_range(Path::new("."), "main..feature", &runner).expect("commits");
+
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[1].subject, "second");
+        assert!(runner.calls()[0].args.contains(&"--reverse".to_string()));
+    }
+
+    #[test]
+    fn list_worktree_paths_reads_porcelain_entries() {
+        let runner = RecordingRunner::from_outputs(vec![output(
+            "worktree /tmp/repo\nHEAD abc\nbranch refs/heads/main\n\nworktree /tmp/repo/worktrees/.review/abc\nHEAD abc\ndetached\n",
+            "",
+            0,
+        )]);
+
+        let paths = list_worktree_paths(Path::new("."), &runner).expect("paths");
+
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/tmp/repo"),
+                PathBuf::from("/tmp/repo/worktrees/.review/abc")
+            ]
+        );
+    }
+
+    #[test]
+    fn list_ignored_directories_keeps_only_directory_entries() {
+        let runner = RecordingRunner::from_outputs(vec![output(
+            "target/\0.env\0web/node_modules/\0",
+            "",
+            0,
+        )]);
+
+        let dirs = list_ignored_directories(Path::new("."), &runner).expect("dirs");
+
+        assert_eq!(
+            dirs,
+            vec![PathBuf::from("target"), PathBuf::from("web/node_modules")]
+        );
+    }
+
     #[test]
     fn force_delete_branch_uses_capital_d() {
         let runner = RecordingRunner::from_outputs(vec![output("", "", 0)]);
         let result = force_delete_branch(Path::new("."), "feature-1", &runner);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn create_branch_from_passes_the_start_point() {
+        let runner = RecordingRunner::from_outputs(vec![output("", "", 0)]);
+        create_branch_from(Path::new("."), "feature-1", "HEAD", &runner).expect("branch");
+        assert_eq!(runner.calls()[0].args, vec!["branch", "feature-1", "HEAD"]);
+    }
+
+    #[test]
+    fn clone_with_separate_git_dir_runs_interactively() {
+        let runner = RecordingRunner::new(Vec::new(), vec![Ok(0)]);
+
+        clone_with_separate_git_dir(
+            "git@github.com:acme/widgets.git",
+            Path::new("/tmp/widgets"),
+            Path::new("/tmp/widgets.git"),
+            &runner,
+        )
+        .expect("clone");
+
+        let calls = runner.calls();
+        assert!(calls[0].interactive);
+        assert_eq!(
+            calls[0].args,
+            vec![
+                "clone",
+                "--separate-git-dir",
+                "/tmp/widgets.git",
+                "git@github.com:acme/widgets.git",
+                "/tmp/widgets"
+            ]
+        );
+    }
+
+    #[test]
+    fn init_repo_points_head_at_the_initial_branch() {
+        let runner = RecordingRunner::from_outputs(vec![output("", "", 0), output("", "", 0)]);
+
+        init_repo(Path::new("/tmp/demo"), "main", &runner).expect("init");
+
+        let calls = runner.calls();
+        assert_eq!(calls[0].args, vec!["init", "-q", "/tmp/demo"]);
+        assert_eq!(
+            calls[1].args,
+            vec!["symbolic-ref", "HEAD", "refs/heads/main"]
+        );
+        assert_eq!(calls[1].cwd.as_deref(), Some(Path::new("/tmp/demo")));
+    }
+
+    #[test]
+    fn worktrees_gitignore_entry_is_relative_or_absent() {
+        let repo_root = Path::new("/code/shop");
+        assert_eq!(
+            worktrees_gitignore_entry(repo_root, &repo_root.join("worktrees")).as_deref(),
+            Some("worktrees/")
+        );
+        assert_eq!(
+            worktrees_gitignore_entry(repo_root, &repo_root.join(".cache/trees")).as_deref(),
+            Some(".cache/trees/")
+        );
+        assert_eq!(
+            worktrees_gitignore_entry(repo_root, Path::new("/mnt/fast/shop")),
+            None
+        );
+    }
+
+    #[test]
+    fn ensure_gitignore_entries_appends_only_missing_entries_once() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        std::fs::write(temp.path().join(".gitignore"), "target\n/worktrees/").expect("write");
+        let entries = vec![
+            "worktrees/".to_string(),
+            ".direnv/".to_string(),
+            "target".to_string(),
+            ".direnv/".to_string(),
+        ];
+
+        assert_eq!(
+            missing_gitignore_entries(temp.path(), &entries).expect("missing"),
+            vec![".direnv/".to_string()]
+        );
+        assert_eq!(
+            ensure_gitignore_entries(temp.path(), &entries).expect("ensure"),
+            vec![".direnv/".to_string()]
+        );
+        assert!(
+            ensure_gitignore_entries(temp.path(), &entries)
+                .expect("ensure again")
+                .is_empty()
+        );
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join(".gitignore")).expect("read"),
+            "target\n/worktrees/\n.direnv/\n"
+        );
+    }
+
+    fn index_entry(size: u32, path: &str) -> Vec<u8> {
+        index_entry_with_oid(size, path, 20)
+    }
+
+    fn index_entry_with_oid(size: u32, path: &str, oid_len: usize) -> Vec<u8> {
+        let mut entry = vec![0u8; 36];
+        entry.extend(size.to_be_bytes());
+        entry.extend(vec![0u8; oid_len]);
+        entry.extend((path.len() as u16).to_be_bytes());
+        entry.extend(path.as_bytes());
+        let padded = (entry.len() + 8) / 8 * 8;
+        entry.resize(padded, 0);
+        entry
+    }
+
+    #[test]
+    fn index_checkout_size_sums_index_entries_through_a_gitdir_file() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let git_dir = temp.path().join("repo.git");
+        let worktree = temp.path().join("checkout");
+        std::fs::create_dir_all(&git_dir).expect("git dir");
+        std::fs::create_dir_all(&worktree).expect("checkout");
+        std::fs::write(worktree.join(".git"), "gitdir: ../repo.git\n").expect("gitdir");
+
+        let mut index = b"DIRC".to_vec();
+        index.extend(2u32.to_be_bytes());
+        index.extend(2u32.to_be_bytes());
+        index.extend(index_entry(1_000, "src/main.rs"));
+        index.extend(index_entry(24, "README"));
+        std::fs::write(git_dir.join("index"), &index).expect("index");
+        let runner = RecordingRunner::from_outputs(vec![
+            output("sha1\n", "", 0),
+            output("sha1\n", "", 0),
+            output("sha256\n", "", 0),
+        ]);
+        assert_eq!(index_checkout_size(&worktree, &runner), Some(1_024));
+        assert_eq!(
+            runner.calls()[0].args,
+            vec!["rev-parse", "--show-object-format"]
+        );
+        assert_eq!(runner.calls()[0].cwd.as_deref(), Some(worktree.as_path()));
+
+        index.truncate(index.len() - 10);
+        std::fs::write(git_dir.join("index"), &index).expect("truncated index");
+        assert_eq!(index_checkout_size(&worktree, &runner), None);
+        assert_eq!(index_checkout_size(temp.path(), &runner), None);
+        assert_eq!(runner.calls().len(), 2);
+
+        let mut index = b"DIRC".to_vec();
+        index.extend(2u32.to_be_bytes());
+        index.extend(1u32.to_be_bytes());
+        index.extend(index_entry_with_oid(512, "main.rs", 32));
+        std::fs::write(git_dir.join("index"), &index).expect("sha256 index");
+        assert_eq!(index_checkout_size(&worktree, &runner), Some(512));
+    }
 }
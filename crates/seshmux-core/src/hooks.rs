@@ -0,0 +1,111 @@
+use std::path::Path;
+
+use thiserror::Error;
+
+use crate::command_adapter::ensure_success;
+use crate::command_runner::CommandRunner;
+
+const HOOK_SHELL: &str = "/bin/sh";
+
+#[derive(Debug, Error)]
+pub enum HookError {
+    #[error("failed to execute hook `{command}`: {message}")]
+    Execute { command: String, message: String },
+    #[error("hook `{command}` failed (exit {status}) {stderr}")]
+    CommandFailed {
+        command: String,
+        status: i32,
+        stderr: String,
+    },
+}
+
+pub fn run_shell_hook(
+    command: &str,
+    cwd: &Path,
+    runner: &dyn CommandRunner,
+) -> Result<(), HookError> {
+    let args = ["-c", command];
+    let output = runner
+        .run_untimed(HOOK_SHELL, &args, Some(cwd))
+        .map_err(|error| HookError::Execute {
+            command: command.to_string(),
+            message: error.to_string(),
+        })?;
+
+    ensure_success(&args, output).map_err(|failure| HookError::CommandFailed {
+        command: command.to_string(),
+        status: failure.status,
+        stderr: failure.stderr,
+    })?;
+
+    Ok(())
+}
+
+pub fn run_shell_hook_streaming(
+    command: &str,
+    cwd: &Path,
+    runner: &dyn CommandRunner,
+    on_line: &mut dyn FnMut(&str),
+) -> Result<(), HookError> {
+    let args = ["-c", command];
+    let output = runner
+        .run_streaming(HOOK_SHELL, &args, Some(cwd), on_line)
+        .map_err(|error| HookError::Execute {
+            command: command.to_string(),
+            message: error.to_string(),
+        })?;
+
+    ensure_success(&args, output).map_err(|failure| HookError::CommandFailed {
+        command: command.to_string(),
+        status: failure.status,
+        stderr: failure.stderr,
+    })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::test_support::{RecordingRunner, output};
+
+    use super::*;
+
+    #[test]
+    fn run_shell_hook_runs_command_through_sh_in_cwd() {
+        let runner = RecordingRunner::new(vec![output("", "", 0)], Vec::new());
+
+        run_shell_hook("docker compose down", Path::new("/tmp/w1"), &runner).expect("hook");
+
+        let calls = runner.calls();
+        assert_eq!(calls[0].program, "/bin/sh");
+        assert_eq!(calls[0].args, vec!["-c", "docker compose down"]);
+        assert_eq!(calls[0].cwd, Some(Path::new("/tmp/w1").to_path_buf()));
+    }
+
+    #[test]
+    fn run_shell_hook_reports_failing_status() {
+        let runner = RecordingRunner::new(vec![output("", "boom", 2)], Vec::new());
+
+        let error = run_shell_hook("false", Path::new("/tmp/w1"), &runner).expect_err("failure");
+
+        assert!(error.to_string().contains("exit 2"));
+        assert!(error.to_string().contains("boom"));
+    }
+
+    #[test]
+    fn run_shell_hook_streaming_hands_over_the_output() {
+        let runner =
+            RecordingRunner::new(vec![output("added 3 packages\n", "warn\n", 0)], Vec::new());
+        let mut lines = Vec::new();
+
+        run_shell_hook_streaming("npm ci", Path::new("/tmp/w1"), &runner, &mut |line| {
+            lines.push(line.to_string())
+        })
+        .expect("hook");
+
+        assert_eq!(lines, vec!["added 3 packages", "warn"]);
+        assert_eq!(runner.calls()[0].args, vec!["-c", "npm ci"]);
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+use toml::Table;
+
+use crate::command_runner::CommandRunner;
+use crate::config::{LayoutConfig, LayoutPane, LayoutWindow};
+use crate::session_model::SessionTarget;
+use crate::tmux::{PaneInfo, TmuxError};
+
+// A pane idling at one of these is recorded without a command; replaying it would only nest a
+// second shell.
+const SHELLS: &[&str] = &[
+    "sh", "bash", "zsh", "fish", "dash", "ksh", "tcsh", "csh", "nu", "pwsh",
+];
+
+#[derive(Debug, Error)]
+pub enum LayoutError {
+    #[error("failed to read config at {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to parse config at {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+    #[error("layout '{name}' already exists in {path}")]
+    Exists { name: String, path: PathBuf },
+    #[error("failed to serialize layout: {source}")]
+    Serialize {
+        #[source]
+        source: toml::ser::Error,
+    },
+    #[error("failed to write config to {path}: {source}")]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+pub fn capture_layout(
+    target: &SessionTarget,
+    worktree_path: &Path,
+    runner: &dyn CommandRunner,
+) -> Result<LayoutConfig, TmuxError> {
+    let panes = target.panes(runner)?;
+    Ok(layout_from_panes(&panes, worktree_path))
+}
+
+pub fn layout_from_panes(panes: &[PaneInfo], worktree_path: &Path) -> LayoutConfig {
+    let mut windows = Vec::<LayoutWindow>::new();
+    let mut current_window = None;
+
+    for pane in panes {
+        if current_window != Some(pane.window_index) {
+            current_window = Some(pane.window_index);
+            windows.push(LayoutWindow {
+                name: pane.window_name.clone(),
+                tmux_layout: Some(pane.window_layout.clone()).filter(|layout| !layout.is_empty()),
+                panes: Vec::new(),
+            });
+        }
+        if let Some(window) = windows.last_mut() {
+            window.panes.push(LayoutPane {
+                cwd: pane_cwd(&pane.path, worktree_path),
+                command: Some(pane.command.trim())
+                    .filter(|command| !command.is_empty() && !SHELLS.contains(command))
+                    .map(str::to_string),
+            });
+        }
+    }
+
+    LayoutConfig { windows }
+}
+
+fn pane_cwd(path: &Path, worktree_path: &Path) -> Option<String> {
+    match path.strip_prefix(worktree_path) {
+        Ok(relative) if relative.as_os_str().is_empty() => None,
+        Ok(relative) => Some(relative.display().to_string()),
+        Err(_) => Some(path.display().to_string()),
+    }
+}
+
+pub fn append_layout(
+    config_path: &Path,
+    name: &str,
+    layout: &LayoutConfig,
+) -> Result<(), LayoutError> {
+    let raw = fs::read_to_string(config_path).map_err(|source| LayoutError::Read {
+        path: config_path.to_path_buf(),
+        source,
+    })?;
+    let parse = |raw: &str| {
+        toml::from_str::<Table>(raw).map_err(|source| LayoutError::Parse {
+            path: config_path.to_path_buf(),
+            source,
+        })
+    };
+
+    let existing = parse(&raw)?;
+    if existing
+        .get("layouts")
+        .and_then(|layouts| layouts.get(name))
+        .is_some()
+    {
+        return Err(LayoutError::Exists {
+            name: name.to_string(),
+            path: config_path.to_path_buf(),
+        });
+    }
+
+    let wrapper = BTreeMap::from([("layouts", BTreeMap::from([(name, layout)]))]);
+    let rendered = toml::to_string(&wrapper).map_err(|source| LayoutError::Serialize { source })?;
+
+    let mut updated = raw;
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated.push('\n');
+    updated.push_str(&rendered);
+    // An inline `layouts = { ... }` table cannot be extended this way; refuse rather than
+    // write a file that no longer parses.
+    parse(&updated)?;
+
+    fs::write(config_path, updated).map_err(|source| LayoutError::Write {
+        path: config_path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use crate::config::{LayoutPane, SeshmuxConfig};
+    use crate::tmux::PaneInfo;
+
+    use super::{LayoutError, append_layout, layout_from_panes};
+
+    fn pane(window_index: usize, window_name: &str, command: &str, path: &str) -> PaneInfo {
+        PaneInfo {
+            window_index,
+            window_name: window_name.to_string(),
+            window_layout: format!("layout-{window_index}"),
+            command: command.to_string(),
+            path: PathBuf::from(path),
+        }
+    }
+
+    #[test]
+    fn panes_group_into_windows_with_relative_dirs_and_no_shell_commands() {
+        let worktree = Path::new("/repo/worktrees/w1");
+        let layout = layout_from_panes(
+            &[
+                pane(1, "editor", "nvim", "/repo/worktrees/w1"),
+                pane(2, "dev", "cargo", "/repo/worktrees/w1/crates/app"),
+                pane(2, "dev", "zsh", "/tmp"),
+            ],
+            worktree,
+        );
+
+        assert_eq!(layout.windows.len(), 2);
+        assert_eq!(layout.windows[0].name, "editor");
+        assert_eq!(layout.windows[0].tmux_layout.as_deref(), Some("layout-1"));
+        assert_eq!(
+            layout.windows[1].panes,
+            vec![
+                LayoutPane {
+                    cwd: Some("crates/app".to_string()),
+                    command: Some("cargo".to_string()),
+                },
+                LayoutPane {
+                    cwd: Some("/tmp".to_string()),
+                    command: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn append_keeps_the_file_and_refuses_to_overwrite_a_layout() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let path = temp.path().join("config.toml");
+        let original = "# mine\nversion = 1\n\n[tmux]\n[[tmux.windows]]\nname = \"editor\"\nprogram = \"nvim\"\n";
+        fs::write(&path, original).expect("write config");
+
+        let layout = layout_from_panes(
+            &[
+                pane(0, "editor", "nvim", "/w"),
+                pane(0, "editor", "bash", "/w/src"),
+            ],
+            Path::new("/w"),
+        );
+        append_layout(&path, "split", &layout).expect("append");
+
+        let written = fs::read_to_string(&path).expect("read config");
+        assert!(written.starts_with(original));
+        let config: SeshmuxConfig = toml::from_str(&written).expect("parse config");
+        assert_eq!(config.layouts["split"], layout);
+
+        let error = append_layout(&path, "split", &layout).expect_err("duplicate");
+        assert!(matches!(error, LayoutError::Exists { .. }));
+    }
+}
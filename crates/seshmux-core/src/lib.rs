@@ -1,12 +1,25 @@
+pub mod archive;
 pub(crate) mod command_adapter;
 pub mod command_runner;
 pub mod config;
+pub mod dependency_cache;
+pub mod direnv;
+pub mod disk_usage;
 pub mod doctor;
 pub mod extras;
 pub mod git;
+pub mod hooks;
+pub mod layout;
 pub mod names;
+pub mod ownership;
 pub mod registry;
+pub mod scaffold;
+pub mod session_env;
+pub mod session_model;
+pub mod tabs;
 #[cfg(test)]
 pub(crate) mod test_support;
 pub mod time;
 pub mod tmux;
+pub mod trash;
+pub mod vcs;
@@ -1,5 +1,10 @@
+use std::collections::BTreeSet;
+
 use thiserror::Error;
 
+const MAX_WORKTREE_NAME_LEN: usize = 48;
+const MAX_DISPLAY_NAME_LEN: usize = 80;
+
 #[derive(Debug, Error)]
 pub enum NameError {
     #[error("worktree name must be between 1 and 48 characters")]
@@ -8,10 +13,64 @@ pub enum NameError {
     InvalidFirstCharacter,
     #[error("worktree name contains invalid character '{character}'")]
     InvalidCharacter { character: char },
+    #[error("branch name {reason}")]
+    InvalidBranchName { reason: &'static str },
+    #[error("display name {reason}")]
+    InvalidDisplayName { reason: &'static str },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeName {
+    pub slug: String,
+    pub display: Option<String>,
+}
+
+pub fn parse_worktree_name(input: &str) -> Result<WorktreeName, NameError> {
+    let input = input.trim();
+    if validate_worktree_name(input).is_ok() {
+        return Ok(WorktreeName {
+            slug: input.to_string(),
+            display: None,
+        });
+    }
+
+    validate_display_name(input)?;
+    let mut slug = slugify_worktree_name(input);
+    if slug.is_empty() {
+        slug = hashed_worktree_name(input);
+    }
+    validate_worktree_name(&slug)?;
+    Ok(WorktreeName {
+        slug,
+        display: Some(input.to_string()),
+    })
+}
+
+// FNV-1a rather than `DefaultHasher`, whose output may change between Rust releases.
+fn hashed_worktree_name(display: &str) -> String {
+    let hash = display.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    format!("wt-{hash:08x}")
+}
+
+pub fn validate_display_name(name: &str) -> Result<(), NameError> {
+    let length = name.chars().count();
+    if name.trim() != name || length == 0 || length > MAX_DISPLAY_NAME_LEN {
+        return Err(NameError::InvalidDisplayName {
+            reason: "must be between 1 and 80 characters without surrounding spaces",
+        });
+    }
+    if name.chars().any(char::is_control) {
+        return Err(NameError::InvalidDisplayName {
+            reason: "must not contain control characters",
+        });
+    }
+    Ok(())
 }
 
 pub fn validate_worktree_name(name: &str) -> Result<(), NameError> {
-    if name.is_empty() || name.len() > 48 {
+    if name.is_empty() || name.len() > MAX_WORKTREE_NAME_LEN {
         return Err(NameError::InvalidLength);
     }
 
@@ -37,6 +96,48 @@ pub fn validate_worktree_name(name: &str) -> Result<(), NameError> {
     Ok(())
 }
 
+pub fn validate_branch_name(name: &str) -> Result<(), NameError> {
+    let invalid = |reason| Err(NameError::InvalidBranchName { reason });
+
+    if name.is_empty() {
+        return invalid("must not be empty");
+    }
+    if name == "@" {
+        return invalid("must not be '@'");
+    }
+    if name.starts_with('-') {
+        return invalid("must not start with '-'");
+    }
+    if name.starts_with('/') || name.ends_with('/') || name.contains("//") {
+        return invalid("must not start or end with '/' or contain '//'");
+    }
+    if name.ends_with('.') {
+        return invalid("must not end with '.'");
+    }
+    if name.contains("..") {
+        return invalid("must not contain '..'");
+    }
+    if name.contains("@{") {
+        return invalid("must not contain '@{'");
+    }
+    if name.chars().any(|character| {
+        character.is_ascii_control()
+            || matches!(character, ' ' | '~' | '^' | ':' | '?' | '*' | '[' | '\\')
+    }) {
+        return invalid("must not contain spaces, control characters, or any of ~ ^ : ? * [ \\");
+    }
+    for component in name.split('/') {
+        if component.starts_with('.') {
+            return invalid("must not have a path component starting with '.'");
+        }
+        if component.ends_with(".lock") {
+            return invalid("must not have a path component ending with '.lock'");
+        }
+    }
+
+    Ok(())
+}
+
 pub fn sanitize_repo_component(value: &str) -> String {
     let mut output = String::with_capacity(value.len());
 
@@ -61,6 +162,67 @@ pub fn sanitize_repo_component(value: &str) -> String {
     output
 }
 
+pub fn detached_worktree_name(short_sha: &str) -> String {
+    format!("detached-{}", short_sha.to_ascii_lowercase())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameTemplateContext {
+    pub date: String,
+    pub branch: Option<String>,
+}
+
+pub fn slugify_worktree_name(value: &str) -> String {
+    let mut slug = String::with_capacity(value.len());
+    for character in value.chars() {
+        if character.is_ascii_alphanumeric() || character == '_' {
+            slug.push(character.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    let mut slug = slug.trim_start_matches('_').to_string();
+    slug.truncate(MAX_WORKTREE_NAME_LEN);
+    slug.trim_end_matches('-').to_string()
+}
+
+pub fn render_name_template(template: &str, context: &NameTemplateContext) -> Option<String> {
+    let mut rendered = template.replace("{date}", &context.date);
+    if rendered.contains("{branch}") {
+        rendered = rendered.replace("{branch}", context.branch.as_deref()?);
+    }
+
+    let name = slugify_worktree_name(&rendered);
+    validate_worktree_name(&name).ok().map(|()| name)
+}
+
+pub fn suggest_worktree_names(
+    templates: &[String],
+    context: &NameTemplateContext,
+    taken: &BTreeSet<String>,
+) -> Vec<String> {
+    let mut suggestions = Vec::<String>::new();
+    for template in templates {
+        let Some(base) = render_name_template(template, context) else {
+            continue;
+        };
+        let unused = |name: &String| !taken.contains(name) && !suggestions.contains(name);
+        let candidate = std::iter::once(base.clone())
+            .chain((2..100).map(|counter| {
+                let suffix = format!("-{counter}");
+                let mut stem = base.clone();
+                stem.truncate(MAX_WORKTREE_NAME_LEN - suffix.len());
+                format!("{}{suffix}", stem.trim_end_matches('-'))
+            }))
+            .find(unused);
+        if let Some(candidate) = candidate {
+            suggestions.push(candidate);
+        }
+    }
+    suggestions
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,9 +246,124 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn parse_worktree_name_keeps_slugs_and_maps_other_text_to_a_display_name() {
+        assert_eq!(
+            parse_worktree_name(" feature_1 ").expect("slug"),
+            WorktreeName {
+                slug: "feature_1".to_string(),
+                display: None,
+            }
+        );
+        assert_eq!(
+            parse_worktree_name("Fix login & SSO").expect("display"),
+            WorktreeName {
+                slug: "fix-login-sso".to_string(),
+                display: Some("Fix login & SSO".to_string()),
+            }
+        );
+        assert_eq!(
+            parse_worktree_name("Café über 2").expect("unicode").slug,
+            "caf-ber-2"
+        );
+        let cjk = parse_worktree_name("日本語").expect("cjk");
+        assert!(cjk.slug.starts_with("wt-") && cjk.slug.len() == 11);
+        assert_eq!(cjk.display.as_deref(), Some("日本語"));
+        assert_eq!(parse_worktree_name("日本語").expect("cjk").slug, cjk.slug);
+        assert_ne!(parse_worktree_name("🚀🔥").expect("emoji").slug, cjk.slug);
+        assert!(matches!(
+            parse_worktree_name("tab\there"),
+            Err(NameError::InvalidDisplayName { .. })
+        ));
+        assert!(parse_worktree_name("").is_err());
+    }
+
+    #[test]
+    fn validate_branch_name_follows_git_ref_rules() {
+        for valid in ["feature/one", "Fix-Typo", "user@host", "release/v1.2"] {
+            assert!(validate_branch_name(valid).is_ok(), "{valid}");
+        }
+        for invalid in [
+            "",
+            "@",
+            "-x",
+            "feature/",
+            "a//b",
+            "a..b",
+            "a.",
+            "a@{1}",
+            "has space",
+            "a:b",
+            "a/.hidden",
+            "a.lock",
+            "a/b.lock/c",
+        ] {
+            assert!(
+                matches!(
+                    validate_branch_name(invalid),
+                    Err(NameError::InvalidBranchName { .. })
+                ),
+                "{invalid}"
+            );
+        }
+    }
+
     #[test]
     fn sanitize_repo_component_normalizes_characters() {
         assert_eq!(sanitize_repo_component("Project Repo"), "project-repo");
         assert_eq!(sanitize_repo_component("repo_name"), "repo_name");
     }
+
+    #[test]
+    fn slugify_collapses_separators_and_trims_the_edges() {
+        assert_eq!(
+            slugify_worktree_name("feature/Login  Flow!"),
+            "feature-login-flow"
+        );
+        assert_eq!(slugify_worktree_name("__JIRA-42__"), "jira-42__");
+        assert_eq!(slugify_worktree_name("///"), "");
+        assert_eq!(slugify_worktree_name(&"a".repeat(60)).len(), 48);
+    }
+
+    #[test]
+    fn templates_render_branch_and_date_and_skip_missing_values() {
+        let context = NameTemplateContext {
+            date: "20261017".to_string(),
+            branch: Some("feature/login".to_string()),
+        };
+        assert_eq!(
+            render_name_template("PROJ-{date}", &context).as_deref(),
+            Some("proj-20261017")
+        );
+        assert_eq!(
+            render_name_template("{branch}", &context).as_deref(),
+            Some("feature-login")
+        );
+
+        let detached = NameTemplateContext {
+            branch: None,
+            ..context
+        };
+        assert_eq!(render_name_template("{branch}-{date}", &detached), None);
+        assert_eq!(render_name_template("!!!", &detached), None);
+    }
+
+    #[test]
+    fn suggestions_step_around_taken_names_and_duplicates() {
+        let context = NameTemplateContext {
+            date: "20261017".to_string(),
+            branch: Some("main".to_string()),
+        };
+        let templates = vec![
+            "{branch}".to_string(),
+            "main".to_string(),
+            "{branch}-{date}".to_string(),
+        ];
+        let taken = BTreeSet::from(["main".to_string(), "main-2".to_string()]);
+
+        assert_eq!(
+            suggest_worktree_names(&templates, &context, &taken),
+            vec!["main-3", "main-4", "main-20261017"]
+        );
+    }
 }
@@ -0,0 +1,57 @@
+use std::fs;
+
+const UNKNOWN_USER: &str = "unknown";
+const UNKNOWN_HOST: &str = "localhost";
+
+pub fn current_owner() -> String {
+    format_owner(&current_user(), &current_host())
+}
+
+pub fn format_owner(user: &str, host: &str) -> String {
+    format!("{user}@{host}")
+}
+
+pub fn foreign_owner<'a>(created_by: Option<&'a str>, current: &str) -> Option<&'a str> {
+    created_by.filter(|owner| *owner != current)
+}
+
+fn current_user() -> String {
+    ["USER", "USERNAME", "LOGNAME"]
+        .into_iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+        .unwrap_or_else(|| UNKNOWN_USER.to_string())
+}
+
+fn current_host() -> String {
+    let from_env = std::env::var("HOSTNAME").ok();
+    let from_files = || {
+        ["/proc/sys/kernel/hostname", "/etc/hostname"]
+            .into_iter()
+            .find_map(|path| fs::read_to_string(path).ok())
+    };
+
+    from_env
+        .or_else(from_files)
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| UNKNOWN_HOST.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{foreign_owner, format_owner};
+
+    #[test]
+    fn foreign_owner_ignores_legacy_and_matching_entries() {
+        let current = format_owner("alice", "devbox");
+
+        assert_eq!(foreign_owner(None, &current), None);
+        assert_eq!(foreign_owner(Some("alice@devbox"), &current), None);
+        assert_eq!(
+            foreign_owner(Some("bob@devbox"), &current),
+            Some("bob@devbox")
+        );
+    }
+}
@@ -5,7 +5,11 @@ use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-const REGISTRY_VERSION: i64 = 1;
+use crate::config::SessionLayout;
+
+const REGISTRY_VERSION: i64 = 2;
+const LEGACY_REGISTRY_VERSION: i64 = 1;
+const REGISTRY_FILE_NAME: &str = "worktree.toml";
 const DEFAULT_ALWAYS_SKIP_BUCKETS: &[&str] = &[
     "target",
     "node_modules",
@@ -37,11 +41,80 @@ const DEFAULT_ALWAYS_SKIP_BUCKETS: &[&str] = &[
     ".dart_tool",
 ];
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct RegistryEntry {
     pub name: String,
     pub path: String,
     pub created_at: String,
+    #[serde(default)]
+    pub created_by: Option<String>,
+    #[serde(default)]
+    pub disk_usage: Option<DiskUsage>,
+    #[serde(default)]
+    pub archived: Option<ArchiveRecord>,
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub start_point: Option<String>,
+    #[serde(default)]
+    pub session_name: Option<String>,
+    #[serde(default)]
+    pub layout: Option<SessionLayout>,
+    #[serde(default)]
+    pub extras_copied: Vec<String>,
+    #[serde(default)]
+    pub last_attached_at: Option<String>,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub autostart: bool,
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub deprovisioned: bool,
+}
+
+impl RegistryEntry {
+    pub fn new(
+        name: impl Into<String>,
+        path: impl Into<String>,
+        created_at: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+            created_at: created_at.into(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct DiskUsage {
+    pub bytes: u64,
+    pub measured_at: String,
+}
+
+impl DiskUsage {
+    pub fn new(bytes: u64, measured_at: impl Into<String>) -> Self {
+        Self {
+            bytes,
+            measured_at: measured_at.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveRecord {
+    pub archived_at: String,
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub tarball: Option<String>,
+    #[serde(default)]
+    pub commit: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -54,7 +127,11 @@ struct RegistryFile {
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 struct RegistrySettings {
+    #[serde(default)]
+    repo_root: Option<String>,
     extras: RegistryExtrasSettings,
+    #[serde(default)]
+    palette: RegistryPaletteSettings,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -63,14 +140,22 @@ struct RegistryExtrasSettings {
     always_skip_buckets: Option<Vec<String>>,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct RegistryPaletteSettings {
+    #[serde(default)]
+    recent: Vec<String>,
+}
+
 impl Default for RegistryFile {
     fn default() -> Self {
         Self {
             version: REGISTRY_VERSION,
             settings: RegistrySettings {
+                repo_root: None,
                 extras: RegistryExtrasSettings {
                     always_skip_buckets: None,
                 },
+                palette: RegistryPaletteSettings::default(),
             },
             entries: Vec::new(),
         }
@@ -78,6 +163,7 @@ impl Default for RegistryFile {
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum RegistryError {
     #[error("failed to read registry at {path}: {source}")]
     Read {
@@ -105,10 +191,14 @@ pub enum RegistryError {
     DuplicateName { name: String },
     #[error("worktree registry already contains path '{path}'")]
     DuplicatePath { path: String },
+    #[error(
+        "worktree registry at {path} belongs to the repository at {owner}; give each repository its own worktrees_dir"
+    )]
+    ForeignRepository { path: PathBuf, owner: String },
 }
 
-pub fn registry_path(repo_root: &Path) -> PathBuf {
-    repo_root.join("worktrees").join("worktree.toml")
+pub fn registry_path(worktrees_dir: &Path) -> PathBuf {
+    worktrees_dir.join(REGISTRY_FILE_NAME)
 }
 
 pub fn default_always_skip_buckets() -> BTreeSet<String> {
@@ -125,14 +215,14 @@ pub struct AlwaysSkipBucketsLoad {
     pub registry_missing: bool,
 }
 
-pub fn load_registry(repo_root: &Path) -> Result<Vec<RegistryEntry>, RegistryError> {
-    Ok(load_registry_file(repo_root)?.entries)
+pub fn load_registry(worktrees_dir: &Path) -> Result<Vec<RegistryEntry>, RegistryError> {
+    Ok(load_registry_file(worktrees_dir)?.entries)
 }
 
 pub fn load_always_skip_buckets_for_indexing(
-    repo_root: &Path,
+    worktrees_dir: &Path,
 ) -> Result<AlwaysSkipBucketsLoad, RegistryError> {
-    let path = registry_path(repo_root);
+    let path = registry_path(worktrees_dir);
     if !path.exists() {
         return Ok(AlwaysSkipBucketsLoad {
             buckets: default_always_skip_buckets(),
@@ -141,7 +231,7 @@ pub fn load_always_skip_buckets_for_indexing(
         });
     }
 
-    let registry = load_registry_file(repo_root)?;
+    let registry = load_registry_file(worktrees_dir)?;
     let configured_buckets = registry
         .settings
         .extras
@@ -159,8 +249,8 @@ pub fn load_always_skip_buckets_for_indexing(
     })
 }
 
-pub fn load_always_skip_buckets(repo_root: &Path) -> Result<BTreeSet<String>, RegistryError> {
-    let mut registry = load_registry_file(repo_root)?;
+pub fn load_always_skip_buckets(worktrees_dir: &Path) -> Result<BTreeSet<String>, RegistryError> {
+    let mut registry = load_registry_file(worktrees_dir)?;
 
     if let Some(values) = registry.settings.extras.always_skip_buckets.clone() {
         return Ok(normalize_bucket_values(values));
@@ -168,42 +258,42 @@ pub fn load_always_skip_buckets(repo_root: &Path) -> Result<BTreeSet<String>, Re
 
     let seeded = default_always_skip_buckets();
     registry.settings.extras.always_skip_buckets = Some(seeded.iter().cloned().collect());
-    write_registry_file(repo_root, &registry)?;
+    write_registry_file(worktrees_dir, &registry)?;
     Ok(seeded)
 }
 
 pub fn save_always_skip_buckets(
-    repo_root: &Path,
+    worktrees_dir: &Path,
     buckets: &BTreeSet<String>,
 ) -> Result<(), RegistryError> {
-    let mut registry = load_registry_file(repo_root)?;
+    let mut registry = load_registry_file(worktrees_dir)?;
     registry.settings.extras.always_skip_buckets = Some(buckets.iter().cloned().collect());
-    write_registry_file(repo_root, &registry)
+    write_registry_file(worktrees_dir, &registry)
 }
 
 pub fn ensure_entry_available(
-    repo_root: &Path,
+    worktrees_dir: &Path,
     name: &str,
     path: &Path,
 ) -> Result<(), RegistryError> {
-    let entries = load_registry(repo_root)?;
+    let entries = load_registry(worktrees_dir)?;
     let path_value = path.to_string_lossy();
     ensure_unique_entry(&entries, name, path_value.as_ref())
 }
 
 pub fn find_entry_by_name(
-    repo_root: &Path,
+    worktrees_dir: &Path,
     name: &str,
 ) -> Result<Option<RegistryEntry>, RegistryError> {
-    let entries = load_registry(repo_root)?;
+    let entries = load_registry(worktrees_dir)?;
     Ok(entries.into_iter().find(|entry| entry.name == name))
 }
 
 pub fn remove_entry_by_name(
-    repo_root: &Path,
+    worktrees_dir: &Path,
     name: &str,
 ) -> Result<Option<RegistryEntry>, RegistryError> {
-    let mut registry = load_registry_file(repo_root)?;
+    let mut registry = load_registry_file(worktrees_dir)?;
     let index = registry.entries.iter().position(|entry| entry.name == name);
 
     let Some(index) = index else {
@@ -211,16 +301,128 @@ pub fn remove_entry_by_name(
     };
 
     let removed = registry.entries.remove(index);
-    write_registry_file(repo_root, &registry)?;
+    write_registry_file(worktrees_dir, &registry)?;
     Ok(Some(removed))
 }
 
-pub fn insert_unique_entry(repo_root: &Path, entry: RegistryEntry) -> Result<(), RegistryError> {
-    let mut registry = load_registry_file(repo_root)?;
+pub fn insert_unique_entry(
+    worktrees_dir: &Path,
+    entry: RegistryEntry,
+) -> Result<(), RegistryError> {
+    let mut registry = load_registry_file(worktrees_dir)?;
     ensure_unique_entry(&registry.entries, &entry.name, &entry.path)?;
 
     registry.entries.push(entry);
-    write_registry_file(repo_root, &registry)
+    write_registry_file(worktrees_dir, &registry)
+}
+
+pub fn record_disk_usage(
+    worktrees_dir: &Path,
+    updates: &[(String, DiskUsage)],
+) -> Result<(), RegistryError> {
+    let mut registry = load_registry_file(worktrees_dir)?;
+    let mut changed = false;
+    for (name, usage) in updates {
+        if let Some(entry) = registry
+            .entries
+            .iter_mut()
+            .find(|entry| entry.name == *name)
+        {
+            entry.disk_usage = Some(usage.clone());
+            changed = true;
+        }
+    }
+
+    if changed {
+        write_registry_file(worktrees_dir, &registry)?;
+    }
+    Ok(())
+}
+
+pub fn set_archived(
+    worktrees_dir: &Path,
+    name: &str,
+    archived: Option<ArchiveRecord>,
+) -> Result<bool, RegistryError> {
+    update_entry(worktrees_dir, name, |entry| entry.archived = archived)
+}
+
+pub fn record_attach(worktrees_dir: &Path, name: &str, at: &str) -> Result<bool, RegistryError> {
+    update_entry(worktrees_dir, name, |entry| {
+        entry.last_attached_at = Some(at.to_string());
+    })
+}
+
+/// Records `repo_root` as the registry's repository the first time, and refuses a registry that
+/// another repository recorded. A registry that does not exist yet is left alone.
+pub fn claim_registry(worktrees_dir: &Path, repo_root: &Path) -> Result<(), RegistryError> {
+    let path = registry_path(worktrees_dir);
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let repo_root = repo_root.to_string_lossy();
+    let mut registry = load_registry_file(worktrees_dir)?;
+    match &registry.settings.repo_root {
+        Some(owner) if *owner == repo_root => Ok(()),
+        Some(owner) => Err(RegistryError::ForeignRepository {
+            path,
+            owner: owner.clone(),
+        }),
+        None => {
+            registry.settings.repo_root = Some(repo_root.into_owned());
+            write_registry_file(worktrees_dir, &registry)
+        }
+    }
+}
+
+pub fn load_recent_commands(worktrees_dir: &Path) -> Result<Vec<String>, RegistryError> {
+    Ok(load_registry_file(worktrees_dir)?.settings.palette.recent)
+}
+
+pub fn record_recent_command(
+    worktrees_dir: &Path,
+    command: &str,
+    limit: usize,
+) -> Result<bool, RegistryError> {
+    if !registry_path(worktrees_dir).exists() {
+        return Ok(false);
+    }
+
+    let mut registry = load_registry_file(worktrees_dir)?;
+    let recent = &mut registry.settings.palette.recent;
+    recent.retain(|existing| existing != command);
+    recent.insert(0, command.to_string());
+    recent.truncate(limit);
+    write_registry_file(worktrees_dir, &registry)?;
+    Ok(true)
+}
+
+pub fn set_autostart(
+    worktrees_dir: &Path,
+    name: &str,
+    autostart: bool,
+) -> Result<bool, RegistryError> {
+    update_entry(worktrees_dir, name, |entry| entry.autostart = autostart)
+}
+
+pub fn mark_deprovisioned(worktrees_dir: &Path, name: &str) -> Result<bool, RegistryError> {
+    update_entry(worktrees_dir, name, |entry| entry.deprovisioned = true)
+}
+
+fn update_entry(
+    worktrees_dir: &Path,
+    name: &str,
+    update: impl FnOnce(&mut RegistryEntry),
+) -> Result<bool, RegistryError> {
+    let mut registry = load_registry_file(worktrees_dir)?;
+    let Some(entry) = registry.entries.iter_mut().find(|entry| entry.name == name) else {
+        return Ok(false);
+    };
+
+    update(entry);
+    write_registry_file(worktrees_dir, &registry)?;
+    Ok(true)
 }
 
 fn ensure_unique_entry(
@@ -243,8 +445,8 @@ fn ensure_unique_entry(
     Ok(())
 }
 
-fn load_registry_file(repo_root: &Path) -> Result<RegistryFile, RegistryError> {
-    let path = registry_path(repo_root);
+fn load_registry_file(worktrees_dir: &Path) -> Result<RegistryFile, RegistryError> {
+    let path = registry_path(worktrees_dir);
     if !path.exists() {
         return Ok(RegistryFile::default());
     }
@@ -262,23 +464,32 @@ fn load_registry_file(repo_root: &Path) -> Result<RegistryFile, RegistryError> {
 
     validate_registry_schema(&parsed_value)?;
 
-    let parsed: RegistryFile = parsed_value
-        .try_into()
-        .map_err(|source| RegistryError::Parse {
-            path: path.clone(),
-            source,
-        })?;
+    let mut parsed: RegistryFile =
+        parsed_value
+            .try_into()
+            .map_err(|source| RegistryError::Parse {
+                path: path.clone(),
+                source,
+            })?;
 
-    if parsed.version != REGISTRY_VERSION {
-        return Err(schema_error(format!(
-            "invalid worktree registry schema: unsupported version (expected {REGISTRY_VERSION}, found {})",
-            parsed.version
-        )));
+    if !is_supported_version(parsed.version) {
+        return Err(unsupported_version_error(parsed.version));
     }
+    parsed.version = REGISTRY_VERSION;
 
     Ok(parsed)
 }
 
+fn is_supported_version(version: i64) -> bool {
+    version == REGISTRY_VERSION || version == LEGACY_REGISTRY_VERSION
+}
+
+fn unsupported_version_error(found: i64) -> RegistryError {
+    schema_error(format!(
+        "invalid worktree registry schema: unsupported version (expected {LEGACY_REGISTRY_VERSION} or {REGISTRY_VERSION}, found {found})"
+    ))
+}
+
 fn validate_registry_schema(value: &toml::Value) -> Result<(), RegistryError> {
     let Some(root) = value.as_table() else {
         return Err(schema_error(
@@ -295,12 +506,8 @@ fn validate_registry_schema(value: &toml::Value) -> Result<(), RegistryError> {
     };
 
     match version.as_integer() {
-        Some(current) if current == REGISTRY_VERSION => {}
-        Some(current) => {
-            return Err(schema_error(format!(
-                "invalid worktree registry schema: unsupported version (expected {REGISTRY_VERSION}, found {current})"
-            )));
-        }
+        Some(current) if is_supported_version(current) => {}
+        Some(current) => return Err(unsupported_version_error(current)),
         None => {
             return Err(schema_error(
                 "invalid worktree registry schema: unsupported version (expected integer)"
@@ -336,8 +543,8 @@ fn validate_registry_schema(value: &toml::Value) -> Result<(), RegistryError> {
     Ok(())
 }
 
-fn write_registry_file(repo_root: &Path, registry: &RegistryFile) -> Result<(), RegistryError> {
-    let path = registry_path(repo_root);
+fn write_registry_file(worktrees_dir: &Path, registry: &RegistryFile) -> Result<(), RegistryError> {
+    let path = registry_path(worktrees_dir);
     let parent = path.parent().expect("worktree registry path has parent");
     fs::create_dir_all(parent).map_err(|source| RegistryError::Write {
         path: parent.to_path_buf(),
@@ -364,7 +571,29 @@ fn write_registry_file(repo_root: &Path, registry: &RegistryFile) -> Result<(),
     }
 
     let mut settings = toml::map::Map::<String, toml::Value>::new();
+    if let Some(repo_root) = &registry.settings.repo_root {
+        settings.insert(
+            "repo_root".to_string(),
+            toml::Value::String(repo_root.clone()),
+        );
+    }
     settings.insert("extras".to_string(), toml::Value::Table(extras));
+    if !registry.settings.palette.recent.is_empty() {
+        let mut palette = toml::map::Map::<String, toml::Value>::new();
+        palette.insert(
+            "recent".to_string(),
+            toml::Value::Array(
+                registry
+                    .settings
+                    .palette
+                    .recent
+                    .iter()
+                    .map(|value| toml::Value::String(value.clone()))
+                    .collect(),
+            ),
+        );
+        settings.insert("palette".to_string(), toml::Value::Table(palette));
+    }
     root.insert("settings".to_string(), toml::Value::Table(settings));
 
     let entries = registry
@@ -378,6 +607,85 @@ fn write_registry_file(repo_root: &Path, registry: &RegistryFile) -> Result<(),
                 "created_at".to_string(),
                 toml::Value::String(entry.created_at.clone()),
             );
+            if let Some(created_by) = &entry.created_by {
+                table.insert(
+                    "created_by".to_string(),
+                    toml::Value::String(created_by.clone()),
+                );
+            }
+            if let Some(usage) = &entry.disk_usage {
+                let mut usage_table = toml::map::Map::<String, toml::Value>::new();
+                usage_table.insert(
+                    "bytes".to_string(),
+                    toml::Value::Integer(i64::try_from(usage.bytes).unwrap_or(i64::MAX)),
+                );
+                usage_table.insert(
+                    "measured_at".to_string(),
+                    toml::Value::String(usage.measured_at.clone()),
+                );
+                table.insert("disk_usage".to_string(), toml::Value::Table(usage_table));
+            }
+            if let Some(archived) = &entry.archived {
+                let mut archived_table = toml::map::Map::<String, toml::Value>::new();
+                archived_table.insert(
+                    "archived_at".to_string(),
+                    toml::Value::String(archived.archived_at.clone()),
+                );
+                if let Some(branch) = &archived.branch {
+                    archived_table
+                        .insert("branch".to_string(), toml::Value::String(branch.clone()));
+                }
+                if let Some(tarball) = &archived.tarball {
+                    archived_table
+                        .insert("tarball".to_string(), toml::Value::String(tarball.clone()));
+                }
+                if let Some(commit) = &archived.commit {
+                    archived_table
+                        .insert("commit".to_string(), toml::Value::String(commit.clone()));
+                }
+                table.insert("archived".to_string(), toml::Value::Table(archived_table));
+            }
+            let optional_strings = [
+                ("branch", &entry.branch),
+                ("start_point", &entry.start_point),
+                ("session_name", &entry.session_name),
+                ("last_attached_at", &entry.last_attached_at),
+                ("display_name", &entry.display_name),
+                ("project", &entry.project),
+            ];
+            for (key, value) in optional_strings {
+                if let Some(value) = value {
+                    table.insert(key.to_string(), toml::Value::String(value.clone()));
+                }
+            }
+            if let Some(layout) = entry.layout {
+                let layout = match layout {
+                    SessionLayout::Session => "session",
+                    SessionLayout::Window => "window",
+                };
+                table.insert(
+                    "layout".to_string(),
+                    toml::Value::String(layout.to_string()),
+                );
+            }
+            if entry.autostart {
+                table.insert("autostart".to_string(), toml::Value::Boolean(true));
+            }
+            if entry.deprovisioned {
+                table.insert("deprovisioned".to_string(), toml::Value::Boolean(true));
+            }
+            if !entry.extras_copied.is_empty() {
+                table.insert(
+                    "extras_copied".to_string(),
+                    toml::Value::Array(
+                        entry
+                            .extras_copied
+                            .iter()
+                            .map(|value| toml::Value::String(value.clone()))
+                            .collect(),
+                    ),
+                );
+            }
             toml::Value::Table(table)
         })
         .collect();
@@ -419,33 +727,33 @@ mod tests {
     #[test]
     fn insert_unique_entry_rejects_duplicate_name() {
         let temp = tempfile::tempdir().expect("temp dir");
-        let repo_root = temp.path();
-        fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees dir");
+        let worktrees_dir = &temp.path().join("worktrees");
+        fs::create_dir_all(worktrees_dir).expect("worktrees dir");
 
         insert_unique_entry(
-            repo_root,
+            worktrees_dir,
             RegistryEntry {
                 name: "w1".to_string(),
-                path: repo_root
-                    .join("worktrees")
-                    .join("w1")
-                    .to_string_lossy()
-                    .to_string(),
+                path: worktrees_dir.join("w1").to_string_lossy().to_string(),
                 created_at: "2026-01-01T00:00:00Z".to_string(),
+                created_by: None,
+                disk_usage: None,
+                archived: None,
+                ..Default::default()
             },
         )
         .expect("first insert");
 
         let error = insert_unique_entry(
-            repo_root,
+            worktrees_dir,
             RegistryEntry {
                 name: "w1".to_string(),
-                path: repo_root
-                    .join("worktrees")
-                    .join("w2")
-                    .to_string_lossy()
-                    .to_string(),
+                path: worktrees_dir.join("w2").to_string_lossy().to_string(),
                 created_at: "2026-01-01T00:00:01Z".to_string(),
+                created_by: None,
+                disk_usage: None,
+                archived: None,
+                ..Default::default()
             },
         )
         .expect_err("duplicate should fail");
@@ -453,59 +761,205 @@ mod tests {
         assert!(matches!(error, RegistryError::DuplicateName { .. }));
     }
 
+    #[test]
+    fn record_disk_usage_updates_known_entries_only() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let worktrees_dir = &temp.path().join("worktrees");
+        insert_unique_entry(
+            worktrees_dir,
+            RegistryEntry {
+                name: "w1".to_string(),
+                path: worktrees_dir.join("w1").to_string_lossy().to_string(),
+                created_at: "2026-01-01T00:00:00Z".to_string(),
+                created_by: None,
+                disk_usage: None,
+                archived: None,
+                ..Default::default()
+            },
+        )
+        .expect("insert");
+
+        let usage = DiskUsage {
+            bytes: 5_000_000_000,
+            measured_at: "2026-01-02T00:00:00Z".to_string(),
+        };
+        record_disk_usage(
+            worktrees_dir,
+            &[
+                ("w1".to_string(), usage.clone()),
+                ("gone".to_string(), usage.clone()),
+            ],
+        )
+        .expect("record");
+
+        let entries = load_registry(worktrees_dir).expect("load");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].disk_usage, Some(usage));
+    }
+
+    #[test]
+    fn set_archived_round_trips_and_clears_the_record() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let worktrees_dir = &temp.path().join("worktrees");
+        insert_unique_entry(
+            worktrees_dir,
+            RegistryEntry {
+                name: "w1".to_string(),
+                path: worktrees_dir.join("w1").to_string_lossy().to_string(),
+                created_at: "2026-01-01T00:00:00Z".to_string(),
+                created_by: None,
+                disk_usage: None,
+                archived: None,
+                ..Default::default()
+            },
+        )
+        .expect("insert");
+
+        let record = ArchiveRecord {
+            archived_at: "2026-03-01T00:00:00Z".to_string(),
+            branch: Some("feature/w1".to_string()),
+            tarball: Some("/repo/worktrees/.archive/w1.tar.gz".to_string()),
+            commit: Some("0123456789abcdef".to_string()),
+        };
+        assert!(set_archived(worktrees_dir, "w1", Some(record.clone())).expect("archive"));
+        assert!(!set_archived(worktrees_dir, "gone", Some(record.clone())).expect("unknown"));
+        assert_eq!(
+            load_registry(worktrees_dir).expect("load")[0].archived,
+            Some(record)
+        );
+
+        assert!(set_archived(worktrees_dir, "w1", None).expect("restore"));
+        assert_eq!(
+            load_registry(worktrees_dir).expect("load")[0].archived,
+            None
+        );
+    }
+
+    #[test]
+    fn set_autostart_is_written_only_while_set() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let worktrees_dir = &temp.path().join("worktrees");
+        insert_unique_entry(
+            worktrees_dir,
+            RegistryEntry {
+                name: "w1".to_string(),
+                path: worktrees_dir.join("w1").to_string_lossy().to_string(),
+                created_at: "2026-01-01T00:00:00Z".to_string(),
+                ..Default::default()
+            },
+        )
+        .expect("insert");
+        let raw = || fs::read_to_string(registry_path(worktrees_dir)).expect("read");
+        assert!(!raw().contains("autostart"));
+
+        assert!(set_autostart(worktrees_dir, "w1", true).expect("flag"));
+        assert!(!set_autostart(worktrees_dir, "gone", true).expect("unknown"));
+        assert!(load_registry(worktrees_dir).expect("load")[0].autostart);
+        assert!(raw().contains("autostart = true"));
+
+        assert!(set_autostart(worktrees_dir, "w1", false).expect("clear"));
+        assert!(!load_registry(worktrees_dir).expect("load")[0].autostart);
+        assert!(!raw().contains("autostart"));
+    }
+
     #[test]
     fn registry_settings_round_trip_preserves_worktree_entries() {
         let temp = tempfile::tempdir().expect("temp dir");
-        let repo_root = temp.path();
+        let worktrees_dir = &temp.path().join("worktrees");
 
         let entry = RegistryEntry {
             name: "w1".to_string(),
-            path: repo_root
-                .join("worktrees")
-                .join("w1")
-                .to_string_lossy()
-                .to_string(),
+            path: worktrees_dir.join("w1").to_string_lossy().to_string(),
             created_at: "2026-01-01T00:00:00Z".to_string(),
+            created_by: Some("alice@devbox".to_string()),
+            disk_usage: None,
+            archived: None,
+            ..Default::default()
         };
-        insert_unique_entry(repo_root, entry.clone()).expect("insert");
+        insert_unique_entry(worktrees_dir, entry.clone()).expect("insert");
 
         let buckets = BTreeSet::from(["target".to_string(), "node_modules".to_string()]);
-        save_always_skip_buckets(repo_root, &buckets).expect("save buckets");
+        save_always_skip_buckets(worktrees_dir, &buckets).expect("save buckets");
 
-        let loaded_entries = load_registry(repo_root).expect("load entries");
+        let loaded_entries = load_registry(worktrees_dir).expect("load entries");
         assert_eq!(loaded_entries, vec![entry]);
         assert_eq!(
-            load_always_skip_buckets(repo_root).expect("load buckets"),
+            load_always_skip_buckets(worktrees_dir).expect("load buckets"),
             buckets
         );
     }
 
+    #[test]
+    fn claim_registry_records_the_first_repository_and_refuses_others() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let worktrees_dir = &temp.path().join("shop");
+
+        claim_registry(worktrees_dir, Path::new("/code/a/shop")).expect("no registry yet");
+        assert!(!registry_path(worktrees_dir).exists());
+
+        save_always_skip_buckets(worktrees_dir, &BTreeSet::new()).expect("create registry");
+        claim_registry(worktrees_dir, Path::new("/code/a/shop")).expect("claim");
+        claim_registry(worktrees_dir, Path::new("/code/a/shop")).expect("same repository");
+        let raw = fs::read_to_string(registry_path(worktrees_dir)).expect("read registry");
+        assert!(raw.contains("repo_root = \"/code/a/shop\""), "{raw}");
+
+        let error = claim_registry(worktrees_dir, Path::new("/code/b/shop"))
+            .expect_err("another repository");
+        assert!(
+            error
+                .to_string()
+                .contains("belongs to the repository at /code/a/shop"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn recent_commands_move_to_the_front_and_keep_the_newest() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let worktrees_dir = &temp.path().join("worktrees");
+
+        assert!(!record_recent_command(worktrees_dir, "new", 3).expect("record"));
+        assert!(!registry_path(worktrees_dir).exists());
+
+        save_always_skip_buckets(worktrees_dir, &BTreeSet::new()).expect("create registry");
+        for command in ["new", "attach w1", "doctor", "new", "open w2"] {
+            assert!(record_recent_command(worktrees_dir, command, 3).expect("record"));
+        }
+
+        assert_eq!(
+            load_recent_commands(worktrees_dir).expect("load"),
+            vec!["open w2", "new", "doctor"]
+        );
+        let raw = fs::read_to_string(registry_path(worktrees_dir)).expect("read registry");
+        assert!(raw.contains("[settings.palette]"), "{raw}");
+    }
+
     #[test]
     fn load_always_skip_buckets_for_indexing_uses_defaults_without_creating_registry_file() {
         let temp = tempfile::tempdir().expect("temp dir");
-        let repo_root = temp.path();
+        let worktrees_dir = &temp.path().join("worktrees");
 
-        let loaded = load_always_skip_buckets_for_indexing(repo_root).expect("load defaults");
+        let loaded = load_always_skip_buckets_for_indexing(worktrees_dir).expect("load defaults");
 
         assert!(loaded.registry_missing);
         assert_eq!(loaded.buckets, default_always_skip_buckets());
         assert!(loaded.configured_buckets.is_empty());
-        assert!(!registry_path(repo_root).exists());
+        assert!(!registry_path(worktrees_dir).exists());
     }
 
     #[test]
     fn load_always_skip_buckets_for_indexing_reads_existing_registry_without_writing() {
         let temp = tempfile::tempdir().expect("temp dir");
-        let repo_root = temp.path();
-        fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees dir");
+        let worktrees_dir = &temp.path().join("worktrees");
+        fs::create_dir_all(worktrees_dir).expect("worktrees dir");
         fs::write(
-            registry_path(repo_root),
+            registry_path(worktrees_dir),
             "version = 1\nworktree=[]\n[settings.extras]\n",
         )
         .expect("write registry");
 
-        let loaded = load_always_skip_buckets_for_indexing(repo_root).expect("load existing");
-        let raw = fs::read_to_string(registry_path(repo_root)).expect("read registry");
+        let loaded = load_always_skip_buckets_for_indexing(worktrees_dir).expect("load existing");
+        let raw = fs::read_to_string(registry_path(worktrees_dir)).expect("read registry");
 
         assert!(!loaded.registry_missing);
         assert_eq!(loaded.buckets, default_always_skip_buckets());
@@ -516,15 +970,15 @@ mod tests {
     #[test]
     fn load_always_skip_buckets_for_indexing_tracks_explicit_config_values() {
         let temp = tempfile::tempdir().expect("temp dir");
-        let repo_root = temp.path();
-        fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees dir");
+        let worktrees_dir = &temp.path().join("worktrees");
+        fs::create_dir_all(worktrees_dir).expect("worktrees dir");
         fs::write(
-            registry_path(repo_root),
+            registry_path(worktrees_dir),
             "version = 1\nworktree=[]\n[settings.extras]\nalways_skip_buckets=['target','vendor/bundle']\n",
         )
         .expect("write registry");
 
-        let loaded = load_always_skip_buckets_for_indexing(repo_root).expect("load configured");
+        let loaded = load_always_skip_buckets_for_indexing(worktrees_dir).expect("load configured");
 
         assert!(!loaded.registry_missing);
         assert_eq!(
@@ -537,50 +991,106 @@ mod tests {
     #[test]
     fn registry_schema_replacement_round_trip_includes_extras_settings_and_worktree_entries() {
         let temp = tempfile::tempdir().expect("temp dir");
-        let repo_root = temp.path();
+        let worktrees_dir = &temp.path().join("worktrees");
 
         let entry = RegistryEntry {
             name: "example".to_string(),
-            path: repo_root
-                .join("worktrees")
-                .join("example")
-                .to_string_lossy()
-                .to_string(),
+            path: worktrees_dir.join("example").to_string_lossy().to_string(),
             created_at: "2026-02-28T00:00:00Z".to_string(),
+            created_by: None,
+            disk_usage: None,
+            archived: None,
+            ..Default::default()
         };
 
         save_always_skip_buckets(
-            repo_root,
+            worktrees_dir,
             &BTreeSet::from(["target".to_string(), "node_modules".to_string()]),
         )
         .expect("save buckets");
-        insert_unique_entry(repo_root, entry.clone()).expect("insert entry");
+        insert_unique_entry(worktrees_dir, entry.clone()).expect("insert entry");
 
-        let raw = fs::read_to_string(registry_path(repo_root)).expect("read registry");
-        assert!(raw.contains("version = 1"));
+        let raw = fs::read_to_string(registry_path(worktrees_dir)).expect("read registry");
+        assert!(raw.contains("version = 2"));
         assert!(raw.contains("[settings.extras]"));
         assert!(raw.contains("always_skip_buckets"));
         assert!(raw.contains("[[worktree]]"));
 
-        let entries = load_registry(repo_root).expect("load entries");
+        let entries = load_registry(worktrees_dir).expect("load entries");
         assert_eq!(entries, vec![entry]);
-        let buckets = load_always_skip_buckets(repo_root).expect("load buckets");
+        let buckets = load_always_skip_buckets(worktrees_dir).expect("load buckets");
         assert!(buckets.contains("target"));
         assert!(buckets.contains("node_modules"));
     }
 
+    #[test]
+    fn registry_v1_entries_migrate_to_v2_on_next_write() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let worktrees_dir = &temp.path().join("worktrees");
+        fs::create_dir_all(worktrees_dir).expect("worktrees dir");
+        fs::write(
+            registry_path(worktrees_dir),
+            "version = 1\n[settings.extras]\n[[worktree]]\nname='w1'\npath='/tmp/w1'\ncreated_at='2026-01-01T00:00:00Z'\n",
+        )
+        .expect("write v1 registry");
+
+        let entries = load_registry(worktrees_dir).expect("load v1");
+        assert_eq!(entries[0].branch, None);
+        assert_eq!(entries[0].session_name, None);
+        assert!(entries[0].extras_copied.is_empty());
+
+        assert!(record_attach(worktrees_dir, "w1", "2026-02-01T00:00:00Z").expect("record"));
+        assert!(!record_attach(worktrees_dir, "missing", "2026-02-01T00:00:00Z").expect("record"));
+
+        let raw = fs::read_to_string(registry_path(worktrees_dir)).expect("read registry");
+        assert!(raw.contains("version = 2"));
+        let entry = find_entry_by_name(worktrees_dir, "w1")
+            .expect("load")
+            .expect("entry");
+        assert_eq!(
+            entry.last_attached_at.as_deref(),
+            Some("2026-02-01T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn registry_v2_metadata_round_trips() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let worktrees_dir = &temp.path().join("worktrees");
+
+        let entry = RegistryEntry {
+            name: "feature".to_string(),
+            path: worktrees_dir.join("feature").to_string_lossy().to_string(),
+            created_at: "2026-02-28T00:00:00Z".to_string(),
+            branch: Some("users/me/feature".to_string()),
+            start_point: Some("main".to_string()),
+            session_name: Some("repo".to_string()),
+            layout: Some(SessionLayout::Window),
+            extras_copied: vec![".env".to_string(), "config/local.toml".to_string()],
+            last_attached_at: Some("2026-03-01T00:00:00Z".to_string()),
+            display_name: Some("Fix login & SSO".to_string()),
+            project: Some("api".to_string()),
+            ..Default::default()
+        };
+        insert_unique_entry(worktrees_dir, entry.clone()).expect("insert entry");
+
+        let raw = fs::read_to_string(registry_path(worktrees_dir)).expect("read registry");
+        assert!(raw.contains("layout = \"window\""));
+        assert_eq!(load_registry(worktrees_dir).expect("load"), vec![entry]);
+    }
+
     #[test]
     fn registry_schema_replacement_rejects_legacy_v1_shape() {
         let temp = tempfile::tempdir().expect("temp dir");
-        let repo_root = temp.path();
-        fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees dir");
+        let worktrees_dir = &temp.path().join("worktrees");
+        fs::create_dir_all(worktrees_dir).expect("worktrees dir");
         fs::write(
-            registry_path(repo_root),
+            registry_path(worktrees_dir),
             "[[worktree]]\nname='w1'\npath='/tmp/w1'\ncreated_at='2026-01-01T00:00:00Z'\n",
         )
         .expect("write legacy registry");
 
-        let error = load_registry(repo_root).expect_err("legacy should fail");
+        let error = load_registry(worktrees_dir).expect_err("legacy should fail");
         assert!(error.to_string().starts_with(
             "invalid worktree registry schema: missing required top-level field 'version'"
         ));
@@ -589,15 +1099,15 @@ mod tests {
     #[test]
     fn registry_schema_replacement_reports_actionable_error_for_missing_version() {
         let temp = tempfile::tempdir().expect("temp dir");
-        let repo_root = temp.path();
-        fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees dir");
+        let worktrees_dir = &temp.path().join("worktrees");
+        fs::create_dir_all(worktrees_dir).expect("worktrees dir");
         fs::write(
-            registry_path(repo_root),
+            registry_path(worktrees_dir),
             "[settings.extras]\nalways_skip_buckets=[\"target\"]\n[[worktree]]\nname='w1'\npath='/tmp/w1'\ncreated_at='2026-01-01T00:00:00Z'\n",
         )
         .expect("write registry");
 
-        let error = load_registry(repo_root).expect_err("should fail");
+        let error = load_registry(worktrees_dir).expect_err("should fail");
         assert!(error.to_string().starts_with(
             "invalid worktree registry schema: missing required top-level field 'version'"
         ));
@@ -606,15 +1116,15 @@ mod tests {
     #[test]
     fn registry_schema_replacement_reports_actionable_error_for_unsupported_version() {
         let temp = tempfile::tempdir().expect("temp dir");
-        let repo_root = temp.path();
-        fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees dir");
+        let worktrees_dir = &temp.path().join("worktrees");
+        fs::create_dir_all(worktrees_dir).expect("worktrees dir");
         fs::write(
-            registry_path(repo_root),
-            "version = 2\n[settings.extras]\nalways_skip_buckets=[\"target\"]\nworktree=[]\n",
+            registry_path(worktrees_dir),
+            "version = 3\n[settings.extras]\nalways_skip_buckets=[\"target\"]\nworktree=[]\n",
         )
         .expect("write registry");
 
-        let error = load_registry(repo_root).expect_err("should fail");
+        let error = load_registry(worktrees_dir).expect_err("should fail");
         assert!(
             error
                 .to_string()
@@ -625,11 +1135,12 @@ mod tests {
     #[test]
     fn registry_schema_replacement_reports_actionable_error_for_missing_settings_extras() {
         let temp = tempfile::tempdir().expect("temp dir");
-        let repo_root = temp.path();
-        fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees dir");
-        fs::write(registry_path(repo_root), "version = 1\nworktree=[]\n").expect("write registry");
+        let worktrees_dir = &temp.path().join("worktrees");
+        fs::create_dir_all(worktrees_dir).expect("worktrees dir");
+        fs::write(registry_path(worktrees_dir), "version = 1\nworktree=[]\n")
+            .expect("write registry");
 
-        let error = load_registry(repo_root).expect_err("should fail");
+        let error = load_registry(worktrees_dir).expect_err("should fail");
         assert!(error.to_string().starts_with(
             "invalid worktree registry schema: missing required section [settings.extras]"
         ));
@@ -638,15 +1149,15 @@ mod tests {
     #[test]
     fn registry_schema_replacement_reports_actionable_error_for_missing_worktree_section() {
         let temp = tempfile::tempdir().expect("temp dir");
-        let repo_root = temp.path();
-        fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees dir");
+        let worktrees_dir = &temp.path().join("worktrees");
+        fs::create_dir_all(worktrees_dir).expect("worktrees dir");
         fs::write(
-            registry_path(repo_root),
+            registry_path(worktrees_dir),
             "version = 1\n[settings.extras]\nalways_skip_buckets=[\"target\"]\n",
         )
         .expect("write registry");
 
-        let error = load_registry(repo_root).expect_err("should fail");
+        let error = load_registry(worktrees_dir).expect_err("should fail");
         assert!(error.to_string().starts_with(
             "invalid worktree registry schema: missing required [[worktree]] entries section"
         ));
@@ -655,37 +1166,37 @@ mod tests {
     #[test]
     fn registry_schema_replacement_seeds_defaults_when_always_skip_buckets_unset() {
         let temp = tempfile::tempdir().expect("temp dir");
-        let repo_root = temp.path();
-        fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees dir");
+        let worktrees_dir = &temp.path().join("worktrees");
+        fs::create_dir_all(worktrees_dir).expect("worktrees dir");
         fs::write(
-            registry_path(repo_root),
+            registry_path(worktrees_dir),
             "version = 1\nworktree=[]\n[settings.extras]\n",
         )
         .expect("write registry");
 
-        let buckets = load_always_skip_buckets(repo_root).expect("load buckets");
+        let buckets = load_always_skip_buckets(worktrees_dir).expect("load buckets");
         assert!(buckets.contains("target"));
         assert!(buckets.contains("node_modules"));
 
-        let raw = fs::read_to_string(registry_path(repo_root)).expect("read registry");
+        let raw = fs::read_to_string(registry_path(worktrees_dir)).expect("read registry");
         assert!(raw.contains("always_skip_buckets"));
     }
 
     #[test]
     fn registry_schema_replacement_preserves_explicit_empty_always_skip_buckets_without_reseed() {
         let temp = tempfile::tempdir().expect("temp dir");
-        let repo_root = temp.path();
-        fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees dir");
+        let worktrees_dir = &temp.path().join("worktrees");
+        fs::create_dir_all(worktrees_dir).expect("worktrees dir");
         fs::write(
-            registry_path(repo_root),
+            registry_path(worktrees_dir),
             "version = 1\nworktree=[]\n[settings.extras]\nalways_skip_buckets=[]\n",
         )
         .expect("write registry");
 
-        let buckets = load_always_skip_buckets(repo_root).expect("load buckets");
+        let buckets = load_always_skip_buckets(worktrees_dir).expect("load buckets");
         assert!(buckets.is_empty());
 
-        let raw = fs::read_to_string(registry_path(repo_root)).expect("read registry");
+        let raw = fs::read_to_string(registry_path(worktrees_dir)).expect("read registry");
         assert!(raw.contains("always_skip_buckets"));
         assert!(!raw.contains("\"target\""));
     }
@@ -693,15 +1204,15 @@ mod tests {
     #[test]
     fn registry_schema_replacement_loads_demo_setup_output() {
         let temp = tempfile::tempdir().expect("temp dir");
-        let repo_root = temp.path();
-        fs::create_dir_all(repo_root.join("worktrees")).expect("worktrees dir");
+        let worktrees_dir = &temp.path().join("worktrees");
+        fs::create_dir_all(worktrees_dir).expect("worktrees dir");
         fs::write(
-            registry_path(repo_root),
+            registry_path(worktrees_dir),
             "version = 1\n[settings.extras]\nalways_skip_buckets=[\"target\",\"node_modules\"]\n[[worktree]]\nname='demo'\npath='/tmp/demo/worktrees/demo'\ncreated_at='2026-02-26T00:00:00Z'\n",
         )
         .expect("write registry");
 
-        let entries = load_registry(repo_root).expect("load registry");
+        let entries = load_registry(worktrees_dir).expect("load registry");
         assert_eq!(entries.len(), 1);
         assert_eq!(entries[0].name, "demo");
     }
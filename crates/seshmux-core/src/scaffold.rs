@@ -0,0 +1,315 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{DEFAULT_WORKTREES_DIR, SessionLayout, WindowSpec};
+use crate::registry::default_always_skip_buckets;
+
+#[derive(Debug, Clone)]
+pub struct RepoConfigScaffold {
+    pub windows: Vec<WindowSpec>,
+    pub always_skip_buckets: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalConfigScaffold {
+    pub editor: Vec<String>,
+    pub windows: Vec<WindowSpec>,
+    pub layout: SessionLayout,
+    pub worktrees_dir: String,
+}
+
+impl GlobalConfigScaffold {
+    pub fn detected(editor: Option<&str>, shell: &str) -> Self {
+        let editor = editor
+            .map(|value| {
+                value
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|command| !command.is_empty())
+            .unwrap_or_else(|| vec!["vi".to_string()]);
+        let windows = starter_windows(&editor, shell)
+            .into_iter()
+            .filter(|window| window.name != "git")
+            .collect();
+
+        Self {
+            editor,
+            windows,
+            layout: SessionLayout::Session,
+            worktrees_dir: DEFAULT_WORKTREES_DIR.to_string(),
+        }
+    }
+}
+
+pub fn starter_windows(editor: &[String], shell: &str) -> Vec<WindowSpec> {
+    let (program, args) = match editor.split_first() {
+        Some((program, args)) => (program.clone(), args.to_vec()),
+        None => ("vi".to_string(), Vec::new()),
+    };
+    let mut editor_args = args;
+    editor_args.push(".".to_string());
+
+    vec![
+        direct_window("editor", &program, editor_args),
+        direct_window("shell", shell, Vec::new()),
+        direct_window("git", "lazygit", Vec::new()),
+    ]
+}
+
+pub fn scaffold_repo_config(
+    repo_root: &Path,
+    ignored_dirs: &[PathBuf],
+    shell: &str,
+) -> RepoConfigScaffold {
+    RepoConfigScaffold {
+        windows: infer_windows(repo_root, shell),
+        always_skip_buckets: suggest_skip_buckets(ignored_dirs),
+    }
+}
+
+pub fn infer_windows(repo_root: &Path, shell: &str) -> Vec<WindowSpec> {
+    let mut windows = vec![shell_window(shell, "editor", "${EDITOR:-vi} .")];
+
+    if let Some(command) = package_json_dev_command(repo_root) {
+        windows.push(shell_window(shell, "dev", &command));
+    }
+
+    if repo_root.join("Cargo.toml").is_file() {
+        windows.push(shell_window(
+            shell,
+            "cargo",
+            "cargo check; exec \"${SHELL:-/bin/sh}\"",
+        ));
+    }
+
+    windows
+}
+
+pub fn suggest_skip_buckets(ignored_dirs: &[PathBuf]) -> BTreeSet<String> {
+    let known = default_always_skip_buckets();
+
+    ignored_dirs
+        .iter()
+        .filter(|dir| !dir.starts_with("worktrees"))
+        .filter_map(|dir| {
+            let normalized = dir.to_string_lossy().replace('\\', "/");
+            let normalized = normalized.trim_matches('/');
+
+            known
+                .iter()
+                .find(|bucket| {
+                    normalized == bucket.as_str() || normalized.ends_with(&format!("/{bucket}"))
+                })
+                .cloned()
+        })
+        .collect()
+}
+
+pub fn render_repo_config(scaffold: &RepoConfigScaffold) -> String {
+    let mut out = String::new();
+    out.push_str("# seshmux per-repository config.\n");
+    out.push_str("# Windows here replace [[tmux.windows]] from ~/.config/seshmux/config.toml.\n");
+    out.push_str("version = 1\n");
+
+    push_windows(&mut out, &scaffold.windows);
+
+    out.push_str("\n# Directories never offered for copying into new worktrees.\n");
+    out.push_str("[extras]\n");
+    let buckets: Vec<String> = scaffold.always_skip_buckets.iter().cloned().collect();
+    out.push_str(&format!(
+        "always_skip_buckets = {}\n",
+        toml_string_array(&buckets)
+    ));
+
+    out
+}
+
+pub fn render_global_config(scaffold: &GlobalConfigScaffold) -> String {
+    let mut out = String::new();
+    out.push_str(
+        "# seshmux config, written by the first-run setup. See README.md for every option.\n",
+    );
+    out.push_str("version = 1\n");
+    out.push_str(&format!(
+        "editor = {}\n",
+        toml_string_array(&scaffold.editor)
+    ));
+    out.push_str(
+        "# Where worktrees are created, relative to the repository root; {repo} is its name.\n",
+    );
+    out.push_str(&format!(
+        "worktrees_dir = {}\n",
+        toml_string(&scaffold.worktrees_dir)
+    ));
+
+    out.push_str("\n[tmux]\n");
+    let layout = match scaffold.layout {
+        SessionLayout::Session => "session",
+        SessionLayout::Window => "window",
+    };
+    out.push_str(&format!("layout = {}\n", toml_string(layout)));
+    push_windows(&mut out, &scaffold.windows);
+
+    out
+}
+
+fn push_windows(out: &mut String, windows: &[WindowSpec]) {
+    for window in windows {
+        out.push_str("\n[[tmux.windows]]\n");
+        out.push_str(&format!("name = {}\n", toml_string(&window.name)));
+        if let Some(program) = &window.program {
+            out.push_str(&format!("program = {}\n", toml_string(program)));
+        }
+        if let Some(args) = &window.args {
+            out.push_str(&format!("args = {}\n", toml_string_array(args)));
+        }
+        if let Some(shell) = &window.shell {
+            out.push_str(&format!("shell = {}\n", toml_string_array(shell)));
+        }
+        if let Some(command) = &window.command {
+            out.push_str(&format!("command = {}\n", toml_string(command)));
+        }
+    }
+}
+
+fn direct_window(name: &str, program: &str, args: Vec<String>) -> WindowSpec {
+    WindowSpec {
+        name: name.to_string(),
+        program: Some(program.to_string()),
+        args: Some(args),
+        shell: None,
+        command: None,
+    }
+}
+
+fn shell_window(shell: &str, name: &str, command: &str) -> WindowSpec {
+    WindowSpec {
+        name: name.to_string(),
+        program: None,
+        args: None,
+        shell: Some(vec![shell.to_string(), "-lc".to_string()]),
+        command: Some(command.to_string()),
+    }
+}
+
+fn package_json_dev_command(repo_root: &Path) -> Option<String> {
+    let raw = fs::read_to_string(repo_root.join("package.json")).ok()?;
+    let manifest: serde_json::Value = serde_json::from_str(&raw).ok()?;
+    let scripts = manifest.get("scripts")?.as_object()?;
+    let script = ["dev", "start"]
+        .into_iter()
+        .find(|name| scripts.contains_key(*name))?;
+
+    Some(format!("{} run {script}", node_package_manager(repo_root)))
+}
+
+fn node_package_manager(repo_root: &Path) -> &'static str {
+    if repo_root.join("pnpm-lock.yaml").exists() {
+        "pnpm"
+    } else if repo_root.join("yarn.lock").exists() {
+        "yarn"
+    } else if repo_root.join("bun.lockb").exists() || repo_root.join("bun.lock").exists() {
+        "bun"
+    } else {
+        "npm"
+    }
+}
+
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+fn toml_string_array(values: &[String]) -> String {
+    let items: Vec<String> = values.iter().map(|value| toml_string(value)).collect();
+    format!("[{}]", items.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{RepoConfig, SeshmuxConfig, validate_config, validate_repo_config};
+
+    #[test]
+    fn infer_windows_detects_node_and_cargo_projects() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        fs::write(
+            temp.path().join("package.json"),
+            r#"{"scripts":{"build":"vite build","dev":"vite"}}"#,
+        )
+        .expect("write package.json");
+        fs::write(temp.path().join("pnpm-lock.yaml"), "").expect("write lockfile");
+        fs::write(temp.path().join("Cargo.toml"), "[workspace]\n").expect("write Cargo.toml");
+
+        let windows = infer_windows(temp.path(), "/bin/zsh");
+        let names: Vec<&str> = windows.iter().map(|window| window.name.as_str()).collect();
+
+        assert_eq!(names, vec!["editor", "dev", "cargo"]);
+        assert_eq!(windows[1].command.as_deref(), Some("pnpm run dev"));
+        assert_eq!(
+            windows[1].shell,
+            Some(vec!["/bin/zsh".to_string(), "-lc".to_string()])
+        );
+    }
+
+    #[test]
+    fn suggest_skip_buckets_keeps_known_artifact_directories() {
+        let ignored = vec![
+            PathBuf::from("target"),
+            PathBuf::from("web/node_modules"),
+            PathBuf::from("secrets"),
+            PathBuf::from("worktrees"),
+        ];
+
+        let suggested = suggest_skip_buckets(&ignored);
+
+        assert_eq!(
+            suggested,
+            BTreeSet::from(["node_modules".to_string(), "target".to_string()])
+        );
+    }
+
+    #[test]
+    fn rendered_repo_config_round_trips_through_validation() {
+        let scaffold = RepoConfigScaffold {
+            windows: vec![shell_window("/bin/sh", "editor", "${EDITOR:-vi} \"$PWD\"")],
+            always_skip_buckets: BTreeSet::from(["target".to_string()]),
+        };
+
+        let rendered = render_repo_config(&scaffold);
+        let parsed: RepoConfig = toml::from_str(&rendered).expect("rendered config parses");
+
+        validate_repo_config(&parsed).expect("rendered config is valid");
+        let windows = parsed.tmux.expect("tmux").windows;
+        assert_eq!(windows[0].command, scaffold.windows[0].command);
+        assert_eq!(
+            parsed.extras.expect("extras").always_skip_buckets,
+            vec!["target".to_string()]
+        );
+    }
+
+    #[test]
+    fn rendered_global_config_round_trips_through_validation() {
+        let mut scaffold = GlobalConfigScaffold::detected(Some("code --wait"), "/bin/zsh");
+        assert_eq!(scaffold.editor, vec!["code", "--wait"]);
+        let names: Vec<&str> = scaffold.windows.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, vec!["editor", "shell"]);
+        scaffold.layout = SessionLayout::Window;
+        scaffold.worktrees_dir = "../{repo}-worktrees".to_string();
+
+        let rendered = render_global_config(&scaffold);
+        let parsed: SeshmuxConfig = toml::from_str(&rendered).expect("rendered config parses");
+
+        validate_config(&parsed).expect("rendered config is valid");
+        assert_eq!(parsed.editor, Some(scaffold.editor.clone()));
+        assert_eq!(parsed.tmux.layout, SessionLayout::Window);
+        assert_eq!(parsed.tmux.windows, scaffold.windows);
+        assert_eq!(
+            parsed.tmux.windows[0].args,
+            Some(vec!["--wait".to_string(), ".".to_string()])
+        );
+        assert_eq!(parsed.worktrees_dir.as_deref(), Some("../{repo}-worktrees"));
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const PORT_BASE: u16 = 20000;
+const PORT_SPAN: u32 = 10000;
+const PLACEHOLDERS: [&str; 5] = ["repo", "name", "branch", "path", "port"];
+
+#[derive(Debug, Clone, Copy)]
+pub struct WorktreeTemplateContext<'a> {
+    pub repo: &'a str,
+    pub name: &'a str,
+    pub branch: &'a str,
+    pub path: &'a Path,
+}
+
+pub fn render_session_environment(
+    environment: &BTreeMap<String, String>,
+    context: WorktreeTemplateContext<'_>,
+) -> Vec<(String, String)> {
+    environment
+        .iter()
+        .map(|(key, template)| (key.clone(), render_template(template, context)))
+        .collect()
+}
+
+pub fn render_template(template: &str, context: WorktreeTemplateContext<'_>) -> String {
+    let path = context.path.to_string_lossy();
+    let port = worktree_port(context.repo, context.name).to_string();
+
+    fill_placeholders(
+        template,
+        &[
+            ("repo", context.repo),
+            ("name", context.name),
+            ("branch", context.branch),
+            ("path", &path),
+            ("port", &port),
+        ],
+    )
+}
+
+/// Substitutes in a single pass, so a value that itself contains `{name}` is never expanded
+/// again. Unknown placeholders are kept as written.
+pub fn fill_placeholders(template: &str, values: &[(&str, &str)]) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            values
+                .iter()
+                .find(|(key, _)| *key == &after[..end])
+                .map(|(_, value)| (end, *value))
+        });
+        match value {
+            Some((end, value)) => {
+                rendered.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                rendered.push('{');
+                rest = after;
+            }
+        }
+    }
+    rendered.push_str(rest);
+
+    rendered
+}
+
+/// Stable across runs and machines so a worktree keeps the same port every time its session starts.
+pub fn worktree_port(repo: &str, name: &str) -> u16 {
+    let mut hash: u32 = 0x811c_9dc5;
+    for byte in repo.bytes().chain([b'/']).chain(name.bytes()) {
+        hash ^= u32::from(byte);
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+
+    PORT_BASE + u16::try_from(hash % PORT_SPAN).expect("port offset fits in u16")
+}
+
+pub fn is_valid_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|character| character.is_ascii_alphanumeric() || character == '_')
+}
+
+pub fn unknown_placeholder(template: &str) -> Option<String> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let end = after.find('}')?;
+        let placeholder = &after[..end];
+        if !PLACEHOLDERS.contains(&placeholder) {
+            return Some(placeholder.to_string());
+        }
+        rest = &after[end + 1..];
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn context(path: &Path) -> WorktreeTemplateContext<'_> {
+        WorktreeTemplateContext {
+            repo: "shop",
+            name: "feature-a",
+            branch: "feature-a",
+            path,
+        }
+    }
+
+    #[test]
+    fn renders_placeholders_for_each_variable() {
+        let path = PathBuf::from("/code/shop/worktrees/feature-a");
+        let environment = BTreeMap::from([
+            (
+                "DATABASE_URL".to_string(),
+                "postgres://localhost/{repo}_{name}".to_string(),
+            ),
+            ("PORT".to_string(), "{port}".to_string()),
+            ("WORKTREE".to_string(), "{path}@{branch}".to_string()),
+        ]);
+
+        let rendered = render_session_environment(&environment, context(&path));
+
+        assert_eq!(
+            rendered[0],
+            (
+                "DATABASE_URL".to_string(),
+                "postgres://localhost/shop_feature-a".to_string()
+            )
+        );
+        assert_eq!(
+            rendered[1].1,
+            worktree_port("shop", "feature-a").to_string()
+        );
+        assert_eq!(rendered[2].1, "/code/shop/worktrees/feature-a@feature-a");
+    }
+
+    #[test]
+    fn substituted_values_are_not_expanded_again() {
+        let path = PathBuf::from("/code/{branch}");
+        let context = WorktreeTemplateContext {
+            repo: "shop",
+            name: "{path}",
+            branch: "main",
+            path: &path,
+        };
+
+        assert_eq!(
+            render_template("{name}:{path}:{unknown}", context),
+            "{path}:/code/{branch}:{unknown}"
+        );
+    }
+
+    #[test]
+    fn worktree_port_is_stable_and_in_range() {
+        let port = worktree_port("shop", "feature-a");
+        assert_eq!(port, worktree_port("shop", "feature-a"));
+        assert_ne!(port, worktree_port("shop", "feature-b"));
+        assert!((20000..30000).contains(&port));
+    }
+
+    #[test]
+    fn validates_names_and_placeholders() {
+        assert!(is_valid_variable_name("DATABASE_URL"));
+        assert!(is_valid_variable_name("_PORT2"));
+        assert!(!is_valid_variable_name("2PORT"));
+        assert!(!is_valid_variable_name("MY-VAR"));
+
+        assert_eq!(unknown_placeholder("{name}-{port}"), None);
+        assert_eq!(unknown_placeholder("{user}"), Some("user".to_string()));
+    }
+}
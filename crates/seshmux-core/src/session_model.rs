@@ -0,0 +1,332 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::command_runner::CommandRunner;
+use crate::config::{SessionBackend, SessionLayout, WindowSpec};
+use crate::tabs::{self, TabBackend, TabError};
+use crate::tmux::{self, SessionHealth, TmuxError, TmuxServer};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionTarget {
+    Session {
+        server: TmuxServer,
+        session: String,
+    },
+    Window {
+        server: TmuxServer,
+        session: String,
+        window: String,
+    },
+    Tab {
+        backend: TabBackend,
+        title: String,
+        worktree: String,
+    },
+}
+
+impl SessionTarget {
+    pub fn for_worktree(
+        layout: SessionLayout,
+        backend: SessionBackend,
+        server: &TmuxServer,
+        repo_name: &str,
+        worktree_name: &str,
+    ) -> Self {
+        if let Some(backend) = TabBackend::for_backend(backend) {
+            return Self::Tab {
+                backend,
+                title: tmux::session_name(repo_name, worktree_name),
+                worktree: worktree_name.to_string(),
+            };
+        }
+        match layout {
+            SessionLayout::Session => Self::Session {
+                server: server.clone(),
+                session: tmux::session_name(repo_name, worktree_name),
+            },
+            SessionLayout::Window => Self::Window {
+                server: server.clone(),
+                session: tmux::repo_session_name(repo_name),
+                window: worktree_name.to_string(),
+            },
+        }
+    }
+
+    pub fn session_name(&self) -> &str {
+        match self {
+            Self::Session { session, .. } | Self::Window { session, .. } => session,
+            Self::Tab { title, .. } => title,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Session { session, .. } => session.clone(),
+            Self::Window {
+                session, window, ..
+            } => format!("{session}:{window}"),
+            Self::Tab { title, .. } => title.clone(),
+        }
+    }
+
+    pub fn exists(&self, runner: &dyn CommandRunner) -> Result<bool, TmuxError> {
+        match self {
+            Self::Session { server, session } => tmux::session_exists(session, server, runner),
+            Self::Window {
+                server,
+                session,
+                window,
+            } => tmux::window_exists(session, window, server, runner),
+            Self::Tab { backend, title, .. } => Ok(tabs::tab_exists(*backend, title, runner)?),
+        }
+    }
+
+    pub fn health(&self, runner: &dyn CommandRunner) -> Result<SessionHealth, TmuxError> {
+        match self {
+            Self::Session { server, session } => tmux::session_health(session, server, runner),
+            Self::Window {
+                server,
+                session,
+                window,
+            } => tmux::window_health(session, window, server, runner),
+            Self::Tab { backend, title, .. } => Ok(tabs::tab_health(*backend, title, runner)?),
+        }
+    }
+
+    pub fn create(
+        &self,
+        cwd: &Path,
+        windows: &[WindowSpec],
+        environment: &[(String, String)],
+        runner: &dyn CommandRunner,
+    ) -> Result<(), TmuxError> {
+        match self {
+            Self::Session { server, session } => {
+                tmux::create_session_and_windows(session, cwd, windows, environment, server, runner)
+            }
+            Self::Window {
+                server,
+                session,
+                window,
+            } => {
+                let launch = windows
+                    .first()
+                    .ok_or_else(|| TmuxError::InvalidWindowMode {
+                        window: "<missing>".to_string(),
+                    })?;
+                tmux::create_window(session, window, cwd, launch, environment, server, runner)
+            }
+            Self::Tab { backend, title, .. } => Ok(tabs::create_tab(
+                *backend,
+                title,
+                cwd,
+                windows,
+                environment,
+                runner,
+            )?),
+        }
+    }
+
+    pub fn panes(&self, runner: &dyn CommandRunner) -> Result<Vec<tmux::PaneInfo>, TmuxError> {
+        match self {
+            Self::Session { server, session } => tmux::list_session_panes(session, server, runner),
+            Self::Window {
+                server,
+                session,
+                window,
+            } => tmux::list_window_panes(session, window, server, runner),
+            Self::Tab { backend, .. } => Err(TabError::Unsupported {
+                program: backend.program(),
+                action: "reading pane layouts",
+            }
+            .into()),
+        }
+    }
+
+    pub fn kill(&self, runner: &dyn CommandRunner) -> Result<(), TmuxError> {
+        match self {
+            Self::Session { server, session } => tmux::kill_session(session, server, runner),
+            Self::Window {
+                server,
+                session,
+                window,
+            } => tmux::kill_window(session, window, server, runner),
+            Self::Tab { backend, title, .. } => Ok(tabs::close_tab(*backend, title, runner)?),
+        }
+    }
+
+    /// The session-level environment new sessions export with `set-environment`. Windows and
+    /// tabs only pass variables to the processes they start, so they have none to inspect.
+    pub fn exported_environment(
+        &self,
+        runner: &dyn CommandRunner,
+    ) -> Result<BTreeMap<String, String>, TmuxError> {
+        match self {
+            Self::Session { server, session } => tmux::session_environment(session, server, runner),
+            Self::Window { .. } | Self::Tab { .. } => Ok(BTreeMap::new()),
+        }
+    }
+
+    pub fn kill_args(&self) -> Vec<String> {
+        match self {
+            Self::Session { session, .. } => {
+                vec![
+                    "kill-session".to_string(),
+                    "-t".to_string(),
+                    tmux::session_target(session),
+                ]
+            }
+            Self::Window {
+                session, window, ..
+            } => vec![
+                "kill-window".to_string(),
+                "-t".to_string(),
+                tmux::window_target(session, window),
+            ],
+            Self::Tab { .. } => Vec::new(),
+        }
+    }
+
+    pub fn connect_command(&self, inside_tmux: bool) -> String {
+        match self {
+            Self::Session { server, session } => {
+                tmux::connect_command(session, inside_tmux, server)
+            }
+            Self::Window {
+                server,
+                session,
+                window,
+            } => tmux::connect_window_command(session, window, inside_tmux, server),
+            Self::Tab {
+                backend,
+                title,
+                worktree,
+            } => tabs::focus_command(*backend, title, worktree),
+        }
+    }
+
+    pub fn connect(&self, inside_tmux: bool, runner: &dyn CommandRunner) -> Result<(), TmuxError> {
+        match self {
+            Self::Session { server, session } => {
+                tmux::connect_session(session, inside_tmux, server, runner)
+            }
+            Self::Window {
+                server,
+                session,
+                window,
+            } => tmux::connect_window(session, window, inside_tmux, server, runner),
+            Self::Tab { backend, title, .. } => Ok(tabs::focus_tab(*backend, title, runner)?),
+        }
+    }
+}
+
+pub fn running_labels(
+    layout: SessionLayout,
+    backend: SessionBackend,
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<BTreeSet<String>, TmuxError> {
+    if let Some(backend) = TabBackend::for_backend(backend) {
+        return Ok(tabs::list_tab_titles(backend, runner)?);
+    }
+    match layout {
+        SessionLayout::Session => tmux::list_session_names(server, runner),
+        SessionLayout::Window => tmux::list_window_labels(server, runner),
+    }
+}
+
+pub fn activity_by_label(
+    layout: SessionLayout,
+    backend: SessionBackend,
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<BTreeMap<String, u64>, TmuxError> {
+    if !backend.is_tmux() {
+        return Ok(BTreeMap::new());
+    }
+    match layout {
+        SessionLayout::Session => tmux::list_session_activity(server, runner),
+        SessionLayout::Window => tmux::list_window_activity(server, runner),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{SessionBackend, SessionLayout};
+    use crate::test_support::{RecordingRunner, output};
+
+    use super::SessionTarget;
+    use crate::tabs::TabBackend;
+    use crate::tmux::TmuxServer;
+
+    #[test]
+    fn window_layout_targets_a_window_of_the_repo_session() {
+        let session = SessionTarget::for_worktree(
+            SessionLayout::Session,
+            SessionBackend::Tmux,
+            &TmuxServer::Default,
+            "My Repo",
+            "w1",
+        );
+        assert_eq!(session.label(), "my-repo/w1");
+
+        let server = TmuxServer::Named("work".to_string());
+        let window = SessionTarget::for_worktree(
+            SessionLayout::Window,
+            SessionBackend::Tmux,
+            &server,
+            "My Repo",
+            "w1",
+        );
+        assert_eq!(window.label(), "my-repo:w1");
+
+        let runner =
+            RecordingRunner::new(vec![output("w1\n", "", 0), output("", "", 0)], Vec::new());
+        assert!(window.exists(&runner).expect("exists"));
+        window.kill(&runner).expect("kill");
+
+        let calls = runner.calls();
+        assert_eq!(
+            calls[0].args,
+            vec![
+                "-L",
+                "work",
+                "list-windows",
+                "-t",
+                "=my-repo",
+                "-F",
+                "#{window_name}"
+            ]
+        );
+        assert_eq!(
+            calls[1].args,
+            vec!["-L", "work", "kill-window", "-t", "my-repo:=w1"]
+        );
+        assert_eq!(window.kill_args(), calls[1].args[2..]);
+        assert_eq!(
+            session.kill_args(),
+            vec!["kill-session", "-t", "=my-repo/w1"]
+        );
+    }
+
+    #[test]
+    fn tab_targets_go_through_the_terminal_and_have_no_tmux_kill() {
+        let tab = SessionTarget::Tab {
+            backend: TabBackend::Kitty,
+            title: "my-repo/w1".to_string(),
+            worktree: "w1".to_string(),
+        };
+        assert_eq!(tab.label(), "my-repo/w1");
+        assert_eq!(tab.session_name(), "my-repo/w1");
+        assert!(tab.kill_args().is_empty());
+
+        let runner = RecordingRunner::from_outputs(vec![output(
+            r#"[{"tabs": [{"id": 3, "title": "my-repo/w1", "windows": []}]}]"#,
+            "",
+            0,
+        )]);
+        assert!(tab.exists(&runner).expect("exists"));
+        assert_eq!(runner.calls()[0].program, "kitty");
+        assert!(tab.panes(&runner).is_err());
+    }
+}
@@ -0,0 +1,574 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::command_adapter;
+use crate::command_runner::{CommandOutput, CommandRunner};
+use crate::config::{SessionBackend, WindowSpec, parse_window_launch};
+use crate::tmux::{SessionHealth, shell_quote};
+
+#[derive(Debug, Error)]
+pub enum TabError {
+    #[error("failed to execute {program}: {message}")]
+    Execute {
+        program: &'static str,
+        message: String,
+    },
+    #[error("{program} command failed: {program} {command} (exit {status}) {stderr}")]
+    CommandFailed {
+        program: &'static str,
+        command: String,
+        status: i32,
+        stderr: String,
+    },
+    #[error("failed to parse {program} output: {message}")]
+    Parse {
+        program: &'static str,
+        message: String,
+    },
+    #[error("no {program} tab is titled '{title}'")]
+    MissingTab {
+        program: &'static str,
+        title: String,
+    },
+    #[error("window '{window}' has invalid launch mode")]
+    InvalidWindowMode { window: String },
+    #[error("worktree path is not valid UTF-8")]
+    InvalidPath,
+    #[error("{action} needs the tmux backend; {program} tabs do not support it")]
+    Unsupported {
+        program: &'static str,
+        action: &'static str,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabBackend {
+    Kitty,
+    Wezterm,
+}
+
+impl TabBackend {
+    pub fn for_backend(backend: SessionBackend) -> Option<Self> {
+        match backend {
+            SessionBackend::Tmux => None,
+            SessionBackend::Kitty => Some(Self::Kitty),
+            SessionBackend::Wezterm => Some(Self::Wezterm),
+        }
+    }
+
+    pub fn program(&self) -> &'static str {
+        match self {
+            Self::Kitty => "kitty",
+            Self::Wezterm => "wezterm",
+        }
+    }
+
+    fn cli_args(&self) -> &'static [&'static str] {
+        match self {
+            Self::Kitty => &["@"],
+            Self::Wezterm => &["cli"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TabInfo {
+    id: u64,
+    title: String,
+    panes: Vec<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KittyOsWindow {
+    tabs: Vec<KittyTab>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KittyTab {
+    id: u64,
+    title: String,
+    windows: Vec<KittyWindow>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KittyWindow {
+    id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct WeztermPane {
+    tab_id: u64,
+    pane_id: u64,
+    #[serde(default)]
+    tab_title: String,
+}
+
+pub fn list_tab_titles(
+    backend: TabBackend,
+    runner: &dyn CommandRunner,
+) -> Result<BTreeSet<String>, TabError> {
+    Ok(list_tabs(backend, runner)?
+        .into_iter()
+        .map(|tab| tab.title)
+        .collect())
+}
+
+pub fn tab_health(
+    backend: TabBackend,
+    title: &str,
+    runner: &dyn CommandRunner,
+) -> Result<SessionHealth, TabError> {
+    let output = run_cli(backend, runner, &list_args(backend))?;
+    if output.status_code != 0 {
+        return Ok(SessionHealth::ServerUnreachable);
+    }
+    let found = parse_tabs(backend, &output.stdout)?
+        .iter()
+        .any(|tab| tab.title == title);
+    Ok(if found {
+        SessionHealth::Running
+    } else {
+        SessionHealth::Missing
+    })
+}
+
+pub fn tab_exists(
+    backend: TabBackend,
+    title: &str,
+    runner: &dyn CommandRunner,
+) -> Result<bool, TabError> {
+    Ok(find_tab(backend, title, runner)?.is_some())
+}
+
+pub fn create_tab(
+    backend: TabBackend,
+    title: &str,
+    cwd: &Path,
+    windows: &[WindowSpec],
+    environment: &[(String, String)],
+    runner: &dyn CommandRunner,
+) -> Result<(), TabError> {
+    let first = windows.first().ok_or_else(|| TabError::InvalidWindowMode {
+        window: "<missing>".to_string(),
+    })?;
+    let cwd = cwd.to_str().ok_or(TabError::InvalidPath)?;
+
+    let mut args = match backend {
+        TabBackend::Kitty => {
+            let mut args = kitty_launch_args("tab", first, cwd, environment);
+            args.extend(["--tab-title".to_string(), title.to_string()]);
+            args
+        }
+        TabBackend::Wezterm => vec!["spawn".to_string(), "--cwd".to_string(), cwd.to_string()],
+    };
+    args.extend(window_command(backend, first, environment)?);
+    let output = run_cli_checked(backend, runner, &args)?;
+    let first_pane = output.stdout.trim().to_string();
+    if first_pane.parse::<u64>().is_err() {
+        return Err(TabError::Parse {
+            program: backend.program(),
+            message: format!("expected the new window id, got '{first_pane}'"),
+        });
+    }
+
+    if backend == TabBackend::Wezterm {
+        run_cli_checked(
+            backend,
+            runner,
+            &["set-tab-title", "--pane-id", first_pane.as_str(), title],
+        )?;
+    }
+
+    for window in &windows[1..] {
+        let mut args = match backend {
+            TabBackend::Kitty => {
+                let mut args = kitty_launch_args("window", window, cwd, environment);
+                args.extend(["--match".to_string(), format!("window_id:{first_pane}")]);
+                args
+            }
+            TabBackend::Wezterm => vec![
+                "split-pane".to_string(),
+                "--pane-id".to_string(),
+                first_pane.clone(),
+                "--cwd".to_string(),
+                cwd.to_string(),
+            ],
+        };
+        args.extend(window_command(backend, window, environment)?);
+        run_cli_checked(backend, runner, &args)?;
+    }
+
+    Ok(())
+}
+
+pub fn close_tab(
+    backend: TabBackend,
+    title: &str,
+    runner: &dyn CommandRunner,
+) -> Result<(), TabError> {
+    let Some(tab) = find_tab(backend, title, runner)? else {
+        return Ok(());
+    };
+    match backend {
+        TabBackend::Kitty => {
+            run_cli_checked(
+                backend,
+                runner,
+                &["close-tab", "--match", &format!("id:{}", tab.id)],
+            )?;
+        }
+        TabBackend::Wezterm => {
+            for pane in &tab.panes {
+                run_cli_checked(
+                    backend,
+                    runner,
+                    &["kill-pane", "--pane-id", &pane.to_string()],
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn focus_tab(
+    backend: TabBackend,
+    title: &str,
+    runner: &dyn CommandRunner,
+) -> Result<(), TabError> {
+    let tab = find_tab(backend, title, runner)?.ok_or_else(|| TabError::MissingTab {
+        program: backend.program(),
+        title: title.to_string(),
+    })?;
+    let id = tab.id.to_string();
+    let args = match backend {
+        TabBackend::Kitty => vec![
+            "focus-tab".to_string(),
+            "--match".to_string(),
+            format!("id:{id}"),
+        ],
+        TabBackend::Wezterm => vec!["activate-tab".to_string(), "--tab-id".to_string(), id],
+    };
+    run_cli_checked(backend, runner, &args)?;
+    Ok(())
+}
+
+/// WezTerm only addresses tabs by id, which changes with every tab, so it goes through
+/// `seshmux attach` instead.
+pub fn focus_command(backend: TabBackend, title: &str, worktree_name: &str) -> String {
+    match backend {
+        TabBackend::Kitty => format!(
+            "kitty @ focus-tab --match {}",
+            shell_quote(&format!("title:^{}$", regex_escape(title)))
+        ),
+        TabBackend::Wezterm => format!("seshmux attach {}", shell_quote(worktree_name)),
+    }
+}
+
+fn find_tab(
+    backend: TabBackend,
+    title: &str,
+    runner: &dyn CommandRunner,
+) -> Result<Option<TabInfo>, TabError> {
+    Ok(list_tabs(backend, runner)?
+        .into_iter()
+        .find(|tab| tab.title == title))
+}
+
+fn list_tabs(backend: TabBackend, runner: &dyn CommandRunner) -> Result<Vec<TabInfo>, TabError> {
+    let output = run_cli(backend, runner, &list_args(backend))?;
+    if output.status_code != 0 {
+        return Ok(Vec::new());
+    }
+    parse_tabs(backend, &output.stdout)
+}
+
+fn list_args(backend: TabBackend) -> Vec<String> {
+    match backend {
+        TabBackend::Kitty => vec!["ls".to_string()],
+        TabBackend::Wezterm => vec![
+            "list".to_string(),
+            "--format".to_string(),
+            "json".to_string(),
+        ],
+    }
+}
+
+fn parse_tabs(backend: TabBackend, raw: &str) -> Result<Vec<TabInfo>, TabError> {
+    let parse_error = |error: serde_json::Error| TabError::Parse {
+        program: backend.program(),
+        message: error.to_string(),
+    };
+    match backend {
+        TabBackend::Kitty => {
+            let os_windows: Vec<KittyOsWindow> = serde_json::from_str(raw).map_err(parse_error)?;
+            Ok(os_windows
+                .into_iter()
+                .flat_map(|os_window| os_window.tabs)
+                .map(|tab| TabInfo {
+                    id: tab.id,
+                    title: tab.title,
+                    panes: tab.windows.iter().map(|window| window.id).collect(),
+                })
+                .collect())
+        }
+        TabBackend::Wezterm => {
+            let panes: Vec<WeztermPane> = serde_json::from_str(raw).map_err(parse_error)?;
+            let mut tabs: Vec<TabInfo> = Vec::new();
+            for pane in panes {
+                match tabs.iter_mut().find(|tab| tab.id == pane.tab_id) {
+                    Some(tab) => tab.panes.push(pane.pane_id),
+                    None => tabs.push(TabInfo {
+                        id: pane.tab_id,
+                        title: pane.tab_title,
+                        panes: vec![pane.pane_id],
+                    }),
+                }
+            }
+            Ok(tabs)
+        }
+    }
+}
+
+fn kitty_launch_args(
+    kind: &str,
+    window: &WindowSpec,
+    cwd: &str,
+    environment: &[(String, String)],
+) -> Vec<String> {
+    let mut args = vec![
+        "launch".to_string(),
+        format!("--type={kind}"),
+        "--keep-focus".to_string(),
+        "--title".to_string(),
+        window.name.clone(),
+        "--cwd".to_string(),
+        cwd.to_string(),
+    ];
+    for (key, value) in environment {
+        args.extend(["--env".to_string(), format!("{key}={value}")]);
+    }
+    args
+}
+
+/// kitty sets the environment itself; `wezterm cli` cannot, so the command runs through `env`.
+fn window_command(
+    backend: TabBackend,
+    window: &WindowSpec,
+    environment: &[(String, String)],
+) -> Result<Vec<String>, TabError> {
+    let launch = parse_window_launch(window)
+        .map(|launch| launch.into_command_parts())
+        .map_err(|_| TabError::InvalidWindowMode {
+            window: window.name.clone(),
+        })?;
+    let mut command = Vec::new();
+    if backend == TabBackend::Wezterm {
+        command.push("--".to_string());
+        if !environment.is_empty() {
+            command.push("env".to_string());
+            command.extend(
+                environment
+                    .iter()
+                    .map(|(key, value)| format!("{key}={value}")),
+            );
+        }
+    }
+    command.extend(launch);
+    Ok(command)
+}
+
+/// kitty matches titles as regular expressions.
+fn regex_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        if !character.is_alphanumeric() && !matches!(character, '/' | '-' | '_' | ' ') {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
+
+fn run_cli_checked(
+    backend: TabBackend,
+    runner: &dyn CommandRunner,
+    args: &[impl AsRef<str>],
+) -> Result<CommandOutput, TabError> {
+    let arg_refs: Vec<&str> = args.iter().map(|value| value.as_ref()).collect();
+    let output = run_cli(backend, runner, &arg_refs)?;
+    command_adapter::ensure_success(&arg_refs, output).map_err(|failure| TabError::CommandFailed {
+        program: backend.program(),
+        command: failure.command,
+        status: failure.status,
+        stderr: failure.stderr,
+    })
+}
+
+fn run_cli(
+    backend: TabBackend,
+    runner: &dyn CommandRunner,
+    args: &[impl AsRef<str>],
+) -> Result<CommandOutput, TabError> {
+    let mut full_args: Vec<&str> = backend.cli_args().to_vec();
+    full_args.extend(args.iter().map(|value| value.as_ref()));
+    command_adapter::run_program(runner, backend.program(), &full_args, None).map_err(|message| {
+        TabError::Execute {
+            program: backend.program(),
+            message,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::config::WindowSpec;
+    use crate::test_support::{RecordingRunner, output};
+    use crate::tmux::SessionHealth;
+
+    use super::{TabBackend, close_tab, create_tab, focus_command, tab_health};
+
+    fn windows() -> Vec<WindowSpec> {
+        vec![
+            WindowSpec {
+                name: "code".to_string(),
+                program: Some("nvim".to_string()),
+                args: None,
+                shell: None,
+                command: None,
+            },
+            WindowSpec {
+                name: "test".to_string(),
+                program: None,
+                args: None,
+                shell: Some(vec!["bash".to_string(), "-lc".to_string()]),
+                command: Some("cargo test".to_string()),
+            },
+        ]
+    }
+
+    #[test]
+    fn kitty_tabs_are_launched_titled_and_split_then_closed_by_id() {
+        let runner = RecordingRunner::from_outputs(vec![
+            output("12\n", "", 0),
+            output("13\n", "", 0),
+            output(
+                r#"[{"id": 1, "tabs": [{"id": 7, "title": "repo/w1", "windows": [{"id": 12}, {"id": 13}]}]}]"#,
+                "",
+                0,
+            ),
+            output("", "", 0),
+        ]);
+        let environment = vec![("SESHMUX_NAME".to_string(), "w1".to_string())];
+
+        create_tab(
+            TabBackend::Kitty,
+            "repo/w1",
+            Path::new("/repo/worktrees/w1"),
+            &windows(),
+            &environment,
+            &runner,
+        )
+        .expect("create");
+        close_tab(TabBackend::Kitty, "repo/w1", &runner).expect("close");
+
+        let calls = runner.calls();
+        assert_eq!(calls[0].program, "kitty");
+        assert_eq!(
+            calls[0].args,
+            vec![
+                "@",
+                "launch",
+                "--type=tab",
+                "--keep-focus",
+                "--title",
+                "code",
+                "--cwd",
+                "/repo/worktrees/w1",
+                "--env",
+                "SESHMUX_NAME=w1",
+                "--tab-title",
+                "repo/w1",
+                "nvim",
+            ]
+        );
+        assert_eq!(
+            calls[1].args[1..4],
+            ["launch", "--type=window", "--keep-focus"]
+        );
+        assert_eq!(
+            calls[1].args[calls[1].args.len() - 5..],
+            ["--match", "window_id:12", "bash", "-lc", "cargo test"]
+        );
+        assert_eq!(calls[3].args, vec!["@", "close-tab", "--match", "id:7"]);
+        assert_eq!(
+            focus_command(TabBackend::Kitty, "my.repo/w1", "w1"),
+            r"kitty @ focus-tab --match 'title:^my\.repo/w1$'"
+        );
+    }
+
+    #[test]
+    fn wezterm_tabs_are_spawned_through_env_and_found_by_tab_title() {
+        let runner = RecordingRunner::from_outputs(vec![
+            output("3\n", "", 0),
+            output("", "", 0),
+            output("4\n", "", 0),
+            output(
+                r#"[{"window_id": 0, "tab_id": 2, "pane_id": 3, "tab_title": "repo/w1"},
+                    {"window_id": 0, "tab_id": 2, "pane_id": 4, "tab_title": "repo/w1"},
+                    {"window_id": 0, "tab_id": 5, "pane_id": 9, "tab_title": ""}]"#,
+                "",
+                0,
+            ),
+            output("", "", 0),
+            output("", "", 0),
+            output("", "no running wezterm", 1),
+        ]);
+        let environment = vec![("SESHMUX_NAME".to_string(), "w1".to_string())];
+
+        create_tab(
+            TabBackend::Wezterm,
+            "repo/w1",
+            Path::new("/repo/worktrees/w1"),
+            &windows(),
+            &environment,
+            &runner,
+        )
+        .expect("create");
+        close_tab(TabBackend::Wezterm, "repo/w1", &runner).expect("close");
+        assert_eq!(
+            tab_health(TabBackend::Wezterm, "repo/w1", &runner).expect("health"),
+            SessionHealth::ServerUnreachable
+        );
+
+        let calls = runner.calls();
+        assert_eq!(
+            calls[0].args,
+            vec![
+                "cli",
+                "spawn",
+                "--cwd",
+                "/repo/worktrees/w1",
+                "--",
+                "env",
+                "SESHMUX_NAME=w1",
+                "nvim",
+            ]
+        );
+        assert_eq!(
+            calls[1].args,
+            vec!["cli", "set-tab-title", "--pane-id", "3", "repo/w1"]
+        );
+        assert_eq!(calls[2].args[1..4], ["split-pane", "--pane-id", "3"]);
+        assert_eq!(calls[4].args, vec!["cli", "kill-pane", "--pane-id", "3"]);
+        assert_eq!(calls[5].args, vec!["cli", "kill-pane", "--pane-id", "4"]);
+        assert_eq!(
+            focus_command(TabBackend::Wezterm, "repo/w1", "w1"),
+            "seshmux attach w1"
+        );
+    }
+}
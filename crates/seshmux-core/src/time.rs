@@ -1,3 +1,5 @@
+use std::time::SystemTime;
+
 use time::OffsetDateTime;
 use time::format_description::well_known::Rfc3339;
 
@@ -5,14 +7,77 @@ pub fn now_utc_rfc3339() -> Result<String, time::error::Format> {
     OffsetDateTime::now_utc().format(&Rfc3339)
 }
 
+pub fn compact_utc_date(value: SystemTime) -> String {
+    let date = OffsetDateTime::from(value).date();
+    format!(
+        "{:04}{:02}{:02}",
+        date.year(),
+        u8::from(date.month()),
+        date.day()
+    )
+}
+
+pub fn system_time_rfc3339(value: SystemTime) -> Result<String, time::error::Format> {
+    OffsetDateTime::from(value).format(&Rfc3339)
+}
+
+pub fn seconds_since_rfc3339(value: &str, now: SystemTime) -> Option<u64> {
+    let then = OffsetDateTime::parse(value.trim(), &Rfc3339).ok()?;
+    let elapsed = OffsetDateTime::from(now) - then;
+    u64::try_from(elapsed.whole_seconds()).ok()
+}
+
+pub fn unix_seconds(value: SystemTime) -> u64 {
+    value
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+pub fn rfc3339_unix_seconds(value: &str) -> Option<u64> {
+    let parsed = OffsetDateTime::parse(value.trim(), &Rfc3339).ok()?;
+    u64::try_from(parsed.unix_timestamp()).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn compact_utc_date_pads_month_and_day() {
+        let value = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_770_000_000);
+        assert_eq!(compact_utc_date(value), "20260202");
+    }
+
     #[test]
     fn now_utc_rfc3339_formats_timestamp() {
         let value = now_utc_rfc3339().expect("timestamp");
         assert!(value.ends_with('Z'));
         assert!(value.contains('T'));
     }
+
+    #[test]
+    fn system_time_rfc3339_formats_in_utc() {
+        let value = system_time_rfc3339(SystemTime::UNIX_EPOCH).expect("timestamp");
+        assert_eq!(value, "1970-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn seconds_since_rfc3339_measures_elapsed_time() {
+        let now = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(90_000);
+        assert_eq!(
+            seconds_since_rfc3339("1970-01-02T00:00:00Z", now),
+            Some(3_600)
+        );
+        assert_eq!(seconds_since_rfc3339("1970-01-03T00:00:00Z", now), None);
+        assert_eq!(seconds_since_rfc3339("yesterday", now), None);
+    }
+
+    #[test]
+    fn unix_seconds_round_trip_through_rfc3339() {
+        let now = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(86_400);
+        assert_eq!(unix_seconds(now), 86_400);
+        assert_eq!(rfc3339_unix_seconds("1970-01-02T00:00:00Z"), Some(86_400));
+        assert_eq!(rfc3339_unix_seconds("1969-12-31T00:00:00Z"), None);
+    }
 }
@@ -1,16 +1,19 @@
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
-use crate::command_adapter;
-use crate::command_runner::CommandRunner;
-use crate::config::{WindowSpec, parse_window_launch};
+use crate::command_adapter::{self, RunFailure};
+use crate::command_runner::{CommandRunner, CommandTimedOut};
+use crate::config::{TmuxConfig, WindowSpec, parse_window_launch};
 use crate::names::sanitize_repo_component;
 
 #[derive(Debug, Error)]
 pub enum TmuxError {
     #[error("failed to execute tmux command: {0}")]
     Execute(String),
+    #[error(transparent)]
+    TimedOut(CommandTimedOut),
     #[error("tmux command failed: tmux {command} (exit {status}) {stderr}")]
     CommandFailed {
         command: String,
@@ -21,16 +24,128 @@ pub enum TmuxError {
     InvalidWindowMode { window: String },
     #[error("worktree path is not valid UTF-8")]
     InvalidPath,
+    #[error(transparent)]
+    Tab(#[from] crate::tabs::TabError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionHealth {
+    Running,
+    Missing,
+    ServerUnreachable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum TmuxServer {
+    #[default]
+    Default,
+    Named(String),
+    Path(PathBuf),
+}
+
+impl TmuxServer {
+    pub fn from_config(config: &TmuxConfig) -> Self {
+        match (&config.socket_name, &config.socket_path) {
+            (Some(name), _) => Self::Named(name.clone()),
+            (None, Some(path)) => Self::Path(PathBuf::from(path)),
+            (None, None) => Self::Default,
+        }
+    }
+
+    pub fn args(&self) -> Vec<String> {
+        match self {
+            Self::Default => Vec::new(),
+            Self::Named(name) => vec!["-L".to_string(), name.clone()],
+            Self::Path(path) => vec!["-S".to_string(), path.to_string_lossy().into_owned()],
+        }
+    }
+
+    pub fn serves_client(&self, tmux_env: &str) -> bool {
+        let socket = Path::new(tmux_env.split(',').next().unwrap_or_default());
+        match self {
+            Self::Default => true,
+            Self::Named(name) => socket.file_name().is_some_and(|file| file == name.as_str()),
+            Self::Path(path) => socket == path,
+        }
+    }
+}
+
+fn server_args(server: &TmuxServer, args: &[&str]) -> Vec<String> {
+    let mut full = server.args();
+    full.extend(args.iter().map(|arg| arg.to_string()));
+    full
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IndexBase {
+    pub window: usize,
+    pub pane: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaneInfo {
+    pub window_index: usize,
+    pub window_name: String,
+    pub window_layout: String,
+    pub command: String,
+    pub path: PathBuf,
+}
+
+const PANE_FORMAT: &str = "#{window_index}\t#{window_name}\t#{window_layout}\t#{pane_current_command}\t#{pane_current_path}";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionStatus {
+    pub windows: usize,
+    pub attached_clients: usize,
+    pub activity: u64,
+}
+
+const SESSION_STATUS_FORMAT: &str =
+    "#{session_name}\t#{session_windows}\t#{session_attached}\t#{session_activity}";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionInfo {
+    pub name: String,
+    pub path: PathBuf,
 }
 
 pub fn session_name(repo_name: &str, worktree_name: &str) -> String {
     format!("{}/{}", sanitize_repo_component(repo_name), worktree_name)
 }
 
+pub fn session_name_prefix(repo_name: &str) -> String {
+    session_name(repo_name, "")
+}
+
+pub fn is_worktree_session_name(name: &str) -> bool {
+    name.split_once('/').is_some_and(|(repo, worktree)| {
+        !repo.is_empty()
+            && sanitize_repo_component(repo) == repo
+            && !worktree.is_empty()
+            && !worktree.contains('/')
+    })
+}
+
+pub fn repo_session_name(repo_name: &str) -> String {
+    sanitize_repo_component(repo_name)
+}
+
+/// A bare `-t repo/w1` is a prefix match that also finds `repo/w10`; `=` makes it exact.
+pub fn session_target(session: &str) -> String {
+    format!("={session}")
+}
+
+/// `=` makes tmux match the window name exactly, so `w1` never resolves to `w10`.
+pub fn window_target(session: &str, window: &str) -> String {
+    format!("{session}:={window}")
+}
+
 pub fn create_session_and_windows(
     session: &str,
     cwd: &Path,
     windows: &[WindowSpec],
+    environment: &[(String, String)],
+    server: &TmuxServer,
     runner: &dyn CommandRunner,
 ) -> Result<(), TmuxError> {
     if windows.is_empty() {
@@ -43,6 +158,10 @@ pub fn create_session_and_windows(
 
     let first = &windows[0];
     let first_launch = build_window_launch(first)?;
+    let environment_args: Vec<String> = environment
+        .iter()
+        .flat_map(|(key, value)| ["-e".to_string(), format!("{key}={value}")])
+        .collect();
 
     let mut create_args = vec![
         "new-session".to_string(),
@@ -54,49 +173,260 @@ pub fn create_session_and_windows(
         "-n".to_string(),
         first.name.clone(),
     ];
+    create_args.extend(environment_args.iter().cloned());
     create_args.extend(first_launch);
 
-    run_tmux_checked(runner, &create_args, None)?;
+    run_tmux_checked(server, runner, &create_args, None)?;
 
-    for window in windows.iter().skip(1) {
+    // Read after new-session so the server has loaded the user's tmux.conf.
+    let base = index_base(server, runner);
+
+    // Windows opened later by hand inherit the session environment rather than `-e`.
+    for (key, value) in environment {
+        run_tmux_checked(
+            server,
+            runner,
+            &[
+                "set-environment",
+                "-t",
+                session,
+                key.as_str(),
+                value.as_str(),
+            ],
+            None,
+        )?;
+    }
+
+    for (offset, window) in windows.iter().enumerate().skip(1) {
         let launch = build_window_launch(window)?;
         let mut args = vec![
             "new-window".to_string(),
             "-t".to_string(),
-            session.to_string(),
+            format!("{session}:{}", base.window + offset),
             "-c".to_string(),
             cwd_value.to_string(),
             "-n".to_string(),
             window.name.clone(),
         ];
+        args.extend(environment_args.iter().cloned());
         args.extend(launch);
 
-        run_tmux_checked(runner, &args, None)?;
+        run_tmux_checked(server, runner, &args, None)?;
+    }
+
+    if windows.len() > 1 {
+        let first_window = format!("{session}:{}", base.window);
+        run_tmux_checked(
+            server,
+            runner,
+            &["select-window", "-t", &first_window],
+            None,
+        )?;
+        let first_pane = format!("{first_window}.{}", base.pane);
+        run_tmux_checked(server, runner, &["select-pane", "-t", &first_pane], None)?;
     }
 
     Ok(())
 }
 
-pub fn session_exists(session: &str, runner: &dyn CommandRunner) -> Result<bool, TmuxError> {
-    let args = ["has-session", "-t", session];
-    let output = run_tmux(runner, &args, None)?;
+pub fn index_base(server: &TmuxServer, runner: &dyn CommandRunner) -> IndexBase {
+    IndexBase {
+        window: global_index_option(server, runner, &["show-options", "-gv", "base-index"]),
+        pane: global_index_option(server, runner, &["show-options", "-gwv", "pane-base-index"]),
+    }
+}
+
+fn global_index_option(server: &TmuxServer, runner: &dyn CommandRunner, args: &[&str]) -> usize {
+    match run_tmux(server, runner, args, None) {
+        Ok(output) if output.status_code == 0 => output.stdout.trim().parse().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+pub fn session_exists(
+    session: &str,
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<bool, TmuxError> {
+    let target = session_target(session);
+    let output = run_tmux(server, runner, &["has-session", "-t", &target], None)?;
 
     Ok(output.status_code == 0)
 }
 
-pub fn connect_session(
+pub fn session_health(
     session: &str,
-    inside_tmux: bool,
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<SessionHealth, TmuxError> {
+    let target = session_target(session);
+    let output = run_tmux(server, runner, &["has-session", "-t", &target], None)?;
+    Ok(health_from_output(&output, |_| true))
+}
+
+pub fn window_health(
+    session: &str,
+    window: &str,
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<SessionHealth, TmuxError> {
+    let target = session_target(session);
+    let output = run_tmux(
+        server,
+        runner,
+        &["list-windows", "-t", &target, "-F", "#{window_name}"],
+        None,
+    )?;
+    Ok(health_from_output(&output, |stdout| {
+        stdout.lines().any(|line| line.trim() == window)
+    }))
+}
+
+fn health_from_output(
+    output: &crate::command_runner::CommandOutput,
+    found: impl Fn(&str) -> bool,
+) -> SessionHealth {
+    if output.status_code == 0 {
+        return if found(&output.stdout) {
+            SessionHealth::Running
+        } else {
+            SessionHealth::Missing
+        };
+    }
+
+    // A missing socket and a stale one left behind by a dead server read differently.
+    if output.stderr.contains("no server running") || output.stderr.contains("error connecting to")
+    {
+        SessionHealth::ServerUnreachable
+    } else {
+        SessionHealth::Missing
+    }
+}
+
+pub fn list_session_names(
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<BTreeSet<String>, TmuxError> {
+    let output = run_tmux(
+        server,
+        runner,
+        &["list-sessions", "-F", "#{session_name}"],
+        None,
+    )?;
+
+    if output.status_code != 0 {
+        return Ok(BTreeSet::new());
+    }
+
+    Ok(output
+        .stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+pub fn list_sessions_with_paths(
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<Vec<SessionInfo>, TmuxError> {
+    let output = run_tmux(
+        server,
+        runner,
+        &["list-sessions", "-F", "#{session_name}\t#{session_path}"],
+        None,
+    )?;
+
+    if output.status_code != 0 {
+        return Ok(Vec::new());
+    }
+
+    Ok(output
+        .stdout
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .filter(|(name, _)| !name.trim().is_empty())
+        .map(|(name, path)| SessionInfo {
+            name: name.trim().to_string(),
+            path: PathBuf::from(path.trim()),
+        })
+        .collect())
+}
+
+pub fn rename_session(
+    session: &str,
+    new_name: &str,
+    server: &TmuxServer,
     runner: &dyn CommandRunner,
 ) -> Result<(), TmuxError> {
-    let args = if inside_tmux {
-        vec!["switch-client", "-t", session]
+    run_tmux_checked(
+        server,
+        runner,
+        &["rename-session", "-t", session, new_name],
+        None,
+    )?;
+    Ok(())
+}
+
+pub fn connect_args(session: &str, inside_tmux: bool) -> [String; 3] {
+    let command = if inside_tmux {
+        "switch-client"
     } else {
-        vec!["attach-session", "-t", session]
+        "attach-session"
     };
+    [
+        command.to_string(),
+        "-t".to_string(),
+        session_target(session),
+    ]
+}
+
+pub fn connect_command(session: &str, inside_tmux: bool, server: &TmuxServer) -> String {
+    let args = connect_args(session, inside_tmux);
+    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+    tmux_command_line(server, &arg_refs)
+}
+
+fn tmux_command_line(server: &TmuxServer, args: &[&str]) -> String {
+    let mut parts = vec!["tmux".to_string()];
+    parts.extend(server_args(server, args).iter().map(|arg| shell_quote(arg)));
+    parts.join(" ")
+}
+
+pub fn shell_quote(value: &str) -> String {
+    let is_plain = !value.is_empty()
+        && value.chars().all(|character| {
+            character.is_ascii_alphanumeric() || matches!(character, '/' | '-' | '_' | '.' | ':')
+        });
+    if is_plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+pub fn connect_session(
+    session: &str,
+    inside_tmux: bool,
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<(), TmuxError> {
+    let args = connect_args(session, inside_tmux);
+    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    run_tmux_interactive(server, runner, &arg_refs)
+}
 
+fn run_tmux_interactive(
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+    args: &[&str],
+) -> Result<(), TmuxError> {
+    let full_args = server_args(server, args);
+    let arg_refs: Vec<&str> = full_args.iter().map(String::as_str).collect();
     let status = runner
-        .run_interactive("tmux", &args, None)
+        .run_interactive("tmux", &arg_refs, None)
         .map_err(|error| TmuxError::Execute(error.to_string()))?;
 
     if status != 0 {
@@ -110,11 +440,504 @@ pub fn connect_session(
     Ok(())
 }
 
-pub fn kill_session(session: &str, runner: &dyn CommandRunner) -> Result<(), TmuxError> {
-    run_tmux_checked(runner, &["kill-session", "-t", session], None)?;
+pub fn kill_session(
+    session: &str,
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<(), TmuxError> {
+    let target = session_target(session);
+    run_tmux_checked(server, runner, &["kill-session", "-t", &target], None)?;
     Ok(())
 }
 
+pub fn create_window(
+    session: &str,
+    name: &str,
+    cwd: &Path,
+    launch: &WindowSpec,
+    environment: &[(String, String)],
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<(), TmuxError> {
+    let cwd_value = cwd.to_str().ok_or(TmuxError::InvalidPath)?;
+    let mut args: Vec<String> = if session_exists(session, server, runner)? {
+        vec![
+            "new-window".to_string(),
+            "-d".to_string(),
+            "-t".to_string(),
+            format!("{session}:"),
+        ]
+    } else {
+        vec![
+            "new-session".to_string(),
+            "-d".to_string(),
+            "-s".to_string(),
+            session.to_string(),
+        ]
+    };
+    args.extend([
+        "-c".to_string(),
+        cwd_value.to_string(),
+        "-n".to_string(),
+        name.to_string(),
+    ]);
+    args.extend(
+        environment
+            .iter()
+            .flat_map(|(key, value)| ["-e".to_string(), format!("{key}={value}")]),
+    );
+    args.extend(build_window_launch(launch)?);
+
+    run_tmux_checked(server, runner, &args, None)
+}
+
+pub fn window_exists(
+    session: &str,
+    window: &str,
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<bool, TmuxError> {
+    let target = session_target(session);
+    let output = run_tmux(
+        server,
+        runner,
+        &["list-windows", "-t", &target, "-F", "#{window_name}"],
+        None,
+    )?;
+    if output.status_code != 0 {
+        return Ok(false);
+    }
+
+    Ok(output.stdout.lines().any(|line| line.trim() == window))
+}
+
+pub fn list_window_labels(
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<BTreeSet<String>, TmuxError> {
+    let output = run_tmux(
+        server,
+        runner,
+        &["list-windows", "-a", "-F", "#{session_name}:#{window_name}"],
+        None,
+    )?;
+
+    if output.status_code != 0 {
+        return Ok(BTreeSet::new());
+    }
+
+    Ok(output
+        .stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+pub fn list_session_status(
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<BTreeMap<String, SessionStatus>, TmuxError> {
+    let output = run_tmux(
+        server,
+        runner,
+        &["list-sessions", "-F", SESSION_STATUS_FORMAT],
+        None,
+    )?;
+
+    if output.status_code != 0 {
+        return Ok(BTreeMap::new());
+    }
+
+    Ok(output
+        .stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let name = fields.next()?.trim();
+            if name.is_empty() {
+                return None;
+            }
+            let status = SessionStatus {
+                windows: fields.next()?.trim().parse().ok()?,
+                attached_clients: fields.next()?.trim().parse().ok()?,
+                activity: fields.next()?.trim().parse().ok()?,
+            };
+            Some((name.to_string(), status))
+        })
+        .collect())
+}
+
+pub fn list_session_activity(
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<BTreeMap<String, u64>, TmuxError> {
+    list_activity(
+        server,
+        runner,
+        &[
+            "list-sessions",
+            "-F",
+            "#{session_name}\t#{session_activity}",
+        ],
+    )
+}
+
+pub fn list_window_activity(
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<BTreeMap<String, u64>, TmuxError> {
+    list_activity(
+        server,
+        runner,
+        &[
+            "list-windows",
+            "-a",
+            "-F",
+            "#{session_name}:#{window_name}\t#{window_activity}",
+        ],
+    )
+}
+
+fn list_activity(
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+    args: &[&str],
+) -> Result<BTreeMap<String, u64>, TmuxError> {
+    let output = run_tmux(server, runner, args, None)?;
+
+    if output.status_code != 0 {
+        return Ok(BTreeMap::new());
+    }
+
+    Ok(output
+        .stdout
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .filter_map(|(label, activity)| {
+            let activity = activity.trim().parse().ok()?;
+            Some((label.trim().to_string(), activity))
+        })
+        .filter(|(label, _)| !label.is_empty())
+        .collect())
+}
+
+pub fn kill_window(
+    session: &str,
+    window: &str,
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<(), TmuxError> {
+    let target = window_target(session, window);
+    run_tmux_checked(server, runner, &["kill-window", "-t", &target], None)
+}
+
+pub fn connect_window_args(session: &str, window: &str, inside_tmux: bool) -> Vec<String> {
+    let mut args = vec![
+        "select-window".to_string(),
+        "-t".to_string(),
+        window_target(session, window),
+        ";".to_string(),
+    ];
+    args.extend(connect_args(session, inside_tmux));
+    args
+}
+
+pub fn connect_window_command(
+    session: &str,
+    window: &str,
+    inside_tmux: bool,
+    server: &TmuxServer,
+) -> String {
+    let args = connect_window_args(session, window, inside_tmux);
+    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+    tmux_command_line(server, &arg_refs)
+}
+
+pub fn connect_window(
+    session: &str,
+    window: &str,
+    inside_tmux: bool,
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<(), TmuxError> {
+    let args = connect_window_args(session, window, inside_tmux);
+    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    run_tmux_interactive(server, runner, &arg_refs)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuItem {
+    pub label: String,
+    pub key: String,
+    pub command: String,
+}
+
+impl MenuItem {
+    pub fn separator() -> Self {
+        Self {
+            label: String::new(),
+            key: String::new(),
+            command: String::new(),
+        }
+    }
+}
+
+pub fn display_menu_args(title: &str, items: &[MenuItem]) -> Vec<String> {
+    let mut args = ["display-menu", "-T", title, "-x", "C", "-y", "C"]
+        .map(str::to_string)
+        .to_vec();
+    for item in items {
+        args.push(item.label.clone());
+        if !item.label.is_empty() {
+            args.push(item.key.clone());
+            args.push(item.command.clone());
+        }
+    }
+    args
+}
+
+pub fn display_menu(
+    title: &str,
+    items: &[MenuItem],
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<(), TmuxError> {
+    run_tmux_checked(server, runner, &display_menu_args(title, items), None)
+}
+
+pub fn command_string(args: &[impl AsRef<str>]) -> String {
+    args.iter()
+        .map(|arg| command_quote(arg.as_ref()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn command_quote(value: &str) -> String {
+    let is_plain = !value.is_empty()
+        && value.chars().all(|character| {
+            character.is_ascii_alphanumeric() || matches!(character, '/' | '-' | '_' | '.' | ':')
+        });
+    if is_plain {
+        value.to_string()
+    } else if !value.contains('\'') {
+        format!("'{value}'")
+    } else {
+        let escaped = value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('$', "\\$");
+        format!("\"{escaped}\"")
+    }
+}
+
+/// Menu titles, labels, and commands go through tmux format expansion, where `##` is a literal `#`.
+pub fn format_escape(value: &str) -> String {
+    value.replace('#', "##")
+}
+
+pub fn current_session(
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<Option<String>, TmuxError> {
+    let output = run_tmux(
+        server,
+        runner,
+        &["display-message", "-p", "#{session_name}"],
+        None,
+    )?;
+    if output.status_code != 0 {
+        return Ok(None);
+    }
+
+    let name = output.stdout.trim();
+    Ok((!name.is_empty()).then(|| name.to_string()))
+}
+
+/// Variables set in the session's environment; ones tmux lists as removed (`-KEY`) are left out.
+pub fn session_environment(
+    session: &str,
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<BTreeMap<String, String>, TmuxError> {
+    let stdout = run_tmux_stdout(server, runner, &["show-environment", "-t", session])?;
+    Ok(stdout
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect())
+}
+
+pub fn list_session_panes(
+    session: &str,
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<Vec<PaneInfo>, TmuxError> {
+    list_panes(
+        server,
+        runner,
+        &["list-panes", "-s", "-t", session, "-F", PANE_FORMAT],
+    )
+}
+
+pub fn list_window_panes(
+    session: &str,
+    window: &str,
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<Vec<PaneInfo>, TmuxError> {
+    let target = window_target(session, window);
+    list_panes(
+        server,
+        runner,
+        &["list-panes", "-t", &target, "-F", PANE_FORMAT],
+    )
+}
+
+fn list_panes(
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+    args: &[&str],
+) -> Result<Vec<PaneInfo>, TmuxError> {
+    let output = run_tmux(server, runner, args, None)?;
+    let output = command_adapter::ensure_success(args, output).map_err(|failure| {
+        TmuxError::CommandFailed {
+            command: failure.command,
+            status: failure.status,
+            stderr: failure.stderr,
+        }
+    })?;
+
+    Ok(output.stdout.lines().filter_map(parse_pane_line).collect())
+}
+
+fn parse_pane_line(line: &str) -> Option<PaneInfo> {
+    let mut fields = line.splitn(5, '\t');
+    let window_index = fields.next()?.trim().parse().ok()?;
+    let window_name = fields.next()?.to_string();
+    let window_layout = fields.next()?.to_string();
+    let command = fields.next()?.to_string();
+    let path = PathBuf::from(fields.next()?);
+    Some(PaneInfo {
+        window_index,
+        window_name,
+        window_layout,
+        command,
+        path,
+    })
+}
+
+pub fn pane_title(pane: &str, runner: &dyn CommandRunner) -> Result<String, TmuxError> {
+    let args = ["display-message", "-p", "-t", pane, "#{pane_title}"];
+    let output = run_client_tmux(runner, &args)?;
+    let output = command_adapter::ensure_success(&args, output).map_err(|failure| {
+        TmuxError::CommandFailed {
+            command: failure.command,
+            status: failure.status,
+            stderr: failure.stderr,
+        }
+    })?;
+
+    Ok(output.stdout.trim_end_matches('\n').to_string())
+}
+
+pub fn set_pane_title(
+    pane: &str,
+    title: &str,
+    runner: &dyn CommandRunner,
+) -> Result<(), TmuxError> {
+    let args = ["select-pane", "-t", pane, "-T", title];
+    let output = run_client_tmux(runner, &args)?;
+    command_adapter::ensure_success(&args, output)
+        .map(|_| ())
+        .map_err(|failure| TmuxError::CommandFailed {
+            command: failure.command,
+            status: failure.status,
+            stderr: failure.stderr,
+        })
+}
+
+const RUN_STATUS_OPTION: &str = "@seshmux-run-status";
+
+pub fn start_run_window(
+    session: &str,
+    name: &str,
+    cwd: &Path,
+    command: &[String],
+    channel: &str,
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<String, TmuxError> {
+    let cwd_value = cwd.to_str().ok_or(TmuxError::InvalidPath)?;
+    // remain-on-exit is set from inside the pane so even an instant exit keeps the window.
+    let script = format!(
+        "tmux set-option -w -t \"$TMUX_PANE\" remain-on-exit on; \"$@\"; status=$?; \
+         tmux set-option -w -t \"$TMUX_PANE\" {RUN_STATUS_OPTION} \"$status\"; \
+         tmux wait-for -S {channel}; exit \"$status\""
+    );
+    let target = format!("{session}:");
+    let mut args = vec![
+        "new-window",
+        "-d",
+        "-P",
+        "-F",
+        "#{window_id}",
+        "-t",
+        &target,
+        "-c",
+        cwd_value,
+        "-n",
+        name,
+        "sh",
+        "-c",
+        &script,
+        "seshmux-run",
+    ];
+    args.extend(command.iter().map(String::as_str));
+
+    let output = run_tmux_stdout(server, runner, &args)?;
+    Ok(output.trim().to_string())
+}
+
+pub fn wait_for_run(
+    channel: &str,
+    window_id: &str,
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+) -> Result<Option<i32>, TmuxError> {
+    run_tmux_checked(server, runner, &["wait-for", channel], None)?;
+    let status = run_tmux_stdout(
+        server,
+        runner,
+        &[
+            "show-options",
+            "-w",
+            "-v",
+            "-t",
+            window_id,
+            RUN_STATUS_OPTION,
+        ],
+    )?;
+    Ok(status.trim().parse().ok())
+}
+
+fn run_tmux_stdout(
+    server: &TmuxServer,
+    runner: &dyn CommandRunner,
+    args: &[&str],
+) -> Result<String, TmuxError> {
+    let output = run_tmux(server, runner, args, None)?;
+    command_adapter::ensure_success(args, output)
+        .map(|output| output.stdout)
+        .map_err(|failure| TmuxError::CommandFailed {
+            command: failure.command,
+            status: failure.status,
+            stderr: failure.stderr,
+        })
+}
+
 fn build_window_launch(window: &WindowSpec) -> Result<Vec<String>, TmuxError> {
     parse_window_launch(window)
         .map(|launch| launch.into_command_parts())
@@ -124,12 +947,13 @@ fn build_window_launch(window: &WindowSpec) -> Result<Vec<String>, TmuxError> {
 }
 
 fn run_tmux_checked(
+    server: &TmuxServer,
     runner: &dyn CommandRunner,
     args: &[impl AsRef<str>],
     cwd: Option<&Path>,
 ) -> Result<(), TmuxError> {
     let arg_refs: Vec<&str> = args.iter().map(|value| value.as_ref()).collect();
-    let output = run_tmux(runner, &arg_refs, cwd)?;
+    let output = run_tmux(server, runner, &arg_refs, cwd)?;
     command_adapter::ensure_success(&arg_refs, output)
         .map(|_| ())
         .map_err(|failure| TmuxError::CommandFailed {
@@ -140,11 +964,30 @@ fn run_tmux_checked(
 }
 
 fn run_tmux(
+    server: &TmuxServer,
     runner: &dyn CommandRunner,
     args: &[&str],
     cwd: Option<&Path>,
 ) -> Result<crate::command_runner::CommandOutput, TmuxError> {
-    command_adapter::run_program(runner, "tmux", args, cwd).map_err(TmuxError::Execute)
+    let full_args = server_args(server, args);
+    let arg_refs: Vec<&str> = full_args.iter().map(String::as_str).collect();
+    command_adapter::run_program_timed(runner, "tmux", &arg_refs, cwd).map_err(tmux_failure)
+}
+
+// For the pane seshmux itself runs in: without -L or -S, tmux talks to the server in `$TMUX`,
+// which need not be the selected one.
+fn run_client_tmux(
+    runner: &dyn CommandRunner,
+    args: &[&str],
+) -> Result<crate::command_runner::CommandOutput, TmuxError> {
+    command_adapter::run_program_timed(runner, "tmux", args, None).map_err(tmux_failure)
+}
+
+fn tmux_failure(failure: RunFailure) -> TmuxError {
+    match failure {
+        RunFailure::TimedOut(timeout) => TmuxError::TimedOut(timeout),
+        RunFailure::Execute(message) => TmuxError::Execute(message),
+    }
 }
 
 #[cfg(test)]
@@ -176,26 +1019,163 @@ mod tests {
         }
     }
 
+    struct PrefixMatchingTmux {
+        sessions: std::cell::RefCell<Vec<String>>,
+    }
+
+    impl PrefixMatchingTmux {
+        fn new(sessions: &[&str]) -> Self {
+            Self {
+                sessions: std::cell::RefCell::new(
+                    sessions.iter().map(|name| (*name).to_string()).collect(),
+                ),
+            }
+        }
+
+        fn find(&self, target: &str) -> Option<usize> {
+            let sessions = self.sessions.borrow();
+            match target.strip_prefix('=') {
+                Some(exact) => sessions.iter().position(|name| name == exact),
+                None => sessions.iter().position(|name| name.starts_with(target)),
+            }
+        }
+    }
+
+    impl CommandRunner for PrefixMatchingTmux {
+        fn run(
+            &self,
+            _program: &str,
+            args: &[&str],
+            _cwd: Option<&Path>,
+        ) -> anyhow::Result<crate::command_runner::CommandOutput> {
+            let target = args
+                .iter()
+                .position(|arg| *arg == "-t")
+                .map(|index| args[index + 1])
+                .unwrap_or_default();
+            let Some(index) = self.find(target) else {
+                return output("", &format!("can't find session: {target}"), 1);
+            };
+            if args[0] == "kill-session" {
+                self.sessions.borrow_mut().remove(index);
+            }
+            output("", "", 0)
+        }
+
+        fn run_interactive(
+            &self,
+            _program: &str,
+            _args: &[&str],
+            _cwd: Option<&Path>,
+        ) -> anyhow::Result<i32> {
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn session_commands_never_fall_back_to_a_sibling_sharing_the_name_prefix() {
+        let tmux = PrefixMatchingTmux::new(&["repo/w10"]);
+
+        assert!(!session_exists("repo/w1", &TmuxServer::Default, &tmux).expect("exists"));
+        assert!(kill_session("repo/w1", &TmuxServer::Default, &tmux).is_err());
+        assert_eq!(*tmux.sessions.borrow(), vec!["repo/w10".to_string()]);
+        assert!(session_exists("repo/w10", &TmuxServer::Default, &tmux).expect("exists"));
+    }
+
+    #[test]
+    fn session_health_misses_a_gone_session_whose_sibling_shares_the_prefix() {
+        let tmux = PrefixMatchingTmux::new(&["repo/w10"]);
+
+        assert_eq!(
+            session_health("repo/w1", &TmuxServer::Default, &tmux).expect("health"),
+            SessionHealth::Missing
+        );
+        assert_eq!(
+            session_health("repo/w10", &TmuxServer::Default, &tmux).expect("health"),
+            SessionHealth::Running
+        );
+    }
+
+    #[test]
+    fn session_health_tells_a_dead_server_from_a_missing_session() {
+        let runner = RecordingRunner::new(
+            vec![
+                output("", "", 0),
+                output("", "can't find session: repo/w1", 1),
+                output("", "no server running on /tmp/tmux-1000/default", 1),
+                output(
+                    "",
+                    "error connecting to /tmp/tmux-1000/default (Connection refused)",
+                    1,
+                ),
+                output("editor\nw2\n", "", 0),
+            ],
+            Vec::new(),
+        );
+
+        assert_eq!(
+            session_health("repo/w1", &TmuxServer::Default, &runner).expect("running"),
+            SessionHealth::Running
+        );
+        assert_eq!(
+            session_health("repo/w1", &TmuxServer::Default, &runner).expect("missing"),
+            SessionHealth::Missing
+        );
+        assert_eq!(
+            session_health("repo/w1", &TmuxServer::Default, &runner).expect("no server"),
+            SessionHealth::ServerUnreachable
+        );
+        assert_eq!(
+            session_health("repo/w1", &TmuxServer::Default, &runner).expect("stale socket"),
+            SessionHealth::ServerUnreachable
+        );
+        assert_eq!(
+            window_health("repo", "w1", &TmuxServer::Default, &runner).expect("window"),
+            SessionHealth::Missing
+        );
+    }
+
     #[test]
     fn session_name_uses_repo_and_worktree_format() {
         assert_eq!(session_name("My Repo", "feature-a"), "my-repo/feature-a");
     }
 
+    #[test]
+    fn worktree_session_names_are_recognized_for_any_repository() {
+        assert!(is_worktree_session_name("other-repo/w1"));
+        assert!(!is_worktree_session_name("scratch"));
+        assert!(!is_worktree_session_name("My Repo/w1"));
+        assert!(!is_worktree_session_name("repo/"));
+        assert!(!is_worktree_session_name("repo/a/b"));
+    }
+
     #[test]
     fn create_session_and_windows_builds_direct_and_shell_commands() {
-        let runner = RecordingRunner::new(vec![output("", "", 0), output("", "", 0)], Vec::new());
+        let runner = RecordingRunner::new(
+            vec![
+                output("", "", 0),
+                output("0\n", "", 0),
+                output("0\n", "", 0),
+                output("", "", 0),
+                output("", "", 0),
+                output("", "", 0),
+            ],
+            Vec::new(),
+        );
         let cwd = PathBuf::from("/tmp/project/worktrees/w1");
 
         create_session_and_windows(
             "project/w1",
             &cwd,
             &[direct_window(), shell_window()],
+            &[],
+            &TmuxServer::Default,
             &runner,
         )
         .expect("create session");
 
         let calls = runner.calls();
-        assert_eq!(calls.len(), 2);
+        assert_eq!(calls.len(), 6);
 
         assert_eq!(calls[0].program, "tmux");
         assert!(calls[0].args.starts_with(&[
@@ -209,21 +1189,130 @@ mod tests {
         assert!(calls[0].args.contains(&".".to_string()));
         assert!(!calls[0].interactive);
 
-        assert!(calls[1].args.starts_with(&[
+        assert_eq!(calls[1].args, vec!["show-options", "-gv", "base-index"]);
+        assert_eq!(
+            calls[2].args,
+            vec!["show-options", "-gwv", "pane-base-index"]
+        );
+        assert!(calls[3].args.starts_with(&[
             "new-window".to_string(),
             "-t".to_string(),
-            "project/w1".to_string()
+            "project/w1:1".to_string()
         ]));
-        assert!(calls[1].args.contains(&"/bin/zsh".to_string()));
-        assert!(calls[1].args.contains(&"-lc".to_string()));
-        assert!(calls[1].args.contains(&"echo ready".to_string()));
-        assert!(!calls[1].interactive);
+        assert!(calls[3].args.contains(&"/bin/zsh".to_string()));
+        assert!(calls[3].args.contains(&"-lc".to_string()));
+        assert!(calls[3].args.contains(&"echo ready".to_string()));
+        assert!(!calls[3].interactive);
+        assert_eq!(calls[4].args, vec!["select-window", "-t", "project/w1:0"]);
+        assert_eq!(calls[5].args, vec!["select-pane", "-t", "project/w1:0.0"]);
+    }
+
+    #[test]
+    fn create_session_and_windows_numbers_windows_from_base_index() {
+        let runner = RecordingRunner::new(
+            vec![
+                output("", "", 0),
+                output("1\n", "", 0),
+                output("1\n", "", 0),
+                output("", "", 0),
+                output("", "", 0),
+                output("", "", 0),
+                output("", "", 0),
+            ],
+            Vec::new(),
+        );
+        let cwd = PathBuf::from("/tmp/project/worktrees/w1");
+
+        create_session_and_windows(
+            "project/w1",
+            &cwd,
+            &[direct_window(), shell_window(), direct_window()],
+            &[],
+            &TmuxServer::Default,
+            &runner,
+        )
+        .expect("create session");
+
+        let targets: Vec<String> = runner
+            .calls()
+            .iter()
+            .skip(3)
+            .map(|call| format!("{} {}", call.args[0], call.args[2]))
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                "new-window project/w1:2",
+                "new-window project/w1:3",
+                "select-window project/w1:1",
+                "select-pane project/w1:1.1",
+            ]
+        );
+    }
+
+    #[test]
+    fn index_base_falls_back_to_zero_when_options_are_unreadable() {
+        let runner = RecordingRunner::new(
+            vec![output("", "no server running", 1), output("x\n", "", 0)],
+            Vec::new(),
+        );
+
+        assert_eq!(
+            index_base(&TmuxServer::Default, &runner),
+            IndexBase::default()
+        );
+    }
+
+    #[test]
+    fn create_session_and_windows_passes_environment_to_every_window() {
+        let runner = RecordingRunner::new(
+            vec![
+                output("", "", 0),
+                output("0\n", "", 0),
+                output("0\n", "", 0),
+                output("", "", 0),
+                output("", "", 0),
+                output("", "", 0),
+                output("", "", 0),
+            ],
+            Vec::new(),
+        );
+        let cwd = PathBuf::from("/tmp/project/worktrees/w1");
+        let environment = vec![("PORT".to_string(), "24001".to_string())];
+
+        create_session_and_windows(
+            "project/w1",
+            &cwd,
+            &[direct_window(), shell_window()],
+            &environment,
+            &TmuxServer::Default,
+            &runner,
+        )
+        .expect("create session");
+
+        let calls = runner.calls();
+        assert!(
+            calls[0]
+                .args
+                .windows(2)
+                .any(|pair| pair == ["-e", "PORT=24001"])
+        );
+        assert_eq!(
+            calls[3].args,
+            vec!["set-environment", "-t", "project/w1", "PORT", "24001"]
+        );
+        assert!(
+            calls[4]
+                .args
+                .windows(2)
+                .any(|pair| pair == ["-e", "PORT=24001"])
+        );
     }
 
     #[test]
     fn connect_session_uses_interactive_runner() {
         let runner = RecordingRunner::new(Vec::new(), vec![Ok(0)]);
-        connect_session("repo/w1", false, &runner).expect("connect");
+        connect_session("repo/w1", false, &TmuxServer::Default, &runner).expect("connect");
 
         let calls = runner.calls();
         assert_eq!(calls.len(), 1);
@@ -233,15 +1322,231 @@ mod tests {
             vec![
                 "attach-session".to_string(),
                 "-t".to_string(),
-                "repo/w1".to_string()
+                "=repo/w1".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn connect_command_matches_connect_args_and_quotes_session() {
+        assert_eq!(
+            connect_command("repo/w1", false, &TmuxServer::Default),
+            "tmux attach-session -t '=repo/w1'"
+        );
+        assert_eq!(
+            connect_command("my repo/it's", true, &TmuxServer::Default),
+            "tmux switch-client -t '=my repo/it'\\''s'"
+        );
+        assert_eq!(
+            connect_command("repo/w1", false, &TmuxServer::Named("work".to_string())),
+            "tmux -L work attach-session -t '=repo/w1'"
+        );
+    }
+
+    #[test]
+    fn create_window_starts_the_repo_session_or_adds_a_background_window() {
+        let runner = RecordingRunner::new(
+            vec![
+                output("", "no server running", 1),
+                output("", "", 0),
+                output("", "", 0),
+                output("", "", 0),
+            ],
+            Vec::new(),
+        );
+        let cwd = PathBuf::from("/tmp/project/worktrees/w1");
+
+        create_window(
+            "project",
+            "w1",
+            &cwd,
+            &direct_window(),
+            &[],
+            &TmuxServer::Default,
+            &runner,
+        )
+        .expect("first");
+        create_window(
+            "project",
+            "w2",
+            &cwd,
+            &direct_window(),
+            &[],
+            &TmuxServer::Default,
+            &runner,
+        )
+        .expect("second");
+
+        let calls = runner.calls();
+        assert_eq!(calls[0].args, vec!["has-session", "-t", "=project"]);
+        assert!(calls[1].args.starts_with(&[
+            "new-session".to_string(),
+            "-d".to_string(),
+            "-s".to_string(),
+            "project".to_string(),
+        ]));
+        assert!(calls[1].args.contains(&"w1".to_string()));
+        assert!(calls[3].args.starts_with(&[
+            "new-window".to_string(),
+            "-d".to_string(),
+            "-t".to_string(),
+            "project:".to_string(),
+        ]));
+        assert!(calls[3].args.contains(&"nvim".to_string()));
+    }
+
+    #[test]
+    fn window_exists_matches_whole_window_names() {
+        let runner = RecordingRunner::new(
+            vec![output("w10\nw2\n", "", 0), output("", "", 1)],
+            Vec::new(),
+        );
+
+        assert!(!window_exists("project", "w1", &TmuxServer::Default, &runner).expect("w1"));
+        assert!(
+            !window_exists("missing", "w1", &TmuxServer::Default, &runner)
+                .expect("missing session")
+        );
+    }
+
+    #[test]
+    fn connect_window_selects_the_window_before_connecting() {
+        assert_eq!(
+            connect_window_command("project", "w1", false, &TmuxServer::Default),
+            "tmux select-window -t 'project:=w1' ';' attach-session -t '=project'"
+        );
+
+        let runner = RecordingRunner::new(Vec::new(), vec![Ok(0)]);
+        connect_window("project", "w1", true, &TmuxServer::Default, &runner).expect("connect");
+        let calls = runner.calls();
+        assert!(calls[0].interactive);
+        assert_eq!(
+            calls[0].args,
+            vec![
+                "select-window",
+                "-t",
+                "project:=w1",
+                ";",
+                "switch-client",
+                "-t",
+                "=project"
+            ]
+        );
+    }
+
+    #[test]
+    fn list_session_names_reads_names_and_treats_missing_server_as_empty() {
+        let runner = RecordingRunner::new(
+            vec![
+                output("repo/w1\nrepo/w2\n", "", 0),
+                output("", "no server running on /tmp/tmux-1000/default", 1),
+            ],
+            Vec::new(),
+        );
+
+        let names = list_session_names(&TmuxServer::Default, &runner).expect("names");
+        assert_eq!(
+            names,
+            BTreeSet::from(["repo/w1".to_string(), "repo/w2".to_string()])
+        );
+
+        let empty = list_session_names(&TmuxServer::Default, &runner).expect("empty");
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn list_session_activity_parses_timestamps_and_skips_bad_lines() {
+        let runner = RecordingRunner::new(
+            vec![output("repo/w1\t1760000000\nrepo/w2\tnot-a-time\n", "", 0)],
+            Vec::new(),
+        );
+
+        let activity = list_session_activity(&TmuxServer::Default, &runner).expect("activity");
+        assert_eq!(
+            activity,
+            BTreeMap::from([("repo/w1".to_string(), 1_760_000_000)])
+        );
+        assert_eq!(
+            runner.calls()[0].args,
+            vec![
+                "list-sessions",
+                "-F",
+                "#{session_name}\t#{session_activity}"
+            ]
+        );
+    }
+
+    #[test]
+    fn list_session_status_reads_windows_clients_and_activity() {
+        let runner = RecordingRunner::new(
+            vec![output(
+                "repo/w1\t3\t1\t1760000000\nrepo/w2\t1\t0\t1760000300\nbroken\t2\n",
+                "",
+                0,
+            )],
+            Vec::new(),
+        );
+
+        let status = list_session_status(&TmuxServer::Default, &runner).expect("status");
+        assert_eq!(
+            status,
+            BTreeMap::from([
+                (
+                    "repo/w1".to_string(),
+                    SessionStatus {
+                        windows: 3,
+                        attached_clients: 1,
+                        activity: 1_760_000_000,
+                    }
+                ),
+                (
+                    "repo/w2".to_string(),
+                    SessionStatus {
+                        windows: 1,
+                        attached_clients: 0,
+                        activity: 1_760_000_300,
+                    }
+                ),
+            ])
+        );
+        assert_eq!(
+            runner.calls()[0].args,
+            vec!["list-sessions", "-F", SESSION_STATUS_FORMAT]
+        );
+    }
+
+    #[test]
+    fn list_sessions_with_paths_splits_name_and_path() {
+        let runner = RecordingRunner::new(
+            vec![output(
+                "repo/w1\t/code/repo/worktrees/w1\nscratch\t/tmp/scratch\n",
+                "",
+                0,
+            )],
+            Vec::new(),
+        );
+
+        let sessions = list_sessions_with_paths(&TmuxServer::Default, &runner).expect("sessions");
+        assert_eq!(
+            sessions,
+            vec![
+                SessionInfo {
+                    name: "repo/w1".to_string(),
+                    path: PathBuf::from("/code/repo/worktrees/w1"),
+                },
+                SessionInfo {
+                    name: "scratch".to_string(),
+                    path: PathBuf::from("/tmp/scratch"),
+                },
             ]
         );
+        assert_eq!(session_name_prefix("My Repo"), "my-repo/");
     }
 
     #[test]
     fn kill_session_invokes_tmux_kill_session() {
         let runner = RecordingRunner::new(vec![output("", "", 0)], Vec::new());
-        kill_session("repo/w1", &runner).expect("kill");
+        kill_session("repo/w1", &TmuxServer::Default, &runner).expect("kill");
 
         let calls = runner.calls();
         assert_eq!(calls.len(), 1);
@@ -250,7 +1555,177 @@ mod tests {
             vec![
                 "kill-session".to_string(),
                 "-t".to_string(),
-                "repo/w1".to_string()
+                "=repo/w1".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn display_menu_passes_separators_without_key_or_command() {
+        let runner = RecordingRunner::new(vec![output("", "", 0)], Vec::new());
+        let items = [
+            MenuItem {
+                label: "w1".to_string(),
+                key: "1".to_string(),
+                command: "switch-client -t repo/w1".to_string(),
+            },
+            MenuItem::separator(),
+            MenuItem {
+                label: "-Nothing to kill".to_string(),
+                key: String::new(),
+                command: String::new(),
+            },
+        ];
+        display_menu("seshmux: repo", &items, &TmuxServer::Default, &runner).expect("menu");
+
+        assert_eq!(
+            runner.calls()[0].args,
+            vec![
+                "display-menu",
+                "-T",
+                "seshmux: repo",
+                "-x",
+                "C",
+                "-y",
+                "C",
+                "w1",
+                "1",
+                "switch-client -t repo/w1",
+                "",
+                "-Nothing to kill",
+                "",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn command_string_quotes_arguments_for_the_tmux_parser() {
+        assert_eq!(
+            command_string(&["kill-window", "-t", "repo:=w1"]),
+            "kill-window -t 'repo:=w1'"
+        );
+        assert_eq!(
+            command_string(&["run-shell", "cd '/tmp/it'\\''s' && echo \"$HOME\""]),
+            r#"run-shell "cd '/tmp/it'\\''s' && echo \"\$HOME\"""#
+        );
+        assert_eq!(format_escape("repo#1"), "repo##1");
+    }
+
+    #[test]
+    fn tmux_server_args_and_clients_follow_the_configured_socket() {
+        assert!(TmuxServer::Default.args().is_empty());
+        assert!(TmuxServer::Default.serves_client("/tmp/tmux-1000/work,12,0"));
+
+        let named = TmuxServer::Named("seshmux".to_string());
+        assert_eq!(named.args(), vec!["-L", "seshmux"]);
+        assert!(named.serves_client("/tmp/tmux-1000/seshmux,12,0"));
+        assert!(!named.serves_client("/tmp/tmux-1000/default,12,0"));
+
+        let path = TmuxServer::Path(PathBuf::from("/run/seshmux.sock"));
+        assert_eq!(path.args(), vec!["-S", "/run/seshmux.sock"]);
+        assert!(path.serves_client("/run/seshmux.sock,40,3"));
+        assert!(!path.serves_client("/tmp/tmux-1000/seshmux.sock,40,3"));
+    }
+
+    #[test]
+    fn window_panes_parse_tab_separated_fields() {
+        let runner = RecordingRunner::new(
+            vec![output(
+                "1\tw1\tb25d,80x24,0,0\tnvim\t/repo/worktrees/w1\n1\tw1\tb25d,80x24,0,0\tzsh\t/repo/my dir\n",
+                "",
+                0,
+            )],
+            Vec::new(),
+        );
+
+        let panes = list_window_panes("repo", "w1", &TmuxServer::Default, &runner).expect("panes");
+        assert_eq!(panes.len(), 2);
+        assert_eq!(panes[0].window_layout, "b25d,80x24,0,0");
+        assert_eq!(panes[0].command, "nvim");
+        assert_eq!(panes[1].path, PathBuf::from("/repo/my dir"));
+        assert_eq!(
+            runner.calls()[0].args[..3],
+            ["list-panes", "-t", "repo:=w1"]
+        );
+    }
+
+    #[test]
+    fn pane_title_round_trips_through_display_and_select_pane() {
+        let runner =
+            RecordingRunner::new(vec![output("zsh\n", "", 0), output("", "", 0)], Vec::new());
+
+        assert_eq!(pane_title("%3", &runner).expect("pane title"), "zsh");
+        set_pane_title("%3", "seshmux: Home", &runner).expect("set pane title");
+
+        let calls = runner.calls();
+        assert_eq!(
+            calls[0].args,
+            vec!["display-message", "-p", "-t", "%3", "#{pane_title}"]
+        );
+        assert_eq!(
+            calls[1].args,
+            vec!["select-pane", "-t", "%3", "-T", "seshmux: Home"]
+        );
+    }
+
+    #[test]
+    fn run_window_passes_the_command_through_and_reads_back_its_status() {
+        let runner = RecordingRunner::new(
+            vec![
+                output("@7\n", "", 0),
+                output("", "", 0),
+                output("101\n", "", 0),
+            ],
+            Vec::new(),
+        );
+        let command = vec!["cargo".to_string(), "test".to_string(), "a b".to_string()];
+
+        let window = start_run_window(
+            "repo/w1",
+            "run-cargo",
+            Path::new("/tmp/w1"),
+            &command,
+            "seshmux-run-1",
+            &TmuxServer::Default,
+            &runner,
+        )
+        .expect("start");
+        assert_eq!(window, "@7");
+        assert_eq!(
+            wait_for_run("seshmux-run-1", &window, &TmuxServer::Default, &runner).expect("wait"),
+            Some(101)
+        );
+
+        let calls = runner.calls();
+        assert_eq!(
+            calls[0].args[..11],
+            [
+                "new-window",
+                "-d",
+                "-P",
+                "-F",
+                "#{window_id}",
+                "-t",
+                "repo/w1:",
+                "-c",
+                "/tmp/w1",
+                "-n",
+                "run-cargo"
+            ]
+        );
+        assert!(calls[0].args[13].contains("wait-for -S seshmux-run-1"));
+        assert_eq!(calls[0].args[14..], ["seshmux-run", "cargo", "test", "a b"]);
+        assert_eq!(calls[1].args, vec!["wait-for", "seshmux-run-1"]);
+        assert_eq!(
+            calls[2].args,
+            vec![
+                "show-options",
+                "-w",
+                "-v",
+                "-t",
+                "@7",
+                "@seshmux-run-status"
             ]
         );
     }
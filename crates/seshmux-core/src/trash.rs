@@ -0,0 +1,136 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::registry::RegistryEntry;
+
+pub const TRASH_DIR_NAME: &str = ".seshmux-trash";
+const MANIFEST_FILE_NAME: &str = "trash.toml";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrashedWorktree {
+    pub trashed_at: String,
+    pub trash_path: String,
+    #[serde(default)]
+    pub delete_branch: bool,
+    pub entry: RegistryEntry,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TrashFile {
+    #[serde(default, rename = "worktree")]
+    worktrees: Vec<TrashedWorktree>,
+}
+
+#[derive(Debug, Error)]
+pub enum TrashError {
+    #[error("failed to read trash manifest at {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to parse trash manifest at {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+    #[error("failed to write trash manifest at {path}: {source}")]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to serialize trash manifest: {0}")]
+    Serialize(toml::ser::Error),
+}
+
+pub fn trash_dir(worktrees_dir: &Path) -> PathBuf {
+    worktrees_dir.join(TRASH_DIR_NAME)
+}
+
+pub fn manifest_path(worktrees_dir: &Path) -> PathBuf {
+    trash_dir(worktrees_dir).join(MANIFEST_FILE_NAME)
+}
+
+/// The unix time keeps a worktree trashed twice under the same name from colliding.
+pub fn trash_path(worktrees_dir: &Path, name: &str, trashed_unix: u64) -> PathBuf {
+    trash_dir(worktrees_dir).join(format!("{name}-{trashed_unix}"))
+}
+
+pub fn load(worktrees_dir: &Path) -> Result<Vec<TrashedWorktree>, TrashError> {
+    let path = manifest_path(worktrees_dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(&path).map_err(|source| TrashError::Read {
+        path: path.clone(),
+        source,
+    })?;
+    let file: TrashFile =
+        toml::from_str(&contents).map_err(|source| TrashError::Parse { path, source })?;
+    Ok(file.worktrees)
+}
+
+pub fn save(worktrees_dir: &Path, worktrees: &[TrashedWorktree]) -> Result<(), TrashError> {
+    let path = manifest_path(worktrees_dir);
+    let parent = path.parent().expect("trash manifest path has parent");
+    fs::create_dir_all(parent).map_err(|source| TrashError::Write {
+        path: parent.to_path_buf(),
+        source,
+    })?;
+
+    let contents = toml::to_string_pretty(&TrashFile {
+        worktrees: worktrees.to_vec(),
+    })
+    .map_err(TrashError::Serialize)?;
+    fs::write(&path, contents).map_err(|source| TrashError::Write { path, source })
+}
+
+pub fn is_expired(trashed_unix: Option<u64>, now: u64, retention_days: u64) -> bool {
+    trashed_unix.is_some_and(|trashed| now.saturating_sub(trashed) >= retention_days * 86_400)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::registry::RegistryEntry;
+
+    use super::{TrashedWorktree, is_expired, load, manifest_path, save, trash_path};
+
+    #[test]
+    fn manifest_round_trips_and_a_missing_one_is_empty() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        assert!(load(temp.path()).expect("empty").is_empty());
+
+        let trashed = TrashedWorktree {
+            trashed_at: "2026-03-01T09:00:00Z".to_string(),
+            trash_path: trash_path(temp.path(), "w1", 1_772_355_600)
+                .to_string_lossy()
+                .to_string(),
+            delete_branch: true,
+            entry: RegistryEntry {
+                name: "w1".to_string(),
+                path: temp.path().join("w1").to_string_lossy().to_string(),
+                created_at: "2026-02-25T10:00:00Z".to_string(),
+                branch: Some("feature/w1".to_string()),
+                ..RegistryEntry::default()
+            },
+        };
+        save(temp.path(), std::slice::from_ref(&trashed)).expect("save");
+
+        assert!(manifest_path(temp.path()).is_file());
+        assert_eq!(load(temp.path()).expect("load"), vec![trashed]);
+    }
+
+    #[test]
+    fn entries_expire_once_the_retention_has_passed() {
+        let day = 86_400;
+        assert!(!is_expired(Some(10 * day), 16 * day, 7));
+        assert!(is_expired(Some(10 * day), 17 * day, 7));
+        assert!(!is_expired(None, 17 * day, 7));
+    }
+}
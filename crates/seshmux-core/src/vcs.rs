@@ -0,0 +1,447 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::command_adapter;
+use crate::command_runner::CommandRunner;
+use crate::git::{self, GitError, HeadState};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum VcsPreference {
+    #[default]
+    Auto,
+    Git,
+    Jj,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VcsKind {
+    Git,
+    Jujutsu,
+}
+
+impl VcsKind {
+    pub fn command(self) -> &'static str {
+        match self {
+            VcsKind::Git => "git",
+            VcsKind::Jujutsu => "jj",
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum VcsError {
+    #[error(transparent)]
+    Git(#[from] GitError),
+    #[error("failed to execute jj command: {0}")]
+    Execute(String),
+    #[error("jj command failed: jj {command} (exit {status}) {stderr}")]
+    CommandFailed {
+        command: String,
+        status: i32,
+        stderr: String,
+    },
+    #[error("failed to parse jj output: {0}")]
+    Parse(String),
+    #[error(
+        "jj repository {} has no colocated git repository; seshmux still runs git for branches, updates, and review, so run `jj git init --colocate` there or set vcs = \"git\"",
+        path.display()
+    )]
+    NotColocated { path: PathBuf },
+    #[error(
+        "workspace {} contains files jj does not track ({}), use --force to delete it",
+        path.display(),
+        files.join(", ")
+    )]
+    UntrackedContent { path: PathBuf, files: Vec<String> },
+    #[error("failed to remove workspace directory {}: {error}", path.display())]
+    RemoveDir {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+pub trait Vcs {
+    fn kind(&self) -> VcsKind;
+
+    fn repo_root(&self, cwd: &Path) -> Result<PathBuf, VcsError>;
+
+    fn head_state(&self, repo_root: &Path) -> Result<Option<HeadState>, VcsError>;
+
+    fn create_worktree(
+        &self,
+        repo_root: &Path,
+        branch: &str,
+        target_path: &Path,
+        start_point: &str,
+    ) -> Result<(), VcsError>;
+
+    fn remove_worktree(
+        &self,
+        repo_root: &Path,
+        target_path: &Path,
+        force: bool,
+    ) -> Result<(), VcsError>;
+}
+
+pub fn detect(cwd: &Path, preference: VcsPreference) -> VcsKind {
+    match preference {
+        VcsPreference::Git => VcsKind::Git,
+        VcsPreference::Jj => VcsKind::Jujutsu,
+        VcsPreference::Auto => {
+            if cwd.ancestors().any(|dir| dir.join(".jj").is_dir()) {
+                VcsKind::Jujutsu
+            } else {
+                VcsKind::Git
+            }
+        }
+    }
+}
+
+pub fn for_kind<'r>(kind: VcsKind, runner: &'r dyn CommandRunner) -> Box<dyn Vcs + 'r> {
+    match kind {
+        VcsKind::Git => Box::new(Git { runner }),
+        VcsKind::Jujutsu => Box::new(Jujutsu { runner }),
+    }
+}
+
+pub struct Git<'r> {
+    runner: &'r dyn CommandRunner,
+}
+
+impl Vcs for Git<'_> {
+    fn kind(&self) -> VcsKind {
+        VcsKind::Git
+    }
+
+    fn repo_root(&self, cwd: &Path) -> Result<PathBuf, VcsError> {
+        Ok(git::repo_root(cwd, self.runner)?)
+    }
+
+    fn head_state(&self, repo_root: &Path) -> Result<Option<HeadState>, VcsError> {
+        Ok(Some(git::head_state(repo_root, self.runner)?))
+    }
+
+    fn create_worktree(
+        &self,
+        repo_root: &Path,
+        branch: &str,
+        target_path: &Path,
+        start_point: &str,
+    ) -> Result<(), VcsError> {
+        Ok(git::create_worktree(
+            repo_root,
+            branch,
+            target_path,
+            start_point,
+            self.runner,
+        )?)
+    }
+
+    fn remove_worktree(
+        &self,
+        repo_root: &Path,
+        target_path: &Path,
+        force: bool,
+    ) -> Result<(), VcsError> {
+        if force {
+            git::force_remove_worktree(repo_root, target_path, self.runner)?;
+        } else {
+            git::remove_worktree(repo_root, target_path, self.runner)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Jujutsu<'r> {
+    runner: &'r dyn CommandRunner,
+}
+
+impl Jujutsu<'_> {
+    fn run_checked(&self, args: &[&str], cwd: &Path) -> Result<String, VcsError> {
+        let output = command_adapter::run_program(self.runner, "jj", args, Some(cwd))
+            .map_err(VcsError::Execute)?;
+        command_adapter::ensure_success(args, output)
+            .map(|output| output.stdout)
+            .map_err(|failure| VcsError::CommandFailed {
+                command: failure.command,
+                status: failure.status,
+                stderr: failure.stderr,
+            })
+    }
+}
+
+impl Vcs for Jujutsu<'_> {
+    fn kind(&self) -> VcsKind {
+        VcsKind::Jujutsu
+    }
+
+    fn repo_root(&self, cwd: &Path) -> Result<PathBuf, VcsError> {
+        let stdout = self.run_checked(&["root"], cwd)?;
+        let workspace_root = PathBuf::from(stdout.trim());
+        if workspace_root.as_os_str().is_empty() {
+            return Err(VcsError::Parse(
+                "jj root returned an empty path".to_string(),
+            ));
+        }
+        let repo_root = main_workspace_root(&workspace_root);
+        if !repo_root.join(".git").exists() {
+            return Err(VcsError::NotColocated { path: repo_root });
+        }
+        Ok(repo_root)
+    }
+
+    fn head_state(&self, _repo_root: &Path) -> Result<Option<HeadState>, VcsError> {
+        Ok(None)
+    }
+
+    fn create_worktree(
+        &self,
+        repo_root: &Path,
+        branch: &str,
+        target_path: &Path,
+        start_point: &str,
+    ) -> Result<(), VcsError> {
+        let target = target_path
+            .to_str()
+            .ok_or_else(|| VcsError::Parse("workspace path is not valid UTF-8".to_string()))?;
+        let name = workspace_name(target_path)?;
+
+        let mut args = vec!["workspace", "add", "--name", name];
+        // Without `-r` the workspace starts on the same parents as the current one, which is what
+        // a git worktree from HEAD gets.
+        if start_point != "HEAD" {
+            args.extend(["-r", start_point]);
+        }
+        args.push(target);
+        self.run_checked(&args, repo_root)?;
+        self.run_checked(&["bookmark", "create", branch, "-r", "@"], target_path)?;
+        Ok(())
+    }
+
+    /// Edits are only kept once a jj command snapshots them, so one runs inside the workspace
+    /// first. Ignored files and files too large to snapshot stay behind on disk, and without
+    /// `force` they block the removal like untracked files do for git.
+    fn remove_worktree(
+        &self,
+        repo_root: &Path,
+        target_path: &Path,
+        force: bool,
+    ) -> Result<(), VcsError> {
+        let name = workspace_name(target_path)?;
+        if target_path.exists() {
+            self.run_checked(&["status"], target_path)?;
+            if !force {
+                let tracked = self.run_checked(&["file", "list"], target_path)?;
+                let tracked = tracked.lines().map(PathBuf::from).collect::<BTreeSet<_>>();
+                let untracked = untracked_files(target_path, &tracked).map_err(|error| {
+                    VcsError::RemoveDir {
+                        path: target_path.to_path_buf(),
+                        error,
+                    }
+                })?;
+                if !untracked.is_empty() {
+                    return Err(VcsError::UntrackedContent {
+                        path: target_path.to_path_buf(),
+                        files: untracked,
+                    });
+                }
+            }
+        }
+        self.run_checked(&["workspace", "forget", name], repo_root)?;
+        if target_path.exists() {
+            std::fs::remove_dir_all(target_path).map_err(|error| VcsError::RemoveDir {
+                path: target_path.to_path_buf(),
+                error,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+fn untracked_files(root: &Path, tracked: &BTreeSet<PathBuf>) -> std::io::Result<Vec<String>> {
+    let mut untracked = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        for entry in std::fs::read_dir(root.join(&relative))? {
+            let entry = entry?;
+            let path = relative.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                if path != Path::new(".jj") && path != Path::new(".git") {
+                    pending.push(path);
+                }
+            } else if !tracked.contains(&path) {
+                untracked.push(path.display().to_string());
+            }
+        }
+    }
+    untracked.sort();
+    Ok(untracked)
+}
+
+fn workspace_name(target_path: &Path) -> Result<&str, VcsError> {
+    target_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| {
+            VcsError::Parse(format!(
+                "cannot name a workspace after {}",
+                target_path.display()
+            ))
+        })
+}
+
+// A secondary workspace's `.jj/repo` is a file pointing at the main workspace's `.jj/repo`
+// directory, relative to the secondary `.jj`.
+fn main_workspace_root(workspace_root: &Path) -> PathBuf {
+    let marker = workspace_root.join(".jj").join("repo");
+    if !marker.is_file() {
+        return workspace_root.to_path_buf();
+    }
+    let Ok(pointer) = std::fs::read_to_string(&marker) else {
+        return workspace_root.to_path_buf();
+    };
+    let repo_dir = workspace_root.join(".jj").join(pointer.trim());
+    let repo_dir = std::fs::canonicalize(&repo_dir).unwrap_or(repo_dir);
+    repo_dir
+        .parent()
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| workspace_root.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use crate::test_support::{RecordingRunner, output};
+
+    use super::{VcsError, VcsKind, VcsPreference, detect, for_kind, main_workspace_root};
+
+    #[test]
+    fn auto_detects_jj_from_a_parent_directory_and_config_can_force_either() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let nested = temp.path().join("repo/src/deep");
+        fs::create_dir_all(&nested).expect("nested");
+        assert_eq!(detect(&nested, VcsPreference::Auto), VcsKind::Git);
+
+        fs::create_dir_all(temp.path().join("repo/.jj")).expect("jj dir");
+        assert_eq!(detect(&nested, VcsPreference::Auto), VcsKind::Jujutsu);
+        assert_eq!(detect(&nested, VcsPreference::Git), VcsKind::Git);
+        assert_eq!(detect(temp.path(), VcsPreference::Jj), VcsKind::Jujutsu);
+    }
+
+    #[test]
+    fn secondary_workspaces_resolve_to_the_main_workspace() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let main = temp.path().join("repo");
+        fs::create_dir_all(main.join(".jj/repo")).expect("main repo");
+        let secondary = main.join("worktrees/w1");
+        fs::create_dir_all(secondary.join(".jj")).expect("secondary");
+        fs::write(secondary.join(".jj/repo"), "../../../.jj/repo").expect("pointer");
+
+        let main = fs::canonicalize(&main).expect("canonical main");
+        assert_eq!(main_workspace_root(&secondary), main);
+        assert_eq!(main_workspace_root(&main), main);
+    }
+
+    #[test]
+    fn jj_repo_root_requires_a_colocated_git_repository() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo = temp.path().join("repo");
+        fs::create_dir_all(repo.join(".jj/repo")).expect("jj repo");
+        let root = format!("{}\n", repo.display());
+
+        let runner = RecordingRunner::from_outputs(vec![output(&root, "", 0)]);
+        let error = for_kind(VcsKind::Jujutsu, &runner)
+            .repo_root(&repo)
+            .expect_err("plain jj repo");
+        assert!(matches!(error, VcsError::NotColocated { ref path } if path == &repo));
+
+        fs::create_dir_all(repo.join(".git")).expect("git dir");
+        let runner = RecordingRunner::from_outputs(vec![output(&root, "", 0)]);
+        assert_eq!(
+            for_kind(VcsKind::Jujutsu, &runner)
+                .repo_root(&repo)
+                .expect("colocated"),
+            repo
+        );
+    }
+
+    #[test]
+    fn jj_workspaces_are_added_with_a_bookmark_and_forgotten_on_remove() {
+        let runner = RecordingRunner::from_outputs(vec![
+            output("", "", 0),
+            output("", "", 0),
+            output("", "", 0),
+        ]);
+        let vcs = for_kind(VcsKind::Jujutsu, &runner);
+        let target = Path::new("/nonexistent/repo/worktrees/w1");
+
+        vcs.create_worktree(Path::new("/repo"), "feature/w1", target, "main")
+            .expect("create");
+        vcs.remove_worktree(Path::new("/repo"), target, true)
+            .expect("remove");
+
+        let calls = runner.calls();
+        assert!(calls.iter().all(|call| call.program == "jj"));
+        assert_eq!(
+            calls[0].args,
+            vec![
+                "workspace",
+                "add",
+                "--name",
+                "w1",
+                "-r",
+                "main",
+                "/nonexistent/repo/worktrees/w1"
+            ]
+        );
+        assert_eq!(
+            calls[1].args,
+            vec!["bookmark", "create", "feature/w1", "-r", "@"]
+        );
+        assert_eq!(calls[2].args, vec!["workspace", "forget", "w1"]);
+    }
+
+    #[test]
+    fn jj_remove_snapshots_first_and_refuses_ignored_files_without_force() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let target = temp.path().join("worktrees/w1");
+        fs::create_dir_all(target.join(".jj")).expect("jj dir");
+        fs::create_dir_all(target.join("src")).expect("src");
+        fs::write(target.join("src/main.rs"), "fn main() {}").expect("tracked");
+        fs::write(target.join(".env"), "SECRET=1").expect("ignored");
+
+        let runner =
+            RecordingRunner::from_outputs(vec![output("", "", 0), output("src/main.rs\n", "", 0)]);
+        let vcs = for_kind(VcsKind::Jujutsu, &runner);
+        let error = vcs
+            .remove_worktree(temp.path(), &target, false)
+            .expect_err("ignored file should block removal");
+        match error {
+            VcsError::UntrackedContent { files, .. } => assert_eq!(files, vec![".env"]),
+            other => panic!("expected untracked content, got {other:?}"),
+        }
+        assert!(target.join(".env").exists());
+
+        let calls = runner.calls();
+        assert_eq!(calls[0].args, vec!["status"]);
+        assert_eq!(calls[0].cwd.as_deref(), Some(target.as_path()));
+        assert_eq!(calls[1].args, vec!["file", "list"]);
+        assert_eq!(calls.len(), 2);
+
+        let runner = RecordingRunner::from_outputs(vec![output("", "", 0), output("", "", 0)]);
+        let vcs = for_kind(VcsKind::Jujutsu, &runner);
+        vcs.remove_worktree(temp.path(), &target, true)
+            .expect("forced remove");
+        assert!(!target.exists());
+        let calls = runner.calls();
+        assert_eq!(calls[0].args, vec!["status"]);
+        assert_eq!(calls[1].args, vec!["workspace", "forget", "w1"]);
+    }
+}
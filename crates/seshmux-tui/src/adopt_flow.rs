@@ -0,0 +1,421 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{List, ListItem, ListState, Paragraph};
+use seshmux_app::{AdoptCandidate, AdoptRequest, AdoptResult, AdoptScan, App};
+
+use crate::UiExit;
+use crate::keymap;
+use crate::theme::{self, Accent, Theme};
+use crate::ui::help::{HelpProvider, RESULT_KEYS, ScreenHelp};
+use crate::ui::modal::{render_error_modal, render_success_modal};
+use crate::ui::text::{
+    compact_hint, focus_line, key_hint_height, key_hint_paragraph, result_footer,
+};
+
+pub(crate) trait AdoptFlowOps {
+    fn scan_orphans(&self, cwd: &Path) -> Result<AdoptScan>;
+    fn adopt_worktrees(&self, request: AdoptRequest) -> Result<AdoptResult>;
+}
+
+impl<'a> AdoptFlowOps for App<'a> {
+    fn scan_orphans(&self, cwd: &Path) -> Result<AdoptScan> {
+        Ok(self.adopt_scan(cwd)?)
+    }
+
+    fn adopt_worktrees(&self, request: AdoptRequest) -> Result<AdoptResult> {
+        Ok(self.adopt(request)?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Select,
+    Success,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlowSignal {
+    Continue,
+    Exit(UiExit),
+}
+
+#[derive(Debug)]
+struct AdoptFlow {
+    cwd: PathBuf,
+    step: Step,
+    candidates: Vec<AdoptCandidate>,
+    checked: Vec<bool>,
+    selected: usize,
+    success_message: Option<String>,
+    error_message: Option<String>,
+}
+
+pub(crate) struct AdoptScreen {
+    flow: AdoptFlow,
+}
+
+impl AdoptScreen {
+    pub(crate) fn new(app: &App<'_>, cwd: &Path) -> Result<Self> {
+        Ok(Self {
+            flow: AdoptFlow::new(app, cwd)?,
+        })
+    }
+
+    pub(crate) fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        self.flow.render(frame, theme);
+    }
+
+    pub(crate) fn on_key(&mut self, key: KeyEvent, app: &App<'_>) -> Result<Option<UiExit>> {
+        match self.flow.on_key(key, app)? {
+            FlowSignal::Continue => Ok(None),
+            FlowSignal::Exit(exit) => Ok(Some(exit)),
+        }
+    }
+}
+
+impl HelpProvider for AdoptScreen {
+    fn help(&self) -> ScreenHelp {
+        match self.flow.step {
+            Step::Select => ScreenHelp::new(
+                "Adopt worktrees",
+                "Git worktrees of this repository that seshmux does not track yet. Adopting registers them so every screen lists them.",
+            )
+            .keys(&[
+                ("Up/Down or j/k", "move"),
+                ("Space", "check or uncheck the worktree"),
+                ("a", "check or uncheck all"),
+                ("r", "scan again"),
+                ("Enter", "adopt the checked worktrees"),
+                ("Esc", "back"),
+            ]),
+            Step::Success => {
+                ScreenHelp::new("Adopted", "The worktrees are registered.").keys(RESULT_KEYS)
+            }
+            Step::Error => ScreenHelp::new("Adopt failed", "Nothing was adopted.")
+                .keys(&[("Enter/Esc", "back to the worktrees")]),
+        }
+    }
+
+    fn typing(&self) -> bool {
+        false
+    }
+}
+
+impl AdoptFlow {
+    fn new(ops: &dyn AdoptFlowOps, cwd: &Path) -> Result<Self> {
+        let mut flow = Self {
+            cwd: cwd.to_path_buf(),
+            step: Step::Select,
+            candidates: Vec::new(),
+            checked: Vec::new(),
+            selected: 0,
+            success_message: None,
+            error_message: None,
+        };
+        flow.rescan(ops)?;
+        Ok(flow)
+    }
+
+    fn rescan(&mut self, ops: &dyn AdoptFlowOps) -> Result<()> {
+        let scan = ops.scan_orphans(&self.cwd)?;
+        self.checked = vec![false; scan.candidates.len()];
+        self.candidates = scan.candidates;
+        self.selected = 0;
+        Ok(())
+    }
+
+    fn on_key(&mut self, key: KeyEvent, ops: &dyn AdoptFlowOps) -> Result<FlowSignal> {
+        match self.step {
+            Step::Select => self.on_key_select(key, ops),
+            Step::Success => Ok(self.on_key_success(key)),
+            Step::Error => {
+                if keymap::is_back(key) || keymap::is_confirm(key) {
+                    self.error_message = None;
+                    self.step = Step::Select;
+                }
+                Ok(FlowSignal::Continue)
+            }
+        }
+    }
+
+    fn on_key_select(&mut self, key: KeyEvent, ops: &dyn AdoptFlowOps) -> Result<FlowSignal> {
+        if keymap::is_back(key) {
+            return Ok(FlowSignal::Exit(UiExit::BackAtRoot));
+        }
+
+        if keymap::is_up(key) {
+            self.selected = self.selected.saturating_sub(1);
+        } else if keymap::is_down(key) {
+            if self.selected + 1 < self.candidates.len() {
+                self.selected += 1;
+            }
+        } else if keymap::is_toggle(key) {
+            if let Some(checked) = self.checked.get_mut(self.selected) {
+                *checked = !*checked;
+            }
+        } else if key.code == KeyCode::Char('a') {
+            let all_checked = self.checked.iter().all(|checked| *checked);
+            self.checked
+                .iter_mut()
+                .for_each(|checked| *checked = !all_checked);
+        } else if key.code == KeyCode::Char('r') {
+            self.rescan(ops)?;
+        } else if keymap::is_confirm(key) {
+            self.adopt(ops)?;
+        }
+
+        Ok(FlowSignal::Continue)
+    }
+
+    fn adopt(&mut self, ops: &dyn AdoptFlowOps) -> Result<()> {
+        let mut worktree_names: Vec<String> = self
+            .candidates
+            .iter()
+            .zip(&self.checked)
+            .filter(|(_, checked)| **checked)
+            .map(|(candidate, _)| candidate.worktree_name.clone())
+            .collect();
+        if worktree_names.is_empty() {
+            let Some(candidate) = self.candidates.get(self.selected) else {
+                return Ok(());
+            };
+            worktree_names.push(candidate.worktree_name.clone());
+        }
+
+        match ops.adopt_worktrees(AdoptRequest {
+            cwd: self.cwd.clone(),
+            worktree_names,
+        }) {
+            Ok(result) => {
+                self.success_message = Some(success_message_for(&result));
+                self.step = Step::Success;
+                self.rescan(ops)?;
+            }
+            Err(error) => {
+                self.error_message = Some(format!("{error:#}"));
+                self.step = Step::Error;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn on_key_success(&mut self, key: KeyEvent) -> FlowSignal {
+        if keymap::is_quit(key) {
+            return FlowSignal::Exit(UiExit::Completed);
+        }
+
+        if keymap::is_back(key) || keymap::is_confirm(key) {
+            return FlowSignal::Exit(UiExit::BackAtRoot);
+        }
+
+        FlowSignal::Continue
+    }
+
+    fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        self.render_select(frame, theme);
+        match self.step {
+            Step::Select => {}
+            Step::Success => {
+                let message = self.success_message.as_deref().unwrap_or("Adopted");
+                render_success_modal(
+                    frame,
+                    theme,
+                    message,
+                    70,
+                    40,
+                    result_footer(frame.area().width),
+                );
+            }
+            Step::Error => {
+                let message = self.error_message.as_deref().unwrap_or("Adopt failed");
+                render_error_modal(frame, theme, message, 80, 40, "Enter/Esc: back");
+            }
+        }
+    }
+
+    fn render_select(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        let area = frame.area();
+        let key_text = compact_hint(
+            area.width,
+            "Space: toggle    a: toggle all    Enter: adopt    Up/Down or j/k: move    r: rescan    Esc: back",
+            "Space: toggle    a: all    Enter: adopt    j/k: move    r: rescan    Esc: back",
+            "Space toggle | a all | Enter adopt | Esc back",
+        );
+        let footer_height = key_hint_height(area.width, key_text);
+        let [body, footer] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(6), Constraint::Length(footer_height)])
+            .areas(area);
+
+        let block = theme::chrome(focus_line(theme, "Orphan tmux sessions"));
+        if self.candidates.is_empty() {
+            let empty = Paragraph::new(
+                "No orphan tmux sessions found. Sessions named <repo>/<name>, or running in an unregistered git worktree, show up here.",
+            )
+            .block(block);
+            frame.render_widget(empty, body);
+        } else {
+            let items: Vec<ListItem<'_>> = self
+                .candidates
+                .iter()
+                .zip(&self.checked)
+                .map(|(candidate, checked)| candidate_item(theme, candidate, *checked))
+                .collect();
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(theme.table_highlight(Accent::Manage));
+            let mut state = ListState::default();
+            state.select(Some(self.selected));
+            frame.render_stateful_widget(list, body, &mut state);
+        }
+
+        let keys = key_hint_paragraph(key_text).block(theme::key_block());
+        frame.render_widget(keys, footer);
+    }
+}
+
+fn candidate_item(theme: &Theme, candidate: &AdoptCandidate, checked: bool) -> ListItem<'static> {
+    let marker = if checked { "[x] " } else { "[ ] " };
+    let session = match &candidate.rename_to {
+        Some(new_name) => format!("{} -> {new_name}", candidate.session_name),
+        None => candidate.session_name.clone(),
+    };
+
+    ListItem::new(Line::from(vec![
+        Span::raw(marker),
+        Span::raw(candidate.worktree_name.clone()),
+        Span::styled(
+            format!("  {session}  {}", candidate.worktree_path.display()),
+            theme.secondary_text(),
+        ),
+    ]))
+}
+
+fn success_message_for(result: &AdoptResult) -> String {
+    result
+        .adopted
+        .iter()
+        .map(|candidate| {
+            format!(
+                "Adopted worktree: {} ({})",
+                candidate.worktree_name,
+                candidate.worktree_path.display()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::path::{Path, PathBuf};
+
+    use anyhow::Result;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use seshmux_app::{AdoptCandidate, AdoptRequest, AdoptResult, AdoptScan};
+
+    use super::{AdoptFlow, AdoptFlowOps, FlowSignal, Step};
+    use crate::theme::Theme;
+
+    struct FakeOps {
+        candidates: Vec<AdoptCandidate>,
+        adopt_calls: RefCell<Vec<AdoptRequest>>,
+    }
+
+    impl FakeOps {
+        fn new(names: &[&str]) -> Self {
+            Self {
+                candidates: names
+                    .iter()
+                    .map(|name| AdoptCandidate {
+                        worktree_name: name.to_string(),
+                        worktree_path: PathBuf::from(format!("/tmp/repo/worktrees/{name}")),
+                        session_name: format!("repo/{name}"),
+                        rename_to: None,
+                    })
+                    .collect(),
+                adopt_calls: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl AdoptFlowOps for FakeOps {
+        fn scan_orphans(&self, _cwd: &Path) -> Result<AdoptScan> {
+            Ok(AdoptScan {
+                repo_root: PathBuf::from("/tmp/repo"),
+                candidates: self.candidates.clone(),
+            })
+        }
+
+        fn adopt_worktrees(&self, request: AdoptRequest) -> Result<AdoptResult> {
+            self.adopt_calls.borrow_mut().push(request.clone());
+            Ok(AdoptResult {
+                repo_root: PathBuf::from("/tmp/repo"),
+                adopted: self
+                    .candidates
+                    .iter()
+                    .filter(|candidate| request.worktree_names.contains(&candidate.worktree_name))
+                    .cloned()
+                    .collect(),
+            })
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn render_output(flow: &AdoptFlow, width: u16, height: u16) -> String {
+        let theme = &Theme::default();
+        let backend = TestBackend::new(width, height);
+        let mut terminal = Terminal::new(backend).expect("terminal");
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render adopt flow");
+        format!("{}", terminal.backend())
+    }
+
+    #[test]
+    fn enter_adopts_checked_rows_or_the_highlighted_one() {
+        let ops = FakeOps::new(&["w1", "w2", "w3"]);
+        let mut flow = AdoptFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+
+        flow.on_key(key(KeyCode::Char(' ')), &ops).expect("toggle");
+        flow.on_key(key(KeyCode::Char('j')), &ops).expect("down");
+        flow.on_key(key(KeyCode::Char('j')), &ops).expect("down");
+        flow.on_key(key(KeyCode::Char(' ')), &ops).expect("toggle");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("adopt");
+        assert_eq!(flow.step, Step::Success);
+
+        let mut single = AdoptFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+        single.on_key(key(KeyCode::Char('j')), &ops).expect("down");
+        single.on_key(key(KeyCode::Enter), &ops).expect("adopt");
+
+        let calls = ops.adopt_calls.borrow();
+        assert_eq!(calls[0].worktree_names, vec!["w1", "w3"]);
+        assert_eq!(calls[1].worktree_names, vec!["w2"]);
+    }
+
+    #[test]
+    fn esc_exits_and_empty_scan_explains_what_qualifies() {
+        let ops = FakeOps::new(&[]);
+        let mut flow = AdoptFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+
+        let output = render_output(&flow, 140, 16);
+        assert!(output.contains("No orphan tmux sessions found."));
+
+        flow.on_key(key(KeyCode::Enter), &ops).expect("enter");
+        assert!(ops.adopt_calls.borrow().is_empty());
+        let signal = flow.on_key(key(KeyCode::Esc), &ops).expect("esc");
+        assert_eq!(signal, FlowSignal::Exit(super::UiExit::BackAtRoot));
+    }
+}
@@ -1,36 +1,68 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-use anyhow::Result;
-use crossterm::event::KeyEvent;
+use anyhow::{Result, bail};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::style::Color;
 use ratatui::text::{Line, Text};
-use seshmux_app::{App, AttachError, AttachRequest, AttachResult, ListResult};
+use seshmux_app::{
+    App, AttachError, AttachRequest, AttachResult, ErrorKind, ForeignWorktree, KillSessionResult,
+    ListResult, WorktreeOrder, WorktreeRow, WorktreeSortKey, classify_error, find_cause,
+};
+use seshmux_core::tmux::SessionStatus;
 
 use crate::UiExit;
 use crate::keymap;
-use crate::theme;
+use crate::theme::{Accent, Theme};
 use crate::ui::binary_choice::{BinaryChoice, BinaryChoiceEvent};
-use crate::ui::modal::{ModalSpec, render_error_modal, render_modal};
+use crate::ui::error_actions::{ErrorActions, ErrorActionsEvent, render_error_with_actions};
+use crate::ui::help::{CHOICE_KEYS, ERROR_KEYS, HelpProvider, RESULT_KEYS, ScreenHelp};
+use crate::ui::modal::{ModalSpec, render_modal};
 use crate::ui::select_step::{SelectSignal, SelectStepState};
 use crate::ui::text::{
-    compact_hint, focus_line, highlighted_label_value_line, key_hint_height, key_hint_paragraph,
-    label_value_line, result_footer, yes_no,
+    compact_hint, copy_result_footer, focus_line, format_age, highlighted_label_value_line,
+    key_hint_height, label_value_line, yes_no,
 };
-use crate::ui::worktree_table::{TableColumn, WorktreeTableRender};
+use crate::ui::worktree_table::{WorktreeTableRender, configured_columns};
 
 pub(crate) trait AttachFlowOps {
     fn list_worktrees(&self, cwd: &Path) -> Result<ListResult>;
+    fn refresh_sessions(&self) -> Result<BTreeSet<String>>;
+    fn session_status(&self) -> Result<BTreeMap<String, SessionStatus>>;
     fn attach_worktree(&self, request: AttachRequest) -> Result<AttachResult>;
+    fn kill_session(
+        &self,
+        cwd: &Path,
+        worktree_name: &str,
+        allow_foreign: bool,
+    ) -> Result<KillSessionResult>;
 }
 
 impl<'a> AttachFlowOps for App<'a> {
     fn list_worktrees(&self, cwd: &Path) -> Result<ListResult> {
-        self.list(cwd)
+        Ok(self.list(cwd)?)
+    }
+
+    fn refresh_sessions(&self) -> Result<BTreeSet<String>> {
+        Ok(App::refresh_sessions(self)?)
+    }
+
+    fn session_status(&self) -> Result<BTreeMap<String, SessionStatus>> {
+        Ok(App::session_status(self)?)
     }
 
     fn attach_worktree(&self, request: AttachRequest) -> Result<AttachResult> {
-        self.attach(request)
+        Ok(self.attach(request)?)
+    }
+
+    fn kill_session(
+        &self,
+        cwd: &Path,
+        worktree_name: &str,
+        allow_foreign: bool,
+    ) -> Result<KillSessionResult> {
+        Ok(self.kill_worktree_session(cwd, worktree_name, allow_foreign)?)
     }
 }
 
@@ -38,6 +70,7 @@ impl<'a> AttachFlowOps for App<'a> {
 enum Step {
     SelectWorktree,
     MissingSessionPrompt,
+    KillConfirm,
     Success,
     Error,
 }
@@ -53,10 +86,18 @@ struct AttachFlow {
     cwd: PathBuf,
     step: Step,
     select: SelectStepState,
+    session_status: BTreeMap<String, SessionStatus>,
     missing_choice: BinaryChoice,
     pending_worktree_name: Option<String>,
+    kill_choice: BinaryChoice,
+    pending_kill: Option<(String, String)>,
+    kill_owner: Option<String>,
+    session_gone: Option<String>,
     success_message: Option<String>,
+    attach_command: Option<String>,
+    pending_copy: Option<String>,
     error_message: Option<String>,
+    error_actions: ErrorActions<AttachRetry>,
 }
 
 pub(crate) struct AttachScreen {
@@ -65,13 +106,49 @@ pub(crate) struct AttachScreen {
 
 impl AttachScreen {
     pub(crate) fn new(app: &App<'_>, cwd: &Path) -> Result<Self> {
-        Ok(Self {
-            flow: AttachFlow::new(app, cwd)?,
-        })
+        let mut flow = AttachFlow::new(app, cwd)?;
+        if let Some(columns) = configured_columns(app) {
+            flow.select.set_columns(&columns);
+        }
+        Ok(Self { flow })
+    }
+
+    pub(crate) fn attach_to(app: &App<'_>, cwd: &Path, worktree_name: &str) -> Result<Self> {
+        let mut screen = Self::new(app, cwd)?;
+        screen.flow.attach_named(worktree_name, app)?;
+        Ok(screen)
+    }
+
+    pub(crate) fn refresh_sessions(&mut self, app: &App<'_>) -> Result<()> {
+        self.flow.refresh_sessions(app)
+    }
+
+    pub(crate) fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        self.flow.render(frame, theme);
     }
 
-    pub(crate) fn render(&self, frame: &mut ratatui::Frame<'_>) {
-        self.flow.render(frame);
+    pub(crate) fn selected_worktree(&self) -> Option<&str> {
+        self.flow.select.selected_row().map(|row| row.name.as_str())
+    }
+
+    pub(crate) fn take_pending_copy(&mut self) -> Option<String> {
+        self.flow
+            .pending_copy
+            .take()
+            .or_else(|| self.flow.select.take_pending_copy())
+    }
+
+    pub(crate) fn on_paste(&mut self, text: &str) {
+        if self.flow.step == Step::SelectWorktree {
+            self.flow.select.on_paste(text);
+        }
+    }
+
+    pub(crate) fn on_mouse(&mut self, mouse: MouseEvent) -> Option<KeyEvent> {
+        if self.flow.step != Step::SelectWorktree {
+            return None;
+        }
+        self.flow.select.on_mouse(mouse)
     }
 
     pub(crate) fn on_key(&mut self, key: KeyEvent, app: &App<'_>) -> Result<Option<UiExit>> {
@@ -82,66 +159,206 @@ impl AttachScreen {
     }
 }
 
+impl HelpProvider for AttachScreen {
+    fn help(&self) -> ScreenHelp {
+        match self.flow.step {
+            Step::SelectWorktree => ScreenHelp::new(
+                "Attach",
+                "Pick a worktree to attach to its tmux session. Recently used sessions come first.",
+            )
+            .keys(&self.flow.select.help_keys(
+                "attach to its session",
+                &[
+                    ("a", "sort A-Z or by recent use"),
+                    ("K", "kill the selected session, keeping the worktree"),
+                ],
+            )),
+            Step::MissingSessionPrompt => ScreenHelp::new(
+                "Create the session?",
+                "The worktree has no tmux session. Yes creates one from the configured windows and attaches.",
+            )
+            .keys(CHOICE_KEYS),
+            Step::KillConfirm => ScreenHelp::new(
+                "Kill the session?",
+                "Yes kills the worktree's tmux session. The worktree and its files are kept, so attaching later starts a fresh session.",
+            )
+            .keys(CHOICE_KEYS),
+            Step::Success => ScreenHelp::new("Attached", "The tmux session is ready.")
+                .keys(&[("y", "copy the attach command")])
+                .keys(RESULT_KEYS),
+            Step::Error => ScreenHelp::new(
+                "Attach failed",
+                "The session could not be attached. When it is gone, it can be recreated from the configured windows.",
+            )
+            .keys(ERROR_KEYS),
+        }
+    }
+
+    fn typing(&self) -> bool {
+        self.flow.step == Step::SelectWorktree
+            && self.flow.select.filter_focused()
+            && !self.flow.select.details_open()
+    }
+}
+
 impl AttachFlow {
     fn new(ops: &dyn AttachFlowOps, cwd: &Path) -> Result<Self> {
         let result = ops.list_worktrees(cwd)?;
+        let order = WorktreeOrder {
+            key: WorktreeSortKey::Recent,
+            ..WorktreeOrder::default()
+        };
+        let mut rows = result.rows;
+        order.sort(&mut rows);
+        let mut select = SelectStepState::new(rows);
+        select.set_order(order);
         Ok(Self {
             cwd: cwd.to_path_buf(),
             step: Step::SelectWorktree,
-            select: SelectStepState::new(result.rows),
+            select,
+            session_status: ops.session_status().unwrap_or_default(),
             missing_choice: BinaryChoice::new(true),
             pending_worktree_name: None,
+            kill_choice: BinaryChoice::new(false),
+            pending_kill: None,
+            kill_owner: None,
+            session_gone: None,
             success_message: None,
+            attach_command: None,
+            pending_copy: None,
             error_message: None,
+            error_actions: ErrorActions::none(),
         })
     }
 
+    fn refresh_sessions(&mut self, ops: &dyn AttachFlowOps) -> Result<()> {
+        let running = ops.refresh_sessions()?;
+        self.select.update_session_status(&running);
+        self.session_status = ops.session_status().unwrap_or_default();
+        Ok(())
+    }
+
     fn on_key(&mut self, key: KeyEvent, ops: &dyn AttachFlowOps) -> Result<FlowSignal> {
         match self.step {
             Step::SelectWorktree => self.on_key_select(key, ops),
             Step::MissingSessionPrompt => self.on_key_missing_prompt(key, ops),
+            Step::KillConfirm => self.on_key_kill_confirm(key, ops),
             Step::Success => Ok(self.on_key_success(key)),
-            Step::Error => Ok(self.on_key_error(key)),
+            Step::Error => Ok(self.on_key_error(key, ops)),
         }
     }
 
     fn on_key_select(&mut self, key: KeyEvent, ops: &dyn AttachFlowOps) -> Result<FlowSignal> {
+        if key.code == KeyCode::Char('a')
+            && !self.select.filter_focused()
+            && !self.select.details_open()
+        {
+            self.toggle_alphabetical();
+            return Ok(FlowSignal::Continue);
+        }
+
+        if key.code == KeyCode::Char('K')
+            && !self.select.filter_focused()
+            && !self.select.details_open()
+        {
+            if let Some(row) = self.select.selected_row()
+                && row.session_running
+            {
+                self.pending_kill = Some((row.name.clone(), row.session_name.clone()));
+                self.kill_owner = None;
+                self.kill_choice = BinaryChoice::new(false);
+                self.step = Step::KillConfirm;
+            }
+            return Ok(FlowSignal::Continue);
+        }
+
         match self.select.on_key(key) {
             SelectSignal::Back => return Ok(FlowSignal::Exit(UiExit::BackAtRoot)),
             SelectSignal::Continue => return Ok(FlowSignal::Continue),
             SelectSignal::Confirm => {}
         }
 
+        self.attach_selected(ops);
+        Ok(FlowSignal::Continue)
+    }
+
+    fn attach_selected(&mut self, ops: &dyn AttachFlowOps) {
         let Some(row) = self.select.selected_row().cloned() else {
-            return Ok(FlowSignal::Continue);
+            return;
         };
 
-        match ops.attach_worktree(AttachRequest {
-            cwd: self.cwd.clone(),
-            worktree_name: row.name,
-            create_if_missing: false,
-        }) {
+        let mut request = AttachRequest::new(self.cwd.clone(), row.name);
+        request.expect_running = row.session_running;
+        request.connect = true;
+        match ops.attach_worktree(request) {
             Ok(result) => {
                 self.success_message = Some(success_message_for(&result));
+                self.attach_command = Some(result.connect_command);
                 self.error_message = None;
                 self.step = Step::Success;
             }
             Err(error) => {
-                if let Some(AttachError::MissingSession { worktree_name, .. }) =
-                    error.downcast_ref::<AttachError>()
-                {
-                    self.pending_worktree_name = Some(worktree_name.clone());
+                let (worktree_name, session_gone) = match find_cause::<AttachError>(&error) {
+                    Some(AttachError::MissingSession { worktree_name, .. }) => {
+                        (Some(worktree_name.clone()), None)
+                    }
+                    Some(gone @ AttachError::SessionGone { worktree_name, .. }) => {
+                        (Some(worktree_name.clone()), Some(gone.to_string()))
+                    }
+                    _ => (None, None),
+                };
+                if let Some(worktree_name) = worktree_name {
+                    self.pending_worktree_name = Some(worktree_name);
+                    self.session_gone = session_gone;
                     self.missing_choice = BinaryChoice::new(true);
                     self.step = Step::MissingSessionPrompt;
                 } else {
-                    self.error_message = Some(format!("{error:#}"));
-                    self.success_message = None;
-                    self.step = Step::Error;
+                    self.show_error(&error);
                 }
             }
         }
+    }
 
-        Ok(FlowSignal::Continue)
+    fn attach_named(&mut self, worktree_name: &str, ops: &dyn AttachFlowOps) -> Result<()> {
+        if !self.select.select_name(worktree_name) {
+            bail!("no worktree named '{worktree_name}'");
+        }
+        self.attach_selected(ops);
+        Ok(())
+    }
+
+    fn show_error(&mut self, error: &anyhow::Error) {
+        self.error_message = Some(format!("{error:#}"));
+        self.error_actions = attach_retry_actions(error);
+        self.success_message = None;
+        self.step = Step::Error;
+    }
+
+    fn recreate_and_attach(&mut self, retry: AttachRetry, ops: &dyn AttachFlowOps) {
+        let mut request = AttachRequest::new(self.cwd.clone(), retry.worktree_name);
+        request.create_if_missing = true;
+        request.connect = true;
+        request.allow_foreign = retry.allow_foreign;
+        match ops.attach_worktree(request) {
+            Ok(result) => {
+                self.success_message = Some(success_message_for(&result));
+                self.attach_command = Some(result.connect_command);
+                self.error_message = None;
+                self.step = Step::Success;
+            }
+            Err(error) => self.show_error(&error),
+        }
+    }
+
+    fn toggle_alphabetical(&mut self) {
+        let mut order = self.select.order();
+        order.key = if order.key == WorktreeSortKey::Name {
+            WorktreeSortKey::Recent
+        } else {
+            WorktreeSortKey::Name
+        };
+        order.reverse = false;
+        self.select.set_order(order);
     }
 
     fn on_key_missing_prompt(
@@ -167,28 +384,55 @@ impl AttachFlow {
                     return Ok(FlowSignal::Continue);
                 };
 
-                match ops.attach_worktree(AttachRequest {
-                    cwd: self.cwd.clone(),
-                    worktree_name,
-                    create_if_missing: true,
-                }) {
-                    Ok(result) => {
-                        self.success_message = Some(success_message_for(&result));
-                        self.error_message = None;
-                        self.step = Step::Success;
-                    }
-                    Err(error) => {
-                        self.error_message = Some(format!("{error:#}"));
-                        self.success_message = None;
-                        self.step = Step::Error;
-                    }
-                }
-
+                self.recreate_and_attach(
+                    AttachRetry {
+                        worktree_name,
+                        allow_foreign: false,
+                    },
+                    ops,
+                );
                 Ok(FlowSignal::Continue)
             }
         }
     }
 
+    fn on_key_kill_confirm(
+        &mut self,
+        key: KeyEvent,
+        ops: &dyn AttachFlowOps,
+    ) -> Result<FlowSignal> {
+        match self.kill_choice.on_key(key) {
+            BinaryChoiceEvent::Continue => {}
+            BinaryChoiceEvent::Back | BinaryChoiceEvent::ConfirmNo => {
+                self.pending_kill = None;
+                self.kill_owner = None;
+                self.step = Step::SelectWorktree;
+            }
+            BinaryChoiceEvent::ConfirmYes => {
+                let Some((worktree_name, session_name)) = self.pending_kill.take() else {
+                    self.step = Step::SelectWorktree;
+                    return Ok(FlowSignal::Continue);
+                };
+                let allow_foreign = self.kill_owner.take().is_some();
+                match ops
+                    .kill_session(&self.cwd, &worktree_name, allow_foreign)
+                    .and_then(|_| self.refresh_sessions(ops))
+                {
+                    Ok(()) => self.step = Step::SelectWorktree,
+                    Err(error) => match find_cause::<ForeignWorktree>(&error) {
+                        Some(foreign) if !allow_foreign => {
+                            self.kill_owner = Some(foreign.owner.clone());
+                            self.pending_kill = Some((worktree_name, session_name));
+                            self.kill_choice = BinaryChoice::new(false);
+                        }
+                        _ => self.show_error(&error),
+                    },
+                }
+            }
+        }
+        Ok(FlowSignal::Continue)
+    }
+
     fn on_key_success(&mut self, key: KeyEvent) -> FlowSignal {
         if keymap::is_quit(key) {
             return FlowSignal::Exit(UiExit::Completed);
@@ -198,29 +442,39 @@ impl AttachFlow {
             return FlowSignal::Exit(UiExit::BackAtRoot);
         }
 
+        if key.code == KeyCode::Char('y') {
+            self.pending_copy = self.attach_command.clone();
+        }
+
         FlowSignal::Continue
     }
 
-    fn on_key_error(&mut self, key: KeyEvent) -> FlowSignal {
-        if keymap::is_back(key) || keymap::is_confirm(key) {
-            self.select.set_filter_focused(false);
-            self.step = Step::SelectWorktree;
-            FlowSignal::Continue
-        } else {
-            FlowSignal::Continue
+    fn on_key_error(&mut self, key: KeyEvent, ops: &dyn AttachFlowOps) -> FlowSignal {
+        match self.error_actions.on_key(key) {
+            ErrorActionsEvent::Continue => {}
+            ErrorActionsEvent::Back => {
+                self.select.set_filter_focused(false);
+                self.step = Step::SelectWorktree;
+            }
+            ErrorActionsEvent::Choose(retry) => self.recreate_and_attach(retry, ops),
         }
+        FlowSignal::Continue
     }
 
-    fn render(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         match self.step {
-            Step::SelectWorktree => self.render_select(frame),
-            Step::MissingSessionPrompt => self.render_missing_prompt(frame),
-            Step::Success => self.render_success(frame),
-            Step::Error => self.render_error(frame),
+            Step::SelectWorktree => self.render_select(frame, theme),
+            Step::MissingSessionPrompt => self.render_missing_prompt(frame, theme),
+            Step::KillConfirm => {
+                self.render_select(frame, theme);
+                self.render_kill_confirm(frame, theme);
+            }
+            Step::Success => self.render_success(frame, theme),
+            Step::Error => self.render_error(frame, theme),
         }
     }
 
-    fn render_select(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_select(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let area = frame.area();
         let key_text = if self.select.filter_focused() {
             compact_hint(
@@ -232,17 +486,18 @@ impl AttachFlow {
         } else {
             compact_hint(
                 area.width,
-                "/: filter focus    Enter: attach    Up/Down or j/k: move    Esc: back",
-                "/: filter    Enter: attach    j/k: move    Esc: back",
+                "/: filter focus    Enter: attach    Up/Down or j/k: move    a: A-Z/recent    K: kill session    e: details    y: copy path    Esc: back",
+                "/: filter    Enter: attach    j/k: move    a: A-Z/recent    K: kill    e: details    y: copy    Esc: back",
                 "/ filter | Enter attach | j/k move | Esc back",
             )
         };
         let footer_height = key_hint_height(area.width, key_text);
-        let [filter_area, table_area, footer] = Layout::default()
+        let [filter_area, table_area, status_area, footer] = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3),
                 Constraint::Min(8),
+                Constraint::Length(1),
                 Constraint::Length(footer_height),
             ])
             .areas(area);
@@ -251,29 +506,10 @@ impl AttachFlow {
         self.select.render_filter(
             frame,
             filter_area,
-            focus_line("Filter"),
+            focus_line(theme, "Filter"),
             Line::from("Filter (/ to focus)"),
         );
 
-        let columns = [
-            TableColumn {
-                title: "Name",
-                width: Constraint::Length(24),
-            },
-            TableColumn {
-                title: "Created",
-                width: Constraint::Length(28),
-            },
-            TableColumn {
-                title: "Branch",
-                width: Constraint::Length(20),
-            },
-            TableColumn {
-                title: "Session",
-                width: Constraint::Length(12),
-            },
-        ];
-
         self.select.render_table(
             frame,
             table_area,
@@ -281,47 +517,51 @@ impl AttachFlow {
                 title: if filter_focused {
                     Line::from("Choose worktree to attach (/ to focus)")
                 } else {
-                    focus_line("Choose worktree to attach")
+                    focus_line(theme, "Choose worktree to attach")
                 },
                 empty_message: "No matching worktrees.",
-                columns: &columns,
-                header_style: theme::table_header(Color::Yellow),
-                highlight_style: theme::table_highlight(Color::Yellow),
-            },
-            |row| {
-                let state = if row.session_running {
-                    "running"
-                } else {
-                    "missing"
-                };
-                vec![
-                    row.name.clone(),
-                    row.created_at.clone(),
-                    row.branch.clone(),
-                    state.to_string(),
-                ]
+                header_style: theme.table_header(Accent::Attach),
+                highlight_style: theme.table_highlight(Accent::Attach),
             },
         );
 
-        let keys = key_hint_paragraph(key_text).block(theme::key_block());
-        frame.render_widget(keys, footer);
+        if let Some(row) = self.select.selected_row() {
+            let now = seshmux_core::time::unix_seconds(SystemTime::now());
+            frame.render_widget(
+                session_status_line(theme, row, &self.session_status, now),
+                status_area,
+            );
+        }
+
+        self.select.render_keys(frame, footer, key_text);
+        self.select.render_details(frame, theme);
     }
 
-    fn render_missing_prompt(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_missing_prompt(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let worktree = self
             .pending_worktree_name
             .as_deref()
             .unwrap_or("UNCONFIRMED");
-        let text = Text::from(vec![
-            label_value_line("Worktree", worktree),
-            highlighted_label_value_line("Current Selection", self.missing_choice.selected_label()),
-        ]);
+        let mut lines = vec![label_value_line(theme, "Worktree", worktree)];
+        if let Some(reason) = &self.session_gone {
+            lines.push(Line::from(reason.clone()));
+        }
+        lines.push(highlighted_label_value_line(
+            theme,
+            "Current Selection",
+            self.missing_choice.selected_label(),
+        ));
         render_modal(
             frame,
+            theme,
             ModalSpec {
-                title: "No tmux session was found. Create one now?",
-                title_style: Some(theme::focus_prompt()),
-                body: text,
+                title: if self.session_gone.is_some() {
+                    "Session no longer running. Recreate it?"
+                } else {
+                    "No tmux session was found. Create one now?"
+                },
+                title_style: Some(theme.focus_prompt()),
+                body: Text::from(lines),
                 key_hint: Some("Space: toggle    Enter: continue    Esc: back"),
                 width_pct: 70,
                 height_pct: 40,
@@ -329,7 +569,45 @@ impl AttachFlow {
         );
     }
 
-    fn render_success(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_kill_confirm(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        let (worktree, session) = self
+            .pending_kill
+            .as_ref()
+            .map_or(("UNCONFIRMED", "UNCONFIRMED"), |(worktree, session)| {
+                (worktree.as_str(), session.as_str())
+            });
+        let mut lines = vec![
+            label_value_line(theme, "Worktree", worktree),
+            label_value_line(theme, "tmux session", session),
+            Line::from("The worktree is kept."),
+        ];
+        if let Some(owner) = &self.kill_owner {
+            lines.push(label_value_line(theme, "Created by", owner));
+        }
+        lines.push(highlighted_label_value_line(
+            theme,
+            "Current Selection",
+            self.kill_choice.selected_label(),
+        ));
+        render_modal(
+            frame,
+            theme,
+            ModalSpec {
+                title: if self.kill_owner.is_some() {
+                    "Someone else created this worktree. Kill its session anyway?"
+                } else {
+                    "Kill this tmux session?"
+                },
+                title_style: Some(theme.focus_prompt()),
+                body: Text::from(lines),
+                key_hint: Some("Space: toggle    Enter: confirm    Esc: cancel"),
+                width_pct: 70,
+                height_pct: 40,
+            },
+        );
+    }
+
+    fn render_success(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let summary = self
             .success_message
             .clone()
@@ -340,20 +618,82 @@ impl AttachFlow {
             .collect();
         render_modal(
             frame,
+            theme,
             ModalSpec {
                 title: "Success",
-                title_style: Some(theme::success_prompt()),
+                title_style: Some(theme.success_prompt()),
                 body: Text::from(lines),
-                key_hint: Some(result_footer(frame.area().width)),
+                key_hint: Some(copy_result_footer(frame.area().width)),
                 width_pct: 70,
                 height_pct: 40,
             },
         );
     }
 
-    fn render_error(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_error(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let text = self.error_message.as_deref().unwrap_or("Attach failed");
-        render_error_modal(frame, text, 80, 40, "Enter/Esc: back");
+        render_error_with_actions(frame, theme, text, &self.error_actions, "back", 80, 40);
+    }
+}
+
+fn session_status_line(
+    theme: &Theme,
+    row: &WorktreeRow,
+    status: &BTreeMap<String, SessionStatus>,
+    now: u64,
+) -> Line<'static> {
+    let session = row
+        .session_name
+        .split_once(':')
+        .map_or(row.session_name.as_str(), |(session, _)| session);
+    let summary = match status.get(session) {
+        Some(status) if row.session_running => {
+            let windows = match status.windows {
+                1 => "1 window".to_string(),
+                count => format!("{count} windows"),
+            };
+            let clients = match status.attached_clients {
+                0 => "no clients attached".to_string(),
+                1 => "1 client attached".to_string(),
+                count => format!("{count} clients attached"),
+            };
+            let age = format_age(now.saturating_sub(status.activity));
+            let activity = if age == "just now" {
+                "active just now".to_string()
+            } else {
+                format!("last active {age} ago")
+            };
+            format!("{}, {windows}, {clients}, {activity}", row.session_name)
+        }
+        _ if row.session_running => format!("{}, running", row.session_name),
+        _ => format!("{}, not running", row.session_name),
+    };
+    label_value_line(theme, "tmux session", summary)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AttachRetry {
+    pub(crate) worktree_name: String,
+    pub(crate) allow_foreign: bool,
+}
+
+pub(crate) fn attach_retry_actions(error: &anyhow::Error) -> ErrorActions<AttachRetry> {
+    match classify_error(error) {
+        ErrorKind::MissingSession { worktree_name } => ErrorActions::new(vec![(
+            "Recreate session and attach".to_string(),
+            AttachRetry {
+                worktree_name,
+                allow_foreign: false,
+            },
+        )]),
+        ErrorKind::ForeignWorktree { name, owner } => ErrorActions::new(vec![(
+            format!("Attach anyway (created by {owner})"),
+            AttachRetry {
+                worktree_name: name,
+                allow_foreign: true,
+            },
+        )]),
+        _ => ErrorActions::none(),
     }
 }
 
@@ -368,48 +708,84 @@ fn success_message_for(result: &AttachResult) -> String {
 
 #[cfg(test)]
 mod tests {
-    use std::cell::RefCell;
-    use std::path::{Path, PathBuf};
+    use std::cell::{Cell, RefCell};
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::path::Path;
+    use std::time::SystemTime;
 
     use anyhow::Result;
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
-    use seshmux_app::{AttachError, AttachRequest, AttachResult, ListResult, WorktreeRow};
+    use seshmux_app::{
+        AttachError, AttachRequest, AttachResult, ForeignWorktree, KillSessionResult, ListResult,
+        WorktreeHead, WorktreeRow,
+    };
+    use seshmux_core::tmux::SessionStatus;
 
     use super::{AttachFlow, AttachFlowOps, FlowSignal, Step};
+    use crate::theme::Theme;
 
     struct FakeOps {
         rows: Vec<WorktreeRow>,
+        session_status: BTreeMap<String, SessionStatus>,
         attach_calls: RefCell<Vec<AttachRequest>>,
+        kill_calls: RefCell<Vec<String>>,
+        dies_while_connecting: Cell<usize>,
+        foreign_owner: Option<String>,
     }
 
     impl FakeOps {
         fn new() -> Self {
             Self {
-                rows: vec![WorktreeRow {
-                    name: "w1".to_string(),
-                    path: PathBuf::from("/tmp/repo/worktrees/w1"),
-                    created_at: "2026-02-25T10:00:00Z".to_string(),
-                    branch: "w1".to_string(),
-                    session_name: "repo/w1".to_string(),
-                    session_running: false,
-                }],
+                rows: vec![WorktreeRow::new(
+                    "w1",
+                    "/tmp/repo/worktrees/w1",
+                    "2026-02-25T10:00:00Z",
+                    WorktreeHead::Branch("w1".to_string()),
+                    "repo/w1",
+                )],
+                session_status: BTreeMap::new(),
                 attach_calls: RefCell::new(Vec::new()),
+                kill_calls: RefCell::new(Vec::new()),
+                dies_while_connecting: Cell::new(0),
+                foreign_owner: None,
             }
         }
     }
 
     impl AttachFlowOps for FakeOps {
+        fn refresh_sessions(&self) -> Result<BTreeSet<String>> {
+            Ok(BTreeSet::new())
+        }
+
+        fn session_status(&self) -> Result<BTreeMap<String, SessionStatus>> {
+            Ok(self.session_status.clone())
+        }
+
         fn list_worktrees(&self, _cwd: &Path) -> Result<ListResult> {
-            Ok(ListResult {
-                repo_root: PathBuf::from("/tmp/repo"),
-                rows: self.rows.clone(),
-            })
+            Ok(ListResult::new("/tmp/repo", self.rows.clone()))
         }
 
         fn attach_worktree(&self, request: AttachRequest) -> Result<AttachResult> {
             self.attach_calls.borrow_mut().push(request.clone());
+            if let Some(owner) = &self.foreign_owner
+                && !request.allow_foreign
+            {
+                return Err(ForeignWorktree {
+                    name: request.worktree_name,
+                    owner: owner.clone(),
+                }
+                .into());
+            }
+            if !request.create_if_missing && request.expect_running {
+                return Err(AttachError::SessionGone {
+                    worktree_name: request.worktree_name,
+                    session_name: "repo/w1".to_string(),
+                    server_reachable: true,
+                }
+                .into());
+            }
             if !request.create_if_missing {
                 return Err(AttachError::MissingSession {
                     worktree_name: request.worktree_name,
@@ -417,12 +793,46 @@ mod tests {
                 }
                 .into());
             }
+            if self.dies_while_connecting.get() > 0 {
+                self.dies_while_connecting
+                    .set(self.dies_while_connecting.get() - 1);
+                return Err(AttachError::SessionGone {
+                    worktree_name: request.worktree_name,
+                    session_name: "repo/w1".to_string(),
+                    server_reachable: true,
+                }
+                .into());
+            }
 
-            Ok(AttachResult {
-                worktree_name: "w1".to_string(),
-                worktree_path: PathBuf::from("/tmp/repo/worktrees/w1"),
-                session_name: "repo/w1".to_string(),
-                created_session: true,
+            Ok({
+                let mut result = AttachResult::new("w1", "/tmp/repo/worktrees/w1", "repo/w1");
+                result.created_session = true;
+                result.connect_command = "tmux attach-session -t repo/w1".to_string();
+                result.attach_status = Some(0);
+                result
+            })
+        }
+
+        fn kill_session(
+            &self,
+            _cwd: &Path,
+            worktree_name: &str,
+            allow_foreign: bool,
+        ) -> Result<KillSessionResult> {
+            if let Some(owner) = &self.foreign_owner
+                && !allow_foreign
+            {
+                return Err(ForeignWorktree {
+                    name: worktree_name.to_string(),
+                    owner: owner.clone(),
+                }
+                .into());
+            }
+            self.kill_calls.borrow_mut().push(worktree_name.to_string());
+            Ok(KillSessionResult {
+                worktree_name: worktree_name.to_string(),
+                session_name: format!("repo/{worktree_name}"),
+                killed: true,
             })
         }
     }
@@ -432,10 +842,11 @@ mod tests {
     }
 
     fn render_output(flow: &AttachFlow, width: u16, height: u16) -> String {
+        let theme = &Theme::default();
         let backend = TestBackend::new(width, height);
         let mut terminal = Terminal::new(backend).expect("terminal");
         terminal
-            .draw(|frame| flow.render(frame))
+            .draw(|frame| flow.render(frame, theme))
             .expect("render attach flow");
         format!("{}", terminal.backend())
     }
@@ -448,6 +859,96 @@ mod tests {
         assert_eq!(signal, FlowSignal::Exit(super::UiExit::BackAtRoot));
     }
 
+    #[test]
+    fn rows_start_most_recently_attached_and_a_toggles_alphabetical() {
+        let mut ops = FakeOps::new();
+        let template = ops.rows[0].clone();
+        ops.rows = ["alpha", "beta", "gamma"]
+            .into_iter()
+            .map(|name| {
+                let mut row = template.clone();
+                row.name = name.to_string();
+                row
+            })
+            .collect();
+        ops.rows[2].last_attached_at = Some("2026-03-01T09:00:00Z".to_string());
+        ops.rows[1].last_attached_at = Some("2026-02-28T09:00:00Z".to_string());
+        let mut flow = AttachFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+
+        assert_eq!(
+            flow.select.selected_row().map(|row| row.name.as_str()),
+            Some("gamma")
+        );
+
+        flow.on_key(key(KeyCode::Char('a')), &ops).expect("toggle");
+        assert_eq!(
+            flow.select.selected_row().map(|row| row.name.as_str()),
+            Some("gamma")
+        );
+        flow.on_key(key(KeyCode::Up), &ops).expect("up");
+        flow.on_key(key(KeyCode::Up), &ops).expect("up");
+        assert_eq!(
+            flow.select.selected_row().map(|row| row.name.as_str()),
+            Some("alpha")
+        );
+
+        flow.on_key(key(KeyCode::Char('a')), &ops)
+            .expect("toggle back");
+        assert_eq!(
+            flow.select.order().key,
+            seshmux_app::WorktreeSortKey::Recent
+        );
+        assert!(ops.attach_calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn selected_session_shows_windows_clients_and_last_activity() {
+        let mut ops = FakeOps::new();
+        let template = ops.rows[0].clone();
+        let mut row = template;
+        row.name = "w2".to_string();
+        row.session_name = "repo/w2".to_string();
+        row.session_running = true;
+        row.last_attached_at = Some("2026-03-01T09:00:00Z".to_string());
+        row.display_name = None;
+        ops.rows.push(row);
+        let now = seshmux_core::time::unix_seconds(SystemTime::now());
+        ops.session_status.insert(
+            "repo/w2".to_string(),
+            SessionStatus {
+                windows: 3,
+                attached_clients: 1,
+                activity: now - 2 * 3_600,
+            },
+        );
+        let mut flow = AttachFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+
+        let rendered = render_output(&flow, 120, 22);
+        assert!(
+            rendered.contains(
+                "tmux session: repo/w2, 3 windows, 1 client attached, last active 2h ago"
+            )
+        );
+
+        flow.on_key(key(KeyCode::Down), &ops).expect("down");
+        let rendered = render_output(&flow, 120, 22);
+        assert!(rendered.contains("tmux session: repo/w1, not running"));
+    }
+
+    #[test]
+    fn attach_named_attaches_that_worktree_without_a_key_press() {
+        let ops = FakeOps::new();
+        let mut flow = AttachFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+
+        let error = flow.attach_named("gone", &ops).expect_err("unknown name");
+        assert!(format!("{error:#}").contains("no worktree named 'gone'"));
+        assert!(ops.attach_calls.borrow().is_empty());
+
+        flow.attach_named("w1", &ops).expect("attach");
+        assert_eq!(flow.step, Step::MissingSessionPrompt);
+        assert_eq!(ops.attach_calls.borrow()[0].worktree_name, "w1");
+    }
+
     #[test]
     fn missing_session_prompt_can_create_and_finish_attach() {
         let ops = FakeOps::new();
@@ -467,6 +968,151 @@ mod tests {
         assert!(calls[1].create_if_missing);
     }
 
+    #[test]
+    fn session_that_died_since_listing_offers_to_recreate_it() {
+        let mut ops = FakeOps::new();
+        ops.rows[0].session_running = true;
+        let mut flow = AttachFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("attempt attach");
+        assert_eq!(flow.step, Step::MissingSessionPrompt);
+        let rendered = render_output(&flow, 120, 22);
+        assert!(rendered.contains("Session no longer running. Recreate it?"));
+        assert!(rendered.contains("is no longer running"));
+
+        flow.on_key(key(KeyCode::Enter), &ops).expect("recreate");
+        assert_eq!(flow.step, Step::Success);
+        let calls = ops.attach_calls.borrow();
+        assert!(calls[0].expect_running);
+        assert!(calls[1].create_if_missing);
+    }
+
+    #[test]
+    fn session_that_dies_while_connecting_offers_recreate_and_attach() {
+        let ops = FakeOps::new();
+        ops.dies_while_connecting.set(1);
+        let mut flow = AttachFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("attempt attach");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("create");
+        assert_eq!(flow.step, Step::Error);
+        assert!(render_output(&flow, 120, 22).contains("Recreate session and attach"));
+
+        flow.on_key(key(KeyCode::Enter), &ops).expect("recreate");
+        assert_eq!(flow.step, Step::Success);
+        let calls = ops.attach_calls.borrow();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[2].worktree_name, "w1");
+        assert!(calls[2].create_if_missing);
+    }
+
+    #[test]
+    fn error_without_a_gone_session_offers_no_recreate() {
+        let ops = FakeOps::new();
+        let mut flow = AttachFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+        flow.show_error(&anyhow::anyhow!("worktree path does not exist on disk"));
+
+        assert!(!render_output(&flow, 120, 22).contains("Recreate session"));
+        flow.on_key(key(KeyCode::Enter), &ops).expect("back");
+        assert_eq!(flow.step, Step::SelectWorktree);
+        assert!(ops.attach_calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn k_kills_the_selected_session_after_confirmation_and_keeps_the_row() {
+        let mut ops = FakeOps::new();
+        ops.rows[0].session_running = true;
+        let mut flow = AttachFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+
+        flow.on_key(key(KeyCode::Char('K')), &ops).expect("prompt");
+        assert_eq!(flow.step, Step::KillConfirm);
+        assert!(render_output(&flow, 120, 22).contains("Kill this tmux session?"));
+        flow.on_key(key(KeyCode::Enter), &ops).expect("decline");
+        assert_eq!(flow.step, Step::SelectWorktree);
+        assert!(ops.kill_calls.borrow().is_empty());
+
+        flow.on_key(key(KeyCode::Char('K')), &ops).expect("prompt");
+        flow.on_key(key(KeyCode::Char(' ')), &ops).expect("toggle");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("confirm");
+
+        assert_eq!(flow.step, Step::SelectWorktree);
+        assert_eq!(*ops.kill_calls.borrow(), vec!["w1".to_string()]);
+        let row = flow.select.selected_row().expect("row kept");
+        assert_eq!(row.name, "w1");
+        assert!(!row.session_running);
+        assert!(ops.attach_calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn killing_the_session_of_someone_elses_worktree_needs_a_second_confirmation() {
+        let mut ops = FakeOps::new();
+        ops.rows[0].session_running = true;
+        ops.foreign_owner = Some("bob@devbox".to_string());
+        let mut flow = AttachFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+
+        flow.on_key(key(KeyCode::Char('K')), &ops).expect("prompt");
+        flow.on_key(key(KeyCode::Char(' ')), &ops).expect("toggle");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("confirm");
+
+        assert_eq!(flow.step, Step::KillConfirm);
+        assert!(ops.kill_calls.borrow().is_empty());
+        let output = render_output(&flow, 120, 22);
+        assert!(output.contains("Kill its session anyway?"));
+        assert!(output.contains("bob@devbox"));
+
+        flow.on_key(key(KeyCode::Char(' ')), &ops).expect("toggle");
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("confirm again");
+        assert_eq!(flow.step, Step::SelectWorktree);
+        assert_eq!(*ops.kill_calls.borrow(), vec!["w1".to_string()]);
+    }
+
+    #[test]
+    fn attaching_to_someone_elses_worktree_is_offered_from_the_error() {
+        let mut ops = FakeOps::new();
+        ops.foreign_owner = Some("bob@devbox".to_string());
+        let mut flow = AttachFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+
+        flow.on_key(key(KeyCode::Enter), &ops).expect("attach");
+        assert_eq!(flow.step, Step::Error);
+        assert!(render_output(&flow, 120, 30).contains("Attach anyway (created by bob@devbox)"));
+
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("attach anyway");
+        assert_eq!(flow.step, Step::Success);
+        let calls = ops.attach_calls.borrow();
+        assert!(!calls[0].allow_foreign);
+        assert!(calls[1].allow_foreign);
+    }
+
+    #[test]
+    fn k_ignores_worktrees_without_a_running_session() {
+        let ops = FakeOps::new();
+        let mut flow = AttachFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+
+        flow.on_key(key(KeyCode::Char('K')), &ops).expect("noop");
+        assert_eq!(flow.step, Step::SelectWorktree);
+        assert!(ops.kill_calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn success_screen_y_copies_the_attach_command() {
+        let ops = FakeOps::new();
+        let mut flow = AttachFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("attach");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("create");
+
+        let signal = flow.on_key(key(KeyCode::Char('y')), &ops).expect("copy");
+        assert_eq!(signal, FlowSignal::Continue);
+        assert_eq!(
+            flow.pending_copy.as_deref(),
+            Some("tmux attach-session -t repo/w1")
+        );
+        assert!(render_output(&flow, 120, 22).contains("y: copy attach command"));
+    }
+
     #[test]
     fn select_step_enter_noop_when_filter_has_no_matches() {
         let ops = FakeOps::new();
@@ -0,0 +1,723 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{List, ListItem, ListState, Paragraph};
+use seshmux_app::{
+    App, AttachRequest, AttachResult, DashResult, DeleteRequest, DeleteResult, ForeignWorktree,
+    WorktreeRow, find_cause,
+};
+
+use crate::UiExit;
+use crate::attach_flow::{AttachRetry, attach_retry_actions};
+use crate::keymap;
+use crate::theme::{self, Accent, Theme};
+use crate::ui::binary_choice::{BinaryChoice, BinaryChoiceEvent};
+use crate::ui::error_actions::{ErrorActions, ErrorActionsEvent, render_error_with_actions};
+use crate::ui::help::{CHOICE_KEYS, ERROR_KEYS, HelpProvider, ScreenHelp};
+use crate::ui::modal::{ModalSpec, render_modal, render_success_modal};
+use crate::ui::text::{
+    compact_hint, focus_line, highlighted_label_value_line, key_hint_height, key_hint_paragraph,
+    label_value_line,
+};
+
+pub(crate) trait DashFlowOps {
+    fn dash(&self) -> Result<DashResult>;
+    fn refresh_sessions(&self) -> Result<BTreeSet<String>>;
+    fn attach_worktree(&self, request: AttachRequest) -> Result<AttachResult>;
+    fn delete_worktree(&self, request: DeleteRequest) -> Result<DeleteResult>;
+}
+
+impl<'a> DashFlowOps for App<'a> {
+    fn dash(&self) -> Result<DashResult> {
+        Ok(App::dash(self)?)
+    }
+
+    fn refresh_sessions(&self) -> Result<BTreeSet<String>> {
+        Ok(App::refresh_sessions(self)?)
+    }
+
+    fn attach_worktree(&self, request: AttachRequest) -> Result<AttachResult> {
+        Ok(self.attach(request)?)
+    }
+
+    fn delete_worktree(&self, request: DeleteRequest) -> Result<DeleteResult> {
+        Ok(self.delete(request)?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Select,
+    ConfirmDelete,
+    Success,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlowSignal {
+    Continue,
+    Exit(UiExit),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DashEntry {
+    repo_root: PathBuf,
+    repo_name: String,
+    row: WorktreeRow,
+}
+
+#[derive(Debug)]
+struct DashFlow {
+    cwd: PathBuf,
+    step: Step,
+    entries: Vec<DashEntry>,
+    problems: Vec<(PathBuf, String)>,
+    selected: usize,
+    delete_choice: BinaryChoice,
+    delete_owner: Option<String>,
+    delete_cwd_inside: bool,
+    message: Option<String>,
+    error_actions: ErrorActions<AttachRetry>,
+}
+
+pub(crate) struct DashScreen {
+    flow: DashFlow,
+}
+
+impl DashScreen {
+    pub(crate) fn new(app: &App<'_>, cwd: &Path) -> Result<Self> {
+        Ok(Self {
+            flow: DashFlow::new(app, cwd)?,
+        })
+    }
+
+    pub(crate) fn refresh_sessions(&mut self, app: &App<'_>) -> Result<()> {
+        self.flow.refresh_sessions(app)
+    }
+
+    pub(crate) fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        self.flow.render(frame, theme);
+    }
+
+    pub(crate) fn selected_worktree(&self) -> Option<&str> {
+        self.flow
+            .selected_entry()
+            .map(|entry| entry.row.session_name.as_str())
+    }
+
+    pub(crate) fn on_key(&mut self, key: KeyEvent, app: &App<'_>) -> Result<Option<UiExit>> {
+        match self.flow.on_key(key, app)? {
+            FlowSignal::Continue => Ok(None),
+            FlowSignal::Exit(exit) => Ok(Some(exit)),
+        }
+    }
+}
+
+impl HelpProvider for DashScreen {
+    fn help(&self) -> ScreenHelp {
+        match self.flow.step {
+            Step::Select => ScreenHelp::new(
+                "Dashboard",
+                "Worktrees of every repository seshmux knows about.",
+            )
+            .keys(&[
+                ("Up/Down or j/k", "move"),
+                ("Enter", "attach, creating the session if needed"),
+                (
+                    "d",
+                    "delete the worktree and its session; the branch is kept",
+                ),
+                ("r", "reload"),
+                ("Esc", "quit seshmux"),
+            ]),
+            Step::ConfirmDelete => ScreenHelp::new(
+                "Delete worktree?",
+                "Yes removes the worktree and kills its tmux session. The branch is kept.",
+            )
+            .keys(CHOICE_KEYS),
+            Step::Success => ScreenHelp::new("Dashboard", "The action finished.").keys(&[
+                ("Enter/Esc", "back to the dashboard"),
+                ("q", "quit seshmux"),
+            ]),
+            Step::Error => ScreenHelp::new("Dashboard", "The action failed.").keys(ERROR_KEYS),
+        }
+    }
+
+    fn typing(&self) -> bool {
+        false
+    }
+}
+
+impl DashFlow {
+    fn new(ops: &dyn DashFlowOps, cwd: &Path) -> Result<Self> {
+        let mut flow = Self {
+            cwd: cwd.to_path_buf(),
+            step: Step::Select,
+            entries: Vec::new(),
+            problems: Vec::new(),
+            selected: 0,
+            delete_choice: BinaryChoice::new(false),
+            delete_owner: None,
+            delete_cwd_inside: false,
+            message: None,
+            error_actions: ErrorActions::none(),
+        };
+        flow.reload(ops)?;
+        Ok(flow)
+    }
+
+    fn reload(&mut self, ops: &dyn DashFlowOps) -> Result<()> {
+        let result = ops.dash()?;
+        self.entries = result
+            .repos
+            .into_iter()
+            .flat_map(|repo| {
+                let repo_name = repo
+                    .repo_root
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| repo.repo_root.display().to_string());
+                repo.rows.into_iter().map(move |row| DashEntry {
+                    repo_root: repo.repo_root.clone(),
+                    repo_name: repo_name.clone(),
+                    row,
+                })
+            })
+            .collect();
+        self.problems = result.problems;
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+        Ok(())
+    }
+
+    fn refresh_sessions(&mut self, ops: &dyn DashFlowOps) -> Result<()> {
+        let running = ops.refresh_sessions()?;
+        for entry in &mut self.entries {
+            entry.row.session_running = running.contains(&entry.row.session_name);
+        }
+        Ok(())
+    }
+
+    fn selected_entry(&self) -> Option<&DashEntry> {
+        self.entries.get(self.selected)
+    }
+
+    fn on_key(&mut self, key: KeyEvent, ops: &dyn DashFlowOps) -> Result<FlowSignal> {
+        match self.step {
+            Step::Select => self.on_key_select(key, ops),
+            Step::ConfirmDelete => self.on_key_confirm_delete(key, ops),
+            Step::Success => {
+                if keymap::is_quit(key) {
+                    return Ok(FlowSignal::Exit(UiExit::Completed));
+                }
+                if keymap::is_back(key) || keymap::is_confirm(key) {
+                    self.message = None;
+                    self.step = Step::Select;
+                }
+                Ok(FlowSignal::Continue)
+            }
+            Step::Error => {
+                match self.error_actions.on_key(key) {
+                    ErrorActionsEvent::Continue => {}
+                    ErrorActionsEvent::Back => {
+                        self.message = None;
+                        self.step = Step::Select;
+                    }
+                    ErrorActionsEvent::Choose(retry) => self.attach(ops, retry.allow_foreign)?,
+                }
+                Ok(FlowSignal::Continue)
+            }
+        }
+    }
+
+    fn on_key_select(&mut self, key: KeyEvent, ops: &dyn DashFlowOps) -> Result<FlowSignal> {
+        if keymap::is_back(key) {
+            return Ok(FlowSignal::Exit(UiExit::Completed));
+        }
+
+        if keymap::is_up(key) {
+            self.selected = self.selected.saturating_sub(1);
+        } else if keymap::is_down(key) {
+            if self.selected + 1 < self.entries.len() {
+                self.selected += 1;
+            }
+        } else if key.code == KeyCode::Char('r') {
+            self.reload(ops)?;
+        } else if key.code == KeyCode::Char('d') && self.selected_entry().is_some() {
+            self.delete_choice = BinaryChoice::new(false);
+            self.delete_owner = None;
+            self.delete_cwd_inside = false;
+            self.step = Step::ConfirmDelete;
+        } else if keymap::is_confirm(key) {
+            self.attach(ops, false)?;
+        }
+
+        Ok(FlowSignal::Continue)
+    }
+
+    fn attach(&mut self, ops: &dyn DashFlowOps, allow_foreign: bool) -> Result<()> {
+        let Some(entry) = self.selected_entry().cloned() else {
+            return Ok(());
+        };
+
+        let mut request = AttachRequest::new(entry.repo_root, entry.row.name);
+        request.create_if_missing = true;
+        request.connect = true;
+        request.allow_foreign = allow_foreign;
+        match ops.attach_worktree(request) {
+            Ok(result) => {
+                let state = if result.created_session {
+                    "created"
+                } else {
+                    "existing"
+                };
+                self.message = Some(format!(
+                    "Attached to {state} tmux session {}",
+                    result.session_name
+                ));
+                self.step = Step::Success;
+                self.refresh_sessions(ops)?;
+            }
+            Err(error) => {
+                self.message = Some(format!("{error:#}"));
+                self.error_actions = attach_retry_actions(&error);
+                self.step = Step::Error;
+            }
+        }
+        Ok(())
+    }
+
+    fn on_key_confirm_delete(
+        &mut self,
+        key: KeyEvent,
+        ops: &dyn DashFlowOps,
+    ) -> Result<FlowSignal> {
+        match self.delete_choice.on_key(key) {
+            BinaryChoiceEvent::Continue => {}
+            BinaryChoiceEvent::Back | BinaryChoiceEvent::ConfirmNo => {
+                self.delete_owner = None;
+                self.delete_cwd_inside = false;
+                self.step = Step::Select;
+            }
+            BinaryChoiceEvent::ConfirmYes => {
+                let Some(entry) = self.selected_entry().cloned() else {
+                    self.step = Step::Select;
+                    return Ok(FlowSignal::Continue);
+                };
+                if !self.delete_cwd_inside && self.cwd.starts_with(&entry.row.path) {
+                    self.delete_cwd_inside = true;
+                    self.delete_choice = BinaryChoice::new(false);
+                    return Ok(FlowSignal::Continue);
+                }
+                let allow_foreign = self.delete_owner.take().is_some();
+                let mut request = DeleteRequest::new(entry.repo_root, entry.row.name);
+                request.kill_tmux_session = true;
+                request.allow_cwd_inside = self.delete_cwd_inside;
+                request.allow_foreign = allow_foreign;
+                match ops.delete_worktree(request) {
+                    Ok(result) => {
+                        self.message = Some(format!(
+                            "Deleted worktree {} from {}",
+                            result.worktree_name,
+                            result.repo_root.display()
+                        ));
+                        self.step = Step::Success;
+                        self.reload(ops)?;
+                    }
+                    Err(error) => match find_cause::<ForeignWorktree>(&error) {
+                        Some(foreign) if !allow_foreign => {
+                            self.delete_owner = Some(foreign.owner.clone());
+                            self.delete_choice = BinaryChoice::new(false);
+                        }
+                        _ => {
+                            self.message = Some(format!("{error:#}"));
+                            self.error_actions = ErrorActions::none();
+                            self.step = Step::Error;
+                        }
+                    },
+                }
+            }
+        }
+        Ok(FlowSignal::Continue)
+    }
+
+    fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        self.render_select(frame, theme);
+        let message = self.message.as_deref().unwrap_or_default();
+        match self.step {
+            Step::Select => {}
+            Step::ConfirmDelete => self.render_confirm_delete(frame, theme),
+            Step::Success => {
+                render_success_modal(frame, theme, message, 70, 40, "Enter/Esc: back    q: quit");
+            }
+            Step::Error => {
+                render_error_with_actions(
+                    frame,
+                    theme,
+                    message,
+                    &self.error_actions,
+                    "back",
+                    80,
+                    40,
+                );
+            }
+        }
+    }
+
+    fn render_select(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        let area = frame.area();
+        let key_text = compact_hint(
+            area.width,
+            "Enter: attach (creates the session if needed)    d: delete    Up/Down or j/k: move    r: reload    Esc: quit",
+            "Enter: attach    d: delete    j/k: move    r: reload    Esc: quit",
+            "Enter attach | d delete | j/k move | Esc quit",
+        );
+        let footer_height = key_hint_height(area.width, key_text);
+        let problems_height = if self.problems.is_empty() {
+            0
+        } else {
+            self.problems.len().min(4) as u16 + 2
+        };
+        let [body, problems, footer] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(6),
+                Constraint::Length(problems_height),
+                Constraint::Length(footer_height),
+            ])
+            .areas(area);
+
+        let repo_count = self
+            .entries
+            .iter()
+            .map(|entry| &entry.repo_root)
+            .collect::<BTreeSet<_>>()
+            .len();
+        let running = self
+            .entries
+            .iter()
+            .filter(|entry| entry.row.session_running)
+            .count();
+        let block = theme::chrome(focus_line(
+            theme,
+            format!(
+                "Dashboard - {} worktree(s) in {repo_count} repo(s), {running} session(s) running",
+                self.entries.len()
+            ),
+        ));
+        if self.entries.is_empty() {
+            let empty = Paragraph::new(
+                "No worktrees are registered in the configured repositories. Set [dash] repos or base_dir in the config to add more.",
+            )
+            .block(block);
+            frame.render_widget(empty, body);
+        } else {
+            let items: Vec<ListItem<'_>> = self
+                .entries
+                .iter()
+                .map(|entry| entry_item(theme, entry))
+                .collect();
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(theme.table_highlight(Accent::Browse))
+                .highlight_symbol(">> ");
+            let mut state = ListState::default();
+            state.select(Some(self.selected));
+            frame.render_stateful_widget(list, body, &mut state);
+        }
+
+        if !self.problems.is_empty() {
+            let lines: Vec<Line<'_>> = self
+                .problems
+                .iter()
+                .map(|(path, reason)| {
+                    let reason = reason.lines().next().unwrap_or_default();
+                    Line::from(format!("{}: {reason}", path.display()))
+                })
+                .collect();
+            let skipped = Paragraph::new(lines).block(theme::chrome(Line::from(format!(
+                "Skipped {} path(s)",
+                self.problems.len()
+            ))));
+            frame.render_widget(skipped, problems);
+        }
+
+        let keys = key_hint_paragraph(key_text).block(theme::key_block());
+        frame.render_widget(keys, footer);
+    }
+
+    fn render_confirm_delete(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        let path = entry.row.path.display().to_string();
+        let mut lines = vec![
+            label_value_line(theme, "Repository", &entry.repo_name),
+            label_value_line(theme, "Worktree", &entry.row.name),
+            label_value_line(theme, "Path", &path),
+        ];
+        if let Some(owner) = &self.delete_owner {
+            lines.push(label_value_line(theme, "Created by", owner));
+        }
+        lines.push(Line::from(""));
+        if self.delete_cwd_inside {
+            lines.push(Line::from(
+                "seshmux was started inside this worktree; that shell is left in a removed directory.",
+            ));
+        }
+        lines.extend([
+            Line::from("The tmux session is killed and the branch is kept."),
+            highlighted_label_value_line(
+                theme,
+                "Current Selection",
+                self.delete_choice.selected_label(),
+            ),
+        ]);
+        render_modal(
+            frame,
+            theme,
+            ModalSpec {
+                title: if self.delete_owner.is_some() {
+                    "Someone else created this worktree. Delete it anyway?"
+                } else if self.delete_cwd_inside {
+                    "The current directory is inside this worktree. Delete it anyway?"
+                } else {
+                    "Delete this worktree?"
+                },
+                title_style: Some(theme.focus_prompt()),
+                body: Text::from(lines),
+                key_hint: Some("Space: toggle    Enter: confirm    Esc: cancel"),
+                width_pct: 70,
+                height_pct: 45,
+            },
+        );
+    }
+}
+
+fn entry_item(theme: &Theme, entry: &DashEntry) -> ListItem<'static> {
+    let session = if entry.row.session_running {
+        "running"
+    } else {
+        "not running"
+    };
+
+    ListItem::new(Line::from(vec![
+        Span::raw(format!("{}/{}", entry.repo_name, entry.row.name)),
+        Span::styled(
+            format!(
+                "  {}  {session}  {}",
+                entry.row.head.label(),
+                entry.row.path.display()
+            ),
+            theme.secondary_text(),
+        ),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::collections::BTreeSet;
+    use std::path::{Path, PathBuf};
+
+    use anyhow::Result;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use seshmux_app::{
+        AttachError, AttachRequest, AttachResult, DashRepo, DashResult, DeleteRequest,
+        DeleteResult, WorktreeHead, WorktreeRow,
+    };
+
+    use super::{DashFlow, DashFlowOps, FlowSignal, Step};
+    use crate::theme::Theme;
+
+    struct FakeOps {
+        repos: RefCell<Vec<DashRepo>>,
+        attach_calls: RefCell<Vec<AttachRequest>>,
+        delete_calls: RefCell<Vec<DeleteRequest>>,
+        session_dies: Cell<bool>,
+    }
+
+    fn row(repo: &str, name: &str) -> WorktreeRow {
+        WorktreeRow::new(
+            name,
+            format!("/src/{repo}/worktrees/{name}"),
+            "2026-02-25T10:00:00Z",
+            WorktreeHead::Branch(name.to_string()),
+            format!("{repo}/{name}"),
+        )
+    }
+
+    impl FakeOps {
+        fn new() -> Self {
+            Self {
+                repos: RefCell::new(vec![
+                    DashRepo {
+                        repo_root: PathBuf::from("/src/api"),
+                        rows: vec![row("api", "auth")],
+                    },
+                    DashRepo {
+                        repo_root: PathBuf::from("/src/web"),
+                        rows: vec![row("web", "login"), row("web", "theme")],
+                    },
+                ]),
+                attach_calls: RefCell::new(Vec::new()),
+                delete_calls: RefCell::new(Vec::new()),
+                session_dies: Cell::new(false),
+            }
+        }
+    }
+
+    impl DashFlowOps for FakeOps {
+        fn dash(&self) -> Result<DashResult> {
+            Ok(DashResult {
+                repos: self.repos.borrow().clone(),
+                problems: vec![(
+                    PathBuf::from("/src/notes"),
+                    "not a git repository".to_string(),
+                )],
+            })
+        }
+
+        fn refresh_sessions(&self) -> Result<BTreeSet<String>> {
+            Ok(BTreeSet::from(["web/login".to_string()]))
+        }
+
+        fn attach_worktree(&self, request: AttachRequest) -> Result<AttachResult> {
+            self.attach_calls.borrow_mut().push(request.clone());
+            if self.session_dies.replace(false) {
+                return Err(AttachError::SessionGone {
+                    worktree_name: request.worktree_name,
+                    session_name: "api/auth".to_string(),
+                    server_reachable: true,
+                }
+                .into());
+            }
+            Ok({
+                let mut result = AttachResult::new(
+                    request.worktree_name.clone(),
+                    request.cwd.join("worktrees").join(&request.worktree_name),
+                    format!("repo/{}", request.worktree_name),
+                );
+                result.connect_command = "tmux attach".to_string();
+                result.attach_status = Some(0);
+                result
+            })
+        }
+
+        fn delete_worktree(&self, request: DeleteRequest) -> Result<DeleteResult> {
+            self.delete_calls.borrow_mut().push(request.clone());
+            for repo in self.repos.borrow_mut().iter_mut() {
+                repo.rows.retain(|row| row.name != request.worktree_name);
+            }
+            Ok(DeleteResult::new(
+                request.worktree_name.clone(),
+                request.cwd.clone(),
+                request.cwd.join("worktrees").join(&request.worktree_name),
+                format!("repo/{}", request.worktree_name),
+                request.worktree_name.clone(),
+            ))
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn render_output(flow: &DashFlow, width: u16, height: u16) -> String {
+        let theme = &Theme::default();
+        let backend = TestBackend::new(width, height);
+        let mut terminal = Terminal::new(backend).expect("terminal");
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render dash flow");
+        format!("{}", terminal.backend())
+    }
+
+    #[test]
+    fn dashboard_lists_worktrees_from_every_repo_and_attaches_in_the_right_repo() {
+        let ops = FakeOps::new();
+        let mut flow = DashFlow::new(&ops, Path::new("/home")).expect("flow");
+        flow.refresh_sessions(&ops).expect("sessions");
+
+        let output = render_output(&flow, 140, 20);
+        assert!(output.contains("3 worktree(s) in 2 repo(s), 1 session(s) running"));
+        assert!(output.contains("api/auth"));
+        assert!(output.contains("web/theme"));
+        assert!(output.contains("/src/notes: not a git repository"));
+
+        flow.on_key(key(KeyCode::Char('j')), &ops).expect("down");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("attach");
+        assert_eq!(flow.step, Step::Success);
+        let calls = ops.attach_calls.borrow();
+        assert_eq!(calls[0].cwd, PathBuf::from("/src/web"));
+        assert_eq!(calls[0].worktree_name, "login");
+        assert!(calls[0].create_if_missing);
+    }
+
+    #[test]
+    fn session_gone_while_attaching_can_be_recreated_from_the_error() {
+        let ops = FakeOps::new();
+        ops.session_dies.set(true);
+        let mut flow = DashFlow::new(&ops, Path::new("/home")).expect("flow");
+
+        flow.on_key(key(KeyCode::Enter), &ops).expect("attach");
+        assert_eq!(flow.step, Step::Error);
+        assert!(render_output(&flow, 140, 24).contains("Recreate session and attach"));
+
+        flow.on_key(key(KeyCode::Enter), &ops).expect("recreate");
+        assert_eq!(flow.step, Step::Success);
+        let calls = ops.attach_calls.borrow();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[1].cwd, PathBuf::from("/src/api"));
+        assert_eq!(calls[1].worktree_name, "auth");
+    }
+
+    #[test]
+    fn delete_requires_confirmation_and_reloads_the_dashboard() {
+        let ops = FakeOps::new();
+        let mut flow = DashFlow::new(&ops, Path::new("/home")).expect("flow");
+
+        flow.on_key(key(KeyCode::Char('d')), &ops).expect("delete");
+        assert_eq!(flow.step, Step::ConfirmDelete);
+        flow.on_key(key(KeyCode::Enter), &ops).expect("decline");
+        assert_eq!(flow.step, Step::Select);
+        assert!(ops.delete_calls.borrow().is_empty());
+
+        flow.on_key(key(KeyCode::Char('d')), &ops).expect("delete");
+        flow.on_key(key(KeyCode::Char(' ')), &ops).expect("toggle");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("confirm");
+
+        assert_eq!(flow.step, Step::Success);
+        assert_eq!(ops.delete_calls.borrow()[0].cwd, PathBuf::from("/src/api"));
+        assert_eq!(flow.entries.len(), 2);
+
+        flow.on_key(key(KeyCode::Enter), &ops).expect("close");
+        let signal = flow.on_key(key(KeyCode::Esc), &ops).expect("esc");
+        assert_eq!(signal, FlowSignal::Exit(super::UiExit::Completed));
+    }
+
+    #[test]
+    fn delete_asks_again_when_started_inside_the_worktree() {
+        let ops = FakeOps::new();
+        let mut flow = DashFlow::new(&ops, Path::new("/src/api/worktrees/auth/src")).expect("flow");
+
+        flow.on_key(key(KeyCode::Char('d')), &ops).expect("delete");
+        flow.on_key(key(KeyCode::Char(' ')), &ops).expect("toggle");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("confirm");
+        assert_eq!(flow.step, Step::ConfirmDelete);
+        assert!(flow.delete_cwd_inside);
+        assert!(ops.delete_calls.borrow().is_empty());
+        assert!(render_output(&flow, 120, 40).contains("inside this worktree"));
+
+        flow.on_key(key(KeyCode::Char(' ')), &ops).expect("toggle");
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("confirm again");
+        assert_eq!(flow.step, Step::Success);
+        assert!(ops.delete_calls.borrow()[0].allow_cwd_inside);
+    }
+}
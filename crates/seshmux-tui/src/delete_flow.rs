@@ -1,43 +1,55 @@
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 
-use anyhow::Result;
-use crossterm::event::KeyEvent;
+use anyhow::{Result, bail};
+use crossterm::event::{KeyEvent, MouseEvent};
 use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::style::Color;
 use ratatui::text::{Line, Text};
-use seshmux_app::{App, DeleteError, DeleteRequest, DeleteResult, ListResult};
+use seshmux_app::{
+    App, DeleteError, DeleteRequest, DeleteResult, ForeignWorktree, ListResult, find_cause,
+};
 
 use crate::UiExit;
 use crate::keymap;
-use crate::theme;
+use crate::theme::{Accent, Theme};
 use crate::ui::binary_choice::{BinaryChoice, BinaryChoiceEvent};
+use crate::ui::help::{CHOICE_KEYS, HelpProvider, RESULT_KEYS, ScreenHelp};
 use crate::ui::modal::{
     ModalSpec, render_error_modal, render_modal, render_notice_modal, render_success_modal,
 };
 use crate::ui::select_step::{SelectSignal, SelectStepState};
 use crate::ui::text::{
-    compact_hint, focus_line, highlighted_label_value_line, key_hint_height, key_hint_paragraph,
-    label_value_line, result_footer, yes_no,
+    compact_hint, focus_line, highlighted_label_value_line, key_hint_height, label_value_line,
+    result_footer, yes_no,
 };
-use crate::ui::worktree_table::{TableColumn, WorktreeTableRender};
+use crate::ui::worktree_table::{WorktreeTableRender, configured_columns};
 
 pub(crate) trait DeleteFlowOps {
     fn list_worktrees(&self, cwd: &Path) -> Result<ListResult>;
+    fn refresh_sessions(&self) -> Result<BTreeSet<String>>;
     fn delete_worktree(&self, request: DeleteRequest) -> Result<DeleteResult>;
     fn force_delete_branch(&self, repo_root: &Path, branch_name: &str) -> Result<()>;
 }
 
 impl<'a> DeleteFlowOps for App<'a> {
     fn list_worktrees(&self, cwd: &Path) -> Result<ListResult> {
-        self.list(cwd)
+        Ok(self.list(cwd)?)
+    }
+
+    fn refresh_sessions(&self) -> Result<BTreeSet<String>> {
+        Ok(App::refresh_sessions(self)?)
     }
 
     fn delete_worktree(&self, request: DeleteRequest) -> Result<DeleteResult> {
-        self.delete(request)
+        Ok(self.delete(request)?)
     }
 
     fn force_delete_branch(&self, repo_root: &Path, branch_name: &str) -> Result<()> {
-        App::force_delete_branch(self, repo_root.to_path_buf(), branch_name.to_string())
+        Ok(App::force_delete_branch(
+            self,
+            repo_root.to_path_buf(),
+            branch_name.to_string(),
+        )?)
     }
 }
 
@@ -46,6 +58,8 @@ enum Step {
     SelectWorktree,
     Options,
     Confirm,
+    CwdInsidePrompt,
+    ForeignPrompt,
     WorktreeForcePrompt,
     BranchForcePrompt,
     Notice,
@@ -81,6 +95,10 @@ struct DeleteFlow {
     options: DeleteOptions,
     option_selected: usize,
     confirm_choice: BinaryChoice,
+    cwd_inside_choice: BinaryChoice,
+    allow_cwd_inside: bool,
+    foreign_choice: BinaryChoice,
+    allow_foreign: bool,
     worktree_force_choice: BinaryChoice,
     branch_force_choice: BinaryChoice,
     pending_result: Option<DeleteResult>,
@@ -94,13 +112,49 @@ pub(crate) struct DeleteScreen {
 
 impl DeleteScreen {
     pub(crate) fn new(app: &App<'_>, cwd: &Path) -> Result<Self> {
-        Ok(Self {
-            flow: DeleteFlow::new(app, cwd)?,
-        })
+        let mut flow = DeleteFlow::new(app, cwd)?;
+        if let Some(columns) = configured_columns(app) {
+            flow.select.set_columns(&columns);
+        }
+        Ok(Self { flow })
+    }
+
+    pub(crate) fn delete_named(app: &App<'_>, cwd: &Path, worktree_name: &str) -> Result<Self> {
+        let mut screen = Self::new(app, cwd)?;
+        screen.flow.choose_named(worktree_name)?;
+        Ok(screen)
+    }
+
+    pub(crate) fn refresh_sessions(&mut self, app: &App<'_>) -> Result<()> {
+        self.flow.refresh_sessions(app)
+    }
+
+    pub(crate) fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        self.flow.render(frame, theme);
+    }
+
+    pub(crate) fn selected_worktree(&self) -> Option<&str> {
+        self.flow
+            .target_name
+            .as_deref()
+            .or_else(|| self.flow.select.selected_row().map(|row| row.name.as_str()))
     }
 
-    pub(crate) fn render(&self, frame: &mut ratatui::Frame<'_>) {
-        self.flow.render(frame);
+    pub(crate) fn take_pending_copy(&mut self) -> Option<String> {
+        self.flow.select.take_pending_copy()
+    }
+
+    pub(crate) fn on_paste(&mut self, text: &str) {
+        if self.flow.step == Step::SelectWorktree {
+            self.flow.select.on_paste(text);
+        }
+    }
+
+    pub(crate) fn on_mouse(&mut self, mouse: MouseEvent) -> Option<KeyEvent> {
+        if self.flow.step != Step::SelectWorktree {
+            return None;
+        }
+        self.flow.select.on_mouse(mouse)
     }
 
     pub(crate) fn on_key(&mut self, key: KeyEvent, app: &App<'_>) -> Result<Option<UiExit>> {
@@ -111,6 +165,69 @@ impl DeleteScreen {
     }
 }
 
+impl HelpProvider for DeleteScreen {
+    fn help(&self) -> ScreenHelp {
+        match self.flow.step {
+            Step::SelectWorktree => ScreenHelp::new(
+                "Delete",
+                "Pick the worktree to delete. Nothing is removed until you confirm.",
+            )
+            .keys(&self.flow.select.help_keys("choose what else to delete", &[])),
+            Step::Options => ScreenHelp::new(
+                "Delete options",
+                "The worktree directory is always removed; choose whether its tmux session and branch go too.",
+            )
+            .keys(&[
+                ("Up/Down or j/k", "move"),
+                ("Space", "toggle the option"),
+                ("Enter", "continue to the confirmation"),
+                ("Esc", "back to the worktrees"),
+            ]),
+            Step::Confirm => ScreenHelp::new(
+                "Confirm delete",
+                "Yes deletes the worktree with the options shown; No cancels without changes.",
+            )
+            .keys(CHOICE_KEYS),
+            Step::CwdInsidePrompt => ScreenHelp::new(
+                "Delete the current directory?",
+                "Your shell is inside this worktree. Yes deletes it anyway and leaves the shell in a removed directory.",
+            )
+            .keys(CHOICE_KEYS),
+            Step::ForeignPrompt => ScreenHelp::new(
+                "Delete someone else's worktree?",
+                "Another user created this worktree. Yes deletes it anyway; No cancels without changes.",
+            )
+            .keys(CHOICE_KEYS),
+            Step::WorktreeForcePrompt => ScreenHelp::new(
+                "Force delete?",
+                "The worktree has uncommitted or untracked changes. Yes deletes it and those changes are lost.",
+            )
+            .keys(CHOICE_KEYS),
+            Step::BranchForcePrompt => ScreenHelp::new(
+                "Force delete the branch?",
+                "The worktree is gone, but its branch is not merged. Yes deletes it with unmerged commits; No keeps it.",
+            )
+            .keys(&[
+                ("Space", "switch between Yes and No"),
+                ("Enter", "confirm the current selection"),
+                ("Esc", "keep the branch"),
+            ]),
+            Step::Notice | Step::Success => {
+                ScreenHelp::new("Delete finished", "What was deleted and what was kept.")
+                    .keys(RESULT_KEYS)
+            }
+            Step::Error => ScreenHelp::new("Delete failed", "The worktree could not be deleted.")
+                .keys(&[("Enter/Esc", "back to the worktrees")]),
+        }
+    }
+
+    fn typing(&self) -> bool {
+        self.flow.step == Step::SelectWorktree
+            && self.flow.select.filter_focused()
+            && !self.flow.select.details_open()
+    }
+}
+
 impl DeleteFlow {
     fn new(ops: &dyn DeleteFlowOps, cwd: &Path) -> Result<Self> {
         let result = ops.list_worktrees(cwd)?;
@@ -126,6 +243,10 @@ impl DeleteFlow {
             },
             option_selected: 0,
             confirm_choice: BinaryChoice::new(false),
+            cwd_inside_choice: BinaryChoice::new(false),
+            allow_cwd_inside: false,
+            foreign_choice: BinaryChoice::new(false),
+            allow_foreign: false,
             worktree_force_choice: BinaryChoice::new(false),
             branch_force_choice: BinaryChoice::new(false),
             pending_result: None,
@@ -134,11 +255,19 @@ impl DeleteFlow {
         })
     }
 
+    fn refresh_sessions(&mut self, ops: &dyn DeleteFlowOps) -> Result<()> {
+        let running = ops.refresh_sessions()?;
+        self.select.update_session_status(&running);
+        Ok(())
+    }
+
     fn on_key(&mut self, key: KeyEvent, ops: &dyn DeleteFlowOps) -> Result<FlowSignal> {
         match self.step {
             Step::SelectWorktree => Ok(self.on_key_select(key)),
             Step::Options => Ok(self.on_key_options(key)),
             Step::Confirm => self.on_key_confirm(key, ops),
+            Step::CwdInsidePrompt => self.on_key_cwd_inside_prompt(key, ops),
+            Step::ForeignPrompt => self.on_key_foreign_prompt(key, ops),
             Step::WorktreeForcePrompt => self.on_key_worktree_force_prompt(key, ops),
             Step::BranchForcePrompt => self.on_key_branch_force_prompt(key, ops),
             Step::Notice => Ok(self.on_key_notice(key)),
@@ -154,8 +283,21 @@ impl DeleteFlow {
             SelectSignal::Confirm => {}
         }
 
+        self.choose_selected();
+        FlowSignal::Continue
+    }
+
+    fn choose_named(&mut self, worktree_name: &str) -> Result<()> {
+        if !self.select.select_name(worktree_name) {
+            bail!("no worktree named '{worktree_name}'");
+        }
+        self.choose_selected();
+        Ok(())
+    }
+
+    fn choose_selected(&mut self) {
         let Some(row) = self.select.selected_row() else {
-            return FlowSignal::Continue;
+            return;
         };
 
         self.target_name = Some(row.name.clone());
@@ -166,6 +308,8 @@ impl DeleteFlow {
         };
         self.option_selected = 0;
         self.confirm_choice = BinaryChoice::new(false);
+        self.cwd_inside_choice = BinaryChoice::new(false);
+        self.allow_cwd_inside = false;
         self.worktree_force_choice = BinaryChoice::new(false);
         self.branch_force_choice = BinaryChoice::new(false);
         self.pending_result = None;
@@ -173,8 +317,6 @@ impl DeleteFlow {
         self.error_message = None;
         self.select.set_filter_focused(false);
         self.step = Step::Options;
-
-        FlowSignal::Continue
     }
 
     fn on_key_options(&mut self, key: KeyEvent) -> FlowSignal {
@@ -229,6 +371,54 @@ impl DeleteFlow {
         }
     }
 
+    fn on_key_cwd_inside_prompt(
+        &mut self,
+        key: KeyEvent,
+        ops: &dyn DeleteFlowOps,
+    ) -> Result<FlowSignal> {
+        match self.cwd_inside_choice.on_key(key) {
+            BinaryChoiceEvent::Back => {
+                self.step = Step::Confirm;
+                Ok(FlowSignal::Continue)
+            }
+            BinaryChoiceEvent::Continue => Ok(FlowSignal::Continue),
+            BinaryChoiceEvent::ConfirmNo => {
+                self.success_message = Some("Delete canceled. No changes were made.".to_string());
+                self.step = Step::Notice;
+                Ok(FlowSignal::Continue)
+            }
+            BinaryChoiceEvent::ConfirmYes => {
+                self.allow_cwd_inside = true;
+                self.execute_delete(ops, false)?;
+                Ok(FlowSignal::Continue)
+            }
+        }
+    }
+
+    fn on_key_foreign_prompt(
+        &mut self,
+        key: KeyEvent,
+        ops: &dyn DeleteFlowOps,
+    ) -> Result<FlowSignal> {
+        match self.foreign_choice.on_key(key) {
+            BinaryChoiceEvent::Back => {
+                self.step = Step::Confirm;
+                Ok(FlowSignal::Continue)
+            }
+            BinaryChoiceEvent::Continue => Ok(FlowSignal::Continue),
+            BinaryChoiceEvent::ConfirmNo => {
+                self.success_message = Some("Delete canceled. No changes were made.".to_string());
+                self.step = Step::Notice;
+                Ok(FlowSignal::Continue)
+            }
+            BinaryChoiceEvent::ConfirmYes => {
+                self.allow_foreign = true;
+                self.execute_delete(ops, false)?;
+                Ok(FlowSignal::Continue)
+            }
+        }
+    }
+
     fn on_key_worktree_force_prompt(
         &mut self,
         key: KeyEvent,
@@ -337,13 +527,13 @@ impl DeleteFlow {
             return Ok(());
         };
 
-        match ops.delete_worktree(DeleteRequest {
-            cwd: self.cwd.clone(),
-            worktree_name,
-            kill_tmux_session: self.options.kill_tmux_session,
-            delete_branch: self.options.delete_branch,
-            force_worktree,
-        }) {
+        let mut request = DeleteRequest::new(self.cwd.clone(), worktree_name);
+        request.kill_tmux_session = self.options.kill_tmux_session;
+        request.delete_branch = self.options.delete_branch;
+        request.force_worktree = force_worktree;
+        request.allow_cwd_inside = self.allow_cwd_inside;
+        request.allow_foreign = self.allow_foreign;
+        match ops.delete_worktree(request) {
             Ok(result) => {
                 self.select.remove_by_name(&result.worktree_name);
                 self.error_message = None;
@@ -358,15 +548,30 @@ impl DeleteFlow {
                 }
             }
             Err(error) => {
-                if !force_worktree {
-                    if let Some(DeleteError::WorktreeDeleteFailed { message }) =
-                        error.downcast_ref::<DeleteError>()
-                    {
-                        self.error_message = Some(message.clone());
-                        self.worktree_force_choice = BinaryChoice::new(false);
-                        self.step = Step::WorktreeForcePrompt;
-                        return Ok(());
-                    }
+                if let Some(DeleteError::CwdInsideWorktree { .. }) =
+                    find_cause::<DeleteError>(&error)
+                {
+                    self.error_message = Some(error.to_string());
+                    self.cwd_inside_choice = BinaryChoice::new(false);
+                    self.step = Step::CwdInsidePrompt;
+                    return Ok(());
+                }
+
+                if find_cause::<ForeignWorktree>(&error).is_some() {
+                    self.error_message = Some(error.to_string());
+                    self.foreign_choice = BinaryChoice::new(false);
+                    self.step = Step::ForeignPrompt;
+                    return Ok(());
+                }
+
+                if !force_worktree
+                    && let Some(DeleteError::WorktreeDeleteFailed { message }) =
+                        find_cause::<DeleteError>(&error)
+                {
+                    self.error_message = Some(message.clone());
+                    self.worktree_force_choice = BinaryChoice::new(false);
+                    self.step = Step::WorktreeForcePrompt;
+                    return Ok(());
                 }
 
                 self.error_message = Some(format!("{error:#}"));
@@ -378,6 +583,19 @@ impl DeleteFlow {
     }
 
     fn success_summary(&self, result: &DeleteResult) -> String {
+        if result.trashed_to.is_some() {
+            let branch_summary = if self.options.delete_branch {
+                "kept until the trash is purged"
+            } else {
+                "kept (not requested)"
+            };
+            let summary = format!(
+                "Moved worktree '{}' to the trash. tmux session '{}'. Branch status: {}.\nRestore it with: seshmux restore {}",
+                result.worktree_name, result.session_name, branch_summary, result.worktree_name
+            );
+            return with_delete_notes(summary, result);
+        }
+
         let branch_summary = if self.options.delete_branch {
             if result.branch_deleted {
                 "deleted".to_string()
@@ -390,10 +608,11 @@ impl DeleteFlow {
             "kept (not requested)".to_string()
         };
 
-        format!(
+        let summary = format!(
             "Deleted worktree '{}'. tmux session '{}'. Branch status: {}.",
             result.worktree_name, result.session_name, branch_summary
-        )
+        );
+        with_delete_notes(summary, result)
     }
 
     fn branch_kept_message(&self, result: &DeleteResult) -> String {
@@ -401,10 +620,11 @@ impl DeleteFlow {
             .branch_delete_error
             .as_deref()
             .unwrap_or("unknown error");
-        format!(
+        let summary = format!(
             "Deleted worktree '{}'. tmux session '{}'. Branch kept (safe delete failed: {}).",
             result.worktree_name, result.session_name, failure
-        )
+        );
+        with_delete_notes(summary, result)
     }
 
     fn option_fields(&self) -> [OptionField; 2] {
@@ -428,20 +648,22 @@ impl DeleteFlow {
         }
     }
 
-    fn render(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         match self.step {
-            Step::SelectWorktree => self.render_select(frame),
-            Step::Options => self.render_options(frame),
-            Step::Confirm => self.render_confirm(frame),
-            Step::WorktreeForcePrompt => self.render_worktree_force_prompt(frame),
-            Step::BranchForcePrompt => self.render_branch_force_prompt(frame),
-            Step::Notice => self.render_notice(frame),
-            Step::Success => self.render_success(frame),
-            Step::Error => self.render_error(frame),
+            Step::SelectWorktree => self.render_select(frame, theme),
+            Step::Options => self.render_options(frame, theme),
+            Step::Confirm => self.render_confirm(frame, theme),
+            Step::CwdInsidePrompt => self.render_cwd_inside_prompt(frame, theme),
+            Step::ForeignPrompt => self.render_foreign_prompt(frame, theme),
+            Step::WorktreeForcePrompt => self.render_worktree_force_prompt(frame, theme),
+            Step::BranchForcePrompt => self.render_branch_force_prompt(frame, theme),
+            Step::Notice => self.render_notice(frame, theme),
+            Step::Success => self.render_success(frame, theme),
+            Step::Error => self.render_error(frame, theme),
         }
     }
 
-    fn render_select(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_select(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let area = frame.area();
         let key_text = if self.select.filter_focused() {
             compact_hint(
@@ -453,8 +675,8 @@ impl DeleteFlow {
         } else {
             compact_hint(
                 area.width,
-                "/: filter focus    Enter: select    Up/Down or j/k: move    Esc: back",
-                "/: filter    Enter: select    j/k: move    Esc: back",
+                "/: filter focus    Enter: select    Up/Down or j/k: move    e: details    Esc: back",
+                "/: filter    Enter: select    j/k: move    e: details    Esc: back",
                 "/ filter | Enter select | j/k move | Esc back",
             )
         };
@@ -472,29 +694,10 @@ impl DeleteFlow {
         self.select.render_filter(
             frame,
             filter_area,
-            focus_line("Filter"),
+            focus_line(theme, "Filter"),
             Line::from("Filter (/ to focus)"),
         );
 
-        let columns = [
-            TableColumn {
-                title: "Name",
-                width: Constraint::Length(24),
-            },
-            TableColumn {
-                title: "Created",
-                width: Constraint::Length(28),
-            },
-            TableColumn {
-                title: "Branch",
-                width: Constraint::Length(20),
-            },
-            TableColumn {
-                title: "Session",
-                width: Constraint::Length(14),
-            },
-        ];
-
         self.select.render_table(
             frame,
             table_area,
@@ -502,33 +705,19 @@ impl DeleteFlow {
                 title: if filter_focused {
                     Line::from("Choose worktree to delete (/ to focus)")
                 } else {
-                    focus_line("Choose worktree to delete")
+                    focus_line(theme, "Choose worktree to delete")
                 },
                 empty_message: "No matching worktrees.",
-                columns: &columns,
-                header_style: theme::table_header(Color::Red),
-                highlight_style: theme::table_highlight(Color::Red),
-            },
-            |row| {
-                let state = if row.session_running {
-                    "running"
-                } else {
-                    "not running"
-                };
-                vec![
-                    row.name.clone(),
-                    row.created_at.clone(),
-                    row.branch.clone(),
-                    state.to_string(),
-                ]
+                header_style: theme.table_header(Accent::Delete),
+                highlight_style: theme.table_highlight(Accent::Delete),
             },
         );
 
-        let keys = key_hint_paragraph(key_text).block(theme::key_block());
-        frame.render_widget(keys, footer);
+        self.select.render_keys(frame, footer, key_text);
+        self.select.render_details(frame, theme);
     }
 
-    fn render_options(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_options(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let target = self.target_name.as_deref().unwrap_or("UNCONFIRMED");
 
         let rows = [
@@ -554,7 +743,7 @@ impl DeleteFlow {
             ),
         ];
 
-        let mut lines = vec![label_value_line("Worktree", target), Line::from("")];
+        let mut lines = vec![label_value_line(theme, "Worktree", target), Line::from("")];
         for (index, (label, value)) in rows.iter().enumerate() {
             let marker = if self.option_selected == index {
                 ">>"
@@ -563,7 +752,7 @@ impl DeleteFlow {
             };
             let row_line = format!("{marker} {label}: {value}");
             if self.option_selected == index {
-                lines.push(focus_line(row_line));
+                lines.push(focus_line(theme, row_line));
             } else {
                 lines.push(Line::from(row_line));
             }
@@ -571,9 +760,10 @@ impl DeleteFlow {
 
         render_modal(
             frame,
+            theme,
             ModalSpec {
                 title: "Choose delete options for this worktree",
-                title_style: Some(theme::focus_prompt()),
+                title_style: Some(theme.focus_prompt()),
                 body: Text::from(lines),
                 key_hint: Some(
                     "Space: toggle selected option    Up/Down or j/k: move option    Enter: continue    Esc: back",
@@ -584,21 +774,30 @@ impl DeleteFlow {
         );
     }
 
-    fn render_confirm(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_confirm(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let target = self.target_name.as_deref().unwrap_or("UNCONFIRMED");
         let text = Text::from(vec![
-            label_value_line("Worktree", target),
-            label_value_line("Kill tmux session", yes_no(self.options.kill_tmux_session)),
-            label_value_line("Delete branch", yes_no(self.options.delete_branch)),
+            label_value_line(theme, "Worktree", target),
+            label_value_line(
+                theme,
+                "Kill tmux session",
+                yes_no(self.options.kill_tmux_session),
+            ),
+            label_value_line(theme, "Delete branch", yes_no(self.options.delete_branch)),
             Line::from(""),
-            highlighted_label_value_line("Current Selection", self.confirm_choice.selected_label()),
+            highlighted_label_value_line(
+                theme,
+                "Current Selection",
+                self.confirm_choice.selected_label(),
+            ),
         ]);
 
         render_modal(
             frame,
+            theme,
             ModalSpec {
                 title: "Confirm worktree deletion",
-                title_style: Some(theme::focus_prompt()),
+                title_style: Some(theme.focus_prompt()),
                 body: text,
                 key_hint: Some("Space: toggle    Enter: confirm    Esc: back"),
                 width_pct: 78,
@@ -607,22 +806,80 @@ impl DeleteFlow {
         );
     }
 
-    fn render_worktree_force_prompt(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_foreign_prompt(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        let error = self.error_message.as_deref().unwrap_or("unknown error");
+        let text = Text::from(vec![
+            Line::from(error.to_string()),
+            Line::from(""),
+            Line::from("Its owner may still be using it."),
+            Line::from(""),
+            highlighted_label_value_line(
+                theme,
+                "Current Selection",
+                self.foreign_choice.selected_label(),
+            ),
+        ]);
+        render_modal(
+            frame,
+            theme,
+            ModalSpec {
+                title: "Someone else created this worktree. Delete it anyway?",
+                title_style: Some(theme.error_prompt()),
+                body: text,
+                key_hint: Some("Space: toggle    Enter: continue    Esc: back"),
+                width_pct: 85,
+                height_pct: 55,
+            },
+        );
+    }
+
+    fn render_cwd_inside_prompt(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        let error = self.error_message.as_deref().unwrap_or("unknown error");
+        let text = Text::from(vec![
+            Line::from(error.to_string()),
+            Line::from(""),
+            Line::from(
+                "seshmux and the shell that started it will be left in a directory that no longer exists.",
+            ),
+            Line::from(""),
+            highlighted_label_value_line(
+                theme,
+                "Current Selection",
+                self.cwd_inside_choice.selected_label(),
+            ),
+        ]);
+        render_modal(
+            frame,
+            theme,
+            ModalSpec {
+                title: "You are inside this worktree. Delete it anyway?",
+                title_style: Some(theme.error_prompt()),
+                body: text,
+                key_hint: Some("Space: toggle    Enter: continue    Esc: back"),
+                width_pct: 85,
+                height_pct: 55,
+            },
+        );
+    }
+
+    fn render_worktree_force_prompt(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let error = self.error_message.as_deref().unwrap_or("unknown error");
         let text = Text::from(vec![
             Line::from("Safe delete error:"),
             Line::from(error.to_string()),
             Line::from(""),
             highlighted_label_value_line(
+                theme,
                 "Current Selection",
                 self.worktree_force_choice.selected_label(),
             ),
         ]);
         render_modal(
             frame,
+            theme,
             ModalSpec {
                 title: "Safe deletion failed. Force delete this worktree?",
-                title_style: Some(theme::focus_prompt()),
+                title_style: Some(theme.focus_prompt()),
                 body: text,
                 key_hint: Some("Space: toggle    Enter: continue    Esc: back"),
                 width_pct: 85,
@@ -631,7 +888,7 @@ impl DeleteFlow {
         );
     }
 
-    fn render_branch_force_prompt(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_branch_force_prompt(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let result = self.pending_result.as_ref();
         let branch_name = result
             .map(|value| value.branch_name.as_str())
@@ -641,20 +898,22 @@ impl DeleteFlow {
             .unwrap_or("unknown error");
 
         let text = Text::from(vec![
-            label_value_line("Branch", branch_name),
+            label_value_line(theme, "Branch", branch_name),
             Line::from("Safe delete error:"),
             Line::from(error.to_string()),
             Line::from(""),
             highlighted_label_value_line(
+                theme,
                 "Current Selection",
                 self.branch_force_choice.selected_label(),
             ),
         ]);
         render_modal(
             frame,
+            theme,
             ModalSpec {
                 title: "Safe branch delete failed. Force delete the branch?",
-                title_style: Some(theme::focus_prompt()),
+                title_style: Some(theme.focus_prompt()),
                 body: text,
                 key_hint: Some("Space: toggle    Enter: continue    Esc: keep branch"),
                 width_pct: 85,
@@ -663,21 +922,29 @@ impl DeleteFlow {
         );
     }
 
-    fn render_success(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_success(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let text = self
             .success_message
             .as_deref()
             .unwrap_or("Delete completed");
-        render_success_modal(frame, text, 75, 35, result_footer(frame.area().width));
+        render_success_modal(
+            frame,
+            theme,
+            text,
+            75,
+            35,
+            result_footer(frame.area().width),
+        );
     }
 
-    fn render_notice(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_notice(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let text = self
             .success_message
             .as_deref()
             .unwrap_or("No changes were made");
         render_notice_modal(
             frame,
+            theme,
             "Delete notice",
             text,
             70,
@@ -686,24 +953,51 @@ impl DeleteFlow {
         );
     }
 
-    fn render_error(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_error(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let text = self.error_message.as_deref().unwrap_or("Delete failed");
-        render_error_modal(frame, text, 80, 40, "Enter/Esc: back");
+        render_error_modal(frame, theme, text, 80, 40, "Enter/Esc: back");
     }
 }
 
+fn with_delete_notes(summary: String, result: &DeleteResult) -> String {
+    let mut lines = vec![summary];
+    if !result.purged.is_empty() {
+        lines.push(format!(
+            "Purged from the trash: {}.",
+            result.purged.join(", ")
+        ));
+    }
+    if let Some(owner) = &result.foreign_owner {
+        lines.push(format!("Warning: this worktree was created by {owner}."));
+    }
+    if !result.leaked_resources.is_empty() {
+        lines.push("Possible leaked resources:".to_string());
+        lines.extend(
+            result
+                .leaked_resources
+                .iter()
+                .map(|leaked| format!("- {leaked}")),
+        );
+    }
+    lines.join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use std::cell::RefCell;
+    use std::collections::BTreeSet;
     use std::path::{Path, PathBuf};
 
     use anyhow::{Result, anyhow};
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
-    use seshmux_app::{DeleteError, DeleteRequest, DeleteResult, ListResult, WorktreeRow};
+    use seshmux_app::{
+        DeleteError, DeleteRequest, DeleteResult, ListResult, WorktreeHead, WorktreeRow,
+    };
 
     use super::{DeleteFlow, DeleteFlowOps, FlowSignal, Step};
+    use crate::theme::Theme;
 
     struct FakeOps {
         rows: Vec<WorktreeRow>,
@@ -722,13 +1016,16 @@ mod tests {
             branch_force_fails: bool,
         ) -> Self {
             Self {
-                rows: vec![WorktreeRow {
-                    name: "w1".to_string(),
-                    path: PathBuf::from("/tmp/repo/worktrees/w1"),
-                    created_at: "2026-02-25T10:00:00Z".to_string(),
-                    branch: "w1".to_string(),
-                    session_name: "repo/w1".to_string(),
-                    session_running,
+                rows: vec![{
+                    let mut row = WorktreeRow::new(
+                        "w1",
+                        "/tmp/repo/worktrees/w1",
+                        "2026-02-25T10:00:00Z",
+                        WorktreeHead::Branch("w1".to_string()),
+                        "repo/w1",
+                    );
+                    row.session_running = session_running;
+                    row
                 }],
                 delete_calls: RefCell::new(Vec::new()),
                 force_branch_calls: RefCell::new(Vec::new()),
@@ -740,16 +1037,26 @@ mod tests {
     }
 
     impl DeleteFlowOps for FakeOps {
+        fn refresh_sessions(&self) -> Result<BTreeSet<String>> {
+            Ok(BTreeSet::new())
+        }
+
         fn list_worktrees(&self, _cwd: &Path) -> Result<ListResult> {
-            Ok(ListResult {
-                repo_root: PathBuf::from("/tmp/repo"),
-                rows: self.rows.clone(),
-            })
+            Ok(ListResult::new("/tmp/repo", self.rows.clone()))
         }
 
         fn delete_worktree(&self, request: DeleteRequest) -> Result<DeleteResult> {
             self.delete_calls.borrow_mut().push(request.clone());
 
+            let worktree_path = PathBuf::from("/tmp/repo/worktrees/w1");
+            if !request.allow_cwd_inside && request.cwd.starts_with(&worktree_path) {
+                return Err(DeleteError::CwdInsideWorktree {
+                    name: request.worktree_name,
+                    path: worktree_path,
+                }
+                .into());
+            }
+
             if !request.force_worktree && *self.worktree_delete_fail_once.borrow() {
                 *self.worktree_delete_fail_once.borrow_mut() = false;
                 return Err(DeleteError::WorktreeDeleteFailed {
@@ -766,14 +1073,17 @@ mod tests {
             };
             let branch_deleted = request.delete_branch && branch_delete_error.is_none();
 
-            Ok(DeleteResult {
-                worktree_name: request.worktree_name,
-                repo_root: PathBuf::from("/tmp/repo"),
-                worktree_path: PathBuf::from("/tmp/repo/worktrees/w1"),
-                session_name: "repo/w1".to_string(),
-                branch_name: "w1".to_string(),
-                branch_deleted,
-                branch_delete_error,
+            Ok({
+                let mut result = DeleteResult::new(
+                    request.worktree_name,
+                    "/tmp/repo",
+                    "/tmp/repo/worktrees/w1",
+                    "repo/w1",
+                    "w1",
+                );
+                result.branch_deleted = branch_deleted;
+                result.branch_delete_error = branch_delete_error;
+                result
             })
         }
 
@@ -795,10 +1105,11 @@ mod tests {
     }
 
     fn render_output(flow: &DeleteFlow, width: u16, height: u16) -> String {
+        let theme = &Theme::default();
         let backend = TestBackend::new(width, height);
         let mut terminal = Terminal::new(backend).expect("terminal");
         terminal
-            .draw(|frame| flow.render(frame))
+            .draw(|frame| flow.render(frame, theme))
             .expect("render delete flow");
         format!("{}", terminal.backend())
     }
@@ -817,6 +1128,20 @@ mod tests {
         assert_eq!(signal, FlowSignal::Exit(super::UiExit::BackAtRoot));
     }
 
+    #[test]
+    fn choose_named_opens_the_options_for_that_worktree() {
+        let ops = FakeOps::new(false, false, false, false);
+        let mut flow = DeleteFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+
+        assert!(flow.choose_named("gone").is_err());
+        assert_eq!(flow.step, Step::SelectWorktree);
+
+        flow.choose_named("w1").expect("choose");
+        assert_eq!(flow.step, Step::Options);
+        assert_eq!(flow.target_name.as_deref(), Some("w1"));
+        assert!(ops.delete_calls.borrow().is_empty());
+    }
+
     #[test]
     fn options_are_collected_and_passed_to_delete_request() {
         let ops = FakeOps::new(true, false, false, false);
@@ -870,6 +1195,34 @@ mod tests {
         assert!(calls[1].force_worktree);
     }
 
+    #[test]
+    fn deleting_the_worktree_cwd_is_inside_needs_a_second_confirmation() {
+        let ops = FakeOps::new(false, false, false, false);
+        let mut flow =
+            DeleteFlow::new(&ops, Path::new("/tmp/repo/worktrees/w1/src")).expect("flow");
+
+        apply_keys(
+            &mut flow,
+            &ops,
+            &[
+                KeyCode::Enter,
+                KeyCode::Enter,
+                KeyCode::Char(' '),
+                KeyCode::Enter,
+            ],
+        );
+        assert_eq!(flow.step, Step::CwdInsidePrompt);
+        assert!(render_output(&flow, 120, 30).contains("You are inside this worktree"));
+
+        apply_keys(&mut flow, &ops, &[KeyCode::Char(' '), KeyCode::Enter]);
+
+        assert_eq!(flow.step, Step::Success);
+        let calls = ops.delete_calls.borrow();
+        assert_eq!(calls.len(), 2);
+        assert!(!calls[0].allow_cwd_inside);
+        assert!(calls[1].allow_cwd_inside);
+    }
+
     #[test]
     fn branch_delete_failure_prompts_force_option() {
         let ops = FakeOps::new(false, false, true, false);
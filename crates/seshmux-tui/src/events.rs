@@ -0,0 +1,295 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use crossterm::event::{self, Event};
+
+use crate::input_script::InputScript;
+
+const READER_POLL: Duration = Duration::from_millis(100);
+
+#[derive(Debug)]
+pub(crate) enum LoopEvent {
+    Terminal(Event),
+    Tick,
+}
+
+enum Message {
+    Terminal(Event),
+    Tick,
+    ReadFailed(String),
+}
+
+#[derive(Debug, Default)]
+struct GateState {
+    paused: bool,
+    parked: bool,
+    stopped: bool,
+}
+
+#[derive(Debug, Default)]
+struct ReaderGate {
+    state: Mutex<GateState>,
+    changed: Condvar,
+}
+
+impl ReaderGate {
+    fn update(&self, edit: impl FnOnce(&mut GateState)) {
+        let mut state = self.state.lock().unwrap_or_else(|error| error.into_inner());
+        edit(&mut state);
+        self.changed.notify_all();
+    }
+
+    fn wait_until_running(&self) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|error| error.into_inner());
+        while state.paused && !state.stopped {
+            state.parked = true;
+            self.changed.notify_all();
+            state = self
+                .changed
+                .wait(state)
+                .unwrap_or_else(|error| error.into_inner());
+        }
+        state.parked = false;
+        !state.stopped
+    }
+
+    fn wait_until_parked(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|error| error.into_inner());
+        while !state.parked && !state.stopped {
+            state = self
+                .changed
+                .wait(state)
+                .unwrap_or_else(|error| error.into_inner());
+        }
+    }
+}
+
+pub(crate) struct EventPump {
+    receiver: Receiver<Message>,
+    gate: Arc<ReaderGate>,
+    ticking: Arc<AtomicBool>,
+    // At most one tick waits in the channel, so a slow frame never leaves a backlog of them.
+    tick_queued: Arc<AtomicBool>,
+    stopped: Arc<AtomicBool>,
+    reader: Option<JoinHandle<()>>,
+}
+
+impl EventPump {
+    pub(crate) fn start(tick_rate: Duration, input: &InputScript) -> Self {
+        let mut input = input.reader();
+        Self::start_with(tick_rate, move || {
+            input.read(
+                &mut |timeout| {
+                    if event::poll(timeout)? {
+                        return event::read().map(Some);
+                    }
+                    Ok(None)
+                },
+                READER_POLL,
+            )
+        })
+    }
+
+    fn start_with(
+        tick_rate: Duration,
+        mut read: impl FnMut() -> std::io::Result<Option<Event>> + Send + 'static,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let gate = Arc::new(ReaderGate::default());
+        let ticking = Arc::new(AtomicBool::new(false));
+        let tick_queued = Arc::new(AtomicBool::new(false));
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let reader = {
+            let sender = sender.clone();
+            let gate = Arc::clone(&gate);
+            thread::spawn(move || {
+                while gate.wait_until_running() {
+                    let message = match read() {
+                        Ok(Some(event)) => Message::Terminal(event),
+                        Ok(None) => continue,
+                        Err(error) => Message::ReadFailed(error.to_string()),
+                    };
+                    let failed = matches!(message, Message::ReadFailed(_));
+                    if sender.send(message).is_err() || failed {
+                        break;
+                    }
+                }
+                // A reader that gave up must not leave `pause` waiting for it to park.
+                gate.update(|state| state.stopped = true);
+            })
+        };
+        spawn_ticker(
+            tick_rate,
+            sender,
+            Arc::clone(&ticking),
+            Arc::clone(&tick_queued),
+            Arc::clone(&stopped),
+        );
+
+        Self {
+            receiver,
+            gate,
+            ticking,
+            tick_queued,
+            stopped,
+            reader: Some(reader),
+        }
+    }
+
+    pub(crate) fn set_ticking(&self, ticking: bool) {
+        self.ticking.store(ticking, Ordering::Relaxed);
+    }
+
+    pub(crate) fn next(&self, timeout: Duration) -> Result<Option<LoopEvent>> {
+        let message = match self.receiver.recv_timeout(timeout) {
+            Ok(message) => message,
+            Err(RecvTimeoutError::Timeout) => return Ok(None),
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(anyhow!("terminal event reader stopped"));
+            }
+        };
+
+        match message {
+            Message::Terminal(event) => Ok(Some(LoopEvent::Terminal(event))),
+            Message::ReadFailed(error) => Err(anyhow!("failed to read terminal event: {error}")),
+            Message::Tick => {
+                self.tick_queued.store(false, Ordering::Relaxed);
+                Ok(Some(LoopEvent::Tick))
+            }
+        }
+    }
+
+    pub(crate) fn pause(&self) -> PauseGuard<'_> {
+        self.gate.update(|state| state.paused = true);
+        self.gate.wait_until_parked();
+        PauseGuard { pump: self }
+    }
+
+    // Joining keeps a last poll from racing whatever takes over the terminal next.
+    pub(crate) fn stop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        self.gate.update(|state| state.stopped = true);
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+    }
+}
+
+impl Drop for EventPump {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+pub(crate) struct PauseGuard<'a> {
+    pump: &'a EventPump,
+}
+
+impl Drop for PauseGuard<'_> {
+    fn drop(&mut self) {
+        self.pump.gate.update(|state| state.paused = false);
+    }
+}
+
+fn spawn_ticker(
+    tick_rate: Duration,
+    sender: Sender<Message>,
+    ticking: Arc<AtomicBool>,
+    tick_queued: Arc<AtomicBool>,
+    stopped: Arc<AtomicBool>,
+) {
+    thread::spawn(move || {
+        loop {
+            thread::sleep(tick_rate);
+            if stopped.load(Ordering::Relaxed) {
+                return;
+            }
+            if !ticking.load(Ordering::Relaxed) || tick_queued.swap(true, Ordering::Relaxed) {
+                continue;
+            }
+            if sender.send(Message::Tick).is_err() {
+                return;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{EventPump, LoopEvent};
+
+    const WAIT: Duration = Duration::from_secs(2);
+
+    fn key_event() -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn ticks_only_flow_while_requested() {
+        let pump = EventPump::start_with(Duration::from_millis(5), || {
+            thread::sleep(Duration::from_millis(5));
+            Ok(None)
+        });
+
+        let idle = pump.next(Duration::from_millis(60)).expect("wait");
+        assert!(idle.is_none());
+
+        pump.set_ticking(true);
+        let ticked = pump.next(WAIT).expect("tick");
+        assert!(matches!(ticked, Some(LoopEvent::Tick)));
+    }
+
+    #[test]
+    fn paused_reader_stops_reading_the_terminal() {
+        let reads = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&reads);
+        let pump = EventPump::start_with(Duration::from_secs(60), move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(2));
+            Ok(Some(key_event()))
+        });
+
+        let first = pump.next(WAIT).expect("event");
+        assert!(matches!(first, Some(LoopEvent::Terminal(Event::Key(_)))));
+
+        {
+            let _paused = pump.pause();
+            let before = reads.load(Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(30));
+            assert_eq!(reads.load(Ordering::SeqCst), before);
+        }
+
+        let before = reads.load(Ordering::SeqCst);
+        let deadline = std::time::Instant::now() + WAIT;
+        while reads.load(Ordering::SeqCst) == before {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "reader did not resume"
+            );
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn read_failures_surface_and_do_not_block_pause() {
+        let pump = EventPump::start_with(Duration::from_secs(60), || {
+            Err(std::io::Error::other("tty closed"))
+        });
+
+        let error = pump.next(WAIT).expect_err("read failure");
+        assert!(error.to_string().contains("tty closed"));
+        drop(pump.pause());
+    }
+}
@@ -0,0 +1,601 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{List, ListItem, ListState, Paragraph};
+use seshmux_app::{
+    App, AttachRequest, AttachResult, OpenLocation, OpenRequest, SearchRequest, SearchResult,
+};
+use tui_input::Input;
+use tui_input::backend::crossterm::EventHandler;
+
+use crate::UiExit;
+use crate::attach_flow::{AttachRetry, attach_retry_actions};
+use crate::keymap;
+use crate::theme::{self, Accent, Theme};
+use crate::ui::error_actions::{ErrorActions, ErrorActionsEvent, render_error_with_actions};
+use crate::ui::help::{ERROR_KEYS, HelpProvider, RESULT_KEYS, ScreenHelp};
+use crate::ui::modal::render_success_modal;
+use crate::ui::paste::paste_into;
+use crate::ui::text::{
+    compact_hint, focus_line, input_viewport, key_hint_height, key_hint_paragraph, result_footer,
+    yes_no,
+};
+
+pub(crate) trait FindFlowOps {
+    fn search_worktrees(&self, request: SearchRequest) -> Result<SearchResult>;
+    fn attach_worktree(&self, request: AttachRequest) -> Result<AttachResult>;
+}
+
+impl<'a> FindFlowOps for App<'a> {
+    fn search_worktrees(&self, request: SearchRequest) -> Result<SearchResult> {
+        Ok(App::search_worktrees(self, request)?)
+    }
+
+    fn attach_worktree(&self, request: AttachRequest) -> Result<AttachResult> {
+        Ok(self.attach(request)?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Query,
+    Results,
+    Success,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FlowSignal {
+    Continue,
+    Open(OpenRequest),
+    Exit(UiExit),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResultLine {
+    Header(usize),
+    Match(usize, usize),
+}
+
+#[derive(Debug)]
+struct FindFlow {
+    cwd: PathBuf,
+    step: Step,
+    query: Input,
+    result: Option<SearchResult>,
+    lines: Vec<ResultLine>,
+    selected: usize,
+    success_message: Option<String>,
+    error_message: Option<String>,
+    error_actions: ErrorActions<AttachRetry>,
+}
+
+pub(crate) struct FindScreen {
+    flow: FindFlow,
+    pending_open: Option<OpenRequest>,
+}
+
+impl FindScreen {
+    pub(crate) fn new(cwd: &Path) -> Self {
+        Self {
+            flow: FindFlow::new(cwd),
+            pending_open: None,
+        }
+    }
+
+    pub(crate) fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        self.flow.render(frame, theme);
+    }
+
+    pub(crate) fn on_paste(&mut self, text: &str) {
+        if self.flow.step == Step::Query {
+            paste_into(&mut self.flow.query, text);
+        }
+    }
+
+    pub(crate) fn on_key(&mut self, key: KeyEvent, app: &App<'_>) -> Result<Option<UiExit>> {
+        match self.flow.on_key(key, app)? {
+            FlowSignal::Continue => Ok(None),
+            FlowSignal::Open(request) => {
+                self.pending_open = Some(request);
+                Ok(None)
+            }
+            FlowSignal::Exit(exit) => Ok(Some(exit)),
+        }
+    }
+
+    pub(crate) fn take_pending_open(&mut self) -> Option<OpenRequest> {
+        self.pending_open.take()
+    }
+}
+
+impl HelpProvider for FindScreen {
+    fn help(&self) -> ScreenHelp {
+        match self.flow.step {
+            Step::Query => ScreenHelp::new(
+                "Find",
+                "Search the tracked files of every worktree with git grep.",
+            )
+            .keys(&[
+                ("Type", "edit the search"),
+                ("Backspace", "delete a character"),
+                ("Enter", "search"),
+                ("Esc", "back"),
+            ]),
+            Step::Results => {
+                ScreenHelp::new("Find results", "Matches grouped by worktree.").keys(&[
+                    ("Up/Down or j/k", "move between matches"),
+                    ("Enter", "attach to the match's worktree"),
+                    ("o", "open the match in your editor"),
+                    ("/ or Esc", "edit the search"),
+                ])
+            }
+            Step::Success => {
+                ScreenHelp::new("Attached", "The tmux session is ready.").keys(RESULT_KEYS)
+            }
+            Step::Error => ScreenHelp::new("Find failed", "The worktree could not be attached.")
+                .keys(ERROR_KEYS),
+        }
+    }
+
+    fn typing(&self) -> bool {
+        self.flow.step == Step::Query
+    }
+}
+
+impl FindFlow {
+    fn new(cwd: &Path) -> Self {
+        Self {
+            cwd: cwd.to_path_buf(),
+            step: Step::Query,
+            query: Input::default(),
+            result: None,
+            lines: Vec::new(),
+            selected: 0,
+            success_message: None,
+            error_message: None,
+            error_actions: ErrorActions::none(),
+        }
+    }
+
+    fn on_key(&mut self, key: KeyEvent, ops: &dyn FindFlowOps) -> Result<FlowSignal> {
+        match self.step {
+            Step::Query => self.on_key_query(key, ops),
+            Step::Results => self.on_key_results(key, ops),
+            Step::Success => Ok(self.on_key_success(key)),
+            Step::Error => {
+                match self.error_actions.on_key(key) {
+                    ErrorActionsEvent::Continue => {}
+                    ErrorActionsEvent::Back => {
+                        self.error_message = None;
+                        self.step = Step::Results;
+                    }
+                    ErrorActionsEvent::Choose(retry) => {
+                        self.attach(retry.worktree_name, retry.allow_foreign, ops)
+                    }
+                }
+                Ok(FlowSignal::Continue)
+            }
+        }
+    }
+
+    fn on_key_query(&mut self, key: KeyEvent, ops: &dyn FindFlowOps) -> Result<FlowSignal> {
+        if keymap::is_back(key) {
+            return Ok(FlowSignal::Exit(UiExit::BackAtRoot));
+        }
+
+        if keymap::is_confirm(key) {
+            if self.query.value().trim().is_empty() {
+                return Ok(FlowSignal::Continue);
+            }
+            let result = ops.search_worktrees(SearchRequest {
+                cwd: self.cwd.clone(),
+                query: self.query.value().trim().to_string(),
+            })?;
+            self.set_result(result);
+            self.step = Step::Results;
+            return Ok(FlowSignal::Continue);
+        }
+
+        self.query.handle_event(&Event::Key(key));
+        Ok(FlowSignal::Continue)
+    }
+
+    fn on_key_results(&mut self, key: KeyEvent, ops: &dyn FindFlowOps) -> Result<FlowSignal> {
+        if keymap::is_back(key) || key.code == KeyCode::Char('/') {
+            self.step = Step::Query;
+            return Ok(FlowSignal::Continue);
+        }
+
+        if keymap::is_up(key) {
+            self.move_selection(-1);
+        } else if keymap::is_down(key) {
+            self.move_selection(1);
+        } else if keymap::is_confirm(key) {
+            let Some((worktree_name, _)) = self.selected_match() else {
+                return Ok(FlowSignal::Continue);
+            };
+            self.attach(worktree_name, false, ops);
+        } else if key.code == KeyCode::Char('o')
+            && let Some((worktree_name, location)) = self.selected_match()
+        {
+            return Ok(FlowSignal::Open(OpenRequest {
+                cwd: self.cwd.clone(),
+                worktree_name,
+                location: Some(location),
+            }));
+        }
+
+        Ok(FlowSignal::Continue)
+    }
+
+    fn attach(&mut self, worktree_name: String, allow_foreign: bool, ops: &dyn FindFlowOps) {
+        let mut request = AttachRequest::new(self.cwd.clone(), worktree_name);
+        request.create_if_missing = true;
+        request.connect = true;
+        request.allow_foreign = allow_foreign;
+        match ops.attach_worktree(request) {
+            Ok(result) => {
+                self.success_message = Some(success_message_for(&result));
+                self.step = Step::Success;
+            }
+            Err(error) => {
+                self.error_message = Some(format!("{error:#}"));
+                self.error_actions = attach_retry_actions(&error);
+                self.step = Step::Error;
+            }
+        }
+    }
+
+    fn on_key_success(&mut self, key: KeyEvent) -> FlowSignal {
+        if keymap::is_quit(key) {
+            return FlowSignal::Exit(UiExit::Completed);
+        }
+
+        if keymap::is_back(key) || keymap::is_confirm(key) {
+            return FlowSignal::Exit(UiExit::BackAtRoot);
+        }
+
+        FlowSignal::Continue
+    }
+
+    fn set_result(&mut self, result: SearchResult) {
+        self.lines = result
+            .groups
+            .iter()
+            .enumerate()
+            .flat_map(|(group, found)| {
+                std::iter::once(ResultLine::Header(group)).chain(
+                    (0..found.matches.len()).map(move |index| ResultLine::Match(group, index)),
+                )
+            })
+            .collect();
+        self.selected = self
+            .lines
+            .iter()
+            .position(|line| matches!(line, ResultLine::Match(..)))
+            .unwrap_or(0);
+        self.result = Some(result);
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let mut index = self.selected as isize + delta;
+        while index >= 0 && (index as usize) < self.lines.len() {
+            if matches!(self.lines[index as usize], ResultLine::Match(..)) {
+                self.selected = index as usize;
+                return;
+            }
+            index += delta;
+        }
+    }
+
+    fn selected_match(&self) -> Option<(String, OpenLocation)> {
+        let ResultLine::Match(group, index) = *self.lines.get(self.selected)? else {
+            return None;
+        };
+        let group = &self.result.as_ref()?.groups[group];
+        let found = &group.matches[index];
+        Some((
+            group.worktree_name.clone(),
+            OpenLocation {
+                path: found.path.clone(),
+                line: found.line,
+            },
+        ))
+    }
+
+    fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        let area = frame.area();
+        let key_text = if self.step == Step::Query {
+            compact_hint(
+                area.width,
+                "Type: search text    Enter: search all worktrees    Esc: back",
+                "Type    Enter: search    Esc: back",
+                "Type | Enter search | Esc back",
+            )
+        } else {
+            compact_hint(
+                area.width,
+                "Up/Down or j/k: move    Enter: attach    o: open match in editor    /: edit search    Esc: back",
+                "j/k: move    Enter: attach    o: open    /: search    Esc: back",
+                "j/k move | Enter attach | o open | Esc back",
+            )
+        };
+        let footer_height = key_hint_height(area.width, key_text);
+        let [query_area, body, footer] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(6),
+                Constraint::Length(footer_height),
+            ])
+            .areas(area);
+
+        let query_focused = self.step == Step::Query;
+        let query_title = if query_focused {
+            focus_line(theme, "Search worktrees")
+        } else {
+            Line::from("Search worktrees (/ to edit)")
+        };
+        let width = query_area.width.saturating_sub(2) as usize;
+        let scroll = self.query.visual_scroll(width);
+        let input = Paragraph::new(input_viewport(self.query.value(), scroll, width))
+            .block(theme::chrome(query_title));
+        frame.render_widget(input, query_area);
+        if query_focused && width > 0 {
+            let visual = self.query.visual_cursor();
+            let relative = visual.saturating_sub(scroll).min(width.saturating_sub(1));
+            frame.set_cursor_position((query_area.x + 1 + relative as u16, query_area.y + 1));
+        }
+
+        self.render_results(frame, theme, body, !query_focused);
+
+        let keys = key_hint_paragraph(key_text).block(theme::key_block());
+        frame.render_widget(keys, footer);
+
+        match self.step {
+            Step::Query | Step::Results => {}
+            Step::Success => {
+                let message = self.success_message.as_deref().unwrap_or("Attached");
+                render_success_modal(
+                    frame,
+                    theme,
+                    message,
+                    70,
+                    40,
+                    result_footer(frame.area().width),
+                );
+            }
+            Step::Error => {
+                let message = self.error_message.as_deref().unwrap_or("Attach failed");
+                render_error_with_actions(
+                    frame,
+                    theme,
+                    message,
+                    &self.error_actions,
+                    "back",
+                    80,
+                    40,
+                );
+            }
+        }
+    }
+
+    fn render_results(
+        &self,
+        frame: &mut ratatui::Frame<'_>,
+        theme: &Theme,
+        area: ratatui::layout::Rect,
+        focused: bool,
+    ) {
+        let title = if focused {
+            focus_line(theme, "Matches")
+        } else {
+            Line::from("Matches")
+        };
+        let block = theme::chrome(title);
+
+        let Some(result) = &self.result else {
+            let hint = Paragraph::new(
+                "Searches tracked and untracked files in every registered worktree with git grep.",
+            )
+            .block(block);
+            frame.render_widget(hint, area);
+            return;
+        };
+        if result.groups.is_empty() {
+            let empty =
+                Paragraph::new(format!("No worktree contains '{}'.", result.query)).block(block);
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let items: Vec<ListItem<'_>> = self
+            .lines
+            .iter()
+            .map(|line| match *line {
+                ResultLine::Header(group) => {
+                    let group = &result.groups[group];
+                    let mut summary = format!("  {} match(es)", group.matches.len());
+                    if group.truncated {
+                        summary.push_str(", truncated");
+                    }
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            group.worktree_name.clone(),
+                            theme.table_header(Accent::Browse),
+                        ),
+                        Span::styled(summary, theme.secondary_text()),
+                    ]))
+                }
+                ResultLine::Match(group, index) => {
+                    let found = &result.groups[group].matches[index];
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("  {}:{}  ", found.path.display(), found.line),
+                            theme.secondary_text(),
+                        ),
+                        Span::raw(found.text.trim().to_string()),
+                    ]))
+                }
+            })
+            .collect();
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(theme.table_highlight(Accent::Browse));
+        let mut state = ListState::default();
+        if focused {
+            state.select(Some(self.selected));
+        }
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+}
+
+fn success_message_for(result: &AttachResult) -> String {
+    format!(
+        "Attached worktree: {}\ntmux session name: {}\nCreated tmux session now: {}",
+        result.worktree_name,
+        result.session_name,
+        yes_no(result.created_session)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::path::{Path, PathBuf};
+
+    use anyhow::Result;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use seshmux_app::{
+        AttachRequest, AttachResult, OpenLocation, SearchGroup, SearchRequest, SearchResult,
+    };
+    use seshmux_core::git::GrepMatch;
+
+    use super::{FindFlow, FindFlowOps, FlowSignal, Step};
+    use crate::theme::Theme;
+
+    struct FakeOps {
+        groups: Vec<SearchGroup>,
+        search_calls: RefCell<Vec<SearchRequest>>,
+        attach_calls: RefCell<Vec<AttachRequest>>,
+    }
+
+    impl FakeOps {
+        fn new() -> Self {
+            let group = |name: &str, paths: &[&str]| SearchGroup {
+                worktree_name: name.to_string(),
+                worktree_path: PathBuf::from(format!("/tmp/repo/worktrees/{name}")),
+                matches: paths
+                    .iter()
+                    .enumerate()
+                    .map(|(index, path)| GrepMatch {
+                        path: PathBuf::from(path),
+                        line: index + 1,
+                        text: "let port = 8080;".to_string(),
+                    })
+                    .collect(),
+                truncated: false,
+            };
+
+            Self {
+                groups: vec![
+                    group("w1", &["src/main.rs"]),
+                    group("w2", &["src/lib.rs", "README.md"]),
+                ],
+                search_calls: RefCell::new(Vec::new()),
+                attach_calls: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl FindFlowOps for FakeOps {
+        fn search_worktrees(&self, request: SearchRequest) -> Result<SearchResult> {
+            self.search_calls.borrow_mut().push(request.clone());
+            Ok(SearchResult {
+                repo_root: PathBuf::from("/tmp/repo"),
+                query: request.query,
+                groups: self.groups.clone(),
+            })
+        }
+
+        fn attach_worktree(&self, request: AttachRequest) -> Result<AttachResult> {
+            self.attach_calls.borrow_mut().push(request.clone());
+            Ok({
+                let mut result = AttachResult::new(
+                    request.worktree_name.clone(),
+                    "/tmp/repo/worktrees/w2",
+                    format!("repo/{}", request.worktree_name),
+                );
+                result.connect_command = "tmux attach-session -t repo/w2".to_string();
+                result.attach_status = Some(0);
+                result
+            })
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn search(flow: &mut FindFlow, ops: &FakeOps, query: &str) {
+        for character in query.chars() {
+            flow.on_key(key(KeyCode::Char(character)), ops)
+                .expect("type");
+        }
+        flow.on_key(key(KeyCode::Enter), ops).expect("search");
+    }
+
+    #[test]
+    fn results_skip_headers_and_open_the_selected_match() {
+        let ops = FakeOps::new();
+        let mut flow = FindFlow::new(Path::new("/tmp/repo"));
+
+        search(&mut flow, &ops, "port");
+        assert_eq!(flow.step, Step::Results);
+        assert_eq!(ops.search_calls.borrow()[0].query, "port");
+
+        flow.on_key(key(KeyCode::Down), &ops).expect("down");
+        flow.on_key(key(KeyCode::Down), &ops).expect("down");
+        let signal = flow.on_key(key(KeyCode::Char('o')), &ops).expect("open");
+
+        let FlowSignal::Open(request) = signal else {
+            panic!("expected an open request, got {signal:?}");
+        };
+        assert_eq!(request.worktree_name, "w2");
+        assert_eq!(
+            request.location,
+            Some(OpenLocation {
+                path: PathBuf::from("README.md"),
+                line: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn enter_attaches_to_the_worktree_of_the_selected_match() {
+        let theme = &Theme::default();
+        let ops = FakeOps::new();
+        let mut flow = FindFlow::new(Path::new("/tmp/repo"));
+
+        search(&mut flow, &ops, "port");
+        flow.on_key(key(KeyCode::Down), &ops).expect("down");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("attach");
+
+        assert_eq!(flow.step, Step::Success);
+        let calls = ops.attach_calls.borrow();
+        assert_eq!(calls[0].worktree_name, "w2");
+        assert!(calls[0].create_if_missing);
+
+        let backend = TestBackend::new(100, 30);
+        let mut terminal = Terminal::new(backend).expect("terminal");
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render results");
+    }
+}
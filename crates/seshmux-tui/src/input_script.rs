@@ -0,0 +1,455 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, anyhow, bail};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+const SCRIPT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ScriptFile {
+    version: u32,
+    events: Vec<ScriptEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ScriptEvent {
+    #[serde(default)]
+    delay_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    paste: Option<String>,
+}
+
+#[derive(Clone, Default)]
+pub struct InputScript {
+    replay: Arc<Mutex<Option<Replay>>>,
+    recording: Arc<Mutex<Option<Recording>>>,
+}
+
+impl InputScript {
+    pub fn replay(&self, path: &Path, speed: f64) -> Result<()> {
+        if !(speed.is_finite() && speed > 0.0) {
+            bail!("replay speed must be a positive number, got {speed}");
+        }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|error| anyhow!("failed to read input script {}: {error}", path.display()))?;
+        let script: ScriptFile = serde_json::from_str(&contents)
+            .map_err(|error| anyhow!("failed to parse input script {}: {error}", path.display()))?;
+        let events = script_events(&script)
+            .map_err(|error| anyhow!("invalid input script {}: {error:#}", path.display()))?;
+
+        *lock(&self.replay) = Some(Replay::new(events, speed));
+        Ok(())
+    }
+
+    pub fn record(&self, path: &Path) -> Result<()> {
+        // Checked up front so a long session is not recorded only to fail on exit.
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            && !parent.is_dir()
+        {
+            bail!(
+                "cannot record input to {}: {} is not a directory",
+                path.display(),
+                parent.display()
+            );
+        }
+        *lock(&self.recording) = Some(Recording {
+            path: path.to_path_buf(),
+            last: None,
+            events: Vec::new(),
+        });
+        Ok(())
+    }
+
+    pub fn finish_recording(&self) -> Result<Option<(PathBuf, usize)>> {
+        let Some(recording) = lock(&self.recording).take() else {
+            return Ok(None);
+        };
+
+        let script = ScriptFile {
+            version: SCRIPT_VERSION,
+            events: recording.events,
+        };
+        let contents =
+            serde_json::to_string_pretty(&script).context("failed to serialize input script")?;
+        std::fs::write(&recording.path, format!("{contents}\n")).with_context(|| {
+            format!("failed to write input script {}", recording.path.display())
+        })?;
+        Ok(Some((recording.path, script.events.len())))
+    }
+
+    pub(crate) fn reader(&self) -> ScriptedInput {
+        ScriptedInput {
+            replay: lock(&self.replay).take(),
+            recording: Arc::clone(&self.recording),
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn script_events(script: &ScriptFile) -> Result<VecDeque<(Duration, Event)>> {
+    if script.version != SCRIPT_VERSION {
+        bail!(
+            "unsupported version {} (expected {SCRIPT_VERSION})",
+            script.version
+        );
+    }
+
+    script
+        .events
+        .iter()
+        .enumerate()
+        .map(|(index, event)| {
+            let parsed = match (&event.key, &event.paste) {
+                (Some(key), None) => Event::Key(
+                    parse_key(key).with_context(|| format!("event {}: bad key", index + 1))?,
+                ),
+                (None, Some(text)) => Event::Paste(text.clone()),
+                _ => bail!(
+                    "event {}: needs exactly one of \"key\" or \"paste\"",
+                    index + 1
+                ),
+            };
+            Ok((Duration::from_millis(event.delay_ms), parsed))
+        })
+        .collect()
+}
+
+pub(crate) struct ScriptedInput {
+    replay: Option<Replay>,
+    recording: Arc<Mutex<Option<Recording>>>,
+}
+
+impl ScriptedInput {
+    pub(crate) fn read(
+        &mut self,
+        terminal: &mut impl FnMut(Duration) -> std::io::Result<Option<Event>>,
+        poll: Duration,
+    ) -> std::io::Result<Option<Event>> {
+        if let Some(replay) = self.replay.as_mut() {
+            match replay.read(terminal, poll)? {
+                ReplayRead::Event(event) => return Ok(Some(event)),
+                ReplayRead::Waiting => return Ok(None),
+                ReplayRead::Interrupted(event) => {
+                    self.replay = None;
+                    self.record(&event);
+                    return Ok(Some(event));
+                }
+                ReplayRead::Finished => self.replay = None,
+            }
+        }
+
+        let event = terminal(poll)?;
+        if let Some(event) = &event {
+            self.record(event);
+        }
+        Ok(event)
+    }
+
+    // Mouse and resize events depend on the terminal size, so only keys and pastes are kept.
+    fn record(&self, event: &Event) {
+        let mut recording = lock(&self.recording);
+        let Some(recording) = recording.as_mut() else {
+            return;
+        };
+
+        let (key, paste) = match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => match format_key(*key) {
+                Some(key) => (Some(key), None),
+                None => return,
+            },
+            Event::Paste(text) => (None, Some(text.clone())),
+            _ => return,
+        };
+        let now = Instant::now();
+        let delay_ms = recording
+            .last
+            .map(|last| now.duration_since(last).as_millis() as u64)
+            .unwrap_or_default();
+        recording.last = Some(now);
+        recording.events.push(ScriptEvent {
+            delay_ms,
+            key,
+            paste,
+        });
+    }
+}
+
+enum ReplayRead {
+    Event(Event),
+    Waiting,
+    Interrupted(Event),
+    Finished,
+}
+
+struct Replay {
+    events: VecDeque<(Duration, Event)>,
+    speed: f64,
+    due: Option<Instant>,
+}
+
+impl Replay {
+    fn new(events: VecDeque<(Duration, Event)>, speed: f64) -> Self {
+        Self {
+            events,
+            speed,
+            due: None,
+        }
+    }
+
+    // Waits at most `poll` so the reader can still be paused or stopped during long delays.
+    fn read(
+        &mut self,
+        terminal: &mut impl FnMut(Duration) -> std::io::Result<Option<Event>>,
+        poll: Duration,
+    ) -> std::io::Result<ReplayRead> {
+        let Some((delay, _)) = self.events.front() else {
+            return Ok(ReplayRead::Finished);
+        };
+        let speed = self.speed;
+        let due = *self
+            .due
+            .get_or_insert_with(|| Instant::now() + delay.div_f64(speed));
+
+        let remaining = due.saturating_duration_since(Instant::now());
+        if !remaining.is_zero() {
+            return match terminal(remaining.min(poll))? {
+                Some(event @ Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                    Ok(ReplayRead::Interrupted(event))
+                }
+                _ => Ok(ReplayRead::Waiting),
+            };
+        }
+
+        self.due = None;
+        let (_, event) = self.events.pop_front().expect("front was checked");
+        Ok(ReplayRead::Event(event))
+    }
+}
+
+struct Recording {
+    path: PathBuf,
+    last: Option<Instant>,
+    events: Vec<ScriptEvent>,
+}
+
+const NAMED_KEYS: [(&str, KeyCode); 15] = [
+    ("Enter", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Tab", KeyCode::Tab),
+    ("BackTab", KeyCode::BackTab),
+    ("Backspace", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+];
+
+fn parse_key(text: &str) -> Result<KeyEvent> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = text;
+    while let Some((prefix, tail)) = rest.split_once('+')
+        && !tail.is_empty()
+    {
+        modifiers |= match prefix.to_ascii_lowercase().as_str() {
+            "ctrl" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => bail!("unknown modifier '{prefix}' in '{text}'"),
+        };
+        rest = tail;
+    }
+
+    let code = if let Some((_, code)) = NAMED_KEYS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(rest))
+    {
+        *code
+    } else if rest.eq_ignore_ascii_case("Space") {
+        KeyCode::Char(' ')
+    } else if let Some(number) = rest
+        .strip_prefix(['F', 'f'])
+        .and_then(|number| number.parse::<u8>().ok())
+        .filter(|number| (1..=12).contains(number))
+    {
+        KeyCode::F(number)
+    } else {
+        let mut chars = rest.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => {
+                if ch.is_uppercase() {
+                    modifiers |= KeyModifiers::SHIFT;
+                }
+                KeyCode::Char(ch)
+            }
+            _ => bail!("unknown key '{text}'"),
+        }
+    };
+    Ok(KeyEvent::new(code, modifiers))
+}
+
+fn format_key(key: KeyEvent) -> Option<String> {
+    let mut modifiers = key.modifiers;
+    let name = match key.code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(ch) => {
+            // Shifted letters arrive uppercase already.
+            if ch.is_uppercase() {
+                modifiers.remove(KeyModifiers::SHIFT);
+            }
+            ch.to_string()
+        }
+        KeyCode::F(number) => format!("F{number}"),
+        code => NAMED_KEYS
+            .iter()
+            .find(|(_, named)| *named == code)
+            .map(|(name, _)| (*name).to_string())?,
+    };
+
+    let mut text = String::new();
+    for (flag, prefix) in [
+        (KeyModifiers::CONTROL, "Ctrl+"),
+        (KeyModifiers::ALT, "Alt+"),
+        (KeyModifiers::SHIFT, "Shift+"),
+    ] {
+        if modifiers.contains(flag) {
+            text.push_str(prefix);
+        }
+    }
+    text.push_str(&name);
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::time::{Duration, Instant};
+
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{Replay, ReplayRead, ScriptFile, format_key, parse_key, script_events};
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn keys_round_trip_through_their_script_names() {
+        for (text, expected) in [
+            ("Enter", key(KeyCode::Enter, KeyModifiers::NONE)),
+            ("j", key(KeyCode::Char('j'), KeyModifiers::NONE)),
+            ("Space", key(KeyCode::Char(' '), KeyModifiers::NONE)),
+            ("Ctrl+n", key(KeyCode::Char('n'), KeyModifiers::CONTROL)),
+            (
+                "Ctrl+Alt+Up",
+                key(KeyCode::Up, KeyModifiers::CONTROL | KeyModifiers::ALT),
+            ),
+            ("F5", key(KeyCode::F(5), KeyModifiers::NONE)),
+            ("+", key(KeyCode::Char('+'), KeyModifiers::NONE)),
+        ] {
+            let parsed = parse_key(text).expect(text);
+            assert_eq!(parsed, expected, "{text}");
+            assert_eq!(format_key(parsed).as_deref(), Some(text));
+        }
+
+        let shifted = parse_key("S").expect("S");
+        assert_eq!(shifted, key(KeyCode::Char('S'), KeyModifiers::SHIFT));
+        assert_eq!(format_key(shifted).as_deref(), Some("S"));
+        assert!(parse_key("Hyper+x").is_err());
+        assert!(parse_key("Launch").is_err());
+    }
+
+    #[test]
+    fn scripts_need_one_key_or_paste_per_event() {
+        let script: ScriptFile = serde_json::from_str(
+            r#"{"version": 1, "events": [
+                {"key": "Down"},
+                {"delay_ms": 250, "paste": "JIRA-42"},
+                {"delay_ms": 50, "key": "Enter", "paste": "x"}
+            ]}"#,
+        )
+        .expect("parse");
+        let error = script_events(&script).expect_err("third event has both");
+        assert!(error.to_string().contains("event 3"));
+
+        let mut script = script;
+        script.events.pop();
+        let events = script_events(&script).expect("valid");
+        assert_eq!(events[0].0, Duration::ZERO);
+        assert_eq!(events[1].0, Duration::from_millis(250));
+        assert_eq!(events[1].1, Event::Paste("JIRA-42".to_string()));
+    }
+
+    #[test]
+    fn replay_waits_out_scaled_delays_and_a_key_press_interrupts_it() {
+        let events = VecDeque::from([
+            (
+                Duration::ZERO,
+                Event::Key(key(KeyCode::Down, KeyModifiers::NONE)),
+            ),
+            (
+                Duration::from_millis(80),
+                Event::Key(key(KeyCode::Enter, KeyModifiers::NONE)),
+            ),
+        ]);
+        let mut replay = Replay::new(events.clone(), 4.0);
+        let mut idle = |timeout: Duration| {
+            std::thread::sleep(timeout);
+            Ok(None)
+        };
+
+        let started = Instant::now();
+        let mut played = Vec::new();
+        loop {
+            match replay
+                .read(&mut idle, Duration::from_millis(5))
+                .expect("read")
+            {
+                ReplayRead::Event(event) => played.push(event),
+                ReplayRead::Waiting => {}
+                ReplayRead::Finished => break,
+                ReplayRead::Interrupted(_) => panic!("nothing was pressed"),
+            }
+        }
+        let elapsed = started.elapsed();
+        assert_eq!(
+            played,
+            events
+                .into_iter()
+                .map(|(_, event)| event)
+                .collect::<Vec<_>>()
+        );
+        assert!(elapsed >= Duration::from_millis(20), "{elapsed:?}");
+        assert!(elapsed < Duration::from_millis(80), "{elapsed:?}");
+
+        let mut replay = Replay::new(
+            VecDeque::from([(
+                Duration::from_secs(60),
+                Event::Key(key(KeyCode::Enter, KeyModifiers::NONE)),
+            )]),
+            1.0,
+        );
+        let pressed = Event::Key(key(KeyCode::Char('q'), KeyModifiers::NONE));
+        let mut typing = |_| Ok(Some(pressed.clone()));
+        assert!(matches!(
+            replay.read(&mut typing, Duration::from_millis(5)).expect("read"),
+            ReplayRead::Interrupted(event) if event == pressed
+        ));
+    }
+}
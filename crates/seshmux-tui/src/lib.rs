@@ -1,41 +1,76 @@
+mod adopt_flow;
 mod attach_flow;
+mod dash_flow;
 mod delete_flow;
+mod events;
+mod find_flow;
+mod input_script;
 mod keymap;
 mod list_flow;
 mod new_flow;
+mod notify;
+mod palette;
+mod perf;
+mod setup_flow;
+mod skip_rules_flow;
 mod theme;
+mod title;
 mod ui;
 
+use std::collections::BTreeSet;
 use std::io::{Stdout, stdout};
-use std::path::Path;
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 
+use crate::theme::{Accent, Theme};
+use adopt_flow::AdoptScreen;
 use anyhow::{Context, Result, anyhow};
 use attach_flow::AttachScreen;
 use crossterm::cursor::{Hide, Show};
 use crossterm::event::{
-    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
-    KeyModifiers,
+    DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event,
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent,
 };
 use crossterm::execute;
+use crossterm::style::Print;
 use crossterm::terminal::{
-    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+    EnterAlternateScreen, LeaveAlternateScreen, SetTitle, disable_raw_mode, enable_raw_mode,
 };
+use dash_flow::DashScreen;
 use delete_flow::DeleteScreen;
+use events::{EventPump, LoopEvent};
+use find_flow::FindScreen;
 use list_flow::ListScreen;
 use new_flow::NewScreen;
+use palette::{Palette, PaletteCommand, PaletteEvent, is_palette_key, render_doctor_report};
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
 use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::style::Color;
 use ratatui::text::{Line, Text};
 use ratatui::widgets::{List, ListItem, ListState};
-use seshmux_app::App;
-
-use crate::ui::modal::render_error_modal;
+use seshmux_app::{
+    App, ConfigCheckRequest, ErrorKind, ListResult, OpenRequest, WorktreeOrder, WorktreeRow,
+    WorktreeSortKey, classify_error, login_shell,
+};
+use seshmux_core::config::{DesktopNotifications, NotificationsConfig, UiConfig};
+use seshmux_core::doctor::DoctorReport;
+use setup_flow::SetupScreen;
+use skip_rules_flow::SkipRulesScreen;
+
+pub use crate::input_script::InputScript;
+pub use crate::perf::FrameTiming;
+
+use crate::notify::{JobCompletion, JobReporter, JobTracker};
+use crate::ui::binary_choice::{BinaryChoice, BinaryChoiceEvent};
+use crate::ui::error_actions::{ErrorActions, ErrorActionsEvent, render_error_with_actions};
+use crate::ui::help::{HelpProvider, ScreenHelp, closes_help, opens_help, render_help};
+use crate::ui::modal::{ModalSpec, render_modal};
+use crate::ui::mouse::{MouseAction, MouseTargets};
 use crate::ui::text::{
-    compact_hint, focus_line, key_hint_height, key_hint_paragraph, wrapped_paragraph,
+    compact_hint, focus_line, format_age, highlighted_label_value_line, key_hint_height,
+    key_hint_paragraph, label_value_line, wrapped_paragraph,
 };
+use crate::ui::toast::{Toast, render_toast};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UiExit {
@@ -53,35 +88,75 @@ enum RootMenuExit {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum RootAction {
     New,
+    QuickNew,
     List,
     Attach,
     Delete,
+    Find,
+    Adopt,
+    SkipRules,
 }
 
 impl RootAction {
     fn title(self) -> &'static str {
         match self {
             Self::New => "New worktree",
+            Self::QuickNew => "Quick new worktree",
             Self::List => "List worktrees",
             Self::Attach => "Attach to tmux session",
             Self::Delete => "Delete worktree",
+            Self::Find => "Find across worktrees",
+            Self::Adopt => "Adopt orphan tmux sessions",
+            Self::SkipRules => "Extras skip rules",
+        }
+    }
+
+    fn command(self) -> &'static str {
+        match self {
+            Self::New => "new",
+            Self::QuickNew => "quick new",
+            Self::List => "list",
+            Self::Attach => "attach",
+            Self::Delete => "delete",
+            Self::Find => "find",
+            Self::Adopt => "adopt",
+            Self::SkipRules => "skip rules",
+        }
+    }
+
+    fn hotkey(self) -> Option<char> {
+        match self {
+            Self::New => Some('n'),
+            Self::List => Some('l'),
+            Self::Attach => Some('a'),
+            Self::Delete => Some('d'),
+            _ => None,
         }
     }
 }
 
-const ROOT_ACTIONS: [RootAction; 4] = [
+const ROOT_ACTIONS: [RootAction; 7] = [
     RootAction::New,
     RootAction::List,
     RootAction::Attach,
     RootAction::Delete,
+    RootAction::Find,
+    RootAction::Adopt,
+    RootAction::SkipRules,
 ];
 
+// XTWINOPS title stack: save the caller's title on entry and put it back on exit.
+const PUSH_TITLE: &str = "\x1b[22;0t";
+const POP_TITLE: &str = "\x1b[23;0t";
+
 pub(crate) struct TerminalSession {
     terminal: Terminal<CrosstermBackend<Stdout>>,
+    title: Option<String>,
+    events: EventPump,
 }
 
 impl TerminalSession {
-    pub(crate) fn enter() -> Result<Self> {
+    pub(crate) fn enter(tick_rate: Duration, input: &InputScript) -> Result<Self> {
         let terminal = enter_with_ops(
             || enable_raw_mode().context("failed to enable raw mode"),
             || {
@@ -109,7 +184,23 @@ impl TerminalSession {
             },
             || disable_raw_mode().context("failed to disable raw mode during rollback"),
         )?;
-        Ok(Self { terminal })
+        let mut session = Self {
+            terminal,
+            title: None,
+            events: EventPump::start(tick_rate, input),
+        };
+        let _ = execute!(session.terminal.backend_mut(), Print(PUSH_TITLE));
+        let _ = execute!(session.terminal.backend_mut(), EnableBracketedPaste);
+        Ok(session)
+    }
+
+    pub(crate) fn set_title(&mut self, title: &str) {
+        if self.title.as_deref() == Some(title) {
+            return;
+        }
+
+        let _ = execute!(self.terminal.backend_mut(), SetTitle(title));
+        self.title = Some(title.to_string());
     }
 
     pub(crate) fn draw<F>(&mut self, draw_fn: F) -> Result<()>
@@ -122,6 +213,54 @@ impl TerminalSession {
         Ok(())
     }
 
+    pub(crate) fn next_event(&self, timeout: Duration) -> Result<Option<LoopEvent>> {
+        self.events.next(timeout)
+    }
+
+    pub(crate) fn set_ticking(&self, ticking: bool) {
+        self.events.set_ticking(ticking);
+    }
+
+    pub(crate) fn suspend<T>(&mut self, run: impl FnOnce() -> T) -> Result<T> {
+        let _paused = self.events.pause();
+        let _ = execute!(self.terminal.backend_mut(), DisableBracketedPaste);
+        execute!(self.terminal.backend_mut(), DisableMouseCapture)
+            .context("failed to disable mouse capture")?;
+        execute!(self.terminal.backend_mut(), Show, LeaveAlternateScreen)
+            .context("failed to leave alternate screen")?;
+        disable_raw_mode().context("failed to disable raw mode")?;
+
+        let value = run();
+
+        enable_raw_mode().context("failed to enable raw mode")?;
+        execute!(self.terminal.backend_mut(), EnterAlternateScreen, Hide)
+            .context("failed to enter alternate screen")?;
+        execute!(self.terminal.backend_mut(), EnableMouseCapture)
+            .context("failed to enable mouse capture")?;
+        let _ = execute!(self.terminal.backend_mut(), EnableBracketedPaste);
+        self.terminal.clear().context("failed to clear terminal")?;
+        Ok(value)
+    }
+
+    pub(crate) fn notify_desktop(&mut self, body: &str) {
+        let _ = execute!(
+            self.terminal.backend_mut(),
+            Print(notify::osc777_sequence(body))
+        );
+    }
+
+    pub(crate) fn copy_to_clipboard(&mut self, text: &str) {
+        let route = ui::clipboard::copy_route(ui::clipboard::over_ssh(), || {
+            ui::clipboard::copy_with_tool(text)
+        });
+        if route == ui::clipboard::CopyRoute::Osc52 {
+            let _ = execute!(
+                self.terminal.backend_mut(),
+                Print(ui::clipboard::osc52_sequence(text))
+            );
+        }
+    }
+
     pub(crate) fn autoresize(&mut self) -> Result<()> {
         self.terminal
             .autoresize()
@@ -132,6 +271,9 @@ impl TerminalSession {
 
 impl Drop for TerminalSession {
     fn drop(&mut self) {
+        self.events.stop();
+        let _ = execute!(self.terminal.backend_mut(), Print(POP_TITLE));
+        let _ = execute!(self.terminal.backend_mut(), DisableBracketedPaste);
         let _ = execute!(self.terminal.backend_mut(), DisableMouseCapture);
         let _ = execute!(self.terminal.backend_mut(), Show, LeaveAlternateScreen);
         let _ = disable_raw_mode();
@@ -295,14 +437,125 @@ pub(crate) fn is_ctrl_c(key: KeyEvent) -> bool {
     key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c')
 }
 
+const ROOT_OVERVIEW_MIN_WIDTH: u16 = 100;
+const ROOT_RECENT_WORKTREES: usize = 5;
+
 #[derive(Debug)]
 struct RootScreen {
     selected: usize,
+    mouse: MouseTargets,
+    overview: Option<RootOverview>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RootOverview {
+    repo_root: PathBuf,
+    rows: Vec<WorktreeRow>,
+}
+
+impl RootOverview {
+    fn from_list(result: ListResult) -> Self {
+        let mut rows = result.rows;
+        WorktreeOrder {
+            key: WorktreeSortKey::Recent,
+            ..WorktreeOrder::default()
+        }
+        .sort(&mut rows);
+        Self {
+            repo_root: result.repo_root,
+            rows,
+        }
+    }
+
+    fn running(&self) -> usize {
+        self.rows.iter().filter(|row| row.session_running).count()
+    }
+
+    fn repo_lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        let name = self
+            .repo_root
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.repo_root.display().to_string());
+        vec![
+            label_value_line(theme, "Name", name),
+            label_value_line(theme, "Path", self.repo_root.display().to_string()),
+            label_value_line(theme, "Worktrees", self.rows.len().to_string()),
+            label_value_line(theme, "Running sessions", self.running().to_string()),
+        ]
+    }
+
+    fn recent_lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        if self.rows.is_empty() {
+            return vec![Line::from("No worktrees yet. Press n to create one.")];
+        }
+        self.rows
+            .iter()
+            .take(ROOT_RECENT_WORKTREES)
+            .map(|row| {
+                let session = if row.session_running {
+                    "running"
+                } else {
+                    "not running"
+                };
+                let attached = row
+                    .last_attached_at
+                    .as_deref()
+                    .and_then(|at| seshmux_core::time::seconds_since_rfc3339(at, SystemTime::now()))
+                    .map(|seconds| match format_age(seconds).as_str() {
+                        "just now" => "attached just now".to_string(),
+                        age => format!("attached {age} ago"),
+                    })
+                    .unwrap_or_else(|| "never attached".to_string());
+                label_value_line(
+                    theme,
+                    row.name.clone(),
+                    format!("{}, {session}, {attached}", row.head.label()),
+                )
+            })
+            .collect()
+    }
 }
 
 impl RootScreen {
     fn new() -> Self {
-        Self { selected: 0 }
+        Self {
+            selected: 0,
+            mouse: MouseTargets::default(),
+            overview: None,
+        }
+    }
+
+    fn load(app: &App<'_>, cwd: &Path) -> Self {
+        Self {
+            overview: app.list(cwd).ok().map(RootOverview::from_list),
+            ..Self::new()
+        }
+    }
+
+    fn refresh_sessions(&mut self, app: &App<'_>) -> Result<()> {
+        if let Some(overview) = self.overview.as_mut() {
+            let running: BTreeSet<String> = app.refresh_sessions()?;
+            for row in &mut overview.rows {
+                row.session_running = running.contains(&row.session_name);
+            }
+        }
+        Ok(())
+    }
+
+    fn on_mouse(&mut self, mouse: MouseEvent) -> Option<KeyEvent> {
+        match self
+            .mouse
+            .action(mouse, ROOT_ACTIONS.len(), self.selected)?
+        {
+            MouseAction::Row(index) => self.selected = index,
+            MouseAction::ScrollUp => self.selected = self.selected.saturating_sub(1),
+            MouseAction::ScrollDown => {
+                self.selected = (self.selected + 1).min(ROOT_ACTIONS.len() - 1);
+            }
+            MouseAction::Key(key) => return Some(key),
+        }
+        None
     }
 
     fn on_key(&mut self, key: KeyEvent) -> Option<RootMenuExit> {
@@ -326,17 +579,42 @@ impl RootScreen {
             return Some(RootMenuExit::Action(ROOT_ACTIONS[self.selected]));
         }
 
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return (key.code == KeyCode::Char('n'))
+                .then_some(RootMenuExit::Action(RootAction::QuickNew));
+        }
+
+        if let KeyCode::Char(pressed) = key.code {
+            return ROOT_ACTIONS
+                .into_iter()
+                .find(|action| action.hotkey() == Some(pressed))
+                .map(RootMenuExit::Action);
+        }
+
         None
     }
 
-    fn render(&self, frame: &mut ratatui::Frame<'_>, cwd: &Path) {
+    fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme, cwd: &Path) {
         let area = frame.area();
-        let key_text = compact_hint(
-            area.width,
-            "Enter: select    Up/Down or j/k: move    Esc/q: exit",
-            "Enter: select    j/k: move    Esc/q: exit",
-            "Enter: select | j/k: move | Esc/q: exit",
-        );
+        let overview = self
+            .overview
+            .as_ref()
+            .filter(|_| area.width >= ROOT_OVERVIEW_MIN_WIDTH);
+        let key_text = if overview.is_some() {
+            compact_hint(
+                area.width,
+                "Enter: select    n/l/a/d: new/list/attach/delete    Ctrl+N: quick new    Ctrl+P: commands    Up/Down or j/k: move    ?: help    Esc/q: exit",
+                "Enter: select    n/l/a/d: jump    Ctrl+N: quick new    j/k: move    ?: help    Esc/q: exit",
+                "Enter: select | Ctrl+N: quick | j/k: move | ?: help | Esc/q: exit",
+            )
+        } else {
+            compact_hint(
+                area.width,
+                "Enter: select    Ctrl+N: quick new    Ctrl+P: commands    Up/Down or j/k: move    ?: help    Esc/q: exit",
+                "Enter: select    Ctrl+N: quick new    j/k: move    ?: help    Esc/q: exit",
+                "Enter: select | Ctrl+N: quick | j/k: move | ?: help | Esc/q: exit",
+            )
+        };
         let footer_height = key_hint_height(area.width, key_text);
         let [header, body, footer] = Layout::default()
             .direction(Direction::Vertical)
@@ -350,25 +628,81 @@ impl RootScreen {
         let header_text = Text::from(vec![
             Line::from("seshmux"),
             Line::from(cwd.to_string_lossy().to_string()),
-            focus_line("Choose what you want to do"),
+            focus_line(theme, "Choose what you want to do"),
         ]);
         let title = wrapped_paragraph(header_text).block(theme::chrome("Home"));
         frame.render_widget(title, header);
 
+        let actions_area = match overview {
+            Some(overview) => {
+                let [actions, details] = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Length(36), Constraint::Min(40)])
+                    .areas(body);
+                let [repo, recent] = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(6), Constraint::Min(4)])
+                    .areas(details);
+                frame.render_widget(
+                    wrapped_paragraph(Text::from(overview.repo_lines(theme)))
+                        .block(theme::chrome("Repository")),
+                    repo,
+                );
+                frame.render_widget(
+                    wrapped_paragraph(Text::from(overview.recent_lines(theme)))
+                        .block(theme::chrome("Recent worktrees")),
+                    recent,
+                );
+                actions
+            }
+            None => body,
+        };
+
         let items: Vec<ListItem<'_>> = ROOT_ACTIONS
             .iter()
-            .map(|action| ListItem::new(action.title()))
+            .map(|action| match (overview, action.hotkey()) {
+                (None, _) => ListItem::new(action.title()),
+                (Some(_), Some(hotkey)) => ListItem::new(format!("{hotkey}  {}", action.title())),
+                (Some(_), None) => ListItem::new(format!("   {}", action.title())),
+            })
             .collect();
         let list = List::new(items)
-            .block(theme::chrome(focus_line("Actions")))
-            .highlight_style(theme::table_highlight(Color::Cyan));
+            .block(theme::chrome(focus_line(theme, "Actions")))
+            .highlight_style(theme.table_highlight(Accent::Browse));
 
         let mut state = ListState::default();
         state.select(Some(self.selected));
-        frame.render_stateful_widget(list, body, &mut state);
+        frame.render_stateful_widget(list, actions_area, &mut state);
+        self.mouse.record_list(actions_area, 0);
 
         let hints = key_hint_paragraph(key_text).block(theme::key_block());
         frame.render_widget(hints, footer);
+        self.mouse.record_hints(frame, footer);
+    }
+}
+
+impl HelpProvider for RootScreen {
+    fn help(&self) -> ScreenHelp {
+        ScreenHelp::new(
+            "Home",
+            "Everything seshmux can do for this repository. Each screen has its own help.",
+        )
+        .keys(&[
+            ("Up/Down or j/k", "move"),
+            ("Enter", "open the highlighted action"),
+            ("n/l/a/d", "open new, list, attach, or delete directly"),
+            ("Ctrl+N", "quick new worktree with the [quick_new] answers"),
+            (
+                "Ctrl+P",
+                "search every command and worktree, recently used first",
+            ),
+            ("?", "show help for the current screen"),
+            ("Esc/q", "exit seshmux"),
+        ])
+    }
+
+    fn typing(&self) -> bool {
+        false
     }
 }
 
@@ -378,6 +712,10 @@ enum ActiveScreen {
     List(Box<ListScreen>),
     Attach(Box<AttachScreen>),
     Delete(Box<DeleteScreen>),
+    Find(Box<FindScreen>),
+    Adopt(Box<AdoptScreen>),
+    SkipRules(Box<SkipRulesScreen>),
+    Dash(Box<DashScreen>),
 }
 
 enum Transition {
@@ -429,55 +767,394 @@ fn root_loop_drain_new_flow_loader(
     root_loop_drain_helper(screen.as_mut(), reason)
 }
 
-pub fn run_root(app: &App<'_>, cwd: &Path) -> Result<UiExit> {
-    let mut session = TerminalSession::enter()?;
-    let mut active = ActiveScreen::Root(RootScreen::new());
-    let mut global_error: Option<String> = None;
-    const TICK_RATE: Duration = Duration::from_millis(120);
+fn root_loop_wants_ticks(active: &ActiveScreen, toast_visible: bool) -> bool {
+    toast_visible
+        || match active {
+            ActiveScreen::New(screen) => {
+                screen.should_drain_loader_after_input() || screen.query_pending()
+            }
+            ActiveScreen::List(screen) => screen.measuring_sizes() || screen.updating(),
+            _ => false,
+        }
+}
+
+fn root_loop_help_provider(active: &ActiveScreen) -> &dyn HelpProvider {
+    match active {
+        ActiveScreen::Root(screen) => screen,
+        ActiveScreen::New(screen) => screen.as_ref(),
+        ActiveScreen::List(screen) => screen.as_ref(),
+        ActiveScreen::Attach(screen) => screen.as_ref(),
+        ActiveScreen::Delete(screen) => screen.as_ref(),
+        ActiveScreen::Find(screen) => screen.as_ref(),
+        ActiveScreen::Adopt(screen) => screen.as_ref(),
+        ActiveScreen::SkipRules(screen) => screen.as_ref(),
+        ActiveScreen::Dash(screen) => screen.as_ref(),
+    }
+}
+
+fn root_loop_refresh_sessions(active: &mut ActiveScreen, app: &App<'_>) -> Result<bool> {
+    match active {
+        ActiveScreen::List(screen) => screen.refresh_sessions(app)?,
+        ActiveScreen::Attach(screen) => screen.refresh_sessions(app)?,
+        ActiveScreen::Delete(screen) => screen.refresh_sessions(app)?,
+        ActiveScreen::Dash(screen) => screen.refresh_sessions(app)?,
+        ActiveScreen::Root(screen) => screen.refresh_sessions(app)?,
+        ActiveScreen::New(_)
+        | ActiveScreen::Find(_)
+        | ActiveScreen::Adopt(_)
+        | ActiveScreen::SkipRules(_) => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+fn root_loop_paste(active: &mut ActiveScreen, text: &str, app: &App<'_>) -> Result<()> {
+    match active {
+        ActiveScreen::New(screen) => screen.on_paste(text, app)?,
+        ActiveScreen::List(screen) => screen.on_paste(text),
+        ActiveScreen::Attach(screen) => screen.on_paste(text),
+        ActiveScreen::Delete(screen) => screen.on_paste(text),
+        ActiveScreen::Find(screen) => screen.on_paste(text),
+        ActiveScreen::SkipRules(screen) => screen.on_paste(text),
+        ActiveScreen::Root(_) | ActiveScreen::Adopt(_) | ActiveScreen::Dash(_) => {}
+    }
+
+    Ok(())
+}
+
+fn root_loop_mouse(active: &mut ActiveScreen, mouse: MouseEvent) -> Result<Option<KeyEvent>> {
+    match active {
+        ActiveScreen::Root(screen) => Ok(screen.on_mouse(mouse)),
+        ActiveScreen::New(screen) => screen.on_mouse(mouse).map(|()| None),
+        ActiveScreen::List(screen) => Ok(screen.on_mouse(mouse)),
+        ActiveScreen::Attach(screen) => Ok(screen.on_mouse(mouse)),
+        ActiveScreen::Delete(screen) => Ok(screen.on_mouse(mouse)),
+        ActiveScreen::Find(_)
+        | ActiveScreen::Adopt(_)
+        | ActiveScreen::SkipRules(_)
+        | ActiveScreen::Dash(_) => Ok(None),
+    }
+}
+
+fn root_loop_take_pending_copy(active: &mut ActiveScreen) -> Option<String> {
+    match active {
+        ActiveScreen::New(screen) => screen.take_pending_copy(),
+        ActiveScreen::List(screen) => screen.take_pending_copy(),
+        ActiveScreen::Attach(screen) => screen.take_pending_copy(),
+        ActiveScreen::Delete(screen) => screen.take_pending_copy(),
+        ActiveScreen::Root(_)
+        | ActiveScreen::Find(_)
+        | ActiveScreen::Adopt(_)
+        | ActiveScreen::SkipRules(_)
+        | ActiveScreen::Dash(_) => None,
+    }
+}
+
+fn announce_completed_job(
+    session: &mut TerminalSession,
+    app: &App<'_>,
+    notifications: &NotificationsConfig,
+    job: &JobCompletion,
+) -> Option<Toast> {
+    if job.elapsed.as_secs() < notifications.min_job_seconds {
+        return None;
+    }
+
+    let message = job.message();
+    match notifications.desktop {
+        DesktopNotifications::Off => {}
+        DesktopNotifications::Osc777 => session.notify_desktop(&message),
+        DesktopNotifications::NotifySend => notify::notify_send(app.runner, &message),
+    }
+
+    Some(Toast::new(message))
+}
+
+fn active_screen_title(active: &ActiveScreen) -> String {
+    match active {
+        ActiveScreen::Root(_) => title::screen_title("Home", None),
+        ActiveScreen::New(_) => title::screen_title("New", None),
+        ActiveScreen::List(screen) => title::screen_title("List", screen.selected_worktree()),
+        ActiveScreen::Attach(screen) => title::screen_title("Attach", screen.selected_worktree()),
+        ActiveScreen::Delete(screen) => title::screen_title("Delete", screen.selected_worktree()),
+        ActiveScreen::Find(_) => title::screen_title("Find", None),
+        ActiveScreen::Adopt(_) => title::screen_title("Adopt", None),
+        ActiveScreen::SkipRules(_) => title::screen_title("Skip rules", None),
+        ActiveScreen::Dash(screen) => title::screen_title("Dash", screen.selected_worktree()),
+    }
+}
+
+fn open_root_action(
+    app: &App<'_>,
+    cwd: &Path,
+    jobs: &JobReporter,
+    action: RootAction,
+) -> Result<ActiveScreen> {
+    Ok(match action {
+        RootAction::New => ActiveScreen::New(Box::new(NewScreen::new(app, cwd, jobs.clone())?)),
+        RootAction::QuickNew => {
+            ActiveScreen::New(Box::new(NewScreen::new_quick(app, cwd, jobs.clone())?))
+        }
+        RootAction::List => ActiveScreen::List(Box::new(ListScreen::new(app, cwd)?)),
+        RootAction::Attach => ActiveScreen::Attach(Box::new(AttachScreen::new(app, cwd)?)),
+        RootAction::Delete => ActiveScreen::Delete(Box::new(DeleteScreen::new(app, cwd)?)),
+        RootAction::Find => ActiveScreen::Find(Box::new(FindScreen::new(cwd))),
+        RootAction::Adopt => ActiveScreen::Adopt(Box::new(AdoptScreen::new(app, cwd)?)),
+        RootAction::SkipRules => ActiveScreen::SkipRules(Box::new(SkipRulesScreen::new(app, cwd)?)),
+    })
+}
+
+pub fn run_root(
+    app: &App<'_>,
+    input: &InputScript,
+    timing: &FrameTiming,
+    cwd: &Path,
+) -> Result<UiExit> {
+    run_from(app, input, timing, cwd, JobTracker::default(), None)
+}
+
+pub fn run_new(
+    app: &App<'_>,
+    input: &InputScript,
+    timing: &FrameTiming,
+    cwd: &Path,
+) -> Result<UiExit> {
+    let jobs = JobTracker::default();
+    let start = open_root_action(app, cwd, &jobs.reporter(), RootAction::New)?;
+    run_from(app, input, timing, cwd, jobs, Some(start))
+}
+
+pub fn run_dash(
+    app: &App<'_>,
+    input: &InputScript,
+    timing: &FrameTiming,
+    cwd: &Path,
+) -> Result<UiExit> {
+    let start = ActiveScreen::Dash(Box::new(DashScreen::new(app, cwd)?));
+    run_from(app, input, timing, cwd, JobTracker::default(), Some(start))
+}
+
+pub fn run_setup(app: &App<'_>, input: &InputScript, force: bool) -> Result<Option<PathBuf>> {
+    let theme = &Theme::default();
+    let mut screen = SetupScreen::new(app.config_setup_defaults(), &login_shell(), force);
+    let mut session =
+        TerminalSession::enter(Duration::from_millis(UiConfig::default().tick_ms), input)?;
+    session.set_ticking(false);
+    session.set_title(&title::screen_title("Setup", None));
+    let mut help: Option<ScreenHelp> = None;
 
     loop {
         session.draw(|frame| {
-            match &active {
-                ActiveScreen::Root(screen) => screen.render(frame, cwd),
-                ActiveScreen::New(screen) => screen.render(frame),
-                ActiveScreen::List(screen) => screen.render(frame),
-                ActiveScreen::Attach(screen) => screen.render(frame),
-                ActiveScreen::Delete(screen) => screen.render(frame),
-            }
-
-            if let Some(message) = global_error.as_deref() {
-                render_global_error(frame, message);
+            screen.render(frame, theme);
+            if let Some(help) = &help {
+                render_help(frame, theme, help);
             }
         })?;
 
-        let has_event = event::poll(TICK_RATE).context("failed to poll terminal event")?;
-        if !has_event {
-            if let Err(error) =
-                root_loop_drain_new_flow_loader(&mut active, NewFlowDrainReason::Timeout)
+        let key = match session.next_event(Duration::from_secs(60))? {
+            Some(LoopEvent::Terminal(Event::Resize(_, _))) => {
+                session.autoresize()?;
+                continue;
+            }
+            Some(LoopEvent::Terminal(Event::Paste(text))) if help.is_none() => {
+                screen.on_paste(&text);
+                continue;
+            }
+            Some(LoopEvent::Terminal(Event::Key(key)))
+                if matches!(key.kind, KeyEventKind::Press) =>
             {
-                global_error = Some(format!("{error:#}"));
+                key
+            }
+            _ => continue,
+        };
+
+        if is_ctrl_c(key) {
+            return Ok(None);
+        }
+        if help.is_some() {
+            if closes_help(key) {
+                help = None;
             }
             continue;
         }
+        if opens_help(key) && !screen.typing() {
+            help = Some(screen.help());
+            continue;
+        }
+        if let Some(written) = screen.on_key(key, app) {
+            return Ok(written);
+        }
+    }
+}
+
+fn run_from(
+    app: &App<'_>,
+    input: &InputScript,
+    timing: &FrameTiming,
+    cwd: &Path,
+    jobs: JobTracker,
+    start: Option<ActiveScreen>,
+) -> Result<UiExit> {
+    let config = app.ensure_config_ready().ok();
+    let notifications = config
+        .as_ref()
+        .map(|config| config.notifications.clone())
+        .unwrap_or_default();
+    let theme = &config
+        .as_ref()
+        .map(|config| Theme::from_config(&config.theme))
+        .unwrap_or_default();
+    let tick_rate =
+        Duration::from_millis(config.map(|config| config.ui).unwrap_or_default().tick_ms);
+    let mut session = TerminalSession::enter(tick_rate, input)?;
+    let mut pane_title = title::PaneTitle::capture(app.runner);
+    let mut toast: Option<Toast> = None;
+    let mut frame_timings = timing.start();
+    let mut active = start.unwrap_or_else(|| ActiveScreen::Root(RootScreen::load(app, cwd)));
+    let mut global_error: Option<GlobalError> = None;
+    let mut help: Option<ScreenHelp> = None;
+    let mut palette: Option<Palette> = None;
+    let mut leave_prompt: Option<LeavePrompt> = None;
+    let mut doctor: Option<DoctorReport> = None;
+    let mut last_session_refresh = Instant::now();
+    const SESSION_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+    loop {
+        if global_error.is_none() && last_session_refresh.elapsed() >= SESSION_REFRESH_INTERVAL {
+            last_session_refresh = Instant::now();
+            let _ = root_loop_refresh_sessions(&mut active, app);
+        }
+        if global_error.is_none()
+            && let ActiveScreen::List(screen) = &mut active
+        {
+            let _ = screen.poll_sizes(app);
+            screen.poll_update();
+        }
+        if let ActiveScreen::New(screen) = &mut active {
+            screen.finish_creation(app);
+            if global_error.is_none()
+                && let Err(error) = screen.run_due_query(app)
+            {
+                global_error = Some(GlobalError::from_error(&error));
+            }
+        }
+
+        while let Some(job) = jobs.poll() {
+            if let Some(next) = announce_completed_job(&mut session, app, &notifications, &job) {
+                toast = Some(next);
+            }
+        }
+        if toast.as_ref().is_some_and(Toast::expired) {
+            toast = None;
+        }
+
+        let screen_title = active_screen_title(&active);
+        session.set_title(&screen_title);
+        if let Some(pane_title) = pane_title.as_mut() {
+            pane_title.set(&screen_title);
+        }
+
+        let draw_started = Instant::now();
+        session.draw(|frame| {
+            match &active {
+                ActiveScreen::Root(screen) => screen.render(frame, theme, cwd),
+                ActiveScreen::New(screen) => screen.render(frame, theme),
+                ActiveScreen::List(screen) => screen.render(frame, theme),
+                ActiveScreen::Attach(screen) => screen.render(frame, theme),
+                ActiveScreen::Delete(screen) => screen.render(frame, theme),
+                ActiveScreen::Find(screen) => screen.render(frame, theme),
+                ActiveScreen::Adopt(screen) => screen.render(frame, theme),
+                ActiveScreen::SkipRules(screen) => screen.render(frame, theme),
+                ActiveScreen::Dash(screen) => screen.render(frame, theme),
+            }
+
+            if let Some(toast) = &toast {
+                render_toast(frame, theme, toast);
+            }
+
+            if let Some(palette) = &palette {
+                palette.render(frame, theme);
+            }
+
+            if let Some(prompt) = &leave_prompt {
+                prompt.render(frame, theme);
+            }
+
+            if let Some(report) = &doctor {
+                render_doctor_report(frame, theme, report);
+            }
+
+            if let Some(error) = &global_error {
+                render_global_error(frame, theme, error);
+            }
+
+            if let Some(help) = &help {
+                render_help(frame, theme, help);
+            }
+        })?;
+        if let Some(timings) = frame_timings.as_mut() {
+            timings.record_draw(draw_started.elapsed());
+        }
 
-        let event = event::read().context("failed to read terminal event")?;
+        session.set_ticking(root_loop_wants_ticks(&active, toast.is_some()));
+        let wait = SESSION_REFRESH_INTERVAL.saturating_sub(last_session_refresh.elapsed());
+        let event = match session.next_event(wait)? {
+            Some(LoopEvent::Terminal(event)) => event,
+            Some(LoopEvent::Tick) | None => {
+                if let Err(error) =
+                    root_loop_drain_new_flow_loader(&mut active, NewFlowDrainReason::Timeout)
+                {
+                    global_error = Some(GlobalError::from_error(&error));
+                }
+                continue;
+            }
+        };
+        let event_started = Instant::now();
         let key = match event {
             Event::Resize(_, _) => {
                 session.autoresize()?;
                 continue;
             }
-            Event::Mouse(mouse) => {
+            Event::Mouse(_) | Event::Paste(_) if global_error.is_some() || help.is_some() => {
+                continue;
+            }
+            Event::Paste(text) if palette.is_some() => {
+                if let Some(palette) = palette.as_mut() {
+                    palette.on_paste(&text);
+                }
+                continue;
+            }
+            Event::Mouse(_) | Event::Paste(_)
+                if palette.is_some() || leave_prompt.is_some() || doctor.is_some() =>
+            {
+                continue;
+            }
+            Event::Mouse(mouse) => match root_loop_mouse(&mut active, mouse) {
+                Ok(Some(key)) => key,
+                Ok(None) => {
+                    if let Err(error) =
+                        root_loop_drain_new_flow_loader(&mut active, NewFlowDrainReason::AfterInput)
+                    {
+                        global_error = Some(GlobalError::from_error(&error));
+                    }
+                    continue;
+                }
+                Err(error) => {
+                    global_error = Some(GlobalError::from_error(&error));
+                    continue;
+                }
+            },
+            Event::Paste(text) => {
                 if global_error.is_none()
-                    && let ActiveScreen::New(screen) = &mut active
-                    && let Err(error) = screen.on_mouse(mouse)
+                    && let Err(error) = root_loop_paste(&mut active, &text, app)
                 {
-                    global_error = Some(format!("{error:#}"));
+                    global_error = Some(GlobalError::from_error(&error));
                 }
                 if global_error.is_none()
                     && let Err(error) =
                         root_loop_drain_new_flow_loader(&mut active, NewFlowDrainReason::AfterInput)
                 {
-                    global_error = Some(format!("{error:#}"));
+                    global_error = Some(GlobalError::from_error(&error));
                 }
                 continue;
             }
@@ -486,16 +1163,105 @@ pub fn run_root(app: &App<'_>, cwd: &Path) -> Result<UiExit> {
         };
 
         if is_ctrl_c(key) {
+            // Quitting mid-creation would skip the rollback, so cancel and let it unwind first.
+            if let ActiveScreen::New(screen) = &active
+                && screen.creating()
+            {
+                screen.cancel_creation();
+                continue;
+            }
             return Ok(UiExit::Canceled);
         }
 
-        if global_error.is_some() {
-            if keymap::is_confirm(key) || keymap::is_back(key) {
-                global_error = None;
+        if let Some(error) = global_error.as_mut() {
+            match error.actions.on_key(key) {
+                ErrorActionsEvent::Continue => {}
+                ErrorActionsEvent::Back => global_error = None,
+                ErrorActionsEvent::Choose(GlobalErrorAction::OpenShell) => {
+                    global_error = None;
+                    if let Err(error) = run_shell(&mut session, app, cwd) {
+                        global_error = Some(GlobalError::from_error(&error));
+                    }
+                }
+                ErrorActionsEvent::Choose(GlobalErrorAction::EditConfig { repo }) => {
+                    global_error = None;
+                    if let Err(error) = edit_config(&mut session, app, cwd, repo) {
+                        global_error = Some(GlobalError::from_error(&error));
+                    }
+                }
+            }
+            continue;
+        }
+
+        if help.is_some() {
+            if closes_help(key) {
+                help = None;
+            }
+            continue;
+        }
+
+        if doctor.is_some() {
+            if closes_help(key) {
+                doctor = None;
+            }
+            continue;
+        }
+
+        let command = if let Some(prompt) = leave_prompt.as_mut() {
+            match prompt.choice.on_key(key) {
+                BinaryChoiceEvent::Continue => continue,
+                BinaryChoiceEvent::ConfirmNo | BinaryChoiceEvent::Back => {
+                    leave_prompt = None;
+                    continue;
+                }
+                BinaryChoiceEvent::ConfirmYes => leave_prompt.take().map(|prompt| prompt.command),
+            }
+        } else if let Some(open) = palette.as_mut() {
+            match open.on_key(key) {
+                PaletteEvent::Continue => continue,
+                PaletteEvent::Close => {
+                    palette = None;
+                    continue;
+                }
+                PaletteEvent::Run(command) => {
+                    palette = None;
+                    if command.replaces_screen()
+                        && matches!(&active, ActiveScreen::New(screen) if screen.has_answers())
+                    {
+                        leave_prompt = Some(LeavePrompt::new(command));
+                        continue;
+                    }
+                    Some(command)
+                }
+            }
+        } else {
+            None
+        };
+        if let Some(command) = command {
+            match run_palette_command(&mut session, app, cwd, &jobs.reporter(), &command) {
+                Ok(PaletteOutcome::Screen(screen)) => active = screen,
+                Ok(PaletteOutcome::Report(report)) => doctor = Some(report),
+                Ok(PaletteOutcome::Done) => {}
+                Err(error) => global_error = Some(GlobalError::from_error(&error)),
             }
             continue;
         }
 
+        let provider = root_loop_help_provider(&active);
+        // Leaving mid-creation would skip the rollback, so the palette waits for it to finish.
+        if is_palette_key(key)
+            && !provider.typing()
+            && !matches!(&active, ActiveScreen::New(screen) if screen.creating())
+        {
+            palette = Some(Palette::new(app, cwd));
+            continue;
+        }
+
+        if opens_help(key) && !provider.typing() {
+            help = Some(provider.help());
+            continue;
+        }
+
         let transition = match &mut active {
             ActiveScreen::Root(screen) => match screen.on_key(key) {
                 Some(RootMenuExit::Action(action)) => Some(Transition::Open(action)),
@@ -505,59 +1271,112 @@ pub fn run_root(app: &App<'_>, cwd: &Path) -> Result<UiExit> {
             ActiveScreen::New(screen) => match screen.on_key(key, app) {
                 Ok(value) => value.map(Transition::Return),
                 Err(error) => {
-                    global_error = Some(format!("{error:#}"));
+                    global_error = Some(GlobalError::from_error(&error));
                     None
                 }
             },
             ActiveScreen::List(screen) => match screen.on_key(key, app) {
                 Ok(value) => value.map(Transition::Return),
                 Err(error) => {
-                    global_error = Some(format!("{error:#}"));
+                    global_error = Some(GlobalError::from_error(&error));
                     None
                 }
             },
             ActiveScreen::Attach(screen) => match screen.on_key(key, app) {
                 Ok(value) => value.map(Transition::Return),
                 Err(error) => {
-                    global_error = Some(format!("{error:#}"));
+                    global_error = Some(GlobalError::from_error(&error));
                     None
                 }
             },
             ActiveScreen::Delete(screen) => match screen.on_key(key, app) {
                 Ok(value) => value.map(Transition::Return),
                 Err(error) => {
-                    global_error = Some(format!("{error:#}"));
+                    global_error = Some(GlobalError::from_error(&error));
+                    None
+                }
+            },
+            ActiveScreen::Find(screen) => match screen.on_key(key, app) {
+                Ok(value) => value.map(Transition::Return),
+                Err(error) => {
+                    global_error = Some(GlobalError::from_error(&error));
+                    None
+                }
+            },
+            ActiveScreen::Adopt(screen) => match screen.on_key(key, app) {
+                Ok(value) => value.map(Transition::Return),
+                Err(error) => {
+                    global_error = Some(GlobalError::from_error(&error));
+                    None
+                }
+            },
+            ActiveScreen::SkipRules(screen) => match screen.on_key(key, app) {
+                Ok(value) => value.map(Transition::Return),
+                Err(error) => {
+                    global_error = Some(GlobalError::from_error(&error));
+                    None
+                }
+            },
+            ActiveScreen::Dash(screen) => match screen.on_key(key, app) {
+                Ok(value) => value.map(Transition::Return),
+                Err(error) => {
+                    global_error = Some(GlobalError::from_error(&error));
                     None
                 }
             },
         };
 
+        let pending_open = match &mut active {
+            ActiveScreen::List(screen) => {
+                screen.take_pending_open().map(|worktree_name| OpenRequest {
+                    cwd: screen.cwd().to_path_buf(),
+                    worktree_name,
+                    location: None,
+                })
+            }
+            ActiveScreen::Find(screen) => screen.take_pending_open(),
+            _ => None,
+        };
+        if let Some(request) = pending_open {
+            match session.suspend(|| app.open(request).map_err(anyhow::Error::from)) {
+                Ok(Ok(_)) => {}
+                Ok(Err(error)) | Err(error) => global_error = Some(GlobalError::from_error(&error)),
+            }
+        }
+
+        if let ActiveScreen::New(screen) = &mut active
+            && let Some(dir) = screen.take_pending_shell()
+            && let Err(error) = run_shell(&mut session, app, &dir)
+        {
+            global_error = Some(GlobalError::from_error(&error));
+        }
+
+        if let ActiveScreen::New(screen) = &mut active
+            && let Some(worktree_name) = screen.take_pending_attach()
+        {
+            match AttachScreen::attach_to(app, cwd, &worktree_name) {
+                Ok(screen) => active = ActiveScreen::Attach(Box::new(screen)),
+                Err(error) => global_error = Some(GlobalError::from_error(&error)),
+            }
+        }
+
+        if let Some(text) = root_loop_take_pending_copy(&mut active) {
+            session.copy_to_clipboard(&text);
+            toast = Some(Toast::new(format!("Copied {text}")));
+        }
+
         if let Some(transition) = transition {
             match transition {
                 Transition::Open(action) => {
-                    match action {
-                        RootAction::New => match NewScreen::new(app, cwd) {
-                            Ok(screen) => active = ActiveScreen::New(Box::new(screen)),
-                            Err(error) => global_error = Some(format!("{error:#}")),
-                        },
-                        RootAction::List => match ListScreen::new(app, cwd) {
-                            Ok(screen) => active = ActiveScreen::List(Box::new(screen)),
-                            Err(error) => global_error = Some(format!("{error:#}")),
-                        },
-                        RootAction::Attach => match AttachScreen::new(app, cwd) {
-                            Ok(screen) => active = ActiveScreen::Attach(Box::new(screen)),
-                            Err(error) => global_error = Some(format!("{error:#}")),
-                        },
-                        RootAction::Delete => match DeleteScreen::new(app, cwd) {
-                            Ok(screen) => active = ActiveScreen::Delete(Box::new(screen)),
-                            Err(error) => global_error = Some(format!("{error:#}")),
-                        },
-                    };
+                    match open_root_action(app, cwd, &jobs.reporter(), action) {
+                        Ok(screen) => active = screen,
+                        Err(error) => global_error = Some(GlobalError::from_error(&error)),
+                    }
                 }
                 Transition::Return(UiExit::Canceled) => return Ok(UiExit::Canceled),
                 Transition::Return(UiExit::Completed) => return Ok(UiExit::Completed),
                 Transition::Return(UiExit::BackAtRoot) => {
-                    active = ActiveScreen::Root(RootScreen::new());
+                    active = ActiveScreen::Root(RootScreen::load(app, cwd));
                 }
             }
         }
@@ -566,14 +1385,146 @@ pub fn run_root(app: &App<'_>, cwd: &Path) -> Result<UiExit> {
             && let Err(error) =
                 root_loop_drain_new_flow_loader(&mut active, NewFlowDrainReason::AfterInput)
         {
-            global_error = Some(format!("{error:#}"));
+            global_error = Some(GlobalError::from_error(&error));
+        }
+
+        if let Some(timings) = frame_timings.as_mut() {
+            timings.record_event(event_started.elapsed());
         }
     }
 }
 
-fn render_global_error(frame: &mut ratatui::Frame<'_>, message: &str) {
-    let text = format!("Operation failed.\n\n{message}");
-    render_error_modal(frame, &text, 88, 72, "Enter/Esc: continue");
+enum PaletteOutcome {
+    Screen(ActiveScreen),
+    Report(DoctorReport),
+    Done,
+}
+
+struct LeavePrompt {
+    command: PaletteCommand,
+    choice: BinaryChoice,
+}
+
+impl LeavePrompt {
+    fn new(command: PaletteCommand) -> Self {
+        Self {
+            command,
+            choice: BinaryChoice::new(false),
+        }
+    }
+
+    fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        let lines = vec![
+            label_value_line(theme, "Command", self.command.label()),
+            Line::from(""),
+            Line::from("The answers given so far for the new worktree are lost."),
+            highlighted_label_value_line(theme, "Current Selection", self.choice.selected_label()),
+        ];
+        render_modal(
+            frame,
+            theme,
+            ModalSpec {
+                title: "Leave the new worktree flow?",
+                title_style: Some(theme.focus_prompt()),
+                body: Text::from(lines),
+                key_hint: Some("Space: toggle    Enter: confirm    Esc: cancel"),
+                width_pct: 70,
+                height_pct: 35,
+            },
+        );
+    }
+}
+
+fn run_palette_command(
+    session: &mut TerminalSession,
+    app: &App<'_>,
+    cwd: &Path,
+    jobs: &JobReporter,
+    command: &PaletteCommand,
+) -> Result<PaletteOutcome> {
+    let outcome = match command {
+        PaletteCommand::Action(action) => {
+            PaletteOutcome::Screen(open_root_action(app, cwd, jobs, *action)?)
+        }
+        PaletteCommand::Attach(name) => PaletteOutcome::Screen(ActiveScreen::Attach(Box::new(
+            AttachScreen::attach_to(app, cwd, name)?,
+        ))),
+        PaletteCommand::Delete(name) => PaletteOutcome::Screen(ActiveScreen::Delete(Box::new(
+            DeleteScreen::delete_named(app, cwd, name)?,
+        ))),
+        PaletteCommand::Open(name) => {
+            session.suspend(|| {
+                app.open(OpenRequest {
+                    cwd: cwd.to_path_buf(),
+                    worktree_name: name.clone(),
+                    location: None,
+                })
+            })??;
+            PaletteOutcome::Done
+        }
+        PaletteCommand::Doctor => PaletteOutcome::Report(app.doctor(cwd)?),
+    };
+    let _ = app.record_command(cwd, &command.label());
+    Ok(outcome)
+}
+
+struct GlobalError {
+    message: String,
+    actions: ErrorActions<GlobalErrorAction>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GlobalErrorAction {
+    OpenShell,
+    EditConfig { repo: bool },
+}
+
+impl GlobalError {
+    fn from_error(error: &anyhow::Error) -> Self {
+        let actions = match classify_error(error) {
+            ErrorKind::NoCommits => ErrorActions::new(vec![(
+                "Open a shell here to make a first commit".to_string(),
+                GlobalErrorAction::OpenShell,
+            )]),
+            ErrorKind::InvalidConfig { repo } => {
+                let label = if repo {
+                    format!("Edit {}", seshmux_core::config::REPO_CONFIG_FILE_NAME)
+                } else {
+                    "Edit the config".to_string()
+                };
+                ErrorActions::new(vec![(label, GlobalErrorAction::EditConfig { repo })])
+            }
+            ErrorKind::CommandTimedOut { .. } => ErrorActions::new(vec![(
+                "Raise command_timeout_secs in the config".to_string(),
+                GlobalErrorAction::EditConfig { repo: false },
+            )]),
+            _ => ErrorActions::none(),
+        };
+        Self {
+            message: format!("{error:#}"),
+            actions,
+        }
+    }
+}
+
+fn render_global_error(frame: &mut ratatui::Frame<'_>, theme: &Theme, error: &GlobalError) {
+    let text = format!("Operation failed.\n\n{}", error.message);
+    render_error_with_actions(frame, theme, &text, &error.actions, "continue", 88, 72);
+}
+
+fn run_shell(session: &mut TerminalSession, app: &App<'_>, dir: &Path) -> Result<()> {
+    session.suspend(|| app.open_shell(dir))??;
+    Ok(())
+}
+
+fn edit_config(session: &mut TerminalSession, app: &App<'_>, cwd: &Path, repo: bool) -> Result<()> {
+    session.suspend(|| {
+        app.config_edit(ConfigCheckRequest {
+            cwd: cwd.to_path_buf(),
+            repo,
+        })
+    })??;
+    Ok(())
 }
 
 pub(crate) fn centered_rect(
@@ -608,12 +1559,20 @@ mod tests {
     use anyhow::anyhow;
     use std::cell::RefCell;
 
-    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use crossterm::event::{
+        KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
     use ratatui::layout::Rect;
+    use seshmux_app::{ListResult, WorktreeHead, WorktreeRow};
+
+    use crate::theme::Theme;
+    use crate::ui::help::HelpProvider;
 
     use super::{
-        NewFlowDrainReason, RootLoopTickTarget, RootMenuExit, RootScreen, centered_rect,
-        enter_with_ops, leave_with_ops, root_loop_drain_helper,
+        NewFlowDrainReason, RootAction, RootLoopTickTarget, RootMenuExit, RootOverview, RootScreen,
+        centered_rect, enter_with_ops, leave_with_ops, root_loop_drain_helper,
     };
 
     fn key(code: KeyCode) -> KeyEvent {
@@ -665,6 +1624,110 @@ mod tests {
         );
     }
 
+    #[test]
+    fn root_screen_ctrl_n_opens_quick_new() {
+        let mut root = RootScreen::new();
+        assert_eq!(root.on_key(key(KeyCode::Char('x'))), None);
+        assert_eq!(
+            root.on_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL)),
+            Some(RootMenuExit::Action(RootAction::QuickNew))
+        );
+    }
+
+    #[test]
+    fn root_screen_hotkeys_open_flows_directly() {
+        let mut root = RootScreen::new();
+        for (hotkey, action) in [
+            ('n', RootAction::New),
+            ('l', RootAction::List),
+            ('a', RootAction::Attach),
+            ('d', RootAction::Delete),
+        ] {
+            assert_eq!(
+                root.on_key(key(KeyCode::Char(hotkey))),
+                Some(RootMenuExit::Action(action))
+            );
+        }
+        assert_eq!(
+            root.on_key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+            None
+        );
+    }
+
+    #[test]
+    fn root_screen_shows_the_overview_only_on_wide_terminals() {
+        let theme = &Theme::default();
+        let row = |name: &str, attached: Option<&str>, running: bool| {
+            let mut row = WorktreeRow::new(
+                name,
+                format!("/src/api/worktrees/{name}"),
+                "2026-02-25T10:00:00Z",
+                WorktreeHead::Branch(format!("feat/{name}")),
+                format!("api/{name}"),
+            );
+            row.session_running = running;
+            row.last_attached_at = attached.map(str::to_string);
+            row
+        };
+        let mut rows: Vec<WorktreeRow> = (1..=5)
+            .map(|day| {
+                row(
+                    &format!("w{day}"),
+                    Some(&format!("2026-03-0{day}T09:00:00Z")),
+                    false,
+                )
+            })
+            .collect();
+        rows.push(row("idle", None, false));
+        rows[2].session_running = true;
+        let mut root = RootScreen::new();
+        root.overview = Some(RootOverview::from_list(ListResult::new("/src/api", rows)));
+
+        let render = |root: &RootScreen, width: u16| {
+            let mut terminal = Terminal::new(TestBackend::new(width, 24)).expect("terminal");
+            terminal
+                .draw(|frame| root.render(frame, theme, std::path::Path::new("/src/api")))
+                .expect("draw");
+            format!("{}", terminal.backend())
+        };
+
+        let wide = render(&root, 130);
+        assert!(wide.contains("Running sessions: 1"));
+        assert!(wide.contains("Worktrees: 6"));
+        assert!(wide.contains("w5: feat/w5, not running, attached"));
+        assert!(wide.contains("w3: feat/w3, running"));
+        assert!(!wide.contains("idle: "));
+        assert!(wide.contains("n  New worktree"));
+        assert!(wide.contains("n/l/a/d: new/list/attach/delete"));
+        assert!(wide.find("w5:") < wide.find("w1:"));
+
+        let narrow = render(&root, 80);
+        assert!(!narrow.contains("Recent worktrees"));
+        assert!(narrow.contains("New worktree"));
+        assert!(!narrow.contains("n  New worktree"));
+    }
+
+    #[test]
+    fn root_screen_help_lists_every_key() {
+        let screen = RootScreen::new();
+        let help = screen.help();
+        assert_eq!(help.title, "Home");
+        let keys: Vec<&str> = help.keys.iter().map(|(keys, _)| *keys).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "Up/Down or j/k",
+                "Enter",
+                "n/l/a/d",
+                "Ctrl+N",
+                "Ctrl+P",
+                "?",
+                "Esc/q"
+            ]
+        );
+        assert!(!screen.typing());
+    }
+
     #[test]
     fn root_screen_supports_j_and_k_navigation() {
         let mut root = RootScreen::new();
@@ -676,6 +1739,42 @@ mod tests {
         assert_eq!(root.selected, 1);
     }
 
+    #[test]
+    fn root_screen_mouse_selects_rows_scrolls_and_clicks_footer_hints() {
+        let theme = &Theme::default();
+        let mut root = RootScreen::new();
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).expect("terminal");
+        terminal
+            .draw(|frame| root.render(frame, theme, std::path::Path::new("/tmp/repo")))
+            .expect("draw");
+        let mouse = |kind, column, row| MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        let click = MouseEventKind::Down(MouseButton::Left);
+
+        assert_eq!(root.on_mouse(mouse(click, 10, 7)), None);
+        assert_eq!(root.selected, 2);
+
+        root.on_mouse(mouse(MouseEventKind::ScrollUp, 0, 0));
+        assert_eq!(root.selected, 1);
+
+        let quick = root.on_mouse(mouse(click, 28, 22)).expect("quick new hint");
+        assert_eq!(
+            root.on_key(quick),
+            Some(RootMenuExit::Action(RootAction::QuickNew))
+        );
+
+        let help = root.on_mouse(mouse(click, 57, 22)).expect("help hint");
+        assert_eq!(help, key(KeyCode::Char('?')));
+
+        let hint = root.on_mouse(mouse(click, 68, 22)).expect("footer hint");
+        assert_eq!(hint, key(KeyCode::Esc));
+        assert_eq!(root.on_key(hint), Some(RootMenuExit::Exit));
+    }
+
     #[test]
     fn root_loop_drain_helper_runs_new_flow_tick_on_timeout_and_after_input() {
         let mut timeout_probe = TickProbe {
@@ -1,92 +1,807 @@
+use std::collections::BTreeSet;
+use std::io;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::SystemTime;
 
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent, MouseEventKind};
 use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::style::Color;
-use ratatui::text::Line;
-use seshmux_app::{App, ListResult};
+use ratatui::text::{Line, Text};
+use seshmux_app::{
+    App, AttachRequest, AttachResult, BranchDiff, BranchDiffRequest, BranchGraph,
+    BranchGraphRequest, ListResult, ManagedSessions, SessionFilter, SessionScope, UpdateOutcome,
+    UpdateRequest, UpdateResult, WorktreeRow,
+};
+use seshmux_core::command_runner::SystemCommandRunner;
+use seshmux_core::config::WorktreeColumn;
+use seshmux_core::disk_usage::directory_size;
+use seshmux_core::registry::DiskUsage;
+use seshmux_core::time::{now_utc_rfc3339, seconds_since_rfc3339};
 
-use crate::UiExit;
-use crate::theme;
+use crate::keymap;
+use crate::theme::{Accent, Theme};
+use crate::ui::binary_choice::{BinaryChoice, BinaryChoiceEvent};
+use crate::ui::help::{CHOICE_KEYS, HelpProvider, ScreenHelp};
+use crate::ui::modal::{ModalSpec, render_modal, render_notice_modal};
 use crate::ui::select_step::{SelectSignal, SelectStepState};
-use crate::ui::text::{compact_hint, focus_line, key_hint_height, key_hint_paragraph};
-use crate::ui::worktree_table::{TableColumn, WorktreeTableRender};
+use crate::ui::text::{
+    compact_hint, focus_line, format_bytes, highlighted_label_value_line, key_hint_height,
+};
+use crate::ui::worktree_table::{WorktreeTableRender, configured_columns};
+use crate::{UiExit, centered_rect};
+
+const GRAPH_PAGE: isize = 10;
+const GRAPH_SCROLL_LINES: isize = 3;
+
+const LIST_COLUMNS: &[WorktreeColumn] = &[
+    WorktreeColumn::Name,
+    WorktreeColumn::Age,
+    WorktreeColumn::Size,
+    WorktreeColumn::Branch,
+    WorktreeColumn::Session,
+    WorktreeColumn::Path,
+];
+
+const SIZE_CACHE_TTL_SECS: u64 = 15 * 60;
 
 pub(crate) trait ListFlowOps {
     fn list_worktrees(&self, cwd: &Path) -> Result<ListResult>;
+    fn refresh_sessions(&self) -> Result<BTreeSet<String>>;
+    fn managed_sessions(&self, cwd: &Path, scope: SessionScope) -> Result<ManagedSessions>;
+    fn kill_sessions(&self, session_names: &[String]) -> Result<Vec<String>>;
+    fn branch_graph(&self, cwd: &Path, worktree_name: &str) -> Result<BranchGraph>;
+    fn branch_diff(&self, cwd: &Path, worktree_name: &str, patch: bool) -> Result<BranchDiff>;
+    fn record_disk_usage(&self, cwd: &Path, sizes: &[(String, u64)]) -> Result<()>;
+    fn spawn_update(&self, cwd: &Path, worktree_name: &str) -> Receiver<UpdateEvent>;
+    fn attach_worktree(&self, request: AttachRequest) -> Result<AttachResult>;
 }
 
 impl<'a> ListFlowOps for App<'a> {
     fn list_worktrees(&self, cwd: &Path) -> Result<ListResult> {
-        self.list(cwd)
+        Ok(self.list(cwd)?)
+    }
+
+    fn refresh_sessions(&self) -> Result<BTreeSet<String>> {
+        Ok(App::refresh_sessions(self)?)
+    }
+
+    fn managed_sessions(&self, cwd: &Path, scope: SessionScope) -> Result<ManagedSessions> {
+        Ok(App::managed_sessions(self, cwd, scope)?)
+    }
+
+    fn kill_sessions(&self, session_names: &[String]) -> Result<Vec<String>> {
+        Ok(App::kill_sessions(self, session_names)?)
+    }
+
+    fn branch_graph(&self, cwd: &Path, worktree_name: &str) -> Result<BranchGraph> {
+        Ok(App::branch_graph(
+            self,
+            BranchGraphRequest {
+                cwd: cwd.to_path_buf(),
+                worktree_name: worktree_name.to_string(),
+            },
+        )?)
+    }
+
+    fn branch_diff(&self, cwd: &Path, worktree_name: &str, patch: bool) -> Result<BranchDiff> {
+        Ok(App::branch_diff(
+            self,
+            BranchDiffRequest {
+                cwd: cwd.to_path_buf(),
+                worktree_name: worktree_name.to_string(),
+                patch,
+            },
+        )?)
+    }
+
+    fn record_disk_usage(&self, cwd: &Path, sizes: &[(String, u64)]) -> Result<()> {
+        Ok(App::record_disk_usage(self, cwd, sizes)?)
+    }
+
+    fn spawn_update(&self, cwd: &Path, worktree_name: &str) -> Receiver<UpdateEvent> {
+        let request = UpdateRequest {
+            cwd: cwd.to_path_buf(),
+            worktree_name: worktree_name.to_string(),
+        };
+        let policy = self.runner.policy();
+        let load_options = self.load_options().clone();
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let runner = SystemCommandRunner::with_policy(policy);
+            let app = App::new(&runner).with_load_options(load_options);
+            let result = app.update_worktree(request, &mut |line| {
+                let _ = sender.send(UpdateEvent::Line(line));
+            });
+            let _ = sender.send(UpdateEvent::Done(result.map_err(anyhow::Error::from)));
+        });
+        receiver
+    }
+
+    fn attach_worktree(&self, request: AttachRequest) -> Result<AttachResult> {
+        Ok(self.attach(request)?)
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 enum FlowSignal {
     Continue,
+    Open(String),
     Exit(UiExit),
 }
 
 #[derive(Debug)]
 struct ListFlow {
     select: SelectStepState,
+    kill_all: Option<KillAllPrompt>,
+    notice: Option<String>,
+    graph: Option<GraphPopup>,
+    diff: Option<DiffPopup>,
+    sizes: Option<SizeScan>,
+    update: Option<UpdatePane>,
+}
+
+#[derive(Debug)]
+struct SizeScan {
+    results: Receiver<(String, io::Result<u64>)>,
+    pending: usize,
+    measured: Vec<(String, u64)>,
+}
+
+impl SizeScan {
+    fn start(rows: &[WorktreeRow]) -> Option<Self> {
+        let now = SystemTime::now();
+        let stale: Vec<(String, PathBuf)> = rows
+            .iter()
+            .filter(|row| {
+                row.disk_usage
+                    .as_ref()
+                    .and_then(|usage| seconds_since_rfc3339(&usage.measured_at, now))
+                    .is_none_or(|age| age >= SIZE_CACHE_TTL_SECS)
+            })
+            .map(|row| (row.name.clone(), row.path.clone()))
+            .collect();
+        if stale.is_empty() {
+            return None;
+        }
+
+        let (sender, results) = mpsc::channel();
+        let pending = stale.len();
+        std::thread::spawn(move || {
+            for (name, path) in stale {
+                // The receiver is dropped when the rows are refreshed; stop measuring then.
+                if sender.send((name, directory_size(&path))).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Some(Self {
+            results,
+            pending,
+            measured: Vec::new(),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct GraphPopup {
+    graph: BranchGraph,
+    scroll: usize,
+}
+
+impl GraphPopup {
+    fn scroll_by(&mut self, delta: isize) {
+        let max = self.graph.lines.len().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+}
+
+#[derive(Debug)]
+struct DiffPopup {
+    diff: BranchDiff,
+    scroll: usize,
+}
+
+impl DiffPopup {
+    fn scroll_by(&mut self, delta: isize) {
+        let max = self.diff.lines.len().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum UpdateEvent {
+    Line(String),
+    Done(Result<UpdateResult>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum UpdateState {
+    Running,
+    Finished(UpdateOutcome),
+    Failed,
+}
+
+#[derive(Debug)]
+struct UpdatePane {
+    worktree_name: String,
+    events: Receiver<UpdateEvent>,
+    lines: Vec<String>,
+    scroll_back: usize,
+    state: UpdateState,
+}
+
+impl UpdatePane {
+    fn new(worktree_name: String, events: Receiver<UpdateEvent>) -> Self {
+        Self {
+            worktree_name,
+            events,
+            lines: Vec::new(),
+            scroll_back: 0,
+            state: UpdateState::Running,
+        }
+    }
+
+    fn scroll_by(&mut self, delta: isize) {
+        let max = self.lines.len().saturating_sub(1);
+        self.scroll_back = self.scroll_back.saturating_add_signed(-delta).min(max);
+    }
+
+    fn has_conflicts(&self) -> bool {
+        matches!(
+            self.state,
+            UpdateState::Finished(UpdateOutcome::Conflicts { .. })
+        )
+    }
+}
+
+#[derive(Debug)]
+struct KillAllPrompt {
+    scope: SessionScope,
+    session_names: Vec<String>,
+    current: Option<String>,
+    choice: BinaryChoice,
 }
 
 pub(crate) struct ListScreen {
     flow: ListFlow,
     cwd: PathBuf,
+    pending_open: Option<String>,
 }
 
 impl ListScreen {
     pub(crate) fn new(app: &App<'_>, cwd: &Path) -> Result<Self> {
+        let mut flow = ListFlow::new(app, cwd)?;
+        if let Some(columns) = configured_columns(app) {
+            flow.select.set_columns(&columns);
+        }
         Ok(Self {
-            flow: ListFlow::new(app, cwd)?,
+            flow,
             cwd: cwd.to_path_buf(),
+            pending_open: None,
         })
     }
 
-    pub(crate) fn render(&self, frame: &mut ratatui::Frame<'_>) {
-        self.flow.render(frame);
+    pub(crate) fn refresh_sessions(&mut self, app: &App<'_>) -> Result<()> {
+        self.flow.refresh_sessions(app)
+    }
+
+    pub(crate) fn poll_sizes(&mut self, app: &App<'_>) -> Result<bool> {
+        self.flow.poll_sizes(app, &self.cwd)
+    }
+
+    pub(crate) fn measuring_sizes(&self) -> bool {
+        self.flow.sizes.is_some()
+    }
+
+    pub(crate) fn poll_update(&mut self) -> bool {
+        self.flow.poll_update()
+    }
+
+    pub(crate) fn updating(&self) -> bool {
+        self.flow
+            .update
+            .as_ref()
+            .is_some_and(|pane| pane.state == UpdateState::Running)
+    }
+
+    pub(crate) fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        self.flow.render(frame, theme);
+    }
+
+    pub(crate) fn selected_worktree(&self) -> Option<&str> {
+        self.flow.select.selected_row().map(|row| row.name.as_str())
+    }
+
+    pub(crate) fn take_pending_copy(&mut self) -> Option<String> {
+        self.flow.select.take_pending_copy()
+    }
+
+    pub(crate) fn on_paste(&mut self, text: &str) {
+        self.flow.select.on_paste(text);
+    }
+
+    pub(crate) fn on_mouse(&mut self, mouse: MouseEvent) -> Option<KeyEvent> {
+        self.flow.on_mouse(mouse)
     }
 
     pub(crate) fn on_key(&mut self, key: KeyEvent, app: &App<'_>) -> Result<Option<UiExit>> {
         match self.flow.on_key(key, app, &self.cwd)? {
             FlowSignal::Continue => Ok(None),
+            FlowSignal::Open(name) => {
+                self.pending_open = Some(name);
+                Ok(None)
+            }
             FlowSignal::Exit(exit) => Ok(Some(exit)),
         }
     }
+
+    pub(crate) fn take_pending_open(&mut self) -> Option<String> {
+        self.pending_open.take()
+    }
+
+    pub(crate) fn cwd(&self) -> &Path {
+        &self.cwd
+    }
+}
+
+impl HelpProvider for ListScreen {
+    fn help(&self) -> ScreenHelp {
+        self.flow.help()
+    }
+
+    fn typing(&self) -> bool {
+        !self.flow.popup_open() && self.flow.select.filter_focused()
+    }
 }
 
 impl ListFlow {
     fn new(ops: &dyn ListFlowOps, cwd: &Path) -> Result<Self> {
         let result = ops.list_worktrees(cwd)?;
+        let sizes = SizeScan::start(&result.rows);
+        let mut select = SelectStepState::new(result.rows);
+        select.set_columns(LIST_COLUMNS);
         Ok(Self {
-            select: SelectStepState::new(result.rows),
+            select,
+            kill_all: None,
+            notice: None,
+            graph: None,
+            diff: None,
+            sizes,
+            update: None,
         })
     }
 
+    fn popup_open(&self) -> bool {
+        self.notice.is_some()
+            || self.update.is_some()
+            || self.graph.is_some()
+            || self.diff.is_some()
+            || self.kill_all.is_some()
+    }
+
+    fn help(&self) -> ScreenHelp {
+        if self.notice.is_some() {
+            return ScreenHelp::new("Notice", "The result of the last action.")
+                .keys(&[("Enter/Esc", "close")]);
+        }
+        if let Some(pane) = &self.update {
+            let help = ScreenHelp::new(
+                "Update branch",
+                "Output of fetching and rebasing or merging the worktree's branch onto its upstream.",
+            )
+            .keys(&[
+                ("Up/Down or j/k", "scroll"),
+                ("PgUp/PgDn", "scroll a page"),
+            ]);
+            let help = if pane.has_conflicts() {
+                help.keys(&[("a", "attach to the session to resolve the conflicts")])
+            } else {
+                help
+            };
+            return help.keys(&[("Enter/Esc", "close and reload the rows")]);
+        }
+        if self.graph.is_some() {
+            return ScreenHelp::new(
+                "Branch graph",
+                "Commits on the worktree's branch compared with its base.",
+            )
+            .keys(&[
+                ("Up/Down or j/k", "scroll"),
+                ("PgUp/PgDn", "scroll a page"),
+                ("b/Enter/Esc", "close"),
+            ]);
+        }
+        if let Some(popup) = &self.diff {
+            let toggle = if popup.diff.patch {
+                ("p", "back to the summary")
+            } else {
+                ("p", "show the full patch")
+            };
+            return ScreenHelp::new(
+                "Diff",
+                "Files changed in the worktree since it forked from its base, including uncommitted changes.",
+            )
+            .keys(&[
+                ("Up/Down or j/k", "scroll"),
+                ("PgUp/PgDn", "scroll a page"),
+                toggle,
+                ("d/Enter/Esc", "close"),
+            ]);
+        }
+        if self.kill_all.is_some() {
+            return ScreenHelp::new(
+                "Kill all sessions",
+                "Kills the listed tmux sessions seshmux manages. The worktrees are kept.",
+            )
+            .keys(&[(
+                "a",
+                "switch between this repository, the repositories under [dash], and other repositories too",
+            )])
+            .keys(CHOICE_KEYS);
+        }
+
+        ScreenHelp::new(
+            "List worktrees",
+            "Every worktree registered for this repository with its branch, tmux session, and size.",
+        )
+        .keys(&self.select.help_keys(
+            "reload the rows",
+            &[
+                ("r", "reload the rows"),
+                ("f", "show all, running only, or not running only"),
+                ("b", "show the branch graph"),
+                ("d", "show what changed against the base branch"),
+                ("u", "update the branch from its upstream"),
+                ("o", "open the worktree in your editor"),
+                ("K", "kill every session for this repository"),
+            ],
+        ))
+    }
+
+    fn reload_rows(&mut self, ops: &dyn ListFlowOps, cwd: &Path) -> Result<()> {
+        let result = ops.list_worktrees(cwd)?;
+        self.sizes = SizeScan::start(&result.rows);
+        self.select.set_rows(result.rows);
+        Ok(())
+    }
+
+    fn poll_sizes(&mut self, ops: &dyn ListFlowOps, cwd: &Path) -> Result<bool> {
+        let Some(scan) = &mut self.sizes else {
+            return Ok(false);
+        };
+
+        let mut changed = false;
+        loop {
+            match scan.results.try_recv() {
+                Ok((name, measured)) => {
+                    changed = true;
+                    scan.pending = scan.pending.saturating_sub(1);
+                    if let Ok(bytes) = measured {
+                        self.select.set_disk_usage(
+                            &name,
+                            DiskUsage::new(bytes, now_utc_rfc3339().unwrap_or_default()),
+                        );
+                        scan.measured.push((name, bytes));
+                    }
+                }
+                Err(TryRecvError::Empty) => return Ok(changed),
+                Err(TryRecvError::Disconnected) => break,
+            }
+        }
+
+        let scan = self.sizes.take().expect("size scan is running");
+        if !scan.measured.is_empty() {
+            ops.record_disk_usage(cwd, &scan.measured)?;
+        }
+        Ok(true)
+    }
+
+    fn table_title(&self) -> String {
+        let rows = self.select.rows();
+        let total: u64 = rows
+            .iter()
+            .filter_map(|row| row.disk_usage.as_ref())
+            .map(|usage| usage.bytes)
+            .sum();
+        let mut title = format!(
+            "Browse worktrees - {} worktree(s), {} total",
+            rows.len(),
+            format_bytes(total)
+        );
+        if let Some(scan) = &self.sizes {
+            title.push_str(&format!(" (measuring {}…)", scan.pending));
+        }
+        let filter = self.select.session_filter();
+        if filter != SessionFilter::All {
+            title.push_str(&format!(" [{}]", filter.label()));
+        }
+        title
+    }
+
+    fn refresh_sessions(&mut self, ops: &dyn ListFlowOps) -> Result<()> {
+        let running = ops.refresh_sessions()?;
+        self.select.update_session_status(&running);
+        Ok(())
+    }
+
+    fn poll_update(&mut self) -> bool {
+        let Some(pane) = self.update.as_mut() else {
+            return false;
+        };
+
+        let mut changed = false;
+        while pane.state == UpdateState::Running {
+            let event = match pane.events.try_recv() {
+                Ok(event) => event,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    UpdateEvent::Done(Err(anyhow::anyhow!("the update stopped unexpectedly")))
+                }
+            };
+            changed = true;
+            match event {
+                UpdateEvent::Line(line) => pane.lines.push(line),
+                UpdateEvent::Done(Ok(result)) => {
+                    match &result.outcome {
+                        UpdateOutcome::Updated => pane.lines.push(format!(
+                            "Updated {} onto {}.",
+                            result.branch, result.upstream
+                        )),
+                        UpdateOutcome::Conflicts { paths } => {
+                            pane.lines.push(format!(
+                                "Conflicts in {} file(s); resolve them in the worktree's session:",
+                                paths.len()
+                            ));
+                            pane.lines
+                                .extend(paths.iter().map(|path| format!("  {path}")));
+                        }
+                    }
+                    pane.state = UpdateState::Finished(result.outcome);
+                }
+                UpdateEvent::Done(Err(error)) => {
+                    pane.lines.push(format!("error: {error:#}"));
+                    pane.state = UpdateState::Failed;
+                }
+            }
+        }
+        changed
+    }
+
+    fn on_key_update(
+        &mut self,
+        key: KeyEvent,
+        ops: &dyn ListFlowOps,
+        cwd: &Path,
+    ) -> Result<FlowSignal> {
+        let Some(pane) = self.update.as_mut() else {
+            return Ok(FlowSignal::Continue);
+        };
+        if pane.state == UpdateState::Running {
+            return Ok(FlowSignal::Continue);
+        }
+
+        if key.code == KeyCode::Char('a') && pane.has_conflicts() {
+            let worktree_name = pane.worktree_name.clone();
+            let mut request = AttachRequest::new(cwd.to_path_buf(), worktree_name);
+            request.create_if_missing = true;
+            request.connect = true;
+            match ops.attach_worktree(request) {
+                Ok(result) => {
+                    self.update = None;
+                    self.reload_rows(ops, cwd)?;
+                    self.notice = Some(format!(
+                        "Attached to tmux session {} to resolve the conflicts.",
+                        result.session_name
+                    ));
+                }
+                Err(error) => pane.lines.push(format!("error: {error:#}")),
+            }
+        } else if keymap::is_back(key) || keymap::is_confirm(key) {
+            self.update = None;
+            self.reload_rows(ops, cwd)?;
+        } else if keymap::is_down(key) {
+            pane.scroll_by(1);
+        } else if keymap::is_up(key) {
+            pane.scroll_by(-1);
+        } else if key.code == KeyCode::PageDown {
+            pane.scroll_by(GRAPH_PAGE);
+        } else if key.code == KeyCode::PageUp {
+            pane.scroll_by(-GRAPH_PAGE);
+        }
+        Ok(FlowSignal::Continue)
+    }
+
     fn on_key(&mut self, key: KeyEvent, ops: &dyn ListFlowOps, cwd: &Path) -> Result<FlowSignal> {
+        if self.notice.is_some() {
+            if keymap::is_back(key) || keymap::is_confirm(key) {
+                self.notice = None;
+            }
+            return Ok(FlowSignal::Continue);
+        }
+
+        if self.update.is_some() {
+            return self.on_key_update(key, ops, cwd);
+        }
+
+        if let Some(popup) = &mut self.graph {
+            if keymap::is_back(key) || keymap::is_confirm(key) || key.code == KeyCode::Char('b') {
+                self.graph = None;
+            } else if keymap::is_down(key) {
+                popup.scroll_by(1);
+            } else if keymap::is_up(key) {
+                popup.scroll_by(-1);
+            } else if key.code == KeyCode::PageDown {
+                popup.scroll_by(GRAPH_PAGE);
+            } else if key.code == KeyCode::PageUp {
+                popup.scroll_by(-GRAPH_PAGE);
+            }
+            return Ok(FlowSignal::Continue);
+        }
+
+        if let Some(popup) = &mut self.diff {
+            if keymap::is_back(key) || keymap::is_confirm(key) || key.code == KeyCode::Char('d') {
+                self.diff = None;
+            } else if key.code == KeyCode::Char('p') {
+                let diff = ops.branch_diff(cwd, &popup.diff.worktree_name, !popup.diff.patch)?;
+                *popup = DiffPopup { diff, scroll: 0 };
+            } else if keymap::is_down(key) {
+                popup.scroll_by(1);
+            } else if keymap::is_up(key) {
+                popup.scroll_by(-1);
+            } else if key.code == KeyCode::PageDown {
+                popup.scroll_by(GRAPH_PAGE);
+            } else if key.code == KeyCode::PageUp {
+                popup.scroll_by(-GRAPH_PAGE);
+            }
+            return Ok(FlowSignal::Continue);
+        }
+
+        if let Some(prompt) = &mut self.kill_all {
+            if key.code == KeyCode::Char('a') {
+                prompt.scope = match prompt.scope {
+                    SessionScope::Repo => SessionScope::Known,
+                    SessionScope::Known => SessionScope::All,
+                    SessionScope::All => SessionScope::Repo,
+                };
+                let sessions = ops.managed_sessions(cwd, prompt.scope)?;
+                prompt.session_names = sessions.session_names;
+                prompt.current = sessions.current;
+                return Ok(FlowSignal::Continue);
+            }
+            match prompt.choice.on_key(key) {
+                BinaryChoiceEvent::Continue => {}
+                BinaryChoiceEvent::Back | BinaryChoiceEvent::ConfirmNo => self.kill_all = None,
+                BinaryChoiceEvent::ConfirmYes => {
+                    let Some(prompt) = self.kill_all.take() else {
+                        return Ok(FlowSignal::Continue);
+                    };
+                    let targets: Vec<String> = prompt
+                        .session_names
+                        .into_iter()
+                        .filter(|name| Some(name) != prompt.current.as_ref())
+                        .collect();
+                    let killed = ops.kill_sessions(&targets)?;
+                    self.refresh_sessions(ops)?;
+                    let mut notice = format!(
+                        "Killed {} tmux session(s):\n{}",
+                        killed.len(),
+                        killed.join("\n")
+                    );
+                    if let Some(current) = prompt.current {
+                        notice.push_str(&format!("\nKept {current}: seshmux is running in it."));
+                    }
+                    self.notice = Some(notice);
+                }
+            }
+            return Ok(FlowSignal::Continue);
+        }
+
         match self.select.on_key(key) {
             SelectSignal::Back => return Ok(FlowSignal::Exit(UiExit::BackAtRoot)),
             SelectSignal::Continue => {}
             SelectSignal::Confirm => {
-                let result = ops.list_worktrees(cwd)?;
-                self.select.set_rows(result.rows);
+                self.reload_rows(ops, cwd)?;
                 return Ok(FlowSignal::Continue);
             }
         }
 
-        if key.code == KeyCode::Char('r') && !self.select.filter_focused() {
-            let result = ops.list_worktrees(cwd)?;
-            self.select.set_rows(result.rows);
+        let shortcuts_active = !self.select.filter_focused() && !self.select.details_open();
+        if key.code == KeyCode::Char('r') && shortcuts_active {
+            self.reload_rows(ops, cwd)?;
+        }
+
+        if key.code == KeyCode::Char('f') && shortcuts_active {
+            let filter = self.select.session_filter().next();
+            self.select.set_session_filter(filter);
+            return Ok(FlowSignal::Continue);
+        }
+
+        if key.code == KeyCode::Char('K') && shortcuts_active {
+            let sessions = ops.managed_sessions(cwd, SessionScope::Repo)?;
+            if sessions.session_names.is_empty() {
+                self.notice = Some("No tmux sessions are running for this repository.".to_string());
+            } else {
+                self.kill_all = Some(KillAllPrompt {
+                    scope: SessionScope::Repo,
+                    session_names: sessions.session_names,
+                    current: sessions.current,
+                    choice: BinaryChoice::new(false),
+                });
+            }
+            return Ok(FlowSignal::Continue);
+        }
+
+        if key.code == KeyCode::Char('b')
+            && shortcuts_active
+            && let Some(row) = self.select.selected_row()
+        {
+            let graph = ops.branch_graph(cwd, &row.name)?;
+            self.graph = Some(GraphPopup { graph, scroll: 0 });
+            return Ok(FlowSignal::Continue);
+        }
+
+        if key.code == KeyCode::Char('d')
+            && shortcuts_active
+            && let Some(row) = self.select.selected_row()
+        {
+            let diff = ops.branch_diff(cwd, &row.name, false)?;
+            self.diff = Some(DiffPopup { diff, scroll: 0 });
+            return Ok(FlowSignal::Continue);
+        }
+
+        if key.code == KeyCode::Char('o')
+            && shortcuts_active
+            && let Some(row) = self.select.selected_row()
+        {
+            return Ok(FlowSignal::Open(row.name.clone()));
+        }
+
+        if key.code == KeyCode::Char('u')
+            && shortcuts_active
+            && let Some(row) = self.select.selected_row()
+        {
+            let events = ops.spawn_update(cwd, &row.name);
+            self.update = Some(UpdatePane::new(row.name.clone(), events));
+            return Ok(FlowSignal::Continue);
         }
 
         Ok(FlowSignal::Continue)
     }
 
-    fn render(&self, frame: &mut ratatui::Frame<'_>) {
+    fn on_mouse(&mut self, mouse: MouseEvent) -> Option<KeyEvent> {
+        if let Some(pane) = self.update.as_mut() {
+            match mouse.kind {
+                MouseEventKind::ScrollUp => pane.scroll_by(-GRAPH_SCROLL_LINES),
+                MouseEventKind::ScrollDown => pane.scroll_by(GRAPH_SCROLL_LINES),
+                _ => {}
+            }
+            return None;
+        }
+        if let Some(graph) = self.graph.as_mut() {
+            match mouse.kind {
+                MouseEventKind::ScrollUp => graph.scroll_by(-GRAPH_SCROLL_LINES),
+                MouseEventKind::ScrollDown => graph.scroll_by(GRAPH_SCROLL_LINES),
+                _ => {}
+            }
+            return None;
+        }
+        if let Some(diff) = self.diff.as_mut() {
+            match mouse.kind {
+                MouseEventKind::ScrollUp => diff.scroll_by(-GRAPH_SCROLL_LINES),
+                MouseEventKind::ScrollDown => diff.scroll_by(GRAPH_SCROLL_LINES),
+                _ => {}
+            }
+            return None;
+        }
+        if self.kill_all.is_some() || self.notice.is_some() {
+            return None;
+        }
+        self.select.on_mouse(mouse)
+    }
+
+    fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let area = frame.area();
         let key_text = if self.select.filter_focused() {
             compact_hint(
@@ -98,9 +813,9 @@ impl ListFlow {
         } else {
             compact_hint(
                 area.width,
-                "/: filter focus    Up/Down or j/k: move    Enter/r: refresh    Esc: back",
-                "/: filter    j/k: move    Enter/r: refresh    Esc: back",
-                "/ filter | j/k move | Enter refresh | Esc back",
+                "/: filter focus    Up/Down or j/k: move    s/S: sort/reverse    g: group running    f: session filter    e: details    y: copy path    b: branch graph    d: diff    u: update branch    o: open in editor    K: kill all sessions    Enter/r: refresh    Esc: back",
+                "/: filter    j/k: move    s/S: sort    g: group    f: sessions    e: details    y: copy    b: graph    d: diff    u: update    o: open    K: kill all    Enter/r: refresh    Esc: back",
+                "/ filter | j/k move | s sort | g group | o open | Esc back",
             )
         };
         let footer_height = key_hint_height(area.width, key_text);
@@ -117,89 +832,353 @@ impl ListFlow {
         self.select.render_filter(
             frame,
             filter_area,
-            focus_line("Filter"),
+            focus_line(theme, "Filter"),
             Line::from("Filter (/ to focus)"),
         );
 
-        let columns = [
-            TableColumn {
-                title: "Name",
-                width: Constraint::Length(24),
-            },
-            TableColumn {
-                title: "Created",
-                width: Constraint::Length(28),
-            },
-            TableColumn {
-                title: "Branch",
-                width: Constraint::Length(20),
-            },
-            TableColumn {
-                title: "Session",
-                width: Constraint::Length(14),
-            },
-            TableColumn {
-                title: "Path",
-                width: Constraint::Min(24),
-            },
-        ];
-
         self.select.render_table(
             frame,
             body,
             WorktreeTableRender {
                 title: if filter_focused {
-                    Line::from("Browse worktrees (/ to focus)")
+                    Line::from(format!("{} (/ to focus)", self.table_title()))
                 } else {
-                    focus_line("Browse worktrees")
+                    focus_line(theme, self.table_title())
                 },
-                empty_message: "No worktrees are registered.",
-                columns: &columns,
-                header_style: theme::table_header(Color::Cyan),
-                highlight_style: theme::table_highlight(Color::Cyan),
-            },
-            |row| {
-                let status = if row.session_running {
-                    "running"
+                empty_message: if self.select.rows().is_empty() {
+                    "No worktrees are registered."
                 } else {
-                    "not running"
-                };
-                vec![
-                    row.name.clone(),
-                    row.created_at.clone(),
-                    row.branch.clone(),
-                    status.to_string(),
-                    row.path.display().to_string(),
-                ]
+                    "No worktrees match the filters."
+                },
+                header_style: theme.table_header(Accent::Browse),
+                highlight_style: theme.table_highlight(Accent::Browse),
             },
         );
 
-        let keys = key_hint_paragraph(key_text).block(theme::key_block());
-        frame.render_widget(keys, footer);
+        self.select.render_keys(frame, footer, key_text);
+        self.select.render_details(frame, theme);
+
+        if let Some(popup) = &self.graph {
+            render_graph_popup(frame, theme, popup);
+        }
+        if let Some(popup) = &self.diff {
+            render_diff_popup(frame, theme, popup);
+        }
+        if let Some(pane) = &self.update {
+            render_update_pane(frame, theme, pane);
+        }
+        if let Some(prompt) = &self.kill_all {
+            render_kill_all_prompt(frame, theme, prompt);
+        }
+        if let Some(notice) = &self.notice {
+            render_notice_modal(frame, theme, "Sessions", notice, 60, 40, "Enter/Esc: close");
+        }
     }
 }
 
+fn render_graph_popup(frame: &mut ratatui::Frame<'_>, theme: &Theme, popup: &GraphPopup) {
+    let graph = &popup.graph;
+    let title = match &graph.base {
+        Some(base) => format!("Branch graph: {} vs {base}", graph.branch),
+        None => format!("Branch graph: {}", graph.branch),
+    };
+    let key_text = compact_hint(
+        frame.area().width,
+        "Up/Down or j/k: scroll    PgUp/PgDn: page    b/Esc: close",
+        "j/k: scroll    PgUp/PgDn: page    Esc: close",
+        "j/k scroll | Esc close",
+    );
+    let lines: Vec<Line<'_>> = graph
+        .lines
+        .iter()
+        .skip(popup.scroll)
+        .map(|line| Line::from(line.as_str()))
+        .collect();
+
+    render_modal(
+        frame,
+        theme,
+        ModalSpec {
+            title: &title,
+            title_style: Some(theme.focus_prompt()),
+            body: Text::from(lines),
+            key_hint: Some(key_text),
+            width_pct: 85,
+            height_pct: 70,
+        },
+    );
+}
+
+fn render_diff_popup(frame: &mut ratatui::Frame<'_>, theme: &Theme, popup: &DiffPopup) {
+    let diff = &popup.diff;
+    let title = match &diff.base {
+        Some(base) => format!("Diff: {} vs {base}", diff.branch),
+        None => format!("Diff: {} (uncommitted changes)", diff.branch),
+    };
+    let key_text = if diff.patch {
+        compact_hint(
+            frame.area().width,
+            "Up/Down or j/k: scroll    PgUp/PgDn: page    p: summary only    d/Esc: close",
+            "j/k: scroll    PgUp/PgDn: page    p: summary    Esc: close",
+            "j/k scroll | p summary | Esc close",
+        )
+    } else {
+        compact_hint(
+            frame.area().width,
+            "Up/Down or j/k: scroll    PgUp/PgDn: page    p: full patch    d/Esc: close",
+            "j/k: scroll    PgUp/PgDn: page    p: patch    Esc: close",
+            "j/k scroll | p patch | Esc close",
+        )
+    };
+    let lines: Vec<Line<'_>> = if diff.lines.is_empty() {
+        vec![Line::from(match &diff.base {
+            Some(base) => format!("No changes against {base}."),
+            None => "No uncommitted changes.".to_string(),
+        })]
+    } else {
+        diff.lines
+            .iter()
+            .skip(popup.scroll)
+            .map(|line| Line::from(line.as_str()))
+            .collect()
+    };
+
+    render_modal(
+        frame,
+        theme,
+        ModalSpec {
+            title: &title,
+            title_style: Some(theme.focus_prompt()),
+            body: Text::from(lines),
+            key_hint: Some(key_text),
+            width_pct: 85,
+            height_pct: 70,
+        },
+    );
+}
+
+fn render_update_pane(frame: &mut ratatui::Frame<'_>, theme: &Theme, pane: &UpdatePane) {
+    let (title, title_style) = match &pane.state {
+        UpdateState::Running => (
+            format!("Updating {}…", pane.worktree_name),
+            theme.focus_prompt(),
+        ),
+        UpdateState::Finished(UpdateOutcome::Updated) => (
+            format!("Updated {}", pane.worktree_name),
+            theme.success_prompt(),
+        ),
+        UpdateState::Finished(UpdateOutcome::Conflicts { .. }) => (
+            format!("Conflicts updating {}", pane.worktree_name),
+            theme.error_prompt(),
+        ),
+        UpdateState::Failed => (
+            format!("Failed to update {}", pane.worktree_name),
+            theme.error_prompt(),
+        ),
+    };
+    let key_text = match &pane.state {
+        UpdateState::Running => "Running git…",
+        UpdateState::Finished(UpdateOutcome::Conflicts { .. }) => compact_hint(
+            frame.area().width,
+            "a: attach to session    Up/Down or j/k: scroll    PgUp/PgDn: page    Enter/Esc: close",
+            "a: attach    j/k: scroll    Enter/Esc: close",
+            "a attach | j/k scroll | Esc close",
+        ),
+        _ => compact_hint(
+            frame.area().width,
+            "Up/Down or j/k: scroll    PgUp/PgDn: page    Enter/Esc: close",
+            "j/k: scroll    PgUp/PgDn: page    Esc: close",
+            "j/k scroll | Esc close",
+        ),
+    };
+
+    let (width_pct, height_pct) = (85, 70);
+    let viewport = usize::from(
+        centered_rect(width_pct, height_pct, frame.area())
+            .height
+            .saturating_sub(2),
+    );
+    let end = pane.lines.len().saturating_sub(pane.scroll_back);
+    let start = end.saturating_sub(viewport);
+    let lines: Vec<Line<'_>> = pane.lines[start..end]
+        .iter()
+        .map(|line| Line::from(line.as_str()))
+        .collect();
+
+    render_modal(
+        frame,
+        theme,
+        ModalSpec {
+            title: &title,
+            title_style: Some(title_style),
+            body: Text::from(lines),
+            key_hint: Some(key_text),
+            width_pct,
+            height_pct,
+        },
+    );
+}
+
+fn render_kill_all_prompt(frame: &mut ratatui::Frame<'_>, theme: &Theme, prompt: &KillAllPrompt) {
+    let key_text = compact_hint(
+        frame.area().width,
+        "Space: toggle    Enter: confirm    a: scope    Esc: cancel",
+        "Space toggle    Enter confirm    a scope    Esc cancel",
+        "Space toggle | Enter confirm | a scope | Esc cancel",
+    );
+    let mut lines = vec![
+        highlighted_label_value_line(theme, "Current Selection", prompt.choice.selected_label()),
+        Line::from(""),
+    ];
+    if prompt.session_names.is_empty() {
+        lines.push(Line::from("  No seshmux sessions are running."));
+    }
+    lines.extend(prompt.session_names.iter().map(|name| {
+        if Some(name) == prompt.current.as_ref() {
+            Line::from(format!("  {name} (kept: seshmux is running in it)"))
+        } else {
+            Line::from(format!("  {name}"))
+        }
+    }));
+
+    render_modal(
+        frame,
+        theme,
+        ModalSpec {
+            title: match prompt.scope {
+                SessionScope::Repo => "Kill every tmux session for this repository?",
+                SessionScope::Known => "Kill every tmux session of the repositories seshmux knows?",
+                SessionScope::All => {
+                    "Kill every seshmux tmux session, including other repositories?"
+                }
+            },
+            title_style: Some(theme.focus_prompt()),
+            body: Text::from(lines),
+            key_hint: Some(key_text),
+            width_pct: 70,
+            height_pct: 50,
+        },
+    );
+}
+
 #[cfg(test)]
 mod tests {
+    use std::cell::RefCell;
+    use std::collections::BTreeSet;
     use std::path::{Path, PathBuf};
+    use std::sync::mpsc::{self, Receiver, Sender};
+    use std::time::{Duration, Instant};
 
     use anyhow::Result;
-    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use crossterm::event::{
+        KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
-    use seshmux_app::{ListResult, WorktreeRow};
+    use seshmux_app::{
+        AttachRequest, AttachResult, BranchDiff, BranchGraph, ListResult, ManagedSessions,
+        SessionScope, UpdateOutcome, UpdateResult, WorktreeHead, WorktreeRow,
+    };
+    use seshmux_core::config::UpdateStrategy;
+    use seshmux_core::registry::DiskUsage;
 
-    use super::{FlowSignal, ListFlow, ListFlowOps};
+    use super::{FlowSignal, ListFlow, ListFlowOps, UpdateEvent};
+    use crate::perf::FrameTimings;
+    use crate::theme::Theme;
 
     struct FakeOps {
         rows: Vec<WorktreeRow>,
+        recorded: RefCell<Vec<(String, u64)>>,
+        attached: RefCell<Vec<String>>,
+        current_session: Option<String>,
+        killed: RefCell<Vec<String>>,
+        update_events: RefCell<Option<Sender<UpdateEvent>>>,
     }
 
     impl ListFlowOps for FakeOps {
+        fn refresh_sessions(&self) -> Result<BTreeSet<String>> {
+            Ok(BTreeSet::new())
+        }
+
         fn list_worktrees(&self, _cwd: &Path) -> Result<ListResult> {
-            Ok(ListResult {
-                repo_root: PathBuf::from("/tmp/repo"),
-                rows: self.rows.clone(),
+            Ok(ListResult::new("/tmp/repo", self.rows.clone()))
+        }
+
+        fn managed_sessions(&self, _cwd: &Path, scope: SessionScope) -> Result<ManagedSessions> {
+            let mut session_names: Vec<String> = self
+                .rows
+                .iter()
+                .map(|row| row.session_name.clone())
+                .collect();
+            if scope != SessionScope::Repo {
+                session_names.push("other/w1".to_string());
+            }
+            session_names.extend(self.current_session.clone());
+            Ok(ManagedSessions {
+                repo_roots: vec![PathBuf::from("/tmp/repo")],
+                session_names,
+                current: self.current_session.clone(),
+            })
+        }
+
+        fn kill_sessions(&self, session_names: &[String]) -> Result<Vec<String>> {
+            self.killed
+                .borrow_mut()
+                .extend(session_names.iter().cloned());
+            Ok(session_names.to_vec())
+        }
+
+        fn branch_graph(&self, _cwd: &Path, worktree_name: &str) -> Result<BranchGraph> {
+            Ok(BranchGraph {
+                worktree_name: worktree_name.to_string(),
+                branch: worktree_name.to_string(),
+                base: Some("main".to_string()),
+                lines: (0..40)
+                    .map(|index| format!("* c{index:02} commit"))
+                    .collect(),
+            })
+        }
+
+        fn branch_diff(&self, _cwd: &Path, worktree_name: &str, patch: bool) -> Result<BranchDiff> {
+            let mut lines = vec![
+                " src/main.rs | 3 ++-".to_string(),
+                " 1 file changed, 2 insertions(+), 1 deletion(-)".to_string(),
+            ];
+            if patch {
+                lines.push("diff --git a/src/main.rs b/src/main.rs".to_string());
+            }
+            Ok(BranchDiff {
+                worktree_name: worktree_name.to_string(),
+                branch: worktree_name.to_string(),
+                base: Some("main".to_string()),
+                lines,
+                patch,
+            })
+        }
+
+        fn record_disk_usage(&self, _cwd: &Path, sizes: &[(String, u64)]) -> Result<()> {
+            self.recorded.borrow_mut().extend_from_slice(sizes);
+            Ok(())
+        }
+
+        fn spawn_update(&self, _cwd: &Path, _worktree_name: &str) -> Receiver<UpdateEvent> {
+            let (sender, receiver) = mpsc::channel();
+            *self.update_events.borrow_mut() = Some(sender);
+            receiver
+        }
+
+        fn attach_worktree(&self, request: AttachRequest) -> Result<AttachResult> {
+            self.attached
+                .borrow_mut()
+                .push(request.worktree_name.clone());
+            Ok({
+                let mut result = AttachResult::new(
+                    request.worktree_name.clone(),
+                    PathBuf::from("/tmp/repo/worktrees").join(&request.worktree_name),
+                    format!("repo/{}", request.worktree_name),
+                );
+                result.attach_status = Some(0);
+                result
             })
         }
     }
@@ -209,17 +1188,25 @@ mod tests {
     }
 
     fn render_output(flow: &ListFlow, width: u16, height: u16) -> String {
+        let theme = &Theme::default();
         let backend = TestBackend::new(width, height);
         let mut terminal = Terminal::new(backend).expect("terminal");
         terminal
-            .draw(|frame| flow.render(frame))
+            .draw(|frame| flow.render(frame, theme))
             .expect("render list flow");
         format!("{}", terminal.backend())
     }
 
     #[test]
     fn esc_on_first_step_exits_flow() {
-        let ops = FakeOps { rows: Vec::new() };
+        let ops = FakeOps {
+            rows: Vec::new(),
+            recorded: RefCell::default(),
+            attached: RefCell::default(),
+            current_session: None,
+            killed: RefCell::default(),
+            update_events: RefCell::default(),
+        };
         let mut flow = ListFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
         let signal = flow
             .on_key(key(KeyCode::Esc), &ops, Path::new("/tmp/repo"))
@@ -231,23 +1218,26 @@ mod tests {
     fn vim_navigation_moves_selection() {
         let ops = FakeOps {
             rows: vec![
-                WorktreeRow {
-                    name: "w1".to_string(),
-                    path: PathBuf::from("/tmp/repo/worktrees/w1"),
-                    created_at: "2026-02-25T10:00:00Z".to_string(),
-                    branch: "w1".to_string(),
-                    session_name: "repo/w1".to_string(),
-                    session_running: false,
-                },
-                WorktreeRow {
-                    name: "w2".to_string(),
-                    path: PathBuf::from("/tmp/repo/worktrees/w2"),
-                    created_at: "2026-02-25T11:00:00Z".to_string(),
-                    branch: "w2".to_string(),
-                    session_name: "repo/w2".to_string(),
-                    session_running: false,
-                },
+                WorktreeRow::new(
+                    "w1",
+                    "/tmp/repo/worktrees/w1",
+                    "2026-02-25T10:00:00Z",
+                    WorktreeHead::Branch("w1".to_string()),
+                    "repo/w1",
+                ),
+                WorktreeRow::new(
+                    "w2",
+                    "/tmp/repo/worktrees/w2",
+                    "2026-02-25T11:00:00Z",
+                    WorktreeHead::Branch("w2".to_string()),
+                    "repo/w2",
+                ),
             ],
+            recorded: RefCell::default(),
+            attached: RefCell::default(),
+            current_session: None,
+            killed: RefCell::default(),
+            update_events: RefCell::default(),
         };
         let mut flow = ListFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
 
@@ -260,17 +1250,73 @@ mod tests {
         assert_eq!(flow.select.selected(), 0);
     }
 
+    #[test]
+    fn mouse_clicks_select_rows_and_trigger_footer_hints() {
+        let theme = &Theme::default();
+        let rows = ["w1", "w2", "w3"]
+            .into_iter()
+            .map(|name| {
+                WorktreeRow::new(
+                    name,
+                    format!("/tmp/repo/worktrees/{name}"),
+                    "2026-02-25T10:00:00Z",
+                    WorktreeHead::Branch(name.to_string()),
+                    format!("repo/{name}"),
+                )
+            })
+            .collect();
+        let ops = FakeOps {
+            rows,
+            recorded: RefCell::default(),
+            attached: RefCell::default(),
+            current_session: None,
+            killed: RefCell::default(),
+            update_events: RefCell::default(),
+        };
+        let mut flow = ListFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+        let mut terminal = Terminal::new(TestBackend::new(60, 20)).expect("terminal");
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("draw");
+        let mouse = |kind, column, row| MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        let click = MouseEventKind::Down(MouseButton::Left);
+
+        assert_eq!(flow.on_mouse(mouse(click, 10, 7)), None);
+        assert_eq!(flow.select.selected(), 2);
+        flow.on_mouse(mouse(MouseEventKind::ScrollUp, 10, 7));
+        assert_eq!(flow.select.selected(), 0);
+
+        let footer_row = 18;
+        let hint = (0..60)
+            .filter_map(|column| flow.on_mouse(mouse(click, column, footer_row)))
+            .find(|hint| hint.code == KeyCode::Char('o'))
+            .expect("open hint");
+        let signal = flow
+            .on_key(hint, &ops, Path::new("/tmp/repo"))
+            .expect("open");
+        assert_eq!(signal, FlowSignal::Open("w1".to_string()));
+    }
+
     #[test]
     fn enter_refreshes_rows() {
         let ops = FakeOps {
-            rows: vec![WorktreeRow {
-                name: "w1".to_string(),
-                path: PathBuf::from("/tmp/repo/worktrees/w1"),
-                created_at: "2026-02-25T10:00:00Z".to_string(),
-                branch: "w1".to_string(),
-                session_name: "repo/w1".to_string(),
-                session_running: false,
-            }],
+            rows: vec![WorktreeRow::new(
+                "w1",
+                "/tmp/repo/worktrees/w1",
+                "2026-02-25T10:00:00Z",
+                WorktreeHead::Branch("w1".to_string()),
+                "repo/w1",
+            )],
+            recorded: RefCell::default(),
+            attached: RefCell::default(),
+            current_session: None,
+            killed: RefCell::default(),
+            update_events: RefCell::default(),
         };
         let mut flow = ListFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
 
@@ -279,27 +1325,300 @@ mod tests {
         assert_eq!(flow.select.filtered_len(), 1);
     }
 
+    #[test]
+    fn o_requests_editor_for_selected_row() {
+        let ops = FakeOps {
+            rows: vec![WorktreeRow::new(
+                "w1",
+                "/tmp/repo/worktrees/w1",
+                "2026-02-25T10:00:00Z",
+                WorktreeHead::Branch("w1".to_string()),
+                "repo/w1",
+            )],
+            recorded: RefCell::default(),
+            attached: RefCell::default(),
+            current_session: None,
+            killed: RefCell::default(),
+            update_events: RefCell::default(),
+        };
+        let mut flow = ListFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+
+        let signal = flow
+            .on_key(key(KeyCode::Char('o')), &ops, Path::new("/tmp/repo"))
+            .expect("open");
+        assert_eq!(signal, FlowSignal::Open("w1".to_string()));
+    }
+
+    #[test]
+    fn kill_all_requires_confirmation_and_reports_killed_sessions() {
+        let ops = FakeOps {
+            rows: vec![{
+                let mut row = WorktreeRow::new(
+                    "w1",
+                    "/tmp/repo/worktrees/w1",
+                    "2026-02-25T10:00:00Z",
+                    WorktreeHead::Branch("w1".to_string()),
+                    "repo/w1",
+                );
+                row.session_running = true;
+                row
+            }],
+            recorded: RefCell::default(),
+            attached: RefCell::default(),
+            current_session: None,
+            killed: RefCell::default(),
+            update_events: RefCell::default(),
+        };
+        let cwd = Path::new("/tmp/repo");
+        let mut flow = ListFlow::new(&ops, cwd).expect("flow");
+
+        flow.on_key(key(KeyCode::Char('K')), &ops, cwd)
+            .expect("prompt");
+        assert!(render_output(&flow, 140, 24).contains("Kill every tmux session"));
+        flow.on_key(key(KeyCode::Enter), &ops, cwd)
+            .expect("decline");
+        assert!(flow.kill_all.is_none());
+        assert!(flow.notice.is_none());
+
+        flow.on_key(key(KeyCode::Char('K')), &ops, cwd)
+            .expect("prompt");
+        flow.on_key(key(KeyCode::Char(' ')), &ops, cwd)
+            .expect("toggle");
+        flow.on_key(key(KeyCode::Enter), &ops, cwd)
+            .expect("confirm");
+        assert_eq!(
+            flow.notice.as_deref(),
+            Some("Killed 1 tmux session(s):\nrepo/w1")
+        );
+
+        flow.on_key(key(KeyCode::Esc), &ops, cwd).expect("close");
+        assert!(flow.notice.is_none());
+
+        flow.on_key(key(KeyCode::Char('K')), &ops, cwd)
+            .expect("prompt");
+        flow.on_key(key(KeyCode::Char('a')), &ops, cwd)
+            .expect("widen scope");
+        assert!(render_output(&flow, 140, 24).contains("other/w1"));
+        flow.on_key(key(KeyCode::Char(' ')), &ops, cwd)
+            .expect("toggle");
+        flow.on_key(key(KeyCode::Enter), &ops, cwd)
+            .expect("confirm");
+        assert_eq!(
+            flow.notice.as_deref(),
+            Some("Killed 2 tmux session(s):\nrepo/w1\nother/w1")
+        );
+    }
+
+    #[test]
+    fn kill_all_keeps_the_session_seshmux_runs_in() {
+        let ops = FakeOps {
+            rows: vec![{
+                let mut row = WorktreeRow::new(
+                    "w1",
+                    "/tmp/repo/worktrees/w1",
+                    "2026-02-25T10:00:00Z",
+                    WorktreeHead::Branch("w1".to_string()),
+                    "repo/w1",
+                );
+                row.session_running = true;
+                row
+            }],
+            recorded: RefCell::default(),
+            attached: RefCell::default(),
+            current_session: Some("repo/here".to_string()),
+            killed: RefCell::default(),
+            update_events: RefCell::default(),
+        };
+        let cwd = Path::new("/tmp/repo");
+        let mut flow = ListFlow::new(&ops, cwd).expect("flow");
+
+        flow.on_key(key(KeyCode::Char('K')), &ops, cwd)
+            .expect("prompt");
+        assert!(render_output(&flow, 140, 24).contains("repo/here (kept"));
+        flow.on_key(key(KeyCode::Char(' ')), &ops, cwd)
+            .expect("toggle");
+        flow.on_key(key(KeyCode::Enter), &ops, cwd)
+            .expect("confirm");
+
+        assert_eq!(*ops.killed.borrow(), vec!["repo/w1".to_string()]);
+        assert!(
+            flow.notice
+                .as_deref()
+                .is_some_and(|notice| notice.ends_with("Kept repo/here: seshmux is running in it."))
+        );
+    }
+
+    #[test]
+    fn b_opens_scrollable_branch_graph_popup() {
+        let ops = FakeOps {
+            rows: vec![WorktreeRow::new(
+                "w1",
+                "/tmp/repo/worktrees/w1",
+                "2026-02-25T10:00:00Z",
+                WorktreeHead::Branch("w1".to_string()),
+                "repo/w1",
+            )],
+            recorded: RefCell::default(),
+            attached: RefCell::default(),
+            current_session: None,
+            killed: RefCell::default(),
+            update_events: RefCell::default(),
+        };
+        let cwd = Path::new("/tmp/repo");
+        let mut flow = ListFlow::new(&ops, cwd).expect("flow");
+
+        flow.on_key(key(KeyCode::Char('b')), &ops, cwd)
+            .expect("graph");
+        let output = render_output(&flow, 140, 40);
+        assert!(output.contains("Branch graph: w1 vs main"));
+        assert!(output.contains("* c00 commit"));
+
+        flow.on_key(key(KeyCode::PageDown), &ops, cwd)
+            .expect("page");
+        flow.on_key(key(KeyCode::Char('j')), &ops, cwd)
+            .expect("down");
+        let output = render_output(&flow, 140, 40);
+        assert!(!output.contains("* c00 commit"));
+        assert!(output.contains("* c11 commit"));
+
+        flow.on_key(key(KeyCode::Esc), &ops, cwd).expect("close");
+        assert!(flow.graph.is_none());
+        assert_eq!(flow.select.selected(), 0);
+    }
+
+    #[test]
+    fn d_shows_the_diff_stat_and_p_toggles_the_patch() {
+        let ops = FakeOps {
+            rows: vec![WorktreeRow::new(
+                "w1",
+                "/tmp/repo/worktrees/w1",
+                "2026-02-25T10:00:00Z",
+                WorktreeHead::Branch("w1".to_string()),
+                "repo/w1",
+            )],
+            recorded: RefCell::default(),
+            attached: RefCell::default(),
+            current_session: None,
+            killed: RefCell::default(),
+            update_events: RefCell::default(),
+        };
+        let cwd = Path::new("/tmp/repo");
+        let mut flow = ListFlow::new(&ops, cwd).expect("flow");
+
+        flow.on_key(key(KeyCode::Char('d')), &ops, cwd)
+            .expect("diff");
+        let output = render_output(&flow, 140, 40);
+        assert!(output.contains("Diff: w1 vs main"));
+        assert!(output.contains("1 file changed, 2 insertions(+), 1 deletion(-)"));
+        assert!(!output.contains("diff --git"));
+        assert!(flow.help().keys.contains(&("p", "show the full patch")));
+
+        flow.on_key(key(KeyCode::Char('p')), &ops, cwd)
+            .expect("patch");
+        let output = render_output(&flow, 140, 40);
+        assert!(output.contains("diff --git a/src/main.rs b/src/main.rs"));
+
+        flow.on_key(key(KeyCode::Char('d')), &ops, cwd)
+            .expect("close");
+        assert!(flow.diff.is_none());
+    }
+
+    #[test]
+    fn u_streams_update_output_and_offers_attach_on_conflicts() {
+        let ops = FakeOps {
+            rows: vec![WorktreeRow::new(
+                "w1",
+                "/tmp/repo/worktrees/w1",
+                "2026-02-25T10:00:00Z",
+                WorktreeHead::Branch("w1".to_string()),
+                "repo/w1",
+            )],
+            recorded: RefCell::default(),
+            attached: RefCell::default(),
+            current_session: None,
+            killed: RefCell::default(),
+            update_events: RefCell::default(),
+        };
+        let cwd = Path::new("/tmp/repo");
+        let mut flow = ListFlow::new(&ops, cwd).expect("flow");
+
+        flow.on_key(key(KeyCode::Char('u')), &ops, cwd)
+            .expect("update");
+        let events = ops
+            .update_events
+            .borrow_mut()
+            .take()
+            .expect("update spawned");
+        assert!(!flow.poll_update());
+
+        events
+            .send(UpdateEvent::Line("$ git rebase origin/main".to_string()))
+            .expect("send line");
+        assert!(flow.poll_update());
+        let output = render_output(&flow, 140, 40);
+        assert!(output.contains("$ git rebase origin/main"));
+        assert!(output.contains("Running git…"));
+
+        events
+            .send(UpdateEvent::Line(
+                "CONFLICT (content): Merge conflict in src/lib.rs".to_string(),
+            ))
+            .expect("send line");
+        events
+            .send(UpdateEvent::Done(Ok(UpdateResult {
+                worktree_name: "w1".to_string(),
+                worktree_path: PathBuf::from("/tmp/repo/worktrees/w1"),
+                branch: "w1".to_string(),
+                upstream: "origin/main".to_string(),
+                strategy: UpdateStrategy::Rebase,
+                outcome: UpdateOutcome::Conflicts {
+                    paths: vec!["src/lib.rs".to_string()],
+                },
+            })))
+            .expect("send done");
+        assert!(flow.poll_update());
+
+        let output = render_output(&flow, 140, 40);
+        assert!(output.contains("Conflicts updating w1"));
+        assert!(output.contains("$ git rebase origin/main"));
+        assert!(output.contains("  src/lib.rs"));
+        assert!(output.contains("a: attach to session"));
+
+        flow.on_key(key(KeyCode::Char('a')), &ops, cwd)
+            .expect("attach");
+        assert_eq!(ops.attached.borrow().as_slice(), ["w1".to_string()]);
+        assert!(flow.update.is_none());
+        assert!(
+            flow.notice
+                .as_deref()
+                .is_some_and(|notice| notice.contains("repo/w1"))
+        );
+    }
+
     #[test]
     fn slash_focus_routes_text_input_to_filter() {
         let ops = FakeOps {
             rows: vec![
-                WorktreeRow {
-                    name: "w1".to_string(),
-                    path: PathBuf::from("/tmp/repo/worktrees/w1"),
-                    created_at: "2026-02-25T10:00:00Z".to_string(),
-                    branch: "w1".to_string(),
-                    session_name: "repo/w1".to_string(),
-                    session_running: false,
-                },
-                WorktreeRow {
-                    name: "w2".to_string(),
-                    path: PathBuf::from("/tmp/repo/worktrees/w2"),
-                    created_at: "2026-02-25T11:00:00Z".to_string(),
-                    branch: "w2".to_string(),
-                    session_name: "repo/w2".to_string(),
-                    session_running: false,
-                },
+                WorktreeRow::new(
+                    "w1",
+                    "/tmp/repo/worktrees/w1",
+                    "2026-02-25T10:00:00Z",
+                    WorktreeHead::Branch("w1".to_string()),
+                    "repo/w1",
+                ),
+                WorktreeRow::new(
+                    "w2",
+                    "/tmp/repo/worktrees/w2",
+                    "2026-02-25T11:00:00Z",
+                    WorktreeHead::Branch("w2".to_string()),
+                    "repo/w2",
+                ),
             ],
+            recorded: RefCell::default(),
+            attached: RefCell::default(),
+            current_session: None,
+            killed: RefCell::default(),
+            update_events: RefCell::default(),
         };
         let mut flow = ListFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
 
@@ -313,13 +1632,172 @@ mod tests {
         assert_eq!(flow.select.filtered_len(), 0);
     }
 
+    #[test]
+    fn f_cycles_the_session_filter_and_combines_with_the_text_filter() {
+        let row = |name: &str, running: bool| {
+            let mut row = WorktreeRow::new(
+                name,
+                format!("/tmp/repo/worktrees/{name}"),
+                "2026-02-25T10:00:00Z",
+                WorktreeHead::Branch(name.to_string()),
+                format!("repo/{name}"),
+            );
+            row.session_running = running;
+            row
+        };
+        let ops = FakeOps {
+            rows: vec![row("api", true), row("web", false), row("api-v2", false)],
+            recorded: RefCell::default(),
+            attached: RefCell::default(),
+            current_session: None,
+            killed: RefCell::default(),
+            update_events: RefCell::default(),
+        };
+        let cwd = Path::new("/tmp/repo");
+        let mut flow = ListFlow::new(&ops, cwd).expect("flow");
+
+        flow.on_key(key(KeyCode::Char('f')), &ops, cwd)
+            .expect("running only");
+        assert_eq!(flow.select.filtered_len(), 1);
+        assert!(flow.table_title().ends_with("[running only]"));
+
+        flow.on_key(key(KeyCode::Char('f')), &ops, cwd)
+            .expect("not running only");
+        assert_eq!(flow.select.filtered_len(), 2);
+        assert!(flow.table_title().ends_with("[not running only]"));
+
+        flow.on_key(key(KeyCode::Char('/')), &ops, cwd)
+            .expect("focus filter");
+        flow.on_key(key(KeyCode::Char('a')), &ops, cwd)
+            .expect("type filter");
+        assert_eq!(flow.select.filtered_len(), 1);
+        assert_eq!(flow.select.selected_row().expect("row").name, "api-v2");
+
+        flow.on_key(key(KeyCode::Char('/')), &ops, cwd)
+            .expect("list focus");
+        flow.on_key(key(KeyCode::Char('f')), &ops, cwd)
+            .expect("all");
+        assert_eq!(flow.select.filtered_len(), 2);
+        assert!(!flow.table_title().contains('['));
+    }
+
     #[test]
     fn select_screen_uses_browse_worktrees_title_without_prompt_duplication() {
-        let ops = FakeOps { rows: Vec::new() };
+        let ops = FakeOps {
+            rows: Vec::new(),
+            recorded: RefCell::default(),
+            attached: RefCell::default(),
+            current_session: None,
+            killed: RefCell::default(),
+            update_events: RefCell::default(),
+        };
         let flow = ListFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
 
         let output = render_output(&flow, 120, 22);
         assert!(output.contains("Browse worktrees"));
         assert!(!output.contains("Browse worktrees and refresh if needed"));
     }
+
+    #[test]
+    fn sizes_are_measured_in_the_background_and_recorded() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let path = temp.path().join("w1");
+        std::fs::create_dir_all(&path).expect("worktree dir");
+        std::fs::write(path.join("file.bin"), vec![0u8; 2_048]).expect("file");
+        let fresh = seshmux_core::time::now_utc_rfc3339().expect("now");
+        let ops = FakeOps {
+            rows: vec![
+                WorktreeRow::new(
+                    "w1",
+                    path,
+                    "2026-02-25T10:00:00Z",
+                    WorktreeHead::Branch("w1".to_string()),
+                    "repo/w1",
+                ),
+                {
+                    let mut row = WorktreeRow::new(
+                        "cached",
+                        temp.path().join("cached"),
+                        "2026-02-25T10:00:00Z",
+                        WorktreeHead::Branch("cached".to_string()),
+                        "repo/cached",
+                    );
+                    row.disk_usage = Some(DiskUsage::new(1_024, fresh));
+                    row
+                },
+            ],
+            recorded: RefCell::default(),
+            attached: RefCell::default(),
+            current_session: None,
+            killed: RefCell::default(),
+            update_events: RefCell::default(),
+        };
+        let mut flow = ListFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+        assert!(render_output(&flow, 160, 22).contains("(measuring 1…)"));
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while flow.sizes.is_some() {
+            assert!(Instant::now() < deadline, "size scan did not finish");
+            flow.poll_sizes(&ops, Path::new("/tmp/repo"))
+                .expect("poll sizes");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        assert_eq!(*ops.recorded.borrow(), vec![("w1".to_string(), 2_048)]);
+        let output = render_output(&flow, 160, 22);
+        assert!(output.contains("2 worktree(s), 3.0 KiB total"));
+        assert!(output.contains("2.0 KiB"));
+    }
+
+    // Run with `cargo test --release -p seshmux-tui -- --ignored` before touching the list render path.
+    #[test]
+    #[ignore]
+    fn list_frame_time_p99_stays_within_budget_with_1k_rows() {
+        let theme = &Theme::default();
+        const FRAME_P99_BUDGET: Duration = Duration::from_millis(16);
+
+        let ops = FakeOps {
+            rows: (0..1000)
+                .map(|index| {
+                    let mut row = WorktreeRow::new(
+                        format!("worktree-{index:04}"),
+                        format!("/tmp/repo/worktrees/worktree-{index:04}"),
+                        format!("2026-02-25T10:{:02}:{:02}Z", index / 60 % 60, index % 60),
+                        WorktreeHead::Branch(format!("feature/worktree-{index:04}")),
+                        format!("repo/worktree-{index:04}"),
+                    );
+                    row.session_running = index % 3 == 0;
+                    row
+                })
+                .collect(),
+            recorded: RefCell::default(),
+            attached: RefCell::default(),
+            current_session: None,
+            killed: RefCell::default(),
+            update_events: RefCell::default(),
+        };
+        let mut flow = ListFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+        let mut terminal = Terminal::new(TestBackend::new(160, 50)).expect("terminal");
+        let mut timings = FrameTimings::default();
+
+        for _ in 0..500 {
+            let event_started = Instant::now();
+            flow.on_key(key(KeyCode::Char('j')), &ops, Path::new("/tmp/repo"))
+                .expect("down");
+            timings.record_event(event_started.elapsed());
+
+            let draw_started = Instant::now();
+            terminal
+                .draw(|frame| flow.render(frame, theme))
+                .expect("render list flow");
+            timings.record_draw(draw_started.elapsed());
+        }
+
+        let p99 = timings.draw_percentile(99).expect("draw samples");
+        assert!(
+            p99 <= FRAME_P99_BUDGET,
+            "p99 frame time {p99:?} exceeds {FRAME_P99_BUDGET:?} ({})",
+            timings.report()
+        );
+    }
 }
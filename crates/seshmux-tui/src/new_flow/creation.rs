@@ -0,0 +1,183 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::Instant;
+
+use anyhow::{Result, anyhow};
+use crossterm::event::KeyEvent;
+use seshmux_app::{App, NewProgress, NewRequest, NewResult, ProvisionLog};
+use seshmux_core::command_runner::{RunPolicy, SystemCommandRunner};
+use seshmux_core::config::LoadOptions;
+
+use crate::keymap;
+use crate::notify::{JobCompletion, JobReporter};
+use crate::ui::loading::LoadingState;
+
+use super::{FlowSignal, NewFlow, NewFlowErrorOrigin, NewFlowErrorState, NewFlowOps, Step};
+
+// Only the tail is drawn; this bounds what a chatty command keeps in memory.
+const MAX_CREATION_OUTPUT: usize = 200;
+
+#[derive(Debug)]
+pub(crate) enum CreationEvent {
+    Progress(NewProgress),
+    Done(Result<Box<NewResult>>),
+}
+
+pub(crate) fn spawn_creation(
+    mut request: NewRequest,
+    policy: RunPolicy,
+    load_options: LoadOptions,
+    provision_log: Option<ProvisionLog>,
+    jobs: JobReporter,
+    cancel: Arc<AtomicBool>,
+) -> Receiver<CreationEvent> {
+    request.connect_now = false;
+    let label = format!("Creating worktree '{}'", request.worktree_name);
+    let started_at = Instant::now();
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let runner = SystemCommandRunner::with_policy(policy);
+        let mut app = App::new(&runner).with_load_options(load_options);
+        if let Some(provision_log) = provision_log {
+            app = app.with_provision_log(provision_log);
+        }
+        let result = app.new_execute_cancelable(
+            request,
+            &mut |progress| {
+                let _ = sender.send(CreationEvent::Progress(progress));
+            },
+            &cancel,
+        );
+        if result.is_ok() {
+            jobs.report(JobCompletion::new(label, started_at.elapsed()));
+        }
+        let _ = sender.send(CreationEvent::Done(
+            result.map(Box::new).map_err(anyhow::Error::from),
+        ));
+    });
+    receiver
+}
+
+#[derive(Debug)]
+pub(super) struct CreationState {
+    receiver: Receiver<CreationEvent>,
+    cancel: Arc<AtomicBool>,
+    connect: bool,
+    pub(super) phases: Vec<NewProgress>,
+    pub(super) output: Vec<String>,
+    pub(super) loading: LoadingState,
+    outcome: Option<Result<Box<NewResult>>>,
+}
+
+impl CreationState {
+    pub(super) fn canceling(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+}
+
+impl NewFlow {
+    pub(super) fn start_creation(&mut self, ops: &dyn NewFlowOps, request: NewRequest) {
+        let connect = request.connect_now;
+        let cancel = Arc::new(AtomicBool::new(false));
+        let receiver = ops.spawn_execute(request, self.jobs.clone(), cancel.clone());
+        self.creation = Some(CreationState {
+            receiver,
+            cancel,
+            connect,
+            phases: Vec::new(),
+            output: Vec::new(),
+            loading: LoadingState::default(),
+            outcome: None,
+        });
+        self.step = Step::Creating;
+    }
+
+    pub(super) fn creating(&self) -> bool {
+        self.step == Step::Creating
+    }
+
+    pub(super) fn cancel_creation(&self) {
+        if let Some(creation) = &self.creation {
+            creation.cancel.store(true, Ordering::Relaxed);
+        }
+    }
+
+    pub(super) fn on_key_creating(&mut self, key: KeyEvent) -> Result<FlowSignal> {
+        if keymap::is_back(key) {
+            self.cancel_creation();
+        }
+        Ok(FlowSignal::Continue)
+    }
+
+    pub(super) fn poll_creation(&mut self) {
+        let Some(creation) = &mut self.creation else {
+            return;
+        };
+        creation.loading.next_frame();
+
+        while creation.outcome.is_none() {
+            match creation.receiver.try_recv() {
+                Ok(CreationEvent::Progress(NewProgress {
+                    output: Some(line), ..
+                })) => {
+                    if creation.output.len() == MAX_CREATION_OUTPUT {
+                        creation.output.remove(0);
+                    }
+                    creation.output.push(line);
+                }
+                Ok(CreationEvent::Progress(progress)) => {
+                    creation.output.clear();
+                    creation.phases.push(progress);
+                }
+                Ok(CreationEvent::Done(result)) => creation.outcome = Some(result),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    creation.outcome =
+                        Some(Err(anyhow!("worktree creation worker ended unexpectedly")));
+                }
+            }
+        }
+    }
+
+    pub(super) fn finish_creation(&mut self, ops: &dyn NewFlowOps) {
+        let Some(CreationState {
+            connect,
+            outcome: Some(outcome),
+            ..
+        }) = self.creation.take_if(|creation| creation.outcome.is_some())
+        else {
+            return;
+        };
+
+        let mut result = match outcome {
+            Ok(result) => *result,
+            Err(error) => {
+                self.step = Step::ErrorScreen(NewFlowErrorState::from_error(
+                    NewFlowErrorOrigin::ReviewSubmit,
+                    &error,
+                ));
+                return;
+            }
+        };
+        let mut problems = Vec::new();
+        if connect {
+            match ops.connect_new(&result) {
+                Ok(()) => result.connected_now = true,
+                Err(error) => problems.push(format!("{error:#}")),
+            }
+        }
+        if self.copy_extras_choice.yes_selected
+            && let Some(buckets) = self.pending_skip_buckets_to_persist_after_create.take()
+            && let Err(error) = ops.save_always_skip_buckets(&self.prepare.repo_root, &buckets)
+        {
+            problems.push(format!("failed to persist extras skip settings: {error}"));
+        }
+
+        self.pending_skip_buckets_to_persist_after_create = None;
+        self.success = Some(result);
+        self.success_notice = (!problems.is_empty())
+            .then(|| format!("Worktree created, but {}", problems.join("; ")));
+        self.step = Step::Success;
+    }
+}
@@ -1,42 +1,80 @@
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::ffi::OsString;
 use std::path::{Component, Path, PathBuf};
 
 use anyhow::Result;
 use crossterm::event::{Event, KeyEvent};
 use rayon::prelude::*;
+use seshmux_core::extras::ExtraOrigin;
 use tui_input::Input;
 use tui_input::backend::crossterm::EventHandler;
 use tui_tree_widget::{TreeItem, TreeState};
 
+// Nodes are keyed by their relative path so names that are not valid UTF-8 still round-trip to
+// the copy step; only the label and search key are lossy.
 #[derive(Debug, Clone)]
 pub(crate) struct ExtraNode {
     pub(crate) label: String,
     pub(crate) search_key: String,
     pub(crate) is_dir: bool,
-    pub(crate) children: Vec<String>,
+    pub(crate) children: Vec<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct ExtrasIndex {
-    pub(crate) nodes: BTreeMap<String, ExtraNode>,
-    pub(crate) roots: Vec<String>,
+    pub(crate) nodes: BTreeMap<PathBuf, ExtraNode>,
+    pub(crate) roots: Vec<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct VisibleRow {
-    pub(crate) key: String,
+    pub(crate) key: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ExtraCategories {
+    pub(crate) dotfiles: bool,
+    pub(crate) ignored: bool,
+    pub(crate) untracked: bool,
+}
+
+impl Default for ExtraCategories {
+    fn default() -> Self {
+        Self {
+            dotfiles: true,
+            ignored: true,
+            untracked: true,
+        }
+    }
+}
+
+impl ExtraCategories {
+    pub(crate) fn hidden_summary(&self) -> Option<String> {
+        let hidden: Vec<&str> = [
+            (self.dotfiles, "dotfiles"),
+            (self.ignored, "ignored"),
+            (self.untracked, "untracked"),
+        ]
+        .into_iter()
+        .filter(|(shown, _)| !shown)
+        .map(|(_, name)| name)
+        .collect();
+        (!hidden.is_empty()).then(|| hidden.join(", "))
+    }
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct ExtrasState {
-    pub(crate) nodes: BTreeMap<String, ExtraNode>,
-    pub(crate) roots: Vec<String>,
-    pub(crate) checked: HashSet<String>,
-    pub(crate) collapsed: HashSet<String>,
+    pub(crate) nodes: BTreeMap<PathBuf, ExtraNode>,
+    pub(crate) roots: Vec<PathBuf>,
+    pub(crate) checked: HashSet<PathBuf>,
+    pub(crate) collapsed: HashSet<PathBuf>,
     pub(crate) visible: Vec<VisibleRow>,
     pub(crate) cursor: usize,
     pub(crate) filter: Input,
     pub(crate) editing_filter: bool,
+    pub(crate) origins: BTreeMap<PathBuf, ExtraOrigin>,
+    pub(crate) categories: ExtraCategories,
 }
 
 impl ExtrasState {
@@ -61,11 +99,47 @@ impl ExtrasState {
             cursor: 0,
             filter: Input::default(),
             editing_filter: false,
+            origins: BTreeMap::new(),
+            categories: ExtraCategories::default(),
         };
         state.refresh_visible();
         state
     }
 
+    pub(crate) fn set_origins(&mut self, origins: BTreeMap<PathBuf, ExtraOrigin>) {
+        self.origins = origins;
+        self.refresh_visible();
+    }
+
+    pub(crate) fn toggle_dotfiles(&mut self) {
+        self.categories.dotfiles = !self.categories.dotfiles;
+        self.refresh_visible();
+    }
+
+    pub(crate) fn toggle_ignored(&mut self) {
+        self.categories.ignored = !self.categories.ignored;
+        self.refresh_visible();
+    }
+
+    pub(crate) fn toggle_untracked(&mut self) {
+        self.categories.untracked = !self.categories.untracked;
+        self.refresh_visible();
+    }
+
+    fn origin_of(&self, key: &Path) -> ExtraOrigin {
+        self.origins
+            .get(key)
+            .copied()
+            .unwrap_or(ExtraOrigin::Untracked)
+    }
+
+    fn origin_shown(&self, key: &Path) -> bool {
+        match self.origin_of(key) {
+            ExtraOrigin::Ignored => self.categories.ignored,
+            ExtraOrigin::Untracked => self.categories.untracked,
+        }
+    }
+
     pub(crate) fn refresh_visible(&mut self) {
         self.visible.clear();
         let roots = self.roots.clone();
@@ -81,13 +155,13 @@ impl ExtrasState {
         }
     }
 
-    fn push_visible(&mut self, key: &str, needle: Option<&str>) {
+    fn push_visible(&mut self, key: &Path, needle: Option<&str>) {
         if !self.subtree_matches_filter(key, needle) {
             return;
         }
 
         self.visible.push(VisibleRow {
-            key: key.to_string(),
+            key: key.to_path_buf(),
         });
 
         let Some(node) = self.nodes.get(key) else {
@@ -104,13 +178,30 @@ impl ExtrasState {
         }
     }
 
-    fn subtree_matches_filter(&self, key: &str, needle: Option<&str>) -> bool {
-        let Some(needle) = needle else {
-            return true;
+    fn subtree_matches_filter(&self, key: &Path, needle: Option<&str>) -> bool {
+        let Some(node) = self.nodes.get(key) else {
+            return false;
         };
 
-        let Some(node) = self.nodes.get(key) else {
+        if !self.categories.dotfiles && node.label.starts_with('.') {
             return false;
+        }
+
+        // With both origins listed a directory always has something to show, so only the
+        // filter decides and large trees are not walked.
+        if !(self.categories.ignored && self.categories.untracked) {
+            if !node.is_dir {
+                return self.origin_shown(key)
+                    && needle.is_none_or(|needle| node.search_key.contains(needle));
+            }
+            return node
+                .children
+                .iter()
+                .any(|child| self.subtree_matches_filter(child, needle));
+        }
+
+        let Some(needle) = needle else {
+            return true;
         };
 
         if node.search_key.contains(needle) {
@@ -156,6 +247,28 @@ impl ExtrasState {
         self.set_recursive_checked(&key, should_select);
     }
 
+    pub(crate) fn preselect_file(&mut self, key: &Path) {
+        if self.nodes.get(key).is_some_and(|node| !node.is_dir) {
+            self.checked.insert(key.to_path_buf());
+        }
+    }
+
+    pub(crate) fn preselect_matching(&mut self, patterns: &[String]) {
+        let matched: Vec<PathBuf> = self
+            .nodes
+            .keys()
+            .filter(|key| {
+                patterns
+                    .iter()
+                    .any(|pattern| seshmux_core::extras::extra_matches_glob(pattern, key))
+            })
+            .cloned()
+            .collect();
+        for key in matched {
+            self.set_recursive_checked(&key, true);
+        }
+    }
+
     pub(crate) fn toggle_fold_current(&mut self) {
         let Some(row) = self.visible.get(self.cursor) else {
             return;
@@ -178,9 +291,9 @@ impl ExtrasState {
         self.refresh_visible();
     }
 
-    fn set_recursive_checked(&mut self, key: &str, value: bool) {
+    fn set_recursive_checked(&mut self, key: &Path, value: bool) {
         if value {
-            self.checked.insert(key.to_string());
+            self.checked.insert(key.to_path_buf());
         } else {
             self.checked.remove(key);
         }
@@ -213,17 +326,23 @@ impl ExtrasState {
         }
     }
 
+    pub(crate) fn paste_filter(&mut self, text: &str) {
+        if crate::ui::paste::paste_into(&mut self.filter, text) {
+            self.refresh_visible();
+        }
+    }
+
     pub(crate) fn selected_for_copy(&self) -> Vec<PathBuf> {
-        let mut selected = Vec::<String>::new();
+        let mut selected = Vec::<PathBuf>::new();
         for root in &self.roots {
             self.collect_selected(root, &mut selected);
         }
         selected.sort();
         selected.dedup();
-        selected.into_iter().map(PathBuf::from).collect()
+        selected
     }
 
-    fn collect_selected(&self, key: &str, selected: &mut Vec<String>) {
+    fn collect_selected(&self, key: &Path, selected: &mut Vec<PathBuf>) {
         let Some(node) = self.nodes.get(key) else {
             return;
         };
@@ -236,11 +355,11 @@ impl ExtrasState {
         }
 
         if self.checked.contains(key) {
-            selected.push(key.to_string());
+            selected.push(key.to_path_buf());
         }
     }
 
-    fn descendants_fully_checked(&self, key: &str) -> bool {
+    fn descendants_fully_checked(&self, key: &Path) -> bool {
         let Some(node) = self.nodes.get(key) else {
             return false;
         };
@@ -261,7 +380,7 @@ impl ExtrasState {
         true
     }
 
-    fn mark_for(&self, key: &str) -> &'static str {
+    fn mark_for(&self, key: &Path) -> &'static str {
         let Some(node) = self.nodes.get(key) else {
             return "[ ]";
         };
@@ -286,7 +405,7 @@ impl ExtrasState {
         }
     }
 
-    fn has_checked_descendant(&self, key: &str) -> bool {
+    fn has_checked_descendant(&self, key: &Path) -> bool {
         let Some(node) = self.nodes.get(key) else {
             return false;
         };
@@ -299,7 +418,7 @@ impl ExtrasState {
         false
     }
 
-    pub(crate) fn tree_items(&self) -> Vec<TreeItem<'static, String>> {
+    pub(crate) fn tree_items(&self) -> Vec<TreeItem<'static, PathBuf>> {
         let mut items = Vec::new();
         let needle = filter_needle(&self.filter);
         for root in &self.roots {
@@ -310,17 +429,22 @@ impl ExtrasState {
         items
     }
 
-    fn tree_item_for(&self, key: &str, needle: Option<&str>) -> Option<TreeItem<'static, String>> {
+    fn tree_item_for(
+        &self,
+        key: &Path,
+        needle: Option<&str>,
+    ) -> Option<TreeItem<'static, PathBuf>> {
         if !self.subtree_matches_filter(key, needle) {
             return None;
         }
 
         let node = self.nodes.get(key)?;
         let label = format!(
-            "{} {} {}",
+            "{} {} {}{}",
             self.mark_for(key),
             if node.is_dir { "📁" } else { "📄" },
-            node.label
+            node.label,
+            self.badge_for(key, node)
         );
 
         let mut children = Vec::new();
@@ -331,16 +455,34 @@ impl ExtrasState {
         }
 
         if children.is_empty() {
-            Some(TreeItem::new_leaf(key.to_string(), label))
+            Some(TreeItem::new_leaf(key.to_path_buf(), label))
         } else {
             Some(
-                TreeItem::new(key.to_string(), label, children)
+                TreeItem::new(key.to_path_buf(), label, children)
                     .expect("all extra tree identifiers are unique"),
             )
         }
     }
 
-    pub(crate) fn tree_state(&self) -> TreeState<String> {
+    fn badge_for(&self, key: &Path, node: &ExtraNode) -> String {
+        let mut badges = Vec::new();
+        if !node.is_dir {
+            badges.push(match self.origin_of(key) {
+                ExtraOrigin::Ignored => "ignored",
+                ExtraOrigin::Untracked => "untracked",
+            });
+        }
+        if node.label.starts_with('.') {
+            badges.push("dotfile");
+        }
+        if badges.is_empty() {
+            String::new()
+        } else {
+            format!("  [{}]", badges.join(", "))
+        }
+    }
+
+    pub(crate) fn tree_state(&self) -> TreeState<PathBuf> {
         let mut state = TreeState::default();
         let filtering = filter_needle(&self.filter).is_some();
         for row in &self.visible {
@@ -363,12 +505,19 @@ impl ExtrasState {
 
 #[derive(Debug)]
 struct PreparedPath {
-    normalized: String,
-    components: Vec<String>,
+    normalized: PathBuf,
+    components: Vec<OsString>,
     lowered_components: Vec<String>,
 }
 
 pub(crate) fn build_extras_index_from_paths(candidates: &[PathBuf]) -> Result<ExtrasIndex> {
+    build_extras_index_with_progress(candidates, &mut |_, _| {})
+}
+
+pub(crate) fn build_extras_index_with_progress(
+    candidates: &[PathBuf],
+    inserted: &mut dyn FnMut(&Path, usize),
+) -> Result<ExtrasIndex> {
     let mut prepared: Vec<PreparedPath> = candidates
         .par_iter()
         .filter_map(|candidate| prepare_candidate_path(candidate))
@@ -377,11 +526,12 @@ pub(crate) fn build_extras_index_from_paths(candidates: &[PathBuf]) -> Result<Ex
     prepared.par_sort_unstable_by(|left, right| left.normalized.cmp(&right.normalized));
     prepared.dedup_by(|left, right| left.normalized == right.normalized);
 
-    let mut nodes = BTreeMap::<String, ExtraNode>::new();
-    let mut roots = BTreeSet::<String>::new();
+    let mut nodes = BTreeMap::<PathBuf, ExtraNode>::new();
+    let mut roots = BTreeSet::<PathBuf>::new();
 
     for path in &prepared {
         insert_prepared_path(&mut nodes, &mut roots, path);
+        inserted(&path.normalized, prepared.len());
     }
 
     Ok(ExtrasIndex {
@@ -390,15 +540,15 @@ pub(crate) fn build_extras_index_from_paths(candidates: &[PathBuf]) -> Result<Ex
     })
 }
 
-pub(crate) fn identifier_path_for_key(key: &str) -> Vec<String> {
+pub(crate) fn identifier_path_for_key(key: &Path) -> Vec<PathBuf> {
     let mut identifiers = Vec::new();
     let mut current = PathBuf::new();
-    for component in Path::new(key).components() {
+    for component in key.components() {
         let Component::Normal(part) = component else {
             continue;
         };
         current.push(part);
-        identifiers.push(current.to_string_lossy().to_string());
+        identifiers.push(current.clone());
     }
     identifiers
 }
@@ -409,12 +559,12 @@ fn prepare_candidate_path(candidate: &Path) -> Option<PreparedPath> {
         return None;
     }
 
-    let mut components = Vec::<String>::new();
+    let mut components = Vec::<OsString>::new();
     for component in normalized.components() {
         let Component::Normal(value) = component else {
             return None;
         };
-        components.push(value.to_str()?.to_string());
+        components.push(value.to_os_string());
     }
 
     if components.is_empty() {
@@ -423,9 +573,9 @@ fn prepare_candidate_path(candidate: &Path) -> Option<PreparedPath> {
 
     let lowered_components: Vec<String> = components
         .iter()
-        .map(|value| value.to_lowercase())
+        .map(|value| value.to_string_lossy().to_lowercase())
         .collect();
-    let normalized = components.join("/");
+    let normalized = components.iter().collect();
 
     Some(PreparedPath {
         normalized,
@@ -435,20 +585,19 @@ fn prepare_candidate_path(candidate: &Path) -> Option<PreparedPath> {
 }
 
 fn insert_prepared_path(
-    nodes: &mut BTreeMap<String, ExtraNode>,
-    roots: &mut BTreeSet<String>,
+    nodes: &mut BTreeMap<PathBuf, ExtraNode>,
+    roots: &mut BTreeSet<PathBuf>,
     path: &PreparedPath,
 ) {
-    let mut key = String::new();
+    let mut key = PathBuf::new();
     let mut lowered_key = String::new();
-    let mut parent: Option<String> = None;
+    let mut parent: Option<PathBuf> = None;
 
     for (index, component) in path.components.iter().enumerate() {
         if index > 0 {
-            key.push('/');
             lowered_key.push('/');
         }
-        key.push_str(component);
+        key.push(component);
         lowered_key.push_str(&path.lowered_components[index]);
 
         let is_last = index + 1 == path.components.len();
@@ -463,7 +612,7 @@ fn insert_prepared_path(
                 }
             })
             .or_insert_with(|| ExtraNode {
-                label: component.clone(),
+                label: component.to_string_lossy().into_owned(),
                 search_key,
                 is_dir: node_is_dir,
                 children: Vec::new(),
@@ -502,10 +651,13 @@ fn filter_needle(input: &Input) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::collections::BTreeMap;
+    use std::path::{Path, PathBuf};
     use std::time::Instant;
 
-    use super::{ExtrasState, build_extras_index_from_paths};
+    use seshmux_core::extras::ExtraOrigin;
+
+    use super::{ExtrasState, build_extras_index_from_paths, build_extras_index_with_progress};
 
     fn open_first_directory(state: &mut ExtrasState) {
         state.cursor = 0;
@@ -521,8 +673,8 @@ mod tests {
         ])
         .expect("state");
 
-        assert!(state.nodes.contains_key("keep.txt"));
-        assert!(!state.nodes.contains_key("../outside.txt"));
+        assert!(state.nodes.contains_key(Path::new("keep.txt")));
+        assert!(!state.nodes.contains_key(Path::new("../outside.txt")));
     }
 
     #[test]
@@ -533,8 +685,8 @@ mod tests {
         ])
         .expect("state");
 
-        assert!(state.nodes.contains_key("keep.txt"));
-        assert!(!state.nodes.contains_key("worktrees"));
+        assert!(state.nodes.contains_key(Path::new("keep.txt")));
+        assert!(!state.nodes.contains_key(Path::new("worktrees")));
     }
 
     #[test]
@@ -546,7 +698,10 @@ mod tests {
         ])
         .expect("state");
 
-        assert_eq!(state.roots, vec!["notes.txt", "src"]);
+        assert_eq!(
+            state.roots,
+            vec![PathBuf::from("notes.txt"), PathBuf::from("src")]
+        );
     }
 
     #[test]
@@ -575,8 +730,15 @@ mod tests {
         state.filter = tui_input::Input::new("one".to_string());
         state.refresh_visible();
 
-        let visible: Vec<String> = state.visible.iter().map(|row| row.key.clone()).collect();
-        assert_eq!(visible, vec!["dir", "dir/sub", "dir/sub/one.txt"]);
+        let visible: Vec<PathBuf> = state.visible.iter().map(|row| row.key.clone()).collect();
+        assert_eq!(
+            visible,
+            vec![
+                PathBuf::from("dir"),
+                PathBuf::from("dir/sub"),
+                PathBuf::from("dir/sub/one.txt")
+            ]
+        );
     }
 
     #[test]
@@ -592,7 +754,27 @@ mod tests {
         state.move_down();
         state.toggle_current();
 
-        assert_eq!(state.mark_for("dir"), "[-]");
+        assert_eq!(state.mark_for(Path::new("dir")), "[-]");
+    }
+
+    #[test]
+    fn build_progress_counts_each_deduplicated_path_against_the_total() {
+        let candidates = vec![
+            PathBuf::from("a/x.txt"),
+            PathBuf::from("./a/x.txt"),
+            PathBuf::from("b/y.txt"),
+        ];
+
+        let mut seen = Vec::new();
+        build_extras_index_with_progress(&candidates, &mut |path, total| {
+            seen.push((path.to_path_buf(), total))
+        })
+        .expect("index");
+
+        assert_eq!(
+            seen,
+            vec![(PathBuf::from("a/x.txt"), 2), (PathBuf::from("b/y.txt"), 2)]
+        );
     }
 
     #[test]
@@ -614,8 +796,14 @@ mod tests {
 
         assert_eq!(first.roots, second.roots);
         assert_eq!(first.roots, third.roots);
-        assert_eq!(first.nodes["a"].children, second.nodes["a"].children);
-        assert_eq!(first.nodes["a"].children, third.nodes["a"].children);
+        assert_eq!(
+            first.nodes[Path::new("a")].children,
+            second.nodes[Path::new("a")].children
+        );
+        assert_eq!(
+            first.nodes[Path::new("a")].children,
+            third.nodes[Path::new("a")].children
+        );
     }
 
     #[test]
@@ -638,6 +826,142 @@ mod tests {
         );
     }
 
+    #[test]
+    fn names_with_spaces_keep_their_path_and_label() {
+        let mut state =
+            ExtrasState::from_candidates(&[PathBuf::from("my notes/to do.txt")]).expect("state");
+
+        open_first_directory(&mut state);
+        state.move_down();
+        state.toggle_current();
+
+        assert_eq!(
+            state.nodes[Path::new("my notes/to do.txt")].label,
+            "to do.txt"
+        );
+        assert_eq!(
+            state.selected_for_copy(),
+            vec![PathBuf::from("my notes/to do.txt")]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_names_get_lossy_labels_but_copy_their_exact_path() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        use seshmux_core::command_runner::{CommandRunner, SystemCommandRunner};
+
+        let temp = tempfile::tempdir().expect("temp dir");
+        let runner = SystemCommandRunner::new();
+        let init = runner
+            .run("git", &["init", "-q"], Some(temp.path()))
+            .expect("git init");
+        assert_eq!(init.status_code, 0, "{}", init.stderr);
+        let odd = PathBuf::from("cache").join(OsStr::from_bytes(b"caf\xe9.bin"));
+        std::fs::create_dir_all(temp.path().join("cache")).expect("cache dir");
+        std::fs::write(temp.path().join(&odd), "odd").expect("odd file");
+        std::fs::write(temp.path().join("cache/ok.bin"), "ok").expect("ok file");
+
+        let origins =
+            seshmux_core::extras::collect_git_extra_origins(temp.path(), &runner).expect("origins");
+        let candidates: Vec<PathBuf> = origins.into_keys().collect();
+        assert!(candidates.contains(&odd), "{candidates:?}");
+        let mut state = ExtrasState::from_candidates(&candidates).expect("state");
+
+        assert_eq!(state.nodes[odd.as_path()].label, "caf\u{FFFD}.bin");
+        state.toggle_current();
+        let mut selected = state.selected_for_copy();
+        selected.sort();
+        let mut expected = vec![odd, PathBuf::from("cache/ok.bin")];
+        expected.sort();
+        assert_eq!(selected, expected);
+        assert_eq!(state.tree_items().len(), 1);
+    }
+
+    fn visible_keys(state: &ExtrasState) -> Vec<PathBuf> {
+        state.visible.iter().map(|row| row.key.clone()).collect()
+    }
+
+    #[test]
+    fn category_toggles_hide_dotfiles_ignored_and_untracked_rows() {
+        let mut state = ExtrasState::from_candidates(&[
+            PathBuf::from(".env"),
+            PathBuf::from("notes.md"),
+            PathBuf::from("target/app"),
+        ])
+        .expect("state");
+        state.collapsed.clear();
+        state.set_origins(BTreeMap::from([
+            (PathBuf::from(".env"), ExtraOrigin::Ignored),
+            (PathBuf::from("target/app"), ExtraOrigin::Ignored),
+        ]));
+        assert_eq!(visible_keys(&state).len(), 4);
+        assert_eq!(state.categories.hidden_summary(), None);
+
+        state.toggle_ignored();
+        assert_eq!(visible_keys(&state), vec![PathBuf::from("notes.md")]);
+        assert_eq!(
+            state.categories.hidden_summary().as_deref(),
+            Some("ignored")
+        );
+
+        state.toggle_ignored();
+        state.toggle_untracked();
+        state.toggle_dotfiles();
+        assert_eq!(
+            visible_keys(&state),
+            vec![PathBuf::from("target"), PathBuf::from("target/app")]
+        );
+        assert_eq!(
+            state.categories.hidden_summary().as_deref(),
+            Some("dotfiles, untracked")
+        );
+    }
+
+    #[test]
+    fn hidden_rows_stay_selected_for_copy() {
+        let mut state =
+            ExtrasState::from_candidates(&[PathBuf::from(".env"), PathBuf::from("notes.md")])
+                .expect("state");
+        state.select_all();
+        state.toggle_dotfiles();
+
+        assert_eq!(visible_keys(&state), vec![PathBuf::from("notes.md")]);
+        assert_eq!(
+            state.selected_for_copy(),
+            vec![PathBuf::from(".env"), PathBuf::from("notes.md")]
+        );
+    }
+
+    #[test]
+    fn rows_carry_origin_and_dotfile_badges() {
+        let mut state = ExtrasState::from_candidates(&[
+            PathBuf::from(".env"),
+            PathBuf::from("notes.md"),
+            PathBuf::from("target/app"),
+        ])
+        .expect("state");
+        state.set_origins(BTreeMap::from([(
+            PathBuf::from(".env"),
+            ExtraOrigin::Ignored,
+        )]));
+
+        assert_eq!(
+            state.badge_for(Path::new(".env"), &state.nodes[Path::new(".env")]),
+            "  [ignored, dotfile]"
+        );
+        assert_eq!(
+            state.badge_for(Path::new("notes.md"), &state.nodes[Path::new("notes.md")]),
+            "  [untracked]"
+        );
+        assert_eq!(
+            state.badge_for(Path::new("target"), &state.nodes[Path::new("target")]),
+            ""
+        );
+    }
+
     #[test]
     #[ignore]
     fn timing_receipt_large_synthetic_tree_index_and_interaction() {
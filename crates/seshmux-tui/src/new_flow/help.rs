@@ -0,0 +1,198 @@
+use crate::ui::help::{
+    CHOICE_KEYS, ERROR_KEYS, FILTER_KEYS, HelpProvider, INPUT_KEYS, RESULT_KEYS, ScreenHelp,
+};
+
+use super::{NewFlow, NewScreen, Step};
+
+const PICKER_KEYS: &[(&str, &str)] = &[
+    ("Up/Down or j/k", "move"),
+    ("Enter", "start the worktree here"),
+    ("/", "type to search"),
+    ("r", "reload the list from git"),
+    ("Esc", "back to the start point choice"),
+];
+
+impl NewFlow {
+    pub(super) fn help(&self) -> ScreenHelp {
+        match &self.step {
+            Step::GitignoreDecision => ScreenHelp::new(
+                "Add to .gitignore?",
+                "Some entries seshmux manages, the worktrees directory and [gitignore] entries, are not in .gitignore yet. Yes appends the missing ones so they stay out of git status.",
+            )
+            .keys(CHOICE_KEYS),
+            Step::NameInput => {
+                let summary = if self.quick {
+                    "Name the worktree directory. Enter creates it straight away with the [quick_new] answers."
+                } else {
+                    "Name the worktree directory; the branch and tmux session are named after it by default. Any other text, like a ticket title, is kept as a display name and saved under the slug shown below the input. Names already registered are flagged as you type."
+                };
+                let mut help = ScreenHelp::new("Worktree name", summary).keys(INPUT_KEYS);
+                if !self.prepare.name_suggestions.is_empty() {
+                    help = help.keys(&[("Tab", "fill in the next suggested name")]);
+                }
+                help
+            }
+            Step::ProjectPicker => ScreenHelp::new(
+                "Project",
+                "The [projects] entry to scope the session to. Its windows start in the project directory inside the worktree, and the extras it lists are checked in the extras picker. The whole repository uses the worktree root and [tmux] windows.",
+            )
+            .keys(&[
+                ("Up/Down or j/k", "move"),
+                ("Enter", "choose"),
+                ("Esc", "back to the worktree name"),
+            ]),
+            Step::BranchNameInput => ScreenHelp::new(
+                "Branch name",
+                "The branch the worktree checks out. It is created from the start point you pick next; branch_template sets the default.",
+            )
+            .keys(INPUT_KEYS),
+            Step::BranchConflict => ScreenHelp::new(
+                "Branch already checked out",
+                "Another worktree has this branch checked out. Pick another name, check it out here as well, or use the other worktree instead.",
+            )
+            .keys(&[
+                ("Up/Down or j/k", "move"),
+                ("Enter", "choose"),
+                ("Esc", "back to the branch name"),
+            ]),
+            Step::StartPointMode => ScreenHelp::new(
+                "Start point",
+                "Where the new branch starts: the current branch, another local or remote branch, or a specific commit.",
+            )
+            .keys(&[
+                ("Up/Down or j/k", "move"),
+                ("Enter", "choose"),
+                ("Esc", "back to the branch name"),
+            ]),
+            Step::BranchPicker => {
+                let help = ScreenHelp::new("Pick a branch", "Branches to start the worktree from. A filter that matches none offers to create that branch from the current HEAD. Lists are kept for the rest of this flow; r asks git again.");
+                if self.branch_filter_focused {
+                    help.keys(FILTER_KEYS)
+                } else {
+                    help.keys(PICKER_KEYS)
+                }
+            }
+            Step::CommitPicker => {
+                let help = ScreenHelp::new(
+                    "Pick a commit",
+                    "Recent commits to start the worktree from, with author and date. Moving past the last one loads the next page. The filter matches hashes across all branches, or commit messages after Tab.",
+                );
+                let help = if self.commit_filter_focused {
+                    help.keys(FILTER_KEYS)
+                } else {
+                    help.keys(PICKER_KEYS)
+                };
+                help.keys(&[("Tab", "switch between hash and message search")])
+            }
+            Step::CopyExtrasDecision => ScreenHelp::new(
+                "Copy extras?",
+                "Extras are untracked and ignored files, like .env, that a fresh checkout does not have. Yes indexes them so you can pick which to copy.",
+            )
+            .keys(CHOICE_KEYS),
+            Step::ExtrasIndexing if self.skip_modal_open() => ScreenHelp::new(
+                "Skip large directories",
+                "These directories hold many files. Skipped ones are left out of the extras list; persisting a choice remembers it for this repository.",
+            )
+            .keys(&[
+                ("Up/Down or j/k", "move"),
+                ("Space", "skip or include the directory"),
+                ("a", "remember the choice for next time"),
+                ("Enter", "continue indexing"),
+                ("Esc", "cancel"),
+            ]),
+            Step::ExtrasIndexing => ScreenHelp::new(
+                "Indexing extras",
+                "Listing the untracked and ignored files that can be copied.",
+            )
+            .keys(&[("Esc", "stop and go back")]),
+            Step::ExtrasPicker => {
+                let help = ScreenHelp::new(
+                    "Pick extras",
+                    "Choose the untracked and ignored files to copy into the new worktree. Selecting a directory selects everything in it. Each file is marked ignored or untracked; hiding a kind keeps what is already selected.",
+                );
+                if self.extras.editing_filter {
+                    help.keys(FILTER_KEYS)
+                } else {
+                    help.keys(&[
+                        ("Up/Down or j/k", "move"),
+                        ("Space", "select or clear the entry"),
+                        ("Tab", "fold or unfold the directory"),
+                        ("a", "select everything"),
+                        ("n", "select nothing"),
+                        (".", "show or hide dotfiles"),
+                        ("i", "show or hide ignored files"),
+                        ("u", "show or hide untracked files"),
+                        ("/", "type to filter"),
+                        ("Enter", "continue"),
+                        ("Esc", "back"),
+                    ])
+                }
+            }
+            Step::ConnectNow => ScreenHelp::new(
+                "Connect now?",
+                "Yes attaches to the new worktree's tmux session as soon as it is created.",
+            )
+            .keys(CHOICE_KEYS),
+            Step::CarryChanges => ScreenHelp::new(
+                "Carry changes?",
+                "Yes applies the uncommitted changes in this checkout to the new worktree and copies its untracked files. They stay here too; files that conflict with the start point are left with conflict markers.",
+            )
+            .keys(CHOICE_KEYS),
+            Step::Review => {
+                let mut help = ScreenHelp::new(
+                    "Review",
+                    "Everything the worktree will be created with. Nothing has changed yet.",
+                )
+                .keys(&[
+                    ("Enter", "create the worktree"),
+                    ("l", "toggle the LFS pull"),
+                    ("s", "toggle submodule init"),
+                ]);
+                if self.prepare.has_envrc {
+                    help = help.keys(&[("d", "toggle direnv allow")]);
+                }
+                help.keys(&[("Esc", "back")])
+            }
+            Step::Creating => ScreenHelp::new(
+                "Creating worktree",
+                "Each step is listed as it starts. Esc cancels before the next step and rolls back what was already created; once the tmux session exists the worktree is finished.",
+            )
+            .keys(&[("Esc", "cancel and roll back")]),
+            Step::Success => ScreenHelp::new("Worktree created", "The worktree is ready.")
+                .keys(&[("y", "copy the attach command")])
+                .keys(RESULT_KEYS),
+            Step::ErrorScreen(_) => ScreenHelp::new(
+                "Worktree not created",
+                "A step failed. Any suggested next steps are listed; otherwise go back and try again.",
+            )
+            .keys(ERROR_KEYS),
+        }
+    }
+
+    pub(super) fn typing(&self) -> bool {
+        match self.step {
+            Step::NameInput | Step::BranchNameInput => true,
+            Step::BranchPicker => self.branch_filter_focused,
+            Step::CommitPicker => self.commit_filter_focused,
+            Step::ExtrasPicker => self.extras.editing_filter,
+            _ => false,
+        }
+    }
+
+    pub(super) fn has_answers(&self) -> bool {
+        !matches!(
+            self.step,
+            Step::GitignoreDecision | Step::NameInput | Step::Success | Step::ErrorScreen(_)
+        )
+    }
+}
+
+impl HelpProvider for NewScreen {
+    fn help(&self) -> ScreenHelp {
+        self.flow.help()
+    }
+
+    fn typing(&self) -> bool {
+        self.flow.typing()
+    }
+}
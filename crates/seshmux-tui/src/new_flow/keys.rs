@@ -1,3 +1,5 @@
+use std::time::{Duration, Instant};
+
 use anyhow::{Context, Result};
 use crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent, MouseEventKind};
 use tui_input::backend::crossterm::EventHandler;
@@ -5,18 +7,30 @@ use tui_input::backend::crossterm::EventHandler;
 use crate::UiExit;
 use crate::keymap;
 use crate::ui::binary_choice::BinaryChoiceEvent;
+use crate::ui::error_actions::ErrorActionsEvent;
+use crate::ui::paste::paste_into;
 
 use super::picker::{PickerAction, PickerState};
 use super::{
-    ConnectBackTarget, FlowSignal, NewFlow, NewFlowErrorOrigin, NewFlowErrorState, NewFlowOps, Step,
+    ConnectBackTarget, FlowSignal, NewErrorAction, NewFlow, NewFlowErrorOrigin, NewFlowErrorState,
+    NewFlowOps, PendingQuery, Step,
 };
 use seshmux_app::{NewRequest, NewStartPoint};
+use seshmux_core::git::CommitSearch;
+use seshmux_core::names::WorktreeName;
+
+const COMMIT_PAGE_SIZE: usize = 50;
+
+const QUERY_DEBOUNCE: Duration = Duration::from_millis(150);
 
 impl NewFlow {
     pub(super) fn on_key(&mut self, key: KeyEvent, ops: &dyn NewFlowOps) -> Result<FlowSignal> {
         match &self.step {
             Step::GitignoreDecision => self.on_key_gitignore(key),
-            Step::NameInput => self.on_key_name(key),
+            Step::NameInput => self.on_key_name(key, ops),
+            Step::ProjectPicker => self.on_key_project(key),
+            Step::BranchNameInput => self.on_key_branch_name(key, ops),
+            Step::BranchConflict => self.on_key_branch_conflict(key),
             Step::StartPointMode => self.on_key_start_mode(key, ops),
             Step::BranchPicker => self.on_key_branch_picker(key, ops),
             Step::CommitPicker => self.on_key_commit_picker(key, ops),
@@ -24,7 +38,9 @@ impl NewFlow {
             Step::ExtrasIndexing => self.on_key_extras_indexing(key, ops),
             Step::ExtrasPicker => self.on_key_extras(key),
             Step::ConnectNow => self.on_key_connect_now(key),
-            Step::Review => self.on_key_review(key, ops),
+            Step::CarryChanges => self.on_key_carry_changes(key),
+            Step::Review => self.on_key_review(key),
+            Step::Creating => self.on_key_creating(key),
             Step::Success => self.on_key_success(key),
             Step::ErrorScreen(_) => self.on_key_error(key),
         }
@@ -42,6 +58,55 @@ impl NewFlow {
         }
     }
 
+    pub(super) fn on_paste(&mut self, text: &str, ops: &dyn NewFlowOps) -> Result<()> {
+        match self.step {
+            Step::NameInput => self.paste_name(text),
+            Step::BranchNameInput => self.paste_branch_name(text),
+            Step::BranchPicker if self.branch_filter_focused => {
+                if !paste_into(&mut self.branch_search_input, text) {
+                    return Ok(());
+                }
+                self.pending_query = None;
+                let query = self.branch_search_input.value().trim().to_string();
+                self.branch_picker = Some(self.load_branches(ops, &query)?);
+            }
+            Step::CommitPicker if self.commit_filter_focused => {
+                if !paste_into(&mut self.commit_search_input, text) {
+                    return Ok(());
+                }
+                self.pending_query = None;
+                let query = self.commit_search_input.value().trim().to_string();
+                self.commit_picker = Some(self.load_commits(ops, &query)?);
+            }
+            Step::ExtrasPicker if self.extras.editing_filter => self.extras.paste_filter(text),
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn paste_name(&mut self, text: &str) {
+        if !paste_into(&mut self.name_input, text) {
+            return;
+        }
+
+        let candidate = self.name_input.value().trim();
+        self.name_error = seshmux_core::names::parse_worktree_name(candidate)
+            .err()
+            .map(|error| error.to_string())
+            .or_else(|| self.taken_name_error(candidate));
+        self.name_suggestion = None;
+    }
+
+    fn taken_name_error(&self, candidate: &str) -> Option<String> {
+        let slug = seshmux_core::names::parse_worktree_name(candidate)
+            .map_or_else(|_| candidate.to_string(), |name| name.slug);
+        self.prepare
+            .existing_names
+            .contains(&slug)
+            .then(|| format!("worktree name '{slug}' already exists"))
+    }
+
     fn on_key_gitignore(&mut self, key: KeyEvent) -> Result<FlowSignal> {
         match self.gitignore_choice.on_key(key) {
             BinaryChoiceEvent::Back => Ok(FlowSignal::Exit(UiExit::BackAtRoot)),
@@ -53,22 +118,64 @@ impl NewFlow {
         }
     }
 
-    fn on_key_name(&mut self, key: KeyEvent) -> Result<FlowSignal> {
+    fn paste_branch_name(&mut self, text: &str) {
+        if !paste_into(&mut self.branch_input, text) {
+            return;
+        }
+
+        let candidate = self.branch_input.value().trim();
+        self.branch_error = seshmux_core::names::validate_branch_name(candidate)
+            .err()
+            .map(|error| error.to_string());
+    }
+
+    fn on_key_name(&mut self, key: KeyEvent, ops: &dyn NewFlowOps) -> Result<FlowSignal> {
         if keymap::is_back(key) {
-            if self.prepare.gitignore_has_worktrees_entry {
+            if self.quick || self.prepare.missing_gitignore_entries.is_empty() {
                 return Ok(FlowSignal::Exit(UiExit::BackAtRoot));
             }
             self.step = Step::GitignoreDecision;
             return Ok(FlowSignal::Continue);
         }
 
+        if key.code == KeyCode::Tab && !self.prepare.name_suggestions.is_empty() {
+            let next = self
+                .name_suggestion
+                .map_or(0, |index| (index + 1) % self.prepare.name_suggestions.len());
+            self.name_input = tui_input::Input::new(self.prepare.name_suggestions[next].clone());
+            self.name_suggestion = Some(next);
+            self.name_error = None;
+            return Ok(FlowSignal::Continue);
+        }
+
         if keymap::is_confirm(key) {
             let candidate = self.name_input.value().trim().to_string();
-            match seshmux_core::names::validate_worktree_name(&candidate) {
-                Ok(()) => {
+            if let Some(error) = self.taken_name_error(&candidate) {
+                self.name_error = Some(error);
+                return Ok(FlowSignal::Continue);
+            }
+            match seshmux_core::names::parse_worktree_name(&candidate) {
+                Ok(name) => {
+                    let default = seshmux_core::config::resolve_branch_name(
+                        self.prepare.branch_template.as_deref(),
+                        &name.slug,
+                    );
+                    let branch = self.branch_input.value();
+                    if branch.is_empty() || branch == self.branch_default {
+                        self.branch_input = tui_input::Input::new(default.clone());
+                        self.branch_error = None;
+                    }
+                    self.branch_default = default;
                     self.name_input = tui_input::Input::new(candidate);
                     self.name_error = None;
-                    self.step = Step::StartPointMode;
+                    if self.quick {
+                        return self.submit_quick(ops, &name.slug);
+                    }
+                    self.step = if self.prepare.projects.is_empty() {
+                        Step::BranchNameInput
+                    } else {
+                        Step::ProjectPicker
+                    };
                 }
                 Err(error) => {
                     self.name_error = Some(error.to_string());
@@ -78,18 +185,135 @@ impl NewFlow {
         }
 
         if self.name_input.handle_event(&Event::Key(key)).is_some() {
-            self.name_error = None;
+            self.name_error = self.taken_name_error(self.name_input.value().trim());
+            self.name_suggestion = None;
         }
 
         Ok(FlowSignal::Continue)
     }
 
-    fn on_key_start_mode(&mut self, key: KeyEvent, ops: &dyn NewFlowOps) -> Result<FlowSignal> {
+    fn on_key_project(&mut self, key: KeyEvent) -> Result<FlowSignal> {
         if keymap::is_back(key) {
             self.step = Step::NameInput;
             return Ok(FlowSignal::Continue);
         }
 
+        if keymap::is_up(key) {
+            self.project_selected = self.project_selected.saturating_sub(1);
+            return Ok(FlowSignal::Continue);
+        }
+
+        if keymap::is_down(key) {
+            if self.project_selected < self.prepare.projects.len() {
+                self.project_selected += 1;
+            }
+            return Ok(FlowSignal::Continue);
+        }
+
+        if keymap::is_confirm(key) {
+            self.step = Step::BranchNameInput;
+        }
+
+        Ok(FlowSignal::Continue)
+    }
+
+    fn on_key_branch_name(&mut self, key: KeyEvent, ops: &dyn NewFlowOps) -> Result<FlowSignal> {
+        if keymap::is_back(key) {
+            self.step = self.step_before_branch_name();
+            return Ok(FlowSignal::Continue);
+        }
+
+        if keymap::is_confirm(key) {
+            let candidate = self.branch_input.value().trim().to_string();
+            match seshmux_core::names::validate_branch_name(&candidate) {
+                Ok(()) => {
+                    let conflicted = self.raise_branch_conflict(ops, &candidate)?;
+                    self.branch_input = tui_input::Input::new(candidate);
+                    self.branch_error = None;
+                    self.ignore_other_worktrees = false;
+                    self.use_existing_branch = false;
+                    if !conflicted {
+                        self.step = Step::StartPointMode;
+                    }
+                }
+                Err(error) => {
+                    self.branch_error = Some(error.to_string());
+                }
+            }
+            return Ok(FlowSignal::Continue);
+        }
+
+        if self.branch_input.handle_event(&Event::Key(key)).is_some() {
+            self.branch_error = None;
+        }
+
+        Ok(FlowSignal::Continue)
+    }
+
+    fn raise_branch_conflict(&mut self, ops: &dyn NewFlowOps, branch: &str) -> Result<bool> {
+        self.branch_conflict = ops.branch_conflict(&self.prepare.repo_root, branch)?;
+        self.conflict_selected = 0;
+        if self.branch_conflict.is_none() {
+            return Ok(false);
+        }
+        self.step = Step::BranchConflict;
+        Ok(true)
+    }
+
+    fn on_key_branch_conflict(&mut self, key: KeyEvent) -> Result<FlowSignal> {
+        if keymap::is_back(key) {
+            self.ignore_other_worktrees = false;
+            self.step = Step::BranchNameInput;
+            return Ok(FlowSignal::Continue);
+        }
+
+        if keymap::is_up(key) {
+            self.conflict_selected = self.conflict_selected.saturating_sub(1);
+            return Ok(FlowSignal::Continue);
+        }
+
+        if keymap::is_down(key) {
+            if self.conflict_selected < 2 {
+                self.conflict_selected += 1;
+            }
+            return Ok(FlowSignal::Continue);
+        }
+
+        if keymap::is_confirm(key) {
+            match self.conflict_selected {
+                0 => {
+                    self.ignore_other_worktrees = false;
+                    self.step = Step::NameInput;
+                }
+                1 => {
+                    let Some(conflict) = &self.branch_conflict else {
+                        return Ok(FlowSignal::Continue);
+                    };
+                    self.ignore_other_worktrees = true;
+                    self.start_point = Some(NewStartPoint::Branch(conflict.branch.clone()));
+                    self.step = Step::CopyExtrasDecision;
+                }
+                _ => {
+                    let Some(conflict) = &self.branch_conflict else {
+                        return Ok(FlowSignal::Continue);
+                    };
+                    return Ok(match &conflict.worktree_name {
+                        Some(name) => FlowSignal::Attach(name.clone()),
+                        None => FlowSignal::Shell(conflict.worktree_path.clone()),
+                    });
+                }
+            }
+        }
+
+        Ok(FlowSignal::Continue)
+    }
+
+    fn on_key_start_mode(&mut self, key: KeyEvent, ops: &dyn NewFlowOps) -> Result<FlowSignal> {
+        if keymap::is_back(key) {
+            self.step = Step::BranchNameInput;
+            return Ok(FlowSignal::Continue);
+        }
+
         if keymap::is_up(key) {
             self.start_mode_selected = self.start_mode_selected.saturating_sub(1);
             return Ok(FlowSignal::Continue);
@@ -128,12 +352,14 @@ impl NewFlow {
 
     fn on_key_branch_picker(&mut self, key: KeyEvent, ops: &dyn NewFlowOps) -> Result<FlowSignal> {
         if keymap::is_back(key) {
+            self.pending_query = None;
             self.branch_filter_focused = false;
             self.step = Step::StartPointMode;
             return Ok(FlowSignal::Continue);
         }
 
         if matches!(key.code, KeyCode::Char('/')) {
+            self.flush_pending_query(ops)?;
             self.branch_filter_focused = !self.branch_filter_focused;
             return Ok(FlowSignal::Continue);
         }
@@ -144,8 +370,7 @@ impl NewFlow {
                 .handle_event(&Event::Key(key))
                 .is_some()
             {
-                let query = self.branch_search_input.value().trim().to_string();
-                self.branch_picker = Some(self.load_branches(ops, &query)?);
+                self.filter_branches();
             }
             return Ok(FlowSignal::Continue);
         }
@@ -155,6 +380,11 @@ impl NewFlow {
             self.branch_picker = Some(self.load_branches(ops, &query)?);
         }
 
+        if key.code == KeyCode::Char('r') {
+            self.refresh_picker(ops)?;
+            return Ok(FlowSignal::Continue);
+        }
+
         if keymap::is_up(key) {
             if let Some(picker) = &mut self.branch_picker {
                 picker.move_up();
@@ -170,15 +400,21 @@ impl NewFlow {
         }
 
         if keymap::is_confirm(key) {
-            let selection = self.branch_picker.as_ref().and_then(|picker| {
-                if let PickerAction::Pick(index) = picker.on_enter() {
-                    picker.items.get(index).map(|branch| branch.name.clone())
-                } else {
-                    None
-                }
-            });
-            if let Some(branch_name) = selection {
-                self.start_point = Some(NewStartPoint::Branch(branch_name));
+            let selection =
+                self.branch_picker
+                    .as_ref()
+                    .and_then(|picker| match picker.on_enter() {
+                        PickerAction::Pick(index) => picker
+                            .items
+                            .get(index)
+                            .map(|branch| NewStartPoint::Branch(branch.name.clone())),
+                        PickerAction::Action(_) => Some(NewStartPoint::NewBranchFrom(
+                            self.branch_search_input.value().trim().to_string(),
+                        )),
+                        PickerAction::Noop => None,
+                    });
+            if let Some(start_point) = selection {
+                self.start_point = Some(start_point);
                 self.branch_filter_focused = false;
                 self.step = Step::CopyExtrasDecision;
             }
@@ -189,24 +425,36 @@ impl NewFlow {
 
     fn on_key_commit_picker(&mut self, key: KeyEvent, ops: &dyn NewFlowOps) -> Result<FlowSignal> {
         if keymap::is_back(key) {
+            self.pending_query = None;
             self.commit_filter_focused = false;
             self.step = Step::StartPointMode;
             return Ok(FlowSignal::Continue);
         }
 
         if matches!(key.code, KeyCode::Char('/')) {
+            self.flush_pending_query(ops)?;
             self.commit_filter_focused = !self.commit_filter_focused;
             return Ok(FlowSignal::Continue);
         }
 
+        if key.code == KeyCode::Tab {
+            self.pending_query = None;
+            self.commit_search = match self.commit_search {
+                CommitSearch::Hash => CommitSearch::Message,
+                CommitSearch::Message => CommitSearch::Hash,
+            };
+            let query = self.commit_search_input.value().trim().to_string();
+            self.commit_picker = Some(self.load_commits(ops, &query)?);
+            return Ok(FlowSignal::Continue);
+        }
+
         if self.commit_filter_focused {
             if self
                 .commit_search_input
                 .handle_event(&Event::Key(key))
                 .is_some()
             {
-                let query = self.commit_search_input.value().trim().to_string();
-                self.commit_picker = Some(self.load_commits(ops, &query)?);
+                self.filter_commits();
             }
             return Ok(FlowSignal::Continue);
         }
@@ -216,6 +464,11 @@ impl NewFlow {
             self.commit_picker = Some(self.load_commits(ops, &query)?);
         }
 
+        if key.code == KeyCode::Char('r') {
+            self.refresh_picker(ops)?;
+            return Ok(FlowSignal::Continue);
+        }
+
         if keymap::is_up(key) {
             if let Some(picker) = &mut self.commit_picker {
                 picker.move_up();
@@ -224,6 +477,13 @@ impl NewFlow {
         }
 
         if keymap::is_down(key) {
+            if self
+                .commit_picker
+                .as_ref()
+                .is_some_and(PickerState::wants_next_page)
+            {
+                self.load_more_commits(ops)?;
+            }
             if let Some(picker) = &mut self.commit_picker {
                 picker.move_down();
             }
@@ -262,7 +522,8 @@ impl NewFlow {
             BinaryChoiceEvent::Continue => Ok(FlowSignal::Continue),
             BinaryChoiceEvent::ConfirmNo => {
                 self.pending_skip_buckets_to_persist_after_create = None;
-                self.connect_choice = crate::ui::binary_choice::BinaryChoice::new(true);
+                self.connect_choice =
+                    crate::ui::binary_choice::BinaryChoice::new(self.prepare.defaults.connect_now);
                 self.connect_back_target = ConnectBackTarget::CopyExtrasDecision;
                 self.step = Step::ConnectNow;
                 Ok(FlowSignal::Continue)
@@ -351,7 +612,8 @@ impl NewFlow {
         }
 
         if keymap::is_confirm(key) {
-            self.connect_choice = crate::ui::binary_choice::BinaryChoice::new(true);
+            self.connect_choice =
+                crate::ui::binary_choice::BinaryChoice::new(self.prepare.defaults.connect_now);
             self.connect_back_target = ConnectBackTarget::ExtrasPicker;
             self.step = Step::ConnectNow;
             return Ok(FlowSignal::Continue);
@@ -361,6 +623,9 @@ impl NewFlow {
             KeyCode::Tab => self.extras.toggle_fold_current(),
             KeyCode::Char('a') => self.extras.select_all(),
             KeyCode::Char('n') => self.extras.select_none(),
+            KeyCode::Char('.') => self.extras.toggle_dotfiles(),
+            KeyCode::Char('i') => self.extras.toggle_ignored(),
+            KeyCode::Char('u') => self.extras.toggle_untracked(),
             _ => {}
         }
 
@@ -378,70 +643,129 @@ impl NewFlow {
             }
             BinaryChoiceEvent::Continue => Ok(FlowSignal::Continue),
             BinaryChoiceEvent::ConfirmYes | BinaryChoiceEvent::ConfirmNo => {
-                self.step = Step::Review;
+                if self.changed_files.is_empty() {
+                    self.show_review();
+                } else {
+                    self.step = Step::CarryChanges;
+                }
                 Ok(FlowSignal::Continue)
             }
         }
     }
 
-    fn on_key_review(&mut self, key: KeyEvent, ops: &dyn NewFlowOps) -> Result<FlowSignal> {
-        if keymap::is_back(key) {
-            self.step = Step::ConnectNow;
-            return Ok(FlowSignal::Continue);
+    fn on_key_carry_changes(&mut self, key: KeyEvent) -> Result<FlowSignal> {
+        match self.carry_choice.on_key(key) {
+            BinaryChoiceEvent::Back => self.step = Step::ConnectNow,
+            BinaryChoiceEvent::Continue => {}
+            BinaryChoiceEvent::ConfirmYes | BinaryChoiceEvent::ConfirmNo => {
+                self.show_review();
+            }
         }
+        Ok(FlowSignal::Continue)
+    }
 
-        if keymap::is_confirm(key) {
-            let Some(start_point) = self.start_point.clone() else {
-                return Ok(FlowSignal::Continue);
+    fn show_review(&mut self) {
+        let slug = match seshmux_core::names::parse_worktree_name(self.name_input.value()) {
+            Ok(WorktreeName { slug, .. }) => slug,
+            Err(_) => self.name_input.value().to_string(),
+        };
+        let path = self.prepare.worktree_path(&slug);
+        self.review_worktree_path =
+            if self.prepare.worktree_path_template.is_some() && path.exists() {
+                format!("{} (already exists)", path.display())
+            } else {
+                path.display().to_string()
             };
+        self.step = Step::Review;
+    }
 
-            let request = NewRequest {
-                cwd: self.cwd.clone(),
-                worktree_name: self.name_input.value().to_string(),
-                start_point,
-                add_worktrees_gitignore_entry: !self.prepare.gitignore_has_worktrees_entry
-                    && self.gitignore_choice.yes_selected,
-                selected_extras: if self.copy_extras_choice.yes_selected {
-                    self.extras.selected_for_copy()
-                } else {
-                    Vec::new()
-                },
-                connect_now: self.connect_choice.yes_selected,
+    fn on_key_review(&mut self, key: KeyEvent) -> Result<FlowSignal> {
+        if keymap::is_back(key) {
+            self.step = if self.quick {
+                Step::NameInput
+            } else {
+                self.step_before_review()
             };
+            return Ok(FlowSignal::Continue);
+        }
 
-            match ops.execute_new(request) {
-                Ok(result) => {
-                    if self.copy_extras_choice.yes_selected
-                        && let Some(buckets) =
-                            self.pending_skip_buckets_to_persist_after_create.take()
-                        && let Err(error) =
-                            ops.save_always_skip_buckets(&self.prepare.repo_root, &buckets)
-                    {
-                        self.success = Some(result);
-                        self.success_notice = Some(format!(
-                            "Worktree created, but failed to persist extras skip settings: {error}"
-                        ));
-                        self.step = Step::Success;
-                        return Ok(FlowSignal::Continue);
-                    }
+        if keymap::is_confirm(key) {
+            return Ok(self
+                .review_request()
+                .map_or(FlowSignal::Continue, FlowSignal::Execute));
+        }
 
-                    self.pending_skip_buckets_to_persist_after_create = None;
-                    self.success = Some(result);
-                    self.success_notice = None;
-                    self.step = Step::Success;
-                }
-                Err(error) => {
-                    self.step = Step::ErrorScreen(NewFlowErrorState {
-                        origin: NewFlowErrorOrigin::ReviewSubmit,
-                        message: format!("{error:#}"),
-                    });
-                }
+        match key.code {
+            KeyCode::Char('l') => self.lfs_pull = !self.lfs_pull,
+            KeyCode::Char('s') => self.init_submodules = !self.init_submodules,
+            KeyCode::Char('d') if self.prepare.has_envrc => {
+                self.direnv_allow = !self.direnv_allow;
             }
+            _ => {}
         }
 
         Ok(FlowSignal::Continue)
     }
 
+    fn review_request(&self) -> Option<NewRequest> {
+        let start_point = self.start_point.clone()?;
+        let selected_extras = match &self.quick_extras {
+            Some(extras) => extras.clone(),
+            None if self.copy_extras_choice.yes_selected => self.extras.selected_for_copy(),
+            None => Vec::new(),
+        };
+
+        let name = seshmux_core::names::parse_worktree_name(self.name_input.value()).ok()?;
+        let mut request = NewRequest::new(self.cwd.clone(), name.slug, start_point);
+        request.display_name = name.display;
+        request.branch_name = Some(self.branch_input.value().to_string());
+        request.add_gitignore_entries = !self.prepare.missing_gitignore_entries.is_empty()
+            && self.gitignore_choice.yes_selected;
+        request.selected_extras = selected_extras;
+        request.project = self.selected_project().map(|(name, _)| name.clone());
+        request.ignore_other_worktrees = self.ignore_other_worktrees;
+        request.use_existing_branch = self.use_existing_branch;
+        request.lfs_pull = Some(self.lfs_pull);
+        request.init_submodules = Some(self.init_submodules);
+        request.direnv_allow = Some(self.direnv_allow);
+        request.carry_changes = !self.changed_files.is_empty() && self.carry_choice.yes_selected;
+        request.connect_now = self.connect_choice.yes_selected;
+        Some(request)
+    }
+
+    fn submit_quick(&mut self, ops: &dyn NewFlowOps, slug: &str) -> Result<FlowSignal> {
+        let request = match ops.quick_request(&self.cwd, slug) {
+            Ok(request) => request,
+            Err(error) => {
+                self.step = Step::ErrorScreen(NewFlowErrorState::from_error(
+                    NewFlowErrorOrigin::ReviewSubmit,
+                    &error,
+                ));
+                return Ok(FlowSignal::Continue);
+            }
+        };
+
+        let branch = self.branch_input.value().trim().to_string();
+        if self.raise_branch_conflict(ops, &branch)? {
+            self.quick = false;
+            return Ok(FlowSignal::Continue);
+        }
+
+        self.start_point = Some(request.start_point);
+        self.gitignore_choice =
+            crate::ui::binary_choice::BinaryChoice::new(request.add_gitignore_entries);
+        self.copy_extras_choice =
+            crate::ui::binary_choice::BinaryChoice::new(!request.selected_extras.is_empty());
+        self.quick_extras = Some(request.selected_extras);
+        self.connect_choice = crate::ui::binary_choice::BinaryChoice::new(request.connect_now);
+        self.ignore_other_worktrees = false;
+        self.use_existing_branch = false;
+        self.show_review();
+        Ok(self
+            .review_request()
+            .map_or(FlowSignal::Continue, FlowSignal::Execute))
+    }
+
     fn on_key_success(&mut self, key: KeyEvent) -> Result<FlowSignal> {
         if keymap::is_quit(key) {
             return Ok(FlowSignal::Exit(UiExit::Completed));
@@ -451,18 +775,55 @@ impl NewFlow {
             return Ok(FlowSignal::Exit(UiExit::BackAtRoot));
         }
 
+        if key.code == KeyCode::Char('y') {
+            self.pending_copy = self
+                .success
+                .as_ref()
+                .map(|result| result.attach_command.clone());
+        }
+
         Ok(FlowSignal::Continue)
     }
 
     fn on_key_error(&mut self, key: KeyEvent) -> Result<FlowSignal> {
-        if keymap::is_back(key) || keymap::is_confirm(key) {
-            let Step::ErrorScreen(error) = &self.step else {
-                return Ok(FlowSignal::Continue);
-            };
-            self.step = match error.origin {
-                NewFlowErrorOrigin::ExtrasIndexing => Step::CopyExtrasDecision,
-                NewFlowErrorOrigin::ReviewSubmit => Step::Review,
-            };
+        let Step::ErrorScreen(error) = &mut self.step else {
+            return Ok(FlowSignal::Continue);
+        };
+        let origin = error.origin;
+
+        match error.actions.on_key(key) {
+            ErrorActionsEvent::Continue => {}
+            ErrorActionsEvent::Back => {
+                self.step = match origin {
+                    NewFlowErrorOrigin::ExtrasIndexing => Step::CopyExtrasDecision,
+                    NewFlowErrorOrigin::ReviewSubmit => Step::Review,
+                };
+            }
+            ErrorActionsEvent::Choose(NewErrorAction::PickAnotherName) => {
+                self.ignore_other_worktrees = false;
+                self.use_existing_branch = false;
+                self.step = Step::NameInput;
+            }
+            ErrorActionsEvent::Choose(NewErrorAction::UseExistingBranch) => {
+                self.use_existing_branch = true;
+                self.start_point =
+                    Some(NewStartPoint::Branch(self.branch_input.value().to_string()));
+                self.show_review();
+            }
+            ErrorActionsEvent::Choose(NewErrorAction::ChooseStartPoint) => {
+                self.quick = false;
+                self.quick_extras = None;
+                self.step = Step::StartPointMode;
+            }
+            ErrorActionsEvent::Choose(NewErrorAction::ChooseExtras) => {
+                self.quick = false;
+                self.quick_extras = None;
+                self.step = Step::CopyExtrasDecision;
+            }
+            ErrorActionsEvent::Choose(NewErrorAction::OpenShell) => {
+                self.show_review();
+                return Ok(FlowSignal::Shell(self.prepare.repo_root.clone()));
+            }
         }
         Ok(FlowSignal::Continue)
     }
@@ -472,10 +833,15 @@ impl NewFlow {
         ops: &dyn NewFlowOps,
         query: &str,
     ) -> Result<PickerState<seshmux_core::git::BranchRef>> {
+        let repo_root = &self.prepare.repo_root;
+        if let Some(picker) = self.query_cache.branches(repo_root, query) {
+            return Ok(offer_new_branch(picker, query));
+        }
         let items = ops
-            .query_branches(&self.prepare.repo_root, query)
+            .query_branches(repo_root, query)
             .with_context(|| "failed to load branch list".to_string())?;
-        Ok(PickerState::from_items(items))
+        self.query_cache.store_branches(repo_root, query, &items);
+        Ok(offer_new_branch(PickerState::from_items(items), query))
     }
 
     fn load_commits(
@@ -483,9 +849,120 @@ impl NewFlow {
         ops: &dyn NewFlowOps,
         query: &str,
     ) -> Result<PickerState<seshmux_core::git::CommitRef>> {
+        let repo_root = &self.prepare.repo_root;
+        if let Some(picker) = self
+            .query_cache
+            .commits(repo_root, self.commit_search, query)
+        {
+            return Ok(picker);
+        }
         let items = ops
-            .query_commits(&self.prepare.repo_root, query, 50)
+            .query_commits(repo_root, query, self.commit_search, 0, COMMIT_PAGE_SIZE)
             .with_context(|| "failed to load commit list".to_string())?;
-        Ok(PickerState::from_items(items))
+        let picker = PickerState::from_page(items, COMMIT_PAGE_SIZE);
+        self.query_cache
+            .store_commits(repo_root, self.commit_search, query, &picker);
+        Ok(picker)
+    }
+
+    fn load_more_commits(&mut self, ops: &dyn NewFlowOps) -> Result<()> {
+        let Some(picker) = &mut self.commit_picker else {
+            return Ok(());
+        };
+        let query = self.commit_search_input.value().trim().to_string();
+        let items = ops
+            .query_commits(
+                &self.prepare.repo_root,
+                &query,
+                self.commit_search,
+                picker.items.len(),
+                COMMIT_PAGE_SIZE,
+            )
+            .with_context(|| "failed to load more commits".to_string())?;
+        picker.append_page(items, COMMIT_PAGE_SIZE);
+        self.query_cache
+            .store_commits(&self.prepare.repo_root, self.commit_search, &query, picker);
+        Ok(())
+    }
+
+    fn filter_branches(&mut self) {
+        let query = self.branch_search_input.value().trim().to_string();
+        match self.query_cache.branches(&self.prepare.repo_root, &query) {
+            Some(picker) => {
+                self.branch_picker = Some(offer_new_branch(picker, &query));
+                self.pending_query = None;
+            }
+            None => {
+                self.pending_query = Some(PendingQuery::Branches(Instant::now() + QUERY_DEBOUNCE));
+            }
+        }
+    }
+
+    fn filter_commits(&mut self) {
+        let query = self.commit_search_input.value().trim().to_string();
+        match self
+            .query_cache
+            .commits(&self.prepare.repo_root, self.commit_search, &query)
+        {
+            Some(picker) => {
+                self.commit_picker = Some(picker);
+                self.pending_query = None;
+            }
+            None => {
+                self.pending_query = Some(PendingQuery::Commits(Instant::now() + QUERY_DEBOUNCE));
+            }
+        }
+    }
+
+    pub(super) fn run_due_query(&mut self, ops: &dyn NewFlowOps, now: Instant) -> Result<()> {
+        match self.pending_query {
+            Some(pending) if pending.due(now) => self.flush_pending_query(ops),
+            _ => Ok(()),
+        }
+    }
+
+    fn flush_pending_query(&mut self, ops: &dyn NewFlowOps) -> Result<()> {
+        match self.pending_query.take() {
+            Some(PendingQuery::Branches(_)) => {
+                let query = self.branch_search_input.value().trim().to_string();
+                self.branch_picker = Some(self.load_branches(ops, &query)?);
+            }
+            Some(PendingQuery::Commits(_)) => {
+                let query = self.commit_search_input.value().trim().to_string();
+                self.commit_picker = Some(self.load_commits(ops, &query)?);
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    fn refresh_picker(&mut self, ops: &dyn NewFlowOps) -> Result<()> {
+        self.query_cache.clear();
+        self.pending_query = None;
+        match self.step {
+            Step::BranchPicker => {
+                let query = self.branch_search_input.value().trim().to_string();
+                self.branch_picker = Some(self.load_branches(ops, &query)?);
+            }
+            Step::CommitPicker => {
+                let query = self.commit_search_input.value().trim().to_string();
+                self.commit_picker = Some(self.load_commits(ops, &query)?);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+fn offer_new_branch(
+    picker: PickerState<seshmux_core::git::BranchRef>,
+    query: &str,
+) -> PickerState<seshmux_core::git::BranchRef> {
+    if !picker.items.is_empty()
+        || query.is_empty()
+        || seshmux_core::names::validate_branch_name(query).is_err()
+    {
+        return picker;
     }
+    picker.with_actions(vec![format!("Create branch '{query}' from current HEAD")])
 }
@@ -1,66 +1,133 @@
+mod creation;
 pub(crate) mod extras;
+mod help;
 mod keys;
 mod picker;
+mod query_cache;
 mod render;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 use std::sync::mpsc::{Receiver, TryRecvError};
+use std::time::Instant;
 
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use crossterm::event::{KeyEvent, MouseEvent};
-use seshmux_app::{App, NewPrepare, NewRequest, NewResult, NewStartPoint};
-use seshmux_core::git::{BranchRef, CommitRef};
+use seshmux_app::{
+    App, BranchConflict, ErrorKind, NewPrepare, NewRequest, NewResult, NewStartPoint, RepoError,
+    classify_error,
+};
+use seshmux_core::config::ProjectConfig;
+use seshmux_core::direnv;
+use seshmux_core::extras::ExtraOrigin;
+use seshmux_core::git::{BranchRef, CommitRef, CommitSearch};
 use tui_input::Input;
 
 use crate::UiExit;
+use crate::notify::{JobCompletion, JobReporter};
+use crate::theme::Theme;
 use crate::ui::binary_choice::BinaryChoice;
+use crate::ui::error_actions::ErrorActions;
 use crate::ui::loading::{
-    BucketPlan, ExtrasLoadEvent, ExtrasLoader, LoadingState, SystemExtrasLoader,
+    BucketPlan, ExtrasLoadEvent, ExtrasLoader, IndexingProgress, LoadingState, SystemExtrasLoader,
 };
 
+use self::creation::{CreationEvent, CreationState};
 use self::extras::ExtrasState;
 use self::picker::PickerState;
+use self::query_cache::QueryCache;
 
 pub(crate) trait NewFlowOps {
     fn prepare(&self, cwd: &Path) -> Result<NewPrepare>;
+    fn branch_conflict(&self, repo_root: &Path, branch: &str) -> Result<Option<BranchConflict>>;
     fn query_branches(&self, repo_root: &Path, query: &str) -> Result<Vec<BranchRef>>;
-    fn query_commits(&self, repo_root: &Path, query: &str, limit: usize) -> Result<Vec<CommitRef>>;
+    fn query_commits(
+        &self,
+        repo_root: &Path,
+        query: &str,
+        search: CommitSearch,
+        skip: usize,
+        limit: usize,
+    ) -> Result<Vec<CommitRef>>;
     fn load_always_skip_buckets_for_indexing(
         &self,
         repo_root: &Path,
     ) -> Result<seshmux_core::registry::AlwaysSkipBucketsLoad>;
     fn save_always_skip_buckets(&self, repo_root: &Path, buckets: &BTreeSet<String>) -> Result<()>;
-    fn execute_new(&self, request: NewRequest) -> Result<NewResult>;
+    fn quick_request(&self, cwd: &Path, worktree_name: &str) -> Result<NewRequest>;
+    fn spawn_execute(
+        &self,
+        request: NewRequest,
+        jobs: JobReporter,
+        cancel: Arc<AtomicBool>,
+    ) -> Receiver<CreationEvent>;
+    fn connect_new(&self, result: &NewResult) -> Result<()>;
+    fn changed_files(&self, cwd: &Path) -> Result<Vec<String>>;
 }
 
 impl<'a> NewFlowOps for App<'a> {
     fn prepare(&self, cwd: &Path) -> Result<NewPrepare> {
-        self.new_prepare(cwd)
+        Ok(self.new_prepare(cwd)?)
+    }
+
+    fn branch_conflict(&self, repo_root: &Path, branch: &str) -> Result<Option<BranchConflict>> {
+        Ok(self.new_branch_conflict(repo_root, branch)?)
     }
 
     fn query_branches(&self, repo_root: &Path, query: &str) -> Result<Vec<BranchRef>> {
-        self.new_query_branches(repo_root, query)
+        Ok(self.new_query_branches(repo_root, query)?)
     }
 
-    fn query_commits(&self, repo_root: &Path, query: &str, limit: usize) -> Result<Vec<CommitRef>> {
-        self.new_query_commits(repo_root, query, limit)
+    fn query_commits(
+        &self,
+        repo_root: &Path,
+        query: &str,
+        search: CommitSearch,
+        skip: usize,
+        limit: usize,
+    ) -> Result<Vec<CommitRef>> {
+        Ok(self.new_query_commits(repo_root, query, search, skip, limit)?)
     }
 
     fn load_always_skip_buckets_for_indexing(
         &self,
         repo_root: &Path,
     ) -> Result<seshmux_core::registry::AlwaysSkipBucketsLoad> {
-        self.new_load_always_skip_buckets_for_indexing(repo_root)
+        Ok(self.new_load_always_skip_buckets_for_indexing(repo_root)?)
     }
 
     fn save_always_skip_buckets(&self, repo_root: &Path, buckets: &BTreeSet<String>) -> Result<()> {
-        self.new_save_always_skip_buckets(repo_root, buckets)
+        Ok(self.new_save_always_skip_buckets(repo_root, buckets)?)
     }
 
-    fn execute_new(&self, request: NewRequest) -> Result<NewResult> {
-        self.new_execute(request)
+    fn quick_request(&self, cwd: &Path, worktree_name: &str) -> Result<NewRequest> {
+        Ok(self.new_quick_request(cwd, worktree_name)?)
+    }
+
+    fn spawn_execute(
+        &self,
+        request: NewRequest,
+        jobs: JobReporter,
+        cancel: Arc<AtomicBool>,
+    ) -> Receiver<CreationEvent> {
+        creation::spawn_creation(
+            request,
+            self.runner.policy(),
+            self.load_options().clone(),
+            self.provision_log().cloned(),
+            jobs,
+            cancel,
+        )
+    }
+
+    fn connect_new(&self, result: &NewResult) -> Result<()> {
+        Ok(self.new_connect(result)?)
+    }
+
+    fn changed_files(&self, cwd: &Path) -> Result<Vec<String>> {
+        Ok(self.new_changed_files(cwd)?)
     }
 }
 
@@ -68,6 +135,9 @@ impl<'a> NewFlowOps for App<'a> {
 enum Step {
     GitignoreDecision,
     NameInput,
+    ProjectPicker,
+    BranchNameInput,
+    BranchConflict,
     StartPointMode,
     BranchPicker,
     CommitPicker,
@@ -75,11 +145,27 @@ enum Step {
     ExtrasIndexing,
     ExtrasPicker,
     ConnectNow,
+    CarryChanges,
     Review,
+    Creating,
     Success,
     ErrorScreen(NewFlowErrorState),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PendingQuery {
+    Branches(Instant),
+    Commits(Instant),
+}
+
+impl PendingQuery {
+    fn due(self, now: Instant) -> bool {
+        match self {
+            Self::Branches(at) | Self::Commits(at) => at <= now,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ConnectBackTarget {
     CopyExtrasDecision,
@@ -96,6 +182,58 @@ enum NewFlowErrorOrigin {
 struct NewFlowErrorState {
     origin: NewFlowErrorOrigin,
     message: String,
+    actions: ErrorActions<NewErrorAction>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NewErrorAction {
+    PickAnotherName,
+    UseExistingBranch,
+    ChooseStartPoint,
+    OpenShell,
+    ChooseExtras,
+}
+
+impl NewFlowErrorState {
+    fn from_error(origin: NewFlowErrorOrigin, error: &anyhow::Error) -> Self {
+        let actions = match classify_error(error) {
+            ErrorKind::BranchExists { branch } => ErrorActions::new(vec![
+                (
+                    "Pick another name".to_string(),
+                    NewErrorAction::PickAnotherName,
+                ),
+                (
+                    format!("Check out the existing branch '{branch}' instead"),
+                    NewErrorAction::UseExistingBranch,
+                ),
+            ]),
+            ErrorKind::BranchCheckedOut { .. } => ErrorActions::new(vec![(
+                "Pick another name".to_string(),
+                NewErrorAction::PickAnotherName,
+            )]),
+            ErrorKind::NoCommits => ErrorActions::new(vec![
+                (
+                    "Start from another branch or commit".to_string(),
+                    NewErrorAction::ChooseStartPoint,
+                ),
+                (
+                    "Open a shell in the repository to make a commit".to_string(),
+                    NewErrorAction::OpenShell,
+                ),
+            ]),
+            ErrorKind::InsufficientSpace { .. } => ErrorActions::new(vec![(
+                "Choose which extras to copy again".to_string(),
+                NewErrorAction::ChooseExtras,
+            )]),
+            _ => ErrorActions::none(),
+        };
+
+        Self {
+            origin,
+            message: format!("{error:#}"),
+            actions,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -112,6 +250,7 @@ struct SkipBucketChoice {
     count: usize,
     skip: bool,
     locked_in_config: bool,
+    suggested: bool,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -127,16 +266,21 @@ impl SkipModalState {
             .iter()
             .filter_map(|value| normalized_path_components(value))
             .collect::<Vec<_>>();
-        let choices = plan
-            .flagged
-            .iter()
-            .map(|item| SkipBucketChoice {
-                bucket: item.bucket.clone(),
-                count: item.count,
-                skip: true,
-                locked_in_config: bucket_locked_in_config(&item.bucket, &configured_patterns),
-            })
-            .collect();
+        let flagged = plan.flagged.iter().map(|item| SkipBucketChoice {
+            bucket: item.bucket.clone(),
+            count: item.count,
+            skip: true,
+            locked_in_config: bucket_locked_in_config(&item.bucket, &configured_patterns),
+            suggested: false,
+        });
+        let suggested = plan.suggested.iter().map(|item| SkipBucketChoice {
+            bucket: item.bucket.clone(),
+            count: item.count,
+            skip: false,
+            locked_in_config: false,
+            suggested: true,
+        });
+        let choices = flagged.chain(suggested).collect();
 
         Self {
             choices,
@@ -203,11 +347,14 @@ fn bucket_locked_in_config(bucket: &str, configured_patterns: &[Vec<String>]) ->
 #[derive(Debug)]
 struct ExtrasIndexingState {
     token: u64,
+    started_at: Instant,
     phase: ExtrasIndexingPhase,
+    progress: Option<IndexingProgress>,
     loading: LoadingState,
     collect_receiver: Option<Receiver<ExtrasLoadEvent>>,
     build_receiver: Option<Receiver<ExtrasLoadEvent>>,
     collect_candidates: Option<Vec<PathBuf>>,
+    collect_origins: BTreeMap<PathBuf, ExtraOrigin>,
     collect_plan: Option<BucketPlan>,
     skip_modal: Option<SkipModalState>,
     persisted_skip_rules: BTreeSet<String>,
@@ -223,6 +370,16 @@ struct NewFlow {
     gitignore_choice: BinaryChoice,
     name_input: Input,
     name_error: Option<String>,
+    name_suggestion: Option<usize>,
+    review_worktree_path: String,
+    project_selected: usize,
+    branch_input: Input,
+    branch_error: Option<String>,
+    branch_default: String,
+    branch_conflict: Option<BranchConflict>,
+    conflict_selected: usize,
+    ignore_other_worktrees: bool,
+    use_existing_branch: bool,
     start_mode_selected: usize,
     start_point: Option<NewStartPoint>,
     branch_picker: Option<PickerState<BranchRef>>,
@@ -231,6 +388,9 @@ struct NewFlow {
     commit_picker: Option<PickerState<CommitRef>>,
     commit_search_input: Input,
     commit_filter_focused: bool,
+    commit_search: CommitSearch,
+    query_cache: QueryCache,
+    pending_query: Option<PendingQuery>,
     copy_extras_choice: BinaryChoice,
     extras_indexing: Option<ExtrasIndexingState>,
     active_extras_index_token: Option<u64>,
@@ -239,38 +399,111 @@ struct NewFlow {
     pending_skip_buckets_to_persist_after_create: Option<BTreeSet<String>>,
     connect_choice: BinaryChoice,
     connect_back_target: ConnectBackTarget,
+    changed_files: Vec<String>,
+    carry_choice: BinaryChoice,
+    lfs_pull: bool,
+    init_submodules: bool,
+    direnv_allow: bool,
+    creation: Option<CreationState>,
+    quick: bool,
+    quick_extras: Option<Vec<PathBuf>>,
     success: Option<NewResult>,
     success_notice: Option<String>,
+    jobs: JobReporter,
+    pending_copy: Option<String>,
 }
 
 pub(crate) struct NewScreen {
     flow: NewFlow,
+    pending_shell: Option<PathBuf>,
+    pending_attach: Option<String>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 enum FlowSignal {
     Continue,
+    Execute(NewRequest),
+    Shell(PathBuf),
+    Attach(String),
     Exit(UiExit),
 }
 
 impl NewScreen {
-    pub(crate) fn new(app: &App<'_>, cwd: &Path) -> Result<Self> {
+    pub(crate) fn new(app: &App<'_>, cwd: &Path, jobs: JobReporter) -> Result<Self> {
+        let mut flow = NewFlow::new(app, cwd)?;
+        flow.jobs = jobs;
         Ok(Self {
-            flow: NewFlow::new(app, cwd)?,
+            flow,
+            pending_shell: None,
+            pending_attach: None,
         })
     }
 
-    pub(crate) fn render(&self, frame: &mut ratatui::Frame<'_>) {
-        self.flow.render(frame);
+    pub(crate) fn new_quick(app: &App<'_>, cwd: &Path, jobs: JobReporter) -> Result<Self> {
+        let mut screen = Self::new(app, cwd, jobs)?;
+        screen.flow.enter_quick_mode();
+        Ok(screen)
+    }
+
+    pub(crate) fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        self.flow.render(frame, theme);
     }
 
     pub(crate) fn on_key(&mut self, key: KeyEvent, app: &App<'_>) -> Result<Option<UiExit>> {
         match self.flow.on_key(key, app)? {
             FlowSignal::Continue => Ok(None),
+            FlowSignal::Execute(request) => {
+                self.flow.start_creation(app, request);
+                Ok(None)
+            }
+            FlowSignal::Shell(dir) => {
+                self.pending_shell = Some(dir);
+                Ok(None)
+            }
+            FlowSignal::Attach(worktree_name) => {
+                self.pending_attach = Some(worktree_name);
+                Ok(None)
+            }
             FlowSignal::Exit(exit) => Ok(Some(exit)),
         }
     }
 
+    pub(crate) fn take_pending_shell(&mut self) -> Option<PathBuf> {
+        self.pending_shell.take()
+    }
+
+    pub(crate) fn take_pending_attach(&mut self) -> Option<String> {
+        self.pending_attach.take()
+    }
+
+    pub(crate) fn finish_creation(&mut self, app: &App<'_>) {
+        self.flow.finish_creation(app);
+    }
+
+    pub(crate) fn creating(&self) -> bool {
+        self.flow.creating()
+    }
+
+    pub(crate) fn has_answers(&self) -> bool {
+        self.flow.has_answers()
+    }
+
+    pub(crate) fn run_due_query(&mut self, app: &App<'_>) -> Result<()> {
+        self.flow.run_due_query(app, Instant::now())
+    }
+
+    pub(crate) fn query_pending(&self) -> bool {
+        self.flow.pending_query.is_some()
+    }
+
+    pub(crate) fn cancel_creation(&self) {
+        self.flow.cancel_creation();
+    }
+
+    pub(crate) fn on_paste(&mut self, text: &str, app: &App<'_>) -> Result<()> {
+        self.flow.on_paste(text, app)
+    }
+
     pub(crate) fn on_tick(&mut self) -> Result<()> {
         self.flow.on_tick();
         Ok(())
@@ -284,6 +517,10 @@ impl NewScreen {
     pub(crate) fn should_drain_loader_after_input(&self) -> bool {
         self.flow.should_drain_loader_after_input()
     }
+
+    pub(crate) fn take_pending_copy(&mut self) -> Option<String> {
+        self.flow.pending_copy.take()
+    }
 }
 
 impl NewFlow {
@@ -297,28 +534,48 @@ impl NewFlow {
         loader: Arc<dyn ExtrasLoader>,
     ) -> Result<Self> {
         let prepare = ops.prepare(cwd)?;
-        let commits = ops.query_commits(&prepare.repo_root, "", 1)?;
+        let commits = ops.query_commits(&prepare.repo_root, "", CommitSearch::Hash, 0, 1)?;
         if commits.is_empty() {
-            return Err(anyhow!(
-                "current branch/HEAD has no commits yet; create an initial commit on this branch before starting seshmux"
-            ));
+            return Err(RepoError::NoCommits.into());
         }
         let extras = ExtrasState::from_candidates(&[])?;
+        let changed_files = ops.changed_files(cwd)?;
 
-        let first_step = if prepare.gitignore_has_worktrees_entry {
+        let first_step = if prepare.missing_gitignore_entries.is_empty() {
             Step::NameInput
         } else {
             Step::GitignoreDecision
         };
+        let (lfs_pull, init_submodules, direnv_allow, defaults) = (
+            prepare.lfs_pull,
+            prepare.init_submodules,
+            prepare.direnv_allow,
+            prepare.defaults,
+        );
+        let name_input = prepare
+            .detached_head
+            .as_deref()
+            .map(|short_sha| Input::new(seshmux_core::names::detached_worktree_name(short_sha)))
+            .unwrap_or_default();
 
         Ok(Self {
             cwd: cwd.to_path_buf(),
             prepare,
             loader,
             step: first_step,
-            gitignore_choice: BinaryChoice::new(true),
-            name_input: Input::default(),
+            gitignore_choice: BinaryChoice::new(defaults.gitignore),
+            name_input,
             name_error: None,
+            name_suggestion: None,
+            review_worktree_path: String::new(),
+            project_selected: 0,
+            branch_input: Input::default(),
+            branch_error: None,
+            branch_default: String::new(),
+            branch_conflict: None,
+            conflict_selected: 0,
+            ignore_other_worktrees: false,
+            use_existing_branch: false,
             start_mode_selected: 0,
             start_point: None,
             branch_picker: None,
@@ -327,30 +584,75 @@ impl NewFlow {
             commit_picker: None,
             commit_search_input: Input::default(),
             commit_filter_focused: false,
-            copy_extras_choice: BinaryChoice::new(false),
+            commit_search: CommitSearch::Hash,
+            query_cache: QueryCache::default(),
+            pending_query: None,
+            copy_extras_choice: BinaryChoice::new(defaults.copy_extras),
             extras_indexing: None,
             active_extras_index_token: None,
             next_extras_index_token: 1,
             extras,
             pending_skip_buckets_to_persist_after_create: None,
-            connect_choice: BinaryChoice::new(true),
+            connect_choice: BinaryChoice::new(defaults.connect_now),
             connect_back_target: ConnectBackTarget::CopyExtrasDecision,
+            changed_files,
+            carry_choice: BinaryChoice::new(false),
+            lfs_pull,
+            init_submodules,
+            direnv_allow,
+            creation: None,
+            quick: false,
+            quick_extras: None,
             success: None,
             success_notice: None,
+            jobs: JobReporter::default(),
+            pending_copy: None,
         })
     }
 
+    fn enter_quick_mode(&mut self) {
+        self.quick = true;
+        self.step = Step::NameInput;
+    }
+
+    fn selected_project(&self) -> Option<(&String, &ProjectConfig)> {
+        let index = self.project_selected.checked_sub(1)?;
+        self.prepare.projects.iter().nth(index)
+    }
+
+    fn step_before_branch_name(&self) -> Step {
+        if self.prepare.projects.is_empty() {
+            Step::NameInput
+        } else {
+            Step::ProjectPicker
+        }
+    }
+
+    fn step_before_review(&self) -> Step {
+        if self.changed_files.is_empty() {
+            Step::ConnectNow
+        } else {
+            Step::CarryChanges
+        }
+    }
+
     fn start_point_step(&self) -> Step {
+        if self.ignore_other_worktrees {
+            return Step::BranchConflict;
+        }
+
         match self.start_point {
             Some(NewStartPoint::CurrentBranch) => Step::StartPointMode,
-            Some(NewStartPoint::Branch(_)) => Step::BranchPicker,
+            Some(NewStartPoint::Branch(_) | NewStartPoint::NewBranchFrom(_)) => Step::BranchPicker,
             Some(NewStartPoint::Commit(_)) => Step::CommitPicker,
-            None => Step::StartPointMode,
+            Some(_) | None => Step::StartPointMode,
         }
     }
 
     fn review_selected_extras_count(&self) -> usize {
-        if self.copy_extras_choice.yes_selected {
+        if let Some(extras) = &self.quick_extras {
+            extras.len()
+        } else if self.copy_extras_choice.yes_selected {
             self.extras.selected_for_copy().len()
         } else {
             0
@@ -363,10 +665,10 @@ impl NewFlow {
             Err(error) => {
                 self.active_extras_index_token = None;
                 self.extras_indexing = None;
-                self.step = Step::ErrorScreen(NewFlowErrorState {
-                    origin: NewFlowErrorOrigin::ExtrasIndexing,
-                    message: format!("{error:#}"),
-                });
+                self.step = Step::ErrorScreen(NewFlowErrorState::from_error(
+                    NewFlowErrorOrigin::ExtrasIndexing,
+                    &error,
+                ));
                 return;
             }
         };
@@ -386,11 +688,14 @@ impl NewFlow {
 
         self.extras_indexing = Some(ExtrasIndexingState {
             token,
+            started_at: Instant::now(),
             phase: ExtrasIndexingPhase::Collecting,
+            progress: None,
             loading: LoadingState::default(),
             collect_receiver: Some(collect_receiver),
             build_receiver: None,
             collect_candidates: None,
+            collect_origins: BTreeMap::new(),
             collect_plan: None,
             skip_modal: None,
             persisted_skip_rules: loaded.buckets,
@@ -406,7 +711,8 @@ impl NewFlow {
     }
 
     fn should_drain_loader_after_input(&self) -> bool {
-        self.step == Step::ExtrasIndexing && self.extras_indexing.is_some()
+        (self.step == Step::ExtrasIndexing && self.extras_indexing.is_some())
+            || self.step == Step::Creating
     }
 
     fn fail_extras_indexing(&mut self, message: String) {
@@ -415,6 +721,7 @@ impl NewFlow {
         self.step = Step::ErrorScreen(NewFlowErrorState {
             origin: NewFlowErrorOrigin::ExtrasIndexing,
             message,
+            actions: ErrorActions::none(),
         });
     }
 
@@ -438,6 +745,7 @@ impl NewFlow {
             indexing.phase = ExtrasIndexingPhase::Building {
                 filtered_count: filtered.len(),
             };
+            indexing.progress = None;
             indexing.build_receiver = Some(build_receiver);
             indexing.collect_receiver = None;
             indexing.collect_candidates = None;
@@ -454,6 +762,10 @@ impl NewFlow {
         match event {
             ExtrasLoadEvent::Collecting => {
                 indexing.phase = ExtrasIndexingPhase::Collecting;
+                indexing.progress = None;
+            }
+            ExtrasLoadEvent::Progress(progress) => {
+                indexing.progress = Some(progress);
             }
             ExtrasLoadEvent::Classifying { candidate_count } => {
                 indexing.phase = ExtrasIndexingPhase::Classifying { candidate_count };
@@ -468,6 +780,7 @@ impl NewFlow {
             ExtrasLoadEvent::DoneCollect {
                 token,
                 candidates,
+                origins,
                 plan,
             } => {
                 if Some(token) != self.active_extras_index_token {
@@ -475,7 +788,8 @@ impl NewFlow {
                 }
 
                 indexing.collect_receiver = None;
-                if plan.flagged_count() == 0 {
+                indexing.collect_origins = origins;
+                if plan.is_empty() {
                     self.start_build_with_candidates(token, candidates, BTreeSet::new());
                     return;
                 }
@@ -511,6 +825,10 @@ impl NewFlow {
         match event {
             ExtrasLoadEvent::Building { filtered_count } => {
                 indexing.phase = ExtrasIndexingPhase::Building { filtered_count };
+                indexing.progress = None;
+            }
+            ExtrasLoadEvent::Progress(progress) => {
+                indexing.progress = Some(progress);
             }
             ExtrasLoadEvent::Done { token, result } => {
                 if Some(token) != self.active_extras_index_token {
@@ -519,7 +837,18 @@ impl NewFlow {
 
                 match result {
                     Ok(index) => {
+                        self.jobs.report(JobCompletion::new(
+                            "Extras indexing",
+                            indexing.started_at.elapsed(),
+                        ));
+                        let origins = std::mem::take(&mut indexing.collect_origins);
                         self.extras = ExtrasState::from_index(index);
+                        self.extras.set_origins(origins);
+                        self.extras.preselect_file(Path::new(direnv::ENVRC));
+                        if let Some((_, project)) = self.selected_project() {
+                            let patterns = project.extras.clone();
+                            self.extras.preselect_matching(&patterns);
+                        }
                         self.invalidate_extras_indexing();
                         self.step = Step::ExtrasPicker;
                     }
@@ -536,6 +865,10 @@ impl NewFlow {
     }
 
     fn on_tick(&mut self) {
+        if self.step == Step::Creating {
+            self.poll_creation();
+            return;
+        }
         if self.step != Step::ExtrasIndexing {
             return;
         }
@@ -696,26 +1029,40 @@ impl NewFlow {
 }
 #[cfg(test)]
 mod tests {
-    use std::collections::BTreeSet;
+    use std::collections::{BTreeMap, BTreeSet};
     use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicBool, Ordering};
     use std::sync::mpsc::{self, Sender};
     use std::sync::{Arc, Mutex};
     use std::time::{Duration, Instant};
 
     use anyhow::Result;
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
-    use seshmux_app::{NewPrepare, NewRequest, NewResult};
-    use seshmux_core::git::{BranchRef, BranchSource, CommitRef};
-
-    use crate::ui::loading::{BucketPlan, ExtrasLoadEvent, ExtrasLoader, FlaggedBucket};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use seshmux_app::{
+        BranchConflict, NewPhase, NewPrepare, NewProgress, NewRequest, NewResult, NewStartPoint,
+    };
+    use seshmux_core::config::{DefaultsConfig, ProjectConfig};
+    use seshmux_core::git::{BranchRef, BranchSource, CommitRef, CommitSearch, GitError};
+
+    use crate::notify::{JobReporter, JobTracker};
+    use crate::theme::Theme;
+    use crate::ui::error_actions::ErrorActions;
+    use crate::ui::loading::{
+        BucketPlan, ExtrasLoadEvent, ExtrasLoader, FlaggedBucket, IndexingProgress,
+    };
 
-    use super::{FlowSignal, NewFlow, NewFlowErrorOrigin, NewFlowErrorState, NewFlowOps, Step};
+    use super::{
+        CreationEvent, FlowSignal, NewFlow, NewFlowErrorOrigin, NewFlowErrorState, NewFlowOps, Step,
+    };
 
     struct FakeOps {
         prepare: NewPrepare,
         branches: Vec<BranchRef>,
         latest_commits: Vec<CommitRef>,
         searched_commits: Vec<CommitRef>,
+        commit_queries: Mutex<Vec<(String, CommitSearch, usize)>>,
         always_skip_buckets: Mutex<BTreeSet<String>>,
         configured_skip_buckets: Mutex<BTreeSet<String>>,
         skip_registry_missing_for_indexing: bool,
@@ -723,6 +1070,13 @@ mod tests {
         save_skip_buckets_error: Option<String>,
         execute_calls: Mutex<Vec<NewRequest>>,
         execute_error: Option<String>,
+        hold_execute: bool,
+        execute_sender: Mutex<Option<Sender<CreationEvent>>>,
+        execute_cancel: Mutex<Option<Arc<AtomicBool>>>,
+        connect_calls: Mutex<Vec<String>>,
+        connect_error: Option<String>,
+        branch_conflict: Option<BranchConflict>,
+        changed_files: Vec<String>,
     }
 
     impl FakeOps {
@@ -731,7 +1085,18 @@ mod tests {
                 prepare: NewPrepare {
                     repo_root: repo_root.clone(),
                     worktrees_dir: repo_root.join("worktrees"),
-                    gitignore_has_worktrees_entry: false,
+                    missing_gitignore_entries: vec!["worktrees/".to_string()],
+                    branch_template: None,
+                    worktree_path_template: None,
+                    detached_head: None,
+                    existing_names: BTreeSet::new(),
+                    name_suggestions: Vec::new(),
+                    lfs_pull: false,
+                    init_submodules: true,
+                    has_envrc: false,
+                    direnv_allow: false,
+                    defaults: DefaultsConfig::default(),
+                    projects: BTreeMap::new(),
                 },
                 branches: vec![BranchRef {
                     name: "main".to_string(),
@@ -743,13 +1108,18 @@ mod tests {
                     short_hash: "aaaaaaa".to_string(),
                     subject: "first".to_string(),
                     display: "aaaaaaa first".to_string(),
+                    author: String::new(),
+                    relative_date: String::new(),
                 }],
                 searched_commits: vec![CommitRef {
                     hash: "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string(),
                     short_hash: "bbbbbbb".to_string(),
                     subject: "second".to_string(),
                     display: "bbbbbbb second".to_string(),
+                    author: String::new(),
+                    relative_date: String::new(),
                 }],
+                commit_queries: Mutex::new(Vec::new()),
                 always_skip_buckets: Mutex::new(BTreeSet::new()),
                 configured_skip_buckets: Mutex::new(BTreeSet::new()),
                 skip_registry_missing_for_indexing: false,
@@ -757,6 +1127,13 @@ mod tests {
                 save_skip_buckets_error: None,
                 execute_calls: Mutex::new(Vec::new()),
                 execute_error: None,
+                hold_execute: false,
+                execute_sender: Mutex::new(None),
+                execute_cancel: Mutex::new(None),
+                connect_calls: Mutex::new(Vec::new()),
+                connect_error: None,
+                branch_conflict: None,
+                changed_files: Vec::new(),
             }
         }
 
@@ -770,6 +1147,17 @@ mod tests {
             Ok(self.prepare.clone())
         }
 
+        fn branch_conflict(
+            &self,
+            _repo_root: &Path,
+            branch: &str,
+        ) -> Result<Option<BranchConflict>> {
+            Ok(self
+                .branch_conflict
+                .clone()
+                .filter(|conflict| conflict.branch == branch))
+        }
+
         fn query_branches(&self, _repo_root: &Path, _query: &str) -> Result<Vec<BranchRef>> {
             Ok(self.branches.clone())
         }
@@ -778,9 +1166,16 @@ mod tests {
             &self,
             _repo_root: &Path,
             query: &str,
+            search: CommitSearch,
+            skip: usize,
             _limit: usize,
         ) -> Result<Vec<CommitRef>> {
-            if query.trim().is_empty() {
+            self.commit_queries.lock().expect("queries lock").push((
+                query.to_string(),
+                search,
+                skip,
+            ));
+            if query.trim().is_empty() && skip == 0 {
                 Ok(self.latest_commits.clone())
             } else {
                 Ok(self.searched_commits.clone())
@@ -822,23 +1217,84 @@ mod tests {
             Ok(())
         }
 
-        fn execute_new(&self, request: NewRequest) -> Result<NewResult> {
-            if let Some(message) = &self.execute_error {
-                return Err(anyhow::anyhow!(message.clone()));
-            }
+        fn quick_request(&self, cwd: &Path, worktree_name: &str) -> Result<NewRequest> {
+            let mut request = NewRequest::new(
+                cwd.to_path_buf(),
+                worktree_name.to_string(),
+                NewStartPoint::Branch("main".to_string()),
+            );
+            request.add_gitignore_entries = true;
+            request.selected_extras = vec![PathBuf::from(".env")];
+            request.connect_now = true;
+            Ok(request)
+        }
+
+        fn spawn_execute(
+            &self,
+            request: NewRequest,
+            _jobs: JobReporter,
+            cancel: Arc<AtomicBool>,
+        ) -> mpsc::Receiver<CreationEvent> {
+            let (sender, receiver) = mpsc::channel();
+            sender
+                .send(CreationEvent::Progress(NewProgress::new(
+                    NewPhase::CreatingWorktree,
+                    request.worktree_name.clone(),
+                )))
+                .expect("send progress");
             self.execute_calls
                 .lock()
                 .expect("execute lock")
                 .push(request.clone());
-            Ok(NewResult {
-                repo_root: request.cwd.clone(),
-                worktrees_dir: request.cwd.join("worktrees"),
-                worktree_path: request.cwd.join("worktrees").join(&request.worktree_name),
-                branch_name: request.worktree_name.clone(),
-                session_name: format!("repo/{}", request.worktree_name),
-                attach_command: format!("tmux attach-session -t repo/{}", request.worktree_name),
-                connected_now: request.connect_now,
-            })
+            *self.execute_cancel.lock().expect("cancel lock") = Some(cancel);
+
+            if self.hold_execute {
+                *self.execute_sender.lock().expect("sender lock") = Some(sender);
+            } else if let Some(message) = &self.execute_error {
+                sender
+                    .send(CreationEvent::Done(Err(anyhow::anyhow!(message.clone()))))
+                    .expect("send done");
+            } else {
+                sender
+                    .send(CreationEvent::Done(Ok(Box::new(fake_result(&request)))))
+                    .expect("send done");
+            }
+            receiver
+        }
+
+        fn connect_new(&self, result: &NewResult) -> Result<()> {
+            self.connect_calls
+                .lock()
+                .expect("connect lock")
+                .push(result.session_name.clone());
+            match &self.connect_error {
+                Some(message) => Err(anyhow::anyhow!(message.clone())),
+                None => Ok(()),
+            }
+        }
+
+        fn changed_files(&self, _cwd: &Path) -> Result<Vec<String>> {
+            Ok(self.changed_files.clone())
+        }
+    }
+
+    fn fake_result(request: &NewRequest) -> NewResult {
+        {
+            let mut result = NewResult::new(
+                request.cwd.clone(),
+                request.cwd.join("worktrees"),
+                request.worktree_name.clone(),
+                request.cwd.join("worktrees").join(&request.worktree_name),
+                request
+                    .branch_name
+                    .clone()
+                    .unwrap_or_else(|| request.worktree_name.clone()),
+                format!("repo/{}", request.worktree_name),
+            );
+            result.display_name = request.display_name.clone();
+            result.attach_command =
+                format!("tmux attach-session -t repo/{}", request.worktree_name);
+            result
         }
     }
 
@@ -944,6 +1400,16 @@ mod tests {
         KeyEvent::new(code, KeyModifiers::NONE)
     }
 
+    fn submit_review(flow: &mut NewFlow, ops: &FakeOps) {
+        let signal = flow.on_key(key(KeyCode::Enter), ops).expect("submit");
+        let FlowSignal::Execute(request) = signal else {
+            panic!("review did not submit: {signal:?}");
+        };
+        flow.start_creation(ops, request);
+        flow.on_tick();
+        flow.finish_creation(ops);
+    }
+
     fn mouse(kind: MouseEventKind) -> MouseEvent {
         MouseEvent {
             kind,
@@ -968,6 +1434,7 @@ mod tests {
                 .expect("name");
         }
         flow.on_key(key(KeyCode::Enter), ops).expect("name enter");
+        flow.on_key(key(KeyCode::Enter), ops).expect("branch enter");
         flow.on_key(key(KeyCode::Enter), ops)
             .expect("start current branch");
         assert_eq!(flow.step, Step::CopyExtrasDecision);
@@ -1000,6 +1467,7 @@ mod tests {
         loader.send_collect(ExtrasLoadEvent::DoneCollect {
             token,
             candidates: paths.to_vec(),
+            origins: BTreeMap::new(),
             plan: BucketPlan::default(),
         });
         flow.on_tick();
@@ -1033,118 +1501,911 @@ mod tests {
     }
 
     #[test]
-    fn new_flow_opt_in_starts_async_collect_only_after_confirmation() {
+    fn commit_picker_loads_the_next_page_and_searches_messages_after_tab() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let mut ops = FakeOps::new(repo_root.clone());
+        let first = ops.latest_commits[0].clone();
+        ops.latest_commits = (0..50)
+            .map(|index| CommitRef {
+                hash: format!("{index:040}"),
+                ..first.clone()
+            })
+            .collect();
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader, &repo_root);
+
+        flow.on_key(key(KeyCode::Enter), &ops).expect("gitignore");
+        for character in "alpha".chars() {
+            flow.on_key(key(KeyCode::Char(character)), &ops)
+                .expect("name");
+        }
+        flow.on_key(key(KeyCode::Enter), &ops).expect("name enter");
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("branch enter");
+        flow.on_key(key(KeyCode::Down), &ops).expect("move");
+        flow.on_key(key(KeyCode::Down), &ops).expect("move");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("commit mode");
+        assert_eq!(flow.step, Step::CommitPicker);
+        assert!(flow.commit_picker.as_ref().expect("picker").has_more);
+
+        for _ in 0..50 {
+            flow.on_key(key(KeyCode::Down), &ops).expect("move");
+        }
+        let picker = flow.commit_picker.as_ref().expect("picker");
+        assert_eq!(picker.items.len(), 51);
+        assert_eq!(picker.selected, 50);
+        assert_eq!(picker.items[50].short_hash, "bbbbbbb");
+        assert!(!picker.has_more);
+
+        flow.on_key(key(KeyCode::Tab), &ops).expect("search mode");
+        assert_eq!(flow.commit_search, CommitSearch::Message);
+        assert_eq!(flow.commit_picker.as_ref().expect("picker").selected, 0);
+        let queries = ops.commit_queries.lock().expect("queries lock").clone();
+        assert_eq!(
+            queries[queries.len() - 2..],
+            [
+                (String::new(), CommitSearch::Hash, 50),
+                (String::new(), CommitSearch::Message, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn commit_picker_debounces_filter_queries_and_reuses_cached_lists() {
         let temp = tempfile::tempdir().expect("temp dir");
         let repo_root = temp.path().join("repo");
         std::fs::create_dir_all(&repo_root).expect("repo");
 
         let ops = FakeOps::new(repo_root.clone());
         let loader = Arc::new(ScriptedLoader::default());
-        let mut flow = new_flow(&ops, loader.clone(), &repo_root);
+        let mut flow = new_flow(&ops, loader, &repo_root);
 
-        advance_to_copy_extras_decision(&mut flow, &ops, "alpha");
-        assert_eq!(loader.collect_call_count(), 0);
+        flow.on_key(key(KeyCode::Enter), &ops).expect("gitignore");
+        for character in "alpha".chars() {
+            flow.on_key(key(KeyCode::Char(character)), &ops)
+                .expect("name");
+        }
+        flow.on_key(key(KeyCode::Enter), &ops).expect("name enter");
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("branch enter");
+        flow.on_key(key(KeyCode::Down), &ops).expect("move");
+        flow.on_key(key(KeyCode::Down), &ops).expect("move");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("commit mode");
+        let query_count = || ops.commit_queries.lock().expect("queries lock").len();
+        let opened = query_count();
+
+        flow.on_key(key(KeyCode::Char('/')), &ops).expect("focus");
+        for character in "abc".chars() {
+            flow.on_key(key(KeyCode::Char(character)), &ops)
+                .expect("filter");
+        }
+        flow.run_due_query(&ops, Instant::now()).expect("not due");
+        assert_eq!(query_count(), opened);
 
-        confirm_copy_extras_yes(&mut flow, &ops);
+        flow.run_due_query(&ops, Instant::now() + Duration::from_secs(1))
+            .expect("due");
+        assert_eq!(query_count(), opened + 1);
+        assert_eq!(
+            ops.commit_queries.lock().expect("queries lock")[opened],
+            ("abc".to_string(), CommitSearch::Hash, 0)
+        );
 
-        assert_eq!(flow.step, Step::ExtrasIndexing);
-        assert_eq!(loader.collect_call_count(), 1);
+        for _ in 0..3 {
+            flow.on_key(key(KeyCode::Backspace), &ops)
+                .expect("backspace");
+        }
+        assert!(flow.pending_query.is_none());
+        flow.on_key(key(KeyCode::Esc), &ops).expect("back");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("reopen");
+        assert_eq!(flow.step, Step::CommitPicker);
+        assert_eq!(query_count(), opened + 1);
+
+        flow.on_key(key(KeyCode::Char('r')), &ops).expect("refresh");
+        assert_eq!(query_count(), opened + 2);
     }
 
     #[test]
-    fn copy_extras_yes_transitions_without_waiting_for_listing() {
+    fn branch_conflict_offers_checking_out_the_existing_branch() {
         let temp = tempfile::tempdir().expect("temp dir");
         let repo_root = temp.path().join("repo");
         std::fs::create_dir_all(&repo_root).expect("repo");
 
-        let ops = FakeOps::new(repo_root.clone());
+        let mut ops = FakeOps::new(repo_root.clone());
+        ops.branch_conflict = Some(BranchConflict {
+            branch: "alpha".to_string(),
+            worktree_path: repo_root.join("worktrees/other"),
+            worktree_name: Some("other".to_string()),
+        });
         let loader = Arc::new(ScriptedLoader::default());
         let mut flow = new_flow(&ops, loader, &repo_root);
-        advance_to_copy_extras_decision(&mut flow, &ops, "alpha");
 
-        let started = Instant::now();
-        confirm_copy_extras_yes(&mut flow, &ops);
-        let elapsed = started.elapsed();
+        flow.on_key(key(KeyCode::Enter), &ops).expect("gitignore");
+        for character in "alpha".chars() {
+            flow.on_key(key(KeyCode::Char(character)), &ops)
+                .expect("name");
+        }
+        flow.on_key(key(KeyCode::Enter), &ops).expect("name enter");
+        assert_eq!(flow.step, Step::BranchNameInput);
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("branch enter");
+        assert_eq!(flow.step, Step::BranchConflict);
 
-        assert!(
-            elapsed < Duration::from_millis(25),
-            "copy extras confirmation should not block: {elapsed:?}"
+        flow.on_key(key(KeyCode::Down), &ops).expect("move");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("select");
+        assert_eq!(flow.step, Step::CopyExtrasDecision);
+        assert!(flow.ignore_other_worktrees);
+        assert_eq!(
+            flow.start_point,
+            Some(NewStartPoint::Branch("alpha".to_string()))
         );
-        assert_eq!(flow.step, Step::ExtrasIndexing);
+
+        flow.on_key(key(KeyCode::Esc), &ops).expect("back");
+        assert_eq!(flow.step, Step::BranchConflict);
+        flow.on_key(key(KeyCode::Esc), &ops).expect("back");
+        assert_eq!(flow.step, Step::BranchNameInput);
+        assert!(!flow.ignore_other_worktrees);
     }
 
     #[test]
-    fn extras_indexing_emits_collecting_classifying_and_building_phases() {
+    fn branch_conflict_can_switch_to_the_worktree_that_has_the_branch() {
         let temp = tempfile::tempdir().expect("temp dir");
         let repo_root = temp.path().join("repo");
         std::fs::create_dir_all(&repo_root).expect("repo");
 
-        let ops = FakeOps::new(repo_root.clone());
+        let mut ops = FakeOps::new(repo_root.clone());
+        ops.branch_conflict = Some(BranchConflict {
+            branch: "alpha".to_string(),
+            worktree_path: repo_root.join("worktrees/other"),
+            worktree_name: Some("other".to_string()),
+        });
         let loader = Arc::new(ScriptedLoader::default());
-        let mut flow = new_flow(&ops, loader.clone(), &repo_root);
-        advance_to_copy_extras_decision(&mut flow, &ops, "alpha");
-        confirm_copy_extras_yes(&mut flow, &ops);
+        let mut flow = new_flow(&ops, loader, &repo_root);
 
-        let token = flow.active_extras_index_token.expect("active token");
-        let candidates = vec![PathBuf::from("src/main.rs")];
+        flow.on_key(key(KeyCode::Enter), &ops).expect("gitignore");
+        for character in "alpha".chars() {
+            flow.on_key(key(KeyCode::Char(character)), &ops)
+                .expect("name");
+        }
+        flow.on_key(key(KeyCode::Enter), &ops).expect("name enter");
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("branch enter");
+        flow.on_key(key(KeyCode::Down), &ops).expect("move");
+        flow.on_key(key(KeyCode::Down), &ops).expect("move");
+        let signal = flow.on_key(key(KeyCode::Enter), &ops).expect("select");
+        assert_eq!(signal, FlowSignal::Attach("other".to_string()));
 
-        loader.send_collect(ExtrasLoadEvent::Collecting);
-        flow.on_tick();
-        assert!(matches!(
-            flow.extras_indexing.as_ref().expect("indexing").phase,
-            super::ExtrasIndexingPhase::Collecting
-        ));
+        flow.branch_conflict
+            .as_mut()
+            .expect("conflict")
+            .worktree_name = None;
+        let signal = flow.on_key(key(KeyCode::Enter), &ops).expect("select");
+        assert_eq!(signal, FlowSignal::Shell(repo_root.join("worktrees/other")));
+    }
 
-        loader.send_collect(ExtrasLoadEvent::Classifying { candidate_count: 1 });
-        flow.on_tick();
-        assert!(matches!(
-            flow.extras_indexing.as_ref().expect("indexing").phase,
-            super::ExtrasIndexingPhase::Classifying { candidate_count: 1 }
-        ));
+    #[test]
+    fn branch_filter_without_matches_offers_creating_that_branch() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
 
-        loader.send_collect(ExtrasLoadEvent::AwaitingSkipDecision {
-            flagged_bucket_count: 0,
-        });
-        loader.send_collect(ExtrasLoadEvent::DoneCollect {
-            token,
-            candidates: candidates.clone(),
-            plan: BucketPlan::default(),
-        });
-        flow.on_tick();
-        assert_eq!(loader.build_call_count(), 1);
+        let mut ops = FakeOps::new(repo_root.clone());
+        ops.branches = Vec::new();
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader, &repo_root);
 
-        loader.send_build(ExtrasLoadEvent::Building { filtered_count: 1 });
-        flow.on_tick();
-        assert!(matches!(
-            flow.extras_indexing.as_ref().expect("indexing").phase,
-            super::ExtrasIndexingPhase::Building { filtered_count: 1 }
-        ));
+        flow.on_key(key(KeyCode::Enter), &ops).expect("gitignore");
+        for character in "alpha".chars() {
+            flow.on_key(key(KeyCode::Char(character)), &ops)
+                .expect("name");
+        }
+        flow.on_key(key(KeyCode::Enter), &ops).expect("name enter");
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("branch enter");
+        flow.on_key(key(KeyCode::Down), &ops).expect("move");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("branch mode");
+        assert_eq!(flow.step, Step::BranchPicker);
+        let picker = flow.branch_picker.as_ref().expect("picker");
+        assert!(picker.actions.is_empty());
+
+        flow.on_key(key(KeyCode::Char('/')), &ops).expect("focus");
+        for character in "topic".chars() {
+            flow.on_key(key(KeyCode::Char(character)), &ops)
+                .expect("filter");
+        }
+        flow.on_key(key(KeyCode::Char('/')), &ops).expect("list");
+        let picker = flow.branch_picker.as_ref().expect("picker");
+        assert_eq!(
+            picker.actions,
+            vec!["Create branch 'topic' from current HEAD".to_string()]
+        );
 
-        loader.send_build(ExtrasLoadEvent::Done {
-            token,
-            result: Ok(build_index(&candidates)),
-        });
-        flow.on_tick();
+        flow.on_key(key(KeyCode::Enter), &ops).expect("create");
+        assert_eq!(flow.step, Step::CopyExtrasDecision);
+        assert_eq!(
+            flow.start_point,
+            Some(NewStartPoint::NewBranchFrom("topic".to_string()))
+        );
 
-        assert_eq!(flow.step, Step::ExtrasPicker);
+        flow.on_key(key(KeyCode::Esc), &ops).expect("back");
+        assert_eq!(flow.step, Step::BranchPicker);
     }
 
-    fn open_skip_modal(flow: &mut NewFlow, loader: &ScriptedLoader, candidates: &[PathBuf]) -> u64 {
-        open_skip_modal_for_bucket(flow, loader, candidates, "target")
-    }
+    #[test]
+    fn branch_name_step_defaults_from_the_template_until_edited() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
 
-    fn open_skip_modal_for_bucket(
-        flow: &mut NewFlow,
-        loader: &ScriptedLoader,
-        candidates: &[PathBuf],
-        bucket: &str,
-    ) -> u64 {
-        let token = flow.active_extras_index_token.expect("token");
-        let plan = BucketPlan {
-            flagged: vec![FlaggedBucket {
-                bucket: bucket.to_string(),
-                count: 1,
+        let mut ops = FakeOps::new(repo_root.clone());
+        ops.prepare.branch_template = Some("feature/{name}".to_string());
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader, &repo_root);
+
+        flow.on_key(key(KeyCode::Enter), &ops).expect("gitignore");
+        flow.on_paste("login", &ops).expect("name");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("name enter");
+        assert_eq!(flow.step, Step::BranchNameInput);
+        assert_eq!(flow.branch_input.value(), "feature/login");
+
+        flow.on_key(key(KeyCode::Esc), &ops).expect("back");
+        flow.name_input = tui_input::Input::new("signup".to_string());
+        flow.on_key(key(KeyCode::Enter), &ops).expect("name enter");
+        assert_eq!(flow.branch_input.value(), "feature/signup");
+
+        flow.on_paste("..x", &ops).expect("branch");
+        assert!(flow.branch_error.is_some());
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("branch enter");
+        assert_eq!(flow.step, Step::BranchNameInput);
+
+        flow.branch_input = tui_input::Input::new("fix/signup".to_string());
+        flow.on_key(key(KeyCode::Esc), &ops).expect("back");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("name enter");
+        assert_eq!(flow.branch_input.value(), "fix/signup");
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("branch enter");
+        assert_eq!(flow.step, Step::StartPointMode);
+
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("current branch");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("no extras");
+        assert_eq!(flow.step, Step::ConnectNow);
+        flow.on_key(key(KeyCode::Enter), &ops).expect("review");
+        submit_review(&mut flow, &ops);
+        let calls = ops.execute_calls.lock().expect("execute lock");
+        assert_eq!(calls[0].worktree_name, "signup");
+        assert_eq!(calls[0].branch_name.as_deref(), Some("fix/signup"));
+    }
+
+    #[test]
+    fn detached_head_defaults_the_name_and_labels_the_start_point() {
+        let theme = &Theme::default();
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let mut ops = FakeOps::new(repo_root.clone());
+        ops.prepare.detached_head = Some("1a2b3c4".to_string());
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader, &repo_root);
+        assert_eq!(flow.name_input.value(), "detached-1a2b3c4");
+
+        flow.on_key(key(KeyCode::Enter), &ops).expect("gitignore");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("name enter");
+        assert_eq!(flow.branch_input.value(), "detached-1a2b3c4");
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("branch enter");
+        assert_eq!(flow.step, Step::StartPointMode);
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).expect("terminal");
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render start mode");
+        assert!(format!("{}", terminal.backend()).contains("From current commit (detached HEAD)"));
+
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("current commit");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("no extras");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("review");
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render review");
+        assert!(format!("{}", terminal.backend()).contains("Current commit: 1a2b3c4"));
+    }
+
+    #[test]
+    fn name_step_offers_suggestions_and_flags_taken_names_while_typing() {
+        let theme = &Theme::default();
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let mut ops = FakeOps::new(repo_root.clone());
+        ops.prepare.missing_gitignore_entries.clear();
+        ops.prepare.existing_names = BTreeSet::from(["w1".to_string()]);
+        ops.prepare.name_suggestions =
+            vec!["main-20261017".to_string(), "proj-20261017".to_string()];
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader, &repo_root);
+        assert_eq!(flow.step, Step::NameInput);
+        assert_eq!(flow.name_input.value(), "");
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).expect("terminal");
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render");
+        let rendered = format!("{}", terminal.backend());
+        assert!(rendered.contains("Suggestions: main-20261017, proj-20261017"));
+        assert!(rendered.contains("Tab: next suggestion"));
+
+        flow.on_key(key(KeyCode::Tab), &ops).expect("first");
+        assert_eq!(flow.name_input.value(), "main-20261017");
+        flow.on_key(key(KeyCode::Tab), &ops).expect("second");
+        assert_eq!(flow.name_input.value(), "proj-20261017");
+        flow.on_key(key(KeyCode::Tab), &ops).expect("wraps");
+        assert_eq!(flow.name_input.value(), "main-20261017");
+
+        flow.name_input = tui_input::Input::default();
+        flow.on_key(key(KeyCode::Char('w')), &ops).expect("w");
+        assert_eq!(flow.name_error, None);
+        flow.on_key(key(KeyCode::Char('1')), &ops).expect("1");
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render");
+        assert!(format!("{}", terminal.backend()).contains("worktree name 'w1' already exists"));
+
+        flow.on_key(key(KeyCode::Enter), &ops).expect("enter");
+        assert_eq!(flow.step, Step::NameInput);
+        flow.on_key(key(KeyCode::Char('2')), &ops).expect("2");
+        assert_eq!(flow.name_error, None);
+        flow.on_key(key(KeyCode::Enter), &ops).expect("enter");
+        assert_eq!(flow.step, Step::BranchNameInput);
+    }
+
+    #[test]
+    fn help_follows_the_step_and_question_mark_is_typed_into_inputs() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let mut ops = FakeOps::new(repo_root.clone());
+        ops.prepare.missing_gitignore_entries.clear();
+        ops.prepare.name_suggestions = vec!["main-20261017".to_string()];
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader, &repo_root);
+
+        assert!(flow.typing());
+        let help = flow.help();
+        assert_eq!(help.title, "Worktree name");
+        assert!(
+            help.keys
+                .contains(&("Tab", "fill in the next suggested name"))
+        );
+
+        assert!(!flow.has_answers());
+        advance_to_copy_extras_decision(&mut flow, &ops, "alpha");
+        assert!(!flow.typing());
+        assert!(flow.has_answers());
+        let help = flow.help();
+        assert_eq!(help.title, "Copy extras?");
+        assert!(help.keys.contains(&("Space", "switch between Yes and No")));
+    }
+
+    #[test]
+    fn review_toggles_checkout_steps_from_the_prepared_defaults() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let ops = FakeOps::new(repo_root.clone());
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader, &repo_root);
+        assert!(!flow.lfs_pull);
+        assert!(flow.init_submodules);
+
+        flow.on_key(key(KeyCode::Enter), &ops).expect("gitignore");
+        flow.on_paste("w1", &ops).expect("name");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("name enter");
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("branch enter");
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("current branch");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("no extras");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("review");
+        assert_eq!(flow.step, Step::Review);
+
+        flow.on_key(key(KeyCode::Char('l')), &ops).expect("lfs");
+        flow.on_key(key(KeyCode::Char('s')), &ops)
+            .expect("submodules");
+        submit_review(&mut flow, &ops);
+        let calls = ops.execute_calls.lock().expect("execute lock");
+        assert_eq!(calls[0].lfs_pull, Some(true));
+        assert_eq!(calls[0].init_submodules, Some(false));
+    }
+
+    #[test]
+    fn review_shows_the_templated_worktree_path_and_flags_an_existing_one() {
+        let theme = &Theme::default();
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let mut ops = FakeOps::new(repo_root.clone());
+        ops.prepare.worktree_path_template = Some("{worktrees_dir}/t-{name}".to_string());
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader, &repo_root);
+        flow.on_key(key(KeyCode::Enter), &ops).expect("gitignore");
+        flow.on_paste("w1", &ops).expect("name");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("name enter");
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("branch enter");
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("current branch");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("no extras");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("review");
+        assert_eq!(flow.step, Step::Review);
+
+        let path = repo_root.join("worktrees").join("t-w1");
+        let mut terminal = Terminal::new(TestBackend::new(240, 30)).expect("terminal");
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render");
+        let screen = format!("{}", terminal.backend());
+        assert!(screen.contains(&path.display().to_string()));
+        assert!(!screen.contains("(already exists)"));
+
+        std::fs::create_dir_all(&path).expect("taken path");
+        flow.on_key(key(KeyCode::Esc), &ops).expect("back");
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("review again");
+        assert_eq!(flow.step, Step::Review);
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render");
+        assert!(format!("{}", terminal.backend()).contains("(already exists)"));
+    }
+
+    #[test]
+    fn review_toggles_direnv_allow_only_when_the_repo_has_an_envrc() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        for has_envrc in [false, true] {
+            let mut ops = FakeOps::new(repo_root.clone());
+            ops.prepare.has_envrc = has_envrc;
+            ops.prepare.direnv_allow = has_envrc;
+            let loader = Arc::new(ScriptedLoader::default());
+            let mut flow = new_flow(&ops, loader, &repo_root);
+
+            flow.on_key(key(KeyCode::Enter), &ops).expect("gitignore");
+            flow.on_paste("w1", &ops).expect("name");
+            flow.on_key(key(KeyCode::Enter), &ops).expect("name enter");
+            flow.on_key(key(KeyCode::Enter), &ops)
+                .expect("branch enter");
+            flow.on_key(key(KeyCode::Enter), &ops)
+                .expect("current branch");
+            flow.on_key(key(KeyCode::Enter), &ops).expect("no extras");
+            flow.on_key(key(KeyCode::Enter), &ops).expect("review");
+            assert_eq!(flow.step, Step::Review);
+            assert_eq!(
+                flow.help().keys.contains(&("d", "toggle direnv allow")),
+                has_envrc
+            );
+
+            flow.on_key(key(KeyCode::Char('d')), &ops).expect("direnv");
+            submit_review(&mut flow, &ops);
+            let calls = ops.execute_calls.lock().expect("execute lock");
+            assert_eq!(calls[0].direnv_allow, Some(false));
+        }
+    }
+
+    #[test]
+    fn binary_choices_start_on_the_configured_defaults() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let mut ops = FakeOps::new(repo_root.clone());
+        ops.prepare.defaults = DefaultsConfig {
+            gitignore: false,
+            copy_extras: true,
+            connect_now: false,
+        };
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader.clone(), &repo_root);
+        assert!(!flow.gitignore_choice.yes_selected);
+
+        advance_to_copy_extras_decision(&mut flow, &ops, "w1");
+        assert!(flow.copy_extras_choice.yes_selected);
+        flow.on_key(key(KeyCode::Enter), &ops).expect("copy extras");
+        finish_indexing_without_modal(&mut flow, &ops, &loader, &[PathBuf::from(".env")]);
+        assert_eq!(flow.step, Step::ConnectNow);
+        assert!(!flow.connect_choice.yes_selected);
+
+        flow.on_key(key(KeyCode::Enter), &ops).expect("connect");
+        submit_review(&mut flow, &ops);
+        let calls = ops.execute_calls.lock().expect("execute lock");
+        assert!(!calls[0].add_gitignore_entries);
+        assert!(!calls[0].connect_now);
+    }
+
+    #[test]
+    fn extras_picker_preselects_the_envrc() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let ops = FakeOps::new(repo_root.clone());
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader.clone(), &repo_root);
+        advance_to_copy_extras_decision(&mut flow, &ops, "feature1");
+        confirm_copy_extras_yes(&mut flow, &ops);
+        finish_indexing_without_modal(
+            &mut flow,
+            &ops,
+            &loader,
+            &[PathBuf::from(".env"), PathBuf::from(".envrc")],
+        );
+
+        assert!(flow.extras.checked.contains(Path::new(".envrc")));
+        assert!(!flow.extras.checked.contains(Path::new(".env")));
+    }
+
+    #[test]
+    fn project_step_scopes_the_request_and_preselects_its_extras() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let mut ops = FakeOps::new(repo_root.clone());
+        ops.prepare.projects.insert(
+            "api".to_string(),
+            ProjectConfig {
+                path: "services/api".to_string(),
+                windows: None,
+                extras: vec!["services/api/.env".to_string()],
+            },
+        );
+        ops.prepare.projects.insert(
+            "web".to_string(),
+            ProjectConfig {
+                path: "web".to_string(),
+                ..ProjectConfig::default()
+            },
+        );
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader.clone(), &repo_root);
+
+        flow.on_key(key(KeyCode::Enter), &ops).expect("gitignore");
+        flow.on_paste("w1", &ops).expect("name");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("name enter");
+        assert_eq!(flow.step, Step::ProjectPicker);
+        flow.on_key(key(KeyCode::Down), &ops).expect("down");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("pick api");
+        assert_eq!(flow.step, Step::BranchNameInput);
+        flow.on_key(key(KeyCode::Esc), &ops).expect("back");
+        assert_eq!(flow.step, Step::ProjectPicker);
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("pick api again");
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("branch enter");
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("start current branch");
+        confirm_copy_extras_yes(&mut flow, &ops);
+        finish_indexing_without_modal(
+            &mut flow,
+            &ops,
+            &loader,
+            &[PathBuf::from(".env"), PathBuf::from("services/api/.env")],
+        );
+
+        assert!(flow.extras.checked.contains(Path::new("services/api/.env")));
+        assert!(!flow.extras.checked.contains(Path::new(".env")));
+
+        flow.on_key(key(KeyCode::Enter), &ops).expect("connect");
+        submit_review(&mut flow, &ops);
+        let calls = ops.execute_calls.lock().expect("execute lock");
+        assert_eq!(calls[0].project.as_deref(), Some("api"));
+    }
+
+    #[test]
+    fn carry_step_appears_only_with_uncommitted_changes_and_defaults_to_no() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let clean = FakeOps::new(repo_root.clone());
+        let mut flow = new_flow(&clean, Arc::new(ScriptedLoader::default()), &repo_root);
+        advance_to_copy_extras_decision(&mut flow, &clean, "w1");
+        flow.on_key(key(KeyCode::Enter), &clean).expect("no extras");
+        flow.on_key(key(KeyCode::Enter), &clean).expect("connect");
+        assert_eq!(flow.step, Step::Review);
+
+        let mut dirty = FakeOps::new(repo_root.clone());
+        dirty.changed_files = vec!["src/lib.rs".to_string()];
+        let mut flow = new_flow(&dirty, Arc::new(ScriptedLoader::default()), &repo_root);
+        advance_to_copy_extras_decision(&mut flow, &dirty, "w2");
+        flow.on_key(key(KeyCode::Enter), &dirty).expect("no extras");
+        flow.on_key(key(KeyCode::Enter), &dirty).expect("connect");
+        assert_eq!(flow.step, Step::CarryChanges);
+        assert!(!flow.carry_choice.yes_selected);
+
+        flow.on_key(key(KeyCode::Char(' ')), &dirty)
+            .expect("toggle carry");
+        flow.on_key(key(KeyCode::Enter), &dirty).expect("carry");
+        assert_eq!(flow.step, Step::Review);
+        flow.on_key(key(KeyCode::Esc), &dirty).expect("back");
+        assert_eq!(flow.step, Step::CarryChanges);
+        flow.on_key(key(KeyCode::Enter), &dirty).expect("carry");
+        submit_review(&mut flow, &dirty);
+
+        let calls = dirty.execute_calls.lock().expect("execute lock");
+        assert!(calls[0].carry_changes);
+    }
+
+    #[test]
+    fn quick_mode_submits_straight_from_the_name_with_profile_answers() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let ops = FakeOps::new(repo_root.clone());
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader, &repo_root);
+        flow.enter_quick_mode();
+        assert_eq!(flow.step, Step::NameInput);
+
+        flow.on_paste("w1", &ops).expect("name");
+        submit_review(&mut flow, &ops);
+
+        assert_eq!(flow.step, Step::Success);
+        let calls = ops.execute_calls.lock().expect("execute lock");
+        assert_eq!(calls[0].branch_name.as_deref(), Some("w1"));
+        assert_eq!(
+            calls[0].start_point,
+            NewStartPoint::Branch("main".to_string())
+        );
+        assert!(calls[0].add_gitignore_entries);
+        assert_eq!(calls[0].selected_extras, vec![PathBuf::from(".env")]);
+        assert_eq!(
+            (calls[0].lfs_pull, calls[0].init_submodules),
+            (Some(false), Some(true))
+        );
+        assert!(calls[0].connect_now);
+        drop(calls);
+
+        let mut flow = new_flow(&ops, Arc::new(ScriptedLoader::default()), &repo_root);
+        flow.enter_quick_mode();
+        let signal = flow.on_key(key(KeyCode::Esc), &ops).expect("back");
+        assert!(matches!(
+            signal,
+            FlowSignal::Exit(crate::UiExit::BackAtRoot)
+        ));
+    }
+
+    #[test]
+    fn quick_mode_raises_a_checked_out_branch_before_submitting() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let mut ops = FakeOps::new(repo_root.clone());
+        ops.branch_conflict = Some(BranchConflict {
+            branch: "w1".to_string(),
+            worktree_path: repo_root.join("worktrees/other"),
+            worktree_name: Some("other".to_string()),
+        });
+        let mut flow = new_flow(&ops, Arc::new(ScriptedLoader::default()), &repo_root);
+        flow.enter_quick_mode();
+
+        flow.on_paste("w1", &ops).expect("name");
+        let signal = flow.on_key(key(KeyCode::Enter), &ops).expect("submit");
+
+        assert!(matches!(signal, FlowSignal::Continue));
+        assert_eq!(flow.step, Step::BranchConflict);
+        assert!(!flow.quick);
+        assert!(ops.execute_calls.lock().expect("execute lock").is_empty());
+    }
+
+    #[test]
+    fn display_names_are_saved_under_a_generated_slug() {
+        let theme = &Theme::default();
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let mut ops = FakeOps::new(repo_root.clone());
+        ops.prepare.existing_names.insert("fix-login".to_string());
+        let mut flow = new_flow(&ops, Arc::new(ScriptedLoader::default()), &repo_root);
+        flow.enter_quick_mode();
+
+        flow.on_paste("Fix login", &ops).expect("taken");
+        assert_eq!(
+            flow.name_error.as_deref(),
+            Some("worktree name 'fix-login' already exists")
+        );
+
+        for character in " & SSO".chars() {
+            flow.on_key(key(KeyCode::Char(character)), &ops)
+                .expect("type");
+        }
+        assert_eq!(flow.name_error, None);
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).expect("terminal");
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render name");
+        assert!(format!("{}", terminal.backend()).contains("Saved as: fix-login-sso"));
+
+        submit_review(&mut flow, &ops);
+        assert_eq!(flow.step, Step::Success);
+        let calls = ops.execute_calls.lock().expect("execute lock");
+        assert_eq!(calls[0].worktree_name, "fix-login-sso");
+        assert_eq!(calls[0].display_name.as_deref(), Some("Fix login & SSO"));
+        assert_eq!(calls[0].branch_name.as_deref(), Some("fix-login-sso"));
+    }
+
+    #[test]
+    fn new_flow_opt_in_starts_async_collect_only_after_confirmation() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let ops = FakeOps::new(repo_root.clone());
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader.clone(), &repo_root);
+
+        advance_to_copy_extras_decision(&mut flow, &ops, "alpha");
+        assert_eq!(loader.collect_call_count(), 0);
+
+        confirm_copy_extras_yes(&mut flow, &ops);
+
+        assert_eq!(flow.step, Step::ExtrasIndexing);
+        assert_eq!(loader.collect_call_count(), 1);
+    }
+
+    #[test]
+    fn copy_extras_yes_transitions_without_waiting_for_listing() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let ops = FakeOps::new(repo_root.clone());
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader, &repo_root);
+        advance_to_copy_extras_decision(&mut flow, &ops, "alpha");
+
+        let started = Instant::now();
+        confirm_copy_extras_yes(&mut flow, &ops);
+        let elapsed = started.elapsed();
+
+        assert!(
+            elapsed < Duration::from_millis(25),
+            "copy extras confirmation should not block: {elapsed:?}"
+        );
+        assert_eq!(flow.step, Step::ExtrasIndexing);
+    }
+
+    #[test]
+    fn extras_indexing_emits_collecting_classifying_and_building_phases() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let ops = FakeOps::new(repo_root.clone());
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader.clone(), &repo_root);
+        let jobs = JobTracker::default();
+        flow.jobs = jobs.reporter();
+        advance_to_copy_extras_decision(&mut flow, &ops, "alpha");
+        confirm_copy_extras_yes(&mut flow, &ops);
+
+        let token = flow.active_extras_index_token.expect("active token");
+        let candidates = vec![PathBuf::from("src/main.rs")];
+
+        loader.send_collect(ExtrasLoadEvent::Collecting);
+        flow.on_tick();
+        assert!(matches!(
+            flow.extras_indexing.as_ref().expect("indexing").phase,
+            super::ExtrasIndexingPhase::Collecting
+        ));
+
+        loader.send_collect(ExtrasLoadEvent::Classifying { candidate_count: 1 });
+        flow.on_tick();
+        assert!(matches!(
+            flow.extras_indexing.as_ref().expect("indexing").phase,
+            super::ExtrasIndexingPhase::Classifying { candidate_count: 1 }
+        ));
+
+        loader.send_collect(ExtrasLoadEvent::AwaitingSkipDecision {
+            flagged_bucket_count: 0,
+        });
+        loader.send_collect(ExtrasLoadEvent::DoneCollect {
+            token,
+            candidates: candidates.clone(),
+            origins: BTreeMap::new(),
+            plan: BucketPlan::default(),
+        });
+        flow.on_tick();
+        assert_eq!(loader.build_call_count(), 1);
+
+        loader.send_build(ExtrasLoadEvent::Building { filtered_count: 1 });
+        flow.on_tick();
+        assert!(matches!(
+            flow.extras_indexing.as_ref().expect("indexing").phase,
+            super::ExtrasIndexingPhase::Building { filtered_count: 1 }
+        ));
+
+        loader.send_build(ExtrasLoadEvent::Done {
+            token,
+            result: Ok(build_index(&candidates)),
+        });
+        flow.on_tick();
+
+        assert_eq!(flow.step, Step::ExtrasPicker);
+        let job = jobs.poll().expect("indexing completion");
+        assert_eq!(job.label, "Extras indexing");
+    }
+
+    #[test]
+    fn extras_indexing_shows_counters_and_time_left_until_the_phase_changes() {
+        let theme = &Theme::default();
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let ops = FakeOps::new(repo_root.clone());
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader.clone(), &repo_root);
+        advance_to_copy_extras_decision(&mut flow, &ops, "alpha");
+        confirm_copy_extras_yes(&mut flow, &ops);
+
+        loader.send_collect(ExtrasLoadEvent::Collecting);
+        loader.send_collect(ExtrasLoadEvent::Progress(IndexingProgress {
+            files_scanned: 250,
+            total_files: 1000,
+            dirs_walked: 12,
+            elapsed: Duration::from_secs(2),
+        }));
+        flow.on_tick();
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).expect("terminal");
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render indexing");
+        let screen = format!("{}", terminal.backend());
+        assert!(screen.contains("250/1000 files in 12 dirs, 2s elapsed, about 6s left"));
+
+        loader.send_collect(ExtrasLoadEvent::Classifying {
+            candidate_count: 1000,
+        });
+        flow.on_tick();
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render classifying");
+        assert!(!format!("{}", terminal.backend()).contains("files in"));
+    }
+
+    fn open_skip_modal(flow: &mut NewFlow, loader: &ScriptedLoader, candidates: &[PathBuf]) -> u64 {
+        open_skip_modal_for_bucket(flow, loader, candidates, "target")
+    }
+
+    fn open_skip_modal_for_bucket(
+        flow: &mut NewFlow,
+        loader: &ScriptedLoader,
+        candidates: &[PathBuf],
+        bucket: &str,
+    ) -> u64 {
+        let token = flow.active_extras_index_token.expect("token");
+        let plan = BucketPlan {
+            flagged: vec![FlaggedBucket {
+                bucket: bucket.to_string(),
+                count: 1,
             }],
+            suggested: Vec::new(),
         };
 
         loader.send_collect(ExtrasLoadEvent::Collecting);
@@ -1157,6 +2418,7 @@ mod tests {
         loader.send_collect(ExtrasLoadEvent::DoneCollect {
             token,
             candidates: candidates.to_vec(),
+            origins: BTreeMap::new(),
             plan,
         });
         flow.on_tick();
@@ -1249,6 +2511,7 @@ mod tests {
         loader.send_collect(ExtrasLoadEvent::DoneCollect {
             token,
             candidates: candidates.clone(),
+            origins: BTreeMap::new(),
             plan: BucketPlan::default(),
         });
         flow.on_tick();
@@ -1257,6 +2520,58 @@ mod tests {
         assert!(!flow.skip_modal_open());
     }
 
+    #[test]
+    fn extras_skip_modal_offers_suggested_buckets_unskipped_until_accepted() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let ops = FakeOps::new(repo_root.clone());
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader.clone(), &repo_root);
+        advance_to_copy_extras_decision(&mut flow, &ops, "alpha");
+        confirm_copy_extras_yes(&mut flow, &ops);
+
+        let token = flow.active_extras_index_token.expect("token");
+        loader.send_collect(ExtrasLoadEvent::DoneCollect {
+            token,
+            candidates: vec![
+                PathBuf::from("data/raw/a.csv"),
+                PathBuf::from("src/main.rs"),
+            ],
+            origins: BTreeMap::new(),
+            plan: BucketPlan {
+                flagged: Vec::new(),
+                suggested: vec![FlaggedBucket {
+                    bucket: "data".to_string(),
+                    count: 1,
+                }],
+            },
+        });
+        flow.on_tick();
+
+        let modal = flow
+            .extras_indexing
+            .as_ref()
+            .and_then(|state| state.skip_modal.as_ref())
+            .expect("modal");
+        let choice = modal.choices.first().expect("suggested bucket");
+        assert!(choice.suggested);
+        assert!(!choice.skip);
+
+        flow.on_key(key(KeyCode::Char(' ')), &ops)
+            .expect("accept suggestion");
+        flow.on_key(key(KeyCode::Char('a')), &ops)
+            .expect("toggle persist");
+        flow.on_key(key(KeyCode::Enter), &ops)
+            .expect("confirm modal");
+
+        let saved = ops.saved_skip_buckets();
+        assert_eq!(saved.len(), 1);
+        assert!(saved[0].contains("data"));
+        assert_eq!(loader.build_call_count(), 1);
+    }
+
     #[test]
     fn extras_skip_modal_defaults_to_yes_and_persists_selection() {
         let temp = tempfile::tempdir().expect("temp dir");
@@ -1497,7 +2812,7 @@ mod tests {
             .expect("continue extras");
         flow.on_key(key(KeyCode::Enter), &ops)
             .expect("continue connect");
-        flow.on_key(key(KeyCode::Enter), &ops).expect("execute");
+        submit_review(&mut flow, &ops);
 
         assert_eq!(flow.step, Step::Success);
         let saved = ops.saved_skip_buckets();
@@ -1557,11 +2872,13 @@ mod tests {
         loader.send_collect(ExtrasLoadEvent::DoneCollect {
             token,
             candidates: vec![PathBuf::from("target/debug/a.o")],
+            origins: BTreeMap::new(),
             plan: BucketPlan {
                 flagged: vec![FlaggedBucket {
                     bucket: "target".to_string(),
                     count: 1,
                 }],
+                suggested: Vec::new(),
             },
         });
         loader.close_collect_channel();
@@ -1611,6 +2928,7 @@ mod tests {
         loader.send_collect(ExtrasLoadEvent::DoneCollect {
             token,
             candidates: vec![PathBuf::from("src/main.rs")],
+            origins: BTreeMap::new(),
             plan: BucketPlan::default(),
         });
         flow.on_tick();
@@ -1665,6 +2983,7 @@ mod tests {
         loader.send_collect(ExtrasLoadEvent::DoneCollect {
             token,
             candidates: candidates.clone(),
+            origins: BTreeMap::new(),
             plan: BucketPlan::default(),
         });
         for _ in 0..20 {
@@ -1708,6 +3027,7 @@ mod tests {
 
     #[test]
     fn review_executes_with_collected_inputs() {
+        let theme = &Theme::default();
         let temp = tempfile::tempdir().expect("temp dir");
         let repo_root = temp.path().join("repo");
         std::fs::create_dir_all(&repo_root).expect("repo");
@@ -1728,13 +3048,176 @@ mod tests {
         flow.on_key(key(KeyCode::Char(' ')), &ops)
             .expect("connect toggle to no");
         flow.on_key(key(KeyCode::Enter), &ops).expect("review");
-        flow.on_key(key(KeyCode::Enter), &ops).expect("execute");
+        let signal = flow.on_key(key(KeyCode::Enter), &ops).expect("submit");
+        let FlowSignal::Execute(request) = signal else {
+            panic!("review did not submit: {signal:?}");
+        };
+        assert!(
+            ops.execute_calls
+                .lock()
+                .expect("execute calls lock")
+                .is_empty()
+        );
 
+        flow.start_creation(&ops, request);
+        assert_eq!(flow.step, Step::Creating);
+        flow.on_tick();
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).expect("terminal");
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render progress");
+        assert!(format!("{}", terminal.backend()).contains("Creating worktree: feature1"));
+
+        flow.finish_creation(&ops);
         assert_eq!(flow.step, Step::Success);
         assert_eq!(
             ops.execute_calls.lock().expect("execute calls lock").len(),
             1
         );
+        assert!(ops.connect_calls.lock().expect("connect lock").is_empty());
+    }
+
+    #[test]
+    fn creation_runs_in_the_background_and_esc_cancels_it() {
+        let theme = &Theme::default();
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let mut ops = FakeOps::new(repo_root.clone());
+        ops.hold_execute = true;
+        let mut flow = new_flow(&ops, Arc::new(ScriptedLoader::default()), &repo_root);
+        flow.enter_quick_mode();
+        flow.on_paste("w1", &ops).expect("name");
+        let signal = flow.on_key(key(KeyCode::Enter), &ops).expect("submit");
+        let FlowSignal::Execute(request) = signal else {
+            panic!("quick name did not submit: {signal:?}");
+        };
+        flow.start_creation(&ops, request);
+        flow.on_tick();
+        flow.finish_creation(&ops);
+        assert_eq!(flow.step, Step::Creating);
+        assert!(flow.should_drain_loader_after_input());
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).expect("terminal");
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render");
+        assert!(format!("{}", terminal.backend()).contains("Esc: cancel"));
+
+        flow.on_key(key(KeyCode::Esc), &ops).expect("cancel");
+        let cancel = ops
+            .execute_cancel
+            .lock()
+            .expect("cancel lock")
+            .clone()
+            .expect("cancel flag");
+        assert!(cancel.load(Ordering::Relaxed));
+        assert_eq!(flow.step, Step::Creating);
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render");
+        assert!(format!("{}", terminal.backend()).contains("Canceling after the current step"));
+
+        ops.execute_sender
+            .lock()
+            .expect("sender lock")
+            .take()
+            .expect("sender")
+            .send(CreationEvent::Done(Err(anyhow::anyhow!(
+                "creating worktree 'w1' was canceled and was rolled back"
+            ))))
+            .expect("send done");
+        flow.on_tick();
+        flow.finish_creation(&ops);
+        let Step::ErrorScreen(error) = &flow.step else {
+            panic!("expected the error screen, got {:?}", flow.step);
+        };
+        assert!(error.message.contains("was canceled"));
+        assert!(ops.connect_calls.lock().expect("connect lock").is_empty());
+    }
+
+    #[test]
+    fn bootstrap_output_streams_under_the_running_phase() {
+        let theme = &Theme::default();
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let mut ops = FakeOps::new(repo_root.clone());
+        ops.hold_execute = true;
+        let mut flow = new_flow(&ops, Arc::new(ScriptedLoader::default()), &repo_root);
+        flow.enter_quick_mode();
+        flow.on_paste("w1", &ops).expect("name");
+        let FlowSignal::Execute(request) = flow.on_key(key(KeyCode::Enter), &ops).expect("submit")
+        else {
+            panic!("quick name did not submit");
+        };
+        flow.start_creation(&ops, request);
+
+        let sender = ops
+            .execute_sender
+            .lock()
+            .expect("sender lock")
+            .take()
+            .expect("sender");
+        let progress = |phase, output: Option<&str>| {
+            CreationEvent::Progress({
+                let mut progress = NewProgress::new(phase, "npm ci");
+                progress.output = output.map(str::to_string);
+                progress
+            })
+        };
+        for event in [
+            progress(NewPhase::Provisioning, None),
+            progress(NewPhase::Provisioning, Some("added 812 packages")),
+        ] {
+            sender.send(event).expect("send progress");
+        }
+        flow.on_tick();
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).expect("terminal");
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render");
+        let screen = format!("{}", terminal.backend());
+        assert!(screen.contains("Running bootstrap command: npm ci"));
+        assert!(screen.contains("added 812 packages"));
+        let creation = flow.creation.as_ref().expect("creation");
+        assert_eq!(creation.phases.len(), 2);
+
+        sender
+            .send(progress(NewPhase::Registering, None))
+            .expect("send progress");
+        flow.on_tick();
+        let creation = flow.creation.as_ref().expect("creation");
+        assert_eq!(creation.phases.len(), 3);
+        assert!(creation.output.is_empty());
+    }
+
+    #[test]
+    fn connect_runs_after_creation_and_a_failure_keeps_the_worktree() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+
+        let mut ops = FakeOps::new(repo_root.clone());
+        ops.connect_error = Some("failed to connect to tmux session 'repo/w1'".to_string());
+        let mut flow = new_flow(&ops, Arc::new(ScriptedLoader::default()), &repo_root);
+        flow.enter_quick_mode();
+        flow.on_paste("w1", &ops).expect("name");
+        submit_review(&mut flow, &ops);
+
+        assert_eq!(flow.step, Step::Success);
+        assert_eq!(
+            *ops.connect_calls.lock().expect("connect lock"),
+            vec!["repo/w1".to_string()]
+        );
+        assert!(!flow.success.as_ref().expect("result").connected_now);
+        assert_eq!(
+            flow.success_notice.as_deref(),
+            Some("Worktree created, but failed to connect to tmux session 'repo/w1'")
+        );
     }
 
     #[test]
@@ -1808,7 +3291,7 @@ mod tests {
             .expect("continue extras");
         flow.on_key(key(KeyCode::Enter), &ops)
             .expect("continue connect");
-        flow.on_key(key(KeyCode::Enter), &ops).expect("execute");
+        submit_review(&mut flow, &ops);
 
         assert_eq!(flow.step, Step::Success);
         assert!(
@@ -1822,6 +3305,13 @@ mod tests {
             1
         );
 
+        flow.on_key(key(KeyCode::Char('y')), &ops)
+            .expect("copy attach command");
+        assert_eq!(
+            flow.pending_copy.as_deref(),
+            Some("tmux attach-session -t repo/feature3")
+        );
+
         let signal = flow
             .on_key(key(KeyCode::Enter), &ops)
             .expect("success enter");
@@ -1844,6 +3334,7 @@ mod tests {
         flow.step = Step::ErrorScreen(NewFlowErrorState {
             origin: NewFlowErrorOrigin::ExtrasIndexing,
             message: "indexing failed".to_string(),
+            actions: ErrorActions::none(),
         });
         flow.on_key(key(KeyCode::Enter), &ops).expect("enter");
         assert_eq!(flow.step, Step::CopyExtrasDecision);
@@ -1851,11 +3342,126 @@ mod tests {
         flow.step = Step::ErrorScreen(NewFlowErrorState {
             origin: NewFlowErrorOrigin::ReviewSubmit,
             message: "submit failed".to_string(),
+            actions: ErrorActions::none(),
         });
         flow.on_key(key(KeyCode::Esc), &ops).expect("esc");
         assert_eq!(flow.step, Step::Review);
     }
 
+    #[test]
+    fn branch_exists_error_offers_checking_out_the_existing_branch() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+        let ops = FakeOps::new(repo_root.clone());
+
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader, &repo_root);
+        advance_to_copy_extras_decision(&mut flow, &ops, "alpha");
+        let error = anyhow::Error::from(GitError::CommandFailed {
+            command: "worktree add -b alpha".to_string(),
+            status: 255,
+            stderr: "fatal: a branch named 'alpha' already exists".to_string(),
+        });
+        flow.step = Step::ErrorScreen(NewFlowErrorState::from_error(
+            NewFlowErrorOrigin::ReviewSubmit,
+            &error,
+        ));
+
+        flow.on_key(key(KeyCode::Down), &ops).expect("move");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("select");
+        assert_eq!(flow.step, Step::Review);
+        assert_eq!(
+            flow.start_point,
+            Some(NewStartPoint::Branch("alpha".to_string()))
+        );
+
+        submit_review(&mut flow, &ops);
+        let calls = ops.execute_calls.lock().expect("execute lock");
+        assert!(calls[0].use_existing_branch);
+    }
+
+    #[test]
+    fn no_commits_error_offers_a_shell_in_the_repository() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+        let ops = FakeOps::new(repo_root.clone());
+
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader, &repo_root);
+        flow.step = Step::ErrorScreen(NewFlowErrorState::from_error(
+            NewFlowErrorOrigin::ReviewSubmit,
+            &anyhow::Error::from(GitError::NoCommits),
+        ));
+
+        flow.on_key(key(KeyCode::Down), &ops).expect("move");
+        let signal = flow.on_key(key(KeyCode::Enter), &ops).expect("select");
+        assert_eq!(signal, FlowSignal::Shell(repo_root));
+        assert_eq!(flow.step, Step::Review);
+    }
+
+    #[test]
+    fn insufficient_space_error_goes_back_to_the_extras_choice() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+        let ops = FakeOps::new(repo_root.clone());
+
+        let loader = Arc::new(ScriptedLoader::default());
+        let mut flow = new_flow(&ops, loader, &repo_root);
+        flow.quick = true;
+        let error = anyhow::Error::from(seshmux_app::NewError::InsufficientSpace {
+            path: repo_root.join("worktrees"),
+            required: 3 * 1024 * 1024 * 1024,
+            available: 512 * 1024 * 1024,
+        });
+        flow.step = Step::ErrorScreen(NewFlowErrorState::from_error(
+            NewFlowErrorOrigin::ReviewSubmit,
+            &error,
+        ));
+        assert_error_screen(
+            &flow,
+            NewFlowErrorOrigin::ReviewSubmit,
+            "needs about 3.0 GiB but only 512 MiB is available",
+        );
+
+        flow.on_key(key(KeyCode::Enter), &ops).expect("select");
+        assert_eq!(flow.step, Step::CopyExtrasDecision);
+        assert!(!flow.quick);
+    }
+
+    #[test]
+    fn paste_into_name_input_strips_newlines_and_validates() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(&repo_root).expect("repo");
+        let ops = FakeOps::new(repo_root.clone());
+        let loader = Arc::new(ScriptedLoader::default());
+
+        let mut flow = new_flow(&ops, loader, &repo_root);
+        flow.on_key(key(KeyCode::Enter), &ops).expect("gitignore");
+        assert_eq!(flow.step, Step::NameInput);
+
+        flow.on_paste("jira-42\r\n", &ops).expect("paste");
+        assert_eq!(flow.name_input.value(), "jira-42");
+        assert_eq!(flow.name_error, None);
+        assert_eq!(flow.step, Step::NameInput);
+
+        flow.on_paste(" fix login\n", &ops).expect("paste");
+        assert_eq!(flow.name_input.value(), "jira-42fix login");
+        assert_eq!(flow.name_error, None);
+
+        flow.name_input = tui_input::Input::default();
+        flow.on_paste("✨✨", &ops).expect("paste");
+        assert_eq!(flow.name_error, None);
+
+        flow.name_input = tui_input::Input::default();
+        flow.on_paste(&"fix login ".repeat(10), &ops)
+            .expect("paste");
+        assert!(flow.name_error.is_some());
+    }
+
     #[test]
     fn esc_on_first_step_exits_flow() {
         let temp = tempfile::tempdir().expect("temp dir");
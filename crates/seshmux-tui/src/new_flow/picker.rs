@@ -1,18 +1,55 @@
 #[derive(Debug, Clone)]
 pub(crate) struct PickerState<T> {
     pub(crate) items: Vec<T>,
+    pub(crate) actions: Vec<String>,
     pub(crate) selected: usize,
+    pub(crate) has_more: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum PickerAction {
     Pick(usize),
+    Action(usize),
     Noop,
 }
 
 impl<T> PickerState<T> {
     pub(crate) fn from_items(items: Vec<T>) -> Self {
-        Self { items, selected: 0 }
+        Self {
+            items,
+            actions: Vec::new(),
+            selected: 0,
+            has_more: false,
+        }
+    }
+
+    pub(crate) fn from_page(items: Vec<T>, page_size: usize) -> Self {
+        let has_more = items.len() >= page_size;
+        Self {
+            items,
+            actions: Vec::new(),
+            selected: 0,
+            has_more,
+        }
+    }
+
+    pub(crate) fn with_actions(mut self, actions: Vec<String>) -> Self {
+        self.actions = actions;
+        self.selected = 0;
+        self
+    }
+
+    pub(crate) fn row_count(&self) -> usize {
+        self.actions.len() + self.items.len()
+    }
+
+    pub(crate) fn append_page(&mut self, items: Vec<T>, page_size: usize) {
+        self.has_more = items.len() >= page_size;
+        self.items.extend(items);
+    }
+
+    pub(crate) fn wants_next_page(&self) -> bool {
+        self.has_more && self.selected + 1 >= self.row_count()
     }
 
     pub(crate) fn move_up(&mut self) {
@@ -20,15 +57,17 @@ impl<T> PickerState<T> {
     }
 
     pub(crate) fn move_down(&mut self) {
-        if self.selected + 1 < self.items.len() {
+        if self.selected + 1 < self.row_count() {
             self.selected += 1;
         }
     }
 
     pub(crate) fn on_enter(&self) -> PickerAction {
         let index = self.selected;
-        if index < self.items.len() {
-            PickerAction::Pick(index)
+        if index < self.actions.len() {
+            PickerAction::Action(index)
+        } else if index < self.row_count() {
+            PickerAction::Pick(index - self.actions.len())
         } else {
             PickerAction::Noop
         }
@@ -55,6 +94,36 @@ mod tests {
         assert_eq!(picker.on_enter(), PickerAction::Noop);
     }
 
+    #[test]
+    fn next_page_is_wanted_only_at_the_end_of_a_full_page() {
+        let mut picker = PickerState::from_page(vec!["a", "b"], 2);
+        assert!(!picker.wants_next_page());
+        picker.move_down();
+        assert!(picker.wants_next_page());
+
+        picker.append_page(vec!["c"], 2);
+        picker.move_down();
+        assert_eq!(picker.selected, 2);
+        assert!(!picker.wants_next_page());
+        assert!(!PickerState::from_page(vec!["a"], 2).has_more);
+    }
+
+    #[test]
+    fn action_rows_come_before_items() {
+        let mut picker =
+            PickerState::from_items(vec!["a"]).with_actions(vec!["Create 'b'".to_string()]);
+        assert_eq!(picker.on_enter(), PickerAction::Action(0));
+
+        picker.move_down();
+        assert_eq!(picker.on_enter(), PickerAction::Pick(0));
+        picker.move_down();
+        assert_eq!(picker.selected, 1);
+
+        let only_action = PickerState::from_items(Vec::<&str>::new())
+            .with_actions(vec!["Create 'b'".to_string()]);
+        assert_eq!(only_action.on_enter(), PickerAction::Action(0));
+    }
+
     #[test]
     fn movement_is_bounded() {
         let mut picker = PickerState::from_items(vec!["a"]);
@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use seshmux_core::git::{BranchRef, CommitRef, CommitSearch};
+
+use super::picker::PickerState;
+
+#[derive(Debug, Default)]
+pub(super) struct QueryCache {
+    branches: HashMap<(PathBuf, String), Vec<BranchRef>>,
+    commits: HashMap<(PathBuf, CommitSearch, String), (Vec<CommitRef>, bool)>,
+}
+
+impl QueryCache {
+    pub(super) fn branches(&self, repo_root: &Path, query: &str) -> Option<PickerState<BranchRef>> {
+        self.branches
+            .get(&(repo_root.to_path_buf(), query.to_string()))
+            .map(|items| PickerState::from_items(items.clone()))
+    }
+
+    pub(super) fn store_branches(&mut self, repo_root: &Path, query: &str, items: &[BranchRef]) {
+        self.branches
+            .insert((repo_root.to_path_buf(), query.to_string()), items.to_vec());
+    }
+
+    pub(super) fn commits(
+        &self,
+        repo_root: &Path,
+        search: CommitSearch,
+        query: &str,
+    ) -> Option<PickerState<CommitRef>> {
+        self.commits
+            .get(&(repo_root.to_path_buf(), search, query.to_string()))
+            .map(|(items, has_more)| {
+                let mut picker = PickerState::from_items(items.clone());
+                picker.has_more = *has_more;
+                picker
+            })
+    }
+
+    pub(super) fn store_commits(
+        &mut self,
+        repo_root: &Path,
+        search: CommitSearch,
+        query: &str,
+        picker: &PickerState<CommitRef>,
+    ) {
+        self.commits.insert(
+            (repo_root.to_path_buf(), search, query.to_string()),
+            (picker.items.clone(), picker.has_more),
+        );
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.branches.clear();
+        self.commits.clear();
+    }
+}
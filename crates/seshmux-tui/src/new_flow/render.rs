@@ -1,7 +1,9 @@
 use ratatui::layout::{Constraint, Direction, Layout, Margin};
-use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{List, ListItem, ListState, Paragraph, ScrollbarOrientation};
+use seshmux_app::CarriedChanges;
+use seshmux_core::git::{CommitRef, CommitSearch};
+use seshmux_core::names::WorktreeName;
 use tui_tree_widget::{Scrollbar as TreeScrollbar, Tree};
 
 use super::picker::PickerState;
@@ -9,40 +11,52 @@ use super::{
     ExtrasIndexingPhase, NewFlow, NewFlowErrorOrigin, NewFlowErrorState, NewStartPoint,
     SkipModalState, Step,
 };
-use crate::theme;
-use crate::ui::loading::render_loading_modal;
-use crate::ui::modal::{ModalSpec, render_modal};
+use crate::theme::{self, Accent, Theme};
+use crate::ui::error_actions::render_error_with_actions;
+use crate::ui::loading::render_loading_modal_with_detail;
+use crate::ui::modal::{
+    ModalSpec, render_input_modal, render_input_modal_with_suggestions, render_modal,
+};
 use crate::ui::text::{
-    compact_hint, focus_line, highlighted_label_value_line, key_hint_height, key_hint_paragraph,
-    label_value_line, result_footer, wrapped_paragraph, yes_no,
+    compact_hint, copy_result_footer, display_width, focus_line, format_bytes,
+    highlighted_label_value_line, input_viewport, key_hint_height, key_hint_paragraph,
+    label_value_line, result_footer, truncate_to_width, wrapped_paragraph, yes_no,
 };
 
+const CREATION_OUTPUT_TAIL: usize = 12;
+
 struct PickerRenderSpec<'a> {
     title: &'a str,
     filter_title: &'a str,
     empty_label: &'a str,
-    highlight_color: Color,
+    accent: Accent,
+    search_modes: bool,
 }
 
 impl NewFlow {
-    pub(super) fn render(&self, frame: &mut ratatui::Frame<'_>) {
+    pub(super) fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         match &self.step {
-            Step::GitignoreDecision => self.render_gitignore_decision(frame),
-            Step::NameInput => self.render_name_input(frame),
-            Step::StartPointMode => self.render_start_mode(frame),
-            Step::BranchPicker => self.render_branch_picker(frame),
-            Step::CommitPicker => self.render_commit_picker(frame),
-            Step::CopyExtrasDecision => self.render_copy_extras_decision(frame),
-            Step::ExtrasIndexing => self.render_extras_indexing(frame),
-            Step::ExtrasPicker => self.render_extras_picker(frame),
-            Step::ConnectNow => self.render_connect_now(frame),
-            Step::Review => self.render_review(frame),
-            Step::Success => self.render_success(frame),
-            Step::ErrorScreen(error) => self.render_error(frame, error),
+            Step::GitignoreDecision => self.render_gitignore_decision(frame, theme),
+            Step::NameInput => self.render_name_input(frame, theme),
+            Step::ProjectPicker => self.render_project_picker(frame, theme),
+            Step::BranchNameInput => self.render_branch_name_input(frame, theme),
+            Step::BranchConflict => self.render_branch_conflict(frame, theme),
+            Step::StartPointMode => self.render_start_mode(frame, theme),
+            Step::BranchPicker => self.render_branch_picker(frame, theme),
+            Step::CommitPicker => self.render_commit_picker(frame, theme),
+            Step::CopyExtrasDecision => self.render_copy_extras_decision(frame, theme),
+            Step::ExtrasIndexing => self.render_extras_indexing(frame, theme),
+            Step::ExtrasPicker => self.render_extras_picker(frame, theme),
+            Step::ConnectNow => self.render_connect_now(frame, theme),
+            Step::CarryChanges => self.render_carry_changes(frame, theme),
+            Step::Review => self.render_review(frame, theme),
+            Step::Creating => self.render_creating(frame, theme),
+            Step::Success => self.render_success(frame, theme),
+            Step::ErrorScreen(error) => self.render_error(frame, theme, error),
         }
     }
 
-    fn render_gitignore_decision(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_gitignore_decision(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let key_text = compact_hint(
             frame.area().width,
             "Space: toggle    Enter: continue    Esc: back",
@@ -51,12 +65,19 @@ impl NewFlow {
         );
         render_modal(
             frame,
+            theme,
             ModalSpec {
-                title: "Add worktrees/ to .gitignore",
-                title_style: Some(theme::focus_prompt()),
+                title: "Add to .gitignore",
+                title_style: Some(theme.focus_prompt()),
                 body: Text::from(vec![
                     Line::from(""),
+                    label_value_line(
+                        theme,
+                        "Missing entries",
+                        self.prepare.missing_gitignore_entries.join(", "),
+                    ),
                     highlighted_label_value_line(
+                        theme,
                         "Current Selection",
                         self.gitignore_choice.selected_label(),
                     ),
@@ -68,66 +89,151 @@ impl NewFlow {
         );
     }
 
-    fn render_name_input(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_name_input(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        let saved_as = seshmux_core::names::parse_worktree_name(self.name_input.value())
+            .ok()
+            .filter(|name| name.display.is_some())
+            .map(|name| format!("Saved as: {}", name.slug));
+        render_input_modal_with_suggestions(
+            frame,
+            theme,
+            if self.quick {
+                "Quick new worktree name"
+            } else {
+                "New worktree name"
+            },
+            &self.name_input,
+            self.name_error.as_deref(),
+            saved_as.as_deref(),
+            &self.prepare.name_suggestions,
+        );
+    }
+
+    fn render_branch_name_input(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        render_input_modal(
+            frame,
+            theme,
+            "Branch name",
+            &self.branch_input,
+            self.branch_error.as_deref(),
+        );
+    }
+
+    fn render_branch_conflict(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        let Some(conflict) = &self.branch_conflict else {
+            return;
+        };
         let key_text = compact_hint(
             frame.area().width,
-            "Type to edit    Enter: continue    Backspace: delete    Esc: back",
-            "Type    Enter: continue    Backspace: delete    Esc: back",
-            "Type | Enter continue | Backspace delete | Esc back",
+            "Up/Down or j/k: move    Enter: select    Esc: back",
+            "j/k: move    Enter: select    Esc: back",
+            "j/k move | Enter select | Esc back",
         );
-        let rendered = render_modal(
+        let use_other = match &conflict.worktree_name {
+            Some(name) => format!("Attach to worktree '{name}' instead"),
+            None => format!(
+                "Open a shell in {} instead",
+                conflict.worktree_path.display()
+            ),
+        };
+        let options = [
+            "Pick another name".to_string(),
+            format!(
+                "Check out '{}' here too (ignore other worktrees)",
+                conflict.branch
+            ),
+            use_other,
+        ];
+
+        let mut body_lines = vec![
+            Line::from(format!(
+                "Branch '{}' is already checked out in {}.",
+                conflict.branch,
+                conflict.worktree_path.display()
+            )),
+            Line::from(""),
+        ];
+        for (index, option) in options.iter().enumerate() {
+            let selected = self.conflict_selected == index;
+            let line = format!("{} {option}", if selected { ">>" } else { "  " });
+            if selected {
+                body_lines.push(Line::from(Span::styled(
+                    line,
+                    theme.table_highlight(Accent::Create),
+                )));
+            } else {
+                body_lines.push(Line::from(line));
+            }
+        }
+
+        render_modal(
             frame,
+            theme,
             ModalSpec {
-                title: "New worktree name",
-                title_style: Some(theme::focus_prompt()),
-                body: Text::from(vec![Line::from("")]),
+                title: "Branch already checked out",
+                title_style: Some(theme.focus_prompt()),
+                body: Text::from(body_lines),
                 key_hint: Some(key_text),
-                width_pct: 72,
-                height_pct: 44,
+                width_pct: 74,
+                height_pct: 46,
             },
         );
+    }
 
-        let inner = rendered.body_area.inner(Margin {
-            vertical: 1,
-            horizontal: 1,
-        });
-        if inner.width == 0 || inner.height == 0 {
-            return;
-        }
-
-        let input_area = ratatui::layout::Rect::new(inner.x, inner.y, inner.width, 1);
-        let width = input_area.width as usize;
-        let scroll = self.name_input.visual_scroll(width);
-        let input = Paragraph::new(self.name_input.value()).scroll((0, scroll as u16));
-        frame.render_widget(input, input_area);
-
-        if let Some(error) = &self.name_error {
-            if inner.height > 1 {
-                let error_area = ratatui::layout::Rect::new(
-                    inner.x,
-                    inner.y + 1,
-                    inner.width,
-                    inner.height.saturating_sub(1),
-                );
-                frame.render_widget(wrapped_paragraph(format!("Invalid: {error}")), error_area);
+    fn render_project_picker(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        let key_text = compact_hint(
+            frame.area().width,
+            "Up/Down or j/k: move    Enter: select    Esc: back",
+            "j/k: move    Enter: select    Esc: back",
+            "j/k move | Enter select | Esc back",
+        );
+        let options = std::iter::once("Whole repository".to_string()).chain(
+            self.prepare
+                .projects
+                .iter()
+                .map(|(name, project)| format!("{name}  ({})", project.path)),
+        );
+        let mut body_lines = Vec::with_capacity(self.prepare.projects.len() + 1);
+        for (index, option) in options.enumerate() {
+            let selected = self.project_selected == index;
+            let line = format!("{} {option}", if selected { ">>" } else { "  " });
+            if selected {
+                body_lines.push(Line::from(Span::styled(
+                    line,
+                    theme.table_highlight(Accent::Create),
+                )));
+            } else {
+                body_lines.push(Line::from(line));
             }
         }
 
-        if width > 0 {
-            let visual = self.name_input.visual_cursor();
-            let relative = visual.saturating_sub(scroll).min(width.saturating_sub(1));
-            frame.set_cursor_position((input_area.x + relative as u16, input_area.y));
-        }
+        render_modal(
+            frame,
+            theme,
+            ModalSpec {
+                title: "Choose the project for this session",
+                title_style: Some(theme.focus_prompt()),
+                body: Text::from(body_lines),
+                key_hint: Some(key_text),
+                width_pct: 74,
+                height_pct: 46,
+            },
+        );
     }
 
-    fn render_start_mode(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_start_mode(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let key_text = compact_hint(
             frame.area().width,
             "Up/Down or j/k: move    Enter: select    Esc: back",
             "j/k: move    Enter: select    Esc: back",
             "j/k move | Enter select | Esc back",
         );
-        let options = ["From current branch", "From other branch", "From commit"];
+        let current = if self.prepare.detached_head.is_some() {
+            "From current commit (detached HEAD)"
+        } else {
+            "From current branch"
+        };
+        let options = [current, "From other branch", "From commit"];
         let mut body_lines = Vec::with_capacity(options.len());
         for (index, option) in options.iter().enumerate() {
             let line = format!(
@@ -141,7 +247,7 @@ impl NewFlow {
             if self.start_mode_selected == index {
                 body_lines.push(Line::from(Span::styled(
                     line,
-                    theme::table_highlight(Color::Green),
+                    theme.table_highlight(Accent::Create),
                 )));
             } else {
                 body_lines.push(Line::from(line));
@@ -150,9 +256,10 @@ impl NewFlow {
 
         render_modal(
             frame,
+            theme,
             ModalSpec {
                 title: "Choose how this worktree should start",
-                title_style: Some(theme::focus_prompt()),
+                title_style: Some(theme.focus_prompt()),
                 body: Text::from(body_lines),
                 key_hint: Some(key_text),
                 width_pct: 74,
@@ -161,39 +268,46 @@ impl NewFlow {
         );
     }
 
-    fn render_branch_picker(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_branch_picker(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         render_searchable_picker_step(
             frame,
+            theme,
             PickerRenderSpec {
                 title: "Choose branch",
                 filter_title: "Filter branches",
                 empty_label: "No branches found",
-                highlight_color: Color::Yellow,
+                accent: Accent::Attach,
+                search_modes: false,
             },
             self.branch_picker.as_ref(),
             &self.branch_search_input,
             self.branch_filter_focused,
-            |branch| branch.display.clone(),
+            |branch, width| truncate_to_width(&branch.display, width),
         );
     }
 
-    fn render_commit_picker(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_commit_picker(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         render_searchable_picker_step(
             frame,
+            theme,
             PickerRenderSpec {
                 title: "Choose commit",
-                filter_title: "Filter commits",
+                filter_title: match self.commit_search {
+                    CommitSearch::Hash => "Filter commits by hash",
+                    CommitSearch::Message => "Search commit messages",
+                },
                 empty_label: "No commits found",
-                highlight_color: Color::Magenta,
+                accent: Accent::Manage,
+                search_modes: true,
             },
             self.commit_picker.as_ref(),
             &self.commit_search_input,
             self.commit_filter_focused,
-            |commit| commit.display.clone(),
+            commit_row_label,
         );
     }
 
-    fn render_copy_extras_decision(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_copy_extras_decision(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let key_text = compact_hint(
             frame.area().width,
             "Space: toggle    Enter: continue    Esc: back",
@@ -203,15 +317,17 @@ impl NewFlow {
         let body = Text::from(vec![
             Line::from(""),
             highlighted_label_value_line(
+                theme,
                 "Current Selection",
                 self.copy_extras_choice.selected_label(),
             ),
         ]);
         render_modal(
             frame,
+            theme,
             ModalSpec {
                 title: "Copy untracked / gitignored files?",
-                title_style: Some(theme::focus_prompt()),
+                title_style: Some(theme.focus_prompt()),
                 body,
                 key_hint: Some(key_text),
                 width_pct: 74,
@@ -220,7 +336,7 @@ impl NewFlow {
         );
     }
 
-    fn render_extras_indexing(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_extras_indexing(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let Some(indexing) = &self.extras_indexing else {
             return;
         };
@@ -243,20 +359,82 @@ impl NewFlow {
                 "Esc: back",
             ),
         };
-        render_loading_modal(
+        let detail = match indexing.phase {
+            ExtrasIndexingPhase::Collecting | ExtrasIndexingPhase::Building { .. } => {
+                indexing.progress.map(|progress| progress.summary())
+            }
+            _ => None,
+        };
+        render_loading_modal_with_detail(
             frame,
+            theme,
             "Preparing extras",
             &message,
+            detail.as_deref(),
             key_hint,
             &indexing.loading,
         );
 
         if let Some(skip_modal) = &indexing.skip_modal {
-            self.render_skip_modal(frame, skip_modal);
+            self.render_skip_modal(frame, theme, skip_modal);
         }
     }
 
-    fn render_skip_modal(&self, frame: &mut ratatui::Frame<'_>, modal: &SkipModalState) {
+    fn render_creating(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        self.render_review(frame, theme);
+        let Some(creation) = &self.creation else {
+            return;
+        };
+
+        let spinner = creation.loading.current_frame();
+        let mut lines = vec![Line::from("")];
+        let running = creation.phases.len().saturating_sub(1);
+        for (index, progress) in creation.phases.iter().enumerate() {
+            let text = format!("{}: {}", progress.phase.label(), progress.detail);
+            if index < running {
+                lines.push(Line::styled(format!("✓ {text}"), theme.secondary_text()));
+            } else {
+                lines.push(Line::from(format!("{spinner} {text}")));
+            }
+        }
+        if creation.phases.is_empty() {
+            lines.push(Line::from(format!("{spinner} Checking the request")));
+        }
+        if !creation.output.is_empty() {
+            lines.push(Line::from(""));
+            let skip = creation.output.len().saturating_sub(CREATION_OUTPUT_TAIL);
+            lines.extend(
+                creation.output[skip..]
+                    .iter()
+                    .map(|line| Line::styled(format!("  {line}"), theme.secondary_text())),
+            );
+        }
+
+        let key_hint = if creation.canceling() {
+            "Canceling after the current step"
+        } else {
+            "Esc: cancel"
+        };
+        render_modal(
+            frame,
+            theme,
+            ModalSpec {
+                title: "Creating worktree",
+                title_style: Some(theme.focus_prompt()),
+                body: Text::from(lines),
+                key_hint: Some(key_hint),
+                width_pct: if creation.output.is_empty() { 72 } else { 88 },
+                height_pct: if creation.output.is_empty() { 60 } else { 80 },
+            },
+        );
+    }
+
+    fn render_skip_modal(
+        &self,
+        frame: &mut ratatui::Frame<'_>,
+        theme: &Theme,
+        modal: &SkipModalState,
+    ) {
         let key_text = compact_hint(
             frame.area().width,
             "Up/Down: move    Space: toggle skip    a: toggle persist    Enter: confirm    Esc: cancel (set as always in config is fixed)",
@@ -266,9 +444,10 @@ impl NewFlow {
 
         let rendered = render_modal(
             frame,
+            theme,
             ModalSpec {
                 title: "Skip large buckets?",
-                title_style: Some(theme::focus_prompt()),
+                title_style: Some(theme.focus_prompt()),
                 body: Text::from(vec![Line::from("")]),
                 key_hint: Some(key_text),
                 width_pct: 86,
@@ -295,7 +474,9 @@ impl NewFlow {
 
         frame.render_widget(
             Paragraph::new(Text::from(vec![
-                Line::from("Large artifact buckets detected. Choose what to skip for this run."),
+                Line::from(
+                    "Large artifact buckets detected. Choose what to skip for this run; suggested buckets start unskipped.",
+                ),
                 Line::from(""),
             ])),
             header_area,
@@ -322,6 +503,8 @@ impl NewFlow {
                 let selected = if choice.skip { "Yes" } else { "No" };
                 let config_note = if choice.locked_in_config {
                     " (set as always in config)"
+                } else if choice.suggested {
+                    " (suggested)"
                 } else {
                     ""
                 };
@@ -332,7 +515,7 @@ impl NewFlow {
                 if absolute_index == modal.selected {
                     list_lines.push(Line::from(Span::styled(
                         line,
-                        theme::table_highlight(Color::Cyan),
+                        theme.table_highlight(Accent::Browse),
                     )));
                 } else {
                     list_lines.push(Line::from(line));
@@ -350,7 +533,7 @@ impl NewFlow {
         );
     }
 
-    fn render_extras_picker(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_extras_picker(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let area = frame.area();
         let key_label = if self.extras.editing_filter {
             compact_hint(
@@ -362,9 +545,9 @@ impl NewFlow {
         } else {
             compact_hint(
                 area.width,
-                "Up/Down or j/k: move    Tab: fold/unfold    Space: toggle    Enter: continue    a: all    n: none    /: filter    Esc: back",
-                "j/k: move    Tab: fold    Space: toggle    Enter: continue    a: all    n: none    /: filter    Esc: back",
-                "j/k move | Tab fold | Space toggle | Enter continue | a all | n none | / filter | Esc back",
+                "Up/Down or j/k: move    Tab: fold/unfold    Space: toggle    Enter: continue    a: all    n: none    .: dotfiles    i: ignored    u: untracked    /: filter    Esc: back",
+                "j/k: move    Tab: fold    Space: toggle    Enter: continue    a: all    n: none    ./i/u: show kinds    /: filter    Esc: back",
+                "j/k move | Tab fold | Space toggle | Enter continue | a all | n none | ./i/u kinds | / filter | Esc back",
             )
         };
         let footer_height = key_hint_height(area.width, key_label);
@@ -379,10 +562,9 @@ impl NewFlow {
 
         let width = filter_area.width.saturating_sub(2) as usize;
         let scroll = self.extras.filter.visual_scroll(width);
-        let filter = Paragraph::new(self.extras.filter.value())
-            .scroll((0, scroll as u16))
+        let filter = Paragraph::new(input_viewport(self.extras.filter.value(), scroll, width))
             .block(theme::chrome(if self.extras.editing_filter {
-                focus_line("Filter extras")
+                focus_line(theme, "Filter extras")
             } else {
                 Line::from("Filter extras (/ to focus)")
             }));
@@ -393,15 +575,24 @@ impl NewFlow {
             frame.set_cursor_position((filter_area.x + 1 + relative as u16, filter_area.y + 1));
         }
 
+        let mut extras_title = "Select untracked / gitignored extras".to_string();
+        if let Some(hidden) = self.extras.categories.hidden_summary() {
+            extras_title.push_str(&format!(" (hiding {hidden})"));
+        }
         let extras_title = if self.extras.editing_filter {
-            Line::from("Select untracked / gitignored extras (/ to focus)")
+            Line::from(format!("{extras_title} (/ to focus)"))
         } else {
-            focus_line("Select untracked / gitignored extras")
+            focus_line(theme, extras_title)
         };
         let items = self.extras.tree_items();
         if items.is_empty() {
+            let message = if self.extras.nodes.is_empty() {
+                "No untracked or gitignored files/folders were found."
+            } else {
+                "Nothing matches. Press ., i, or u to list dotfiles, ignored, or untracked files again."
+            };
             frame.render_widget(
-                Paragraph::new("No untracked or gitignored files/folders were found.")
+                Paragraph::new(message)
                     .wrap(ratatui::widgets::Wrap { trim: false })
                     .block(theme::chrome(extras_title.clone())),
                 body,
@@ -416,12 +607,7 @@ impl NewFlow {
                         .begin_symbol(None)
                         .end_symbol(None),
                 ))
-                .highlight_style(
-                    Style::default()
-                        .fg(Color::Black)
-                        .bg(Color::Blue)
-                        .add_modifier(Modifier::BOLD),
-                )
+                .highlight_style(theme.table_highlight(Accent::Extras))
                 .highlight_symbol(">> ");
             frame.render_stateful_widget(tree, body, &mut state);
         }
@@ -430,7 +616,7 @@ impl NewFlow {
         frame.render_widget(keys, footer);
     }
 
-    fn render_connect_now(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_connect_now(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let key_text = compact_hint(
             frame.area().width,
             "Space: toggle    Enter: continue    Esc: back",
@@ -438,14 +624,16 @@ impl NewFlow {
             "Space toggle | Enter continue | Esc back",
         );
         let body = Text::from(vec![highlighted_label_value_line(
+            theme,
             "Current Selection",
             self.connect_choice.selected_label(),
         )]);
         render_modal(
             frame,
+            theme,
             ModalSpec {
                 title: "Attach to the tmux session now?",
-                title_style: Some(theme::focus_prompt()),
+                title_style: Some(theme.focus_prompt()),
                 body,
                 key_hint: Some(key_text),
                 width_pct: 70,
@@ -454,51 +642,137 @@ impl NewFlow {
         );
     }
 
-    fn render_review(&self, frame: &mut ratatui::Frame<'_>) {
+    fn render_carry_changes(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
         let key_text = compact_hint(
             frame.area().width,
-            "Enter: create worktree    Esc: back",
-            "Enter: create    Esc: back",
-            "Enter create | Esc back",
+            "Space: toggle    Enter: continue    Esc: back",
+            "Space toggle    Enter continue    Esc back",
+            "Space toggle | Enter continue | Esc back",
         );
+        let mut lines = vec![
+            highlighted_label_value_line(
+                theme,
+                "Current Selection",
+                self.carry_choice.selected_label(),
+            ),
+            Line::from(""),
+            Line::from("Yes applies these changes to the new worktree; this checkout keeps them."),
+            Line::from(""),
+        ];
+        lines.extend(
+            self.changed_files
+                .iter()
+                .map(|path| Line::from(format!("  {path}"))),
+        );
+        render_modal(
+            frame,
+            theme,
+            ModalSpec {
+                title: "Carry uncommitted changes into the new worktree?",
+                title_style: Some(theme.focus_prompt()),
+                body: Text::from(lines),
+                key_hint: Some(key_text),
+                width_pct: 70,
+                height_pct: 50,
+            },
+        );
+    }
+
+    fn render_review(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        let key_text = if self.prepare.has_envrc {
+            compact_hint(
+                frame.area().width,
+                "Enter: create worktree    l: toggle LFS pull    s: toggle submodules    d: toggle direnv allow    Esc: back",
+                "Enter: create    l: LFS pull    s: submodules    d: direnv    Esc: back",
+                "Enter create | l lfs | s subm | d direnv | Esc back",
+            )
+        } else {
+            compact_hint(
+                frame.area().width,
+                "Enter: create worktree    l: toggle LFS pull    s: toggle submodules    Esc: back",
+                "Enter: create    l: LFS pull    s: submodules    Esc: back",
+                "Enter create | l lfs | s submodules | Esc back",
+            )
+        };
+        let direnv = if self.prepare.has_envrc {
+            yes_no(self.direnv_allow).to_string()
+        } else {
+            "no .envrc in the repository".to_string()
+        };
 
         let start_point = match &self.start_point {
-            Some(NewStartPoint::CurrentBranch) => "Current branch".to_string(),
+            Some(NewStartPoint::CurrentBranch) => match &self.prepare.detached_head {
+                Some(short_sha) => format!("Current commit: {short_sha} (detached HEAD)"),
+                None => "Current branch".to_string(),
+            },
             Some(NewStartPoint::Branch(name)) => format!("Branch: {name}"),
             Some(NewStartPoint::Commit(hash)) => format!("Commit: {hash}"),
+            Some(NewStartPoint::NewBranchFrom(name)) => {
+                format!("New branch: {name} (from current HEAD)")
+            }
+            Some(other) => format!("{other:?}"),
             None => "UNCONFIRMED".to_string(),
         };
 
         let extras_count = self.review_selected_extras_count();
-        let review = Text::from(vec![
-            label_value_line("Worktree name", self.name_input.value()),
-            label_value_line("Start from", start_point),
+        let mut review = vec![
+            label_value_line(theme, "Worktree name", self.review_worktree_name()),
+            label_value_line(theme, "Worktree path", self.review_worktree_path.as_str()),
+            label_value_line(theme, "Branch", self.branch_input.value()),
+            label_value_line(theme, "Start from", start_point),
             label_value_line(
-                "Add worktrees/ to .gitignore",
+                theme,
+                "Add to .gitignore",
                 yes_no(
-                    !self.prepare.gitignore_has_worktrees_entry
+                    !self.prepare.missing_gitignore_entries.is_empty()
                         && self.gitignore_choice.yes_selected,
                 ),
             ),
             label_value_line(
+                theme,
                 "Copy untracked / gitignored files",
                 yes_no(self.copy_extras_choice.yes_selected),
             ),
             label_value_line(
+                theme,
                 "Untracked / gitignored files selected",
                 extras_count.to_string(),
             ),
+            label_value_line(theme, "Pull Git LFS objects", yes_no(self.lfs_pull)),
+            label_value_line(
+                theme,
+                "Initialize submodules (recursive)",
+                yes_no(self.init_submodules),
+            ),
+            label_value_line(theme, "Run direnv allow", direnv),
             label_value_line(
+                theme,
                 "Connect to tmux now",
                 yes_no(self.connect_choice.yes_selected),
             ),
-        ]);
+        ];
+        if !self.prepare.projects.is_empty() {
+            let project = self.selected_project().map_or_else(
+                || "Whole repository".to_string(),
+                |(name, project)| format!("{name} ({})", project.path),
+            );
+            review.insert(3, label_value_line(theme, "Project", project));
+        }
+        if !self.changed_files.is_empty() {
+            review.push(label_value_line(
+                theme,
+                "Carry uncommitted changes",
+                yes_no(self.carry_choice.yes_selected),
+            ));
+        }
+
         render_modal(
             frame,
+            theme,
             ModalSpec {
                 title: "Confirm settings before creating the worktree",
-                title_style: Some(theme::focus_prompt()),
-                body: review,
+                title_style: Some(theme.focus_prompt()),
+                body: Text::from(review),
                 key_hint: Some(key_text),
                 width_pct: 82,
                 height_pct: 62,
@@ -506,18 +780,118 @@ impl NewFlow {
         );
     }
 
-    fn render_success(&self, frame: &mut ratatui::Frame<'_>) {
-        let footer = result_footer(frame.area().width);
+    fn review_worktree_name(&self) -> String {
+        match seshmux_core::names::parse_worktree_name(self.name_input.value()) {
+            Ok(WorktreeName {
+                slug,
+                display: Some(display),
+            }) => format!("{display} (saved as {slug})"),
+            Ok(WorktreeName { slug, .. }) => slug,
+            Err(_) => self.name_input.value().to_string(),
+        }
+    }
+
+    fn render_success(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        let footer = if self.success.is_some() {
+            copy_result_footer(frame.area().width)
+        } else {
+            result_footer(frame.area().width)
+        };
         let success = if let Some(result) = &self.success {
             let mut lines = vec![
-                label_value_line("Worktree path", result.worktree_path.display().to_string()),
-                label_value_line("tmux session name", result.session_name.clone()),
-                label_value_line("Attach command", result.attach_command.clone()),
-                label_value_line("Connected in this terminal", yes_no(result.connected_now)),
+                label_value_line(
+                    theme,
+                    "Worktree name",
+                    result
+                        .display_name
+                        .as_deref()
+                        .unwrap_or(&result.worktree_name),
+                ),
+                label_value_line(
+                    theme,
+                    "Worktree path",
+                    result.worktree_path.display().to_string(),
+                ),
+                label_value_line(theme, "tmux session name", result.session_name.clone()),
+                label_value_line(theme, "Attach command", result.attach_command.clone()),
+                label_value_line(
+                    theme,
+                    "Connected in this terminal",
+                    yes_no(result.connected_now),
+                ),
             ];
+            if result.extras_copied.copied > 0 {
+                lines.push(label_value_line(
+                    theme,
+                    "Copied extras",
+                    format!(
+                        "{} file(s), {}",
+                        result.extras_copied.copied,
+                        format_bytes(result.extras_copied.bytes)
+                    ),
+                ));
+            }
+            if !result.gitignore_added.is_empty() {
+                lines.push(label_value_line(
+                    theme,
+                    "Added to .gitignore",
+                    result.gitignore_added.join(", "),
+                ));
+            }
+            if !result.shared_caches.is_empty() {
+                let caches = result
+                    .shared_caches
+                    .iter()
+                    .map(|cache| {
+                        format!(
+                            "{} ({})",
+                            cache.relative_path.display(),
+                            cache.strategy.label()
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                lines.push(label_value_line(theme, "Shared caches", caches));
+            }
+            match &result.carried_changes {
+                Some(CarriedChanges::Applied) => {
+                    lines.push(label_value_line(theme, "Uncommitted changes", "applied"));
+                }
+                Some(CarriedChanges::Conflicts { paths }) => {
+                    lines.push(label_value_line(
+                        theme,
+                        "Uncommitted changes",
+                        format!("conflicts to resolve in {}", paths.join(", ")),
+                    ));
+                }
+                None => {}
+            }
+            let provision = &result.provision;
+            match &provision.failure {
+                Some(failure) => lines.push(label_value_line(
+                    theme,
+                    "Bootstrap commands",
+                    format!("{} ran, then {failure}", provision.ran.len()),
+                )),
+                None if !provision.ran.is_empty() => lines.push(label_value_line(
+                    theme,
+                    "Bootstrap commands",
+                    format!("{} ran", provision.ran.len()),
+                )),
+                None => {}
+            }
+            if !result.environment.is_empty() {
+                let variables = result
+                    .environment
+                    .iter()
+                    .map(|(key, value)| format!("{key}={value}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                lines.push(label_value_line(theme, "Session environment", variables));
+            }
             if let Some(notice) = &self.success_notice {
                 lines.push(Line::from(""));
-                lines.push(label_value_line("Notice", notice.clone()));
+                lines.push(label_value_line(theme, "Notice", notice.clone()));
             }
             Text::from(lines)
         } else {
@@ -526,9 +900,10 @@ impl NewFlow {
 
         render_modal(
             frame,
+            theme,
             ModalSpec {
                 title: "Success",
-                title_style: Some(theme::success_prompt()),
+                title_style: Some(theme.success_prompt()),
                 body: success,
                 key_hint: Some(footer),
                 width_pct: 80,
@@ -537,42 +912,62 @@ impl NewFlow {
         );
     }
 
-    fn render_error(&self, frame: &mut ratatui::Frame<'_>, error: &NewFlowErrorState) {
+    fn render_error(
+        &self,
+        frame: &mut ratatui::Frame<'_>,
+        theme: &Theme,
+        error: &NewFlowErrorState,
+    ) {
         let headline = match error.origin {
             NewFlowErrorOrigin::ExtrasIndexing => "Failed to prepare extras selection",
             NewFlowErrorOrigin::ReviewSubmit => "Failed to create worktree",
         };
-        let body = Text::from(vec![
-            Line::from(headline),
-            Line::from(""),
-            Line::from(error.message.clone()),
-        ]);
-        render_modal(
-            frame,
-            ModalSpec {
-                title: "Error",
-                title_style: Some(theme::error_prompt()),
-                body,
-                key_hint: Some("Enter/Esc: back"),
-                width_pct: 85,
-                height_pct: 70,
-            },
-        );
+        let message = format!("{headline}\n\n{}", error.message);
+        render_error_with_actions(frame, theme, &message, &error.actions, "back", 85, 70);
     }
 }
 
+fn commit_row_label(commit: &CommitRef, width: usize) -> String {
+    let details = format!("{}  {}", commit.author, commit.relative_date);
+    let details = details.trim();
+    let subject_width = width.saturating_sub(display_width(details) + 2);
+    if details.is_empty() || subject_width < 24 {
+        return truncate_to_width(&commit.display, width);
+    }
+    let subject = truncate_to_width(&commit.display, subject_width);
+    let padding = width - display_width(&subject) - display_width(details);
+    format!("{subject}{}{details}", " ".repeat(padding))
+}
+
 fn render_searchable_picker_step<T, F>(
     frame: &mut ratatui::Frame<'_>,
+    theme: &Theme,
     spec: PickerRenderSpec<'_>,
     picker: Option<&PickerState<T>>,
     filter_input: &tui_input::Input,
     filter_focused: bool,
     item_label: F,
 ) where
-    F: Fn(&T) -> String,
+    F: Fn(&T, usize) -> String,
 {
     let area = frame.area();
-    let key_text = if filter_focused {
+    let key_text = if spec.search_modes {
+        if filter_focused {
+            compact_hint(
+                area.width,
+                "Type: filter    Tab: search mode    /: list focus    Esc: back",
+                "Type filter    Tab: mode    /: list    Esc: back",
+                "Type | Tab mode | / list | Esc back",
+            )
+        } else {
+            compact_hint(
+                area.width,
+                "/: filter focus    Tab: search mode    Enter: choose    j/k: move    r: refresh    Esc: back",
+                "/: filter    Tab: mode    Enter: choose    j/k: move    r: refresh    Esc: back",
+                "/ filter | Tab mode | Enter | j/k | Esc",
+            )
+        }
+    } else if filter_focused {
         compact_hint(
             area.width,
             "Type: filter    Backspace: delete    /: list focus    Esc: back",
@@ -582,8 +977,8 @@ fn render_searchable_picker_step<T, F>(
     } else {
         compact_hint(
             area.width,
-            "/: filter focus    Enter: choose    Up/Down or j/k: move    Esc: back",
-            "/: filter    Enter: choose    j/k: move    Esc: back",
+            "/: filter focus    Enter: choose    Up/Down or j/k: move    r: refresh    Esc: back",
+            "/: filter    Enter: choose    j/k: move    r: refresh    Esc: back",
             "/ filter | Enter choose | j/k move | Esc back",
         )
     };
@@ -600,12 +995,11 @@ fn render_searchable_picker_step<T, F>(
     let width = filter_area.width.saturating_sub(2) as usize;
     let scroll = filter_input.visual_scroll(width);
     let filter_title = if filter_focused {
-        focus_line(spec.filter_title)
+        focus_line(theme, spec.filter_title)
     } else {
         Line::from(format!("{} (/ to focus)", spec.filter_title))
     };
-    let filter = Paragraph::new(filter_input.value())
-        .scroll((0, scroll as u16))
+    let filter = Paragraph::new(input_viewport(filter_input.value(), scroll, width))
         .block(theme::chrome(filter_title));
     frame.render_widget(filter, filter_area);
     if filter_focused && width > 0 {
@@ -614,19 +1008,32 @@ fn render_searchable_picker_step<T, F>(
         frame.set_cursor_position((filter_area.x + 1 + relative as u16, filter_area.y + 1));
     }
 
+    let title = match picker {
+        Some(picker) if picker.has_more => {
+            format!("{} ({} shown, more below)", spec.title, picker.items.len())
+        }
+        _ => spec.title.to_string(),
+    };
     let list_title = if filter_focused {
-        Line::from(format!("{} (/ to focus)", spec.title))
+        Line::from(format!("{title} (/ to focus)"))
     } else {
-        focus_line(spec.title)
+        focus_line(theme, title)
     };
 
+    let label_width = usize::from(body.width.saturating_sub(2));
     let mut rows = Vec::new();
     if let Some(picker) = picker {
+        rows.extend(
+            picker
+                .actions
+                .iter()
+                .map(|label| ListItem::new(format!("+ {label}")).style(theme.focus_prompt())),
+        );
         rows.extend(
             picker
                 .items
                 .iter()
-                .map(|item| ListItem::new(item_label(item))),
+                .map(|item| ListItem::new(item_label(item, label_width))),
         );
     }
 
@@ -638,12 +1045,7 @@ fn render_searchable_picker_step<T, F>(
     } else {
         let list = List::new(rows)
             .block(theme::chrome(list_title))
-            .highlight_style(
-                Style::default()
-                    .fg(Color::Black)
-                    .bg(spec.highlight_color)
-                    .add_modifier(Modifier::BOLD),
-            );
+            .highlight_style(theme.table_highlight(spec.accent));
         let mut state = ListState::default();
         state.select(Some(picker.map(|value| value.selected).unwrap_or(0)));
         frame.render_stateful_widget(list, body, &mut state);
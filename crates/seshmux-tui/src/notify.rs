@@ -0,0 +1,115 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
+
+use seshmux_core::command_runner::CommandRunner;
+
+const NOTIFICATION_TITLE: &str = "seshmux";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct JobCompletion {
+    pub(crate) label: String,
+    pub(crate) elapsed: Duration,
+}
+
+impl JobCompletion {
+    pub(crate) fn new(label: impl Into<String>, elapsed: Duration) -> Self {
+        Self {
+            label: label.into(),
+            elapsed,
+        }
+    }
+
+    pub(crate) fn message(&self) -> String {
+        format!("{} finished in {}s", self.label, self.elapsed.as_secs())
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct JobTracker {
+    sender: Sender<JobCompletion>,
+    receiver: Receiver<JobCompletion>,
+}
+
+impl Default for JobTracker {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self { sender, receiver }
+    }
+}
+
+impl JobTracker {
+    pub(crate) fn reporter(&self) -> JobReporter {
+        JobReporter(Some(self.sender.clone()))
+    }
+
+    pub(crate) fn poll(&self) -> Option<JobCompletion> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct JobReporter(Option<Sender<JobCompletion>>);
+
+impl JobReporter {
+    pub(crate) fn report(&self, job: JobCompletion) {
+        if let Some(sender) = &self.0 {
+            let _ = sender.send(job);
+        }
+    }
+}
+
+pub(crate) fn osc777_sequence(body: &str) -> String {
+    format!(
+        "\x1b]777;notify;{NOTIFICATION_TITLE};{}\x07",
+        sanitize_osc_field(body)
+    )
+}
+
+pub(crate) fn notify_send(runner: &dyn CommandRunner, body: &str) {
+    // Desktop notifications are best-effort; a missing notify-send must not disturb the TUI.
+    let _ = runner.run("notify-send", &[NOTIFICATION_TITLE, body], None);
+}
+
+fn sanitize_osc_field(value: &str) -> String {
+    value
+        .chars()
+        .filter(|character| !character.is_control())
+        .map(|character| if character == ';' { ',' } else { character })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{JobCompletion, JobTracker, osc777_sequence};
+
+    #[test]
+    fn job_completion_message_reports_whole_seconds() {
+        let job = JobCompletion::new("Extras indexing", Duration::from_millis(6_400));
+        assert_eq!(job.message(), "Extras indexing finished in 6s");
+    }
+
+    #[test]
+    fn job_tracker_receives_reports_from_a_worker_thread() {
+        let jobs = JobTracker::default();
+        let reporter = jobs.reporter();
+        std::thread::spawn(move || {
+            reporter.report(JobCompletion::new("Creating worktree 'w1'", Duration::ZERO));
+        })
+        .join()
+        .expect("worker");
+
+        let job = jobs.poll().expect("completion");
+        assert_eq!(job.label, "Creating worktree 'w1'");
+        assert!(jobs.poll().is_none());
+    }
+
+    #[test]
+    fn osc777_sequence_strips_separators_and_control_characters() {
+        assert_eq!(
+            osc777_sequence("done; ok\x1b"),
+            "\x1b]777;notify;seshmux;done, ok\x07"
+        );
+    }
+}
@@ -0,0 +1,453 @@
+use std::path::Path;
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Margin, Rect};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{List, ListItem, ListState, Paragraph};
+use seshmux_app::{App, ListResult};
+use seshmux_core::doctor::DoctorReport;
+use tui_input::Input;
+use tui_input::backend::crossterm::EventHandler;
+
+use crate::RootAction;
+use crate::keymap;
+use crate::theme::{Accent, Theme};
+use crate::ui::modal::{ModalSpec, render_modal, render_notice_modal};
+use crate::ui::text::{compact_hint, input_viewport};
+
+pub(crate) trait PaletteOps {
+    fn list_worktrees(&self, cwd: &Path) -> Result<ListResult>;
+    fn recent_commands(&self, cwd: &Path) -> Result<Vec<String>>;
+}
+
+impl<'a> PaletteOps for App<'a> {
+    fn list_worktrees(&self, cwd: &Path) -> Result<ListResult> {
+        Ok(self.list(cwd)?)
+    }
+
+    fn recent_commands(&self, cwd: &Path) -> Result<Vec<String>> {
+        Ok(App::recent_commands(self, cwd)?)
+    }
+}
+
+const PALETTE_ACTIONS: [RootAction; 8] = [
+    RootAction::New,
+    RootAction::QuickNew,
+    RootAction::List,
+    RootAction::Attach,
+    RootAction::Delete,
+    RootAction::Find,
+    RootAction::Adopt,
+    RootAction::SkipRules,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PaletteCommand {
+    Action(RootAction),
+    Attach(String),
+    Delete(String),
+    Open(String),
+    Doctor,
+}
+
+impl PaletteCommand {
+    pub(crate) fn label(&self) -> String {
+        match self {
+            Self::Action(action) => action.command().to_string(),
+            Self::Attach(name) => format!("attach {name}"),
+            Self::Delete(name) => format!("delete {name}"),
+            Self::Open(name) => format!("open {name}"),
+            Self::Doctor => "doctor".to_string(),
+        }
+    }
+
+    pub(crate) fn replaces_screen(&self) -> bool {
+        matches!(self, Self::Action(_) | Self::Attach(_) | Self::Delete(_))
+    }
+
+    fn description(&self) -> String {
+        match self {
+            Self::Action(action) => action.title().to_string(),
+            Self::Attach(_) => "Attach to its tmux session".to_string(),
+            Self::Delete(_) => "Delete the worktree".to_string(),
+            Self::Open(_) => "Open the worktree in your editor".to_string(),
+            Self::Doctor => "Check tmux, git, and the config".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PaletteEvent {
+    Continue,
+    Close,
+    Run(PaletteCommand),
+}
+
+#[derive(Debug)]
+pub(crate) struct Palette {
+    query: Input,
+    commands: Vec<PaletteCommand>,
+    recent: Vec<String>,
+    matches: Vec<usize>,
+    selected: usize,
+}
+
+impl Palette {
+    pub(crate) fn new(ops: &dyn PaletteOps, cwd: &Path) -> Self {
+        let mut commands: Vec<PaletteCommand> = PALETTE_ACTIONS
+            .into_iter()
+            .map(PaletteCommand::Action)
+            .collect();
+        commands.push(PaletteCommand::Doctor);
+        if let Ok(listed) = ops.list_worktrees(cwd) {
+            for row in listed.rows {
+                commands.push(PaletteCommand::Attach(row.name.clone()));
+                commands.push(PaletteCommand::Open(row.name.clone()));
+                commands.push(PaletteCommand::Delete(row.name));
+            }
+        }
+
+        let mut palette = Self {
+            query: Input::default(),
+            commands,
+            recent: ops.recent_commands(cwd).unwrap_or_default(),
+            matches: Vec::new(),
+            selected: 0,
+        };
+        palette.refresh_matches();
+        palette
+    }
+
+    pub(crate) fn on_key(&mut self, key: KeyEvent) -> PaletteEvent {
+        if keymap::is_back(key) || is_palette_key(key) {
+            return PaletteEvent::Close;
+        }
+
+        if keymap::is_confirm(key) {
+            return match self.selected_command() {
+                Some(command) => PaletteEvent::Run(command.clone()),
+                None => PaletteEvent::Continue,
+            };
+        }
+
+        match key.code {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => {
+                if self.selected + 1 < self.matches.len() {
+                    self.selected += 1;
+                }
+            }
+            _ => {
+                if self.query.handle_event(&Event::Key(key)).is_some() {
+                    self.refresh_matches();
+                }
+            }
+        }
+        PaletteEvent::Continue
+    }
+
+    pub(crate) fn on_paste(&mut self, text: &str) {
+        if crate::ui::paste::paste_into(&mut self.query, text) {
+            self.refresh_matches();
+        }
+    }
+
+    fn selected_command(&self) -> Option<&PaletteCommand> {
+        self.matches
+            .get(self.selected)
+            .map(|index| &self.commands[*index])
+    }
+
+    fn recent_rank(&self, command: &PaletteCommand) -> Option<usize> {
+        let label = command.label();
+        self.recent.iter().position(|recent| *recent == label)
+    }
+
+    fn refresh_matches(&mut self) {
+        let query = self.query.value().trim().to_lowercase();
+        let mut scored: Vec<(Option<usize>, u32, usize)> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(index, command)| {
+                let score = fuzzy_score(&query, &command.label())?;
+                Some((self.recent_rank(command), score, index))
+            })
+            .collect();
+        scored.sort_by(|left, right| {
+            let recent = |rank: Option<usize>| rank.unwrap_or(usize::MAX);
+            recent(left.0)
+                .cmp(&recent(right.0))
+                .then(right.1.cmp(&left.1))
+                .then(left.2.cmp(&right.2))
+        });
+        self.matches = scored.into_iter().map(|(_, _, index)| index).collect();
+        self.selected = 0;
+    }
+
+    pub(crate) fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        let key_text = compact_hint(
+            frame.area().width,
+            "Type: search    Up/Down: move    Enter: run    Esc/Ctrl+P: close",
+            "Type    Up/Down: move    Enter: run    Esc: close",
+            "Type | Enter run | Esc close",
+        );
+        let rendered = render_modal(
+            frame,
+            theme,
+            ModalSpec {
+                title: "Commands",
+                title_style: Some(theme.focus_prompt()),
+                body: Text::from(vec![Line::from("")]),
+                key_hint: Some(key_text),
+                width_pct: 72,
+                height_pct: 60,
+            },
+        );
+
+        let inner = rendered.body_area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+        if inner.width == 0 || inner.height < 2 {
+            return;
+        }
+
+        let input_area = Rect::new(inner.x, inner.y, inner.width, 1);
+        let width = input_area.width as usize;
+        let scroll = self.query.visual_scroll(width);
+        frame.render_widget(
+            Paragraph::new(input_viewport(self.query.value(), scroll, width)),
+            input_area,
+        );
+        let visual = self.query.visual_cursor();
+        let relative = visual.saturating_sub(scroll).min(width.saturating_sub(1));
+        frame.set_cursor_position((input_area.x + relative as u16, input_area.y));
+
+        let list_area = Rect::new(inner.x, inner.y + 1, inner.width, inner.height - 1);
+        if self.matches.is_empty() {
+            frame.render_widget(
+                Paragraph::new(Line::styled(
+                    "No matching commands.",
+                    theme.secondary_text(),
+                )),
+                list_area,
+            );
+            return;
+        }
+
+        let items: Vec<ListItem<'_>> = self
+            .matches
+            .iter()
+            .map(|index| {
+                let command = &self.commands[*index];
+                let mut spans = vec![
+                    Span::raw(command.label()),
+                    Span::styled(
+                        format!("  {}", command.description()),
+                        theme.secondary_text(),
+                    ),
+                ];
+                if self.recent_rank(command).is_some() {
+                    spans.push(Span::styled("  (recent)", theme.secondary_text()));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+        let list = List::new(items).highlight_style(theme.table_highlight(Accent::Browse));
+        let mut state = ListState::default();
+        state.select(Some(self.selected));
+        frame.render_stateful_widget(list, list_area, &mut state);
+    }
+}
+
+pub(crate) fn is_palette_key(key: KeyEvent) -> bool {
+    key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('p')
+}
+
+fn fuzzy_score(query: &str, label: &str) -> Option<u32> {
+    let label: Vec<char> = label.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for wanted in query.chars().filter(|ch| !ch.is_whitespace()) {
+        let found = position + label[position..].iter().position(|ch| *ch == wanted)?;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 3;
+        }
+        if found == 0 || !label[found - 1].is_alphanumeric() {
+            score += 2;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
+
+pub(crate) fn render_doctor_report(
+    frame: &mut ratatui::Frame<'_>,
+    theme: &Theme,
+    report: &DoctorReport,
+) {
+    let mut message = report
+        .checks
+        .iter()
+        .map(|check| format!("{}  {}: {}", check.state, check.name, check.details))
+        .collect::<Vec<_>>()
+        .join("\n");
+    message.push_str(&format!("\n\n{}", report.summary()));
+    render_notice_modal(frame, theme, "Doctor", &message, 80, 70, "Enter/Esc: close");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use anyhow::{Result, anyhow};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use seshmux_app::{ListResult, WorktreeHead, WorktreeRow};
+
+    use super::{Palette, PaletteCommand, PaletteEvent, PaletteOps, fuzzy_score};
+    use crate::RootAction;
+
+    struct FakeOps {
+        names: Option<Vec<&'static str>>,
+        recent: Vec<&'static str>,
+    }
+
+    impl PaletteOps for FakeOps {
+        fn list_worktrees(&self, _cwd: &Path) -> Result<ListResult> {
+            let names = self
+                .names
+                .clone()
+                .ok_or_else(|| anyhow!("not a repository"))?;
+            Ok(ListResult::new(
+                "/tmp/repo",
+                names
+                    .into_iter()
+                    .map(|name| {
+                        WorktreeRow::new(
+                            name,
+                            format!("/tmp/repo/worktrees/{name}"),
+                            "2026-02-25T10:00:00Z",
+                            WorktreeHead::Branch(name.to_string()),
+                            format!("repo/{name}"),
+                        )
+                    })
+                    .collect(),
+            ))
+        }
+
+        fn recent_commands(&self, _cwd: &Path) -> Result<Vec<String>> {
+            Ok(self.recent.iter().map(|label| label.to_string()).collect())
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(palette: &mut Palette, text: &str) {
+        for ch in text.chars() {
+            palette.on_key(key(KeyCode::Char(ch)));
+        }
+    }
+
+    fn labels(palette: &Palette) -> Vec<String> {
+        palette
+            .matches
+            .iter()
+            .map(|index| palette.commands[*index].label())
+            .collect()
+    }
+
+    #[test]
+    fn fuzzy_score_needs_every_character_in_order() {
+        assert!(fuzzy_score("atw1", "attach w1").is_some());
+        assert!(fuzzy_score("w1a", "attach w1").is_none());
+        assert!(fuzzy_score("", "doctor").is_some());
+        assert!(fuzzy_score("att", "attach w1") > fuzzy_score("ath", "attach w1"));
+    }
+
+    #[test]
+    fn palette_lists_flows_and_commands_for_each_worktree() {
+        let ops = FakeOps {
+            names: Some(vec!["w1"]),
+            recent: Vec::new(),
+        };
+        let palette = Palette::new(&ops, Path::new("/tmp/repo"));
+        let labels = labels(&palette);
+
+        for expected in [
+            "new",
+            "attach",
+            "doctor",
+            "attach w1",
+            "open w1",
+            "delete w1",
+        ] {
+            assert!(labels.contains(&expected.to_string()), "{labels:?}");
+        }
+    }
+
+    #[test]
+    fn palette_without_a_repository_still_lists_the_flows() {
+        let ops = FakeOps {
+            names: None,
+            recent: Vec::new(),
+        };
+        let palette = Palette::new(&ops, Path::new("/tmp"));
+
+        assert_eq!(labels(&palette)[0], "new");
+        assert!(labels(&palette).contains(&"doctor".to_string()));
+    }
+
+    #[test]
+    fn recent_commands_rank_first_and_survive_filtering() {
+        let ops = FakeOps {
+            names: Some(vec!["w1", "w2"]),
+            recent: vec!["delete w2", "doctor", "attach gone"],
+        };
+        let mut palette = Palette::new(&ops, Path::new("/tmp/repo"));
+        assert_eq!(labels(&palette)[..2], ["delete w2", "doctor"]);
+
+        type_text(&mut palette, "w2");
+        assert_eq!(labels(&palette)[0], "delete w2");
+        assert!(labels(&palette).iter().all(|label| label.contains("w2")));
+    }
+
+    #[test]
+    fn enter_runs_the_highlighted_command_and_esc_closes() {
+        let ops = FakeOps {
+            names: Some(vec!["w1"]),
+            recent: Vec::new(),
+        };
+        let mut palette = Palette::new(&ops, Path::new("/tmp/repo"));
+
+        type_text(&mut palette, "open");
+        assert_eq!(
+            palette.on_key(key(KeyCode::Enter)),
+            PaletteEvent::Run(PaletteCommand::Open("w1".to_string()))
+        );
+
+        palette.on_key(key(KeyCode::Backspace));
+        palette.on_key(key(KeyCode::Backspace));
+        palette.on_key(key(KeyCode::Backspace));
+        palette.on_key(key(KeyCode::Backspace));
+        type_text(&mut palette, "quick");
+        assert_eq!(
+            palette.on_key(key(KeyCode::Enter)),
+            PaletteEvent::Run(PaletteCommand::Action(RootAction::QuickNew))
+        );
+
+        type_text(&mut palette, "zzz");
+        assert_eq!(palette.on_key(key(KeyCode::Enter)), PaletteEvent::Continue);
+        assert_eq!(palette.on_key(key(KeyCode::Esc)), PaletteEvent::Close);
+        assert_eq!(
+            palette.on_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
+            PaletteEvent::Close
+        );
+    }
+}
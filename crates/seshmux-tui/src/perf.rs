@@ -0,0 +1,148 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Default)]
+pub struct FrameTiming {
+    enabled: bool,
+    report: Arc<Mutex<Option<String>>>,
+}
+
+impl FrameTiming {
+    pub fn enabled() -> Self {
+        Self {
+            enabled: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn take_report(&self) -> Option<String> {
+        self.report
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+
+    pub(crate) fn start(&self) -> Option<FrameTimings> {
+        self.enabled.then(|| FrameTimings {
+            draws: Vec::new(),
+            events: Vec::new(),
+            report: Arc::clone(&self.report),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct FrameTimings {
+    draws: Vec<Duration>,
+    events: Vec<Duration>,
+    report: Arc<Mutex<Option<String>>>,
+}
+
+impl FrameTimings {
+    pub(crate) fn record_draw(&mut self, elapsed: Duration) {
+        self.draws.push(elapsed);
+    }
+
+    pub(crate) fn record_event(&mut self, elapsed: Duration) {
+        self.events.push(elapsed);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn draw_percentile(&self, percentile: u32) -> Option<Duration> {
+        percentile_of(&self.draws, percentile)
+    }
+
+    pub(crate) fn report(&self) -> String {
+        format!(
+            "frame timing: draw {}; event {}",
+            summarize(&self.draws),
+            summarize(&self.events)
+        )
+    }
+}
+
+impl Drop for FrameTimings {
+    fn drop(&mut self) {
+        // The TUI has many exit paths; publishing on drop keeps every one of them covered.
+        *self.report.lock().unwrap_or_else(PoisonError::into_inner) = Some(self.report());
+    }
+}
+
+fn summarize(samples: &[Duration]) -> String {
+    let Some(max) = samples.iter().max() else {
+        return "n=0".to_string();
+    };
+    let micros = |percentile| {
+        percentile_of(samples, percentile)
+            .unwrap_or_default()
+            .as_micros()
+    };
+
+    format!(
+        "n={} p50={}us p95={}us p99={}us max={}us",
+        samples.len(),
+        micros(50),
+        micros(95),
+        micros(99),
+        max.as_micros()
+    )
+}
+
+fn percentile_of(samples: &[Duration], percentile: u32) -> Option<Duration> {
+    if samples.is_empty() {
+        return None;
+    }
+
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable();
+    let rank = (sorted.len() * percentile.min(100) as usize).div_ceil(100);
+    Some(sorted[rank.saturating_sub(1)])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{FrameTiming, percentile_of, summarize};
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let samples: Vec<Duration> = (1..=100).map(Duration::from_micros).collect();
+
+        assert_eq!(percentile_of(&samples, 50), Some(Duration::from_micros(50)));
+        assert_eq!(percentile_of(&samples, 99), Some(Duration::from_micros(99)));
+        assert_eq!(
+            percentile_of(&samples, 100),
+            Some(Duration::from_micros(100))
+        );
+        assert_eq!(percentile_of(&[], 50), None);
+    }
+
+    #[test]
+    fn report_is_published_to_the_handle_only_when_enabled() {
+        let disabled = FrameTiming::default();
+        assert!(disabled.start().is_none());
+        assert_eq!(disabled.take_report(), None);
+
+        let timing = FrameTiming::enabled();
+        let mut timings = timing.start().expect("timings");
+        timings.record_draw(Duration::from_micros(10));
+        drop(timings);
+
+        assert_eq!(
+            timing.take_report().as_deref(),
+            Some("frame timing: draw n=1 p50=10us p95=10us p99=10us max=10us; event n=0")
+        );
+        assert_eq!(timing.take_report(), None);
+    }
+
+    #[test]
+    fn summary_reports_count_and_percentiles() {
+        let samples = [Duration::from_micros(10), Duration::from_micros(30)];
+        assert_eq!(
+            summarize(&samples),
+            "n=2 p50=10us p95=30us p99=30us max=30us"
+        );
+        assert_eq!(summarize(&[]), "n=0");
+    }
+}
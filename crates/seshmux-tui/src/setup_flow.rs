@@ -0,0 +1,661 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{List, ListItem, ListState, Paragraph};
+use seshmux_app::{App, ConfigInitRequest};
+use seshmux_core::config::{SessionLayout, WindowSpec};
+use seshmux_core::scaffold::{GlobalConfigScaffold, render_global_config, starter_windows};
+use tui_input::Input;
+use tui_input::backend::crossterm::EventHandler;
+
+use crate::keymap;
+use crate::theme::{self, Accent, Theme};
+use crate::ui::help::{HelpProvider, INPUT_KEYS, ScreenHelp};
+use crate::ui::modal::{render_error_modal, render_input_modal};
+use crate::ui::paste::paste_into;
+use crate::ui::text::{compact_hint, focus_line, key_hint_height, key_hint_paragraph};
+
+pub(crate) trait SetupFlowOps {
+    fn write_config(&self, scaffold: &GlobalConfigScaffold, force: bool) -> Result<PathBuf>;
+}
+
+impl<'a> SetupFlowOps for App<'a> {
+    fn write_config(&self, scaffold: &GlobalConfigScaffold, force: bool) -> Result<PathBuf> {
+        Ok(self.config_init(ConfigInitRequest {
+            scaffold: scaffold.clone(),
+            force,
+        })?)
+    }
+}
+
+const LAYOUTS: [(SessionLayout, &str, &str); 2] = [
+    (
+        SessionLayout::Session,
+        "session",
+        "a tmux session per worktree, holding every window",
+    ),
+    (
+        SessionLayout::Window,
+        "window",
+        "one session per repository with a window per worktree",
+    ),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Editor,
+    Windows,
+    Layout,
+    WorktreesDir,
+    Review,
+    Error,
+}
+
+impl Step {
+    fn number(self) -> usize {
+        match self {
+            Self::Editor => 1,
+            Self::Windows => 2,
+            Self::Layout => 3,
+            Self::WorktreesDir => 4,
+            Self::Review | Self::Error => 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FlowSignal {
+    Continue,
+    Exit(Option<PathBuf>),
+}
+
+#[derive(Debug)]
+struct SetupFlow {
+    step: Step,
+    force: bool,
+    shell: String,
+    editor: Input,
+    windows: Vec<(WindowSpec, bool)>,
+    window_selected: usize,
+    layout_selected: usize,
+    worktrees_dir: Input,
+    input_error: Option<String>,
+    error_message: Option<String>,
+}
+
+pub(crate) struct SetupScreen {
+    flow: SetupFlow,
+}
+
+impl SetupScreen {
+    pub(crate) fn new(defaults: GlobalConfigScaffold, shell: &str, force: bool) -> Self {
+        Self {
+            flow: SetupFlow::new(defaults, shell, force),
+        }
+    }
+
+    pub(crate) fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        self.flow.render(frame, theme);
+    }
+
+    pub(crate) fn on_paste(&mut self, text: &str) {
+        match self.flow.step {
+            Step::Editor => {
+                paste_into(&mut self.flow.editor, text);
+            }
+            Step::WorktreesDir => {
+                paste_into(&mut self.flow.worktrees_dir, text);
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn on_key(&mut self, key: KeyEvent, app: &App<'_>) -> Option<Option<PathBuf>> {
+        match self.flow.on_key(key, app) {
+            FlowSignal::Continue => None,
+            FlowSignal::Exit(path) => Some(path),
+        }
+    }
+}
+
+impl HelpProvider for SetupScreen {
+    fn help(&self) -> ScreenHelp {
+        match self.flow.step {
+            Step::Editor => ScreenHelp::new(
+                "Editor",
+                "The command `seshmux open` runs, and what the editor window starts in each worktree.",
+            )
+            .keys(&[
+                ("Type", "edit the command"),
+                ("Enter", "continue"),
+                ("Esc", "quit the setup without writing anything"),
+            ]),
+            Step::Windows => ScreenHelp::new(
+                "Windows",
+                "The tmux windows every new worktree session opens with. Pick at least one.",
+            )
+            .keys(&[
+                ("Up/Down or j/k", "move"),
+                ("Space", "pick or drop the window"),
+                ("Enter", "continue"),
+                ("Esc", "back"),
+            ]),
+            Step::Layout => ScreenHelp::new(
+                "Layout",
+                "How worktree sessions are arranged in tmux. tmux.layout in config.toml changes it later.",
+            )
+            .keys(&[
+                ("Up/Down or j/k", "move"),
+                ("Enter", "continue"),
+                ("Esc", "back"),
+            ]),
+            Step::WorktreesDir => ScreenHelp::new(
+                "Worktrees directory",
+                "Where worktrees are created, relative to the repository root. {repo} is the repository name.",
+            )
+            .keys(INPUT_KEYS),
+            Step::Review => ScreenHelp::new(
+                "Review",
+                "The config.toml that will be written. Everything can be changed later with `seshmux config edit`.",
+            )
+            .keys(&[("Enter", "write the config"), ("Esc", "back")]),
+            Step::Error => ScreenHelp::new("Setup", "The config could not be written.")
+                .keys(&[("Enter/Esc", "back to the review")]),
+        }
+    }
+
+    fn typing(&self) -> bool {
+        matches!(self.flow.step, Step::Editor | Step::WorktreesDir)
+    }
+}
+
+impl SetupFlow {
+    fn new(defaults: GlobalConfigScaffold, shell: &str, force: bool) -> Self {
+        let picked: Vec<String> = defaults
+            .windows
+            .iter()
+            .map(|window| window.name.clone())
+            .collect();
+        let layout_selected = LAYOUTS
+            .iter()
+            .position(|(layout, _, _)| *layout == defaults.layout)
+            .unwrap_or(0);
+
+        let mut flow = Self {
+            step: Step::Editor,
+            force,
+            shell: shell.to_string(),
+            editor: Input::new(defaults.editor.join(" ")),
+            windows: Vec::new(),
+            window_selected: 0,
+            layout_selected,
+            worktrees_dir: Input::new(defaults.worktrees_dir),
+            input_error: None,
+            error_message: None,
+        };
+        flow.offer_windows(&defaults.editor, &picked);
+        flow
+    }
+
+    fn editor_command(&self) -> Vec<String> {
+        self.editor
+            .value()
+            .split_whitespace()
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn offer_windows(&mut self, editor: &[String], picked: &[String]) {
+        self.windows = starter_windows(editor, &self.shell)
+            .into_iter()
+            .map(|window| {
+                let chosen = picked.contains(&window.name);
+                (window, chosen)
+            })
+            .collect();
+        self.window_selected = self
+            .window_selected
+            .min(self.windows.len().saturating_sub(1));
+    }
+
+    fn picked_names(&self) -> Vec<String> {
+        self.windows
+            .iter()
+            .filter(|(_, picked)| *picked)
+            .map(|(window, _)| window.name.clone())
+            .collect()
+    }
+
+    fn scaffold(&self) -> GlobalConfigScaffold {
+        GlobalConfigScaffold {
+            editor: self.editor_command(),
+            windows: self
+                .windows
+                .iter()
+                .filter(|(_, picked)| *picked)
+                .map(|(window, _)| window.clone())
+                .collect(),
+            layout: LAYOUTS[self.layout_selected].0,
+            worktrees_dir: self.worktrees_dir.value().trim().to_string(),
+        }
+    }
+
+    fn on_key(&mut self, key: KeyEvent, ops: &dyn SetupFlowOps) -> FlowSignal {
+        match self.step {
+            Step::Editor => return self.on_key_editor(key),
+            Step::Windows => self.on_key_windows(key),
+            Step::Layout => self.on_key_layout(key),
+            Step::WorktreesDir => self.on_key_worktrees_dir(key),
+            Step::Review => return self.on_key_review(key, ops),
+            Step::Error => {
+                if keymap::is_back(key) || keymap::is_confirm(key) {
+                    self.error_message = None;
+                    self.step = Step::Review;
+                }
+            }
+        }
+        FlowSignal::Continue
+    }
+
+    fn on_key_editor(&mut self, key: KeyEvent) -> FlowSignal {
+        if keymap::is_back(key) {
+            return FlowSignal::Exit(None);
+        }
+
+        if keymap::is_confirm(key) {
+            let editor = self.editor_command();
+            if editor.is_empty() {
+                self.input_error = Some("Enter the command that opens your editor".to_string());
+                return FlowSignal::Continue;
+            }
+            let picked = self.picked_names();
+            self.offer_windows(&editor, &picked);
+            self.input_error = None;
+            self.step = Step::Windows;
+            return FlowSignal::Continue;
+        }
+
+        if self.editor.handle_event(&Event::Key(key)).is_some() {
+            self.input_error = None;
+        }
+        FlowSignal::Continue
+    }
+
+    fn on_key_windows(&mut self, key: KeyEvent) {
+        if keymap::is_back(key) {
+            self.input_error = None;
+            self.step = Step::Editor;
+        } else if keymap::is_up(key) {
+            self.window_selected = self.window_selected.saturating_sub(1);
+        } else if keymap::is_down(key) {
+            if self.window_selected + 1 < self.windows.len() {
+                self.window_selected += 1;
+            }
+        } else if keymap::is_toggle(key) {
+            if let Some((_, picked)) = self.windows.get_mut(self.window_selected) {
+                *picked = !*picked;
+                self.input_error = None;
+            }
+        } else if keymap::is_confirm(key) {
+            if self.windows.iter().any(|(_, picked)| *picked) {
+                self.input_error = None;
+                self.step = Step::Layout;
+            } else {
+                self.input_error = Some("Pick at least one window with Space".to_string());
+            }
+        }
+    }
+
+    fn on_key_layout(&mut self, key: KeyEvent) {
+        if keymap::is_back(key) {
+            self.step = Step::Windows;
+        } else if keymap::is_up(key) {
+            self.layout_selected = self.layout_selected.saturating_sub(1);
+        } else if keymap::is_down(key) {
+            self.layout_selected = (self.layout_selected + 1).min(LAYOUTS.len() - 1);
+        } else if keymap::is_confirm(key) {
+            self.step = Step::WorktreesDir;
+        }
+    }
+
+    fn on_key_worktrees_dir(&mut self, key: KeyEvent) {
+        if keymap::is_back(key) {
+            self.input_error = None;
+            self.step = Step::Layout;
+            return;
+        }
+
+        if keymap::is_confirm(key) {
+            if self.worktrees_dir.value().trim().is_empty() {
+                self.input_error = Some("Enter a directory, such as worktrees".to_string());
+            } else {
+                self.input_error = None;
+                self.step = Step::Review;
+            }
+            return;
+        }
+
+        if self.worktrees_dir.handle_event(&Event::Key(key)).is_some() {
+            self.input_error = None;
+        }
+    }
+
+    fn on_key_review(&mut self, key: KeyEvent, ops: &dyn SetupFlowOps) -> FlowSignal {
+        if keymap::is_back(key) {
+            self.step = Step::WorktreesDir;
+        } else if keymap::is_confirm(key) {
+            match ops.write_config(&self.scaffold(), self.force) {
+                Ok(path) => return FlowSignal::Exit(Some(path)),
+                Err(error) => {
+                    self.error_message = Some(format!("{error:#}"));
+                    self.step = Step::Error;
+                }
+            }
+        }
+        FlowSignal::Continue
+    }
+
+    fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        let area = frame.area();
+        let key_text = match self.step {
+            Step::Editor | Step::WorktreesDir => "Enter: continue    Esc: back",
+            Step::Windows => compact_hint(
+                area.width,
+                "Space: pick    Up/Down or j/k: move    Enter: continue    Esc: back",
+                "Space: pick    j/k: move    Enter: continue    Esc: back",
+                "Space pick | Enter next | Esc back",
+            ),
+            Step::Layout => compact_hint(
+                area.width,
+                "Up/Down or j/k: move    Enter: continue    Esc: back",
+                "j/k: move    Enter: continue    Esc: back",
+                "Enter next | Esc back",
+            ),
+            Step::Review | Step::Error => "Enter: write config.toml    Esc: back",
+        };
+        let footer_height = key_hint_height(area.width, key_text);
+        let [body, status, footer] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(6),
+                Constraint::Length(1),
+                Constraint::Length(footer_height),
+            ])
+            .areas(area);
+
+        let block = theme::chrome(focus_line(
+            theme,
+            format!(
+                "seshmux setup ({} of 5): no config.toml yet, so let's write one",
+                self.step.number()
+            ),
+        ));
+        match self.step {
+            Step::Windows => {
+                let items: Vec<ListItem<'_>> = self
+                    .windows
+                    .iter()
+                    .map(|window| window_item(theme, window))
+                    .collect();
+                let list = List::new(items)
+                    .block(block)
+                    .highlight_style(theme.table_highlight(Accent::Manage));
+                let mut state = ListState::default();
+                state.select(Some(self.window_selected));
+                frame.render_stateful_widget(list, body, &mut state);
+            }
+            Step::Layout => {
+                let items: Vec<ListItem<'_>> = LAYOUTS
+                    .iter()
+                    .map(|(_, name, description)| {
+                        ListItem::new(Line::from(vec![
+                            Span::raw(name.to_string()),
+                            Span::styled(format!("  {description}"), theme.secondary_text()),
+                        ]))
+                    })
+                    .collect();
+                let list = List::new(items)
+                    .block(block)
+                    .highlight_style(theme.table_highlight(Accent::Manage));
+                let mut state = ListState::default();
+                state.select(Some(self.layout_selected));
+                frame.render_stateful_widget(list, body, &mut state);
+            }
+            Step::Review | Step::Error => {
+                let preview =
+                    Paragraph::new(Text::raw(render_global_config(&self.scaffold()))).block(block);
+                frame.render_widget(preview, body);
+            }
+            Step::Editor | Step::WorktreesDir => {
+                frame.render_widget(Paragraph::new(self.summary()).block(block), body);
+            }
+        }
+
+        let status_line = match (&self.input_error, self.step) {
+            (Some(error), Step::Windows) => {
+                Line::from(Span::styled(format!(" {error}"), theme.error_prompt()))
+            }
+            _ => Line::from(Span::styled(
+                " Everything here can be changed later with seshmux config edit",
+                theme.secondary_text(),
+            )),
+        };
+        frame.render_widget(Paragraph::new(status_line), status);
+
+        let keys = key_hint_paragraph(key_text).block(theme::key_block());
+        frame.render_widget(keys, footer);
+
+        match self.step {
+            Step::Editor => render_input_modal(
+                frame,
+                theme,
+                "Editor command (e.g. nvim, hx, code --wait)",
+                &self.editor,
+                self.input_error.as_deref(),
+            ),
+            Step::WorktreesDir => render_input_modal(
+                frame,
+                theme,
+                "Worktrees directory, relative to the repository ({repo} is its name)",
+                &self.worktrees_dir,
+                self.input_error.as_deref(),
+            ),
+            Step::Error => {
+                let message = self
+                    .error_message
+                    .as_deref()
+                    .unwrap_or("Writing the config failed");
+                render_error_modal(frame, theme, message, 80, 40, "Enter/Esc: back");
+            }
+            _ => {}
+        }
+    }
+
+    fn summary(&self) -> Text<'static> {
+        let picked = self.picked_names();
+        Text::from(vec![
+            Line::from(format!("Editor: {}", self.editor.value())),
+            Line::from(format!("Windows: {}", picked.join(", "))),
+            Line::from(format!("Layout: {}", LAYOUTS[self.layout_selected].1)),
+            Line::from(format!(
+                "Worktrees directory: {}",
+                self.worktrees_dir.value()
+            )),
+        ])
+    }
+}
+
+fn window_item(theme: &Theme, (window, picked): &(WindowSpec, bool)) -> ListItem<'static> {
+    let mark = if *picked { "[x]" } else { "[ ]" };
+    let mut command = window.program.clone().unwrap_or_default();
+    if let Some(args) = &window.args
+        && !args.is_empty()
+    {
+        command = format!("{command} {}", args.join(" "));
+    }
+
+    ListItem::new(Line::from(vec![
+        Span::raw(format!("{mark} {}", window.name)),
+        Span::styled(format!("  {command}"), theme.secondary_text()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::path::PathBuf;
+
+    use anyhow::{Result, bail};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use seshmux_core::config::SessionLayout;
+    use seshmux_core::scaffold::GlobalConfigScaffold;
+
+    use super::{FlowSignal, SetupFlow, SetupFlowOps, Step};
+    use crate::theme::Theme;
+
+    #[derive(Default)]
+    struct FakeOps {
+        written: RefCell<Vec<(GlobalConfigScaffold, bool)>>,
+        fail: bool,
+    }
+
+    impl SetupFlowOps for FakeOps {
+        fn write_config(&self, scaffold: &GlobalConfigScaffold, force: bool) -> Result<PathBuf> {
+            if self.fail {
+                bail!("/home/me/.config/seshmux/config.toml already exists");
+            }
+            self.written.borrow_mut().push((scaffold.clone(), force));
+            Ok(PathBuf::from("/home/me/.config/seshmux/config.toml"))
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn press(flow: &mut SetupFlow, ops: &FakeOps, codes: &[KeyCode]) -> FlowSignal {
+        let mut signal = FlowSignal::Continue;
+        for code in codes {
+            signal = flow.on_key(key(*code), ops);
+        }
+        signal
+    }
+
+    fn type_text(flow: &mut SetupFlow, ops: &FakeOps, text: &str) {
+        for character in text.chars() {
+            flow.on_key(key(KeyCode::Char(character)), ops);
+        }
+    }
+
+    fn render_output(flow: &SetupFlow, width: u16, height: u16) -> String {
+        let theme = &Theme::default();
+        let backend = TestBackend::new(width, height);
+        let mut terminal = Terminal::new(backend).expect("terminal");
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render setup flow");
+        format!("{}", terminal.backend())
+    }
+
+    #[test]
+    fn answers_are_written_as_the_config() {
+        let ops = FakeOps::default();
+        let defaults = GlobalConfigScaffold::detected(Some("vim"), "/bin/zsh");
+        let mut flow = SetupFlow::new(defaults, "/bin/zsh", false);
+
+        press(&mut flow, &ops, &[KeyCode::Backspace; 3]);
+        type_text(&mut flow, &ops, "hx");
+        press(&mut flow, &ops, &[KeyCode::Enter]);
+        assert_eq!(flow.step, Step::Windows);
+        assert!(render_output(&flow, 120, 16).contains("[x] editor  hx ."));
+
+        press(
+            &mut flow,
+            &ops,
+            &[
+                KeyCode::Down,
+                KeyCode::Char(' '),
+                KeyCode::Down,
+                KeyCode::Char(' '),
+                KeyCode::Enter,
+            ],
+        );
+        assert_eq!(flow.step, Step::Layout);
+        press(&mut flow, &ops, &[KeyCode::Down, KeyCode::Enter]);
+
+        press(&mut flow, &ops, &[KeyCode::Backspace; 9]);
+        type_text(&mut flow, &ops, "../{repo}-wt");
+        press(&mut flow, &ops, &[KeyCode::Enter]);
+        assert_eq!(flow.step, Step::Review);
+        let output = render_output(&flow, 120, 30);
+        assert!(output.contains("editor = [\"hx\"]"));
+        assert!(output.contains("layout = \"window\""));
+
+        let signal = press(&mut flow, &ops, &[KeyCode::Enter]);
+        assert_eq!(
+            signal,
+            FlowSignal::Exit(Some(PathBuf::from("/home/me/.config/seshmux/config.toml")))
+        );
+        let written = ops.written.borrow();
+        let (scaffold, force) = &written[0];
+        assert!(!force);
+        assert_eq!(scaffold.editor, vec!["hx"]);
+        let names: Vec<&str> = scaffold.windows.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, vec!["editor", "git"]);
+        assert_eq!(scaffold.layout, SessionLayout::Window);
+        assert_eq!(scaffold.worktrees_dir, "../{repo}-wt");
+    }
+
+    #[test]
+    fn empty_answers_stay_on_their_step_and_failures_return_to_the_review() {
+        let ops = FakeOps {
+            fail: true,
+            ..FakeOps::default()
+        };
+        let defaults = GlobalConfigScaffold::detected(None, "/bin/sh");
+        let mut flow = SetupFlow::new(defaults, "/bin/sh", false);
+
+        press(&mut flow, &ops, &[KeyCode::Backspace, KeyCode::Backspace]);
+        press(&mut flow, &ops, &[KeyCode::Enter]);
+        assert_eq!(flow.step, Step::Editor);
+        assert!(flow.input_error.is_some());
+
+        type_text(&mut flow, &ops, "nvim");
+        press(
+            &mut flow,
+            &ops,
+            &[
+                KeyCode::Enter,
+                KeyCode::Char(' '),
+                KeyCode::Down,
+                KeyCode::Char(' '),
+                KeyCode::Enter,
+            ],
+        );
+        assert_eq!(flow.step, Step::Windows);
+        assert!(render_output(&flow, 120, 16).contains("Pick at least one window"));
+
+        press(
+            &mut flow,
+            &ops,
+            &[KeyCode::Char(' '), KeyCode::Enter, KeyCode::Enter],
+        );
+        press(&mut flow, &ops, &[KeyCode::Enter, KeyCode::Enter]);
+        assert_eq!(flow.step, Step::Error);
+        press(&mut flow, &ops, &[KeyCode::Esc]);
+        assert_eq!(flow.step, Step::Review);
+
+        press(&mut flow, &ops, &[KeyCode::Esc, KeyCode::Esc, KeyCode::Esc]);
+        assert_eq!(flow.step, Step::Windows);
+        press(&mut flow, &ops, &[KeyCode::Esc]);
+        assert_eq!(
+            press(&mut flow, &ops, &[KeyCode::Esc]),
+            FlowSignal::Exit(None)
+        );
+    }
+}
@@ -0,0 +1,530 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{List, ListItem, ListState, Paragraph};
+use seshmux_app::{App, SkipRule, SkipRules};
+use tui_input::Input;
+use tui_input::backend::crossterm::EventHandler;
+
+use crate::UiExit;
+use crate::keymap;
+use crate::theme::{self, Accent, Theme};
+use crate::ui::help::{HelpProvider, INPUT_KEYS, ScreenHelp};
+use crate::ui::modal::{render_error_modal, render_input_modal};
+use crate::ui::paste::paste_into;
+use crate::ui::text::{compact_hint, focus_line, key_hint_height, key_hint_paragraph};
+
+pub(crate) trait SkipRulesFlowOps {
+    fn load_rules(&self, cwd: &Path) -> Result<SkipRules>;
+    fn add_rule(&self, cwd: &Path, bucket: &str) -> Result<SkipRules>;
+    fn remove_rule(&self, cwd: &Path, bucket: &str) -> Result<SkipRules>;
+    fn replace_rule(&self, cwd: &Path, from: &str, to: &str) -> Result<SkipRules>;
+}
+
+impl<'a> SkipRulesFlowOps for App<'a> {
+    fn load_rules(&self, cwd: &Path) -> Result<SkipRules> {
+        Ok(self.skip_rules(cwd)?)
+    }
+
+    fn add_rule(&self, cwd: &Path, bucket: &str) -> Result<SkipRules> {
+        Ok(self.add_skip_rule(cwd, bucket)?)
+    }
+
+    fn remove_rule(&self, cwd: &Path, bucket: &str) -> Result<SkipRules> {
+        Ok(self.remove_skip_rule(cwd, bucket)?)
+    }
+
+    fn replace_rule(&self, cwd: &Path, from: &str, to: &str) -> Result<SkipRules> {
+        Ok(self.replace_skip_rule(cwd, from, to)?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Rules,
+    Input(Option<String>),
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlowSignal {
+    Continue,
+    Exit(UiExit),
+}
+
+#[derive(Debug)]
+struct SkipRulesFlow {
+    cwd: PathBuf,
+    step: Step,
+    rules: SkipRules,
+    selected: usize,
+    input: Input,
+    input_error: Option<String>,
+    error_message: Option<String>,
+}
+
+pub(crate) struct SkipRulesScreen {
+    flow: SkipRulesFlow,
+}
+
+impl SkipRulesScreen {
+    pub(crate) fn new(app: &App<'_>, cwd: &Path) -> Result<Self> {
+        Ok(Self {
+            flow: SkipRulesFlow::new(app, cwd)?,
+        })
+    }
+
+    pub(crate) fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        self.flow.render(frame, theme);
+    }
+
+    pub(crate) fn on_paste(&mut self, text: &str) {
+        if matches!(self.flow.step, Step::Input(_)) {
+            paste_into(&mut self.flow.input, text);
+        }
+    }
+
+    pub(crate) fn on_key(&mut self, key: KeyEvent, app: &App<'_>) -> Result<Option<UiExit>> {
+        match self.flow.on_key(key, app)? {
+            FlowSignal::Continue => Ok(None),
+            FlowSignal::Exit(exit) => Ok(Some(exit)),
+        }
+    }
+}
+
+impl HelpProvider for SkipRulesScreen {
+    fn help(&self) -> ScreenHelp {
+        match &self.flow.step {
+            Step::Rules => ScreenHelp::new(
+                "Skip rules",
+                "Directories left out of the extras list when creating a worktree. Rules from config.toml are read-only here.",
+            )
+            .keys(&[
+                ("Up/Down or j/k", "move"),
+                ("a", "add a rule"),
+                ("Enter/e", "edit the rule"),
+                ("d/Delete", "remove the rule"),
+                ("r", "reload the rules"),
+                ("Esc/q", "back"),
+            ]),
+            Step::Input(editing) => {
+                let title = if editing.is_some() {
+                    "Edit skip rule"
+                } else {
+                    "Add skip rule"
+                };
+                ScreenHelp::new(title, "A directory path relative to the repository root.")
+                    .keys(INPUT_KEYS)
+            }
+            Step::Error => ScreenHelp::new("Skip rules", "The rule could not be changed.")
+                .keys(&[("Enter/Esc", "back to the rules")]),
+        }
+    }
+
+    fn typing(&self) -> bool {
+        matches!(self.flow.step, Step::Input(_))
+    }
+}
+
+impl SkipRulesFlow {
+    fn new(ops: &dyn SkipRulesFlowOps, cwd: &Path) -> Result<Self> {
+        Ok(Self {
+            cwd: cwd.to_path_buf(),
+            step: Step::Rules,
+            rules: ops.load_rules(cwd)?,
+            selected: 0,
+            input: Input::default(),
+            input_error: None,
+            error_message: None,
+        })
+    }
+
+    fn selected_rule(&self) -> Option<&SkipRule> {
+        self.rules.rules.get(self.selected)
+    }
+
+    fn replace_rules(&mut self, rules: SkipRules, focus: Option<&str>) {
+        self.rules = rules;
+        if let Some(index) = focus.and_then(|bucket| {
+            self.rules
+                .rules
+                .iter()
+                .position(|rule| rule.bucket == bucket)
+        }) {
+            self.selected = index;
+        }
+        self.selected = self.selected.min(self.rules.rules.len().saturating_sub(1));
+    }
+
+    fn on_key(&mut self, key: KeyEvent, ops: &dyn SkipRulesFlowOps) -> Result<FlowSignal> {
+        match self.step.clone() {
+            Step::Rules => self.on_key_rules(key, ops),
+            Step::Input(editing) => {
+                self.on_key_input(key, editing, ops);
+                Ok(FlowSignal::Continue)
+            }
+            Step::Error => {
+                if keymap::is_back(key) || keymap::is_confirm(key) {
+                    self.error_message = None;
+                    self.step = Step::Rules;
+                }
+                Ok(FlowSignal::Continue)
+            }
+        }
+    }
+
+    fn on_key_rules(&mut self, key: KeyEvent, ops: &dyn SkipRulesFlowOps) -> Result<FlowSignal> {
+        if keymap::is_back(key) || keymap::is_quit(key) {
+            return Ok(FlowSignal::Exit(UiExit::BackAtRoot));
+        }
+
+        if keymap::is_up(key) {
+            self.selected = self.selected.saturating_sub(1);
+        } else if keymap::is_down(key) {
+            if self.selected + 1 < self.rules.rules.len() {
+                self.selected += 1;
+            }
+        } else if key.code == KeyCode::Char('a') {
+            self.open_input(None);
+        } else if keymap::is_confirm(key) || key.code == KeyCode::Char('e') {
+            if let Some(rule) = self.selected_rule().cloned() {
+                if rule.source.editable() {
+                    self.open_input(Some(rule.bucket));
+                } else {
+                    self.show_read_only(&rule);
+                }
+            }
+        } else if matches!(key.code, KeyCode::Char('d') | KeyCode::Delete) {
+            if let Some(rule) = self.selected_rule().cloned() {
+                if rule.source.editable() {
+                    match ops.remove_rule(&self.cwd, &rule.bucket) {
+                        Ok(rules) => self.replace_rules(rules, None),
+                        Err(error) => {
+                            self.error_message = Some(format!("{error:#}"));
+                            self.step = Step::Error;
+                        }
+                    }
+                } else {
+                    self.show_read_only(&rule);
+                }
+            }
+        } else if key.code == KeyCode::Char('r') {
+            let rules = ops.load_rules(&self.cwd)?;
+            self.replace_rules(rules, None);
+        }
+
+        Ok(FlowSignal::Continue)
+    }
+
+    fn show_read_only(&mut self, rule: &SkipRule) {
+        self.error_message = Some(format!(
+            "'{}' comes from [extras] always_skip_buckets in {}. Edit that file to change it.",
+            rule.bucket,
+            rule.source.label()
+        ));
+        self.step = Step::Error;
+    }
+
+    fn open_input(&mut self, editing: Option<String>) {
+        self.input = Input::new(editing.clone().unwrap_or_default());
+        self.input_error = None;
+        self.step = Step::Input(editing);
+    }
+
+    fn on_key_input(&mut self, key: KeyEvent, editing: Option<String>, ops: &dyn SkipRulesFlowOps) {
+        if keymap::is_back(key) {
+            self.step = Step::Rules;
+            return;
+        }
+
+        if keymap::is_confirm(key) {
+            let bucket = self.input.value().trim().to_string();
+            let result = match &editing {
+                Some(from) if *from == bucket => {
+                    self.step = Step::Rules;
+                    return;
+                }
+                Some(from) => ops.replace_rule(&self.cwd, from, &bucket),
+                None => ops.add_rule(&self.cwd, &bucket),
+            };
+            match result {
+                Ok(rules) => {
+                    self.replace_rules(rules, Some(&bucket));
+                    self.step = Step::Rules;
+                }
+                Err(error) => self.input_error = Some(format!("{error:#}")),
+            }
+            return;
+        }
+
+        if self.input.handle_event(&Event::Key(key)).is_some() {
+            self.input_error = None;
+        }
+    }
+
+    fn render(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        self.render_rules(frame, theme);
+        match &self.step {
+            Step::Rules => {}
+            Step::Input(editing) => {
+                let title = if editing.is_some() {
+                    "Edit skip bucket"
+                } else {
+                    "Add skip bucket (repository-relative path)"
+                };
+                render_input_modal(
+                    frame,
+                    theme,
+                    title,
+                    &self.input,
+                    self.input_error.as_deref(),
+                );
+            }
+            Step::Error => {
+                let message = self
+                    .error_message
+                    .as_deref()
+                    .unwrap_or("Skip rule update failed");
+                render_error_modal(frame, theme, message, 80, 40, "Enter/Esc: back");
+            }
+        }
+    }
+
+    fn render_rules(&self, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
+        let area = frame.area();
+        let key_text = compact_hint(
+            area.width,
+            "a: add    Enter/e: edit    d: remove    Up/Down or j/k: move    r: reload    Esc: back",
+            "a: add    e: edit    d: remove    j/k: move    r: reload    Esc: back",
+            "a add | e edit | d remove | Esc back",
+        );
+        let footer_height = key_hint_height(area.width, key_text);
+        let [body, registry, footer] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(6),
+                Constraint::Length(1),
+                Constraint::Length(footer_height),
+            ])
+            .areas(area);
+
+        let block = theme::chrome(focus_line(theme, "Extras always-skip buckets"));
+        if self.rules.rules.is_empty() {
+            let empty = Paragraph::new(
+                "No buckets are skipped. Press a to skip a directory, such as node_modules, whenever extras are indexed.",
+            )
+            .block(block);
+            frame.render_widget(empty, body);
+        } else {
+            let items: Vec<ListItem<'_>> = self
+                .rules
+                .rules
+                .iter()
+                .map(|rule| rule_item(theme, rule))
+                .collect();
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(theme.table_highlight(Accent::Manage));
+            let mut state = ListState::default();
+            state.select(Some(self.selected));
+            frame.render_stateful_widget(list, body, &mut state);
+        }
+
+        let registry_line = Paragraph::new(Line::from(Span::styled(
+            format!(" Saved in {}", self.rules.registry_path.display()),
+            theme.secondary_text(),
+        )));
+        frame.render_widget(registry_line, registry);
+
+        let keys = key_hint_paragraph(key_text).block(theme::key_block());
+        frame.render_widget(keys, footer);
+    }
+}
+
+fn rule_item(theme: &Theme, rule: &SkipRule) -> ListItem<'static> {
+    let source = if rule.source.editable() {
+        rule.source.label().to_string()
+    } else {
+        format!("{} (read-only)", rule.source.label())
+    };
+
+    ListItem::new(Line::from(vec![
+        Span::raw(rule.bucket.clone()),
+        Span::styled(format!("  {source}"), theme.secondary_text()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::BTreeSet;
+    use std::path::{Path, PathBuf};
+
+    use anyhow::{Result, anyhow, bail};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use seshmux_app::{SkipRule, SkipRuleSource, SkipRules};
+
+    use super::{FlowSignal, SkipRulesFlow, SkipRulesFlowOps, Step};
+    use crate::theme::Theme;
+
+    struct FakeOps {
+        registry: RefCell<BTreeSet<String>>,
+        repo_config: Vec<String>,
+    }
+
+    impl FakeOps {
+        fn new(registry: &[&str], repo_config: &[&str]) -> Self {
+            Self {
+                registry: RefCell::new(registry.iter().map(|value| value.to_string()).collect()),
+                repo_config: repo_config.iter().map(|value| value.to_string()).collect(),
+            }
+        }
+
+        fn rules(&self) -> SkipRules {
+            let mut rules: Vec<SkipRule> = self
+                .registry
+                .borrow()
+                .iter()
+                .map(|bucket| SkipRule {
+                    bucket: bucket.clone(),
+                    source: SkipRuleSource::Registry,
+                })
+                .collect();
+            rules.extend(self.repo_config.iter().map(|bucket| SkipRule {
+                bucket: bucket.clone(),
+                source: SkipRuleSource::RepoConfig,
+            }));
+            SkipRules {
+                repo_root: PathBuf::from("/tmp/repo"),
+                registry_path: PathBuf::from("/tmp/repo/worktrees/worktree.toml"),
+                rules,
+            }
+        }
+    }
+
+    impl SkipRulesFlowOps for FakeOps {
+        fn load_rules(&self, _cwd: &Path) -> Result<SkipRules> {
+            Ok(self.rules())
+        }
+
+        fn add_rule(&self, _cwd: &Path, bucket: &str) -> Result<SkipRules> {
+            if bucket.is_empty() {
+                bail!("'' is not a repository-relative path");
+            }
+            self.registry.borrow_mut().insert(bucket.to_string());
+            Ok(self.rules())
+        }
+
+        fn remove_rule(&self, _cwd: &Path, bucket: &str) -> Result<SkipRules> {
+            if !self.registry.borrow_mut().remove(bucket) {
+                return Err(anyhow!("'{bucket}' is not an always-skip bucket"));
+            }
+            Ok(self.rules())
+        }
+
+        fn replace_rule(&self, _cwd: &Path, from: &str, to: &str) -> Result<SkipRules> {
+            self.registry.borrow_mut().remove(from);
+            self.registry.borrow_mut().insert(to.to_string());
+            Ok(self.rules())
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(flow: &mut SkipRulesFlow, ops: &FakeOps, text: &str) {
+        for character in text.chars() {
+            flow.on_key(key(KeyCode::Char(character)), ops)
+                .expect("type");
+        }
+    }
+
+    fn render_output(flow: &SkipRulesFlow, width: u16, height: u16) -> String {
+        let theme = &Theme::default();
+        let backend = TestBackend::new(width, height);
+        let mut terminal = Terminal::new(backend).expect("terminal");
+        terminal
+            .draw(|frame| flow.render(frame, theme))
+            .expect("render skip rules flow");
+        format!("{}", terminal.backend())
+    }
+
+    #[test]
+    fn add_edit_and_remove_write_through_the_ops() {
+        let ops = FakeOps::new(&["node_modules"], &[]);
+        let mut flow = SkipRulesFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+
+        flow.on_key(key(KeyCode::Char('a')), &ops).expect("add");
+        type_text(&mut flow, &ops, "dist");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("submit");
+        assert_eq!(flow.step, Step::Rules);
+        assert_eq!(flow.selected_rule().expect("rule").bucket, "dist");
+
+        flow.on_key(key(KeyCode::Char('e')), &ops).expect("edit");
+        assert_eq!(flow.step, Step::Input(Some("dist".to_string())));
+        flow.on_key(key(KeyCode::Backspace), &ops)
+            .expect("backspace");
+        flow.on_key(key(KeyCode::Backspace), &ops)
+            .expect("backspace");
+        type_text(&mut flow, &ops, "sk");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("submit");
+        assert_eq!(
+            *ops.registry.borrow(),
+            BTreeSet::from(["disk".to_string(), "node_modules".to_string()])
+        );
+
+        flow.on_key(key(KeyCode::Char('d')), &ops).expect("remove");
+        assert_eq!(
+            *ops.registry.borrow(),
+            BTreeSet::from(["node_modules".to_string()])
+        );
+        assert_eq!(flow.selected, 0);
+    }
+
+    #[test]
+    fn invalid_input_stays_open_with_the_error() {
+        let ops = FakeOps::new(&[], &[]);
+        let mut flow = SkipRulesFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+
+        let output = render_output(&flow, 140, 16);
+        assert!(output.contains("No buckets are skipped."));
+
+        flow.on_key(key(KeyCode::Char('a')), &ops).expect("add");
+        flow.on_key(key(KeyCode::Enter), &ops).expect("submit");
+        assert_eq!(flow.step, Step::Input(None));
+        assert!(
+            flow.input_error
+                .as_deref()
+                .is_some_and(|error| error.contains("repository-relative"))
+        );
+
+        flow.on_key(key(KeyCode::Esc), &ops).expect("cancel");
+        let signal = flow.on_key(key(KeyCode::Esc), &ops).expect("esc");
+        assert_eq!(signal, FlowSignal::Exit(super::UiExit::BackAtRoot));
+    }
+
+    #[test]
+    fn repo_config_rules_are_shown_read_only() {
+        let ops = FakeOps::new(&[], &["vendor"]);
+        let mut flow = SkipRulesFlow::new(&ops, Path::new("/tmp/repo")).expect("flow");
+
+        let output = render_output(&flow, 140, 16);
+        assert!(output.contains("vendor  .seshmux.toml (read-only)"));
+        assert!(output.contains("Saved in /tmp/repo/worktrees/worktree.toml"));
+
+        flow.on_key(key(KeyCode::Char('d')), &ops).expect("remove");
+        assert_eq!(flow.step, Step::Error);
+        assert!(
+            flow.error_message
+                .as_deref()
+                .is_some_and(|message| message.contains("Edit that file"))
+        );
+        assert_eq!(ops.rules().rules.len(), 1);
+    }
+}
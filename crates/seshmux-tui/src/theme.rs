@@ -1,42 +1,231 @@
+use std::str::FromStr;
+
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::Line;
 use ratatui::widgets::{Block, Borders};
+use seshmux_core::config::{ThemeConfig, ThemePreset};
 
-pub(crate) fn chrome<'a>(title: impl Into<Line<'a>>) -> Block<'a> {
-    Block::default().borders(Borders::ALL).title(title)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Accent {
+    Browse,
+    Create,
+    Delete,
+    Attach,
+    Manage,
+    Extras,
 }
 
-pub(crate) fn key_block() -> Block<'static> {
-    chrome("Keys")
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Theme {
+    browse: Color,
+    create: Color,
+    delete: Color,
+    attach: Color,
+    manage: Color,
+    extras: Color,
+    selected_text: Color,
+    highlight: Option<Color>,
+    header: Option<Color>,
+    focus: Color,
+    success: Color,
+    error: Color,
+    secondary: Color,
+    dim_secondary: bool,
+    modal_border: Color,
 }
 
-pub(crate) fn table_header(color: Color) -> Style {
-    Style::default().fg(color).add_modifier(Modifier::BOLD)
-}
+const DARK: Theme = Theme {
+    browse: Color::Cyan,
+    create: Color::Green,
+    delete: Color::Red,
+    attach: Color::Yellow,
+    manage: Color::Magenta,
+    extras: Color::Blue,
+    selected_text: Color::Black,
+    highlight: None,
+    header: None,
+    focus: Color::Blue,
+    success: Color::Green,
+    error: Color::Red,
+    secondary: Color::Gray,
+    dim_secondary: true,
+    modal_border: Color::Reset,
+};
+
+const LIGHT: Theme = Theme {
+    browse: Color::Blue,
+    create: Color::Green,
+    delete: Color::Red,
+    attach: Color::Magenta,
+    manage: Color::DarkGray,
+    extras: Color::Blue,
+    selected_text: Color::White,
+    highlight: None,
+    header: None,
+    focus: Color::Blue,
+    success: Color::Green,
+    error: Color::Red,
+    secondary: Color::DarkGray,
+    dim_secondary: false,
+    modal_border: Color::DarkGray,
+};
 
-pub(crate) fn table_highlight(color: Color) -> Style {
-    Style::default()
-        .fg(Color::Black)
-        .bg(color)
-        .add_modifier(Modifier::BOLD)
+const HIGH_CONTRAST: Theme = Theme {
+    browse: Color::White,
+    create: Color::LightGreen,
+    delete: Color::LightRed,
+    attach: Color::LightYellow,
+    manage: Color::LightCyan,
+    extras: Color::LightBlue,
+    selected_text: Color::Black,
+    highlight: None,
+    header: None,
+    focus: Color::LightYellow,
+    success: Color::LightGreen,
+    error: Color::LightRed,
+    secondary: Color::White,
+    dim_secondary: false,
+    modal_border: Color::White,
+};
+
+// Okabe-Ito: sky blue, blue, vermillion, orange, reddish purple, and bluish green. Success uses
+// blue rather than green so it never depends on telling red from green.
+const COLORBLIND_SAFE: Theme = Theme {
+    browse: Color::Rgb(86, 180, 233),
+    create: Color::Rgb(0, 114, 178),
+    delete: Color::Rgb(213, 94, 0),
+    attach: Color::Rgb(230, 159, 0),
+    manage: Color::Rgb(204, 121, 167),
+    extras: Color::Rgb(0, 158, 115),
+    selected_text: Color::Black,
+    highlight: None,
+    header: None,
+    focus: Color::Rgb(86, 180, 233),
+    success: Color::Rgb(0, 114, 178),
+    error: Color::Rgb(213, 94, 0),
+    secondary: Color::Gray,
+    dim_secondary: true,
+    modal_border: Color::Reset,
+};
+
+impl Default for Theme {
+    fn default() -> Self {
+        DARK
+    }
 }
 
-pub(crate) fn focus_prompt() -> Style {
-    Style::default()
-        .fg(Color::Blue)
-        .add_modifier(Modifier::BOLD)
+impl Theme {
+    pub(crate) fn from_config(config: &ThemeConfig) -> Self {
+        let mut theme = match config.preset {
+            ThemePreset::Dark => DARK,
+            ThemePreset::Light => LIGHT,
+            ThemePreset::HighContrast => HIGH_CONTRAST,
+            ThemePreset::ColorblindSafe => COLORBLIND_SAFE,
+        };
+        let parse = |value: &Option<String>| {
+            value
+                .as_deref()
+                .and_then(|value| Color::from_str(value.trim()).ok())
+        };
+        theme.highlight = parse(&config.highlight).or(theme.highlight);
+        theme.header = parse(&config.header).or(theme.header);
+        theme.error = parse(&config.error).unwrap_or(theme.error);
+        theme.modal_border = parse(&config.modal_border).unwrap_or(theme.modal_border);
+        theme
+    }
+
+    fn accent(&self, accent: Accent) -> Color {
+        match accent {
+            Accent::Browse => self.browse,
+            Accent::Create => self.create,
+            Accent::Delete => self.delete,
+            Accent::Attach => self.attach,
+            Accent::Manage => self.manage,
+            Accent::Extras => self.extras,
+        }
+    }
+
+    pub(crate) fn modal_chrome<'a>(&self, title: impl Into<Line<'a>>) -> Block<'a> {
+        chrome(title).border_style(Style::default().fg(self.modal_border))
+    }
+
+    pub(crate) fn table_header(&self, accent: Accent) -> Style {
+        Style::default()
+            .fg(self.header.unwrap_or(self.accent(accent)))
+            .add_modifier(Modifier::BOLD)
+    }
+
+    pub(crate) fn table_highlight(&self, accent: Accent) -> Style {
+        Style::default()
+            .fg(self.selected_text)
+            .bg(self.highlight.unwrap_or(self.accent(accent)))
+            .add_modifier(Modifier::BOLD)
+    }
+
+    pub(crate) fn focus_prompt(&self) -> Style {
+        Style::default().fg(self.focus).add_modifier(Modifier::BOLD)
+    }
+
+    pub(crate) fn success_prompt(&self) -> Style {
+        Style::default()
+            .fg(self.success)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    pub(crate) fn error_prompt(&self) -> Style {
+        Style::default().fg(self.error).add_modifier(Modifier::BOLD)
+    }
+
+    pub(crate) fn secondary_text(&self) -> Style {
+        let style = Style::default().fg(self.secondary);
+        if self.dim_secondary {
+            style.add_modifier(Modifier::DIM)
+        } else {
+            style
+        }
+    }
 }
 
-pub(crate) fn success_prompt() -> Style {
-    Style::default()
-        .fg(Color::Green)
-        .add_modifier(Modifier::BOLD)
+pub(crate) fn chrome<'a>(title: impl Into<Line<'a>>) -> Block<'a> {
+    Block::default().borders(Borders::ALL).title(title)
 }
 
-pub(crate) fn error_prompt() -> Style {
-    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+pub(crate) fn key_block() -> Block<'static> {
+    chrome("Keys")
 }
 
-pub(crate) fn secondary_text() -> Style {
-    Style::default().fg(Color::Gray).add_modifier(Modifier::DIM)
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Color;
+    use seshmux_core::config::{ThemeConfig, ThemePreset};
+
+    use super::{Accent, DARK, Theme};
+
+    #[test]
+    fn default_config_keeps_the_dark_palette() {
+        assert_eq!(Theme::from_config(&ThemeConfig::default()), DARK);
+        assert_eq!(DARK.table_highlight(Accent::Delete).bg, Some(Color::Red));
+    }
+
+    #[test]
+    fn overrides_replace_preset_colors_for_every_accent() {
+        let theme = Theme::from_config(&ThemeConfig {
+            preset: ThemePreset::Light,
+            highlight: Some("#112233".to_string()),
+            header: Some("light-magenta".to_string()),
+            error: Some("208".to_string()),
+            modal_border: None,
+        });
+
+        for accent in [Accent::Browse, Accent::Delete, Accent::Extras] {
+            assert_eq!(
+                theme.table_highlight(accent).bg,
+                Some(Color::Rgb(0x11, 0x22, 0x33))
+            );
+            assert_eq!(theme.table_header(accent).fg, Some(Color::LightMagenta));
+        }
+        assert_eq!(theme.error, Color::Indexed(208));
+        assert_eq!(theme.selected_text, Color::White);
+        assert_eq!(theme.modal_border, Color::DarkGray);
+    }
 }
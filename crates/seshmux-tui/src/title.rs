@@ -0,0 +1,117 @@
+use seshmux_core::command_runner::CommandRunner;
+
+pub(crate) fn screen_title(screen: &str, worktree: Option<&str>) -> String {
+    match worktree {
+        Some(name) => format!("seshmux: {screen} ({name})"),
+        None => format!("seshmux: {screen}"),
+    }
+}
+
+pub(crate) struct PaneTitle<'a> {
+    runner: &'a dyn CommandRunner,
+    pane: String,
+    original: String,
+    current: Option<String>,
+}
+
+impl<'a> PaneTitle<'a> {
+    pub(crate) fn capture(runner: &'a dyn CommandRunner) -> Option<Self> {
+        std::env::var_os("TMUX")?;
+        let pane = std::env::var("TMUX_PANE").ok()?;
+        let original = seshmux_core::tmux::pane_title(&pane, runner).ok()?;
+
+        Some(Self {
+            runner,
+            pane,
+            original,
+            current: None,
+        })
+    }
+
+    pub(crate) fn set(&mut self, title: &str) {
+        if self.current.as_deref() == Some(title) {
+            return;
+        }
+
+        let _ = seshmux_core::tmux::set_pane_title(&self.pane, title, self.runner);
+        self.current = Some(title.to_string());
+    }
+}
+
+impl Drop for PaneTitle<'_> {
+    fn drop(&mut self) {
+        if self.current.is_some() {
+            let _ = seshmux_core::tmux::set_pane_title(&self.pane, &self.original, self.runner);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::sync::Mutex;
+
+    use seshmux_core::command_runner::{CommandOutput, CommandRunner};
+
+    use super::{PaneTitle, screen_title};
+
+    #[derive(Default)]
+    struct TitleRunner {
+        calls: Mutex<Vec<Vec<String>>>,
+    }
+
+    impl CommandRunner for TitleRunner {
+        fn run(
+            &self,
+            _program: &str,
+            args: &[&str],
+            _cwd: Option<&Path>,
+        ) -> anyhow::Result<CommandOutput> {
+            self.calls
+                .lock()
+                .expect("calls lock")
+                .push(args.iter().map(|value| (*value).to_string()).collect());
+            Ok(CommandOutput::new(0, "", ""))
+        }
+
+        fn run_interactive(
+            &self,
+            _program: &str,
+            _args: &[&str],
+            _cwd: Option<&Path>,
+        ) -> anyhow::Result<i32> {
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn screen_title_includes_selected_worktree() {
+        assert_eq!(screen_title("Home", None), "seshmux: Home");
+        assert_eq!(screen_title("List", Some("w1")), "seshmux: List (w1)");
+    }
+
+    #[test]
+    fn pane_title_skips_repeats_and_restores_original_on_drop() {
+        let runner = TitleRunner::default();
+        {
+            let mut pane = PaneTitle {
+                runner: &runner,
+                pane: "%1".to_string(),
+                original: "zsh".to_string(),
+                current: None,
+            };
+            pane.set("seshmux: Home");
+            pane.set("seshmux: Home");
+            pane.set("seshmux: List (w1)");
+        }
+
+        let titles: Vec<String> = runner
+            .calls
+            .lock()
+            .expect("calls lock")
+            .iter()
+            .map(|args| args[4].clone())
+            .collect();
+        assert_eq!(titles, vec!["seshmux: Home", "seshmux: List (w1)", "zsh"]);
+    }
+}
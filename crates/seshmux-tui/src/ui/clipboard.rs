@@ -0,0 +1,107 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const CLIPBOARD_TOOLS: [(&str, &[&str]); 4] = [
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CopyRoute {
+    Tool,
+    Osc52,
+}
+
+// A local clipboard tool is more reliable than OSC 52, which some terminals ignore. Over SSH the
+// tool would fill the remote machine's clipboard, so the terminal escape is used instead.
+pub(crate) fn copy_route(over_ssh: bool, tool_copied: impl FnOnce() -> bool) -> CopyRoute {
+    if !over_ssh && tool_copied() {
+        CopyRoute::Tool
+    } else {
+        CopyRoute::Osc52
+    }
+}
+
+pub(crate) fn over_ssh() -> bool {
+    std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some()
+}
+
+pub(crate) fn copy_with_tool(text: &str) -> bool {
+    CLIPBOARD_TOOLS
+        .iter()
+        .any(|(program, args)| pipe_to(program, args, text))
+}
+
+fn pipe_to(program: &str, args: &[&str], text: &str) -> bool {
+    let Ok(mut child) = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return false;
+    };
+
+    let written = child
+        .stdin
+        .take()
+        .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+    child.wait().is_ok_and(|status| status.success()) && written
+}
+
+pub(crate) fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+fn base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let buffer = chunk.iter().enumerate().fold(0u32, |acc, (index, byte)| {
+            acc | u32::from(*byte) << (16 - 8 * index)
+        });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = (buffer >> (18 - 6 * index)) & 0x3f;
+                encoded.push(char::from(BASE64_ALPHABET[sextet as usize]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CopyRoute, base64, copy_route, osc52_sequence};
+
+    #[test]
+    fn base64_pads_partial_chunks() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64("/tmp/機能".as_bytes()), "L3RtcC/mqZ/og70=");
+    }
+
+    #[test]
+    fn copy_route_prefers_a_local_tool_except_over_ssh() {
+        assert_eq!(copy_route(false, || true), CopyRoute::Tool);
+        assert_eq!(copy_route(false, || false), CopyRoute::Osc52);
+        assert_eq!(
+            copy_route(true, || panic!("tool must not run over ssh")),
+            CopyRoute::Osc52
+        );
+    }
+
+    #[test]
+    fn osc52_sequence_targets_clipboard_selection() {
+        assert_eq!(osc52_sequence("foo"), "\x1b]52;c;Zm9v\x07");
+    }
+}
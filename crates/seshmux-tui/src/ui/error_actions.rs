@@ -0,0 +1,151 @@
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::text::{Line, Span, Text};
+
+use crate::keymap;
+use crate::theme::{Accent, Theme};
+use crate::ui::modal::{ModalSpec, render_modal};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ErrorActions<A> {
+    items: Vec<(String, A)>,
+    selected: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ErrorActionsEvent<A> {
+    Continue,
+    Choose(A),
+    Back,
+}
+
+impl<A: Clone> ErrorActions<A> {
+    pub(crate) fn new(items: Vec<(String, A)>) -> Self {
+        Self { items, selected: 0 }
+    }
+
+    pub(crate) fn none() -> Self {
+        Self::new(Vec::new())
+    }
+
+    pub(crate) fn on_key(&mut self, key: KeyEvent) -> ErrorActionsEvent<A> {
+        if keymap::is_back(key) {
+            return ErrorActionsEvent::Back;
+        }
+
+        if keymap::is_up(key) {
+            self.selected = self.selected.saturating_sub(1);
+            return ErrorActionsEvent::Continue;
+        }
+
+        if keymap::is_down(key) {
+            if self.selected + 1 < self.items.len() {
+                self.selected += 1;
+            }
+            return ErrorActionsEvent::Continue;
+        }
+
+        if keymap::is_confirm(key) {
+            return match self.items.get(self.selected) {
+                Some((_, action)) => ErrorActionsEvent::Choose(action.clone()),
+                None => ErrorActionsEvent::Back,
+            };
+        }
+
+        ErrorActionsEvent::Continue
+    }
+
+    fn key_hint(&self, back_label: &str) -> String {
+        if self.items.is_empty() {
+            format!("Enter/Esc: {back_label}")
+        } else {
+            format!("Up/Down or j/k: move    Enter: select    Esc: {back_label}")
+        }
+    }
+
+    fn lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        if self.items.is_empty() {
+            return Vec::new();
+        }
+
+        let mut lines = vec![Line::from(""), Line::from("What next?")];
+        for (index, (label, _)) in self.items.iter().enumerate() {
+            let selected = self.selected == index;
+            let line = format!("{} {label}", if selected { ">>" } else { "  " });
+            if selected {
+                lines.push(Line::from(Span::styled(
+                    line,
+                    theme.table_highlight(Accent::Create),
+                )));
+            } else {
+                lines.push(Line::from(line));
+            }
+        }
+        lines
+    }
+}
+
+pub(crate) fn render_error_with_actions<A: Clone>(
+    frame: &mut Frame<'_>,
+    theme: &Theme,
+    message: &str,
+    actions: &ErrorActions<A>,
+    back_label: &str,
+    width_pct: u16,
+    height_pct: u16,
+) {
+    let mut lines: Vec<Line<'static>> = message
+        .trim_end()
+        .lines()
+        .map(|line| Line::from(line.to_string()))
+        .collect();
+    lines.extend(actions.lines(theme));
+
+    render_modal(
+        frame,
+        theme,
+        ModalSpec {
+            title: "Error",
+            title_style: Some(theme.error_prompt()),
+            body: Text::from(lines),
+            key_hint: Some(&actions.key_hint(back_label)),
+            width_pct,
+            height_pct,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{ErrorActions, ErrorActionsEvent};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn enter_chooses_the_selected_action_or_goes_back_without_any() {
+        let mut actions = ErrorActions::new(vec![
+            ("Rename".to_string(), 1),
+            ("Use existing".to_string(), 2),
+        ]);
+        assert_eq!(
+            actions.on_key(key(KeyCode::Down)),
+            ErrorActionsEvent::Continue
+        );
+        assert_eq!(
+            actions.on_key(key(KeyCode::Down)),
+            ErrorActionsEvent::Continue
+        );
+        assert_eq!(
+            actions.on_key(key(KeyCode::Enter)),
+            ErrorActionsEvent::Choose(2)
+        );
+        assert_eq!(actions.on_key(key(KeyCode::Esc)), ErrorActionsEvent::Back);
+
+        let mut none = ErrorActions::<u8>::none();
+        assert_eq!(none.on_key(key(KeyCode::Enter)), ErrorActionsEvent::Back);
+    }
+}
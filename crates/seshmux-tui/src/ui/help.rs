@@ -0,0 +1,144 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::text::{Line, Span, Text};
+
+use crate::keymap;
+use crate::theme::Theme;
+use crate::ui::modal::{ModalSpec, render_modal};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ScreenHelp {
+    pub(crate) title: String,
+    pub(crate) summary: String,
+    pub(crate) keys: Vec<(&'static str, &'static str)>,
+}
+
+impl ScreenHelp {
+    pub(crate) fn new(title: impl Into<String>, summary: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            summary: summary.into(),
+            keys: Vec::new(),
+        }
+    }
+
+    pub(crate) fn keys(mut self, keys: &[(&'static str, &'static str)]) -> Self {
+        self.keys.extend_from_slice(keys);
+        self
+    }
+}
+
+pub(crate) trait HelpProvider {
+    fn help(&self) -> ScreenHelp;
+
+    fn typing(&self) -> bool;
+}
+
+pub(crate) const FILTER_KEYS: &[(&str, &str)] = &[
+    ("Type", "narrow the list"),
+    ("Backspace", "delete a character"),
+    ("/", "return to the list"),
+    ("Esc", "back"),
+];
+
+pub(crate) const INPUT_KEYS: &[(&str, &str)] = &[
+    ("Type", "edit the value"),
+    ("Backspace", "delete a character"),
+    ("Enter", "continue"),
+    ("Esc", "back"),
+];
+
+pub(crate) const CHOICE_KEYS: &[(&str, &str)] = &[
+    ("Space", "switch between Yes and No"),
+    ("Enter", "confirm the current selection"),
+    ("Esc", "back"),
+];
+
+pub(crate) const RESULT_KEYS: &[(&str, &str)] =
+    &[("Enter/Esc", "back to home"), ("q", "quit seshmux")];
+
+pub(crate) const ERROR_KEYS: &[(&str, &str)] = &[
+    (
+        "Up/Down or j/k",
+        "move between suggested next steps, if any",
+    ),
+    ("Enter", "take the selected step, or go back"),
+    ("Esc", "back"),
+];
+
+pub(crate) fn opens_help(key: KeyEvent) -> bool {
+    key.code == KeyCode::Char('?')
+}
+
+pub(crate) fn closes_help(key: KeyEvent) -> bool {
+    opens_help(key) || keymap::is_back(key) || keymap::is_confirm(key) || keymap::is_quit(key)
+}
+
+pub(crate) fn render_help(frame: &mut Frame<'_>, theme: &Theme, help: &ScreenHelp) {
+    let width = help
+        .keys
+        .iter()
+        .map(|(keys, _)| keys.chars().count())
+        .max()
+        .unwrap_or_default();
+    let mut lines = vec![Line::from(help.summary.clone()), Line::from("")];
+    lines.extend(help.keys.iter().map(|(keys, action)| {
+        Line::from(vec![
+            Span::styled(format!("{keys:>width$}  "), theme.focus_prompt()),
+            Span::raw(*action),
+        ])
+    }));
+
+    let title = format!("Help: {}", help.title);
+    render_modal(
+        frame,
+        theme,
+        ModalSpec {
+            title: &title,
+            title_style: Some(theme.focus_prompt()),
+            body: Text::from(lines),
+            key_hint: Some("?/Esc: close help"),
+            width_pct: 76,
+            height_pct: 70,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    use super::{INPUT_KEYS, ScreenHelp, closes_help, opens_help, render_help};
+    use crate::theme::Theme;
+
+    #[test]
+    fn help_lists_the_summary_and_right_aligned_keys() {
+        let theme = &Theme::default();
+        let help = ScreenHelp::new("Worktree name", "Name the new worktree.").keys(INPUT_KEYS);
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).expect("terminal");
+        terminal
+            .draw(|frame| render_help(frame, theme, &help))
+            .expect("render");
+        let rendered = format!("{}", terminal.backend());
+
+        assert!(rendered.contains("Help: Worktree name"));
+        assert!(rendered.contains("Name the new worktree."));
+        assert!(rendered.contains("     Type  edit the value"));
+        assert!(rendered.contains("Backspace  delete a character"));
+        assert!(rendered.contains("?/Esc: close help"));
+    }
+
+    #[test]
+    fn question_mark_opens_and_closes_help_even_when_shifted() {
+        let shifted = KeyEvent::new(KeyCode::Char('?'), KeyModifiers::SHIFT);
+        assert!(opens_help(shifted));
+        assert!(closes_help(shifted));
+        assert!(closes_help(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)));
+        assert!(!closes_help(KeyEvent::new(
+            KeyCode::Char('j'),
+            KeyModifiers::NONE
+        )));
+    }
+}
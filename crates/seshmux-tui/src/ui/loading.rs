@@ -1,16 +1,20 @@
-use std::collections::BTreeSet;
-use std::path::PathBuf;
-use std::sync::mpsc::{self, Receiver};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, Instant};
 
 use ratatui::Frame;
 use ratatui::text::{Line, Text};
 use seshmux_core::command_runner::SystemCommandRunner;
+use seshmux_core::extras::ExtraOrigin;
 
-use crate::new_flow::extras::{ExtrasIndex, build_extras_index_from_paths};
-use crate::theme;
+use crate::new_flow::extras::{ExtrasIndex, build_extras_index_with_progress};
+use crate::theme::Theme;
 use crate::ui::modal::{ModalSpec, render_modal};
 
+const SUGGESTED_SKIP_MIN_FILES: usize = 200;
 const FRAMES: [&str; 8] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧"];
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
 
 #[derive(Debug, Clone, Default)]
 pub(crate) struct LoadingState {
@@ -22,7 +26,7 @@ impl LoadingState {
         self.frame_index = (self.frame_index + 1) % FRAMES.len();
     }
 
-    fn current_frame(&self) -> &'static str {
+    pub(crate) fn current_frame(&self) -> &'static str {
         FRAMES[self.frame_index]
     }
 }
@@ -36,17 +40,100 @@ pub(crate) struct FlaggedBucket {
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub(crate) struct BucketPlan {
     pub(crate) flagged: Vec<FlaggedBucket>,
+    pub(crate) suggested: Vec<FlaggedBucket>,
 }
 
 impl BucketPlan {
     pub(crate) fn flagged_count(&self) -> usize {
         self.flagged.len()
     }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.flagged.is_empty() && self.suggested.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct IndexingProgress {
+    pub(crate) files_scanned: usize,
+    pub(crate) total_files: usize,
+    pub(crate) dirs_walked: usize,
+    pub(crate) elapsed: Duration,
+}
+
+impl IndexingProgress {
+    pub(crate) fn remaining(&self) -> Option<Duration> {
+        if self.files_scanned == 0 || self.files_scanned >= self.total_files {
+            return None;
+        }
+        let left = (self.total_files - self.files_scanned) as f64;
+        Some(self.elapsed.mul_f64(left / self.files_scanned as f64))
+    }
+
+    pub(crate) fn summary(&self) -> String {
+        let mut summary = format!(
+            "{}/{} files in {} dirs, {}s elapsed",
+            self.files_scanned,
+            self.total_files,
+            self.dirs_walked,
+            self.elapsed.as_secs()
+        );
+        if let Some(remaining) = self.remaining() {
+            summary.push_str(&format!(", about {}s left", remaining.as_secs().max(1)));
+        }
+        summary
+    }
+}
+
+struct ProgressCounter<'a> {
+    sender: &'a Sender<ExtrasLoadEvent>,
+    started_at: Instant,
+    last_sent: Option<Instant>,
+    files_scanned: usize,
+    dirs: HashSet<PathBuf>,
+}
+
+impl<'a> ProgressCounter<'a> {
+    fn new(sender: &'a Sender<ExtrasLoadEvent>) -> Self {
+        Self {
+            sender,
+            started_at: Instant::now(),
+            last_sent: None,
+            files_scanned: 0,
+            dirs: HashSet::new(),
+        }
+    }
+
+    fn record(&mut self, path: &Path, total_files: usize) {
+        self.files_scanned += 1;
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+            && !self.dirs.contains(parent)
+        {
+            self.dirs.insert(parent.to_path_buf());
+        }
+
+        let due = self
+            .last_sent
+            .is_none_or(|sent| sent.elapsed() >= PROGRESS_INTERVAL);
+        if due || self.files_scanned >= total_files {
+            self.last_sent = Some(Instant::now());
+            let _ = self
+                .sender
+                .send(ExtrasLoadEvent::Progress(IndexingProgress {
+                    files_scanned: self.files_scanned,
+                    total_files,
+                    dirs_walked: self.dirs.len(),
+                    elapsed: self.started_at.elapsed(),
+                }));
+        }
+    }
 }
 
 #[derive(Debug)]
 pub(crate) enum ExtrasLoadEvent {
     Collecting,
+    Progress(IndexingProgress),
     Classifying {
         candidate_count: usize,
     },
@@ -56,6 +143,7 @@ pub(crate) enum ExtrasLoadEvent {
     DoneCollect {
         token: u64,
         candidates: Vec<PathBuf>,
+        origins: BTreeMap<PathBuf, ExtraOrigin>,
         plan: BucketPlan,
     },
     Building {
@@ -99,9 +187,28 @@ impl ExtrasLoader for SystemExtrasLoader {
             let _ = sender.send(ExtrasLoadEvent::Collecting);
 
             let runner = SystemCommandRunner::new();
-            let candidates = match seshmux_core::extras::list_extra_candidates(&repo_root, &runner)
-            {
-                Ok(candidates) => candidates,
+            let collected = seshmux_core::extras::collect_git_extra_origins(&repo_root, &runner)
+                .and_then(|origins| {
+                    let raw = origins.keys().cloned().collect::<Vec<_>>();
+                    let total = raw.len();
+                    let mut counter = ProgressCounter::new(&sender);
+                    let candidates = seshmux_core::extras::filter_safe_extra_paths_with_progress(
+                        &repo_root,
+                        raw,
+                        &mut |path| counter.record(path, total),
+                    )?;
+                    let origins = origins
+                        .into_iter()
+                        .filter_map(|(path, origin)| {
+                            seshmux_core::extras::normalize_extra_relative_path(&path)
+                                .ok()
+                                .map(|path| (path, origin))
+                        })
+                        .collect::<BTreeMap<_, _>>();
+                    Ok((candidates, origins))
+                });
+            let (candidates, origins) = match collected {
+                Ok(collected) => collected,
                 Err(error) => {
                     let _ = sender.send(ExtrasLoadEvent::Done {
                         token,
@@ -115,11 +222,24 @@ impl ExtrasLoader for SystemExtrasLoader {
                 candidate_count: candidates.len(),
             });
 
-            let flagged = seshmux_core::extras::classify_flagged_buckets(&candidates, &skip_rules)
-                .into_iter()
-                .map(|(bucket, count)| FlaggedBucket { bucket, count })
-                .collect::<Vec<_>>();
-            let plan = BucketPlan { flagged };
+            let flagged = seshmux_core::extras::classify_flagged_buckets(&candidates, &skip_rules);
+            let flagged_buckets = flagged.keys().cloned().collect::<BTreeSet<_>>();
+            let suggested = seshmux_core::extras::suggest_skip_buckets(
+                &candidates,
+                &origins,
+                &flagged_buckets,
+                SUGGESTED_SKIP_MIN_FILES,
+            );
+            let plan = BucketPlan {
+                flagged: flagged
+                    .into_iter()
+                    .map(|(bucket, count)| FlaggedBucket { bucket, count })
+                    .collect(),
+                suggested: suggested
+                    .into_iter()
+                    .map(|(bucket, count)| FlaggedBucket { bucket, count })
+                    .collect(),
+            };
 
             let _ = sender.send(ExtrasLoadEvent::AwaitingSkipDecision {
                 flagged_bucket_count: plan.flagged_count(),
@@ -127,6 +247,7 @@ impl ExtrasLoader for SystemExtrasLoader {
             let _ = sender.send(ExtrasLoadEvent::DoneCollect {
                 token,
                 candidates,
+                origins,
                 plan,
             });
         });
@@ -140,30 +261,40 @@ impl ExtrasLoader for SystemExtrasLoader {
             let _ = sender.send(ExtrasLoadEvent::Building {
                 filtered_count: candidates.len(),
             });
-            let result =
-                build_extras_index_from_paths(&candidates).map_err(|error| format!("{error:#}"));
+            let mut counter = ProgressCounter::new(&sender);
+            let result = build_extras_index_with_progress(&candidates, &mut |path, total| {
+                counter.record(path, total)
+            })
+            .map_err(|error| format!("{error:#}"));
             let _ = sender.send(ExtrasLoadEvent::Done { token, result });
         });
         receiver
     }
 }
 
-pub(crate) fn render_loading_modal(
+pub(crate) fn render_loading_modal_with_detail(
     frame: &mut Frame<'_>,
+    theme: &Theme,
     title: &str,
     message: &str,
+    detail: Option<&str>,
     key_hint: &str,
     loading: &LoadingState,
 ) {
-    let body = Text::from(vec![
+    let mut lines = vec![
         Line::from(""),
         Line::from(format!("{} {}", loading.current_frame(), message)),
-    ]);
+    ];
+    if let Some(detail) = detail {
+        lines.push(Line::styled(format!("  {detail}"), theme.secondary_text()));
+    }
+    let body = Text::from(lines);
     render_modal(
         frame,
+        theme,
         ModalSpec {
             title,
-            title_style: Some(theme::focus_prompt()),
+            title_style: Some(theme.focus_prompt()),
             body,
             key_hint: Some(key_hint),
             width_pct: 72,
@@ -1,6 +1,12 @@
 pub(crate) mod binary_choice;
+pub(crate) mod clipboard;
+pub(crate) mod error_actions;
+pub(crate) mod help;
 pub(crate) mod loading;
 pub(crate) mod modal;
+pub(crate) mod mouse;
+pub(crate) mod paste;
 pub(crate) mod select_step;
 pub(crate) mod text;
+pub(crate) mod toast;
 pub(crate) mod worktree_table;
@@ -1,16 +1,20 @@
 use ratatui::Frame;
-use ratatui::layout::Rect;
+use ratatui::layout::{Margin, Rect};
 use ratatui::style::Style;
 use ratatui::text::{Line, Span, Text};
-use ratatui::widgets::Clear;
+use ratatui::widgets::{Clear, Paragraph};
 use ratatui::{
     layout::{Constraint, Direction, Layout},
     text::Line as TextLine,
 };
 
+use tui_input::Input;
+
 use crate::centered_rect;
-use crate::theme;
-use crate::ui::text::{key_hint_height, key_hint_paragraph, wrapped_paragraph};
+use crate::theme::{self, Theme};
+use crate::ui::text::{
+    compact_hint, input_viewport, key_hint_height, key_hint_paragraph, wrapped_paragraph,
+};
 
 pub(crate) struct ModalSpec<'a> {
     pub(crate) title: &'a str,
@@ -25,7 +29,11 @@ pub(crate) struct ModalRenderResult {
     pub(crate) body_area: Rect,
 }
 
-pub(crate) fn render_modal(frame: &mut Frame<'_>, spec: ModalSpec<'_>) -> ModalRenderResult {
+pub(crate) fn render_modal(
+    frame: &mut Frame<'_>,
+    theme: &Theme,
+    spec: ModalSpec<'_>,
+) -> ModalRenderResult {
     let area = centered_rect(spec.width_pct, spec.height_pct, frame.area());
     let title = if let Some(style) = spec.title_style {
         Line::from(Span::styled(spec.title.to_string(), style))
@@ -49,7 +57,7 @@ pub(crate) fn render_modal(frame: &mut Frame<'_>, spec: ModalSpec<'_>) -> ModalR
 
     frame.render_widget(Clear, body_area);
     frame.render_widget(
-        wrapped_paragraph(spec.body).block(theme::chrome(title)),
+        wrapped_paragraph(spec.body).block(theme.modal_chrome(title)),
         body_area,
     );
 
@@ -80,8 +88,99 @@ fn choose_key_area(screen: Rect, body: Rect, footer_height: u16) -> Option<Rect>
     None
 }
 
+pub(crate) fn render_input_modal(
+    frame: &mut Frame<'_>,
+    theme: &Theme,
+    title: &str,
+    input: &Input,
+    error: Option<&str>,
+) {
+    render_input_modal_with_suggestions(frame, theme, title, input, error, None, &[]);
+}
+
+pub(crate) fn render_input_modal_with_suggestions(
+    frame: &mut Frame<'_>,
+    theme: &Theme,
+    title: &str,
+    input: &Input,
+    error: Option<&str>,
+    note: Option<&str>,
+    suggestions: &[String],
+) {
+    let key_text = if suggestions.is_empty() {
+        compact_hint(
+            frame.area().width,
+            "Type to edit    Enter: continue    Backspace: delete    Esc: back",
+            "Type    Enter: continue    Backspace: delete    Esc: back",
+            "Type | Enter continue | Backspace delete | Esc back",
+        )
+    } else {
+        compact_hint(
+            frame.area().width,
+            "Type to edit    Tab: next suggestion    Enter: continue    Backspace: delete    Esc: back",
+            "Type    Tab: suggestion    Enter: continue    Esc: back",
+            "Type | Tab suggest | Enter continue | Esc back",
+        )
+    };
+    let rendered = render_modal(
+        frame,
+        theme,
+        ModalSpec {
+            title,
+            title_style: Some(theme.focus_prompt()),
+            body: Text::from(vec![Line::from("")]),
+            key_hint: Some(key_text),
+            width_pct: 72,
+            height_pct: 44,
+        },
+    );
+
+    let inner = rendered.body_area.inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    if inner.width == 0 || inner.height == 0 {
+        return;
+    }
+
+    let input_area = Rect::new(inner.x, inner.y, inner.width, 1);
+    let width = input_area.width as usize;
+    let scroll = input.visual_scroll(width);
+    let viewport = Paragraph::new(input_viewport(input.value(), scroll, width));
+    frame.render_widget(viewport, input_area);
+
+    let mut details = Vec::new();
+    if let Some(error) = error {
+        details.push(Line::from(format!("Invalid: {error}")));
+    } else if let Some(note) = note {
+        details.push(Line::styled(note.to_string(), theme.secondary_text()));
+    }
+    if !suggestions.is_empty() {
+        details.push(Line::from(vec![
+            Span::styled("Suggestions: ", theme.secondary_text()),
+            Span::raw(suggestions.join(", ")),
+        ]));
+    }
+    if !details.is_empty() && inner.height > 1 {
+        let details_area = Rect::new(
+            inner.x,
+            inner.y + 1,
+            inner.width,
+            inner.height.saturating_sub(1),
+        );
+        frame.render_widget(wrapped_paragraph(Text::from(details)), details_area);
+    }
+
+    if width > 0 {
+        let visual = input.visual_cursor();
+        let relative = visual.saturating_sub(scroll).min(width.saturating_sub(1));
+        frame.set_cursor_position((input_area.x + relative as u16, input_area.y));
+    }
+}
+
 pub(crate) fn render_error_modal(
     frame: &mut Frame<'_>,
+    theme: &Theme,
     message: &str,
     width_pct: u16,
     height_pct: u16,
@@ -89,9 +188,10 @@ pub(crate) fn render_error_modal(
 ) {
     render_modal(
         frame,
+        theme,
         ModalSpec {
             title: "Error",
-            title_style: Some(crate::theme::error_prompt()),
+            title_style: Some(theme.error_prompt()),
             body: text_from_message(message),
             key_hint: Some(footer),
             width_pct,
@@ -102,6 +202,7 @@ pub(crate) fn render_error_modal(
 
 pub(crate) fn render_notice_modal(
     frame: &mut Frame<'_>,
+    theme: &Theme,
     title: &str,
     message: &str,
     width_pct: u16,
@@ -110,9 +211,10 @@ pub(crate) fn render_notice_modal(
 ) {
     render_modal(
         frame,
+        theme,
         ModalSpec {
             title,
-            title_style: Some(crate::theme::focus_prompt()),
+            title_style: Some(theme.focus_prompt()),
             body: text_from_message(message),
             key_hint: Some(footer),
             width_pct,
@@ -123,6 +225,7 @@ pub(crate) fn render_notice_modal(
 
 pub(crate) fn render_success_modal(
     frame: &mut Frame<'_>,
+    theme: &Theme,
     message: &str,
     width_pct: u16,
     height_pct: u16,
@@ -130,9 +233,10 @@ pub(crate) fn render_success_modal(
 ) {
     render_modal(
         frame,
+        theme,
         ModalSpec {
             title: "Success",
-            title_style: Some(crate::theme::success_prompt()),
+            title_style: Some(theme.success_prompt()),
             body: text_from_message(message),
             key_hint: Some(footer),
             width_pct,
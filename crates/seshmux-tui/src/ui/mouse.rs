@@ -0,0 +1,279 @@
+use std::cell::{Cell, RefCell};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::Frame;
+use ratatui::layout::{Margin, Rect};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MouseAction {
+    Row(usize),
+    ScrollUp,
+    ScrollDown,
+    Key(KeyEvent),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ListRegion {
+    area: Rect,
+    header_rows: u16,
+}
+
+#[derive(Debug, Clone, Default)]
+struct HintRegion {
+    area: Rect,
+    rows: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct MouseTargets {
+    list: Cell<Option<ListRegion>>,
+    hints: RefCell<HintRegion>,
+}
+
+impl MouseTargets {
+    pub(crate) fn record_list(&self, area: Rect, header_rows: u16) {
+        self.list.set(Some(ListRegion { area, header_rows }));
+    }
+
+    pub(crate) fn record_hints(&self, frame: &mut Frame<'_>, area: Rect) {
+        let inner = area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+        let buffer = frame.buffer_mut();
+        let rows = inner
+            .rows()
+            .map(|row| {
+                row.columns()
+                    .map(|position| {
+                        buffer
+                            .cell(position)
+                            .map(|cell| cell.symbol().to_string())
+                            .unwrap_or_default()
+                    })
+                    .collect()
+            })
+            .collect();
+        *self.hints.borrow_mut() = HintRegion { area: inner, rows };
+    }
+
+    pub(crate) fn action(
+        &self,
+        mouse: MouseEvent,
+        len: usize,
+        selected: usize,
+    ) -> Option<MouseAction> {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => Some(MouseAction::ScrollUp),
+            MouseEventKind::ScrollDown => Some(MouseAction::ScrollDown),
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(region) = self.list.get()
+                    && let Some(index) = list_row_at(
+                        region.area,
+                        region.header_rows,
+                        len,
+                        selected,
+                        mouse.column,
+                        mouse.row,
+                    )
+                {
+                    return Some(MouseAction::Row(index));
+                }
+                let hints = self.hints.borrow();
+                if !contains(hints.area, mouse.column, mouse.row) {
+                    return None;
+                }
+                let row = &hints.rows[usize::from(mouse.row - hints.area.y)];
+                hint_key_at(row, usize::from(mouse.column - hints.area.x)).map(MouseAction::Key)
+            }
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn contains(area: Rect, column: u16, row: u16) -> bool {
+    column >= area.x
+        && column < area.x.saturating_add(area.width)
+        && row >= area.y
+        && row < area.y.saturating_add(area.height)
+}
+
+// Maps a click inside a bordered list or table to an item index. Lists and tables are rendered
+// from a fresh state each frame, so the scroll offset is the smallest one keeping the selection
+// visible.
+pub(crate) fn list_row_at(
+    area: Rect,
+    header_rows: u16,
+    len: usize,
+    selected: usize,
+    column: u16,
+    row: u16,
+) -> Option<usize> {
+    let first_row = area.y.saturating_add(1).saturating_add(header_rows);
+    let viewport = usize::from(area.height.saturating_sub(2).saturating_sub(header_rows));
+    if viewport == 0 || !contains(area, column, row) || row < first_row {
+        return None;
+    }
+
+    let offset = selected.saturating_sub(viewport - 1);
+    let index = offset + usize::from(row - first_row);
+    (usize::from(row - first_row) < viewport && index < len).then_some(index)
+}
+
+pub(crate) fn hint_key_at(cells: &[String], column: usize) -> Option<KeyEvent> {
+    if is_separator(cells, column) {
+        return None;
+    }
+
+    let mut start = column;
+    while start > 0 && !is_separator(cells, start - 1) {
+        start -= 1;
+    }
+    let mut end = column;
+    while end + 1 < cells.len() && !is_separator(cells, end + 1) {
+        end += 1;
+    }
+    let segment: String = cells[start..=end].concat();
+    segment_key(segment.trim())
+}
+
+fn is_separator(cells: &[String], index: usize) -> bool {
+    let gap = |index: Option<usize>| {
+        index
+            .and_then(|index| cells.get(index))
+            .is_none_or(|cell| cell.trim().is_empty() || cell == "|")
+    };
+    match cells.get(index) {
+        None => true,
+        Some(cell) if cell == "|" => true,
+        Some(cell) if cell.trim().is_empty() => gap(index.checked_sub(1)) || gap(Some(index + 1)),
+        Some(_) => false,
+    }
+}
+
+fn segment_key(segment: &str) -> Option<KeyEvent> {
+    let keys = match segment.split_once(':') {
+        Some((keys, _)) => keys,
+        None => segment.split_whitespace().next()?,
+    };
+    let first = keys.split('/').next()?.trim();
+    if let Some(key) = first.strip_prefix("Ctrl+") {
+        let mut chars = key.chars();
+        return match (chars.next(), chars.next()) {
+            (Some(character), None) => Some(KeyEvent::new(
+                KeyCode::Char(character.to_ascii_lowercase()),
+                KeyModifiers::CONTROL,
+            )),
+            _ => None,
+        };
+    }
+    let code = match first {
+        "Enter" => KeyCode::Enter,
+        "Esc" => KeyCode::Esc,
+        "Tab" => KeyCode::Tab,
+        "Space" => KeyCode::Char(' '),
+        "Backspace" => KeyCode::Backspace,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        _ => {
+            let mut chars = first.chars();
+            match (chars.next(), chars.next()) {
+                (Some(character), None) => KeyCode::Char(character),
+                _ => return None,
+            }
+        }
+    };
+    Some(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{
+        KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use ratatui::layout::Rect;
+
+    use super::{MouseAction, MouseTargets, list_row_at};
+    use crate::theme;
+    use crate::ui::text::key_hint_paragraph;
+
+    fn hint_targets(text: &str, width: u16, height: u16) -> MouseTargets {
+        let targets = MouseTargets::default();
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("terminal");
+        terminal
+            .draw(|frame| {
+                let area = frame.area();
+                frame.render_widget(key_hint_paragraph(text).block(theme::key_block()), area);
+                targets.record_hints(frame, area);
+            })
+            .expect("draw");
+        targets
+    }
+
+    fn click(targets: &MouseTargets, column: u16, row: u16) -> Option<MouseAction> {
+        targets.action(
+            MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            },
+            0,
+            0,
+        )
+    }
+
+    fn key(code: KeyCode) -> Option<MouseAction> {
+        Some(MouseAction::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+    }
+
+    #[test]
+    fn list_row_at_accounts_for_borders_header_and_scroll() {
+        let area = Rect::new(0, 0, 40, 7);
+
+        assert_eq!(list_row_at(area, 1, 10, 0, 5, 2), Some(0));
+        assert_eq!(list_row_at(area, 1, 10, 0, 5, 5), Some(3));
+        assert_eq!(list_row_at(area, 1, 10, 0, 5, 1), None);
+        assert_eq!(list_row_at(area, 1, 10, 0, 5, 6), None);
+        assert_eq!(list_row_at(area, 1, 2, 0, 5, 4), None);
+
+        assert_eq!(list_row_at(area, 1, 10, 7, 5, 2), Some(4));
+        assert_eq!(list_row_at(area, 0, 10, 0, 5, 1), Some(0));
+    }
+
+    #[test]
+    fn footer_clicks_map_hints_to_their_first_key() {
+        let targets = hint_targets("Enter: select    j/k: move    Esc/q: exit", 43, 3);
+
+        assert_eq!(click(&targets, 1, 1), key(KeyCode::Enter));
+        assert_eq!(click(&targets, 18, 1), key(KeyCode::Char('j')));
+        assert_eq!(click(&targets, 40, 1), key(KeyCode::Esc));
+        assert_eq!(click(&targets, 15, 1), None);
+        assert_eq!(click(&targets, 1, 2), None);
+
+        let control = hint_targets("Ctrl+N: quick new", 19, 3);
+        assert_eq!(
+            click(&control, 3, 1),
+            Some(MouseAction::Key(KeyEvent::new(
+                KeyCode::Char('n'),
+                KeyModifiers::CONTROL
+            )))
+        );
+
+        let compact = hint_targets("o open | Esc back | Type filter", 33, 3);
+        assert_eq!(click(&compact, 3, 1), key(KeyCode::Char('o')));
+        assert_eq!(click(&compact, 12, 1), key(KeyCode::Esc));
+        assert_eq!(click(&compact, 25, 1), None);
+    }
+
+    #[test]
+    fn footer_clicks_follow_wrapped_hints() {
+        let targets = hint_targets("Enter: select    j/k: move    Esc/q: exit", 24, 5);
+
+        assert_eq!(click(&targets, 20, 1), key(KeyCode::Char('j')));
+        assert_eq!(click(&targets, 4, 2), None);
+        assert_eq!(click(&targets, 12, 2), key(KeyCode::Esc));
+    }
+}
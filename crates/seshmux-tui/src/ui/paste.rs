@@ -0,0 +1,48 @@
+use tui_input::{Input, InputRequest};
+
+// Inputs are single-line; pasted newlines would otherwise arrive as Enter keystrokes.
+pub(crate) fn single_line_paste(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .filter(|character| !character.is_control())
+        .collect()
+}
+
+pub(crate) fn paste_into(input: &mut Input, text: &str) -> bool {
+    let text = single_line_paste(text);
+    for character in text.chars() {
+        input.handle(InputRequest::InsertChar(character));
+    }
+
+    !text.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use tui_input::Input;
+
+    use super::{paste_into, single_line_paste};
+
+    #[test]
+    fn single_line_paste_strips_newlines_and_control_characters() {
+        assert_eq!(single_line_paste("feature/login\r\n"), "feature/login");
+        assert_eq!(
+            single_line_paste("  JIRA-42\n\nfix\tbug "),
+            "JIRA-42 fixbug"
+        );
+        assert_eq!(single_line_paste("\n\r\n"), "");
+    }
+
+    #[test]
+    fn paste_into_inserts_at_cursor() {
+        let mut input = Input::new("ab".to_string());
+        assert!(paste_into(&mut input, "xy\n"));
+        assert_eq!(input.value(), "abxy");
+        assert!(!paste_into(&mut input, "\n"));
+        assert_eq!(input.value(), "abxy");
+    }
+}
@@ -1,11 +1,20 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use std::collections::BTreeSet;
+
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 use ratatui::Frame;
 use ratatui::layout::Rect;
-use ratatui::text::Line;
-use seshmux_app::WorktreeRow;
+use ratatui::text::{Line, Text};
+use seshmux_app::{SessionFilter, WorktreeOrder, WorktreeRow};
+use seshmux_core::config::WorktreeColumn;
+use seshmux_core::registry::DiskUsage;
 
 use crate::keymap;
+use crate::theme::{self, Theme};
 
+use super::help::FILTER_KEYS;
+use super::modal::{ModalSpec, render_modal};
+use super::mouse::{MouseAction, MouseTargets};
+use super::text::{format_bytes, key_hint_paragraph, label_value_line, yes_no};
 use super::worktree_table::{WorktreeTableRender, WorktreeTableState};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,17 +28,30 @@ pub(crate) enum SelectSignal {
 pub(crate) struct SelectStepState {
     table: WorktreeTableState,
     filter_focused: bool,
+    details_open: bool,
+    pending_copy: Option<String>,
+    mouse: MouseTargets,
 }
 
+const SCROLL_ROWS: usize = 3;
+
 impl SelectStepState {
     pub(crate) fn new(rows: Vec<WorktreeRow>) -> Self {
         Self {
             table: WorktreeTableState::new(rows),
             filter_focused: false,
+            details_open: false,
+            pending_copy: None,
+            mouse: MouseTargets::default(),
         }
     }
 
     pub(crate) fn on_key(&mut self, key: KeyEvent) -> SelectSignal {
+        if self.details_open {
+            self.on_details_key(key);
+            return SelectSignal::Continue;
+        }
+
         if keymap::is_back(key) {
             return SelectSignal::Back;
         }
@@ -58,9 +80,148 @@ impl SelectStepState {
             return SelectSignal::Confirm;
         }
 
+        if key.code == KeyCode::Char('e') {
+            self.details_open = self.table.selected_row().is_some();
+            return SelectSignal::Continue;
+        }
+
+        if key.code == KeyCode::Char('y') {
+            self.copy_selected_path();
+            return SelectSignal::Continue;
+        }
+
+        let mut order = self.table.order();
+        match key.code {
+            KeyCode::Char('s') => order.key = order.key.next(),
+            KeyCode::Char('S') => order.reverse = !order.reverse,
+            KeyCode::Char('g') => order.group_running = !order.group_running,
+            _ => return SelectSignal::Continue,
+        }
+        self.table.set_order(order);
+
         SelectSignal::Continue
     }
 
+    pub(crate) fn on_mouse(&mut self, mouse: MouseEvent) -> Option<KeyEvent> {
+        if self.details_open {
+            return None;
+        }
+
+        match self
+            .mouse
+            .action(mouse, self.table.filtered_len(), self.table.selected())?
+        {
+            MouseAction::Row(index) => self.table.select(index),
+            MouseAction::ScrollUp => (0..SCROLL_ROWS).for_each(|_| self.table.move_up()),
+            MouseAction::ScrollDown => (0..SCROLL_ROWS).for_each(|_| self.table.move_down()),
+            MouseAction::Key(key) => return Some(key),
+        }
+        None
+    }
+
+    fn on_details_key(&mut self, key: KeyEvent) {
+        if keymap::is_back(key) || keymap::is_confirm(key) || key.code == KeyCode::Char('e') {
+            self.details_open = false;
+        } else if key.code == KeyCode::Char('y') {
+            self.copy_selected_path();
+        }
+    }
+
+    fn copy_selected_path(&mut self) {
+        self.pending_copy = self
+            .table
+            .selected_row()
+            .map(|row| row.path.display().to_string());
+    }
+
+    pub(crate) fn help_keys(
+        &self,
+        confirm: &'static str,
+        extra: &[(&'static str, &'static str)],
+    ) -> Vec<(&'static str, &'static str)> {
+        if self.details_open {
+            return vec![
+                ("y", "copy the worktree path"),
+                ("e/Enter/Esc", "close the details"),
+            ];
+        }
+        if self.filter_focused {
+            return FILTER_KEYS.to_vec();
+        }
+
+        let mut keys = vec![
+            ("Up/Down or j/k", "move"),
+            ("Enter", confirm),
+            ("/", "type to filter the rows"),
+            ("e", "show the worktree's details"),
+            ("y", "copy the worktree path"),
+            ("s", "sort by the next column"),
+            ("S", "reverse the sort"),
+            ("g", "group running sessions first"),
+        ];
+        keys.extend_from_slice(extra);
+        keys.push(("Esc", "back"));
+        keys
+    }
+
+    pub(crate) fn details_open(&self) -> bool {
+        self.details_open
+    }
+
+    pub(crate) fn take_pending_copy(&mut self) -> Option<String> {
+        self.pending_copy.take()
+    }
+
+    pub(crate) fn render_details(&self, frame: &mut Frame<'_>, theme: &Theme) {
+        if !self.details_open {
+            return;
+        }
+        let Some(row) = self.table.selected_row() else {
+            return;
+        };
+
+        let path = row.path.display().to_string();
+        let size = match &row.disk_usage {
+            Some(usage) => format!(
+                "{} (measured {})",
+                format_bytes(usage.bytes),
+                usage.measured_at
+            ),
+            None => "not measured yet".to_string(),
+        };
+        let mut lines = vec![label_value_line(theme, "Name", &row.name)];
+        if let Some(display_name) = &row.display_name {
+            lines.push(label_value_line(theme, "Display name", display_name));
+        }
+        lines.extend([
+            label_value_line(theme, "Branch", row.head.label()),
+            label_value_line(theme, "Path", &path),
+            label_value_line(theme, "Created", &row.created_at),
+            label_value_line(theme, "Size", &size),
+            label_value_line(theme, "Session", &row.session_name),
+            label_value_line(theme, "Session running", yes_no(row.session_running)),
+        ]);
+        let body = Text::from(lines);
+        render_modal(
+            frame,
+            theme,
+            ModalSpec {
+                title: "Worktree details",
+                title_style: Some(theme.focus_prompt()),
+                body,
+                key_hint: Some("y: copy path    e/Esc: close"),
+                width_pct: 80,
+                height_pct: 50,
+            },
+        );
+    }
+
+    pub(crate) fn on_paste(&mut self, text: &str) {
+        if self.filter_focused {
+            self.table.paste_filter(text);
+        }
+    }
+
     pub(crate) fn render_filter(
         &self,
         frame: &mut Frame<'_>,
@@ -77,30 +238,69 @@ impl SelectStepState {
             .render_filter(frame, area, title, self.filter_focused);
     }
 
-    pub(crate) fn render_table<F>(
+    pub(crate) fn render_table(
         &self,
         frame: &mut Frame<'_>,
         area: Rect,
         render: WorktreeTableRender<'_>,
-        row_builder: F,
-    ) where
-        F: Fn(&WorktreeRow) -> Vec<String>,
-    {
-        self.table.render_table(frame, area, render, row_builder);
+    ) {
+        self.mouse.record_list(area, 1);
+        self.table.render_table(frame, area, render);
+    }
+
+    pub(crate) fn render_keys(&self, frame: &mut Frame<'_>, area: Rect, key_text: &str) {
+        frame.render_widget(key_hint_paragraph(key_text).block(theme::key_block()), area);
+        self.mouse.record_hints(frame, area);
+    }
+
+    pub(crate) fn order(&self) -> WorktreeOrder {
+        self.table.order()
+    }
+
+    pub(crate) fn set_order(&mut self, order: WorktreeOrder) {
+        self.table.set_order(order);
+    }
+
+    pub(crate) fn session_filter(&self) -> SessionFilter {
+        self.table.session_filter()
+    }
+
+    pub(crate) fn set_session_filter(&mut self, filter: SessionFilter) {
+        self.table.set_session_filter(filter);
+    }
+
+    pub(crate) fn set_columns(&mut self, columns: &[WorktreeColumn]) {
+        self.table.set_columns(columns);
     }
 
     pub(crate) fn selected_row(&self) -> Option<&WorktreeRow> {
         self.table.selected_row()
     }
 
+    pub(crate) fn select_name(&mut self, name: &str) -> bool {
+        self.table.select_name(name)
+    }
+
     pub(crate) fn set_rows(&mut self, rows: Vec<WorktreeRow>) {
         self.table.set_rows(rows);
     }
 
+    pub(crate) fn update_session_status(&mut self, running: &BTreeSet<String>) {
+        self.table.update_session_status(running);
+    }
+
     pub(crate) fn remove_by_name(&mut self, name: &str) {
         self.table.remove_by_name(name);
     }
 
+    pub(crate) fn set_disk_usage(&mut self, name: &str, usage: DiskUsage) {
+        self.table.set_disk_usage(name, usage);
+    }
+
+    pub(crate) fn rows(&self) -> &[WorktreeRow] {
+        self.table.rows()
+    }
+
     pub(crate) fn filter_focused(&self) -> bool {
         self.filter_focused
     }
@@ -122,22 +322,20 @@ impl SelectStepState {
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
 
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-    use seshmux_app::WorktreeRow;
+    use seshmux_app::{WorktreeHead, WorktreeRow, WorktreeSortKey};
 
     use super::{SelectSignal, SelectStepState};
 
     fn row(name: &str) -> WorktreeRow {
-        WorktreeRow {
-            name: name.to_string(),
-            path: PathBuf::from(format!("/tmp/repo/worktrees/{name}")),
-            created_at: "2026-02-25T10:00:00Z".to_string(),
-            branch: name.to_string(),
-            session_name: format!("repo/{name}"),
-            session_running: false,
-        }
+        WorktreeRow::new(
+            name,
+            format!("/tmp/repo/worktrees/{name}"),
+            "2026-02-25T10:00:00Z",
+            WorktreeHead::Branch(name.to_string()),
+            format!("repo/{name}"),
+        )
     }
 
     fn key(code: KeyCode) -> KeyEvent {
@@ -159,6 +357,82 @@ mod tests {
         assert_eq!(state.filtered_len(), 1);
     }
 
+    #[test]
+    fn paste_applies_to_focused_filter_only() {
+        let mut state = SelectStepState::new(vec![row("alpha"), row("beta")]);
+
+        state.on_paste("beta");
+        assert_eq!(state.filtered_len(), 2);
+
+        state.on_key(key(KeyCode::Char('/')));
+        state.on_paste("beta\n");
+        assert_eq!(state.filtered_len(), 1);
+    }
+
+    #[test]
+    fn details_popup_captures_keys_and_copies_path() {
+        let mut state = SelectStepState::new(vec![row("alpha"), row("beta")]);
+
+        state.on_key(key(KeyCode::Char('e')));
+        assert!(state.details_open());
+
+        assert_eq!(
+            state.on_key(key(KeyCode::Char('j'))),
+            SelectSignal::Continue
+        );
+        assert_eq!(state.selected(), 0);
+
+        state.on_key(key(KeyCode::Char('y')));
+        assert_eq!(
+            state.take_pending_copy().as_deref(),
+            Some("/tmp/repo/worktrees/alpha")
+        );
+
+        assert_eq!(state.on_key(key(KeyCode::Esc)), SelectSignal::Continue);
+        assert!(!state.details_open());
+        assert_eq!(state.on_key(key(KeyCode::Esc)), SelectSignal::Back);
+    }
+
+    #[test]
+    fn y_copies_the_selected_path_unless_typing_a_filter() {
+        let mut state = SelectStepState::new(vec![row("alpha"), row("beta")]);
+
+        state.on_key(key(KeyCode::Char('j')));
+        state.on_key(key(KeyCode::Char('y')));
+        assert_eq!(
+            state.take_pending_copy().as_deref(),
+            Some("/tmp/repo/worktrees/beta")
+        );
+
+        state.on_key(key(KeyCode::Char('/')));
+        state.on_key(key(KeyCode::Char('y')));
+        assert_eq!(state.take_pending_copy(), None);
+    }
+
+    #[test]
+    fn sort_keys_cycle_reverse_and_group_outside_filter_focus() {
+        let mut alpha = row("alpha");
+        alpha.session_running = true;
+        let mut state = SelectStepState::new(vec![row("beta"), alpha, row("gamma")]);
+
+        state.on_key(key(KeyCode::Char('s')));
+        state.on_key(key(KeyCode::Char('s')));
+        state.on_key(key(KeyCode::Char('s')));
+        state.on_key(key(KeyCode::Char('s')));
+        assert_eq!(state.selected_row().expect("selected").name, "beta");
+        state.on_key(key(KeyCode::Char('k')));
+        assert_eq!(state.selected_row().expect("selected").name, "alpha");
+
+        state.on_key(key(KeyCode::Char('S')));
+        state.on_key(key(KeyCode::Char('g')));
+        state.on_key(key(KeyCode::Char('j')));
+        assert_eq!(state.selected_row().expect("selected").name, "gamma");
+
+        state.on_key(key(KeyCode::Char('/')));
+        state.on_key(key(KeyCode::Char('s')));
+        assert_eq!(state.table.order().key, WorktreeSortKey::Created);
+    }
+
     #[test]
     fn movement_and_confirm_work_in_list_focus() {
         let mut state = SelectStepState::new(vec![row("one"), row("two")]);
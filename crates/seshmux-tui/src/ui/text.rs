@@ -2,8 +2,12 @@ use ratatui::layout::Alignment;
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{Paragraph, Wrap};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
-use crate::theme;
+use crate::theme::Theme;
+
+pub(crate) use seshmux_core::disk_usage::format_bytes;
 
 pub(crate) fn wrapped_paragraph<'a, T>(text: T) -> Paragraph<'a>
 where
@@ -40,30 +44,32 @@ pub(crate) fn compact_hint<'a>(
     }
 }
 
-pub(crate) fn focus_line(message: impl Into<String>) -> Line<'static> {
-    Line::from(Span::styled(message.into(), theme::focus_prompt()))
+pub(crate) fn focus_line(theme: &Theme, message: impl Into<String>) -> Line<'static> {
+    Line::from(Span::styled(message.into(), theme.focus_prompt()))
 }
 
 pub(crate) fn label_value_line(
+    theme: &Theme,
     label: impl Into<String>,
     value: impl Into<String>,
 ) -> Line<'static> {
     let label = label.into();
     let value = value.into();
     Line::from(vec![
-        Span::styled(format!("{label}: "), theme::secondary_text()),
+        Span::styled(format!("{label}: "), theme.secondary_text()),
         Span::raw(value),
     ])
 }
 
 pub(crate) fn highlighted_label_value_line(
+    theme: &Theme,
     label: impl Into<String>,
     value: impl Into<String>,
 ) -> Line<'static> {
     let label = label.into();
     let value = value.into();
     Line::from(vec![
-        Span::styled(format!("{label}: "), theme::focus_prompt()),
+        Span::styled(format!("{label}: "), theme.focus_prompt()),
         Span::styled(value, Style::default().add_modifier(Modifier::UNDERLINED)),
     ])
 }
@@ -81,6 +87,120 @@ pub(crate) fn result_footer(width: u16) -> &'static str {
     )
 }
 
+pub(crate) fn copy_result_footer(width: u16) -> &'static str {
+    compact_hint(
+        width,
+        "y: copy attach command    Enter/Esc: back to home    q: quit seshmux",
+        "y: copy command    Enter/Esc: home    q: quit",
+        "y copy | Enter/Esc home | q quit",
+    )
+}
+
+const ELLIPSIS: &str = "…";
+
+pub(crate) fn display_width(text: &str) -> usize {
+    text.width()
+}
+
+// Cuts on grapheme boundaries so emoji sequences and combining marks are never split.
+pub(crate) fn truncate_to_width(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+
+    let budget = width - ELLIPSIS.width();
+    let mut used = 0;
+    let mut truncated = String::new();
+    for grapheme in text.graphemes(true) {
+        let grapheme_width = grapheme.width();
+        if used + grapheme_width > budget {
+            break;
+        }
+        used += grapheme_width;
+        truncated.push_str(grapheme);
+    }
+    truncated.push_str(ELLIPSIS);
+    truncated
+}
+
+pub(crate) fn truncate_middle(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+    if width <= ELLIPSIS.width() {
+        return truncate_to_width(text, width);
+    }
+
+    let budget = width - ELLIPSIS.width();
+    let graphemes: Vec<&str> = text.graphemes(true).collect();
+    let mut head_end = 0;
+    let mut tail_start = graphemes.len();
+    let (mut head_width, mut tail_width) = (0, 0);
+    while head_end < tail_start {
+        let take_head = head_width <= tail_width;
+        let next = if take_head {
+            graphemes[head_end]
+        } else {
+            graphemes[tail_start - 1]
+        };
+        if head_width + tail_width + next.width() > budget {
+            break;
+        }
+        if take_head {
+            head_width += next.width();
+            head_end += 1;
+        } else {
+            tail_width += next.width();
+            tail_start -= 1;
+        }
+    }
+
+    let mut truncated = graphemes[..head_end].concat();
+    truncated.push_str(ELLIPSIS);
+    truncated.push_str(&graphemes[tail_start..].concat());
+    truncated
+}
+
+// The visible slice of an input scrolled by `scroll` columns. A wide grapheme cut by the left
+// edge is replaced with spaces so the remaining text keeps its columns.
+pub(crate) fn input_viewport(value: &str, scroll: usize, width: usize) -> String {
+    let mut column = 0;
+    let mut visible = String::new();
+    for grapheme in value.graphemes(true) {
+        let grapheme_width = grapheme.width();
+        let start = column;
+        column += grapheme_width;
+        if column <= scroll {
+            continue;
+        }
+        if start >= scroll + width {
+            break;
+        }
+
+        if start < scroll {
+            visible.push_str(&" ".repeat(column - scroll));
+        } else if column > scroll + width {
+            visible.push_str(&" ".repeat(scroll + width - start));
+        } else {
+            visible.push_str(grapheme);
+        }
+    }
+    visible
+}
+
+pub(crate) fn format_age(seconds: u64) -> String {
+    match seconds {
+        0..60 => "just now".to_string(),
+        60..3_600 => format!("{}m", seconds / 60),
+        3_600..86_400 => format!("{}h", seconds / 3_600),
+        86_400..604_800 => format!("{}d", seconds / 86_400),
+        _ => format!("{}w", seconds / 604_800),
+    }
+}
+
 fn wrapped_line_count(text: &str, width: usize) -> u16 {
     if text.is_empty() {
         return 1;
@@ -101,8 +221,12 @@ fn wrapped_line_count_single(line: &str, width: usize) -> u16 {
 
     let mut lines = 1u16;
     let mut used = 0usize;
-    for ch in line.chars() {
-        let mut remaining = if ch == '\t' { 4 } else { 1 };
+    for grapheme in line.graphemes(true) {
+        let mut remaining = if grapheme == "\t" {
+            4
+        } else {
+            grapheme.width()
+        };
         while remaining > 0 {
             let space_left = width.saturating_sub(used);
             if space_left == 0 {
@@ -129,9 +253,20 @@ mod tests {
     use ratatui::style::{Color, Modifier};
 
     use super::{
-        compact_hint, focus_line, highlighted_label_value_line, key_hint_height, label_value_line,
-        result_footer, wrapped_line_count_single, yes_no,
+        compact_hint, display_width, focus_line, format_age, highlighted_label_value_line,
+        input_viewport, key_hint_height, label_value_line, result_footer, truncate_middle,
+        truncate_to_width, wrapped_line_count_single, yes_no,
     };
+    use crate::theme::Theme;
+
+    #[test]
+    fn format_age_picks_the_largest_whole_unit() {
+        assert_eq!(format_age(5), "just now");
+        assert_eq!(format_age(125), "2m");
+        assert_eq!(format_age(5 * 3_600), "5h");
+        assert_eq!(format_age(3 * 86_400 + 7), "3d");
+        assert_eq!(format_age(15 * 86_400), "2w");
+    }
 
     #[test]
     fn compact_hint_selects_variant_by_width() {
@@ -154,7 +289,7 @@ mod tests {
 
     #[test]
     fn focus_line_uses_blue_bold_style() {
-        let line = focus_line("choose an option");
+        let line = focus_line(&Theme::default(), "choose an option");
         assert_eq!(line.spans.len(), 1);
         assert_eq!(line.spans[0].content.as_ref(), "choose an option");
         assert_eq!(line.spans[0].style.fg, Some(Color::Blue));
@@ -163,7 +298,7 @@ mod tests {
 
     #[test]
     fn label_value_line_formats_with_colon() {
-        let line = label_value_line("tmux session name", "repo/w1");
+        let line = label_value_line(&Theme::default(), "tmux session name", "repo/w1");
         assert_eq!(line.spans.len(), 2);
         assert_eq!(line.spans[0].content.as_ref(), "tmux session name: ");
         assert_eq!(line.spans[1].content.as_ref(), "repo/w1");
@@ -177,7 +312,7 @@ mod tests {
 
     #[test]
     fn highlighted_label_value_line_formats_single_focus_span() {
-        let line = highlighted_label_value_line("Current selection", "Yes");
+        let line = highlighted_label_value_line(&Theme::default(), "Current selection", "Yes");
         assert_eq!(line.spans.len(), 2);
         assert_eq!(line.spans[0].content.as_ref(), "Current selection: ");
         assert_eq!(line.spans[1].content.as_ref(), "Yes");
@@ -190,6 +325,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn wrapped_line_count_uses_display_width() {
+        assert_eq!(wrapped_line_count_single("日本語", 4), 2);
+        assert_eq!(wrapped_line_count_single("日本", 4), 1);
+    }
+
+    #[test]
+    fn truncate_to_width_respects_wide_characters_and_graphemes() {
+        assert_eq!(truncate_to_width("feature-a", 20), "feature-a");
+        assert_eq!(truncate_to_width("feature-login", 8), "feature…");
+        assert_eq!(truncate_to_width("機能ブランチ", 7), "機能ブ…");
+        assert_eq!(display_width(&truncate_to_width("機能ブランチ", 6)), 5);
+        assert_eq!(truncate_to_width("a👩‍💻bc", 4), "a👩‍💻…");
+        assert_eq!(truncate_to_width("a👩‍💻bc", 3), "a…");
+        assert_eq!(truncate_to_width("e\u{301}cole", 3), "e\u{301}c…");
+        assert_eq!(truncate_to_width("abc", 0), "");
+    }
+
+    #[test]
+    fn truncate_middle_keeps_both_ends() {
+        assert_eq!(truncate_middle("feature/login", 20), "feature/login");
+        assert_eq!(
+            truncate_middle("/home/dev/code/shop/worktrees/feature-a", 21),
+            "/home/dev/…/feature-a"
+        );
+        assert_eq!(truncate_middle("機能ブランチ", 7), "機能…チ");
+        assert_eq!(truncate_middle("abcdef", 1), "…");
+    }
+
+    #[test]
+    fn input_viewport_slices_by_column() {
+        assert_eq!(input_viewport("abcdef", 2, 3), "cde");
+        assert_eq!(input_viewport("日本語", 1, 4), " 本 ");
+        assert_eq!(input_viewport("日本語", 0, 3), "日 ");
+        assert_eq!(input_viewport("ab", 0, 10), "ab");
+    }
+
     #[test]
     fn yes_no_maps_boolean_values() {
         assert_eq!(yes_no(true), "Yes");
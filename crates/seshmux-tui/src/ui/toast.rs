@@ -0,0 +1,69 @@
+use std::time::{Duration, Instant};
+
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::widgets::{Clear, Paragraph};
+
+use crate::theme::{self, Theme};
+use crate::ui::text::display_width;
+
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+#[derive(Debug, Clone)]
+pub(crate) struct Toast {
+    message: String,
+    shown_at: Instant,
+}
+
+impl Toast {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            shown_at: Instant::now(),
+        }
+    }
+
+    pub(crate) fn expired(&self) -> bool {
+        self.shown_at.elapsed() >= TOAST_DURATION
+    }
+}
+
+pub(crate) fn render_toast(frame: &mut Frame<'_>, theme: &Theme, toast: &Toast) {
+    let area = toast_area(frame.area(), &toast.message);
+    if area.width < 3 || area.height < 3 {
+        return;
+    }
+
+    let block = theme::chrome("Done").border_style(theme.success_prompt());
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(toast.message.as_str()).block(block), area);
+}
+
+fn toast_area(area: Rect, message: &str) -> Rect {
+    let desired = u16::try_from(display_width(message).saturating_add(4)).unwrap_or(u16::MAX);
+    let width = desired.min(area.width);
+    let height = 3.min(area.height);
+
+    Rect {
+        x: area.x + area.width - width,
+        y: area.y,
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+
+    use super::toast_area;
+
+    #[test]
+    fn toast_sits_in_top_right_corner_and_fits_small_terminals() {
+        let area = Rect::new(0, 0, 80, 24);
+        assert_eq!(toast_area(area, "indexing done"), Rect::new(63, 0, 17, 3));
+
+        let narrow = Rect::new(0, 0, 10, 2);
+        assert_eq!(toast_area(narrow, "indexing done"), Rect::new(0, 0, 10, 2));
+    }
+}
@@ -1,26 +1,155 @@
+use std::collections::BTreeSet;
+use std::time::SystemTime;
+
 use crossterm::event::{Event, KeyEvent};
 use ratatui::Frame;
-use ratatui::layout::{Constraint, Margin, Rect};
+use ratatui::layout::{Constraint, Flex, Layout, Margin, Rect};
 use ratatui::style::Style;
 use ratatui::text::Line;
 use ratatui::widgets::{
     Paragraph, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table, TableState,
 };
-use seshmux_app::WorktreeRow;
+use seshmux_app::{App, SessionFilter, WorktreeOrder, WorktreeRow, WorktreeSortKey};
+use seshmux_core::config::WorktreeColumn;
+use seshmux_core::registry::DiskUsage;
+use seshmux_core::time::seconds_since_rfc3339;
 use tui_input::Input;
 use tui_input::backend::crossterm::EventHandler;
 
+use super::text::{
+    display_width, format_age, format_bytes, input_viewport, truncate_middle, truncate_to_width,
+};
+
 #[derive(Debug, Clone, Copy)]
-pub(crate) struct TableColumn {
+pub(crate) struct ColumnSpec {
+    pub(crate) id: WorktreeColumn,
     pub(crate) title: &'static str,
     pub(crate) width: Constraint,
+    pub(crate) sort_key: Option<WorktreeSortKey>,
+    pub(crate) cell: fn(&WorktreeRow) -> String,
+    pub(crate) truncate: fn(&str, usize) -> String,
+}
+
+const COLUMNS: [ColumnSpec; 7] = [
+    ColumnSpec {
+        id: WorktreeColumn::Name,
+        title: "Name",
+        width: Constraint::Length(24),
+        sort_key: Some(WorktreeSortKey::Name),
+        cell: |row| row.label().to_string(),
+        truncate: truncate_to_width,
+    },
+    ColumnSpec {
+        id: WorktreeColumn::Created,
+        title: "Created",
+        width: Constraint::Length(28),
+        sort_key: Some(WorktreeSortKey::Created),
+        cell: |row| row.created_at.clone(),
+        truncate: truncate_to_width,
+    },
+    ColumnSpec {
+        id: WorktreeColumn::Age,
+        title: "Age",
+        width: Constraint::Length(9),
+        sort_key: Some(WorktreeSortKey::Created),
+        cell: |row| {
+            seconds_since_rfc3339(&row.created_at, SystemTime::now())
+                .map(format_age)
+                .unwrap_or_else(|| "?".to_string())
+        },
+        truncate: truncate_to_width,
+    },
+    ColumnSpec {
+        id: WorktreeColumn::Size,
+        title: "Size",
+        width: Constraint::Length(10),
+        sort_key: None,
+        cell: |row| match &row.disk_usage {
+            Some(usage) => format_bytes(usage.bytes),
+            None => "-".to_string(),
+        },
+        truncate: truncate_to_width,
+    },
+    ColumnSpec {
+        id: WorktreeColumn::Branch,
+        title: "Branch",
+        width: Constraint::Length(20),
+        sort_key: Some(WorktreeSortKey::Branch),
+        cell: |row| row.head.label().to_string(),
+        truncate: truncate_middle,
+    },
+    ColumnSpec {
+        id: WorktreeColumn::Session,
+        title: "Session",
+        width: Constraint::Length(14),
+        sort_key: Some(WorktreeSortKey::Session),
+        cell: |row| {
+            if row.session_running {
+                "running".to_string()
+            } else {
+                "not running".to_string()
+            }
+        },
+        truncate: truncate_to_width,
+    },
+    ColumnSpec {
+        id: WorktreeColumn::Path,
+        title: "Path",
+        width: Constraint::Min(24),
+        sort_key: None,
+        cell: |row| row.path.display().to_string(),
+        truncate: truncate_middle,
+    },
+];
+
+const HIGHLIGHT_SYMBOL: &str = ">> ";
+
+pub(crate) const DEFAULT_COLUMNS: &[WorktreeColumn] = &[
+    WorktreeColumn::Name,
+    WorktreeColumn::Created,
+    WorktreeColumn::Branch,
+    WorktreeColumn::Session,
+];
+
+pub(crate) fn column_spec(id: WorktreeColumn) -> &'static ColumnSpec {
+    COLUMNS
+        .iter()
+        .find(|spec| spec.id == id)
+        .expect("every worktree column is registered")
+}
+
+pub(crate) fn configured_columns(app: &App<'_>) -> Option<Vec<WorktreeColumn>> {
+    app.ensure_config_ready()
+        .ok()
+        .and_then(|config| config.ui.columns)
+}
+
+// Mirrors Table's own layout (borders, highlight column, one space between columns) so cells can
+// be ellipsized to exactly the width they will get.
+fn column_widths(area: Rect, widths: &[Constraint]) -> Vec<usize> {
+    let content = area.inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    let [_, columns_area] = Layout::horizontal([
+        Constraint::Length(display_width(HIGHLIGHT_SYMBOL) as u16),
+        Constraint::Fill(0),
+    ])
+    .areas(content);
+
+    Layout::horizontal(widths.iter().copied())
+        .flex(Flex::Start)
+        .spacing(1)
+        .split(columns_area)
+        .iter()
+        .map(|rect| usize::from(rect.width))
+        .collect()
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct WorktreeTableRender<'a> {
     pub(crate) title: Line<'a>,
     pub(crate) empty_message: &'a str,
-    pub(crate) columns: &'a [TableColumn],
     pub(crate) header_style: Style,
     pub(crate) highlight_style: Style,
 }
@@ -31,6 +160,9 @@ pub(crate) struct WorktreeTableState {
     filtered: Vec<usize>,
     selected: usize,
     query: Input,
+    order: Option<WorktreeOrder>,
+    session_filter: SessionFilter,
+    columns: Vec<WorktreeColumn>,
 }
 
 impl WorktreeTableState {
@@ -40,6 +172,9 @@ impl WorktreeTableState {
             filtered: Vec::new(),
             selected: 0,
             query: Input::default(),
+            order: None,
+            session_filter: SessionFilter::All,
+            columns: DEFAULT_COLUMNS.to_vec(),
         };
         state.refresh_filtered();
         state
@@ -50,6 +185,50 @@ impl WorktreeTableState {
         self.refresh_filtered();
     }
 
+    pub(crate) fn update_session_status(&mut self, running: &BTreeSet<String>) {
+        for row in &mut self.rows {
+            row.session_running = running.contains(&row.session_name);
+        }
+        let grouped = self
+            .order
+            .is_some_and(|order| order.group_running || order.key == WorktreeSortKey::Session);
+        if grouped || self.session_filter != SessionFilter::All {
+            self.reorder_keeping_selection();
+        }
+    }
+
+    pub(crate) fn set_disk_usage(&mut self, name: &str, usage: DiskUsage) {
+        if let Some(row) = self.rows.iter_mut().find(|row| row.name == name) {
+            row.disk_usage = Some(usage);
+        }
+    }
+
+    pub(crate) fn rows(&self) -> &[WorktreeRow] {
+        &self.rows
+    }
+
+    pub(crate) fn order(&self) -> WorktreeOrder {
+        self.order.unwrap_or_default()
+    }
+
+    pub(crate) fn session_filter(&self) -> SessionFilter {
+        self.session_filter
+    }
+
+    pub(crate) fn set_session_filter(&mut self, filter: SessionFilter) {
+        self.session_filter = filter;
+        self.reorder_keeping_selection();
+    }
+
+    pub(crate) fn set_columns(&mut self, columns: &[WorktreeColumn]) {
+        self.columns = columns.to_vec();
+    }
+
+    pub(crate) fn set_order(&mut self, order: WorktreeOrder) {
+        self.order = Some(order);
+        self.reorder_keeping_selection();
+    }
+
     pub(crate) fn remove_by_name(&mut self, name: &str) {
         self.rows.retain(|row| row.name != name);
         self.refresh_filtered();
@@ -61,6 +240,12 @@ impl WorktreeTableState {
         }
     }
 
+    pub(crate) fn paste_filter(&mut self, text: &str) {
+        if super::paste::paste_into(&mut self.query, text) {
+            self.refresh_filtered();
+        }
+    }
+
     pub(crate) fn move_up(&mut self) {
         self.selected = self.selected.saturating_sub(1);
     }
@@ -71,17 +256,33 @@ impl WorktreeTableState {
         }
     }
 
+    pub(crate) fn select(&mut self, index: usize) {
+        if index < self.filtered.len() {
+            self.selected = index;
+        }
+    }
+
+    pub(crate) fn select_name(&mut self, name: &str) -> bool {
+        let Some(position) = self
+            .filtered
+            .iter()
+            .position(|index| self.rows[*index].name == name)
+        else {
+            return false;
+        };
+        self.selected = position;
+        true
+    }
+
     pub(crate) fn selected_row(&self) -> Option<&WorktreeRow> {
         let index = *self.filtered.get(self.selected)?;
         self.rows.get(index)
     }
 
-    #[cfg(test)]
     pub(crate) fn selected(&self) -> usize {
         self.selected
     }
 
-    #[cfg(test)]
     pub(crate) fn filtered_len(&self) -> usize {
         self.filtered.len()
     }
@@ -95,8 +296,7 @@ impl WorktreeTableState {
     ) {
         let width = area.width.saturating_sub(2) as usize;
         let scroll = self.query.visual_scroll(width);
-        let paragraph = Paragraph::new(self.query.value())
-            .scroll((0, scroll as u16))
+        let paragraph = Paragraph::new(input_viewport(self.query.value(), scroll, width))
             .block(crate::theme::chrome(title));
         frame.render_widget(paragraph, area);
 
@@ -109,15 +309,12 @@ impl WorktreeTableState {
         frame.set_cursor_position((area.x + 1 + relative as u16, area.y + 1));
     }
 
-    pub(crate) fn render_table<F>(
+    pub(crate) fn render_table(
         &self,
         frame: &mut Frame<'_>,
         area: Rect,
         render: WorktreeTableRender<'_>,
-        row_builder: F,
-    ) where
-        F: Fn(&WorktreeRow) -> Vec<String>,
-    {
+    ) {
         if self.filtered.is_empty() {
             let empty = Paragraph::new(render.empty_message)
                 .block(crate::theme::chrome(render.title.clone()));
@@ -125,20 +322,29 @@ impl WorktreeTableState {
             return;
         }
 
-        let header =
-            Row::new(render.columns.iter().map(|column| column.title)).style(render.header_style);
+        let columns: Vec<&ColumnSpec> = self.columns.iter().copied().map(column_spec).collect();
+        let header = Row::new(columns.iter().map(|column| self.column_header(column)))
+            .style(render.header_style);
+        let widths: Vec<Constraint> = columns.iter().map(|column| column.width).collect();
+        let cell_widths = column_widths(area, &widths);
         let rows = self
             .filtered
             .iter()
             .filter_map(|index| self.rows.get(*index))
-            .map(|row| Row::new(row_builder(row)));
-        let widths: Vec<Constraint> = render.columns.iter().map(|column| column.width).collect();
+            .map(|row| {
+                Row::new(
+                    columns
+                        .iter()
+                        .zip(&cell_widths)
+                        .map(|(column, width)| (column.truncate)(&(column.cell)(row), *width)),
+                )
+            });
 
         let table = Table::new(rows, widths)
             .header(header)
             .block(crate::theme::chrome(render.title))
             .row_highlight_style(render.highlight_style)
-            .highlight_symbol(">> ");
+            .highlight_symbol(HIGHLIGHT_SYMBOL);
 
         let mut state = TableState::new();
         state.select(Some(self.selected));
@@ -160,24 +366,57 @@ impl WorktreeTableState {
         );
     }
 
+    fn column_header(&self, column: &ColumnSpec) -> String {
+        let order = self.order();
+        if column.sort_key != Some(order.key) {
+            return column.title.to_string();
+        }
+
+        let arrow = if order.reverse { "^" } else { "v" };
+        format!("{} {arrow}", column.title)
+    }
+
+    fn reorder_keeping_selection(&mut self) {
+        let selected = self.selected_row().map(|row| row.name.clone());
+        self.refresh_filtered();
+        if let Some(name) = selected
+            && let Some(position) = self
+                .filtered
+                .iter()
+                .position(|index| self.rows[*index].name == name)
+        {
+            self.selected = position;
+        }
+    }
+
     fn refresh_filtered(&mut self) {
         let query = self.query.value().trim().to_lowercase();
         self.filtered = self
             .rows
             .iter()
             .enumerate()
+            .filter(|(_, row)| self.session_filter.matches(row))
             .filter(|(_, row)| {
                 if query.is_empty() {
                     return true;
                 }
 
                 row.name.to_lowercase().contains(&query)
+                    || row
+                        .display_name
+                        .as_ref()
+                        .is_some_and(|name| name.to_lowercase().contains(&query))
                     || row.path.to_string_lossy().to_lowercase().contains(&query)
-                    || row.branch.to_lowercase().contains(&query)
+                    || row.head.label().to_lowercase().contains(&query)
                     || row.created_at.to_lowercase().contains(&query)
             })
             .map(|(index, _)| index)
             .collect();
+        if let Some(order) = self.order {
+            let rows = &self.rows;
+            self.filtered
+                .sort_by(|left, right| order.compare(&rows[*left], &rows[*right]));
+        }
 
         if self.filtered.is_empty() {
             self.selected = 0;
@@ -189,22 +428,23 @@ impl WorktreeTableState {
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
 
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-    use seshmux_app::WorktreeRow;
+    use seshmux_app::{WorktreeHead, WorktreeOrder, WorktreeRow, WorktreeSortKey};
+    use seshmux_core::config::WorktreeColumn;
+
+    use ratatui::layout::{Constraint, Rect};
 
-    use super::WorktreeTableState;
+    use super::{WorktreeTableState, column_spec, column_widths};
 
     fn row(name: &str) -> WorktreeRow {
-        WorktreeRow {
-            name: name.to_string(),
-            path: PathBuf::from(format!("/tmp/repo/worktrees/{name}")),
-            created_at: "2026-02-25T10:00:00Z".to_string(),
-            branch: name.to_string(),
-            session_name: format!("repo/{name}"),
-            session_running: false,
-        }
+        WorktreeRow::new(
+            name,
+            format!("/tmp/repo/worktrees/{name}"),
+            "2026-02-25T10:00:00Z",
+            WorktreeHead::Branch(name.to_string()),
+            format!("repo/{name}"),
+        )
     }
 
     fn key(code: KeyCode) -> KeyEvent {
@@ -221,6 +461,24 @@ mod tests {
         assert_eq!(state.selected_row().expect("selected row").name, "beta");
     }
 
+    #[test]
+    fn display_names_are_shown_and_filterable() {
+        let mut titled = row("fix-login-sso");
+        titled.display_name = Some("Fix login & SSO".to_string());
+        let mut state = WorktreeTableState::new(vec![titled, row("other")]);
+
+        for character in "& sso".chars() {
+            state.on_filter_key(key(KeyCode::Char(character)));
+        }
+
+        assert_eq!(state.filtered_len(), 1);
+        let selected = state.selected_row().expect("selected row");
+        assert_eq!(
+            (column_spec(WorktreeColumn::Name).cell)(selected),
+            "Fix login & SSO"
+        );
+    }
+
     #[test]
     fn selection_movement_stays_in_bounds() {
         let mut state = WorktreeTableState::new(vec![row("one"), row("two")]);
@@ -244,4 +502,67 @@ mod tests {
         assert_eq!(state.filtered_len(), 0);
         assert!(state.selected_row().is_none());
     }
+
+    #[test]
+    fn set_order_resorts_rows_and_keeps_selected_worktree() {
+        let mut state = WorktreeTableState::new(vec![row("one"), row("two"), row("three")]);
+        state.move_down();
+        state.move_down();
+        assert_eq!(state.selected_row().expect("selected row").name, "three");
+
+        state.set_order(WorktreeOrder {
+            key: WorktreeSortKey::Name,
+            reverse: false,
+            group_running: false,
+        });
+
+        assert_eq!(state.selected(), 1);
+        assert_eq!(state.selected_row().expect("selected row").name, "three");
+        assert_eq!(
+            state.column_header(column_spec(WorktreeColumn::Name)),
+            "Name v"
+        );
+        assert_eq!(
+            state.column_header(column_spec(WorktreeColumn::Created)),
+            "Created"
+        );
+    }
+
+    #[test]
+    fn column_registry_renders_cells_for_each_column() {
+        let mut running = row("one");
+        running.session_running = true;
+
+        let cells: Vec<String> = [
+            WorktreeColumn::Name,
+            WorktreeColumn::Session,
+            WorktreeColumn::Path,
+        ]
+        .into_iter()
+        .map(|id| (column_spec(id).cell)(&running))
+        .collect();
+
+        assert_eq!(cells, vec!["one", "running", "/tmp/repo/worktrees/one"]);
+        assert_eq!(column_spec(WorktreeColumn::Path).sort_key, None);
+    }
+
+    #[test]
+    fn column_widths_match_table_layout() {
+        let area = Rect::new(0, 0, 60, 10);
+        let widths = column_widths(area, &[Constraint::Length(24), Constraint::Min(10)]);
+
+        assert_eq!(widths, vec![24, 30]);
+    }
+
+    #[test]
+    fn update_session_status_marks_rows_in_place() {
+        let mut state = WorktreeTableState::new(vec![row("one"), row("two")]);
+        state.move_down();
+
+        state.update_session_status(&std::collections::BTreeSet::from(["repo/two".to_string()]));
+
+        let selected = state.selected_row().expect("selected row");
+        assert_eq!(selected.name, "two");
+        assert!(selected.session_running);
+    }
 }